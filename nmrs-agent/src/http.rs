@@ -0,0 +1,459 @@
+//! Optional localhost HTTP bridge (`http` feature).
+//!
+//! Exposes the same commands as the control socket as a tiny REST API for
+//! kiosk and appliance web UIs. The server only binds loopback addresses and
+//! every request must carry `Authorization: Bearer <token>`.
+//!
+//! | Method | Path | Request |
+//! |--------|------|---------|
+//! | `GET`  | `/status` | `status` |
+//! | `GET`  | `/devices` | `list_devices` |
+//! | `GET`  | `/networks[?interface=wlan0]` | `list_networks` |
+//! | `POST` | `/scan[?interface=wlan0]` | `scan` |
+//! | `POST` | `/connect` | `connect`, JSON body `{"ssid":..,"password":..,"interface":..}` |
+//! | `POST` | `/disconnect[?interface=wlan0]` | `disconnect` |
+
+use std::io;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use log::{debug, info};
+use nmrs::{ConnectionError, NetworkManager};
+use serde::Deserialize;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::protocol::{Request, Response};
+use crate::server::execute;
+
+/// Upper bound on the request line plus headers.
+const MAX_HEAD: usize = 16 * 1024;
+/// Upper bound on a request body.
+const MAX_BODY: usize = 64 * 1024;
+/// How long a client gets to send its whole request.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Serves the HTTP bridge on `addr`, which must be a loopback address.
+pub async fn serve(nm: NetworkManager, addr: SocketAddr, token: String) -> io::Result<()> {
+    if !addr.ip().is_loopback() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("HTTP bridge only binds loopback addresses, got {addr}"),
+        ));
+    }
+    if token.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "HTTP bridge requires a non-empty token",
+        ));
+    }
+
+    let listener = TcpListener::bind(addr).await?;
+    info!("HTTP bridge listening on http://{addr}");
+
+    loop {
+        let (stream, peer) = listener.accept().await?;
+        let nm = nm.clone();
+        let token = token.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle(nm, stream, &token).await {
+                debug!("HTTP client {peer}: {e}");
+            }
+        });
+    }
+}
+
+async fn handle(nm: NetworkManager, stream: TcpStream, token: &str) -> io::Result<()> {
+    let (read, mut write) = stream.into_split();
+    let mut reader = BufReader::new(read);
+
+    let request = tokio::time::timeout(READ_TIMEOUT, read_request(&mut reader))
+        .await
+        .unwrap_or(Ok(Err(Status::RequestTimeout)))?;
+    let (status, response) = match request {
+        Err(status) => (status, Response::err(status.reason())),
+        Ok(req) => {
+            if !authorized(req.authorization.as_deref(), token) {
+                (
+                    Status::Unauthorized,
+                    Response::err("missing or invalid token"),
+                )
+            } else {
+                match route(&req.method, &req.target, &req.body) {
+                    Ok(request) => match execute(&nm, request).await {
+                        Ok(response) => (Status::Ok, response),
+                        Err(e) => (error_status(&e), Response::err(e)),
+                    },
+                    Err(status) => (status, Response::err(status.reason())),
+                }
+            }
+        }
+    };
+
+    let body = serde_json::to_vec(&response)?;
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status.code(),
+        status.reason(),
+        body.len()
+    );
+    write.write_all(head.as_bytes()).await?;
+    write.write_all(&body).await?;
+    write.shutdown().await
+}
+
+/// HTTP status codes the bridge replies with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Status {
+    Ok,
+    BadRequest,
+    Unauthorized,
+    NotFound,
+    MethodNotAllowed,
+    RequestTimeout,
+    PayloadTooLarge,
+    BadGateway,
+}
+
+impl Status {
+    fn code(self) -> u16 {
+        match self {
+            Self::Ok => 200,
+            Self::BadRequest => 400,
+            Self::Unauthorized => 401,
+            Self::NotFound => 404,
+            Self::MethodNotAllowed => 405,
+            Self::RequestTimeout => 408,
+            Self::PayloadTooLarge => 413,
+            Self::BadGateway => 502,
+        }
+    }
+
+    fn reason(self) -> &'static str {
+        match self {
+            Self::Ok => "OK",
+            Self::BadRequest => "Bad Request",
+            Self::Unauthorized => "Unauthorized",
+            Self::NotFound => "Not Found",
+            Self::MethodNotAllowed => "Method Not Allowed",
+            Self::RequestTimeout => "Request Timeout",
+            Self::PayloadTooLarge => "Payload Too Large",
+            Self::BadGateway => "Bad Gateway",
+        }
+    }
+}
+
+struct HttpRequest {
+    method: String,
+    target: String,
+    authorization: Option<String>,
+    body: Vec<u8>,
+}
+
+async fn read_request<R>(reader: &mut R) -> io::Result<Result<HttpRequest, Status>>
+where
+    R: AsyncBufReadExt + Unpin,
+{
+    // Every head line is read through this limit, so a client cannot make
+    // us buffer more than `MAX_HEAD` bytes before the first newline.
+    let mut head = (&mut *reader).take(MAX_HEAD as u64);
+    let mut line = String::new();
+
+    if !read_head_line(&mut head, &mut line).await? {
+        return Ok(Err(Status::PayloadTooLarge));
+    }
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Ok(Err(Status::BadRequest));
+    };
+    let (method, target) = (method.to_string(), target.to_string());
+
+    let mut authorization = None;
+    let mut content_length = 0usize;
+    loop {
+        line.clear();
+        if !read_head_line(&mut head, &mut line).await? {
+            return Ok(Err(Status::PayloadTooLarge));
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        let Some((name, value)) = header.split_once(':') else {
+            return Ok(Err(Status::BadRequest));
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("authorization") {
+            authorization = Some(value.to_string());
+        } else if name.eq_ignore_ascii_case("content-length") {
+            match value.parse() {
+                Ok(len) => content_length = len,
+                Err(_) => return Ok(Err(Status::BadRequest)),
+            }
+        }
+    }
+
+    if content_length > MAX_BODY {
+        return Ok(Err(Status::PayloadTooLarge));
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).await?;
+
+    Ok(Ok(HttpRequest {
+        method,
+        target,
+        authorization,
+        body,
+    }))
+}
+
+/// Reads one head line into `line`.
+///
+/// Returns `false` if the head limit ran out before the line ended; a
+/// client closing the connection early reads as an empty line.
+async fn read_head_line<R>(head: &mut tokio::io::Take<R>, line: &mut String) -> io::Result<bool>
+where
+    R: AsyncBufReadExt + Unpin,
+{
+    head.read_line(line).await?;
+    Ok(line.ends_with('\n') || head.limit() > 0)
+}
+
+/// The status for a failed library call: bad input is the client's fault,
+/// a missing network or device is `404`, anything else is NetworkManager's.
+pub(crate) fn error_status(e: &ConnectionError) -> Status {
+    match e {
+        ConnectionError::InvalidInput { .. }
+        | ConnectionError::InvalidBssid(_)
+        | ConnectionError::InvalidAddress(_)
+        | ConnectionError::InvalidPeers(_)
+        | ConnectionError::InvalidPrivateKey(_)
+        | ConnectionError::InvalidPublicKey(_)
+        | ConnectionError::InvalidGateway(_)
+        | ConnectionError::InvalidVlanId { .. }
+        | ConnectionError::IncompleteBuilder(_)
+        | ConnectionError::ConnectionInvalid(_)
+        | ConnectionError::MissingPassword => Status::BadRequest,
+        ConnectionError::NotFound
+        | ConnectionError::ApBssidNotFound { .. }
+        | ConnectionError::NoWifiDevice
+        | ConnectionError::NotAWifiDevice { .. }
+        | ConnectionError::WifiInterfaceNotFound { .. }
+        | ConnectionError::UnknownDevice(_)
+        | ConnectionError::SavedConnectionNotFound(_) => Status::NotFound,
+        _ => Status::BadGateway,
+    }
+}
+
+/// Checks a `Bearer` authorization header against `token` in constant time.
+pub(crate) fn authorized(header: Option<&str>, token: &str) -> bool {
+    let Some(presented) = header.and_then(|h| h.strip_prefix("Bearer ")) else {
+        return false;
+    };
+    let (a, b) = (presented.trim().as_bytes(), token.as_bytes());
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[derive(Deserialize)]
+struct ConnectBody {
+    ssid: String,
+    #[serde(default)]
+    password: Option<String>,
+    #[serde(default)]
+    interface: Option<String>,
+}
+
+/// Maps a method and request target onto a control [`Request`].
+pub(crate) fn route(method: &str, target: &str, body: &[u8]) -> Result<Request, Status> {
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let interface = query_param(query, "interface")?;
+
+    let expect = |wanted: &str| {
+        if method == wanted {
+            Ok(())
+        } else {
+            Err(Status::MethodNotAllowed)
+        }
+    };
+
+    match path {
+        "/status" => expect("GET").map(|()| Request::Status),
+        "/devices" => expect("GET").map(|()| Request::ListDevices),
+        "/networks" => expect("GET").map(|()| Request::ListNetworks { interface }),
+        "/scan" => expect("POST").map(|()| Request::Scan { interface }),
+        "/disconnect" => expect("POST").map(|()| Request::Disconnect { interface }),
+        "/connect" => {
+            expect("POST")?;
+            let body: ConnectBody = serde_json::from_slice(body).map_err(|_| Status::BadRequest)?;
+            Ok(Request::Connect {
+                ssid: body.ssid,
+                password: body.password,
+                interface: body.interface,
+            })
+        }
+        _ => Err(Status::NotFound),
+    }
+}
+
+/// The percent-decoded value of `key` in a query string; empty counts as
+/// absent.
+fn query_param(query: &str, key: &str) -> Result<Option<String>, Status> {
+    for (k, v) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+        if percent_decode(k)? == key {
+            let value = percent_decode(v)?;
+            return Ok(Some(value).filter(|v| !v.is_empty()));
+        }
+    }
+    Ok(None)
+}
+
+/// Decodes `%XX` escapes and `+` as in `application/x-www-form-urlencoded`.
+pub(crate) fn percent_decode(s: &str) -> Result<String, Status> {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut input = s.bytes();
+    while let Some(b) = input.next() {
+        bytes.push(match b {
+            b'+' => b' ',
+            b'%' => {
+                let hex = [input.next(), input.next()];
+                let [Some(hi), Some(lo)] = hex else {
+                    return Err(Status::BadRequest);
+                };
+                std::str::from_utf8(&[hi, lo])
+                    .ok()
+                    .and_then(|h| u8::from_str_radix(h, 16).ok())
+                    .ok_or(Status::BadRequest)?
+            }
+            b => b,
+        });
+    }
+    String::from_utf8(bytes).map_err(|_| Status::BadRequest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bearer_token_checked() {
+        assert!(authorized(Some("Bearer s3cret"), "s3cret"));
+        assert!(!authorized(Some("Bearer s3cre"), "s3cret"));
+        assert!(!authorized(Some("Bearer other!"), "s3cret"));
+        assert!(!authorized(Some("s3cret"), "s3cret"));
+        assert!(!authorized(None, "s3cret"));
+    }
+
+    #[test]
+    fn routes_get_endpoints() {
+        assert_eq!(route("GET", "/status", b""), Ok(Request::Status));
+        assert_eq!(route("GET", "/devices", b""), Ok(Request::ListDevices));
+        assert_eq!(
+            route("GET", "/networks?interface=wlan1", b""),
+            Ok(Request::ListNetworks {
+                interface: Some("wlan1".into())
+            })
+        );
+        assert_eq!(
+            route("GET", "/networks", b""),
+            Ok(Request::ListNetworks { interface: None })
+        );
+    }
+
+    #[test]
+    fn decodes_query_values() {
+        assert_eq!(
+            route("POST", "/scan?interface=wl%61n0", b""),
+            Ok(Request::Scan {
+                interface: Some("wlan0".into())
+            })
+        );
+        assert_eq!(percent_decode("a+b%20c%C3%A9"), Ok("a b cé".into()));
+        assert_eq!(percent_decode("%2"), Err(Status::BadRequest));
+        assert_eq!(percent_decode("%zz"), Err(Status::BadRequest));
+        assert_eq!(percent_decode("%ff"), Err(Status::BadRequest));
+        assert_eq!(
+            route("GET", "/networks?interface=%", b""),
+            Err(Status::BadRequest)
+        );
+    }
+
+    #[test]
+    fn maps_library_errors_to_status() {
+        let invalid = ConnectionError::InvalidInput {
+            field: "ssid".into(),
+            reason: "empty".into(),
+        };
+        assert_eq!(error_status(&invalid), Status::BadRequest);
+        assert_eq!(
+            error_status(&ConnectionError::MissingPassword),
+            Status::BadRequest
+        );
+        assert_eq!(error_status(&ConnectionError::NotFound), Status::NotFound);
+        assert_eq!(
+            error_status(&ConnectionError::NoWifiDevice),
+            Status::NotFound
+        );
+        assert_eq!(error_status(&ConnectionError::Timeout), Status::BadGateway);
+    }
+
+    #[test]
+    fn routes_connect_body() {
+        assert_eq!(
+            route("POST", "/connect", br#"{"ssid":"Home","password":"pw"}"#),
+            Ok(Request::Connect {
+                ssid: "Home".into(),
+                password: Some("pw".into()),
+                interface: None,
+            })
+        );
+        assert_eq!(route("POST", "/connect", b"{}"), Err(Status::BadRequest));
+    }
+
+    #[test]
+    fn rejects_wrong_method_and_path() {
+        assert_eq!(route("GET", "/scan", b""), Err(Status::MethodNotAllowed));
+        assert_eq!(route("POST", "/status", b""), Err(Status::MethodNotAllowed));
+        assert_eq!(route("GET", "/reboot", b""), Err(Status::NotFound));
+    }
+
+    #[tokio::test]
+    async fn parses_request_head_and_body() {
+        let raw = b"POST /connect HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer t\r\nContent-Length: 15\r\n\r\n{\"ssid\":\"Home\"}";
+        let mut reader = BufReader::new(&raw[..]);
+        let req = read_request(&mut reader).await.unwrap().unwrap();
+        assert_eq!(req.method, "POST");
+        assert_eq!(req.target, "/connect");
+        assert_eq!(req.authorization.as_deref(), Some("Bearer t"));
+        assert_eq!(req.body, br#"{"ssid":"Home"}"#);
+    }
+
+    #[tokio::test]
+    async fn rejects_oversized_head() {
+        let raw = format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(MAX_HEAD));
+        let mut reader = BufReader::new(raw.as_bytes());
+        assert_eq!(
+            read_request(&mut reader).await.unwrap().err(),
+            Some(Status::PayloadTooLarge)
+        );
+
+        let raw = format!("GET / HTTP/1.1\r\nX-Pad: {}\r\n\r\n", "a".repeat(MAX_HEAD));
+        let mut reader = BufReader::new(raw.as_bytes());
+        assert_eq!(
+            read_request(&mut reader).await.unwrap().err(),
+            Some(Status::PayloadTooLarge)
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_oversized_body() {
+        let raw = format!(
+            "POST /connect HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY + 1
+        );
+        let mut reader = BufReader::new(raw.as_bytes());
+        assert_eq!(
+            read_request(&mut reader).await.unwrap().err(),
+            Some(Status::PayloadTooLarge)
+        );
+    }
+}
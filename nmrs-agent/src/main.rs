@@ -0,0 +1,156 @@
+//! `nmrs-agent`: headless companion daemon for nmrs.
+//!
+//! Runs the nmrs monitoring subsystems without a UI and exposes a small
+//! line-delimited JSON API on a Unix socket, so window managers, status bars
+//! and scripts can query and drive NetworkManager without linking GTK or
+//! talking D-Bus themselves. See [`protocol`] for the wire format.
+//!
+//! ```text
+//! nmrs-agent [--socket PATH] [--auto-connect]
+//! echo '{"cmd":"status"}' | socat - UNIX-CONNECT:$XDG_RUNTIME_DIR/nmrs-agent.sock
+//! ```
+//!
+//! With the `http` feature, `--http 127.0.0.1:8787` additionally serves a
+//! token-authenticated REST bridge; the token is read from
+//! `NMRS_AGENT_HTTP_TOKEN`. See [`http`].
+
+#[cfg(feature = "http")]
+mod http;
+mod protocol;
+mod server;
+
+use std::env;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use log::{error, info};
+use nmrs::NetworkManager;
+
+const SOCKET_NAME: &str = "nmrs-agent.sock";
+
+/// Default socket location: `$XDG_RUNTIME_DIR/nmrs-agent.sock`.
+///
+/// There is deliberately no fallback: a fixed path in a shared directory
+/// such as `/tmp` could be claimed by another user first.
+fn default_socket_path() -> Option<PathBuf> {
+    env::var_os("XDG_RUNTIME_DIR")
+        .filter(|dir| !dir.is_empty())
+        .map(|dir| PathBuf::from(dir).join(SOCKET_NAME))
+}
+
+/// Environment variable holding the HTTP bridge bearer token.
+#[cfg(feature = "http")]
+const HTTP_TOKEN_VAR: &str = "NMRS_AGENT_HTTP_TOKEN";
+
+struct Args {
+    socket: PathBuf,
+    auto_connect: bool,
+    #[cfg(feature = "http")]
+    http: Option<std::net::SocketAddr>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut socket = None;
+    let mut auto_connect = false;
+    #[cfg(feature = "http")]
+    let mut http = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--socket" | "-s" => {
+                socket = Some(
+                    args.next()
+                        .map(PathBuf::from)
+                        .ok_or("--socket requires a path")?,
+                );
+            }
+            "--auto-connect" => auto_connect = true,
+            #[cfg(feature = "http")]
+            "--http" => {
+                let addr = args.next().ok_or("--http requires an address")?;
+                http = Some(
+                    addr.parse()
+                        .map_err(|e| format!("invalid --http address '{addr}': {e}"))?,
+                );
+            }
+            "--help" | "-h" => {
+                return Err(format!(
+                    "usage: nmrs-agent [--socket PATH] [--auto-connect]{}\n\ndefault socket: {}",
+                    if cfg!(feature = "http") {
+                        " [--http 127.0.0.1:PORT]"
+                    } else {
+                        ""
+                    },
+                    default_socket_path().map_or_else(
+                        || "none, XDG_RUNTIME_DIR is not set".to_string(),
+                        |path| path.display().to_string()
+                    )
+                ));
+            }
+            other => return Err(format!("unknown argument: {other}")),
+        }
+    }
+    let socket = socket
+        .or_else(default_socket_path)
+        .ok_or("XDG_RUNTIME_DIR is not set; pass --socket PATH")?;
+    Ok(Args {
+        socket,
+        auto_connect,
+        #[cfg(feature = "http")]
+        http,
+    })
+}
+
+/// Runs the HTTP bridge if `--http` was given, otherwise never completes.
+#[cfg(feature = "http")]
+async fn serve_http(nm: NetworkManager, args: &Args) -> std::io::Result<()> {
+    let Some(addr) = args.http else {
+        return std::future::pending().await;
+    };
+    let token = env::var(HTTP_TOKEN_VAR).unwrap_or_default();
+    http::serve(nm, addr, token).await
+}
+
+#[cfg(not(feature = "http"))]
+async fn serve_http(_nm: NetworkManager, _args: &Args) -> std::io::Result<()> {
+    std::future::pending().await
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(msg) => {
+            eprintln!("{msg}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let nm = match NetworkManager::new().await {
+        Ok(nm) => nm,
+        Err(e) => {
+            error!("Failed to connect to NetworkManager: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let socket = &args.socket;
+    let result = tokio::select! {
+        result = server::serve(nm.clone(), socket, args.auto_connect) => result,
+        result = serve_http(nm, &args) => result,
+        _ = tokio::signal::ctrl_c() => {
+            info!("Shutting down");
+            Ok(())
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            error!("Server failed: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
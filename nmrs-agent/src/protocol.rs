@@ -0,0 +1,251 @@
+//! Wire format of the control socket.
+//!
+//! Clients write one JSON object per line and read one JSON object per line
+//! back. Every request carries a `cmd` tag:
+//!
+//! ```text
+//! {"cmd":"status"}
+//! {"cmd":"list_networks","interface":"wlan0"}
+//! {"cmd":"connect","ssid":"Home","password":"hunter22"}
+//! ```
+//!
+//! Replies are `{"ok":true,"data":...}` or `{"ok":false,"error":"..."}`.
+//! After `{"cmd":"subscribe"}` the connection switches to a stream of
+//! `{"event":...}` lines until the client disconnects.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A single command read from the socket.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+pub enum Request {
+    /// Connectivity, radio state and the active Wi-Fi network.
+    Status,
+    /// Every device NetworkManager knows about.
+    ListDevices,
+    /// Visible Wi-Fi networks, optionally on one interface.
+    ListNetworks {
+        #[serde(default)]
+        interface: Option<String>,
+    },
+    /// Trigger a Wi-Fi scan, optionally on one interface.
+    Scan {
+        #[serde(default)]
+        interface: Option<String>,
+    },
+    /// Connect to an SSID; `password` selects WPA-PSK, otherwise open.
+    Connect {
+        ssid: String,
+        #[serde(default)]
+        password: Option<String>,
+        #[serde(default)]
+        interface: Option<String>,
+    },
+    /// Disconnect Wi-Fi, optionally on one interface.
+    Disconnect {
+        #[serde(default)]
+        interface: Option<String>,
+    },
+    /// Enable or disable the Wi-Fi radio.
+    SetWireless { enabled: bool },
+    /// Stream network and device change events.
+    Subscribe,
+}
+
+/// Reply to a [`Request`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum Response {
+    Ok { ok: bool, data: Value },
+    Err { ok: bool, error: String },
+}
+
+impl Response {
+    pub fn ok(data: impl Serialize) -> Self {
+        Self::Ok {
+            ok: true,
+            data: serde_json::to_value(data).unwrap_or(Value::Null),
+        }
+    }
+
+    pub fn err(error: impl ToString) -> Self {
+        Self::Err {
+            ok: false,
+            error: error.to_string(),
+        }
+    }
+}
+
+/// Pushed to subscribed clients.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    /// The set of visible access points changed.
+    NetworksChanged,
+    /// A device was added, removed, or changed state.
+    DevicesChanged,
+    /// The system resumed from suspend and network state was rechecked.
+    Resumed {
+        /// Whether the connection failed to survive the suspend cycle.
+        stale: bool,
+    },
+    /// The auto-connect watchdog brought Wi-Fi back up.
+    AutoConnected {
+        /// Name of the saved profile it activated.
+        id: String,
+    },
+    /// A NetworkManager checkpoint was created or went away.
+    Checkpoint {
+        /// D-Bus path of the checkpoint.
+        path: String,
+        /// What happened to it.
+        change: CheckpointChange,
+    },
+}
+
+/// Lifecycle step reported by [`Event::Checkpoint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckpointChange {
+    Created,
+    /// NM rolled the checkpoint back after its timeout.
+    RolledBack,
+    Destroyed,
+}
+
+impl From<&nmrs::CheckpointEvent> for Event {
+    fn from(event: &nmrs::CheckpointEvent) -> Self {
+        let change = match event {
+            nmrs::CheckpointEvent::Created { .. } => CheckpointChange::Created,
+            nmrs::CheckpointEvent::RolledBack { .. } => CheckpointChange::RolledBack,
+            _ => CheckpointChange::Destroyed,
+        };
+        Self::Checkpoint {
+            path: event.path().to_string(),
+            change,
+        }
+    }
+}
+
+/// Device entry in `list_devices` replies.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeviceInfo {
+    pub interface: String,
+    pub device_type: String,
+    pub state: String,
+    pub state_reason: String,
+    pub managed: Option<bool>,
+    pub ip4_address: Option<String>,
+    pub ip6_address: Option<String>,
+}
+
+impl From<&nmrs::Device> for DeviceInfo {
+    fn from(d: &nmrs::Device) -> Self {
+        Self {
+            interface: d.interface.clone(),
+            device_type: d.device_type.to_string(),
+            state: d.state.to_string(),
+            state_reason: d.state_reason.to_string(),
+            managed: d.managed,
+            ip4_address: d.ip4_address.clone(),
+            ip6_address: d.ip6_address.clone(),
+        }
+    }
+}
+
+/// Payload of `status` replies.
+#[derive(Debug, Clone, Serialize)]
+pub struct Status {
+    pub connectivity: String,
+    pub wireless_enabled: bool,
+    pub wireless_hardware_enabled: bool,
+    pub connecting: bool,
+    pub network: Option<nmrs::Network>,
+}
+
+/// Parses one request line.
+pub fn parse_request(line: &str) -> Result<Request, String> {
+    serde_json::from_str(line.trim()).map_err(|e| format!("invalid request: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_unit_commands() {
+        assert_eq!(parse_request(r#"{"cmd":"status"}"#), Ok(Request::Status));
+        assert_eq!(
+            parse_request(r#"{"cmd":"subscribe"}"#),
+            Ok(Request::Subscribe)
+        );
+    }
+
+    #[test]
+    fn interface_is_optional() {
+        assert_eq!(
+            parse_request(r#"{"cmd":"scan"}"#),
+            Ok(Request::Scan { interface: None })
+        );
+        assert_eq!(
+            parse_request(r#"{"cmd":"list_networks","interface":"wlan1"}"#),
+            Ok(Request::ListNetworks {
+                interface: Some("wlan1".into())
+            })
+        );
+    }
+
+    #[test]
+    fn parses_connect() {
+        assert_eq!(
+            parse_request(r#"{"cmd":"connect","ssid":"Home","password":"pw"}"#),
+            Ok(Request::Connect {
+                ssid: "Home".into(),
+                password: Some("pw".into()),
+                interface: None,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_and_malformed() {
+        assert!(parse_request(r#"{"cmd":"reboot"}"#).is_err());
+        assert!(parse_request(r#"{"cmd":"connect"}"#).is_err());
+        assert!(parse_request("not json").is_err());
+    }
+
+    #[test]
+    fn response_shapes() {
+        let ok = serde_json::to_string(&Response::ok(vec![1, 2])).unwrap();
+        assert_eq!(ok, r#"{"ok":true,"data":[1,2]}"#);
+        let err = serde_json::to_string(&Response::err("boom")).unwrap();
+        assert_eq!(err, r#"{"ok":false,"error":"boom"}"#);
+    }
+
+    #[test]
+    fn event_shape() {
+        let ev = serde_json::to_string(&Event::NetworksChanged).unwrap();
+        assert_eq!(ev, r#"{"event":"networks_changed"}"#);
+    }
+
+    #[test]
+    fn auto_connected_event_shape() {
+        let ev = Event::AutoConnected { id: "Home".into() };
+        assert_eq!(
+            serde_json::to_string(&ev).unwrap(),
+            r#"{"event":"auto_connected","id":"Home"}"#
+        );
+    }
+
+    #[test]
+    fn checkpoint_event_shape() {
+        let ev = Event::from(&nmrs::CheckpointEvent::RolledBack {
+            path: "/org/freedesktop/NetworkManager/Checkpoint/1".into(),
+        });
+        assert_eq!(
+            serde_json::to_string(&ev).unwrap(),
+            r#"{"event":"checkpoint","path":"/org/freedesktop/NetworkManager/Checkpoint/1","change":"rolled_back"}"#
+        );
+    }
+}
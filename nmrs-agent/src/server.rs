@@ -0,0 +1,346 @@
+//! Control socket server and monitoring subsystems.
+
+use std::fs;
+use std::io;
+use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+use std::path::Path;
+use std::time::Duration;
+
+use log::{debug, error, info, warn};
+use nmrs::{ConnectionError, NetworkManager, WifiSecurity};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::broadcast;
+use tokio::time::MissedTickBehavior;
+
+use crate::protocol::{DeviceInfo, Event, Request, Response, Status, parse_request};
+
+/// Capacity of the event fan-out channel; slow subscribers skip older events.
+const EVENT_BUFFER: usize = 64;
+/// Upper bound on a single request line.
+const MAX_LINE: u64 = 64 * 1024;
+/// How often the auto-connect watchdog checks whether Wi-Fi dropped.
+const WATCHDOG_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Binds the control socket at `path` and serves clients until the task is dropped.
+///
+/// With `auto_connect`, a watchdog also reconnects to the best saved network
+/// whenever Wi-Fi is enabled but idle.
+pub async fn serve(nm: NetworkManager, path: &Path, auto_connect: bool) -> io::Result<()> {
+    let listener = bind(path).await?;
+    let _socket = SocketFile(path);
+    info!("Listening on {}", path.display());
+
+    let (events, _) = broadcast::channel(EVENT_BUFFER);
+    spawn_monitors(&nm, &events);
+    if auto_connect {
+        spawn_watchdog(&nm, &events);
+    }
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let nm = nm.clone();
+        let events = events.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_client(nm, stream, events).await {
+                debug!("Client disconnected: {e}");
+            }
+        });
+    }
+}
+
+/// Removes the control socket when the server stops or its task is dropped.
+struct SocketFile<'a>(&'a Path);
+
+impl Drop for SocketFile<'_> {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(self.0);
+    }
+}
+
+/// Binds the control socket at `path` with mode `0600`.
+///
+/// A socket file left by a previous run is removed only if nothing answers
+/// on it; a live agent or a path that is not a socket is an error.
+async fn bind(path: &Path) -> io::Result<UnixListener> {
+    match fs::symlink_metadata(path) {
+        Ok(meta) if !meta.file_type().is_socket() => {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} exists and is not a socket", path.display()),
+            ));
+        }
+        Ok(_) => match UnixStream::connect(path).await {
+            Ok(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::AddrInUse,
+                    format!("another agent is listening on {}", path.display()),
+                ));
+            }
+            Err(e) if e.kind() == io::ErrorKind::ConnectionRefused => {
+                debug!("Removing stale socket {}", path.display());
+                fs::remove_file(path)?;
+            }
+            Err(e) => return Err(e),
+        },
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+
+    // Restrict the socket before anything accepts on it.
+    let listener = UnixListener::bind(path)?;
+    if let Err(e) = fs::set_permissions(path, fs::Permissions::from_mode(0o600)) {
+        let _ = fs::remove_file(path);
+        return Err(e);
+    }
+    Ok(listener)
+}
+
+/// Runs the network, device, resume and checkpoint monitors, forwarding changes to subscribers.
+fn spawn_monitors(nm: &NetworkManager, events: &broadcast::Sender<Event>) {
+    let (monitor_nm, tx) = (nm.clone(), events.clone());
+    tokio::spawn(async move {
+        let result = monitor_nm
+            .monitor_network_changes(move || {
+                let _ = tx.send(Event::NetworksChanged);
+            })
+            .await;
+        if let Err(e) = result {
+            error!("Network monitor stopped: {e}");
+        }
+    });
+
+    let (monitor_nm, tx) = (nm.clone(), events.clone());
+    tokio::spawn(async move {
+        let result = monitor_nm
+            .monitor_device_changes(move || {
+                let _ = tx.send(Event::DevicesChanged);
+            })
+            .await;
+        if let Err(e) = result {
+            error!("Device monitor stopped: {e}");
+        }
+    });
+
+    let (monitor_nm, tx) = (nm.clone(), events.clone());
+    tokio::spawn(async move {
+        let result = monitor_nm
+            .resume_hook(move |report| {
+                let _ = tx.send(Event::Resumed {
+                    stale: report.is_stale(),
+                });
+            })
+            .await;
+        if let Err(e) = result {
+            warn!("Resume hook stopped: {e}");
+        }
+    });
+
+    let (monitor_nm, tx) = (nm.clone(), events.clone());
+    tokio::spawn(async move {
+        let result = monitor_nm
+            .monitor_checkpoints(move |event| {
+                let _ = tx.send(Event::from(&event));
+            })
+            .await;
+        if let Err(e) = result {
+            warn!("Checkpoint monitor stopped: {e}");
+        }
+    });
+}
+
+/// Reconnects to the best saved network whenever Wi-Fi is enabled but
+/// neither connected nor connecting, checking every [`WATCHDOG_INTERVAL`].
+fn spawn_watchdog(nm: &NetworkManager, events: &broadcast::Sender<Event>) {
+    let (nm, tx) = (nm.clone(), events.clone());
+    tokio::spawn(async move {
+        let mut ticks = tokio::time::interval(WATCHDOG_INTERVAL);
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticks.tick().await;
+            match wifi_idle(&nm).await {
+                Ok(true) => {}
+                Ok(false) => continue,
+                Err(e) => {
+                    debug!("Watchdog check failed: {e}");
+                    continue;
+                }
+            }
+            match nm.connect_best_available(None).await {
+                Ok(profile) => {
+                    info!("Auto-connected to '{}'", profile.id);
+                    let _ = tx.send(Event::AutoConnected { id: profile.id });
+                }
+                // Nothing saved is in range; try again on the next tick.
+                Err(ConnectionError::NotFound) => debug!("No saved network in range"),
+                Err(e) => warn!("Auto-connect failed: {e}"),
+            }
+        }
+    });
+}
+
+/// Whether the Wi-Fi radio is on but has no network and no activation in progress.
+async fn wifi_idle(nm: &NetworkManager) -> nmrs::Result<bool> {
+    let wifi = nm.wifi_state().await?;
+    if !wifi.enabled || !wifi.hardware_enabled || nm.is_connecting().await? {
+        return Ok(false);
+    }
+    Ok(nm.current_network().await?.is_none())
+}
+
+async fn handle_client(
+    nm: NetworkManager,
+    stream: UnixStream,
+    events: broadcast::Sender<Event>,
+) -> io::Result<()> {
+    let (read, mut write) = stream.into_split();
+    let mut reader = BufReader::new(read);
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+        let n = (&mut reader).take(MAX_LINE).read_line(&mut line).await?;
+        if n == 0 {
+            return Ok(());
+        }
+        if !line.ends_with('\n') && n as u64 == MAX_LINE {
+            return write_line(&mut write, &Response::err("request line too long")).await;
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+        let request = match parse_request(&line) {
+            Ok(request) => request,
+            Err(e) => {
+                write_line(&mut write, &Response::err(e)).await?;
+                continue;
+            }
+        };
+
+        if request == Request::Subscribe {
+            write_line(&mut write, &Response::ok(())).await?;
+            let mut rx = events.subscribe();
+            loop {
+                match rx.recv().await {
+                    Ok(event) => write_line(&mut write, &event).await?,
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        warn!("Subscriber lagged, skipped {n} events");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return Ok(()),
+                }
+            }
+        }
+
+        let response = dispatch(&nm, request).await;
+        write_line(&mut write, &response).await?;
+    }
+}
+
+/// Executes a single request against the library.
+pub(crate) async fn dispatch(nm: &NetworkManager, request: Request) -> Response {
+    execute(nm, request).await.unwrap_or_else(Response::err)
+}
+
+/// Like [`dispatch`], but hands library errors back to the caller so the
+/// HTTP bridge can pick a status code from them.
+pub(crate) async fn execute(nm: &NetworkManager, request: Request) -> nmrs::Result<Response> {
+    match request {
+        Request::Status => status(nm).await.map(Response::ok),
+        Request::ListDevices => nm
+            .list_devices()
+            .await
+            .map(|devices| Response::ok(devices.iter().map(DeviceInfo::from).collect::<Vec<_>>())),
+        Request::ListNetworks { interface } => nm
+            .list_networks(interface.as_deref())
+            .await
+            .map(Response::ok),
+        Request::Scan { interface } => nm
+            .scan_networks(interface.as_deref())
+            .await
+            .map(Response::ok),
+        Request::Connect {
+            ssid,
+            password,
+            interface,
+        } => {
+            let creds = match password {
+                Some(psk) => WifiSecurity::WpaPsk { psk },
+                None => WifiSecurity::Open,
+            };
+            nm.connect(&ssid, interface.as_deref(), creds)
+                .await
+                .map(Response::ok)
+        }
+        Request::Disconnect { interface } => {
+            nm.disconnect(interface.as_deref()).await.map(Response::ok)
+        }
+        Request::SetWireless { enabled } => {
+            nm.set_wireless_enabled(enabled).await.map(Response::ok)
+        }
+        Request::Subscribe => unreachable!("handled by the connection loop"),
+    }
+}
+
+async fn status(nm: &NetworkManager) -> nmrs::Result<Status> {
+    let connectivity = nm.connectivity().await?;
+    let wifi = nm.wifi_state().await?;
+    Ok(Status {
+        connectivity: connectivity.to_string(),
+        wireless_enabled: wifi.enabled,
+        wireless_hardware_enabled: wifi.hardware_enabled,
+        connecting: nm.is_connecting().await?,
+        network: nm.current_network().await?,
+    })
+}
+
+async fn write_line<T: serde::Serialize>(
+    write: &mut tokio::net::unix::OwnedWriteHalf,
+    value: &T,
+) -> io::Result<()> {
+    let mut buf = serde_json::to_vec(value)?;
+    buf.push(b'\n');
+    write.write_all(&buf).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn scratch_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("nmrs-agent-{}-{name}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[tokio::test]
+    async fn binds_private_socket() {
+        let path = scratch_path("private.sock");
+        let _listener = bind(&path).await.unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn replaces_only_stale_sockets() {
+        let path = scratch_path("stale.sock");
+        drop(std::os::unix::net::UnixListener::bind(&path).unwrap());
+        let live = bind(&path).await.unwrap();
+
+        let err = bind(&path).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AddrInUse);
+        drop(live);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn refuses_non_socket_paths() {
+        let path = scratch_path("regular");
+        fs::write(&path, b"").unwrap();
+        let err = bind(&path).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        assert!(path.exists());
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,53 @@
+//! `list_networks` in crowded environments.
+//!
+//! Seeds a `MockBackend` with 100+ visible access points (several BSSIDs
+//! per SSID) and lists networks through the public
+//! `nmrs::backend::list_networks`, which reads every access point's
+//! properties, decodes them and groups them into networks as
+//! `NetworkManager::list_networks` does, without D-Bus.
+//!
+//! Run with `cargo bench -p nmrs --features test-util --bench list_networks`.
+
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use futures::executor::block_on;
+use nmrs::backend::{self, MockAccessPoint, MockBackend};
+use nmrs::{DeviceState, DeviceType};
+
+/// BSSIDs advertising each SSID, as in an office or campus deployment.
+const BSSIDS_PER_SSID: usize = 4;
+
+/// A Wi-Fi device seeing `count` access points.
+fn crowded(count: usize) -> MockBackend {
+    (0..count).fold(
+        MockBackend::new().with_device("wlan0", DeviceType::Wifi, DeviceState::Disconnected),
+        |backend, i| {
+            let ssid = format!("Network-{:03}", i / BSSIDS_PER_SSID);
+            let bssid = format!("02:00:00:00:{:02X}:{:02X}", i / 256, i % 256);
+            let frequency = if i % 2 == 0 { 2412 } else { 5180 };
+            let ap =
+                MockAccessPoint::new(&ssid, &bssid, frequency).with_strength((i * 37 % 100) as u8);
+            backend.with_access_point("wlan0", ap)
+        },
+    )
+}
+
+fn list_networks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("list_networks");
+    for count in [100, 250, 500] {
+        let backend = crowded(count);
+        group.throughput(Throughput::Elements(count as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(count),
+            &backend,
+            |b, backend| {
+                b.iter(|| block_on(backend::list_networks(black_box(backend), None)));
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, list_networks);
+criterion_main!(benches);
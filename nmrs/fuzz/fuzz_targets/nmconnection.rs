@@ -0,0 +1,15 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use nmrs::keyfile::Keyfile;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    if let Ok(kf) = Keyfile::parse(input) {
+        // Anything we accept must survive a serialize/parse round trip.
+        let reparsed = Keyfile::parse(&kf.to_string()).expect("serialized keyfile must parse");
+        assert_eq!(reparsed, kf);
+    }
+});
@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use nmrs::keyfile::WgQuickConfig;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    if let Ok(conf) = WgQuickConfig::parse(input) {
+        let _ = conf.to_wireguard_config("fuzz");
+    }
+});
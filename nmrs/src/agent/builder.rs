@@ -8,9 +8,10 @@ use log::debug;
 use zbus::Connection;
 
 use crate::ConnectionError;
-use crate::dbus::AgentManagerProxy;
+use crate::dbus::{AgentManagerProxy, dbus_operation};
 
 use super::iface::SecretAgentInterface;
+use super::provider::{SecretProvider, spawn_provider};
 use super::request::{CancelReason, SecretAgentCapabilities, SecretRequest, SecretStoreEvent};
 
 const DEFAULT_IDENTIFIER: &str = "com.system76.CosmicApplets.nmrs.secret_agent";
@@ -67,12 +68,14 @@ impl SecretAgent {
 /// | capabilities | [`SecretAgentCapabilities::VPN_HINTS`] |
 /// | object path | `/com/system76/CosmicApplets/nmrs/SecretAgent` |
 /// | queue depth | 32 |
+/// | bus | a new system-bus connection |
 #[derive(Debug)]
 pub struct SecretAgentBuilder {
     identifier: String,
     capabilities: SecretAgentCapabilities,
     object_path: String,
     queue_depth: usize,
+    connection: Option<Connection>,
 }
 
 impl Default for SecretAgentBuilder {
@@ -82,6 +85,7 @@ impl Default for SecretAgentBuilder {
             capabilities: SecretAgentCapabilities::VPN_HINTS,
             object_path: DEFAULT_OBJECT_PATH.into(),
             queue_depth: DEFAULT_QUEUE_DEPTH,
+            connection: None,
         }
     }
 }
@@ -122,6 +126,37 @@ impl SecretAgentBuilder {
         self
     }
 
+    /// Serves the agent on `conn` instead of a new system-bus connection.
+    ///
+    /// Use this when NetworkManager is reached over a custom bus, as with
+    /// [`NetworkManager::with_connection`](crate::NetworkManager::with_connection).
+    #[must_use]
+    pub fn with_connection(mut self, conn: Connection) -> Self {
+        self.connection = Some(conn);
+        self
+    }
+
+    /// Registers the agent and answers its requests with `provider`.
+    ///
+    /// Requests and cancellations are handed to the provider on background
+    /// tasks, so this must be called within a Tokio runtime. The returned
+    /// handle's [`cancellations()`](SecretAgentHandle::cancellations) stream
+    /// stays empty; cancellations go to [`SecretProvider::cancelled`].
+    ///
+    /// # Errors
+    ///
+    /// Fails like [`register()`](Self::register).
+    pub async fn register_provider<P: SecretProvider>(
+        self,
+        provider: P,
+    ) -> crate::Result<SecretAgentHandle> {
+        let (mut handle, requests) = self.register().await?;
+        let (_, closed) = mpsc::unbounded();
+        let cancellations = std::mem::replace(&mut handle.cancel_rx, closed);
+        spawn_provider(provider, requests, cancellations);
+        Ok(handle)
+    }
+
     /// Connects to the system bus, registers the agent with NetworkManager,
     /// and returns a handle and a stream of incoming secret requests.
     ///
@@ -150,19 +185,21 @@ impl SecretAgentBuilder {
             pending: Arc::new(Mutex::new(HashMap::new())),
         };
 
-        let conn = Connection::system()
-            .await
-            .map_err(|e| ConnectionError::DbusOperation {
-                context: "connecting to system bus for secret agent".into(),
-                source: e,
-            })?;
+        let conn = match self.connection {
+            Some(conn) => conn,
+            None => Connection::system()
+                .await
+                .map_err(|e| dbus_operation("connecting to system bus for secret agent", e))?,
+        };
 
         conn.object_server()
             .at(&*self.object_path, iface)
             .await
-            .map_err(|e| ConnectionError::DbusOperation {
-                context: format!("serving SecretAgent interface at {}", self.object_path),
-                source: e,
+            .map_err(|e| {
+                dbus_operation(
+                    format!("serving SecretAgent interface at {}", self.object_path),
+                    e,
+                )
             })?;
 
         conn.request_name(&*self.identifier).await.map_err(|e| {
@@ -176,21 +213,14 @@ impl SecretAgentBuilder {
             self.identifier, self.object_path
         );
 
-        let agent_proxy =
-            AgentManagerProxy::new(&conn)
-                .await
-                .map_err(|e| ConnectionError::DbusOperation {
-                    context: "creating AgentManager proxy".into(),
-                    source: e,
-                })?;
+        let agent_proxy = AgentManagerProxy::new(&conn)
+            .await
+            .map_err(|e| dbus_operation("creating AgentManager proxy", e))?;
 
         agent_proxy
             .register_with_capabilities(&self.identifier, self.capabilities.bits())
             .await
-            .map_err(|e| ConnectionError::DbusOperation {
-                context: "registering secret agent with NetworkManager".into(),
-                source: e,
-            })?;
+            .map_err(|e| dbus_operation("registering secret agent with NetworkManager", e))?;
 
         debug!(
             "Registered secret agent '{}' with capabilities {:?}",
@@ -243,19 +273,13 @@ impl SecretAgentHandle {
     ///
     /// Returns an error if the D-Bus call to `RegisterWithCapabilities` fails.
     pub async fn reregister(&self) -> crate::Result<()> {
-        let proxy = AgentManagerProxy::new(&self.conn).await.map_err(|e| {
-            ConnectionError::DbusOperation {
-                context: "creating AgentManager proxy for re-registration".into(),
-                source: e,
-            }
-        })?;
+        let proxy = AgentManagerProxy::new(&self.conn)
+            .await
+            .map_err(|e| dbus_operation("creating AgentManager proxy for re-registration", e))?;
         proxy
             .register_with_capabilities(&self.identifier, self.capabilities.bits())
             .await
-            .map_err(|e| ConnectionError::DbusOperation {
-                context: "re-registering secret agent with NetworkManager".into(),
-                source: e,
-            })?;
+            .map_err(|e| dbus_operation("re-registering secret agent with NetworkManager", e))?;
         debug!("Re-registered secret agent '{}'", self.identifier);
         Ok(())
     }
@@ -269,26 +293,27 @@ impl SecretAgentHandle {
     ///
     /// Returns an error if the D-Bus `Unregister` call fails.
     pub async fn unregister(self) -> crate::Result<()> {
-        let proxy = AgentManagerProxy::new(&self.conn).await.map_err(|e| {
-            ConnectionError::DbusOperation {
-                context: "creating AgentManager proxy for unregistration".into(),
-                source: e,
-            }
-        })?;
+        let proxy = AgentManagerProxy::new(&self.conn)
+            .await
+            .map_err(|e| dbus_operation("creating AgentManager proxy for unregistration", e))?;
         proxy
             .unregister()
             .await
-            .map_err(|e| ConnectionError::DbusOperation {
-                context: "unregistering secret agent".into(),
-                source: e,
-            })?;
+            .map_err(|e| dbus_operation("unregistering secret agent", e))?;
+        // The connection may be shared, so take the interface down rather
+        // than relying on the connection being dropped.
+        if let Err(e) = self
+            .conn
+            .object_server()
+            .remove::<SecretAgentInterface, _>(&*self.object_path)
+            .await
+        {
+            debug!("Failed to remove SecretAgent interface: {e}");
+        }
         self.conn
             .release_name(&*self.identifier)
             .await
-            .map_err(|e| ConnectionError::DbusOperation {
-                context: format!("releasing bus name '{}'", self.identifier),
-                source: e,
-            })?;
+            .map_err(|e| dbus_operation(format!("releasing bus name '{}'", self.identifier), e))?;
         debug!("Unregistered secret agent '{}'", self.identifier);
         Ok(())
     }
@@ -49,6 +49,11 @@
 //!         └── handle.unregister().await?
 //! ```
 //!
+//! Instead of draining the request stream, a consumer can implement
+//! [`SecretProvider`](crate::agent::SecretProvider) and register it with
+//! [`NetworkManager::register_secret_agent`](crate::NetworkManager::register_secret_agent);
+//! each request is then passed to the provider on a background task.
+//!
 //! If NetworkManager restarts while the agent is running, call
 //! [`SecretAgentHandle::reregister()`](crate::agent::SecretAgentHandle::reregister)
 //! to re-register.
@@ -86,9 +91,11 @@
 
 mod builder;
 pub(crate) mod iface;
+mod provider;
 mod request;
 
 pub use builder::{SecretAgent, SecretAgentBuilder, SecretAgentHandle};
+pub use provider::SecretProvider;
 pub use request::{
     CancelReason, SecretAgentCapabilities, SecretAgentFlags, SecretRequest, SecretResponder,
     SecretSetting, SecretStoreEvent,
@@ -0,0 +1,143 @@
+//! Callback-style secret agent driven by a [`SecretProvider`].
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use futures::channel::mpsc;
+use log::warn;
+
+use super::request::{CancelReason, SecretRequest};
+
+/// Answers NetworkManager's secret requests on behalf of a registered agent.
+///
+/// Implement this instead of driving the request stream yourself, then pass
+/// it to [`NetworkManager::register_secret_agent`](crate::NetworkManager::register_secret_agent)
+/// or [`SecretAgentBuilder::register_provider`](super::SecretAgentBuilder::register_provider).
+/// Each request is handled on its own task, so a provider that waits for
+/// the user (e.g. behind a dialog) does not hold up other requests.
+///
+/// # Example
+///
+/// ```no_run
+/// use async_trait::async_trait;
+/// use nmrs::NetworkManager;
+/// use nmrs::agent::{SecretAgentFlags, SecretProvider, SecretRequest, SecretSetting};
+///
+/// struct StaticPassword(String);
+///
+/// #[async_trait]
+/// impl SecretProvider for StaticPassword {
+///     async fn provide(&self, req: SecretRequest) -> nmrs::Result<()> {
+///         match req.setting {
+///             SecretSetting::WifiPsk { .. }
+///                 if req.flags.contains(SecretAgentFlags::ALLOW_INTERACTION) =>
+///             {
+///                 req.responder.wifi_psk(self.0.clone()).await
+///             }
+///             _ => req.responder.no_secrets().await,
+///         }
+///     }
+/// }
+///
+/// # async fn example() -> nmrs::Result<()> {
+/// let nm = NetworkManager::new().await?;
+/// let handle = nm
+///     .register_secret_agent(StaticPassword("hunter22".into()))
+///     .await?;
+/// // ... later
+/// handle.unregister().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[async_trait]
+pub trait SecretProvider: Send + Sync + 'static {
+    /// Answers one `GetSecrets` request.
+    ///
+    /// Reply through `request.responder`. If the responder is dropped
+    /// without a reply, NetworkManager is told there are no secrets. An
+    /// error is logged and otherwise ignored.
+    async fn provide(&self, request: SecretRequest) -> crate::Result<()>;
+
+    /// Called when NetworkManager abandons a pending request, e.g. to close
+    /// the dialog opened for it. NetworkManager has already been answered.
+    async fn cancelled(&self, _reason: CancelReason) {}
+}
+
+/// Feeds `requests` and `cancellations` to `provider` until both streams end.
+pub(crate) fn spawn_provider<P: SecretProvider>(
+    provider: P,
+    mut requests: mpsc::Receiver<SecretRequest>,
+    mut cancellations: mpsc::UnboundedReceiver<CancelReason>,
+) {
+    let provider = Arc::new(provider);
+
+    let on_request = Arc::clone(&provider);
+    tokio::spawn(async move {
+        while let Some(request) = requests.next().await {
+            let provider = Arc::clone(&on_request);
+            tokio::spawn(async move {
+                let id = request.connection_id.clone();
+                if let Err(e) = provider.provide(request).await {
+                    warn!("Secret provider failed to answer request for '{id}': {e}");
+                }
+            });
+        }
+    });
+
+    tokio::spawn(async move {
+        while let Some(reason) = cancellations.next().await {
+            provider.cancelled(reason).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::SinkExt;
+    use futures::channel::oneshot;
+    use zvariant::OwnedObjectPath;
+
+    use super::*;
+    use crate::agent::request::{SecretReply, SecretResponder};
+    use crate::agent::{SecretAgentFlags, SecretSetting};
+
+    struct Psk;
+
+    #[async_trait]
+    impl SecretProvider for Psk {
+        async fn provide(&self, request: SecretRequest) -> crate::Result<()> {
+            request.responder.wifi_psk("hunter22").await
+        }
+    }
+
+    #[tokio::test]
+    async fn provider_answers_requests() {
+        let (mut request_tx, request_rx) = mpsc::channel(1);
+        let (_cancel_tx, cancel_rx) = mpsc::unbounded();
+        spawn_provider(Psk, request_rx, cancel_rx);
+
+        let (reply_tx, reply_rx) = oneshot::channel();
+        request_tx
+            .send(SecretRequest {
+                connection_uuid: "uuid".into(),
+                connection_id: "Home".into(),
+                connection_type: "802-11-wireless".into(),
+                connection_path: OwnedObjectPath::default(),
+                setting: SecretSetting::WifiPsk {
+                    ssid: "Home".into(),
+                },
+                hints: Vec::new(),
+                flags: SecretAgentFlags::ALLOW_INTERACTION,
+                responder: SecretResponder::new(reply_tx, "802-11-wireless-security".into()),
+            })
+            .await
+            .unwrap();
+
+        let SecretReply::Secrets(secrets) = reply_rx.await.unwrap() else {
+            panic!("expected secrets");
+        };
+        let psk = secrets["802-11-wireless-security"]["psk"].clone();
+        assert_eq!(String::try_from(psk).unwrap(), "hunter22");
+    }
+}
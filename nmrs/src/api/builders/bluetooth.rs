@@ -25,6 +25,7 @@
 use std::collections::HashMap;
 use zvariant::Value;
 
+use super::connection_builder::profile_uuid;
 use crate::{
     ConnectionOptions,
     models::{BluetoothIdentity, BluetoothNetworkRole},
@@ -39,7 +40,11 @@ pub fn base_connection_section(
     let mut s = HashMap::new();
     s.insert("type", Value::from("bluetooth"));
     s.insert("id", Value::from(name.to_string()));
-    s.insert("uuid", Value::from(uuid::Uuid::new_v4().to_string()));
+    let uuid = opts
+        .uuid_namespace
+        .map(|ns| profile_uuid(&ns, "bluetooth", name))
+        .unwrap_or_else(uuid::Uuid::new_v4);
+    s.insert("uuid", Value::from(uuid.to_string()));
     s.insert("autoconnect", Value::from(opts.autoconnect));
 
     if let Some(p) = opts.autoconnect_priority {
@@ -97,6 +102,13 @@ mod tests {
             autoconnect: true,
             autoconnect_priority: Some(10),
             autoconnect_retries: Some(3),
+            uuid_namespace: None,
+            trust_level: None,
+            ipv4: None,
+            ipv6: None,
+            mac_policy: None,
+            persistence: Default::default(),
+            activation_binding: Default::default(),
         }
     }
 
@@ -149,6 +161,13 @@ mod tests {
             autoconnect: false,
             autoconnect_priority: None,
             autoconnect_retries: None,
+            uuid_namespace: None,
+            trust_level: None,
+            ipv4: None,
+            ipv6: None,
+            mac_policy: None,
+            persistence: Default::default(),
+            activation_binding: Default::default(),
         };
         let section = base_connection_section("MinimalBT", &opts);
 
@@ -250,6 +269,13 @@ mod tests {
             autoconnect: false,
             autoconnect_priority: None,
             autoconnect_retries: None,
+            uuid_namespace: None,
+            trust_level: None,
+            ipv4: None,
+            ipv6: None,
+            mac_policy: None,
+            persistence: Default::default(),
+            activation_binding: Default::default(),
         };
         let conn = build_bluetooth_connection("MobileHotspot", &identity, &opts);
 
@@ -0,0 +1,220 @@
+//! Bridge, bond and slave connection builders.
+//!
+//! A bridge or bond is a master profile for a software device; each
+//! physical port is a separate Ethernet profile pointing at the master by
+//! interface name (`connection.master` / `connection.slave-type`). Slaves
+//! carry no IP configuration of their own; the master is addressed instead.
+
+use std::collections::HashMap;
+use zvariant::Value;
+
+use super::connection_builder::ConnectionBuilder;
+use crate::api::models::{BondMode, BridgeOptions, ConnectionError, ConnectionOptions, SlaveType};
+use crate::util::validation::validate_interface_name;
+
+/// Builds a bridge master profile for the interface `name`.
+///
+/// IP settings in `bridge` take precedence over those in `opts`.
+///
+/// # Errors
+///
+/// Returns `ConnectionError::InvalidInput` if `name` is not a valid
+/// interface name or `bridge` fails [`BridgeOptions::validate`].
+///
+/// # Examples
+///
+/// ```rust
+/// use nmrs::builders::build_bridge_connection;
+/// use nmrs::{BridgeOptions, ConnectionOptions};
+///
+/// let settings = build_bridge_connection(
+///     "br0",
+///     &BridgeOptions::new().with_stp(false),
+///     &ConnectionOptions::new(true),
+/// )
+/// .unwrap();
+/// assert!(settings.contains_key("bridge"));
+/// ```
+pub fn build_bridge_connection(
+    name: &str,
+    bridge: &BridgeOptions,
+    opts: &ConnectionOptions,
+) -> Result<HashMap<&'static str, HashMap<&'static str, Value<'static>>>, ConnectionError> {
+    validate_interface_name(name)?;
+    bridge.validate()?;
+
+    let mut section = HashMap::new();
+    if let Some(stp) = bridge.stp {
+        section.insert("stp", Value::from(stp));
+    }
+    if let Some(priority) = bridge.priority {
+        section.insert("priority", Value::from(u32::from(priority)));
+    }
+    if let Some(delay) = bridge.forward_delay {
+        section.insert("forward-delay", Value::from(delay));
+    }
+
+    let mut opts = opts.clone();
+    if bridge.ipv4.is_some() {
+        opts.ipv4.clone_from(&bridge.ipv4);
+    }
+    if bridge.ipv6.is_some() {
+        opts.ipv6.clone_from(&bridge.ipv6);
+    }
+
+    Ok(ConnectionBuilder::new("bridge", name)
+        .options(&opts)
+        .interface_name(name)
+        .with_section("bridge", section)
+        .ipv4_auto()
+        .ipv6_auto()
+        .build())
+}
+
+/// Builds a bond master profile for the interface `name`.
+///
+/// Link monitoring (MII, every 100 ms) is enabled so failed slaves are
+/// taken out of the bond.
+///
+/// # Errors
+///
+/// Returns `ConnectionError::InvalidInput` if `name` is not a valid
+/// interface name.
+///
+/// # Examples
+///
+/// ```rust
+/// use nmrs::builders::build_bond_connection;
+/// use nmrs::{BondMode, ConnectionOptions};
+///
+/// let settings =
+///     build_bond_connection("bond0", BondMode::ActiveBackup, &ConnectionOptions::new(true))
+///         .unwrap();
+/// assert!(settings.contains_key("bond"));
+/// ```
+pub fn build_bond_connection(
+    name: &str,
+    mode: BondMode,
+    opts: &ConnectionOptions,
+) -> Result<HashMap<&'static str, HashMap<&'static str, Value<'static>>>, ConnectionError> {
+    validate_interface_name(name)?;
+
+    let bond_options = HashMap::from([
+        ("mode".to_string(), mode.as_str().to_string()),
+        ("miimon".to_string(), "100".to_string()),
+    ]);
+    let mut section = HashMap::new();
+    section.insert("options", Value::from(bond_options));
+
+    Ok(ConnectionBuilder::new("bond", name)
+        .options(opts)
+        .interface_name(name)
+        .with_section("bond", section)
+        .ipv4_auto()
+        .ipv6_auto()
+        .build())
+}
+
+/// Builds an Ethernet slave profile attaching `interface` to `master`.
+///
+/// The profile is named `<slave type>-slave-<interface>`, as `nmcli`
+/// does. IP settings in `opts` are ignored.
+///
+/// # Errors
+///
+/// Returns `ConnectionError::InvalidInput` if either name is not a valid
+/// interface name.
+///
+/// # Examples
+///
+/// ```rust
+/// use nmrs::builders::build_slave_connection;
+/// use nmrs::{ConnectionOptions, SlaveType};
+///
+/// let settings =
+///     build_slave_connection("eth1", "br0", SlaveType::Bridge, &ConnectionOptions::new(true))
+///         .unwrap();
+/// assert!(!settings.contains_key("ipv4"));
+/// ```
+pub fn build_slave_connection(
+    interface: &str,
+    master: &str,
+    slave_type: SlaveType,
+    opts: &ConnectionOptions,
+) -> Result<HashMap<&'static str, HashMap<&'static str, Value<'static>>>, ConnectionError> {
+    validate_interface_name(interface)?;
+    validate_interface_name(master)?;
+
+    let mut opts = opts.clone();
+    opts.ipv4 = None;
+    opts.ipv6 = None;
+
+    let id = format!("{}-slave-{interface}", slave_type.as_str());
+    Ok(ConnectionBuilder::new("802-3-ethernet", id)
+        .options(&opts)
+        .interface_name(interface)
+        .with_section("802-3-ethernet", HashMap::new())
+        .update_section("connection", |connection| {
+            connection.insert("master", Value::from(master.to_string()));
+            connection.insert("slave-type", Value::from(slave_type.as_str()));
+        })
+        .build())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn opts() -> ConnectionOptions {
+        ConnectionOptions::new(true)
+    }
+
+    #[test]
+    fn bridge_section_carries_stp_settings() {
+        let bridge = BridgeOptions::new()
+            .with_stp(false)
+            .with_priority(4096)
+            .with_forward_delay(4);
+        let settings = build_bridge_connection("br0", &bridge, &opts()).unwrap();
+
+        let connection = settings.get("connection").unwrap();
+        assert_eq!(connection.get("type"), Some(&Value::from("bridge")));
+        assert_eq!(connection.get("interface-name"), Some(&Value::from("br0")));
+        let section = settings.get("bridge").unwrap();
+        assert_eq!(section.get("stp"), Some(&Value::from(false)));
+        assert_eq!(section.get("priority"), Some(&Value::from(4096u32)));
+        assert_eq!(section.get("forward-delay"), Some(&Value::from(4u32)));
+        assert!(settings.contains_key("ipv4"));
+    }
+
+    #[test]
+    fn bond_section_sets_mode() {
+        let settings = build_bond_connection("bond0", BondMode::Lacp, &opts()).unwrap();
+        let section = settings.get("bond").unwrap();
+        let Some(Value::Dict(options)) = section.get("options") else {
+            panic!("bond options missing");
+        };
+        assert_eq!(
+            options.get::<&str, String>(&"mode").unwrap(),
+            Some("802.3ad".to_string())
+        );
+    }
+
+    #[test]
+    fn slave_points_at_master_without_ip() {
+        let settings = build_slave_connection("eth1", "bond0", SlaveType::Bond, &opts()).unwrap();
+        let connection = settings.get("connection").unwrap();
+        assert_eq!(connection.get("id"), Some(&Value::from("bond-slave-eth1")));
+        assert_eq!(connection.get("master"), Some(&Value::from("bond0")));
+        assert_eq!(connection.get("slave-type"), Some(&Value::from("bond")));
+        assert!(!settings.contains_key("ipv4"));
+        assert!(!settings.contains_key("ipv6"));
+    }
+
+    #[test]
+    fn rejects_invalid_names() {
+        assert!(build_bridge_connection("", &BridgeOptions::new(), &opts()).is_err());
+        assert!(build_bond_connection("bond with space", BondMode::default(), &opts()).is_err());
+        assert!(build_slave_connection("eth1", "", SlaveType::Bridge, &opts()).is_err());
+    }
+}
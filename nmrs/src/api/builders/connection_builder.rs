@@ -25,15 +25,37 @@
 //!     .build();
 //! ```
 
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::net::{Ipv4Addr, Ipv6Addr};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use uuid::Uuid;
 use zvariant::Value;
 
-use crate::api::models::ConnectionOptions;
+use crate::api::models::{ConnectionOptions, IpMethod, IpSettings, MacPolicy, TrustLevel};
+
+/// Derives a deterministic profile UUID from a namespace.
+///
+/// Returns a v5 UUID over `"{connection_type}:{id}"` in `namespace`. Fleet
+/// tooling can pick one namespace per organisation so that the same profile
+/// provisioned on many machines ends up with the same UUID everywhere.
+///
+/// # Example
+///
+/// ```rust
+/// use nmrs::builders::profile_uuid;
+/// use uuid::Uuid;
+///
+/// let a = profile_uuid(&Uuid::NAMESPACE_OID, "802-11-wireless", "Office");
+/// let b = profile_uuid(&Uuid::NAMESPACE_OID, "802-11-wireless", "Office");
+/// assert_eq!(a, b);
+/// ```
+#[must_use]
+pub fn profile_uuid(namespace: &Uuid, connection_type: &str, id: &str) -> Uuid {
+    Uuid::new_v5(namespace, format!("{connection_type}:{id}").as_bytes())
+}
 
 /// IP address configuration with CIDR prefix.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct IpConfig {
     pub address: String,
     pub prefix: u32,
@@ -51,7 +73,7 @@ impl IpConfig {
 }
 
 /// Route configuration for static routing.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Route {
     pub dest: String,
     pub prefix: u32,
@@ -116,6 +138,9 @@ impl Route {
 /// ```
 pub struct ConnectionBuilder {
     settings: HashMap<&'static str, HashMap<&'static str, Value<'static>>>,
+    cloned_mac: Option<MacPolicy>,
+    ipv4_override: Option<IpSettings>,
+    ipv6_override: Option<IpSettings>,
 }
 
 impl ConnectionBuilder {
@@ -145,7 +170,12 @@ impl ConnectionBuilder {
 
         settings.insert("connection", connection);
 
-        Self { settings }
+        Self {
+            settings,
+            cloned_mac: None,
+            ipv4_override: None,
+            ipv6_override: None,
+        }
     }
 
     /// Sets a specific UUID for the connection.
@@ -160,6 +190,26 @@ impl ConnectionBuilder {
         self
     }
 
+    /// Derives the connection UUID from `namespace` via [`profile_uuid`].
+    ///
+    /// Uses the connection type and ID passed to [`new`](Self::new), so the
+    /// result is stable for a given namespace and profile.
+    #[must_use]
+    pub fn uuid_namespace(self, namespace: Uuid) -> Self {
+        let key = self.settings.get("connection").and_then(|conn| {
+            match (conn.get("type"), conn.get("id")) {
+                (Some(Value::Str(ty)), Some(Value::Str(id))) => {
+                    Some(profile_uuid(&namespace, ty.as_str(), id.as_str()))
+                }
+                _ => None,
+            }
+        });
+        match key {
+            Some(uuid) => self.uuid(uuid),
+            None => self,
+        }
+    }
+
     /// Sets the network interface name for this connection.
     ///
     /// This restricts the connection to a specific interface (e.g., "wlan0", "eth0").
@@ -204,11 +254,46 @@ impl ConnectionBuilder {
         self
     }
 
+    /// Applies a [`TrustLevel`] preset.
+    ///
+    /// Sets the firewalld zone, mDNS/LLMNR policy and metered hint in the
+    /// `connection` section. For Wi-Fi and Ethernet profiles, the MAC
+    /// address policy is also written when the settings are built.
+    #[must_use]
+    pub fn trust_level(mut self, level: TrustLevel) -> Self {
+        if let Some(conn) = self.settings.get_mut("connection") {
+            conn.insert("zone", Value::from(level.zone()));
+            conn.insert("mdns", Value::from(level.multicast_dns()));
+            conn.insert("llmnr", Value::from(level.multicast_dns()));
+            match level.metered() {
+                Some(metered) => conn.insert("metered", Value::from(metered)),
+                None => conn.remove("metered"),
+            };
+        }
+        self.cloned_mac = Some(level.mac_policy());
+        self
+    }
+
+    /// Sets the MAC address policy of Wi-Fi and Ethernet profiles.
+    ///
+    /// Written to the device section's `cloned-mac-address` when the
+    /// settings are built; ignored for other connection types.
+    #[must_use]
+    pub fn mac_policy(mut self, policy: MacPolicy) -> Self {
+        self.cloned_mac = Some(policy);
+        self
+    }
+
     /// Applies multiple connection options at once.
     ///
     /// This is a convenience method to apply all fields from `ConnectionOptions`.
+    /// IP settings from `opts` are applied in [`build`](Self::build), so they
+    /// win over `ipv4_auto()`/`ipv6_auto()` defaults set by type-specific
+    /// builders.
     #[must_use]
     pub fn options(mut self, opts: &ConnectionOptions) -> Self {
+        self.ipv4_override.clone_from(&opts.ipv4);
+        self.ipv6_override.clone_from(&opts.ipv6);
         if let Some(conn) = self.settings.get_mut("connection") {
             conn.insert("autoconnect", Value::from(opts.autoconnect));
 
@@ -220,7 +305,16 @@ impl ConnectionBuilder {
                 conn.insert("autoconnect-retries", Value::from(retries));
             }
         }
-        self
+        if let Some(level) = opts.trust_level {
+            self = self.trust_level(level);
+        }
+        if let Some(policy) = &opts.mac_policy {
+            self = self.mac_policy(policy.clone());
+        }
+        match opts.uuid_namespace {
+            Some(namespace) => self.uuid_namespace(namespace),
+            None => self,
+        }
     }
 
     /// Configures IPv4 to use automatic configuration (DHCP).
@@ -441,6 +535,68 @@ impl ConnectionBuilder {
         self
     }
 
+    /// Configures the `ipv4` section from an [`IpSettings`].
+    ///
+    /// IPv6 gateway and DNS entries in `ip` are ignored.
+    #[must_use]
+    pub fn ipv4_settings(self, ip: &IpSettings) -> Self {
+        let mut builder = match ip.method {
+            IpMethod::Manual => self.ipv4_manual(ip.addresses.clone()),
+            IpMethod::LinkLocal => self.ipv4_link_local(),
+            IpMethod::Disabled => self.ipv4_disabled(),
+            _ => self.ipv4_auto(),
+        };
+        if let Some(IpAddr::V4(gateway)) = ip.gateway {
+            builder = builder.ipv4_gateway(gateway);
+        }
+        let dns: Vec<Ipv4Addr> = ip
+            .dns
+            .iter()
+            .filter_map(|addr| match addr {
+                IpAddr::V4(v4) => Some(*v4),
+                IpAddr::V6(_) => None,
+            })
+            .collect();
+        if !dns.is_empty() {
+            builder = builder.ipv4_dns(dns);
+        }
+        if !ip.routes.is_empty() {
+            builder = builder.ipv4_routes(ip.routes.clone());
+        }
+        builder.update_section("ipv4", |section| apply_ip_extras(section, ip))
+    }
+
+    /// Configures the `ipv6` section from an [`IpSettings`].
+    ///
+    /// IPv4 gateway and DNS entries in `ip` are ignored.
+    #[must_use]
+    pub fn ipv6_settings(self, ip: &IpSettings) -> Self {
+        let mut builder = match ip.method {
+            IpMethod::Manual => self.ipv6_manual(ip.addresses.clone()),
+            IpMethod::LinkLocal => self.ipv6_link_local(),
+            IpMethod::Disabled => self.ipv6_ignore(),
+            _ => self.ipv6_auto(),
+        };
+        if let Some(IpAddr::V6(gateway)) = ip.gateway {
+            builder = builder.ipv6_gateway(gateway);
+        }
+        let dns: Vec<Ipv6Addr> = ip
+            .dns
+            .iter()
+            .filter_map(|addr| match addr {
+                IpAddr::V6(v6) => Some(*v6),
+                IpAddr::V4(_) => None,
+            })
+            .collect();
+        if !dns.is_empty() {
+            builder = builder.ipv6_dns(dns);
+        }
+        if !ip.routes.is_empty() {
+            builder = builder.ipv6_routes(ip.routes.clone());
+        }
+        builder.update_section("ipv6", |section| apply_ip_extras(section, ip))
+    }
+
     /// Adds or replaces a complete settings section.
     ///
     /// This is useful for type-specific settings that don't have dedicated
@@ -505,11 +661,40 @@ impl ConnectionBuilder {
     /// This consumes the builder and returns the complete settings structure
     /// ready to be passed to NetworkManager's D-Bus API.
     #[must_use]
-    pub fn build(self) -> HashMap<&'static str, HashMap<&'static str, Value<'static>>> {
+    pub fn build(mut self) -> HashMap<&'static str, HashMap<&'static str, Value<'static>>> {
+        if let Some(ip) = self.ipv4_override.take() {
+            self = self.ipv4_settings(&ip);
+        }
+        if let Some(ip) = self.ipv6_override.take() {
+            self = self.ipv6_settings(&ip);
+        }
+        if let Some(mac) = self.cloned_mac.take() {
+            let device_section = match self.settings.get("connection").and_then(|c| c.get("type")) {
+                Some(Value::Str(ty)) if ty.as_str() == "802-11-wireless" => Some("802-11-wireless"),
+                Some(Value::Str(ty)) if ty.as_str() == "802-3-ethernet" => Some("802-3-ethernet"),
+                _ => None,
+            };
+            if let Some(name) = device_section {
+                self.settings
+                    .entry(name)
+                    .or_default()
+                    .insert("cloned-mac-address", Value::from(mac.as_str().to_string()));
+            }
+        }
         self.settings
     }
 }
 
+/// Writes the family-independent `dns-search` and `never-default` keys.
+fn apply_ip_extras(section: &mut HashMap<&'static str, Value<'static>>, ip: &IpSettings) {
+    if !ip.dns_search.is_empty() {
+        section.insert("dns-search", Value::from(ip.dns_search.clone()));
+    }
+    if ip.never_default {
+        section.insert("never-default", Value::from(true));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -536,6 +721,38 @@ mod tests {
         assert_eq!(conn.get("uuid"), Some(&Value::from(test_uuid.to_string())));
     }
 
+    #[test]
+    fn uuid_namespace_is_deterministic() {
+        let ns = Uuid::NAMESPACE_OID;
+        let a = ConnectionBuilder::new("802-11-wireless", "Office")
+            .uuid_namespace(ns)
+            .build();
+        let b = ConnectionBuilder::new("802-11-wireless", "Office")
+            .options(&ConnectionOptions::new(true).with_uuid_namespace(ns))
+            .build();
+
+        let expected = Value::from(profile_uuid(&ns, "802-11-wireless", "Office").to_string());
+        assert_eq!(a["connection"].get("uuid"), Some(&expected));
+        assert_eq!(b["connection"].get("uuid"), Some(&expected));
+    }
+
+    #[test]
+    fn profile_uuid_depends_on_type_and_id() {
+        let ns = Uuid::NAMESPACE_OID;
+        assert_ne!(
+            profile_uuid(&ns, "802-11-wireless", "Office"),
+            profile_uuid(&ns, "802-3-ethernet", "Office")
+        );
+        assert_ne!(
+            profile_uuid(&ns, "802-11-wireless", "Office"),
+            profile_uuid(&ns, "802-11-wireless", "Home")
+        );
+        assert_ne!(
+            profile_uuid(&ns, "vpn", "Office"),
+            profile_uuid(&Uuid::NAMESPACE_URL, "vpn", "Office")
+        );
+    }
+
     #[test]
     fn sets_interface_name() {
         let settings = ConnectionBuilder::new("802-3-ethernet", "MyConnection")
@@ -566,6 +783,13 @@ mod tests {
             autoconnect: true,
             autoconnect_priority: Some(5),
             autoconnect_retries: Some(2),
+            uuid_namespace: None,
+            trust_level: None,
+            ipv4: None,
+            ipv6: None,
+            mac_policy: None,
+            persistence: Default::default(),
+            activation_binding: Default::default(),
         };
 
         let settings = ConnectionBuilder::new("802-3-ethernet", "eth0")
@@ -682,4 +906,104 @@ mod tests {
         assert!(ipv4.contains_key("gateway"));
         assert!(ipv4.contains_key("dns"));
     }
+
+    #[test]
+    fn trust_level_sets_connection_keys() {
+        let settings = ConnectionBuilder::new("802-11-wireless", "Cafe")
+            .trust_level(TrustLevel::Public)
+            .build();
+        let conn = settings.get("connection").unwrap();
+        assert_eq!(conn.get("zone"), Some(&Value::from("public")));
+        assert_eq!(conn.get("mdns"), Some(&Value::from(0i32)));
+        assert_eq!(conn.get("llmnr"), Some(&Value::from(0i32)));
+        assert!(conn.get("metered").is_none());
+
+        let settings = ConnectionBuilder::new("802-3-ethernet", "Desk")
+            .trust_level(TrustLevel::Home)
+            .build();
+        let conn = settings.get("connection").unwrap();
+        assert_eq!(conn.get("zone"), Some(&Value::from("home")));
+        assert_eq!(conn.get("metered"), Some(&Value::from(2i32)));
+    }
+
+    #[test]
+    fn trust_level_mac_policy_survives_device_section() {
+        let settings = ConnectionBuilder::new("802-11-wireless", "Cafe")
+            .options(&ConnectionOptions::new(true).with_trust_level(TrustLevel::Public))
+            .with_section("802-11-wireless", HashMap::new())
+            .build();
+        let wireless = settings.get("802-11-wireless").unwrap();
+        assert_eq!(
+            wireless.get("cloned-mac-address"),
+            Some(&Value::from("random"))
+        );
+    }
+
+    #[test]
+    fn mac_policy_overrides_trust_level() {
+        let opts = ConnectionOptions::new(true)
+            .with_trust_level(TrustLevel::Public)
+            .with_mac_policy(MacPolicy::Explicit("02:00:00:00:00:01".into()));
+        let settings = ConnectionBuilder::new("802-3-ethernet", "Desk")
+            .options(&opts)
+            .build();
+        assert_eq!(
+            settings["802-3-ethernet"].get("cloned-mac-address"),
+            Some(&Value::from("02:00:00:00:00:01"))
+        );
+        assert!(MacPolicy::Explicit("02:00".into()).validate().is_err());
+    }
+
+    #[test]
+    fn trust_level_skips_mac_for_other_types() {
+        let settings = ConnectionBuilder::new("wireguard", "wg0")
+            .trust_level(TrustLevel::Work)
+            .build();
+        assert_eq!(settings.len(), 1);
+        assert_eq!(
+            settings.get("connection").unwrap().get("zone"),
+            Some(&Value::from("work"))
+        );
+    }
+
+    #[test]
+    fn ip_settings_from_options_override_auto() {
+        let opts = ConnectionOptions::new(true).with_ipv4(
+            IpSettings::manual(vec![IpConfig::new("192.168.1.50", 24)])
+                .with_gateway(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 1)))
+                .with_dns(vec![
+                    IpAddr::V4(Ipv4Addr::new(1, 1, 1, 1)),
+                    IpAddr::V6(Ipv6Addr::LOCALHOST),
+                ])
+                .with_dns_search(vec!["lan".into()])
+                .with_never_default(true),
+        );
+        let settings = ConnectionBuilder::new("802-3-ethernet", "eth0")
+            .options(&opts)
+            .ipv4_auto()
+            .ipv6_auto()
+            .build();
+
+        let ipv4 = &settings["ipv4"];
+        assert_eq!(ipv4.get("method"), Some(&Value::from("manual")));
+        assert_eq!(ipv4.get("gateway"), Some(&Value::from("192.168.1.1")));
+        assert_eq!(
+            ipv4.get("dns"),
+            Some(&Value::from(vec![u32::from(Ipv4Addr::new(1, 1, 1, 1))]))
+        );
+        assert_eq!(
+            ipv4.get("dns-search"),
+            Some(&Value::from(vec!["lan".to_string()]))
+        );
+        assert_eq!(ipv4.get("never-default"), Some(&Value::from(true)));
+        assert_eq!(settings["ipv6"].get("method"), Some(&Value::from("auto")));
+    }
+
+    #[test]
+    fn ipv6_settings_disabled() {
+        let settings = ConnectionBuilder::new("802-3-ethernet", "eth0")
+            .ipv6_settings(&IpSettings::disabled())
+            .build();
+        assert_eq!(settings["ipv6"].get("method"), Some(&Value::from("ignore")));
+    }
 }
@@ -0,0 +1,130 @@
+//! Mobile broadband (GSM/LTE) connection builder.
+//!
+//! NetworkManager drives the modem through ModemManager; the profile only
+//! carries the APN and optional credentials.
+
+use std::collections::HashMap;
+use zvariant::Value;
+
+use super::connection_builder::profile_uuid;
+use crate::ConnectionOptions;
+use crate::api::models::GsmCredentials;
+
+/// Builds a GSM connection settings dictionary for NetworkManager.
+///
+/// # Examples
+///
+/// ```rust
+/// use nmrs::builders::build_gsm_connection;
+/// use nmrs::{ConnectionOptions, GsmCredentials};
+///
+/// let creds = GsmCredentials::new("internet");
+/// let settings = build_gsm_connection("Carrier LTE", &creds, &ConnectionOptions::new(true));
+/// assert!(settings.contains_key("gsm"));
+/// ```
+#[must_use]
+pub fn build_gsm_connection(
+    name: &str,
+    creds: &GsmCredentials,
+    opts: &ConnectionOptions,
+) -> HashMap<&'static str, HashMap<&'static str, Value<'static>>> {
+    let mut conn: HashMap<&'static str, HashMap<&'static str, Value<'static>>> = HashMap::new();
+
+    conn.insert("connection", connection_section(name, opts));
+    conn.insert("gsm", gsm_section(creds));
+
+    let mut ipv4 = HashMap::new();
+    ipv4.insert("method", Value::from("auto"));
+    conn.insert("ipv4", ipv4);
+
+    let mut ipv6 = HashMap::new();
+    ipv6.insert("method", Value::from("auto"));
+    conn.insert("ipv6", ipv6);
+
+    conn
+}
+
+fn connection_section(
+    name: &str,
+    opts: &ConnectionOptions,
+) -> HashMap<&'static str, Value<'static>> {
+    let mut s = HashMap::new();
+    s.insert("type", Value::from("gsm"));
+    s.insert("id", Value::from(name.to_string()));
+    let uuid = opts
+        .uuid_namespace
+        .map(|ns| profile_uuid(&ns, "gsm", name))
+        .unwrap_or_else(uuid::Uuid::new_v4);
+    s.insert("uuid", Value::from(uuid.to_string()));
+    s.insert("autoconnect", Value::from(opts.autoconnect));
+
+    if let Some(p) = opts.autoconnect_priority {
+        s.insert("autoconnect-priority", Value::from(p));
+    }
+
+    if let Some(r) = opts.autoconnect_retries {
+        s.insert("autoconnect-retries", Value::from(r));
+    }
+
+    s
+}
+
+fn gsm_section(creds: &GsmCredentials) -> HashMap<&'static str, Value<'static>> {
+    let mut s = HashMap::new();
+    s.insert("apn", Value::from(creds.apn.clone()));
+    if let Some(user) = &creds.username {
+        s.insert("username", Value::from(user.clone()));
+    }
+    if let Some(password) = &creds.password {
+        s.insert("password", Value::from(password.clone()));
+    }
+    if let Some(pin) = &creds.pin {
+        s.insert("pin", Value::from(pin.clone()));
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apn_only() {
+        let settings = build_gsm_connection(
+            "LTE",
+            &GsmCredentials::new("internet"),
+            &ConnectionOptions::new(false),
+        );
+        assert_eq!(settings["connection"]["type"], Value::from("gsm"));
+        assert_eq!(settings["connection"]["autoconnect"], Value::from(false));
+        let gsm = &settings["gsm"];
+        assert_eq!(gsm["apn"], Value::from("internet"));
+        assert!(!gsm.contains_key("username"));
+        assert!(!gsm.contains_key("pin"));
+    }
+
+    #[test]
+    fn credentials_and_pin() {
+        let creds = GsmCredentials::new("corp.apn")
+            .with_username("user")
+            .with_password("secret")
+            .with_pin("0000");
+        let gsm = &build_gsm_connection("Corp", &creds, &ConnectionOptions::new(true))["gsm"];
+        assert_eq!(gsm["username"], Value::from("user"));
+        assert_eq!(gsm["password"], Value::from("secret"));
+        assert_eq!(gsm["pin"], Value::from("0000"));
+    }
+
+    #[test]
+    fn deterministic_uuid_with_namespace() {
+        let ns = uuid::Uuid::new_v4();
+        let opts = ConnectionOptions {
+            uuid_namespace: Some(ns),
+            ..ConnectionOptions::new(true)
+        };
+        let creds = GsmCredentials::new("internet");
+        let a = build_gsm_connection("LTE", &creds, &opts);
+        let b = build_gsm_connection("LTE", &creds, &opts);
+        assert_eq!(a["connection"]["uuid"], b["connection"]["uuid"]);
+    }
+}
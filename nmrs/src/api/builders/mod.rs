@@ -22,10 +22,13 @@
 //!
 //! # Free functions
 //!
-//! - [`build_wifi_connection`] / [`build_ethernet_connection`] (in [`wifi`])
+//! - [`build_wifi_connection`] / [`build_wifi_connection_with_options`] /
+//!   [`build_ethernet_connection`] / [`build_wired_connection`] (in [`wifi`])
 //! - [`build_wireguard_connection`] / [`build_openvpn_connection`] (in [`vpn`])
 //! - [`build_bluetooth_connection`] (in [`bluetooth`])
 //! - [`build_vlan_connection`] (in [`vlan`])
+//! - [`build_bridge_connection`] / [`build_bond_connection`] /
+//!   [`build_slave_connection`] (in [`bridge`])
 //!
 //! # When to use these
 //!
@@ -79,22 +82,31 @@
 //! `AddConnection` or `AddAndActivateConnection` D-Bus methods.
 
 pub mod bluetooth;
+pub mod bridge;
 pub mod connection_builder;
+pub mod gsm;
 pub mod openvpn_builder;
 pub mod vlan;
 pub mod vpn;
 pub mod wifi;
 pub mod wifi_builder;
+pub mod wifi_p2p;
 pub mod wireguard_builder;
 
 // Re-export core builder types
-pub use connection_builder::{ConnectionBuilder, IpConfig, Route};
+pub use connection_builder::{ConnectionBuilder, IpConfig, Route, profile_uuid};
 pub use openvpn_builder::OpenVpnBuilder;
-pub use wifi_builder::{WifiBand, WifiConnectionBuilder, WifiMode};
+pub use wifi_builder::{WifiBand, WifiConnectionBuilder, WifiMode, WpsMethod};
 pub use wireguard_builder::WireGuardBuilder;
 
 // Re-export builder functions for convenience
 pub use bluetooth::build_bluetooth_connection;
+pub use bridge::{build_bond_connection, build_bridge_connection, build_slave_connection};
+pub use gsm::build_gsm_connection;
 pub use vlan::build_vlan_connection;
-pub use vpn::{build_openvpn_connection, build_wireguard_connection};
-pub use wifi::{build_ethernet_connection, build_wifi_connection};
+pub use vpn::{build_ikev2_connection, build_openvpn_connection, build_wireguard_connection};
+pub use wifi::{
+    build_ethernet_connection, build_wifi_connection, build_wifi_connection_with_options,
+    build_wired_connection,
+};
+pub use wifi_p2p::build_wifi_p2p_connection;
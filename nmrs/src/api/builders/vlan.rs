@@ -6,6 +6,7 @@
 use std::collections::HashMap;
 use zvariant::Value;
 
+use super::connection_builder::profile_uuid;
 use crate::ConnectionOptions;
 use crate::api::models::{ConnectionError, VlanConfig};
 
@@ -70,8 +71,13 @@ fn connection_section(
     let mut s = HashMap::new();
 
     s.insert("type", Value::from("vlan"));
-    s.insert("id", Value::from(config.effective_connection_name()));
-    s.insert("uuid", Value::from(uuid::Uuid::new_v4().to_string()));
+    let id = config.effective_connection_name();
+    let uuid = opts
+        .uuid_namespace
+        .map(|ns| profile_uuid(&ns, "vlan", &id))
+        .unwrap_or_else(uuid::Uuid::new_v4);
+    s.insert("id", Value::from(id));
+    s.insert("uuid", Value::from(uuid.to_string()));
     s.insert("autoconnect", Value::from(opts.autoconnect));
     s.insert(
         "interface-name",
@@ -121,6 +127,13 @@ mod tests {
             autoconnect: true,
             autoconnect_priority: Some(10),
             autoconnect_retries: Some(3),
+            uuid_namespace: None,
+            trust_level: None,
+            ipv4: None,
+            ipv6: None,
+            mac_policy: None,
+            persistence: Default::default(),
+            activation_binding: Default::default(),
         }
     }
 
@@ -303,4 +316,18 @@ mod tests {
 
         assert_ne!(uuid1, uuid2, "UUIDs should be unique");
     }
+
+    #[test]
+    fn uuid_namespace_gives_stable_uuid() {
+        let config = VlanConfig::new("eth0", 100);
+        let opts = test_opts().with_uuid_namespace(uuid::Uuid::NAMESPACE_OID);
+
+        let conn1 = build_vlan_connection(&config, &opts).unwrap();
+        let conn2 = build_vlan_connection(&config, &opts).unwrap();
+
+        assert_eq!(
+            conn1["connection"].get("uuid"),
+            conn2["connection"].get("uuid")
+        );
+    }
 }
@@ -5,6 +5,7 @@
 //!
 //! - **WireGuard** — Modern, high-performance VPN protocol
 //! - **OpenVPN** — Widely-used open-source VPN protocol (via NM plugin)
+//! - **IKEv2** — IPsec/IKEv2 via the strongSwan NM plugin
 //!
 //! # Usage
 //!
@@ -68,10 +69,11 @@
 use std::collections::HashMap;
 use zvariant::{Dict, Value, signature};
 
+use super::connection_builder::profile_uuid;
 use super::wireguard_builder::WireGuardBuilder;
 use crate::api::models::{
-    ConnectionError, ConnectionOptions, OpenVpnAuthType, OpenVpnCompression, OpenVpnConfig,
-    OpenVpnProxy, VpnCredentials,
+    ConnectionError, ConnectionOptions, Ikev2Auth, Ikev2Config, OpenVpnAuthType,
+    OpenVpnCompression, OpenVpnConfig, OpenVpnProxy, VpnCredentials,
 };
 
 /// Builds WireGuard VPN connection settings.
@@ -177,7 +179,14 @@ pub fn build_openvpn_connection(
         ));
     }
 
-    let uuid = config.uuid.unwrap_or_else(uuid::Uuid::new_v4).to_string();
+    let uuid = config
+        .uuid
+        .or_else(|| {
+            opts.uuid_namespace
+                .map(|ns| profile_uuid(&ns, "vpn", &config.name))
+        })
+        .unwrap_or_else(uuid::Uuid::new_v4)
+        .to_string();
 
     let mut connection: HashMap<&'static str, Value<'static>> = HashMap::new();
     connection.insert("type", Value::from("vpn"));
@@ -378,6 +387,123 @@ pub fn build_openvpn_connection(
 
     Ok(settings)
 }
+/// Builds IKEv2 connection settings for the strongSwan NetworkManager plugin.
+///
+/// Like OpenVPN, this uses `connection.type = "vpn"`, here with
+/// `vpn.service-type = "org.freedesktop.NetworkManager.strongswan"`.
+/// The authentication method selects `vpn.data.method` (`psk`, `key` or
+/// `eap`). The PSK, key passphrase or EAP password is stored as the
+/// `password` secret when present.
+///
+/// # Errors
+///
+/// - `ConnectionError::InvalidGateway` if `gateway` is empty
+#[must_use = "the connection settings must be passed to NetworkManager"]
+pub fn build_ikev2_connection(
+    config: &Ikev2Config,
+    opts: &ConnectionOptions,
+) -> Result<HashMap<&'static str, HashMap<&'static str, Value<'static>>>, ConnectionError> {
+    if config.gateway.trim().is_empty() {
+        return Err(ConnectionError::InvalidGateway(
+            "IKEv2 gateway must not be empty".into(),
+        ));
+    }
+
+    let uuid = config
+        .uuid
+        .or_else(|| {
+            opts.uuid_namespace
+                .map(|ns| profile_uuid(&ns, "vpn", &config.name))
+        })
+        .unwrap_or_else(uuid::Uuid::new_v4)
+        .to_string();
+
+    let mut connection: HashMap<&'static str, Value<'static>> = HashMap::new();
+    connection.insert("type", Value::from("vpn"));
+    connection.insert("id", Value::from(config.name.clone()));
+    connection.insert("uuid", Value::from(uuid));
+    connection.insert("autoconnect", Value::from(opts.autoconnect));
+    if let Some(p) = opts.autoconnect_priority {
+        connection.insert("autoconnect-priority", Value::from(p));
+    }
+
+    let yes_no = |b: bool| if b { "yes" } else { "no" }.to_string();
+
+    let mut vpn_data: Vec<(String, String)> = vec![
+        ("address".into(), config.gateway.clone()),
+        ("virtual".into(), yes_no(config.virtual_ip)),
+        ("encap".into(), yes_no(config.udp_encapsulation)),
+        ("ipcomp".into(), yes_no(config.ipcomp)),
+    ];
+    push_opt_str(&mut vpn_data, "certificate", config.ca_cert.as_ref());
+    push_opt_str(
+        &mut vpn_data,
+        "remote-identity",
+        config.remote_identity.as_ref(),
+    );
+    if config.ike_proposal.is_some() || config.esp_proposal.is_some() {
+        vpn_data.push(("proposal".into(), "yes".into()));
+        push_opt_str(&mut vpn_data, "ike", config.ike_proposal.as_ref());
+        push_opt_str(&mut vpn_data, "esp", config.esp_proposal.as_ref());
+    }
+
+    let mut vpn_secrets: Vec<(String, String)> = Vec::new();
+    let mut identity = config.identity.clone();
+
+    match &config.auth {
+        Ikev2Auth::Psk { psk } => {
+            vpn_data.push(("method".into(), "psk".into()));
+            vpn_secrets.push(("password".into(), psk.clone()));
+        }
+        Ikev2Auth::Certificate {
+            cert,
+            key,
+            key_password,
+        } => {
+            vpn_data.push(("method".into(), "key".into()));
+            vpn_data.push(("usercert".into(), cert.clone()));
+            vpn_data.push(("userkey".into(), key.clone()));
+            push_opt_str(&mut vpn_secrets, "password", key_password.as_ref());
+        }
+        Ikev2Auth::Eap { username, password } => {
+            vpn_data.push(("method".into(), "eap".into()));
+            identity.get_or_insert_with(|| username.clone());
+            push_opt_str(&mut vpn_secrets, "password", password.as_ref());
+        }
+    }
+    push_opt_str(&mut vpn_data, "user", identity.as_ref());
+
+    let data_dict = string_pairs_to_dict(vpn_data)?;
+
+    let mut vpn: HashMap<&'static str, Value<'static>> = HashMap::new();
+    vpn.insert(
+        "service-type",
+        Value::from("org.freedesktop.NetworkManager.strongswan"),
+    );
+    vpn.insert("data", Value::from(data_dict));
+    if !vpn_secrets.is_empty() {
+        vpn.insert("secrets", Value::from(string_pairs_to_dict(vpn_secrets)?));
+    }
+
+    let mut ipv4: HashMap<&'static str, Value<'static>> = HashMap::new();
+    ipv4.insert("method", Value::from("auto"));
+    if let Some(dns) = &config.dns {
+        let dns_array: Vec<Value> = dns.iter().map(|s| Value::from(s.clone())).collect();
+        ipv4.insert("dns", Value::from(dns_array));
+    }
+
+    let mut ipv6: HashMap<&'static str, Value<'static>> = HashMap::new();
+    ipv6.insert("method", Value::from("ignore"));
+
+    let mut settings = HashMap::new();
+    settings.insert("connection", connection);
+    settings.insert("vpn", vpn);
+    settings.insert("ipv4", ipv4);
+    settings.insert("ipv6", ipv6);
+
+    Ok(settings)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1302,4 +1428,137 @@ mod tests {
         let ipv4 = settings.get("ipv4").unwrap();
         assert_eq!(ipv4.get("never-default"), Some(&Value::from(false)));
     }
+
+    fn create_ikev2_config(auth: Ikev2Auth) -> Ikev2Config {
+        Ikev2Config::new("WorkVPN", "vpn.example.com", auth)
+    }
+
+    fn get_vpn_secret_value(
+        settings: &HashMap<&str, HashMap<&str, Value>>,
+        key: &str,
+    ) -> Option<String> {
+        let Value::Dict(dict) = settings.get("vpn")?.get("secrets")? else {
+            return None;
+        };
+        dict.get::<Value, String>(&Value::from(key)).ok()?
+    }
+
+    #[test]
+    fn builds_ikev2_connection_with_strongswan_service() {
+        let config = create_ikev2_config(Ikev2Auth::Psk {
+            psk: "s3cret".into(),
+        });
+        let settings = build_ikev2_connection(&config, &create_test_options()).unwrap();
+
+        let connection = settings.get("connection").unwrap();
+        assert_eq!(connection.get("type"), Some(&Value::from("vpn")));
+        let vpn = settings.get("vpn").unwrap();
+        assert_eq!(
+            vpn.get("service-type"),
+            Some(&Value::from("org.freedesktop.NetworkManager.strongswan"))
+        );
+        assert_eq!(
+            get_vpn_data_value(&settings, "address").as_deref(),
+            Some("vpn.example.com")
+        );
+        assert_eq!(
+            get_vpn_data_value(&settings, "virtual").as_deref(),
+            Some("yes")
+        );
+    }
+
+    #[test]
+    fn ikev2_psk_auth() {
+        let config = create_ikev2_config(Ikev2Auth::Psk {
+            psk: "s3cret".into(),
+        })
+        .with_identity("client@example.com");
+        let settings = build_ikev2_connection(&config, &create_test_options()).unwrap();
+        assert_eq!(
+            get_vpn_data_value(&settings, "method").as_deref(),
+            Some("psk")
+        );
+        assert_eq!(
+            get_vpn_data_value(&settings, "user").as_deref(),
+            Some("client@example.com")
+        );
+        assert_eq!(
+            get_vpn_secret_value(&settings, "password").as_deref(),
+            Some("s3cret")
+        );
+    }
+
+    #[test]
+    fn ikev2_certificate_auth() {
+        let config = create_ikev2_config(Ikev2Auth::Certificate {
+            cert: "/etc/ipsec.d/certs/me.pem".into(),
+            key: "/etc/ipsec.d/private/me.pem".into(),
+            key_password: None,
+        })
+        .with_ca_cert("/etc/ipsec.d/cacerts/ca.pem");
+        let settings = build_ikev2_connection(&config, &create_test_options()).unwrap();
+        assert_eq!(
+            get_vpn_data_value(&settings, "method").as_deref(),
+            Some("key")
+        );
+        assert_eq!(
+            get_vpn_data_value(&settings, "usercert").as_deref(),
+            Some("/etc/ipsec.d/certs/me.pem")
+        );
+        assert_eq!(
+            get_vpn_data_value(&settings, "userkey").as_deref(),
+            Some("/etc/ipsec.d/private/me.pem")
+        );
+        assert_eq!(
+            get_vpn_data_value(&settings, "certificate").as_deref(),
+            Some("/etc/ipsec.d/cacerts/ca.pem")
+        );
+        assert!(settings.get("vpn").unwrap().get("secrets").is_none());
+    }
+
+    #[test]
+    fn ikev2_eap_uses_username_as_identity() {
+        let config = create_ikev2_config(Ikev2Auth::Eap {
+            username: "alice".into(),
+            password: Some("pw".into()),
+        });
+        let settings = build_ikev2_connection(&config, &create_test_options()).unwrap();
+        assert_eq!(
+            get_vpn_data_value(&settings, "method").as_deref(),
+            Some("eap")
+        );
+        assert_eq!(
+            get_vpn_data_value(&settings, "user").as_deref(),
+            Some("alice")
+        );
+        assert_eq!(
+            get_vpn_secret_value(&settings, "password").as_deref(),
+            Some("pw")
+        );
+    }
+
+    #[test]
+    fn ikev2_proposals_enable_custom_proposal() {
+        let config = create_ikev2_config(Ikev2Auth::Psk { psk: "k".into() })
+            .with_ike_proposal("aes256-sha256-modp2048");
+        let settings = build_ikev2_connection(&config, &create_test_options()).unwrap();
+        assert_eq!(
+            get_vpn_data_value(&settings, "proposal").as_deref(),
+            Some("yes")
+        );
+        assert_eq!(
+            get_vpn_data_value(&settings, "ike").as_deref(),
+            Some("aes256-sha256-modp2048")
+        );
+        assert!(get_vpn_data_value(&settings, "esp").is_none());
+    }
+
+    #[test]
+    fn ikev2_rejects_empty_gateway() {
+        let config = Ikev2Config::new("WorkVPN", " ", Ikev2Auth::Psk { psk: "k".into() });
+        assert!(matches!(
+            build_ikev2_connection(&config, &create_test_options()),
+            Err(ConnectionError::InvalidGateway(_))
+        ));
+    }
 }
@@ -63,18 +63,66 @@ pub fn build_wifi_connection(
     security: &models::WifiSecurity,
     opts: &ConnectionOptions,
 ) -> HashMap<&'static str, HashMap<&'static str, Value<'static>>> {
-    let mut builder = WifiConnectionBuilder::new(ssid)
+    wifi_builder(ssid, security, opts).build()
+}
+
+/// Builds a Wi-Fi profile like [`build_wifi_connection`], restricted to the
+/// band and channel in `wifi`.
+///
+/// # Errors
+///
+/// Returns `ConnectionError::InvalidInput` if `wifi` fails
+/// [`WifiOptions::validate`](models::WifiOptions::validate).
+///
+/// # Examples
+///
+/// ```rust
+/// use nmrs::builders::{WifiBand, build_wifi_connection_with_options};
+/// use nmrs::{ConnectionOptions, WifiOptions, WifiSecurity};
+///
+/// let settings = build_wifi_connection_with_options(
+///     "HomeWiFi",
+///     &WifiSecurity::Open,
+///     &ConnectionOptions::new(true),
+///     &WifiOptions::new().with_band(WifiBand::A),
+/// )
+/// .unwrap();
+/// assert!(settings["802-11-wireless"].contains_key("band"));
+/// ```
+pub fn build_wifi_connection_with_options(
+    ssid: &str,
+    security: &models::WifiSecurity,
+    opts: &ConnectionOptions,
+    wifi: &models::WifiOptions,
+) -> Result<HashMap<&'static str, HashMap<&'static str, Value<'static>>>, models::ConnectionError> {
+    wifi.validate()?;
+
+    let mut builder = wifi_builder(ssid, security, opts);
+    if let Some(band) = wifi.band {
+        builder = builder.band(band);
+    }
+    if let Some(channel) = wifi.channel {
+        builder = builder.channel(channel);
+    }
+    Ok(builder.build())
+}
+
+/// Infrastructure-mode builder with `security`, `opts` and automatic IP.
+fn wifi_builder(
+    ssid: &str,
+    security: &models::WifiSecurity,
+    opts: &ConnectionOptions,
+) -> WifiConnectionBuilder {
+    let builder = WifiConnectionBuilder::new(ssid)
         .options(opts)
         .ipv4_auto()
         .ipv6_auto();
 
-    builder = match security {
+    match security {
         models::WifiSecurity::Open => builder.open(),
         models::WifiSecurity::WpaPsk { psk } => builder.wpa_psk(psk),
         models::WifiSecurity::WpaEap { opts } => builder.wpa_eap(opts.clone()),
-    };
-
-    builder.build()
+    }
 }
 
 /// Builds a complete Ethernet connection settings dictionary.
@@ -107,6 +155,76 @@ pub fn build_ethernet_connection(
         .build()
 }
 
+/// Builds an Ethernet profile bound to `interface` with `wired` applied.
+///
+/// The profile is named after the interface. IP settings in `wired` take
+/// precedence over those in `opts`; a cloned MAC in `wired` overrides the
+/// MAC policy of a [`TrustLevel`](crate::TrustLevel). A fixed link mode
+/// turns off auto-negotiation.
+///
+/// # Errors
+///
+/// Returns `ConnectionError::InvalidInput` if `wired` fails
+/// [`WiredOptions::validate`](models::WiredOptions::validate), or
+/// `ConnectionError::InvalidBssid` for an invalid explicit
+/// [`MacPolicy`](models::MacPolicy) in `opts`.
+///
+/// # Examples
+///
+/// ```rust
+/// use nmrs::builders::build_wired_connection;
+/// use nmrs::{ConnectionOptions, WiredOptions};
+///
+/// let settings = build_wired_connection(
+///     "enp3s0",
+///     &ConnectionOptions::new(true),
+///     &WiredOptions::new().with_mtu(9000),
+/// )
+/// .unwrap();
+/// assert!(settings.contains_key("802-3-ethernet"));
+/// ```
+pub fn build_wired_connection(
+    interface: &str,
+    opts: &ConnectionOptions,
+    wired: &models::WiredOptions,
+) -> Result<HashMap<&'static str, HashMap<&'static str, Value<'static>>>, models::ConnectionError> {
+    wired.validate()?;
+    if let Some(policy) = &opts.mac_policy {
+        policy.validate()?;
+    }
+
+    let mut opts = opts.clone();
+    if wired.ipv4.is_some() {
+        opts.ipv4.clone_from(&wired.ipv4);
+    }
+    if wired.ipv6.is_some() {
+        opts.ipv6.clone_from(&wired.ipv6);
+    }
+
+    let mut settings = ConnectionBuilder::new("802-3-ethernet", interface)
+        .options(&opts)
+        .interface_name(interface)
+        .with_section("802-3-ethernet", HashMap::new())
+        .ipv4_auto()
+        .ipv6_auto()
+        .build();
+
+    let ethernet = settings.entry("802-3-ethernet").or_default();
+    if let Some(mtu) = wired.mtu {
+        ethernet.insert("mtu", Value::from(mtu));
+    }
+    if let Some(mac) = &wired.cloned_mac {
+        ethernet.insert("cloned-mac-address", Value::from(mac.clone()));
+    }
+    if let (Some(speed), Some(duplex)) = (wired.speed, wired.duplex) {
+        ethernet.insert("auto-negotiate", Value::from(false));
+        ethernet.insert("speed", Value::from(speed));
+        ethernet.insert("duplex", Value::from(duplex.as_str()));
+    }
+
+    Ok(settings)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -118,6 +236,13 @@ mod tests {
             autoconnect: true,
             autoconnect_priority: None,
             autoconnect_retries: None,
+            uuid_namespace: None,
+            trust_level: None,
+            ipv4: None,
+            ipv6: None,
+            mac_policy: None,
+            persistence: Default::default(),
+            activation_binding: Default::default(),
         }
     }
 
@@ -126,6 +251,13 @@ mod tests {
             autoconnect: false,
             autoconnect_priority: Some(10),
             autoconnect_retries: Some(3),
+            uuid_namespace: None,
+            trust_level: None,
+            ipv4: None,
+            ipv6: None,
+            mac_policy: None,
+            persistence: Default::default(),
+            activation_binding: Default::default(),
         }
     }
 
@@ -191,6 +323,11 @@ mod tests {
             domain_suffix_match: Some("example.com".into()),
             ca_cert_path: None,
             system_ca_certs: true,
+            client_cert_path: None,
+            private_key_path: None,
+            private_key_password: None,
+            fast_provisioning: None,
+            pac_file: None,
             method: EapMethod::Peap,
             phase2: Phase2::Mschapv2,
         };
@@ -228,6 +365,11 @@ mod tests {
             domain_suffix_match: None,
             ca_cert_path: Some("file:///etc/ssl/certs/ca.pem".into()),
             system_ca_certs: false,
+            client_cert_path: None,
+            private_key_path: None,
+            private_key_password: None,
+            fast_provisioning: None,
+            pac_file: None,
             method: EapMethod::Ttls,
             phase2: Phase2::Pap,
         };
@@ -296,4 +438,72 @@ mod tests {
         let ssid = wireless.get("ssid").unwrap();
         assert_eq!(ssid, &Value::from("Café-Wïfì_123".as_bytes().to_vec()));
     }
+
+    #[test]
+    fn wifi_connection_with_options_sets_band_and_channel() {
+        let wifi = models::WifiOptions::new()
+            .with_band(crate::builders::WifiBand::Bg)
+            .with_channel(11);
+        let conn = build_wifi_connection_with_options(
+            "Home",
+            &WifiSecurity::WpaPsk {
+                psk: "secret123".into(),
+            },
+            &default_opts(),
+            &wifi,
+        )
+        .unwrap();
+
+        let wireless = conn.get("802-11-wireless").unwrap();
+        assert_eq!(wireless.get("band"), Some(&Value::from("bg")));
+        assert_eq!(wireless.get("channel"), Some(&Value::from(11u32)));
+        assert!(conn.contains_key("802-11-wireless-security"));
+
+        let no_band = models::WifiOptions::new().with_channel(11);
+        assert!(
+            build_wifi_connection_with_options(
+                "Home",
+                &WifiSecurity::Open,
+                &default_opts(),
+                &no_band
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn wired_connection_applies_link_options() {
+        let wired = models::WiredOptions::new()
+            .with_ipv4(models::IpSettings::manual(vec![
+                crate::builders::IpConfig::new("10.0.0.10", 24),
+            ]))
+            .with_mtu(9000)
+            .with_cloned_mac("stable")
+            .with_link_mode(1000, models::Duplex::Full);
+        let conn = build_wired_connection("enp3s0", &default_opts(), &wired).unwrap();
+
+        let connection = conn.get("connection").unwrap();
+        assert_eq!(connection.get("id"), Some(&Value::from("enp3s0")));
+        assert_eq!(
+            connection.get("interface-name"),
+            Some(&Value::from("enp3s0"))
+        );
+        let ethernet = conn.get("802-3-ethernet").unwrap();
+        assert_eq!(ethernet.get("mtu"), Some(&Value::from(9000u32)));
+        assert_eq!(
+            ethernet.get("cloned-mac-address"),
+            Some(&Value::from("stable"))
+        );
+        assert_eq!(ethernet.get("auto-negotiate"), Some(&Value::from(false)));
+        assert_eq!(ethernet.get("speed"), Some(&Value::from(1000u32)));
+        assert_eq!(ethernet.get("duplex"), Some(&Value::from("full")));
+        let ipv4 = conn.get("ipv4").unwrap();
+        assert_eq!(ipv4.get("method"), Some(&Value::from("manual")));
+    }
+
+    #[test]
+    fn wired_connection_rejects_invalid_options() {
+        let wired = models::WiredOptions::new().with_cloned_mac("nonsense");
+        assert!(build_wired_connection("eth0", &default_opts(), &wired).is_err());
+    }
 }
@@ -48,6 +48,27 @@ impl WifiMode {
     }
 }
 
+/// How a WPS-provisioned profile obtains its key.
+///
+/// Written to `802-11-wireless-security.wps-method`.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WpsMethod {
+    /// The user presses the WPS button on the access point.
+    PushButton,
+    /// The user enters a PIN on the access point (or its label PIN is used).
+    Pin,
+}
+
+impl WpsMethod {
+    fn as_nm_flag(self) -> u32 {
+        match self {
+            Self::PushButton => 0x4,
+            Self::Pin => 0x8,
+        }
+    }
+}
+
 /// Builder for WiFi (802.11) connections.
 ///
 /// This builder provides a type-safe, ergonomic API for creating WiFi connection
@@ -115,6 +136,7 @@ pub struct WifiConnectionBuilder {
     security_configured: bool,
     hidden: Option<bool>,
     band: Option<WifiBand>,
+    channel: Option<u16>,
     bssid: Option<String>,
 }
 
@@ -135,6 +157,7 @@ impl WifiConnectionBuilder {
             security_configured: false,
             hidden: None,
             band: None,
+            channel: None,
             bssid: None,
         }
     }
@@ -171,9 +194,28 @@ impl WifiConnectionBuilder {
         self
     }
 
+    /// Configures WPA-PSK security whose key is obtained through WPS.
+    ///
+    /// No passphrase is stored: NetworkManager runs WPS enrollment with the
+    /// access point and saves the credentials it receives into the profile.
+    #[must_use]
+    pub fn wps(mut self, method: WpsMethod) -> Self {
+        let mut security = HashMap::new();
+        security.insert("key-mgmt", Value::from("wpa-psk"));
+        security.insert("auth-alg", Value::from("open"));
+        security.insert("wps-method", Value::from(method.as_nm_flag()));
+
+        self.inner = self
+            .inner
+            .with_section("802-11-wireless-security", security);
+        self.security_configured = true;
+        self
+    }
+
     /// Configures WPA-EAP (Enterprise) security with 802.1X authentication.
     ///
-    /// Supports PEAP and TTLS methods with various inner authentication protocols.
+    /// Supports PEAP, TTLS and FAST with various inner authentication
+    /// protocols, password-only PWD, and certificate-based TLS.
     #[must_use]
     pub fn wpa_eap(mut self, opts: models::EapOptions) -> Self {
         let mut security = HashMap::new();
@@ -190,20 +232,51 @@ impl WifiConnectionBuilder {
         let eap_str = match opts.method {
             EapMethod::Peap => "peap",
             EapMethod::Ttls => "ttls",
+            EapMethod::Tls => "tls",
+            EapMethod::Pwd => "pwd",
+            EapMethod::Fast => "fast",
         };
         e1x.insert("eap", Self::string_array(&[eap_str]));
         e1x.insert("identity", Value::from(opts.identity));
-        e1x.insert("password", Value::from(opts.password));
 
         if let Some(ai) = opts.anonymous_identity {
             e1x.insert("anonymous-identity", Value::from(ai));
         }
 
-        let p2 = match opts.phase2 {
-            models::Phase2::Mschapv2 => "mschapv2",
-            models::Phase2::Pap => "pap",
-        };
-        e1x.insert("phase2-auth", Value::from(p2));
+        if opts.method == EapMethod::Tls {
+            if let Some(cert) = opts.client_cert_path {
+                e1x.insert("client-cert", Self::cert_path(&cert));
+            }
+            if let Some(key) = opts.private_key_path {
+                e1x.insert("private-key", Self::cert_path(&key));
+            }
+            if let Some(password) = opts.private_key_password {
+                e1x.insert("private-key-password", Value::from(password));
+            }
+        } else {
+            e1x.insert("password", Value::from(opts.password));
+        }
+
+        if opts.method.uses_phase2() {
+            let p2 = match opts.phase2 {
+                models::Phase2::Mschapv2 => "mschapv2",
+                models::Phase2::Pap => "pap",
+                models::Phase2::Gtc => "gtc",
+            };
+            e1x.insert("phase2-auth", Value::from(p2));
+        }
+
+        if opts.method == EapMethod::Fast {
+            if let Some(provisioning) = opts.fast_provisioning {
+                e1x.insert(
+                    "phase1-fast-provisioning",
+                    Value::from(provisioning.as_nm_str()),
+                );
+            }
+            if let Some(pac) = opts.pac_file {
+                e1x.insert("pac-file", Value::from(pac));
+            }
+        }
 
         if opts.system_ca_certs {
             e1x.insert("system-ca-certs", Value::from(true));
@@ -234,6 +307,16 @@ impl WifiConnectionBuilder {
         self
     }
 
+    /// Restricts connection to a specific channel of the configured band.
+    ///
+    /// NetworkManager only honours the channel together with
+    /// [`band`](Self::band).
+    #[must_use]
+    pub fn channel(mut self, channel: u16) -> Self {
+        self.channel = Some(channel);
+        self
+    }
+
     /// Restricts connection to a specific access point by BSSID (MAC address).
     ///
     /// Format: "00:11:22:33:44:55"
@@ -274,6 +357,13 @@ impl WifiConnectionBuilder {
         self
     }
 
+    /// Restricts the connection to one interface (e.g. `"wlan0"`).
+    #[must_use]
+    pub fn interface_name(mut self, name: impl Into<String>) -> Self {
+        self.inner = self.inner.interface_name(name);
+        self
+    }
+
     /// Enables or disables automatic connection.
     #[must_use]
     pub fn autoconnect(mut self, enabled: bool) -> Self {
@@ -350,6 +440,10 @@ impl WifiConnectionBuilder {
             wireless.insert("band", Value::from(band_str));
         }
 
+        if let Some(channel) = self.channel {
+            wireless.insert("channel", Value::from(u32::from(channel)));
+        }
+
         if let Some(bssid) = self.bssid {
             wireless.insert("bssid", Value::from(bssid));
         }
@@ -372,6 +466,14 @@ impl WifiConnectionBuilder {
         let vals: Vec<String> = xs.iter().map(|s| s.to_string()).collect();
         Value::from(vals)
     }
+
+    /// Encodes a `file://` certificate or key path as NM's NUL-terminated
+    /// byte array (`ay`).
+    fn cert_path(path: &str) -> Value<'static> {
+        let mut bytes = path.as_bytes().to_vec();
+        bytes.push(0);
+        Value::from(bytes)
+    }
 }
 
 #[cfg(test)]
@@ -401,6 +503,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn builds_wps_wifi() {
+        let settings = WifiConnectionBuilder::new("Printer")
+            .wps(WpsMethod::PushButton)
+            .ipv4_auto()
+            .build();
+
+        let security = settings.get("802-11-wireless-security").unwrap();
+        assert_eq!(security.get("key-mgmt"), Some(&Value::from("wpa-psk")));
+        assert_eq!(security.get("wps-method"), Some(&Value::from(0x4u32)));
+        assert!(!security.contains_key("psk"));
+
+        let wireless = settings.get("802-11-wireless").unwrap();
+        assert_eq!(
+            wireless.get("security"),
+            Some(&Value::from("802-11-wireless-security"))
+        );
+    }
+
     #[test]
     fn builds_wpa_psk_wifi() {
         let settings = WifiConnectionBuilder::new("SecureNet")
@@ -440,6 +561,11 @@ mod tests {
             domain_suffix_match: Some("example.com".into()),
             ca_cert_path: None,
             system_ca_certs: true,
+            client_cert_path: None,
+            private_key_path: None,
+            private_key_password: None,
+            fast_provisioning: None,
+            pac_file: None,
             method: EapMethod::Peap,
             phase2: Phase2::Mschapv2,
         };
@@ -465,6 +591,59 @@ mod tests {
         assert_eq!(e1x.get("phase2-auth"), Some(&Value::from("mschapv2")));
     }
 
+    #[test]
+    fn builds_eap_tls_wifi() {
+        let eap_opts = EapOptions::tls(
+            "host/laptop",
+            "file:///etc/pki/laptop.crt",
+            "file:///etc/pki/laptop.key",
+        )
+        .with_private_key_password("hunter2");
+
+        let settings = WifiConnectionBuilder::new("Corp").wpa_eap(eap_opts).build();
+
+        let e1x = settings.get("802-1x").unwrap();
+        assert_eq!(e1x.get("eap"), Some(&Value::from(vec!["tls".to_string()])));
+        assert_eq!(
+            e1x.get("client-cert"),
+            Some(&Value::from(b"file:///etc/pki/laptop.crt\0".to_vec()))
+        );
+        assert_eq!(
+            e1x.get("private-key"),
+            Some(&Value::from(b"file:///etc/pki/laptop.key\0".to_vec()))
+        );
+        assert_eq!(
+            e1x.get("private-key-password"),
+            Some(&Value::from("hunter2".to_string()))
+        );
+        assert!(e1x.get("password").is_none());
+        assert!(e1x.get("phase2-auth").is_none());
+    }
+
+    #[test]
+    fn builds_eap_fast_and_pwd_wifi() {
+        let fast = EapOptions::new("jdoe", "token")
+            .with_method(EapMethod::Fast)
+            .with_phase2(Phase2::Gtc)
+            .with_fast_provisioning(models::FastProvisioning::Both)
+            .with_pac_file("/var/lib/corp.pac");
+        let settings = WifiConnectionBuilder::new("Corp").wpa_eap(fast).build();
+        let e1x = settings.get("802-1x").unwrap();
+        assert_eq!(e1x.get("phase2-auth"), Some(&Value::from("gtc")));
+        assert_eq!(e1x.get("phase1-fast-provisioning"), Some(&Value::from("3")));
+        assert_eq!(
+            e1x.get("pac-file"),
+            Some(&Value::from("/var/lib/corp.pac".to_string()))
+        );
+
+        let pwd = EapOptions::new("student", "secret").with_method(EapMethod::Pwd);
+        let settings = WifiConnectionBuilder::new("eduroam").wpa_eap(pwd).build();
+        let e1x = settings.get("802-1x").unwrap();
+        assert_eq!(e1x.get("eap"), Some(&Value::from(vec!["pwd".to_string()])));
+        assert!(e1x.get("phase2-auth").is_none());
+        assert!(e1x.get("pac-file").is_none());
+    }
+
     #[test]
     fn configures_hidden_network() {
         let settings = WifiConnectionBuilder::new("HiddenSSID")
@@ -489,6 +668,19 @@ mod tests {
         assert_eq!(wireless.get("band"), Some(&Value::from("a")));
     }
 
+    #[test]
+    fn configures_specific_channel() {
+        let settings = WifiConnectionBuilder::new("Lab")
+            .open()
+            .band(WifiBand::A)
+            .channel(149)
+            .build();
+
+        let wireless = settings.get("802-11-wireless").unwrap();
+        assert_eq!(wireless.get("band"), Some(&Value::from("a")));
+        assert_eq!(wireless.get("channel"), Some(&Value::from(149u32)));
+    }
+
     #[test]
     fn configures_bssid() {
         let settings = WifiConnectionBuilder::new("SpecificAP")
@@ -552,6 +744,13 @@ mod tests {
             autoconnect: false,
             autoconnect_priority: Some(5),
             autoconnect_retries: Some(3),
+            uuid_namespace: None,
+            trust_level: None,
+            ipv4: None,
+            ipv6: None,
+            mac_policy: None,
+            persistence: Default::default(),
+            activation_binding: Default::default(),
         };
 
         let settings = WifiConnectionBuilder::new("TestNet")
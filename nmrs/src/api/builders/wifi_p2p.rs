@@ -0,0 +1,76 @@
+//! Wi-Fi Direct (P2P) connection builder.
+
+use std::collections::HashMap;
+use zvariant::Value;
+
+use super::connection_builder::ConnectionBuilder;
+use crate::ConnectionOptions;
+
+/// Builds a `wifi-p2p` connection settings dictionary for NetworkManager.
+///
+/// `peer` is the peer's MAC address. `wfd_ies` carries Wi-Fi Display
+/// information elements to advertise, e.g. for screen casting.
+///
+/// # Examples
+///
+/// ```rust
+/// use nmrs::builders::build_wifi_p2p_connection;
+/// use nmrs::ConnectionOptions;
+///
+/// let settings = build_wifi_p2p_connection(
+///     "TV",
+///     "aa:bb:cc:dd:ee:ff",
+///     None,
+///     &ConnectionOptions::new(false),
+/// );
+/// assert!(settings.contains_key("wifi-p2p"));
+/// ```
+#[must_use]
+pub fn build_wifi_p2p_connection(
+    connection_id: &str,
+    peer: &str,
+    wfd_ies: Option<&[u8]>,
+    opts: &ConnectionOptions,
+) -> HashMap<&'static str, HashMap<&'static str, Value<'static>>> {
+    let mut p2p = HashMap::new();
+    p2p.insert("peer", Value::from(peer.to_string()));
+    if let Some(ies) = wfd_ies.filter(|ies| !ies.is_empty()) {
+        p2p.insert("wfd-ies", Value::from(ies.to_vec()));
+    }
+
+    ConnectionBuilder::new("wifi-p2p", connection_id)
+        .options(opts)
+        .with_section("wifi-p2p", p2p)
+        .ipv4_auto()
+        .ipv6_auto()
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn peer_and_type() {
+        let s = build_wifi_p2p_connection(
+            "TV",
+            "aa:bb:cc:dd:ee:ff",
+            None,
+            &ConnectionOptions::new(false),
+        );
+        assert_eq!(s["connection"]["type"], Value::from("wifi-p2p"));
+        assert_eq!(s["wifi-p2p"]["peer"], Value::from("aa:bb:cc:dd:ee:ff"));
+        assert!(!s["wifi-p2p"].contains_key("wfd-ies"));
+    }
+
+    #[test]
+    fn wfd_ies_included_when_present() {
+        let s = build_wifi_p2p_connection(
+            "TV",
+            "aa:bb:cc:dd:ee:ff",
+            Some(&[0, 0, 6, 0, 0x11]),
+            &ConnectionOptions::new(false),
+        );
+        assert!(s["wifi-p2p"].contains_key("wfd-ies"));
+    }
+}
@@ -46,6 +46,7 @@ pub struct WireGuardBuilder {
     dns: Option<Vec<String>>,
     mtu: Option<u32>,
     uuid: Option<Uuid>,
+    uuid_namespace: Option<Uuid>,
 }
 
 impl WireGuardBuilder {
@@ -68,6 +69,7 @@ impl WireGuardBuilder {
             dns: None,
             mtu: None,
             uuid: None,
+            uuid_namespace: None,
         }
     }
 
@@ -145,12 +147,25 @@ impl WireGuardBuilder {
         self
     }
 
+    /// Sets the namespace used to derive the UUID when none is given.
+    ///
+    /// Defaults to [`Uuid::NAMESPACE_DNS`]. The UUID is a v5 hash of
+    /// `"wg:{name}"` in this namespace.
+    #[must_use]
+    pub fn uuid_namespace(mut self, namespace: Uuid) -> Self {
+        self.uuid_namespace = Some(namespace);
+        self
+    }
+
     // Delegation methods to inner ConnectionBuilder
 
     /// Applies connection options.
     #[must_use]
     pub fn options(mut self, opts: &ConnectionOptions) -> Self {
         self.inner = self.inner.options(opts);
+        if let Some(namespace) = opts.uuid_namespace {
+            self.uuid_namespace = Some(namespace);
+        }
         self
     }
 
@@ -238,7 +253,8 @@ impl WireGuardBuilder {
         // Set UUID (deterministic or provided)
         let uuid = self.uuid.unwrap_or_else(|| {
             // Generate deterministic UUID based on name
-            Uuid::new_v5(&Uuid::NAMESPACE_DNS, format!("wg:{}", self.name).as_bytes())
+            let namespace = self.uuid_namespace.unwrap_or(Uuid::NAMESPACE_DNS);
+            Uuid::new_v5(&namespace, format!("wg:{}", self.name).as_bytes())
         });
 
         self.inner = self.inner.uuid(uuid);
@@ -246,29 +262,7 @@ impl WireGuardBuilder {
         // Build wireguard section
         let mut wireguard = HashMap::new();
         wireguard.insert("private-key", Value::from(private_key));
-
-        // Build peers array
-        let mut peers_array: Vec<HashMap<String, zvariant::Value<'static>>> = Vec::new();
-
-        for peer in self.peers {
-            let mut peer_dict: HashMap<String, zvariant::Value<'static>> = HashMap::new();
-
-            peer_dict.insert("public-key".into(), Value::from(peer.public_key));
-            peer_dict.insert("endpoint".into(), Value::from(peer.gateway));
-            peer_dict.insert("allowed-ips".into(), Value::from(peer.allowed_ips));
-
-            if let Some(psk) = peer.preshared_key {
-                peer_dict.insert("preshared-key".into(), Value::from(psk));
-            }
-
-            if let Some(ka) = peer.persistent_keepalive {
-                peer_dict.insert("persistent-keepalive".into(), Value::from(ka));
-            }
-
-            peers_array.push(peer_dict);
-        }
-
-        wireguard.insert("peers", Value::from(peers_array));
+        wireguard.insert("peers", encode_peers(self.peers));
 
         if let Some(mtu) = self.mtu {
             wireguard.insert("mtu", Value::from(mtu));
@@ -310,6 +304,31 @@ impl WireGuardBuilder {
     }
 }
 
+/// Encodes `peers` as the `aa{sv}` value of `wireguard.peers`.
+pub(crate) fn encode_peers(peers: Vec<WireGuardPeer>) -> Value<'static> {
+    let mut peers_array: Vec<HashMap<String, Value<'static>>> = Vec::new();
+
+    for peer in peers {
+        let mut peer_dict: HashMap<String, Value<'static>> = HashMap::new();
+
+        peer_dict.insert("public-key".into(), Value::from(peer.public_key));
+        peer_dict.insert("endpoint".into(), Value::from(peer.gateway));
+        peer_dict.insert("allowed-ips".into(), Value::from(peer.allowed_ips));
+
+        if let Some(psk) = peer.preshared_key {
+            peer_dict.insert("preshared-key".into(), Value::from(psk));
+        }
+
+        if let Some(ka) = peer.persistent_keepalive {
+            peer_dict.insert("persistent-keepalive".into(), Value::from(ka));
+        }
+
+        peers_array.push(peer_dict);
+    }
+
+    Value::from(peers_array)
+}
+
 // Validation functions (same as in vpn.rs)
 
 fn validate_wireguard_key(key: &str, key_type: &str) -> Result<(), ConnectionError> {
@@ -558,4 +577,37 @@ mod tests {
 
         assert!(settings.contains_key("wireguard"));
     }
+
+    #[test]
+    fn uuid_namespace_changes_derived_uuid() {
+        let build = |builder: WireGuardBuilder| {
+            builder
+                .private_key("YBk6X3pP8KjKz7+HFWzVHNqL3qTZq8hX9VxFQJ4zVmM=")
+                .address("10.0.0.2/24")
+                .add_peer(create_test_peer())
+                .build()
+                .expect("Failed to build")
+        };
+
+        let default_ns = build(WireGuardBuilder::new("TestVPN"));
+        let custom_ns = build(WireGuardBuilder::new("TestVPN").uuid_namespace(Uuid::NAMESPACE_OID));
+        let via_opts = build(
+            WireGuardBuilder::new("TestVPN")
+                .options(&ConnectionOptions::new(false).with_uuid_namespace(Uuid::NAMESPACE_OID)),
+        );
+
+        let expected = Uuid::new_v5(&Uuid::NAMESPACE_OID, b"wg:TestVPN").to_string();
+        assert_eq!(
+            custom_ns["connection"].get("uuid"),
+            Some(&Value::from(expected))
+        );
+        assert_eq!(
+            custom_ns["connection"].get("uuid"),
+            via_opts["connection"].get("uuid")
+        );
+        assert_ne!(
+            default_ns["connection"].get("uuid"),
+            custom_ns["connection"].get("uuid")
+        );
+    }
 }
@@ -0,0 +1,82 @@
+//! Cancellable connection attempts.
+//!
+//! A cancellable connect returns a [`ConnectHandle`] together with the
+//! attempt itself, a future the caller drives on any executor. Cancelling
+//! through the handle aborts the attempt cleanly: the pending activation is
+//! deactivated and a profile created for the attempt is deleted, instead of
+//! being left behind as when the future is simply dropped.
+//!
+//! ```no_run
+//! use nmrs::{ConnectionError, NetworkManager, WifiSecurity};
+//!
+//! # async fn example() -> nmrs::Result<()> {
+//! let nm = NetworkManager::new().await?;
+//! let (handle, attempt) = nm.connect_cancellable(
+//!     "CafeWiFi",
+//!     None,
+//!     WifiSecurity::WpaPsk { psk: "password".into() },
+//! );
+//!
+//! // e.g. from a "Cancel" button
+//! handle.cancel();
+//!
+//! match attempt.await {
+//!     Err(ConnectionError::Cancelled) => println!("aborted"),
+//!     other => other?,
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::future::Future;
+
+use tokio::sync::watch;
+use zbus::Connection;
+
+use crate::Result;
+use crate::core::cancel::cancellable;
+
+/// Cancels a connection attempt.
+///
+/// Returned by [`NetworkManager::connect_cancellable`](crate::NetworkManager::connect_cancellable)
+/// and [`NetworkManager::connect_vpn_cancellable`](crate::NetworkManager::connect_vpn_cancellable)
+/// next to the attempt's future. Dropping the handle does not cancel the
+/// attempt; it just can no longer be cancelled.
+#[derive(Debug)]
+pub struct ConnectHandle {
+    cancel: watch::Sender<bool>,
+}
+
+impl ConnectHandle {
+    /// Wraps `connect` so that it rolls back profile `id` if cancelled.
+    pub(crate) fn new<F>(
+        conn: Connection,
+        id: String,
+        connect: F,
+    ) -> (Self, impl Future<Output = Result<()>> + Send + 'static)
+    where
+        F: Future<Output = Result<()>> + Send + 'static,
+    {
+        let (cancel, mut cancelled) = watch::channel(false);
+        let attempt = async move {
+            let signal = async move {
+                // A dropped handle can no longer cancel.
+                if cancelled.wait_for(|c| *c).await.is_err() {
+                    std::future::pending::<()>().await;
+                }
+            };
+            cancellable(&conn, &id, connect, signal).await
+        };
+        (Self { cancel }, attempt)
+    }
+
+    /// Requests cancellation.
+    ///
+    /// Has no effect once the attempt has finished. The attempt's future
+    /// resolves to [`ConnectionError::Cancelled`](crate::ConnectionError::Cancelled)
+    /// after the rollback completes. A connection that was disconnected to
+    /// make room for this one is not restored.
+    pub fn cancel(&self) {
+        self.cancel.send_replace(true);
+    }
+}
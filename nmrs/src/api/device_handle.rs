@@ -0,0 +1,319 @@
+//! Rename-safe references to a network device.
+//!
+//! Interface names are not stable: predictable-name policies, udev rules
+//! and USB re-plugs can turn `wlan0` into `wlx00c0ca123456`, and a re-plugged
+//! adapter comes back under a new D-Bus object path. [`DeviceHandle`] pins
+//! a device by its permanent MAC instead, follows it across
+//! `DeviceRemoved`/`DeviceAdded` signals, and re-resolves the current
+//! interface name whenever it is used:
+//!
+//! ```no_run
+//! use nmrs::NetworkManager;
+//!
+//! # async fn example() -> nmrs::Result<()> {
+//! let nm = NetworkManager::new().await?;
+//! let dongle = nm.device_handle("wlan1").await?;
+//!
+//! // Still works after the adapter is re-plugged as wlan2.
+//! let stats = nm.traffic_stats(&dongle).await?;
+//! println!("rx {} B", stats.rx_bytes);
+//! nm.wifi_device(&dongle).scan().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::borrow::Cow;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use futures::{FutureExt, Stream, StreamExt, future};
+use log::debug;
+use zbus::Connection;
+use zvariant::OwnedObjectPath;
+
+use crate::Result;
+use crate::api::models::{ConnectionError, DeviceIdentity};
+use crate::core::connection::get_device_by_interface;
+use crate::core::device::{device_binding, find_binding_by_identity};
+use crate::dbus::NMProxy;
+
+type PathStream = Pin<Box<dyn Stream<Item = OwnedObjectPath> + Send>>;
+
+/// A network device tracked by hardware identity rather than by name.
+///
+/// Created via [`NetworkManager::device_handle`](crate::NetworkManager::device_handle).
+/// Pass `&handle` wherever a method accepts a [`DeviceRef`], or scope Wi-Fi
+/// operations to it with
+/// [`NetworkManager::wifi_device`](crate::NetworkManager::wifi_device).
+///
+/// Each use checks that the last known object path still belongs to the
+/// device and looks it up by permanent MAC if not, so operations follow the
+/// device across interface renames and re-plugs. `DeviceRemoved` and
+/// `DeviceAdded` signals received since the last use are applied first, so
+/// a re-plugged device is usually found without a full lookup. No task is
+/// spawned; the handle works under any async runtime. Cloning is cheap;
+/// clones share the tracking.
+#[derive(Debug, Clone)]
+pub struct DeviceHandle {
+    conn: Connection,
+    identity: DeviceIdentity,
+    tracker: Arc<Mutex<Tracker>>,
+}
+
+/// Where the device was last seen.
+#[derive(Debug, Clone)]
+struct Binding {
+    path: OwnedObjectPath,
+    interface: String,
+}
+
+/// The last known binding plus the device signals not yet applied to it.
+struct Tracker {
+    binding: Option<Binding>,
+    added: PathStream,
+    removed: PathStream,
+    /// Devices added since the last [`DeviceHandle::resolve`], to check
+    /// before falling back to a full lookup.
+    candidates: Vec<OwnedObjectPath>,
+}
+
+impl Tracker {
+    /// Applies the signals buffered since the last call, without waiting.
+    fn drain(&mut self, identity: &DeviceIdentity) {
+        while let Some(Some(path)) = self.removed.next().now_or_never() {
+            self.candidates.retain(|c| *c != path);
+            if self.binding.as_ref().is_some_and(|b| b.path == path) {
+                debug!("Device {} removed", identity.permanent_mac);
+                self.binding = None;
+            }
+        }
+        while let Some(Some(path)) = self.added.next().now_or_never() {
+            self.candidates.push(path);
+        }
+    }
+}
+
+impl fmt::Debug for Tracker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tracker")
+            .field("binding", &self.binding)
+            .field("candidates", &self.candidates)
+            .finish_non_exhaustive()
+    }
+}
+
+impl DeviceHandle {
+    /// Binds to the device currently named `interface`.
+    pub(crate) async fn new(conn: &Connection, interface: &str) -> Result<Self> {
+        // Subscribe before the lookup so a re-plug in between is not missed.
+        let nm = NMProxy::new(conn).await?;
+        let added = nm
+            .receive_device_added()
+            .await?
+            .filter_map(|s| future::ready(s.args().ok().map(|a| a.device)));
+        let removed = nm
+            .receive_device_removed()
+            .await?
+            .filter_map(|s| future::ready(s.args().ok().map(|a| a.device)));
+
+        let path = get_device_by_interface(conn, interface).await?;
+        let (interface, identity) = device_binding(conn, &path).await?;
+        let tracker = Tracker {
+            binding: Some(Binding { path, interface }),
+            added: Box::pin(added),
+            removed: Box::pin(removed),
+            candidates: Vec::new(),
+        };
+
+        Ok(Self {
+            conn: conn.clone(),
+            identity,
+            tracker: Arc::new(Mutex::new(tracker)),
+        })
+    }
+
+    /// Hardware identity the handle tracks.
+    #[must_use]
+    pub fn identity(&self) -> &DeviceIdentity {
+        &self.identity
+    }
+
+    /// Interface name the device was last seen under, or `None` while it is
+    /// absent.
+    ///
+    /// Does not touch D-Bus; use [`resolve`](Self::resolve) for a checked
+    /// name.
+    #[must_use]
+    pub fn interface(&self) -> Option<String> {
+        self.lock().binding.as_ref().map(|b| b.interface.clone())
+    }
+
+    /// D-Bus object path the device was last seen at, or `None` while it is
+    /// absent.
+    #[must_use]
+    pub fn path(&self) -> Option<OwnedObjectPath> {
+        self.lock().binding.as_ref().map(|b| b.path.clone())
+    }
+
+    /// Returns the device's current interface name.
+    ///
+    /// Re-reads the last known object path and, if it is gone or now holds
+    /// different hardware, checks devices added since the last call, then
+    /// looks the device up again by permanent MAC.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NotFound`](crate::ConnectionError::NotFound) if the device
+    /// is not present.
+    pub async fn resolve(&self) -> Result<String> {
+        let (cached, candidates) = {
+            let mut tracker = self.lock();
+            let candidates = std::mem::take(&mut tracker.candidates);
+            (tracker.binding.clone(), candidates)
+        };
+        let known = cached.into_iter().map(|b| b.path);
+        for path in known.chain(candidates) {
+            if let Ok((interface, found)) = device_binding(&self.conn, &path).await
+                && found.same_hardware(&self.identity)
+            {
+                self.bind(Some(Binding {
+                    path,
+                    interface: interface.clone(),
+                }));
+                return Ok(interface);
+            }
+        }
+
+        match find_binding_by_identity(&self.conn, &self.identity).await? {
+            Some((path, interface)) => {
+                debug!(
+                    "Device {} re-resolved to {interface} ({})",
+                    self.identity.permanent_mac,
+                    path.as_str()
+                );
+                self.bind(Some(Binding {
+                    path,
+                    interface: interface.clone(),
+                }));
+                Ok(interface)
+            }
+            None => {
+                self.bind(None);
+                Err(ConnectionError::NotFound)
+            }
+        }
+    }
+
+    fn bind(&self, binding: Option<Binding>) {
+        self.lock().binding = binding;
+    }
+
+    /// Locks the tracker with pending signals applied.
+    fn lock(&self) -> MutexGuard<'_, Tracker> {
+        let mut tracker = self.tracker.lock().unwrap_or_else(|e| e.into_inner());
+        tracker.drain(&self.identity);
+        tracker
+    }
+}
+
+/// A device named either by interface or by [`DeviceHandle`].
+///
+/// Methods taking `impl Into<DeviceRef>` accept `"wlan0"`, a `&String` or
+/// a `&DeviceHandle`; a handle is resolved to its current interface name
+/// on each call.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy)]
+pub enum DeviceRef<'a> {
+    /// A kernel interface name such as `"wlan0"`.
+    Interface(&'a str),
+    /// A device tracked by hardware identity.
+    Handle(&'a DeviceHandle),
+}
+
+impl<'a> DeviceRef<'a> {
+    /// The interface name this refers to right now.
+    pub(crate) async fn interface(self) -> Result<Cow<'a, str>> {
+        match self {
+            DeviceRef::Interface(name) => Ok(Cow::Borrowed(name)),
+            DeviceRef::Handle(handle) => handle.resolve().await.map(Cow::Owned),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for DeviceRef<'a> {
+    fn from(name: &'a str) -> Self {
+        DeviceRef::Interface(name)
+    }
+}
+
+impl<'a> From<&'a String> for DeviceRef<'a> {
+    fn from(name: &'a String) -> Self {
+        DeviceRef::Interface(name)
+    }
+}
+
+impl<'a> From<&'a DeviceHandle> for DeviceRef<'a> {
+    fn from(handle: &'a DeviceHandle) -> Self {
+        DeviceRef::Handle(handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::channel::mpsc;
+
+    fn path(p: &str) -> OwnedObjectPath {
+        OwnedObjectPath::try_from(p).unwrap()
+    }
+
+    #[test]
+    fn drain_applies_buffered_signals() {
+        let (added_tx, added_rx) = mpsc::unbounded();
+        let (removed_tx, removed_rx) = mpsc::unbounded();
+        let mut tracker = Tracker {
+            binding: Some(Binding {
+                path: path("/org/freedesktop/NetworkManager/Devices/3"),
+                interface: "wlan1".into(),
+            }),
+            added: Box::pin(added_rx),
+            removed: Box::pin(removed_rx),
+            candidates: Vec::new(),
+        };
+        let identity = DeviceIdentity::new("00:11:22:33:44:55".into(), "00:11:22:33:44:55".into());
+
+        // Nothing buffered: drain returns without blocking.
+        tracker.drain(&identity);
+        assert!(tracker.binding.is_some());
+
+        removed_tx
+            .unbounded_send(path("/org/freedesktop/NetworkManager/Devices/3"))
+            .unwrap();
+        added_tx
+            .unbounded_send(path("/org/freedesktop/NetworkManager/Devices/7"))
+            .unwrap();
+        tracker.drain(&identity);
+        assert!(tracker.binding.is_none());
+        assert_eq!(
+            tracker.candidates,
+            [path("/org/freedesktop/NetworkManager/Devices/7")]
+        );
+
+        removed_tx
+            .unbounded_send(path("/org/freedesktop/NetworkManager/Devices/7"))
+            .unwrap();
+        tracker.drain(&identity);
+        assert!(tracker.candidates.is_empty());
+    }
+
+    #[tokio::test]
+    async fn names_resolve_to_themselves() {
+        let owned = String::from("wlan1");
+        for device in [DeviceRef::from("wlan1"), DeviceRef::from(&owned)] {
+            assert!(matches!(device, DeviceRef::Interface("wlan1")));
+            let name = device.interface().await.unwrap();
+            assert!(matches!(name, Cow::Borrowed("wlan1")));
+        }
+    }
+}
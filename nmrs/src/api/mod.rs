@@ -3,6 +3,11 @@
 //! This module contains the high-level user-facing API for the `nmrs` crate.
 
 pub mod builders;
+pub mod connect_handle;
+pub mod device_handle;
 pub mod models;
 pub mod network_manager;
+pub mod scan_scheduler;
+pub mod stats_collector;
+pub mod vpn_supervisor;
 pub mod wifi_scope;
@@ -7,6 +7,7 @@
 //! for the deduplicated SSID-grouped view.
 
 use std::fmt;
+use std::time::{Duration, SystemTime};
 
 use serde::{Deserialize, Serialize};
 use zvariant::OwnedObjectPath;
@@ -44,19 +45,87 @@ pub struct AccessPoint {
     pub security: SecurityFeatures,
     /// Monotonic seconds since boot when last seen, or `None` if never.
     pub last_seen_secs: Option<i64>,
+    /// Wall-clock time of the scan that last saw this AP, or `None` if never
+    /// seen. See [`age`](Self::age).
+    pub seen: Option<SystemTime>,
     /// `true` if this AP is the active connection on `device_path`.
     pub is_active: bool,
     /// State of the wireless device at enumeration time (not live).
     pub device_state: DeviceState,
+    /// Load reported in the AP's BSS Load element, or `None` if the AP does
+    /// not advertise it or wpa_supplicant is not reachable.
+    pub bss_load: Option<BssLoad>,
+}
+
+impl AccessPoint {
+    /// Time since a scan last saw this AP.
+    ///
+    /// NetworkManager keeps APs listed for a while after they disappear;
+    /// an age of minutes means the AP is probably gone.
+    #[must_use]
+    pub fn age(&self) -> Option<Duration> {
+        age_of(self.seen)
+    }
+}
+
+/// Time elapsed since `seen`, zero for timestamps in the future.
+pub(crate) fn age_of(seen: Option<SystemTime>) -> Option<Duration> {
+    seen.map(|t| SystemTime::now().duration_since(t).unwrap_or_default())
+}
+
+/// Utilization advertised by an access point in its BSS Load element
+/// (IEEE 802.11 element ID 11).
+///
+/// Useful for site surveys: among APs with similar signal, the one with
+/// fewer stations and lower channel utilization is usually the better pick.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct BssLoad {
+    /// Number of stations currently associated with the AP.
+    pub station_count: u16,
+    /// Share of time the AP sensed the channel busy, scaled to 0–255.
+    pub channel_utilization: u8,
+    /// Remaining admission control capacity in units of 32 µs/s.
+    pub available_admission_capacity: u16,
+}
+
+impl BssLoad {
+    /// Channel utilization as a percentage (0–100).
+    #[must_use]
+    pub fn utilization_percent(&self) -> u8 {
+        (u16::from(self.channel_utilization) * 100 / 255) as u8
+    }
+}
+
+/// Element ID of the BSS Load information element.
+const IE_BSS_LOAD: u8 = 11;
+
+/// Extracts the BSS Load element from a raw information element list.
+pub(crate) fn parse_bss_load(ies: &[u8]) -> Option<BssLoad> {
+    let mut rest = ies;
+    while let [id, len, tail @ ..] = rest {
+        let len = usize::from(*len);
+        let body = tail.get(..len)?;
+        if *id == IE_BSS_LOAD && len >= 5 {
+            return Some(BssLoad {
+                station_count: u16::from_le_bytes([body[0], body[1]]),
+                channel_utilization: body[2],
+                available_admission_capacity: u16::from_le_bytes([body[3], body[4]]),
+            });
+        }
+        rest = &tail[len..];
+    }
+    None
 }
 
 /// Wi-Fi access point operating mode.
 #[non_exhaustive]
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default, Serialize, Deserialize)]
 pub enum ApMode {
     /// Ad-hoc (IBSS) network.
     Adhoc,
     /// Infrastructure (managed) mode — the most common.
+    #[default]
     Infrastructure,
     /// Access point (hotspot) mode.
     Ap,
@@ -212,6 +281,228 @@ impl fmt::Display for ConnectType {
     }
 }
 
+/// A Wi-Fi data or group cipher.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum WifiCipher {
+    /// WEP with a 40-bit key.
+    Wep40,
+    /// WEP with a 104-bit key.
+    Wep104,
+    /// TKIP (WPA1). Deprecated and considered insecure.
+    Tkip,
+    /// CCMP-128 (AES), the WPA2 default.
+    Ccmp,
+    /// GCMP-256, mandated by WPA3-Enterprise 192-bit (Suite B).
+    Gcmp256,
+}
+
+impl fmt::Display for WifiCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Wep40 => write!(f, "WEP-40"),
+            Self::Wep104 => write!(f, "WEP-104"),
+            Self::Tkip => write!(f, "TKIP"),
+            Self::Ccmp => write!(f, "CCMP"),
+            Self::Gcmp256 => write!(f, "GCMP-256"),
+        }
+    }
+}
+
+/// Management frame protection (802.11w / PMF) status of an access point.
+///
+/// NetworkManager does not export the RSN capabilities field, so this is
+/// inferred from the advertised key management: WPA3-only networks (SAE,
+/// OWE, Suite B) require PMF, WPA2/WPA3 transition networks make it
+/// optional, and networks without RSN cannot use it.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default, Serialize, Deserialize)]
+pub enum MfpStatus {
+    /// No RSN element, so PMF is unavailable.
+    Disabled,
+    /// PMF capable but not required (e.g. WPA2/WPA3 transition mode).
+    Optional,
+    /// PMF required (WPA3-only networks).
+    Required,
+    /// RSN network whose PMF setting cannot be derived from NM's flags.
+    #[default]
+    Unknown,
+}
+
+impl fmt::Display for MfpStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Disabled => write!(f, "disabled"),
+            Self::Optional => write!(f, "optional"),
+            Self::Required => write!(f, "required"),
+            Self::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Cipher suites and PMF status decoded from an AP's `WpaFlags` / `RsnFlags`.
+///
+/// # Example
+///
+/// ```no_run
+/// use nmrs::{NetworkManager, WifiCipher};
+///
+/// # async fn example() -> nmrs::Result<()> {
+/// let nm = NetworkManager::new().await?;
+/// for net in nm.list_networks(None).await? {
+///     let info = nm.show_details(&net).await?;
+///     if info.ciphers.is_tkip_only() {
+///         println!("{} only offers TKIP", info.ssid);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Eq, PartialEq, Default, Serialize, Deserialize)]
+pub struct CipherSuites {
+    /// Pairwise (unicast) ciphers offered, strongest first.
+    pub pairwise: Vec<WifiCipher>,
+    /// Group (broadcast) ciphers offered, strongest first.
+    pub group: Vec<WifiCipher>,
+    /// Management frame protection status.
+    pub mfp: MfpStatus,
+}
+
+impl CipherSuites {
+    /// Returns `true` if TKIP is the only pairwise cipher offered.
+    #[must_use]
+    pub fn is_tkip_only(&self) -> bool {
+        self.pairwise == [WifiCipher::Tkip]
+    }
+
+    /// Returns `true` if any pairwise or group cipher is WEP or TKIP.
+    #[must_use]
+    pub fn has_legacy_cipher(&self) -> bool {
+        self.pairwise
+            .iter()
+            .chain(&self.group)
+            .any(|c| matches!(c, WifiCipher::Wep40 | WifiCipher::Wep104 | WifiCipher::Tkip))
+    }
+}
+
+impl fmt::Display for CipherSuites {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |list: &[WifiCipher]| {
+            if list.is_empty() {
+                "none".to_string()
+            } else {
+                list.iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("/")
+            }
+        };
+        write!(
+            f,
+            "pairwise {}, group {}, PMF {}",
+            join(&self.pairwise),
+            join(&self.group),
+            self.mfp
+        )
+    }
+}
+
+/// Highest WPA generation an access point offers.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum WpaGeneration {
+    /// No encryption.
+    Open,
+    /// Static WEP keys.
+    Wep,
+    /// WPA (WPA1), advertised in the vendor-specific WPA element.
+    Wpa,
+    /// WPA2, advertised in the RSN element.
+    Wpa2,
+    /// WPA3: SAE, OWE or Suite B 192-bit key management.
+    Wpa3,
+}
+
+impl fmt::Display for WpaGeneration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Open => write!(f, "Open"),
+            Self::Wep => write!(f, "WEP"),
+            Self::Wpa => write!(f, "WPA"),
+            Self::Wpa2 => write!(f, "WPA2"),
+            Self::Wpa3 => write!(f, "WPA3"),
+        }
+    }
+}
+
+/// IEEE 802.11 amendment an access point most likely implements.
+///
+/// NetworkManager does not expose the HT/VHT/HE capability elements, so
+/// this is guessed from the band and the advertised maximum bitrate; see
+/// [`guess`](Self::guess).
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum WifiStandard {
+    /// 802.11b, 2.4 GHz, up to 11 Mbit/s.
+    B,
+    /// 802.11a, 5 GHz, up to 54 Mbit/s.
+    A,
+    /// 802.11g, 2.4 GHz, up to 54 Mbit/s.
+    G,
+    /// 802.11n (Wi-Fi 4).
+    N,
+    /// 802.11ac (Wi-Fi 5), 5 GHz only.
+    Ac,
+    /// 802.11ax (Wi-Fi 6/6E).
+    Ax,
+}
+
+impl WifiStandard {
+    /// Guesses the standard from the operating frequency in MHz and the
+    /// maximum bitrate in Kbit/s, or `None` if the band is unknown or no
+    /// bitrate is reported.
+    ///
+    /// 6 GHz is 802.11ax only. Elsewhere the bitrate picks the oldest
+    /// standard able to reach it, so an 802.11ax AP whose rates an older
+    /// standard also reaches is reported as that standard.
+    #[must_use]
+    pub fn guess(frequency_mhz: u32, max_bitrate_kbps: u32) -> Option<Self> {
+        if max_bitrate_kbps == 0 {
+            return None;
+        }
+        match frequency_mhz {
+            2400..=2500 => Some(match max_bitrate_kbps {
+                0..=11_000 => Self::B,
+                11_001..=54_000 => Self::G,
+                54_001..=600_000 => Self::N,
+                _ => Self::Ax,
+            }),
+            4900..=5924 => Some(match max_bitrate_kbps {
+                0..=54_000 => Self::A,
+                54_001..=600_000 => Self::N,
+                600_001..=6_934_000 => Self::Ac,
+                _ => Self::Ax,
+            }),
+            5925..=7125 => Some(Self::Ax),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for WifiStandard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::B => write!(f, "802.11b"),
+            Self::A => write!(f, "802.11a"),
+            Self::G => write!(f, "802.11g"),
+            Self::N => write!(f, "802.11n"),
+            Self::Ac => write!(f, "802.11ac"),
+            Self::Ax => write!(f, "802.11ax"),
+        }
+    }
+}
+
 // NM80211ApFlags
 const AP_FLAGS_PRIVACY: u32 = 0x1;
 const AP_FLAGS_WPS: u32 = 0x2;
@@ -221,6 +512,10 @@ const SEC_PAIR_WEP40: u32 = 0x1;
 const SEC_PAIR_WEP104: u32 = 0x2;
 const SEC_PAIR_TKIP: u32 = 0x4;
 const SEC_PAIR_CCMP: u32 = 0x8;
+const SEC_GROUP_WEP40: u32 = 0x10;
+const SEC_GROUP_WEP104: u32 = 0x20;
+const SEC_GROUP_TKIP: u32 = 0x40;
+const SEC_GROUP_CCMP: u32 = 0x80;
 const SEC_KEY_MGMT_PSK: u32 = 0x100;
 const SEC_KEY_MGMT_802_1X: u32 = 0x200;
 const SEC_KEY_MGMT_SAE: u32 = 0x400;
@@ -250,10 +545,180 @@ pub(crate) fn decode_security(flags: u32, wpa: u32, rsn: u32) -> SecurityFeature
     }
 }
 
+/// Decodes pairwise/group ciphers and PMF status from `WpaFlags` and `RsnFlags`.
+///
+/// NM has no GCMP flag; Suite B 192-bit mandates GCMP-256, so it is reported
+/// whenever `KEY_MGMT_EAP_SUITE_B_192` is set.
+pub(crate) fn decode_ciphers(wpa: u32, rsn: u32) -> CipherSuites {
+    let combined = wpa | rsn;
+    let suite_b = (rsn & SEC_KEY_MGMT_EAP_SUITE_B_192) != 0;
+
+    let pick = |table: &[(u32, WifiCipher)]| -> Vec<WifiCipher> {
+        table
+            .iter()
+            .filter(|(bit, _)| combined & bit != 0)
+            .map(|&(_, cipher)| cipher)
+            .collect()
+    };
+
+    let mut pairwise = pick(&[
+        (SEC_PAIR_CCMP, WifiCipher::Ccmp),
+        (SEC_PAIR_TKIP, WifiCipher::Tkip),
+        (SEC_PAIR_WEP104, WifiCipher::Wep104),
+        (SEC_PAIR_WEP40, WifiCipher::Wep40),
+    ]);
+    let mut group = pick(&[
+        (SEC_GROUP_CCMP, WifiCipher::Ccmp),
+        (SEC_GROUP_TKIP, WifiCipher::Tkip),
+        (SEC_GROUP_WEP104, WifiCipher::Wep104),
+        (SEC_GROUP_WEP40, WifiCipher::Wep40),
+    ]);
+    if suite_b {
+        pairwise.insert(0, WifiCipher::Gcmp256);
+        group.insert(0, WifiCipher::Gcmp256);
+    }
+
+    let wpa3 = rsn & (SEC_KEY_MGMT_SAE | SEC_KEY_MGMT_OWE) != 0;
+    let legacy_akm = rsn & (SEC_KEY_MGMT_PSK | SEC_KEY_MGMT_802_1X) != 0;
+    let mfp = if rsn == 0 {
+        MfpStatus::Disabled
+    } else if suite_b || (wpa3 && !legacy_akm) {
+        MfpStatus::Required
+    } else if wpa3 || rsn & SEC_KEY_MGMT_OWE_TM != 0 {
+        MfpStatus::Optional
+    } else {
+        MfpStatus::Unknown
+    };
+
+    CipherSuites {
+        pairwise,
+        group,
+        mfp,
+    }
+}
+
+/// Derives the highest WPA generation from NM's AP flag triplet.
+///
+/// The OWE transition flag is set on the open half of an OWE transition
+/// pair, which NetworkManager joins through its OWE twin.
+pub(crate) fn decode_wpa_generation(flags: u32, wpa: u32, rsn: u32) -> WpaGeneration {
+    let wpa3 =
+        SEC_KEY_MGMT_SAE | SEC_KEY_MGMT_OWE | SEC_KEY_MGMT_OWE_TM | SEC_KEY_MGMT_EAP_SUITE_B_192;
+    if rsn & wpa3 != 0 {
+        WpaGeneration::Wpa3
+    } else if rsn != 0 {
+        WpaGeneration::Wpa2
+    } else if wpa != 0 {
+        WpaGeneration::Wpa
+    } else if flags & AP_FLAGS_PRIVACY != 0 {
+        WpaGeneration::Wep
+    } else {
+        WpaGeneration::Open
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn wpa_generation_from_flags() {
+        assert_eq!(decode_wpa_generation(0, 0, 0), WpaGeneration::Open);
+        assert_eq!(
+            decode_wpa_generation(AP_FLAGS_PRIVACY, 0, 0),
+            WpaGeneration::Wep
+        );
+        assert_eq!(
+            decode_wpa_generation(AP_FLAGS_PRIVACY, SEC_PAIR_TKIP | SEC_KEY_MGMT_PSK, 0),
+            WpaGeneration::Wpa
+        );
+        assert_eq!(
+            decode_wpa_generation(
+                AP_FLAGS_PRIVACY,
+                SEC_PAIR_TKIP | SEC_KEY_MGMT_PSK,
+                SEC_PAIR_CCMP | SEC_KEY_MGMT_PSK
+            ),
+            WpaGeneration::Wpa2
+        );
+        assert_eq!(
+            decode_wpa_generation(
+                AP_FLAGS_PRIVACY,
+                0,
+                SEC_PAIR_CCMP | SEC_KEY_MGMT_PSK | SEC_KEY_MGMT_SAE
+            ),
+            WpaGeneration::Wpa3
+        );
+        assert_eq!(
+            decode_wpa_generation(0, 0, SEC_KEY_MGMT_OWE_TM),
+            WpaGeneration::Wpa3
+        );
+    }
+
+    #[test]
+    fn wifi_standard_from_band_and_rate() {
+        assert_eq!(WifiStandard::guess(2412, 11_000), Some(WifiStandard::B));
+        assert_eq!(WifiStandard::guess(2437, 54_000), Some(WifiStandard::G));
+        assert_eq!(WifiStandard::guess(2462, 300_000), Some(WifiStandard::N));
+        assert_eq!(WifiStandard::guess(2437, 1_147_000), Some(WifiStandard::Ax));
+        assert_eq!(WifiStandard::guess(5180, 54_000), Some(WifiStandard::A));
+        assert_eq!(WifiStandard::guess(5180, 450_000), Some(WifiStandard::N));
+        assert_eq!(WifiStandard::guess(5745, 866_700), Some(WifiStandard::Ac));
+        assert_eq!(WifiStandard::guess(5500, 9_608_000), Some(WifiStandard::Ax));
+        assert_eq!(WifiStandard::guess(5955, 54_000), Some(WifiStandard::Ax));
+        assert_eq!(WifiStandard::guess(5180, 0), None);
+        assert_eq!(WifiStandard::guess(60_480, 54_000), None);
+        assert_eq!(WifiStandard::Ac.to_string(), "802.11ac");
+    }
+
+    #[test]
+    fn ciphers_wpa2_ccmp() {
+        let c = decode_ciphers(0, SEC_PAIR_CCMP | SEC_GROUP_CCMP | SEC_KEY_MGMT_PSK);
+        assert_eq!(c.pairwise, vec![WifiCipher::Ccmp]);
+        assert_eq!(c.group, vec![WifiCipher::Ccmp]);
+        assert_eq!(c.mfp, MfpStatus::Unknown);
+        assert!(!c.is_tkip_only());
+        assert!(!c.has_legacy_cipher());
+        assert_eq!(c.to_string(), "pairwise CCMP, group CCMP, PMF unknown");
+    }
+
+    #[test]
+    fn ciphers_tkip_only() {
+        let c = decode_ciphers(SEC_PAIR_TKIP | SEC_GROUP_TKIP | SEC_KEY_MGMT_PSK, 0);
+        assert!(c.is_tkip_only());
+        assert!(c.has_legacy_cipher());
+        assert_eq!(c.mfp, MfpStatus::Disabled);
+    }
+
+    #[test]
+    fn ciphers_mixed_mode_not_tkip_only() {
+        let c = decode_ciphers(
+            SEC_PAIR_TKIP | SEC_GROUP_TKIP | SEC_KEY_MGMT_PSK,
+            SEC_PAIR_CCMP | SEC_GROUP_TKIP | SEC_KEY_MGMT_PSK,
+        );
+        assert_eq!(c.pairwise, vec![WifiCipher::Ccmp, WifiCipher::Tkip]);
+        assert_eq!(c.group, vec![WifiCipher::Tkip]);
+        assert!(!c.is_tkip_only());
+        assert!(c.has_legacy_cipher());
+    }
+
+    #[test]
+    fn ciphers_mfp_from_key_mgmt() {
+        let sae = decode_ciphers(0, SEC_PAIR_CCMP | SEC_KEY_MGMT_SAE);
+        assert_eq!(sae.mfp, MfpStatus::Required);
+        let transition = decode_ciphers(0, SEC_PAIR_CCMP | SEC_KEY_MGMT_SAE | SEC_KEY_MGMT_PSK);
+        assert_eq!(transition.mfp, MfpStatus::Optional);
+        let owe_tm = decode_ciphers(0, SEC_KEY_MGMT_OWE_TM);
+        assert_eq!(owe_tm.mfp, MfpStatus::Optional);
+    }
+
+    #[test]
+    fn ciphers_suite_b_implies_gcmp256() {
+        let c = decode_ciphers(0, SEC_KEY_MGMT_EAP_SUITE_B_192);
+        assert_eq!(c.pairwise, vec![WifiCipher::Gcmp256]);
+        assert_eq!(c.group, vec![WifiCipher::Gcmp256]);
+        assert_eq!(c.mfp, MfpStatus::Required);
+    }
+
     #[test]
     fn decode_open_network() {
         let sec = decode_security(0, 0, 0);
@@ -407,4 +872,22 @@ mod tests {
         };
         assert_eq!(sec.preferred_connect_type(), ConnectType::Sae);
     }
+
+    #[test]
+    fn parses_bss_load_element() {
+        // SSID "ab", then BSS Load: 3 stations, utilization 128, capacity 0x1234
+        let ies = [0, 2, b'a', b'b', 11, 5, 3, 0, 128, 0x34, 0x12];
+        let load = parse_bss_load(&ies).unwrap();
+        assert_eq!(load.station_count, 3);
+        assert_eq!(load.channel_utilization, 128);
+        assert_eq!(load.available_admission_capacity, 0x1234);
+        assert_eq!(load.utilization_percent(), 50);
+    }
+
+    #[test]
+    fn bss_load_absent_or_truncated() {
+        assert_eq!(parse_bss_load(&[]), None);
+        assert_eq!(parse_bss_load(&[0, 2, b'a', b'b']), None);
+        assert_eq!(parse_bss_load(&[11, 5, 3, 0]), None);
+    }
 }
@@ -0,0 +1,59 @@
+use std::fmt::{Display, Formatter};
+
+/// Why NetworkManager will not automatically activate a saved profile.
+///
+/// NetworkManager does not publish its internal blocked state over D-Bus,
+/// so this is derived from the profile's `connection.autoconnect` flag and
+/// the `Autoconnect` flag and last state reason of the devices that can
+/// use the profile.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutoconnectBlockReason {
+    /// `connection.autoconnect` is turned off for the profile.
+    Disabled,
+    /// The device was disconnected by the user, which turns off its
+    /// autoconnect until the next manual activation.
+    UserDisconnected,
+    /// The last attempt failed because no secrets were available.
+    NoSecrets,
+    /// The last attempt failed (authentication, DHCP, network not found).
+    Failed,
+}
+
+impl Display for AutoconnectBlockReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Disabled => write!(f, "autoconnect disabled for this profile"),
+            Self::UserDisconnected => write!(f, "device was disconnected by the user"),
+            Self::NoSecrets => write!(f, "password or other secrets missing"),
+            Self::Failed => write!(f, "previous connection attempts failed"),
+        }
+    }
+}
+
+/// Autoconnect state of one saved profile.
+///
+/// Returned by
+/// [`NetworkManager::autoconnect_status`](crate::NetworkManager::autoconnect_status).
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutoconnectStatus {
+    /// Profile name (`connection.id`).
+    pub id: String,
+    /// Profile UUID.
+    pub uuid: String,
+    /// Whether the profile is currently active on some device.
+    pub active: bool,
+    /// Why the profile would not be auto-activated, if it would not.
+    pub blocked: Option<AutoconnectBlockReason>,
+    /// Interface the block was observed on, for device-level reasons.
+    pub interface: Option<String>,
+}
+
+impl AutoconnectStatus {
+    /// Whether NetworkManager is expected to activate this profile on its own.
+    #[must_use]
+    pub fn is_blocked(&self) -> bool {
+        self.blocked.is_some()
+    }
+}
@@ -0,0 +1,192 @@
+//! Bridge and bond configuration.
+//!
+//! Types for software master devices created by
+//! [`NetworkManager::create_bridge`](crate::NetworkManager::create_bridge) and
+//! [`NetworkManager::create_bond`](crate::NetworkManager::create_bond), and
+//! for the Ethernet slaves attached to them.
+
+use super::error::ConnectionError;
+use super::ip::IpSettings;
+
+/// Kind of master a slave connection is attached to
+/// (`connection.slave-type`).
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlaveType {
+    /// A Linux bridge.
+    Bridge,
+    /// A bond.
+    Bond,
+}
+
+impl SlaveType {
+    /// Value of the `connection.slave-type` property.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Bridge => "bridge",
+            Self::Bond => "bond",
+        }
+    }
+}
+
+/// Bonding mode (`bond.options` `mode`).
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BondMode {
+    /// Round-robin transmission over all slaves.
+    #[default]
+    BalanceRr,
+    /// One active slave; another takes over when it fails.
+    ActiveBackup,
+    /// Transmit slave chosen by a hash of the packet.
+    BalanceXor,
+    /// Every packet on every slave.
+    Broadcast,
+    /// IEEE 802.3ad dynamic link aggregation (LACP).
+    Lacp,
+    /// Adaptive transmit load balancing.
+    BalanceTlb,
+    /// Adaptive transmit and receive load balancing.
+    BalanceAlb,
+}
+
+impl BondMode {
+    /// Mode name as understood by the kernel bonding driver.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::BalanceRr => "balance-rr",
+            Self::ActiveBackup => "active-backup",
+            Self::BalanceXor => "balance-xor",
+            Self::Broadcast => "broadcast",
+            Self::Lacp => "802.3ad",
+            Self::BalanceTlb => "balance-tlb",
+            Self::BalanceAlb => "balance-alb",
+        }
+    }
+}
+
+/// Bridge settings.
+///
+/// Unset fields keep NetworkManager's defaults (STP on, priority 32768,
+/// 15 s forward delay, automatic addressing).
+///
+/// # Examples
+///
+/// ```rust
+/// use nmrs::BridgeOptions;
+///
+/// let options = BridgeOptions::new().with_stp(false).with_forward_delay(4);
+/// assert!(options.validate().is_ok());
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Default)]
+pub struct BridgeOptions {
+    /// Whether the Spanning Tree Protocol is enabled.
+    pub stp: Option<bool>,
+    /// STP bridge priority; lower values win root election.
+    pub priority: Option<u16>,
+    /// STP forward delay in seconds (2-30).
+    pub forward_delay: Option<u32>,
+    /// IPv4 configuration of the bridge; DHCP when `None`.
+    pub ipv4: Option<IpSettings>,
+    /// IPv6 configuration of the bridge; automatic when `None`.
+    pub ipv6: Option<IpSettings>,
+}
+
+impl BridgeOptions {
+    /// Options with every setting left at its default.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enables or disables STP.
+    #[must_use]
+    pub fn with_stp(mut self, stp: bool) -> Self {
+        self.stp = Some(stp);
+        self
+    }
+
+    /// Sets the STP bridge priority.
+    #[must_use]
+    pub fn with_priority(mut self, priority: u16) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Sets the STP forward delay in seconds.
+    #[must_use]
+    pub fn with_forward_delay(mut self, seconds: u32) -> Self {
+        self.forward_delay = Some(seconds);
+        self
+    }
+
+    /// Sets the IPv4 configuration of the bridge.
+    #[must_use]
+    pub fn with_ipv4(mut self, ipv4: IpSettings) -> Self {
+        self.ipv4 = Some(ipv4);
+        self
+    }
+
+    /// Sets the IPv6 configuration of the bridge.
+    #[must_use]
+    pub fn with_ipv6(mut self, ipv6: IpSettings) -> Self {
+        self.ipv6 = Some(ipv6);
+        self
+    }
+
+    /// Validates the options.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConnectionError::InvalidInput` if the forward delay is
+    /// outside 2-30 seconds.
+    pub fn validate(&self) -> Result<(), ConnectionError> {
+        if let Some(delay) = self.forward_delay
+            && !(2..=30).contains(&delay)
+        {
+            return Err(ConnectionError::InvalidInput {
+                field: "forward_delay".to_string(),
+                reason: format!("forward delay must be 2-30 seconds, got {delay}"),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bond_mode_names() {
+        assert_eq!(BondMode::default().as_str(), "balance-rr");
+        assert_eq!(BondMode::Lacp.as_str(), "802.3ad");
+        assert_eq!(BondMode::ActiveBackup.as_str(), "active-backup");
+    }
+
+    #[test]
+    fn forward_delay_range() {
+        assert!(BridgeOptions::new().validate().is_ok());
+        assert!(
+            BridgeOptions::new()
+                .with_forward_delay(2)
+                .validate()
+                .is_ok()
+        );
+        assert!(
+            BridgeOptions::new()
+                .with_forward_delay(1)
+                .validate()
+                .is_err()
+        );
+        assert!(
+            BridgeOptions::new()
+                .with_forward_delay(31)
+                .validate()
+                .is_err()
+        );
+    }
+}
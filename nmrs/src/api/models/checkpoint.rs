@@ -0,0 +1,51 @@
+//! NetworkManager checkpoint lifecycle events.
+//!
+//! A checkpoint snapshots device configuration so it can be restored later.
+//! If the creator does not destroy it before its rollback timeout, NM rolls
+//! the devices back on its own. These events let admin tools notice when
+//! that automatic rollback reverted their changes.
+
+use std::time::Duration;
+
+/// A change in the set of NetworkManager checkpoints.
+///
+/// Emitted by [`crate::NetworkManager::monitor_checkpoints`], and by
+/// [`crate::NetworkManager::events`] as [`crate::NetworkEvent::Checkpoint`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckpointEvent {
+    /// A checkpoint was created.
+    Created {
+        /// D-Bus object path of the checkpoint.
+        path: String,
+        /// Interface names of the devices it covers.
+        devices: Vec<String>,
+        /// Delay before automatic rollback, or `None` if it never expires.
+        rollback_timeout: Option<Duration>,
+    },
+    /// A checkpoint reached its rollback timeout and NM reverted the devices.
+    RolledBack {
+        /// D-Bus object path of the checkpoint.
+        path: String,
+    },
+    /// A checkpoint went away before its timeout.
+    ///
+    /// NM does not say whether a client destroyed it (keeping the changes)
+    /// or rolled it back explicitly; both look the same on the bus.
+    Destroyed {
+        /// D-Bus object path of the checkpoint.
+        path: String,
+    },
+}
+
+impl CheckpointEvent {
+    /// D-Bus object path of the checkpoint this event refers to.
+    #[must_use]
+    pub fn path(&self) -> &str {
+        match self {
+            Self::Created { path, .. } | Self::RolledBack { path } | Self::Destroyed { path } => {
+                path
+            }
+        }
+    }
+}
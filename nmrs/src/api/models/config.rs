@@ -1,4 +1,10 @@
+use serde::{Deserialize, Serialize};
 use std::time::Duration;
+use uuid::Uuid;
+
+use super::IpSettings;
+use super::error::{ConnectionError, ErrorClass};
+use crate::util::validation::validate_bssid;
 
 /// Timeout configuration for NetworkManager operations.
 ///
@@ -32,6 +38,12 @@ pub struct TimeoutConfig {
     pub connection_timeout: Duration,
     /// Timeout for device disconnection (default: 10 seconds)
     pub disconnect_timeout: Duration,
+    /// Number of extra scans to run when the target SSID is not visible
+    /// yet (default: 0, disabled)
+    pub scan_retries: u32,
+    /// Longest wait for each retry scan to finish; doubles on every attempt
+    /// (default: 2 seconds)
+    pub scan_retry_delay: Duration,
 }
 
 impl Default for TimeoutConfig {
@@ -40,10 +52,14 @@ impl Default for TimeoutConfig {
     /// Defaults:
     /// - `connection_timeout`: 30 seconds
     /// - `disconnect_timeout`: 10 seconds
+    /// - `scan_retries`: 0 (no rescans)
+    /// - `scan_retry_delay`: 2 seconds
     fn default() -> Self {
         Self {
             connection_timeout: Duration::from_secs(30),
             disconnect_timeout: Duration::from_secs(10),
+            scan_retries: 0,
+            scan_retry_delay: Duration::from_secs(2),
         }
     }
 }
@@ -103,6 +119,145 @@ impl TimeoutConfig {
         self.disconnect_timeout = timeout;
         self
     }
+
+    /// Sets how many times to rescan when the target SSID is not found.
+    ///
+    /// Roaming laptops often try to connect a moment before the access
+    /// point shows up in scan results. With retries enabled, a connect
+    /// that would fail with [`ConnectionError::NotFound`](crate::ConnectionError::NotFound)
+    /// instead requests another scan, waits, and looks again.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nmrs::TimeoutConfig;
+    ///
+    /// let config = TimeoutConfig::new().with_scan_retries(3);
+    /// ```
+    #[must_use]
+    pub fn with_scan_retries(mut self, retries: u32) -> Self {
+        self.scan_retries = retries;
+        self
+    }
+
+    /// Sets the base wait between retry scans.
+    ///
+    /// Each retry waits until the scan finishes (the device's `LastScan`
+    /// advances), but at most this long, doubling on each attempt: a base
+    /// of 2 seconds allows 2s, 4s, 8s, ... Results are only re-checked
+    /// after a scan has finished.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nmrs::TimeoutConfig;
+    /// use std::time::Duration;
+    ///
+    /// let config = TimeoutConfig::new()
+    ///     .with_scan_retries(2)
+    ///     .with_scan_retry_delay(Duration::from_secs(1));
+    /// ```
+    #[must_use]
+    pub fn with_scan_retry_delay(mut self, delay: Duration) -> Self {
+        self.scan_retry_delay = delay;
+        self
+    }
+
+    /// Wait before re-checking results on retry `attempt` (1-based).
+    pub(crate) fn scan_retry_wait(&self, attempt: u32) -> Duration {
+        let factor = 1u32 << attempt.saturating_sub(1).min(16);
+        self.scan_retry_delay.saturating_mul(factor)
+    }
+}
+
+/// Retry policy for [`NetworkManager::connect_with_policy`](crate::NetworkManager::connect_with_policy).
+///
+/// A failed attempt is retried if its [`ErrorClass`] is in `retry_on` and
+/// attempts remain. Every attempt scans again before connecting. The wait
+/// between attempts starts at `backoff` and doubles up to `max_backoff`.
+///
+/// Defaults: 3 attempts, 2 second backoff capped at 30 seconds, retrying
+/// [`ErrorClass::Timeout`], [`ErrorClass::SupplicantTimeout`] and
+/// [`ErrorClass::Dhcp`]. Rejected credentials are never retried by default.
+///
+/// # Examples
+///
+/// ```rust
+/// use nmrs::{ConnectPolicy, ErrorClass};
+/// use std::time::Duration;
+///
+/// let policy = ConnectPolicy::new()
+///     .with_max_attempts(5)
+///     .with_backoff(Duration::from_secs(1), Duration::from_secs(10))
+///     .with_retry_on(vec![ErrorClass::Timeout, ErrorClass::NotFound]);
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectPolicy {
+    /// Total attempts, including the first (at least 1)
+    pub max_attempts: u32,
+    /// Wait before the second attempt; doubles on each further attempt
+    pub backoff: Duration,
+    /// Longest wait between attempts
+    pub max_backoff: Duration,
+    /// Error classes worth another attempt
+    pub retry_on: Vec<ErrorClass>,
+}
+
+impl Default for ConnectPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            backoff: Duration::from_secs(2),
+            max_backoff: Duration::from_secs(30),
+            retry_on: vec![
+                ErrorClass::Timeout,
+                ErrorClass::SupplicantTimeout,
+                ErrorClass::Dhcp,
+            ],
+        }
+    }
+}
+
+impl ConnectPolicy {
+    /// Creates a policy with the default values.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the total number of attempts; `0` is treated as `1`.
+    #[must_use]
+    pub fn with_max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts;
+        self
+    }
+
+    /// Sets the wait before the second attempt and the cap it doubles up to.
+    #[must_use]
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.backoff = initial;
+        self.max_backoff = max.max(initial);
+        self
+    }
+
+    /// Sets the error classes that are retried.
+    #[must_use]
+    pub fn with_retry_on(mut self, classes: Vec<ErrorClass>) -> Self {
+        self.retry_on = classes;
+        self
+    }
+
+    /// Whether `error` after attempt `attempt` (1-based) is retried.
+    pub(crate) fn should_retry(&self, error: &ConnectionError, attempt: u32) -> bool {
+        attempt < self.max_attempts && self.retry_on.contains(&error.class())
+    }
+
+    /// Wait after failed attempt `attempt` (1-based).
+    pub(crate) fn delay(&self, attempt: u32) -> Duration {
+        let factor = 1u32 << attempt.saturating_sub(1).min(16);
+        self.backoff.saturating_mul(factor).min(self.max_backoff)
+    }
 }
 
 /// Connection options for saved NetworkManager connections.
@@ -127,7 +282,8 @@ impl TimeoutConfig {
 /// let opts_manual = ConnectionOptions::new(false);
 /// ```
 #[non_exhaustive]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ConnectionOptions {
     /// Whether to automatically connect when available
     pub autoconnect: bool,
@@ -135,6 +291,23 @@ pub struct ConnectionOptions {
     pub autoconnect_priority: Option<i32>,
     /// Maximum number of auto-connect retry attempts
     pub autoconnect_retries: Option<i32>,
+    /// Namespace for deterministic (v5) profile UUIDs; random when `None`
+    pub uuid_namespace: Option<Uuid>,
+    /// Combined trust preset applied to new profiles; see [`TrustLevel`]
+    pub trust_level: Option<TrustLevel>,
+    /// IPv4 configuration of new profiles; DHCP when `None`
+    pub ipv4: Option<IpSettings>,
+    /// IPv6 configuration of new profiles; automatic when `None`
+    pub ipv6: Option<IpSettings>,
+    /// MAC address presented by new Wi-Fi and Ethernet profiles; overrides
+    /// the policy of [`trust_level`](Self::trust_level)
+    pub mac_policy: Option<MacPolicy>,
+    /// Where profiles added by a connect are stored; see
+    /// [`ConnectionPersistence`]
+    pub persistence: ConnectionPersistence,
+    /// What the lifetime of an activation that adds a profile is tied to;
+    /// see [`ActivationBinding`]
+    pub activation_binding: ActivationBinding,
 }
 
 impl Default for ConnectionOptions {
@@ -144,11 +317,24 @@ impl Default for ConnectionOptions {
     /// - `autoconnect`: `true`
     /// - `autoconnect_priority`: `None` (uses NetworkManager's default of 0)
     /// - `autoconnect_retries`: `None` (unlimited retries)
+    /// - `uuid_namespace`: `None` (random profile UUIDs)
+    /// - `trust_level`: `None` (NetworkManager defaults)
+    /// - `ipv4` / `ipv6`: `None` (automatic addressing)
+    /// - `mac_policy`: `None` (NetworkManager's default MAC policy)
+    /// - `persistence`: [`ConnectionPersistence::Disk`]
+    /// - `activation_binding`: [`ActivationBinding::None`]
     fn default() -> Self {
         Self {
             autoconnect: true,
             autoconnect_priority: None,
             autoconnect_retries: None,
+            uuid_namespace: None,
+            trust_level: None,
+            ipv4: None,
+            ipv6: None,
+            mac_policy: None,
+            persistence: ConnectionPersistence::Disk,
+            activation_binding: ActivationBinding::None,
         }
     }
 }
@@ -169,6 +355,13 @@ impl ConnectionOptions {
             autoconnect,
             autoconnect_priority: None,
             autoconnect_retries: None,
+            uuid_namespace: None,
+            trust_level: None,
+            ipv4: None,
+            ipv6: None,
+            mac_policy: None,
+            persistence: ConnectionPersistence::Disk,
+            activation_binding: ActivationBinding::None,
         }
     }
 
@@ -185,4 +378,393 @@ impl ConnectionOptions {
         self.autoconnect_retries = Some(retries);
         self
     }
+
+    /// Derives profile UUIDs from `namespace` instead of generating random ones.
+    ///
+    /// The UUID is a v5 hash of the namespace, connection type and connection
+    /// id (see [`profile_uuid`](crate::builders::profile_uuid)), so the same
+    /// profile provisioned on different machines gets the same UUID.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nmrs::ConnectionOptions;
+    /// use uuid::Uuid;
+    ///
+    /// let fleet = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
+    /// let opts = ConnectionOptions::new(true).with_uuid_namespace(fleet);
+    /// ```
+    #[must_use]
+    pub fn with_uuid_namespace(mut self, namespace: Uuid) -> Self {
+        self.uuid_namespace = Some(namespace);
+        self
+    }
+
+    /// Applies a [`TrustLevel`] preset to profiles created with these options.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nmrs::{ConnectionOptions, TrustLevel};
+    ///
+    /// let opts = ConnectionOptions::new(true).with_trust_level(TrustLevel::Public);
+    /// ```
+    #[must_use]
+    pub fn with_trust_level(mut self, level: TrustLevel) -> Self {
+        self.trust_level = Some(level);
+        self
+    }
+
+    /// Sets the IPv4 configuration of profiles created with these options.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nmrs::{ConnectionOptions, IpSettings};
+    /// use nmrs::builders::IpConfig;
+    ///
+    /// let opts = ConnectionOptions::new(true)
+    ///     .with_ipv4(IpSettings::manual(vec![IpConfig::new("10.0.0.5", 24)]));
+    /// ```
+    #[must_use]
+    pub fn with_ipv4(mut self, settings: IpSettings) -> Self {
+        self.ipv4 = Some(settings);
+        self
+    }
+
+    /// Sets the IPv6 configuration of profiles created with these options.
+    #[must_use]
+    pub fn with_ipv6(mut self, settings: IpSettings) -> Self {
+        self.ipv6 = Some(settings);
+        self
+    }
+
+    /// Sets the MAC address policy of profiles created with these options.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nmrs::{ConnectionOptions, MacPolicy};
+    ///
+    /// let opts = ConnectionOptions::new(true).with_mac_policy(MacPolicy::Random);
+    /// ```
+    #[must_use]
+    pub fn with_mac_policy(mut self, policy: MacPolicy) -> Self {
+        self.mac_policy = Some(policy);
+        self
+    }
+
+    /// Sets where profiles added by a connect are stored.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nmrs::{ConnectionOptions, ConnectionPersistence};
+    ///
+    /// // A one-off connection that leaves no saved profile behind.
+    /// let opts = ConnectionOptions::new(false).with_persistence(ConnectionPersistence::Volatile);
+    /// ```
+    #[must_use]
+    pub fn with_persistence(mut self, persistence: ConnectionPersistence) -> Self {
+        self.persistence = persistence;
+        self
+    }
+
+    /// Ties the lifetime of the activation to this process.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nmrs::{ActivationBinding, ConnectionOptions, ConnectionPersistence};
+    ///
+    /// // Gone, profile and all, once the process exits.
+    /// let opts = ConnectionOptions::new(false)
+    ///     .with_persistence(ConnectionPersistence::Volatile)
+    ///     .with_activation_binding(ActivationBinding::DbusClient);
+    /// ```
+    #[must_use]
+    pub fn with_activation_binding(mut self, binding: ActivationBinding) -> Self {
+        self.activation_binding = binding;
+        self
+    }
+}
+
+/// Where NetworkManager keeps a profile added by a connect.
+///
+/// Anything other than [`Disk`](Self::Disk) adds the profile with
+/// `AddAndActivateConnection2`, which needs NetworkManager 1.16 or newer.
+/// Only profiles created by the connect are affected; an existing saved
+/// profile is activated as it is.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum ConnectionPersistence {
+    /// Saved to disk like any other profile.
+    #[default]
+    Disk,
+    /// Kept in memory until NetworkManager restarts.
+    Memory,
+    /// Kept in memory and deleted once the connection deactivates, so
+    /// one-off connections do not show up among saved profiles.
+    Volatile,
+}
+
+impl ConnectionPersistence {
+    /// Value of the `persist` option of `AddAndActivateConnection2`.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Disk => "disk",
+            Self::Memory => "memory",
+            Self::Volatile => "volatile",
+        }
+    }
+}
+
+/// What an activation that adds a profile stays alive for.
+///
+/// With [`DbusClient`](Self::DbusClient) NetworkManager deactivates the
+/// connection as soon as the D-Bus connection that requested it goes away:
+/// when the last clone of the [`NetworkManager`](crate::NetworkManager)
+/// that connected is dropped, or the process exits or crashes. Combine it
+/// with [`ConnectionPersistence::Volatile`] to leave nothing behind.
+///
+/// Sent as the `bind-activation` option of `AddAndActivateConnection2`,
+/// which needs NetworkManager 1.24 or newer. As with persistence, an
+/// existing saved profile is activated unbound.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum ActivationBinding {
+    /// The connection stays up until something deactivates it.
+    #[default]
+    None,
+    /// The connection goes down with the requesting D-Bus client.
+    DbusClient,
+}
+
+impl ActivationBinding {
+    /// Value of the `bind-activation` option of `AddAndActivateConnection2`.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::DbusClient => "dbus-client",
+        }
+    }
+}
+
+/// Which MAC address a Wi-Fi or Ethernet profile presents on the link.
+///
+/// Written to `802-11-wireless.cloned-mac-address` or
+/// `802-3-ethernet.cloned-mac-address`. MAC randomization while scanning
+/// (`wifi.scan-rand-mac-address`) is a daemon-wide `NetworkManager.conf`
+/// setting and cannot be set per profile.
+///
+/// # Examples
+///
+/// ```rust
+/// use nmrs::MacPolicy;
+///
+/// assert_eq!(MacPolicy::Stable.as_str(), "stable");
+/// assert!(MacPolicy::Explicit("02:00:00:00:00:01".into()).validate().is_ok());
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MacPolicy {
+    /// The device's permanent (burned-in) address.
+    Permanent,
+    /// Whatever address the device currently has.
+    Preserve,
+    /// A new random address on every activation.
+    Random,
+    /// A random address that stays the same for this profile on this host.
+    Stable,
+    /// A fixed address in `XX:XX:XX:XX:XX:XX` form.
+    Explicit(String),
+}
+
+impl MacPolicy {
+    /// Value of the `cloned-mac-address` property.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Permanent => "permanent",
+            Self::Preserve => "preserve",
+            Self::Random => "random",
+            Self::Stable => "stable",
+            Self::Explicit(mac) => mac,
+        }
+    }
+
+    /// Validates an [`Explicit`](Self::Explicit) address.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConnectionError::InvalidBssid` if the address is not in
+    /// `XX:XX:XX:XX:XX:XX` form.
+    pub fn validate(&self) -> Result<(), ConnectionError> {
+        match self {
+            Self::Explicit(mac) => validate_bssid(mac),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// How much a network is trusted, as offered by the familiar "network trust" prompt.
+///
+/// Each level maps to a combined set of profile defaults:
+///
+/// | Level    | firewalld zone | mDNS / LLMNR | Metered | MAC address |
+/// |----------|----------------|--------------|---------|-------------|
+/// | `Home`   | `home`         | yes          | no      | permanent   |
+/// | `Work`   | `work`         | resolve only | no      | stable      |
+/// | `Public` | `public`       | no           | (guess) | random      |
+///
+/// The MAC policy only applies to Wi-Fi and Ethernet profiles. For `Public`
+/// the metered flag is left for NetworkManager to guess.
+///
+/// # Examples
+///
+/// ```rust
+/// use nmrs::TrustLevel;
+///
+/// assert_eq!(TrustLevel::Work.zone(), "work");
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TrustLevel {
+    /// A private network the user controls.
+    Home,
+    /// A managed network shared with colleagues.
+    Work,
+    /// An untrusted network such as a café or airport hotspot.
+    Public,
+}
+
+impl TrustLevel {
+    /// firewalld zone written to `connection.zone`.
+    #[must_use]
+    pub fn zone(self) -> &'static str {
+        match self {
+            Self::Home => "home",
+            Self::Work => "work",
+            Self::Public => "public",
+        }
+    }
+
+    /// Value for `connection.mdns` and `connection.llmnr`
+    /// (`2` = yes, `1` = resolve only, `0` = no).
+    pub(crate) fn multicast_dns(self) -> i32 {
+        match self {
+            Self::Home => 2,
+            Self::Work => 1,
+            Self::Public => 0,
+        }
+    }
+
+    /// Value for `connection.metered` (`2` = no), or `None` to let NM guess.
+    pub(crate) fn metered(self) -> Option<i32> {
+        match self {
+            Self::Home | Self::Work => Some(2),
+            Self::Public => None,
+        }
+    }
+
+    /// MAC policy for the device section's `cloned-mac-address`.
+    pub(crate) fn mac_policy(self) -> MacPolicy {
+        match self {
+            Self::Home => MacPolicy::Permanent,
+            Self::Work => MacPolicy::Stable,
+            Self::Public => MacPolicy::Random,
+        }
+    }
+}
+
+/// How saved profiles are matched against a name or SSID.
+///
+/// Used by [`NetworkManager::has_saved_connection`](crate::NetworkManager::has_saved_connection),
+/// [`get_saved_connection_path`](crate::NetworkManager::get_saved_connection_path)
+/// and [`forget`](crate::NetworkManager::forget). By default a profile
+/// matches when its `connection.id` equals the name or its
+/// `802-11-wireless.ssid` bytes equal the SSID, so profiles renamed by
+/// other tools (e.g. `"Home 1"` for SSID `"Home"`) are still found.
+///
+/// # Examples
+///
+/// ```rust
+/// use nmrs::MatchOptions;
+///
+/// let opts = MatchOptions::new()
+///     .with_case_insensitive(true)
+///     .with_trim_whitespace(true);
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatchOptions {
+    /// Also match Wi-Fi profiles by their SSID bytes (default: true)
+    pub match_ssid: bool,
+    /// Compare ignoring letter case (default: false)
+    pub case_insensitive: bool,
+    /// Ignore leading and trailing whitespace (default: false)
+    pub trim_whitespace: bool,
+}
+
+impl Default for MatchOptions {
+    fn default() -> Self {
+        Self {
+            match_ssid: true,
+            case_insensitive: false,
+            trim_whitespace: false,
+        }
+    }
+}
+
+impl MatchOptions {
+    /// Creates a new `MatchOptions` with default values.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether Wi-Fi profiles are also matched by SSID.
+    #[must_use]
+    pub fn with_ssid_match(mut self, enabled: bool) -> Self {
+        self.match_ssid = enabled;
+        self
+    }
+
+    /// Sets whether comparisons ignore letter case.
+    #[must_use]
+    pub fn with_case_insensitive(mut self, enabled: bool) -> Self {
+        self.case_insensitive = enabled;
+        self
+    }
+
+    /// Sets whether comparisons ignore surrounding whitespace.
+    #[must_use]
+    pub fn with_trim_whitespace(mut self, enabled: bool) -> Self {
+        self.trim_whitespace = enabled;
+        self
+    }
+
+    /// Compares a profile's id (or other string key) with `target`.
+    pub(crate) fn matches(&self, candidate: &str, target: &str) -> bool {
+        let (candidate, target) = if self.trim_whitespace {
+            (candidate.trim(), target.trim())
+        } else {
+            (candidate, target)
+        };
+        if self.case_insensitive {
+            candidate.to_lowercase() == target.to_lowercase()
+        } else {
+            candidate == target
+        }
+    }
+
+    /// Compares a profile's raw SSID bytes with `target`.
+    pub(crate) fn matches_ssid(&self, ssid: &[u8], target: &str) -> bool {
+        if !self.match_ssid {
+            return false;
+        }
+        ssid == target.as_bytes() || self.matches(&String::from_utf8_lossy(ssid), target)
+    }
 }
@@ -1,5 +1,9 @@
 use std::fmt::{Display, Formatter};
 
+use serde::{Deserialize, Serialize};
+use zvariant::OwnedObjectPath;
+
+use super::device::DeviceState;
 use super::error::ConnectionError;
 
 /// NetworkManager active connection state.
@@ -49,13 +53,221 @@ impl Display for ActiveConnectionState {
     }
 }
 
+/// A connection NetworkManager is activating, has activated or is
+/// deactivating, from its `Connection.Active` object.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveConnection {
+    /// D-Bus path of the active connection object.
+    pub path: OwnedObjectPath,
+    /// D-Bus path of the saved profile it was activated from.
+    pub connection: OwnedObjectPath,
+    /// Profile name (`connection.id`).
+    pub id: String,
+    /// Profile UUID.
+    pub uuid: String,
+    /// Profile type (`connection.type`, e.g. `"802-11-wireless"` or `"vpn"`).
+    pub conn_type: String,
+    /// Activation state.
+    pub state: ActiveConnectionState,
+    /// D-Bus paths of the devices the connection is active on.
+    pub devices: Vec<OwnedObjectPath>,
+    /// `true` if this connection holds the IPv4 default route.
+    pub default: bool,
+    /// `true` if this connection holds the IPv6 default route.
+    pub default6: bool,
+    /// `true` if this is a plugin VPN connection.
+    pub vpn: bool,
+    /// Object the connection was activated with (e.g. the access point),
+    /// or `None` if there is none.
+    pub specific_object: Option<OwnedObjectPath>,
+}
+
+/// Kind of link a connection goes over, from its `connection.type`.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UplinkKind {
+    /// Wi-Fi (`802-11-wireless`).
+    Wifi,
+    /// Wired Ethernet (`802-3-ethernet`).
+    Ethernet,
+    /// Mobile broadband (`gsm` or `cdma`).
+    Wwan,
+    /// VPN (`vpn` or `wireguard`).
+    Vpn,
+    /// Bluetooth PAN or DUN (`bluetooth`).
+    Bluetooth,
+    /// Any other connection type, e.g. `bond` or `vlan`.
+    Other(String),
+}
+
+impl From<&str> for UplinkKind {
+    fn from(conn_type: &str) -> Self {
+        match conn_type {
+            "802-11-wireless" => Self::Wifi,
+            "802-3-ethernet" => Self::Ethernet,
+            "gsm" | "cdma" => Self::Wwan,
+            "vpn" | "wireguard" => Self::Vpn,
+            "bluetooth" => Self::Bluetooth,
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+impl Display for UplinkKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Wifi => write!(f, "Wi-Fi"),
+            Self::Ethernet => write!(f, "Ethernet"),
+            Self::Wwan => write!(f, "mobile broadband"),
+            Self::Vpn => write!(f, "VPN"),
+            Self::Bluetooth => write!(f, "Bluetooth"),
+            Self::Other(conn_type) => write!(f, "{conn_type}"),
+        }
+    }
+}
+
+/// The connection NetworkManager considers the default uplink: the one
+/// holding the default route, or else the first activated one.
+///
+/// Returned by
+/// [`NetworkManager::primary_connection`](crate::NetworkManager::primary_connection).
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrimaryConnection {
+    /// Kind of link, from `PrimaryConnectionType`.
+    pub kind: UplinkKind,
+    /// The primary active connection.
+    pub connection: ActiveConnection,
+}
+
+/// Intermediate step of a connection activation.
+///
+/// NetworkManager walks a device through several states between "connect
+/// requested" and "connected". These are reported to the `on_stage`
+/// callback of [`connect_with_stages`](crate::NetworkManager::connect_with_stages)
+/// so UIs can show which step is in progress instead of a generic
+/// "connecting" spinner.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActivationStage {
+    /// The device is preparing the connection.
+    Prepare,
+    /// The device is being configured (e.g. associating with an AP).
+    Config,
+    /// Credentials are being requested from a secret agent.
+    NeedAuth,
+    /// Requesting IP addresses (DHCP, SLAAC, static).
+    IpConfig,
+    /// Checking whether further action is required for the connection.
+    IpCheck,
+    /// Waiting for secondary connections such as VPNs.
+    Secondaries,
+    /// The connection is fully activated.
+    Activated,
+}
+
+impl ActivationStage {
+    /// Maps a device state to its activation stage.
+    ///
+    /// Returns `None` for states that are not part of activation
+    /// (disconnected, deactivating, failed, ...).
+    #[must_use]
+    pub fn from_device_state(state: DeviceState) -> Option<Self> {
+        match state {
+            DeviceState::Prepare => Some(Self::Prepare),
+            DeviceState::Config => Some(Self::Config),
+            DeviceState::NeedAuth => Some(Self::NeedAuth),
+            DeviceState::IpConfig => Some(Self::IpConfig),
+            DeviceState::IpCheck => Some(Self::IpCheck),
+            DeviceState::Secondaries => Some(Self::Secondaries),
+            DeviceState::Activated => Some(Self::Activated),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ActivationStage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Prepare => write!(f, "preparing"),
+            Self::Config => write!(f, "configuring"),
+            Self::NeedAuth => write!(f, "waiting for credentials"),
+            Self::IpConfig => write!(f, "requesting IP address"),
+            Self::IpCheck => write!(f, "checking connectivity"),
+            Self::Secondaries => write!(f, "starting secondary connections"),
+            Self::Activated => write!(f, "activated"),
+        }
+    }
+}
+
+/// Progress of a Wi-Fi connection attempt.
+///
+/// Yielded by [`connect_with_progress`](crate::NetworkManager::connect_with_progress).
+/// Coarser than [`ActivationStage`], and also covers the scan that precedes
+/// activation. The stream ends with [`Activated`](Self::Activated) or
+/// [`Failed`](Self::Failed).
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub enum ConnectProgress {
+    /// Scanning for the requested network.
+    Scanning,
+    /// The network was found; activation is about to start.
+    ApFound,
+    /// Preparing the device and associating with the access point.
+    Associating,
+    /// Waiting for credentials from a secret agent.
+    Authenticating,
+    /// Obtaining and checking IP configuration.
+    GettingIp,
+    /// The connection is fully activated.
+    Activated,
+    /// The attempt failed.
+    Failed(ConnectionError),
+}
+
+impl ConnectProgress {
+    /// Whether this is the last item of a progress stream.
+    #[must_use]
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Self::Activated | Self::Failed(_))
+    }
+}
+
+impl From<ActivationStage> for ConnectProgress {
+    fn from(stage: ActivationStage) -> Self {
+        match stage {
+            ActivationStage::Prepare | ActivationStage::Config => Self::Associating,
+            ActivationStage::NeedAuth => Self::Authenticating,
+            ActivationStage::IpConfig | ActivationStage::IpCheck | ActivationStage::Secondaries => {
+                Self::GettingIp
+            }
+            ActivationStage::Activated => Self::Activated,
+        }
+    }
+}
+
+impl Display for ConnectProgress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Scanning => write!(f, "scanning"),
+            Self::ApFound => write!(f, "network found"),
+            Self::Associating => write!(f, "associating"),
+            Self::Authenticating => write!(f, "authenticating"),
+            Self::GettingIp => write!(f, "getting IP address"),
+            Self::Activated => write!(f, "activated"),
+            Self::Failed(e) => write!(f, "failed: {e}"),
+        }
+    }
+}
+
 /// NetworkManager active connection state reason codes.
 ///
 /// These values indicate why an active connection transitioned to its
 /// current state. Use `ConnectionStateReason::from(code)` to convert
 /// from the raw u32 values returned by NetworkManager signals.
 #[non_exhaustive]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ConnectionStateReason {
     /// The reason is unknown.
     Unknown,
@@ -155,3 +367,99 @@ pub fn connection_state_reason_to_error(code: u32) -> ConnectionError {
         _ => ConnectionError::ActivationFailed(reason),
     }
 }
+
+/// Reason a plugin VPN changed state, from the `VpnStateChanged` signal.
+///
+/// Unlike [`ConnectionStateReason`], these come from the VPN plugin itself
+/// and tell a rejected login apart from a plugin that failed to start. Use
+/// `VpnStateReason::from(code)` to convert NetworkManager's raw values.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VpnStateReason {
+    /// The reason is unknown.
+    Unknown,
+    /// No specific reason.
+    None,
+    /// User disconnected.
+    UserDisconnected,
+    /// The underlying device disconnected.
+    DeviceDisconnected,
+    /// The VPN service stopped.
+    ServiceStopped,
+    /// The VPN service returned an invalid IP configuration.
+    IpConfigInvalid,
+    /// Connecting to the VPN server timed out.
+    ConnectTimeout,
+    /// The VPN service did not start in time.
+    ServiceStartTimeout,
+    /// The VPN service failed to start.
+    ServiceStartFailed,
+    /// No secrets (password, key passphrase) were provided.
+    NoSecrets,
+    /// The VPN server rejected the login.
+    LoginFailed,
+    /// The connection was removed.
+    ConnectionRemoved,
+    /// Unknown reason code not mapped to a specific variant.
+    Other(u32),
+}
+
+impl From<u32> for VpnStateReason {
+    fn from(code: u32) -> Self {
+        match code {
+            0 => Self::Unknown,
+            1 => Self::None,
+            2 => Self::UserDisconnected,
+            3 => Self::DeviceDisconnected,
+            4 => Self::ServiceStopped,
+            5 => Self::IpConfigInvalid,
+            6 => Self::ConnectTimeout,
+            7 => Self::ServiceStartTimeout,
+            8 => Self::ServiceStartFailed,
+            9 => Self::NoSecrets,
+            10 => Self::LoginFailed,
+            11 => Self::ConnectionRemoved,
+            v => Self::Other(v),
+        }
+    }
+}
+
+impl Display for VpnStateReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unknown => write!(f, "unknown"),
+            Self::None => write!(f, "none"),
+            Self::UserDisconnected => write!(f, "user disconnected"),
+            Self::DeviceDisconnected => write!(f, "device disconnected"),
+            Self::ServiceStopped => write!(f, "VPN service stopped"),
+            Self::IpConfigInvalid => write!(f, "IP configuration invalid"),
+            Self::ConnectTimeout => write!(f, "connection timed out"),
+            Self::ServiceStartTimeout => write!(f, "VPN service start timed out"),
+            Self::ServiceStartFailed => write!(f, "VPN service failed to start"),
+            Self::NoSecrets => write!(f, "no secrets provided"),
+            Self::LoginFailed => write!(f, "login failed"),
+            Self::ConnectionRemoved => write!(f, "connection was removed"),
+            Self::Other(v) => write!(f, "unknown reason ({v})"),
+        }
+    }
+}
+
+/// Converts a VPN state reason code to a specific `ConnectionError`.
+///
+/// Maps missing or rejected credentials to `VpnAuthFailed`, plugin start
+/// and crash failures to `VpnServiceFailed`, timeouts to `Timeout`, and
+/// everything else to `VpnFailed`.
+#[must_use]
+pub fn vpn_state_reason_to_error(code: u32) -> ConnectionError {
+    let reason = VpnStateReason::from(code);
+    match reason {
+        VpnStateReason::NoSecrets | VpnStateReason::LoginFailed => {
+            ConnectionError::VpnAuthFailed(reason)
+        }
+        VpnStateReason::ServiceStopped
+        | VpnStateReason::ServiceStartTimeout
+        | VpnStateReason::ServiceStartFailed => ConnectionError::VpnServiceFailed(reason),
+        VpnStateReason::ConnectTimeout => ConnectionError::Timeout,
+        _ => ConnectionError::VpnFailed(reason.to_string()),
+    }
+}
@@ -11,6 +11,8 @@
 
 use std::fmt;
 
+use super::ActiveConnectionState;
+
 /// NM's `NMConnectivityState` enum.
 #[non_exhaustive]
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
@@ -86,6 +88,10 @@ impl fmt::Display for ConnectivityState {
 pub struct ConnectivityReport {
     /// Current connectivity state.
     pub state: ConnectivityState,
+    /// Whether checking is possible at all; `false` when no check URI is
+    /// configured, in which case the state stays
+    /// [`Unknown`](ConnectivityState::Unknown).
+    pub check_available: bool,
     /// Whether NM is allowed to probe.
     pub check_enabled: bool,
     /// URL NM probes when checking (may be empty if disabled).
@@ -95,6 +101,40 @@ pub struct ConnectivityReport {
     pub captive_portal_url: Option<String>,
 }
 
+/// Result of the post-resume check run by
+/// [`crate::NetworkManager::resume_hook`].
+///
+/// After a suspend/resume cycle NM's cached scan results and the active
+/// connection are often stale. The hook rescans and re-validates the primary
+/// connection, then hands this report to the caller so it can reconnect when
+/// [`is_stale`](Self::is_stale) returns `true`.
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct ResumeReport {
+    /// Whether the post-resume Wi-Fi rescan was accepted by NM.
+    pub rescanned: bool,
+    /// Connectivity after resume (re-probed when checking is enabled).
+    pub connectivity: ConnectivityState,
+    /// Name (`connection.id`) of the primary connection, if any.
+    pub primary_connection: Option<String>,
+    /// State of the primary connection, if any.
+    pub primary_state: Option<ActiveConnectionState>,
+}
+
+impl ResumeReport {
+    /// `true` when the connection did not survive the suspend cycle: there is
+    /// no activated primary connection, or connectivity is neither full nor a
+    /// captive portal.
+    #[must_use]
+    pub fn is_stale(&self) -> bool {
+        self.primary_state != Some(ActiveConnectionState::Activated)
+            || !matches!(
+                self.connectivity,
+                ConnectivityState::Full | ConnectivityState::Portal | ConnectivityState::Unknown
+            )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -126,4 +166,34 @@ mod tests {
         assert!(!ConnectivityState::Limited.is_usable_for_internet());
         assert!(!ConnectivityState::Unknown.is_usable_for_internet());
     }
+
+    fn report(
+        connectivity: ConnectivityState,
+        primary_state: Option<ActiveConnectionState>,
+    ) -> ResumeReport {
+        ResumeReport {
+            rescanned: true,
+            connectivity,
+            primary_connection: primary_state.map(|_| "Home".into()),
+            primary_state,
+        }
+    }
+
+    #[test]
+    fn resume_report_staleness() {
+        let activated = Some(ActiveConnectionState::Activated);
+        assert!(!report(ConnectivityState::Full, activated).is_stale());
+        assert!(!report(ConnectivityState::Portal, activated).is_stale());
+        assert!(!report(ConnectivityState::Unknown, activated).is_stale());
+        assert!(report(ConnectivityState::Limited, activated).is_stale());
+        assert!(report(ConnectivityState::None, activated).is_stale());
+        assert!(report(ConnectivityState::Full, None).is_stale());
+        assert!(
+            report(
+                ConnectivityState::Full,
+                Some(ActiveConnectionState::Activating)
+            )
+            .is_stale()
+        );
+    }
 }
@@ -1,7 +1,10 @@
 use std::fmt::{Display, Formatter};
 
+use serde::{Deserialize, Serialize};
 use zvariant::OwnedObjectPath;
 
+use super::state_reason::StateReason;
+
 /// Represents a network device managed by NetworkManager.
 ///
 /// A device can be a WiFi adapter, Ethernet interface, or other network hardware.
@@ -39,7 +42,7 @@ use zvariant::OwnedObjectPath;
 /// # }
 /// ```
 #[non_exhaustive]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Device {
     /// D-Bus object path
     pub path: String,
@@ -51,6 +54,8 @@ pub struct Device {
     pub device_type: DeviceType,
     /// Current device state
     pub state: DeviceState,
+    /// Reason for the last state change (e.g. why it is `Failed` or `Disconnected`)
+    pub state_reason: StateReason,
     /// Whether NetworkManager manages this device
     pub managed: Option<bool>,
     /// Kernel driver name
@@ -59,6 +64,8 @@ pub struct Device {
     pub ip4_address: Option<String>,
     /// Assigned IPv6 address with CIDR notation (only present when connected)
     pub ip6_address: Option<String>,
+    /// Why the device is unavailable (only present in the `Unavailable` state)
+    pub unavailable_reason: Option<UnavailableReason>,
     // Link speed in Mb/s (wired devices)
     // pub speed: Option<u32>,
 }
@@ -102,7 +109,7 @@ pub struct WifiDevice {
 /// MAC is burned into the hardware, while the current MAC may be different
 /// if MAC address randomization or spoofing is enabled.
 #[non_exhaustive]
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct DeviceIdentity {
     /// The permanent (factory-assigned) MAC address.
     pub permanent_mac: String,
@@ -124,6 +131,176 @@ impl DeviceIdentity {
             current_mac,
         }
     }
+
+    /// Returns `true` if both identities refer to the same physical device.
+    ///
+    /// Compares permanent MACs (case-insensitive), which survive interface
+    /// renames and USB re-plugs. Falls back to the current MAC when either
+    /// side has no usable permanent MAC (all zeros or empty).
+    #[must_use]
+    pub fn same_hardware(&self, other: &DeviceIdentity) -> bool {
+        fn usable(mac: &str) -> bool {
+            !mac.is_empty() && mac != "00:00:00:00:00:00"
+        }
+
+        if usable(&self.permanent_mac) && usable(&other.permanent_mac) {
+            self.permanent_mac
+                .eq_ignore_ascii_case(&other.permanent_mac)
+        } else {
+            usable(&self.current_mac) && self.current_mac.eq_ignore_ascii_case(&other.current_mac)
+        }
+    }
+}
+
+/// Why a managed device is in the [`DeviceState::Unavailable`] state.
+///
+/// Lets UIs explain a dead-looking interface ("firmware missing", "radio is
+/// switched off") instead of just showing "Unavailable".
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UnavailableReason {
+    /// The driver could not load the device firmware.
+    FirmwareMissing,
+    /// The NetworkManager plugin for this device type is not installed.
+    PluginMissing,
+    /// The radio is blocked by a hardware switch or rfkill hard block.
+    RadioHardwareBlocked,
+    /// The radio is disabled in software (e.g. airplane mode).
+    RadioDisabled,
+    /// No cable is plugged in (wired devices).
+    NoCarrier,
+    /// NetworkManager did not expose a more specific cause.
+    Unknown,
+}
+
+impl UnavailableReason {
+    /// Picks the most specific reason from the individual signals.
+    ///
+    /// `radio` is `(software_enabled, hardware_enabled)` for devices backed
+    /// by a radio (Wi-Fi, WWAN) and `carrier` is the link state for wired
+    /// devices; pass `None` when they do not apply.
+    #[must_use]
+    pub fn classify(
+        firmware_missing: bool,
+        plugin_missing: bool,
+        radio: Option<(bool, bool)>,
+        carrier: Option<bool>,
+    ) -> Self {
+        if firmware_missing {
+            return Self::FirmwareMissing;
+        }
+        if plugin_missing {
+            return Self::PluginMissing;
+        }
+        match (radio, carrier) {
+            (Some((_, false)), _) => Self::RadioHardwareBlocked,
+            (Some((false, true)), _) => Self::RadioDisabled,
+            (_, Some(false)) => Self::NoCarrier,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+impl Display for UnavailableReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FirmwareMissing => write!(f, "firmware missing"),
+            Self::PluginMissing => write!(f, "NetworkManager plugin missing"),
+            Self::RadioHardwareBlocked => write!(f, "radio blocked by hardware switch"),
+            Self::RadioDisabled => write!(f, "radio disabled"),
+            Self::NoCarrier => write!(f, "cable unplugged"),
+            Self::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// A device NetworkManager knows about but does not manage.
+///
+/// Returned by [`list_unmanaged_devices`](crate::NetworkManager::list_unmanaged_devices)
+/// to help explain why an interface is missing from the usual device lists.
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct UnmanagedDevice {
+    /// The device itself.
+    pub device: Device,
+    /// Why NetworkManager is not managing it.
+    pub reason: UnmanagedReason,
+}
+
+/// Why NetworkManager leaves a device unmanaged.
+///
+/// Derived from the device's `StateReason`. NetworkManager 1.48 and newer
+/// report a dedicated reason for each unmanaged source; older versions only
+/// say "unmanaged", in which case [`Unknown`](Self::Unknown) is returned
+/// (or [`ByDefault`](Self::ByDefault) for loopback).
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UnmanagedReason {
+    /// The device type is unmanaged by default (loopback, veth, ...).
+    ByDefault,
+    /// The link is down and owned by an external tool.
+    ExternalDown,
+    /// udev has not finished initializing the link yet.
+    LinkNotInitialized,
+    /// NetworkManager is shutting down.
+    Quitting,
+    /// NetworkManager is asleep (suspend or `nmcli networking off` on old versions).
+    Sleeping,
+    /// Excluded by `unmanaged-devices` in `NetworkManager.conf`.
+    UserConfig,
+    /// Explicitly set unmanaged at runtime (`nmcli device set <iface> managed no`).
+    UserExplicit,
+    /// Excluded by a connection/keyfile setting.
+    UserSettings,
+    /// Excluded by a udev rule (`ENV{NM_UNMANAGED}=1`).
+    Udev,
+    /// Networking is disabled globally.
+    NetworkingOff,
+    /// NetworkManager did not report a specific reason.
+    Unknown,
+    /// Unrecognized state reason code.
+    Other(u32),
+}
+
+impl UnmanagedReason {
+    /// Maps a device `StateReason` code to an unmanaged reason.
+    ///
+    /// Decodes `code` with [`StateReason::from`], so both agree on the
+    /// `NM_DEVICE_STATE_REASON_UNMANAGED_*` values. `device_type` is used as
+    /// a fallback hint when NetworkManager does not report a specific
+    /// unmanaged reason.
+    #[must_use]
+    pub fn from_state_reason(code: u32, device_type: &DeviceType) -> Self {
+        match StateReason::from(code) {
+            StateReason::Unmanaged(reason) => reason,
+            StateReason::Sleeping => Self::Sleeping,
+            _ if matches!(device_type, DeviceType::Loopback) => Self::ByDefault,
+            StateReason::None
+            | StateReason::Unknown
+            | StateReason::NowManaged
+            | StateReason::NowUnmanaged => Self::Unknown,
+            _ => Self::Other(code),
+        }
+    }
+}
+
+impl Display for UnmanagedReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ByDefault => write!(f, "unmanaged by default for this device type"),
+            Self::ExternalDown => write!(f, "link is down and externally managed"),
+            Self::LinkNotInitialized => write!(f, "link not initialized by udev"),
+            Self::Quitting => write!(f, "NetworkManager is quitting"),
+            Self::Sleeping => write!(f, "NetworkManager is sleeping"),
+            Self::UserConfig => write!(f, "excluded by NetworkManager.conf"),
+            Self::UserExplicit => write!(f, "set unmanaged by user"),
+            Self::UserSettings => write!(f, "excluded by connection settings"),
+            Self::Udev => write!(f, "excluded by udev rule"),
+            Self::NetworkingOff => write!(f, "networking is disabled"),
+            Self::Unknown => write!(f, "unknown"),
+            Self::Other(v) => write!(f, "state reason {v}"),
+        }
+    }
 }
 
 /// NetworkManager device types.
@@ -132,7 +309,7 @@ impl DeviceIdentity {
 /// This enum uses a registry-based system to support adding new device
 /// types without breaking the API.
 #[non_exhaustive]
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum DeviceType {
     /// Wired Ethernet device.
     Ethernet,
@@ -146,6 +323,8 @@ pub enum DeviceType {
     Bluetooth,
     /// VLAN (802.1Q) virtual device.
     Vlan,
+    /// Mobile broadband (WWAN) modem managed through ModemManager.
+    Modem,
     /// Unknown or unsupported device type with raw code.
     ///
     /// Use the methods on `DeviceType` to query capabilities of unknown device types,
@@ -185,12 +364,13 @@ impl DeviceType {
 
     /// Returns whether this device type has a global enabled/disabled state.
     ///
-    /// WiFi has a global radio killswitch that can enable/disable all WiFi devices.
+    /// WiFi has a global radio killswitch that can enable/disable all WiFi devices,
+    /// and modems share the global WWAN switch.
     /// For unknown device types, consults the internal device type registry.
     #[must_use]
     pub fn has_global_enabled_state(&self) -> bool {
         match self {
-            Self::Wifi => true,
+            Self::Wifi | Self::Modem => true,
             Self::Other(code) => {
                 crate::types::device_type_registry::has_global_enabled_state(*code)
             }
@@ -211,6 +391,7 @@ impl DeviceType {
             Self::Loopback => "loopback",
             Self::Bluetooth => "bluetooth",
             Self::Vlan => "vlan",
+            Self::Modem => "gsm",
             Self::Other(code) => {
                 crate::types::device_type_registry::connection_type_for_code(*code)
                     .unwrap_or("generic")
@@ -228,6 +409,7 @@ impl DeviceType {
             Self::Loopback => 32,
             Self::Bluetooth => 6,
             Self::Vlan => 11,
+            Self::Modem => 8,
             Self::Other(code) => *code,
         }
     }
@@ -237,7 +419,7 @@ impl DeviceType {
 ///
 /// Represents the current operational state of a network device.
 #[non_exhaustive]
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum DeviceState {
     /// Device is not managed by NetworkManager.
     Unmanaged,
@@ -318,6 +500,12 @@ impl Device {
     pub fn is_vlan(&self) -> bool {
         matches!(self.device_type, DeviceType::Vlan)
     }
+
+    /// Returns `true` if this is a mobile broadband (WWAN) modem.
+    #[must_use]
+    pub fn is_modem(&self) -> bool {
+        matches!(self.device_type, DeviceType::Modem)
+    }
 }
 
 impl Display for Device {
@@ -336,6 +524,7 @@ impl From<u32> for DeviceType {
             1 => DeviceType::Ethernet,
             2 => DeviceType::Wifi,
             5 => DeviceType::Bluetooth,
+            8 => DeviceType::Modem,
             11 => DeviceType::Vlan,
             30 => DeviceType::WifiP2P,
             32 => DeviceType::Loopback,
@@ -364,6 +553,41 @@ impl From<u32> for DeviceState {
     }
 }
 
+impl From<DeviceType> for u32 {
+    fn from(value: DeviceType) -> Self {
+        match value {
+            DeviceType::Ethernet => 1,
+            DeviceType::Wifi => 2,
+            DeviceType::Bluetooth => 5,
+            DeviceType::Modem => 8,
+            DeviceType::Vlan => 11,
+            DeviceType::WifiP2P => 30,
+            DeviceType::Loopback => 32,
+            DeviceType::Other(v) => v,
+        }
+    }
+}
+
+impl From<DeviceState> for u32 {
+    fn from(value: DeviceState) -> Self {
+        match value {
+            DeviceState::Unmanaged => 10,
+            DeviceState::Unavailable => 20,
+            DeviceState::Disconnected => 30,
+            DeviceState::Prepare => 40,
+            DeviceState::Config => 50,
+            DeviceState::NeedAuth => 60,
+            DeviceState::IpConfig => 70,
+            DeviceState::IpCheck => 80,
+            DeviceState::Secondaries => 90,
+            DeviceState::Activated => 100,
+            DeviceState::Deactivating => 110,
+            DeviceState::Failed => 120,
+            DeviceState::Other(v) => v,
+        }
+    }
+}
+
 impl Display for DeviceType {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -373,6 +597,7 @@ impl Display for DeviceType {
             DeviceType::Loopback => write!(f, "Loopback"),
             DeviceType::Bluetooth => write!(f, "Bluetooth"),
             DeviceType::Vlan => write!(f, "VLAN"),
+            DeviceType::Modem => write!(f, "Modem"),
             DeviceType::Other(v) => write!(
                 f,
                 "{}",
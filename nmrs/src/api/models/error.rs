@@ -1,8 +1,9 @@
+use serde::ser::{Serialize, SerializeStruct, Serializer};
 use thiserror::Error;
 
 use crate::core::ovpn_parser::error::OvpnParseError;
 
-use super::connection_state::ConnectionStateReason;
+use super::connection_state::{ConnectionStateReason, VpnStateReason};
 use super::state_reason::StateReason;
 
 /// Errors that can occur during network operations.
@@ -41,34 +42,56 @@ use super::state_reason::StateReason;
 ///
 /// ## Retry Logic
 ///
+/// [`NetworkManager::connect_with_policy`](crate::NetworkManager::connect_with_policy)
+/// retries transient failures for you; [`class`](Self::class) tells
+/// them apart when writing your own loop.
+///
 /// ```no_run
-/// use nmrs::{NetworkManager, WifiSecurity, ConnectionError};
+/// use nmrs::{ConnectPolicy, NetworkManager, WifiSecurity};
 ///
 /// # async fn example() -> nmrs::Result<()> {
 /// let nm = NetworkManager::new().await?;
+/// nm.connect_with_policy("MyNetwork", None, WifiSecurity::Open, ConnectPolicy::new())
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
 ///
-/// for attempt in 1..=3 {
-///     match nm.connect("MyNetwork", None, WifiSecurity::Open).await {
-///         Ok(_) => {
-///             println!("Connected on attempt {}", attempt);
-///             break;
-///         }
-///         Err(ConnectionError::Timeout) if attempt < 3 => {
-///             println!("Timeout, retrying...");
-///             continue;
-///         }
-///         Err(e) => return Err(e),
-///     }
+/// ## NetworkManager Errors
+///
+/// Well-known D-Bus errors from NetworkManager are mapped to typed
+/// variants, so there is no need to inspect error names:
+///
+/// ```no_run
+/// use nmrs::{ConnectionError, NetworkManager};
+///
+/// # async fn example() -> nmrs::Result<()> {
+/// let nm = NetworkManager::new().await?;
+/// match nm.forget("Office").await {
+///     Err(ConnectionError::PermissionDenied(msg)) => eprintln!("not allowed: {msg}"),
+///     other => other?,
 /// }
 /// # Ok(())
 /// # }
 /// ```
+///
+/// ## Serialization
+///
+/// Errors serialize as `{"kind": "<variant>", "message": "<Display>"}`, e.g.
+/// `{"kind":"AuthFailed","message":"authentication failed"}`. They cannot
+/// be deserialized, as the underlying D-Bus errors cannot be rebuilt.
 #[non_exhaustive]
-#[derive(Debug, Error)]
+#[derive(Debug, Clone, Error)]
 pub enum ConnectionError {
     /// A D-Bus communication error occurred.
+    ///
+    /// Well-known NetworkManager errors are reported as
+    /// [`UnknownDevice`](Self::UnknownDevice),
+    /// [`PermissionDenied`](Self::PermissionDenied),
+    /// [`MissingPlugin`](Self::MissingPlugin) or
+    /// [`ConnectionInvalid`](Self::ConnectionInvalid) instead.
     #[error("D-Bus error: {0}")]
-    Dbus(#[from] zbus::Error),
+    Dbus(zbus::Error),
 
     /// The requested network was not found during scan.
     #[error("network not found")]
@@ -182,10 +205,27 @@ pub enum ConnectionError {
     #[error("VPN connection failed: {0}")]
     VpnFailed(String),
 
+    /// The VPN plugin reported missing or rejected credentials.
+    #[error("VPN authentication failed: {0}")]
+    VpnAuthFailed(VpnStateReason),
+
+    /// The VPN plugin failed to start or stopped unexpectedly, e.g. because
+    /// it is not installed.
+    #[error("VPN service failed: {0}")]
+    VpnServiceFailed(VpnStateReason),
+
     /// Bluetooth device not found.
     #[error("Bluetooth device not found")]
     NoBluetoothDevice,
 
+    /// No Wi-Fi P2P (Wi-Fi Direct) device found.
+    #[error("no Wi-Fi P2P device found")]
+    NoWifiP2pDevice,
+
+    /// No mobile broadband modem found.
+    #[error("no mobile broadband modem found")]
+    NoModemDevice,
+
     /// A D-Bus operation failed, with context about what was being attempted.
     #[error("{context}: {source}")]
     DbusOperation {
@@ -196,6 +236,26 @@ pub enum ConnectionError {
         source: zbus::Error,
     },
 
+    /// NetworkManager does not know the device the request referred to
+    /// (`org.freedesktop.NetworkManager.UnknownDevice`).
+    #[error("unknown device: {0}")]
+    UnknownDevice(String),
+
+    /// Polkit or the D-Bus policy denied the request (any NetworkManager
+    /// `PermissionDenied` error, or `org.freedesktop.DBus.Error.AccessDenied`).
+    #[error("permission denied: {0}")]
+    PermissionDenied(String),
+
+    /// A NetworkManager plugin the request needs, such as a VPN or device
+    /// plugin, is not installed (`org.freedesktop.NetworkManager.MissingPlugin`).
+    #[error("missing plugin: {0}")]
+    MissingPlugin(String),
+
+    /// NetworkManager rejected the profile's settings as invalid
+    /// (`InvalidConnection`, or a missing or invalid setting or property).
+    #[error("invalid connection: {0}")]
+    ConnectionInvalid(String),
+
     /// Secret agent registration with NetworkManager failed.
     #[error("secret agent registration failed: {context}")]
     AgentRegistration {
@@ -215,6 +275,10 @@ pub enum ConnectionError {
     #[error("error while parsing a configuration: {0}")]
     ParseError(OvpnParseError),
 
+    /// An `.nmconnection` keyfile or wg-quick config could not be parsed.
+    #[error("error while parsing a keyfile: {0}")]
+    KeyfileParse(#[from] crate::keyfile::KeyfileError),
+
     /// Access point with the given SSID and BSSID was not found.
     #[error("access point for SSID '{ssid}' with BSSID '{bssid}' not found")]
     ApBssidNotFound {
@@ -242,6 +306,20 @@ pub enum ConnectionError {
         interface: String,
     },
 
+    /// Interface exists but is not an Ethernet device.
+    #[error("interface '{interface}' is not a wired device")]
+    NotAWiredDevice {
+        /// The interface name that was checked.
+        interface: String,
+    },
+
+    /// No wired device with the given interface name.
+    #[error("no wired device named '{interface}'")]
+    WiredInterfaceNotFound {
+        /// The interface name that was searched for.
+        interface: String,
+    },
+
     /// A radio is hardware-disabled via rfkill.
     #[error("radio is hardware-disabled (rfkill)")]
     HardwareRadioKilled,
@@ -261,6 +339,27 @@ pub enum ConnectionError {
         id: u16,
     },
 
+    /// The network is behind a captive portal; the user must log in at `url`.
+    #[error("captive portal login required at {url}")]
+    CaptivePortal {
+        /// Login page (or the probe URL the portal intercepts).
+        url: String,
+    },
+
+    /// The captive portal probe request could not be completed.
+    #[error("captive portal probe failed: {0}")]
+    PortalProbeFailed(String),
+
+    /// Writing exported events failed, or the export socket stopped
+    /// accepting clients.
+    #[error("event export failed: {0}")]
+    EventExportFailed(String),
+
+    /// The operation was cancelled through a
+    /// [`ConnectHandle`](crate::ConnectHandle).
+    #[error("operation cancelled")]
+    Cancelled,
+
     /// Invalid input for a configuration field.
     #[error("invalid {field}: {reason}")]
     InvalidInput {
@@ -270,3 +369,74 @@ pub enum ConnectionError {
         reason: String,
     },
 }
+
+/// Broad category of a [`ConnectionError`], used to decide whether a failed
+/// operation is worth retrying.
+///
+/// See [`ConnectionError::class`] and [`ConnectPolicy`](crate::ConnectPolicy).
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorClass {
+    /// Activation timed out or got stuck.
+    Timeout,
+    /// The supplicant timed out while authenticating, often due to a weak
+    /// signal rather than wrong credentials.
+    SupplicantTimeout,
+    /// DHCP did not provide an address.
+    Dhcp,
+    /// The network or access point was not visible.
+    NotFound,
+    /// Credentials were rejected or missing.
+    Auth,
+    /// The device or activation failed for another reason.
+    Activation,
+    /// Talking to NetworkManager over D-Bus failed.
+    Dbus,
+    /// Invalid input, missing hardware, cancellation and everything else.
+    Other,
+}
+
+impl ConnectionError {
+    /// The [`ErrorClass`] this error belongs to.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nmrs::{ConnectionError, ErrorClass};
+    ///
+    /// assert_eq!(ConnectionError::DhcpFailed.class(), ErrorClass::Dhcp);
+    /// assert_eq!(ConnectionError::AuthFailed.class(), ErrorClass::Auth);
+    /// ```
+    #[must_use]
+    pub fn class(&self) -> ErrorClass {
+        match self {
+            Self::Timeout | Self::Stuck(_) => ErrorClass::Timeout,
+            Self::SupplicantTimeout => ErrorClass::SupplicantTimeout,
+            Self::DhcpFailed => ErrorClass::Dhcp,
+            Self::NotFound | Self::ApBssidNotFound { .. } => ErrorClass::NotFound,
+            Self::AuthFailed | Self::MissingPassword | Self::VpnAuthFailed(_) => ErrorClass::Auth,
+            Self::DeviceFailed(_)
+            | Self::ActivationFailed(_)
+            | Self::VpnFailed(_)
+            | Self::VpnServiceFailed(_)
+            | Self::WifiNotReady => ErrorClass::Activation,
+            Self::Dbus(_) | Self::DbusOperation { .. } => ErrorClass::Dbus,
+            _ => ErrorClass::Other,
+        }
+    }
+}
+
+impl Serialize for ConnectionError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // The variant name is the leading identifier of the derived `Debug`.
+        let debug = format!("{self:?}");
+        let kind = debug
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .next()
+            .unwrap_or_default();
+        let mut error = serializer.serialize_struct("ConnectionError", 2)?;
+        error.serialize_field("kind", kind)?;
+        error.serialize_field("message", &self.to_string())?;
+        error.end()
+    }
+}
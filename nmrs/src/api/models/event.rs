@@ -0,0 +1,72 @@
+use zvariant::OwnedObjectPath;
+
+use super::{CheckpointEvent, ConnectivityState, DeviceState, StateReason};
+
+/// A change reported by [`NetworkManager::events`](crate::NetworkManager::events).
+///
+/// Object paths identify devices and access points; they match
+/// [`Device::path`](crate::Device::path) and
+/// [`AccessPoint::path`](crate::AccessPoint::path).
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+pub enum NetworkEvent {
+    /// NetworkManager started managing a new device.
+    DeviceAdded {
+        /// Device object path.
+        path: OwnedObjectPath,
+    },
+    /// A device disappeared.
+    DeviceRemoved {
+        /// Device object path.
+        path: OwnedObjectPath,
+    },
+    /// A Wi-Fi device saw a new access point.
+    AccessPointAdded {
+        /// Wi-Fi device object path.
+        device: OwnedObjectPath,
+        /// Access point object path.
+        path: OwnedObjectPath,
+    },
+    /// An access point went out of range.
+    AccessPointRemoved {
+        /// Wi-Fi device object path.
+        device: OwnedObjectPath,
+        /// Access point object path.
+        path: OwnedObjectPath,
+    },
+    /// A device moved through its connection lifecycle (connecting,
+    /// activated, disconnected, failed, ...).
+    ConnectionStateChanged {
+        /// Device object path.
+        device: OwnedObjectPath,
+        /// Interface name of the device.
+        interface: String,
+        /// New state.
+        state: DeviceState,
+        /// Previous state.
+        previous: DeviceState,
+        /// Why the state changed.
+        reason: StateReason,
+    },
+    /// The Wi-Fi radio was switched on or off.
+    WirelessEnabledChanged(bool),
+    /// NetworkManager's connectivity assessment changed.
+    ConnectivityChanged(ConnectivityState),
+    /// The NetworkManager daemon stopped or came back.
+    ///
+    /// After [`DaemonEvent::Restarted`] the stream has re-subscribed to the
+    /// new instance; object paths seen before the restart are stale.
+    Daemon(DaemonEvent),
+    /// A checkpoint was created, rolled back or destroyed.
+    Checkpoint(CheckpointEvent),
+}
+
+/// Lifecycle change of the NetworkManager daemon.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DaemonEvent {
+    /// NetworkManager left the bus (stopped or crashed).
+    Stopped,
+    /// A NetworkManager instance (re)appeared on the bus.
+    Restarted,
+}
@@ -0,0 +1,25 @@
+use std::time::SystemTime;
+
+use super::{ConnectionError, StateReason};
+
+/// The most recent activation failure on a device.
+///
+/// Returned by [`NetworkManager::last_failure`](crate::NetworkManager::last_failure).
+/// Failures of connection attempts made through this crate carry the error,
+/// profile and time. Failures nmrs did not witness (e.g. NetworkManager
+/// autoconnecting before the app started) are reconstructed from the
+/// device's last state reason and leave those fields empty.
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct ConnectionFailure {
+    /// Interface the activation failed on.
+    pub interface: String,
+    /// SSID or profile name being activated, when known.
+    pub profile: Option<String>,
+    /// Error returned to the caller, for attempts made through nmrs.
+    pub error: Option<ConnectionError>,
+    /// Device state reason reported by NetworkManager.
+    pub reason: StateReason,
+    /// When the failure was recorded, for attempts made through nmrs.
+    pub at: Option<SystemTime>,
+}
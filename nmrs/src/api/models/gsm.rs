@@ -0,0 +1,60 @@
+//! Mobile broadband (GSM/LTE) connection configuration.
+
+/// Credentials and APN for a GSM/UMTS/LTE mobile broadband connection.
+///
+/// Most carriers only need the APN; username, password and SIM PIN are
+/// optional.
+///
+/// # Examples
+///
+/// ```rust
+/// use nmrs::GsmCredentials;
+///
+/// let creds = GsmCredentials::new("internet.carrier.example")
+///     .with_pin("1234");
+/// assert_eq!(creds.apn, "internet.carrier.example");
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GsmCredentials {
+    /// Access Point Name provided by the carrier.
+    pub apn: String,
+    /// Username, if the carrier requires one.
+    pub username: Option<String>,
+    /// Password, if the carrier requires one.
+    pub password: Option<String>,
+    /// SIM PIN, if the SIM is locked.
+    pub pin: Option<String>,
+}
+
+impl GsmCredentials {
+    /// Creates credentials for `apn` without username, password or PIN.
+    #[must_use]
+    pub fn new(apn: impl Into<String>) -> Self {
+        Self {
+            apn: apn.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Sets the username.
+    #[must_use]
+    pub fn with_username(mut self, username: impl Into<String>) -> Self {
+        self.username = Some(username.into());
+        self
+    }
+
+    /// Sets the password.
+    #[must_use]
+    pub fn with_password(mut self, password: impl Into<String>) -> Self {
+        self.password = Some(password.into());
+        self
+    }
+
+    /// Sets the SIM PIN.
+    #[must_use]
+    pub fn with_pin(mut self, pin: impl Into<String>) -> Self {
+        self.pin = Some(pin.into());
+        self
+    }
+}
@@ -0,0 +1,207 @@
+use super::vpn::{VpnConfig, VpnKind};
+use uuid::Uuid;
+
+/// How the client authenticates to an IKEv2 gateway.
+///
+/// Maps to the strongSwan plugin's `method` key in `vpn.data`.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Ikev2Auth {
+    /// Pre-shared key (`method = psk`).
+    Psk {
+        /// The shared secret.
+        psk: String,
+    },
+    /// Client certificate and private key (`method = key`).
+    Certificate {
+        /// Path to the client certificate.
+        cert: String,
+        /// Path to the client private key.
+        key: String,
+        /// Passphrase for an encrypted private key.
+        key_password: Option<String>,
+    },
+    /// EAP username/password (`method = eap`), typically EAP-MSCHAPv2.
+    Eap {
+        /// EAP identity.
+        username: String,
+        /// EAP password. Left to the secret agent when `None`.
+        password: Option<String>,
+    },
+}
+
+/// IKEv2 connection configuration for the strongSwan NetworkManager plugin.
+///
+/// # Example
+///
+/// ```rust
+/// use nmrs::{Ikev2Auth, Ikev2Config};
+///
+/// let config = Ikev2Config::new(
+///     "WorkVPN",
+///     "vpn.example.com",
+///     Ikev2Auth::Eap {
+///         username: "alice".into(),
+///         password: Some("secret".into()),
+///     },
+/// )
+/// .with_ca_cert("/etc/ipsec.d/cacerts/ca.pem")
+/// .with_dns(vec!["10.0.0.1".into()]);
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct Ikev2Config {
+    /// Connection name.
+    pub name: String,
+    /// Gateway hostname or IP address.
+    pub gateway: String,
+    /// Authentication method and credentials.
+    pub auth: Ikev2Auth,
+    /// Local identity (IKE ID) sent to the gateway.
+    pub identity: Option<String>,
+    /// Expected identity of the gateway, if it differs from `gateway`.
+    pub remote_identity: Option<String>,
+    /// Path to the CA or gateway certificate used to authenticate the server.
+    pub ca_cert: Option<String>,
+    /// Request an inner IP address from the gateway.
+    pub virtual_ip: bool,
+    /// Force UDP encapsulation of ESP packets (NAT traversal).
+    pub udp_encapsulation: bool,
+    /// Enable IP payload compression.
+    pub ipcomp: bool,
+    /// IKE proposal (e.g. `"aes256-sha256-modp2048"`).
+    pub ike_proposal: Option<String>,
+    /// ESP proposal (e.g. `"aes256-sha256"`).
+    pub esp_proposal: Option<String>,
+    /// DNS servers to use when connected.
+    pub dns: Option<Vec<String>>,
+    /// MTU size.
+    pub mtu: Option<u32>,
+    /// Connection UUID.
+    pub uuid: Option<Uuid>,
+}
+
+impl Ikev2Config {
+    /// Creates a new `Ikev2Config` with required fields.
+    ///
+    /// A virtual IP is requested by default, matching the plugin's defaults.
+    pub fn new(name: impl Into<String>, gateway: impl Into<String>, auth: Ikev2Auth) -> Self {
+        Self {
+            name: name.into(),
+            gateway: gateway.into(),
+            auth,
+            identity: None,
+            remote_identity: None,
+            ca_cert: None,
+            virtual_ip: true,
+            udp_encapsulation: false,
+            ipcomp: false,
+            ike_proposal: None,
+            esp_proposal: None,
+            dns: None,
+            mtu: None,
+            uuid: None,
+        }
+    }
+
+    /// Sets the local identity.
+    #[must_use]
+    pub fn with_identity(mut self, identity: impl Into<String>) -> Self {
+        self.identity = Some(identity.into());
+        self
+    }
+
+    /// Sets the expected gateway identity.
+    #[must_use]
+    pub fn with_remote_identity(mut self, identity: impl Into<String>) -> Self {
+        self.remote_identity = Some(identity.into());
+        self
+    }
+
+    /// Sets the CA or gateway certificate path.
+    #[must_use]
+    pub fn with_ca_cert(mut self, path: impl Into<String>) -> Self {
+        self.ca_cert = Some(path.into());
+        self
+    }
+
+    /// Sets whether to request a virtual IP from the gateway.
+    #[must_use]
+    pub fn with_virtual_ip(mut self, enabled: bool) -> Self {
+        self.virtual_ip = enabled;
+        self
+    }
+
+    /// Sets whether to force UDP encapsulation.
+    #[must_use]
+    pub fn with_udp_encapsulation(mut self, enabled: bool) -> Self {
+        self.udp_encapsulation = enabled;
+        self
+    }
+
+    /// Sets whether to enable IP payload compression.
+    #[must_use]
+    pub fn with_ipcomp(mut self, enabled: bool) -> Self {
+        self.ipcomp = enabled;
+        self
+    }
+
+    /// Sets the IKE proposal.
+    #[must_use]
+    pub fn with_ike_proposal(mut self, proposal: impl Into<String>) -> Self {
+        self.ike_proposal = Some(proposal.into());
+        self
+    }
+
+    /// Sets the ESP proposal.
+    #[must_use]
+    pub fn with_esp_proposal(mut self, proposal: impl Into<String>) -> Self {
+        self.esp_proposal = Some(proposal.into());
+        self
+    }
+
+    /// Sets the DNS servers.
+    #[must_use]
+    pub fn with_dns(mut self, dns: Vec<String>) -> Self {
+        self.dns = Some(dns);
+        self
+    }
+
+    /// Sets the MTU.
+    #[must_use]
+    pub fn with_mtu(mut self, mtu: u32) -> Self {
+        self.mtu = Some(mtu);
+        self
+    }
+
+    /// Sets the UUID.
+    #[must_use]
+    pub fn with_uuid(mut self, uuid: Uuid) -> Self {
+        self.uuid = Some(uuid);
+        self
+    }
+}
+
+impl super::vpn::sealed::Sealed for Ikev2Config {}
+
+impl VpnConfig for Ikev2Config {
+    fn vpn_kind(&self) -> VpnKind {
+        VpnKind::Plugin
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn dns(&self) -> Option<&[String]> {
+        self.dns.as_deref()
+    }
+
+    fn mtu(&self) -> Option<u32> {
+        self.mtu
+    }
+
+    fn uuid(&self) -> Option<Uuid> {
+        self.uuid
+    }
+}
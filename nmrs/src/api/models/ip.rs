@@ -0,0 +1,249 @@
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+use std::time::{Duration, SystemTime};
+
+use crate::api::builders::{IpConfig, Route};
+use serde::{Deserialize, Serialize};
+
+/// How an address family obtains its configuration.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum IpMethod {
+    /// DHCP for IPv4, SLAAC/DHCPv6 for IPv6.
+    #[default]
+    Auto,
+    /// Static addresses from [`IpSettings::addresses`].
+    Manual,
+    /// Link-local addressing only.
+    LinkLocal,
+    /// The address family is not configured.
+    Disabled,
+}
+
+/// IPv4 or IPv6 configuration for a new connection profile.
+///
+/// Pass it to [`ConnectionOptions::with_ipv4`](super::ConnectionOptions::with_ipv4)
+/// / [`with_ipv6`](super::ConnectionOptions::with_ipv6) to create profiles with
+/// static addressing instead of DHCP. Addresses, gateway and DNS servers of
+/// the other address family are ignored.
+///
+/// # Examples
+///
+/// ```rust
+/// use nmrs::IpSettings;
+/// use nmrs::builders::IpConfig;
+/// use std::net::{IpAddr, Ipv4Addr};
+///
+/// let ipv4 = IpSettings::manual(vec![IpConfig::new("192.168.1.50", 24)])
+///     .with_gateway(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 1)))
+///     .with_dns(vec![IpAddr::V4(Ipv4Addr::new(1, 1, 1, 1))])
+///     .with_dns_search(vec!["lan".into()]);
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IpSettings {
+    /// Configuration method.
+    pub method: IpMethod,
+    /// Static addresses, used with [`IpMethod::Manual`].
+    pub addresses: Vec<IpConfig>,
+    /// Default gateway.
+    pub gateway: Option<IpAddr>,
+    /// DNS servers.
+    pub dns: Vec<IpAddr>,
+    /// DNS search domains.
+    pub dns_search: Vec<String>,
+    /// Static routes.
+    pub routes: Vec<Route>,
+    /// Never use this connection as the default route.
+    pub never_default: bool,
+}
+
+impl IpSettings {
+    /// Automatic configuration (DHCP / SLAAC).
+    #[must_use]
+    pub fn auto() -> Self {
+        Self::default()
+    }
+
+    /// Static configuration with the given addresses.
+    #[must_use]
+    pub fn manual(addresses: Vec<IpConfig>) -> Self {
+        Self {
+            method: IpMethod::Manual,
+            addresses,
+            ..Self::default()
+        }
+    }
+
+    /// Leaves the address family unconfigured.
+    #[must_use]
+    pub fn disabled() -> Self {
+        Self {
+            method: IpMethod::Disabled,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the default gateway.
+    #[must_use]
+    pub fn with_gateway(mut self, gateway: IpAddr) -> Self {
+        self.gateway = Some(gateway);
+        self
+    }
+
+    /// Sets the DNS servers.
+    #[must_use]
+    pub fn with_dns(mut self, servers: Vec<IpAddr>) -> Self {
+        self.dns = servers;
+        self
+    }
+
+    /// Sets the DNS search domains.
+    #[must_use]
+    pub fn with_dns_search(mut self, domains: Vec<String>) -> Self {
+        self.dns_search = domains;
+        self
+    }
+
+    /// Sets the static routes.
+    #[must_use]
+    pub fn with_routes(mut self, routes: Vec<Route>) -> Self {
+        self.routes = routes;
+        self
+    }
+
+    /// Sets whether the connection may never become the default route.
+    #[must_use]
+    pub fn with_never_default(mut self, never_default: bool) -> Self {
+        self.never_default = never_default;
+        self
+    }
+}
+
+/// DNS override for a saved profile.
+///
+/// Applied with [`NetworkManager::set_dns`](crate::NetworkManager::set_dns).
+/// Servers are split by address family; search domains and
+/// `ignore_auto_dns` apply to both families.
+///
+/// # Examples
+///
+/// ```rust
+/// use nmrs::DnsConfig;
+/// use std::net::{IpAddr, Ipv4Addr};
+///
+/// let dns = DnsConfig::new(vec![IpAddr::V4(Ipv4Addr::new(9, 9, 9, 9))])
+///     .with_search_domains(vec!["corp.example".into()])
+///     .with_ignore_auto_dns(true);
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DnsConfig {
+    /// DNS servers, in priority order.
+    pub servers: Vec<IpAddr>,
+    /// DNS search domains.
+    pub search_domains: Vec<String>,
+    /// Whether to drop DNS servers and domains learned from DHCP, SLAAC or
+    /// the VPN, so only the ones given here are used.
+    pub ignore_auto_dns: bool,
+}
+
+impl DnsConfig {
+    /// DNS override with the given servers.
+    #[must_use]
+    pub fn new(servers: Vec<IpAddr>) -> Self {
+        Self {
+            servers,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the search domains.
+    #[must_use]
+    pub fn with_search_domains(mut self, domains: Vec<String>) -> Self {
+        self.search_domains = domains;
+        self
+    }
+
+    /// Sets whether automatically obtained DNS is ignored.
+    #[must_use]
+    pub fn with_ignore_auto_dns(mut self, ignore: bool) -> Self {
+        self.ignore_auto_dns = ignore;
+        self
+    }
+}
+
+/// IPv4 or IPv6 configuration currently applied to an active connection.
+///
+/// Read from NetworkManager's `IP4Config` / `IP6Config` objects, so it
+/// reflects what DHCP, SLAAC, a VPN plugin or static settings actually
+/// produced rather than what the profile requested.
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IpDetails {
+    /// Assigned addresses with their prefix length.
+    pub addresses: Vec<IpConfig>,
+    /// Default gateway, if any.
+    pub gateway: Option<IpAddr>,
+    /// DNS servers in priority order.
+    pub nameservers: Vec<IpAddr>,
+    /// DNS domains and search domains.
+    pub domains: Vec<String>,
+    /// Routes installed for this configuration.
+    pub routes: Vec<Route>,
+}
+
+/// IP details of one active connection.
+///
+/// Returned by
+/// [`NetworkManager::active_connection_details`](crate::NetworkManager::active_connection_details).
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveConnectionDetails {
+    /// Connection profile name.
+    pub id: String,
+    /// Connection profile UUID.
+    pub uuid: String,
+    /// Interface the connection is active on, if it has a device.
+    pub interface: Option<String>,
+    /// IPv4 configuration, or `None` if IPv4 is not configured.
+    pub ipv4: Option<IpDetails>,
+    /// IPv6 configuration, or `None` if IPv6 is not configured.
+    pub ipv6: Option<IpDetails>,
+}
+
+/// A DHCP lease as reported by the DHCP client.
+///
+/// The well-known options are parsed into fields; everything the server
+/// sent is kept in [`options`](Self::options) under the client's option
+/// names (`dhcp_lease_time`, `dhcp6_server_id`, ...).
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DhcpLease {
+    /// Address or DUID identifying the DHCP server.
+    pub server_identifier: Option<String>,
+    /// Lease time granted by the server.
+    pub lease_time: Option<Duration>,
+    /// When the lease expires.
+    pub expiry: Option<SystemTime>,
+    /// Domain name or search domains handed out by the server.
+    pub domain: Option<String>,
+    /// NTP servers handed out by the server.
+    pub ntp_servers: Vec<String>,
+    /// All lease options, including the ones parsed above.
+    pub options: BTreeMap<String, String>,
+}
+
+/// DHCP leases held by a device.
+///
+/// Returned by [`NetworkManager::dhcp_info`](crate::NetworkManager::dhcp_info).
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DhcpInfo {
+    /// Interface the leases belong to.
+    pub interface: String,
+    /// DHCPv4 lease, or `None` if IPv4 was not configured by DHCP.
+    pub ipv4: Option<DhcpLease>,
+    /// DHCPv6 lease, or `None` if IPv6 was not configured by DHCPv6.
+    pub ipv6: Option<DhcpLease>,
+}
@@ -0,0 +1,129 @@
+//! Wi-Fi link quality snapshots and their aggregates.
+//!
+//! [`LinkStats`] combines what NetworkManager reports for a connected Wi-Fi
+//! device (percentage strength, transmit bitrate) with what only
+//! wpa_supplicant exposes: signal and noise levels in dBm, the receive
+//! bitrate and the retry and beacon-loss counters. [`LinkSummary`]
+//! reduces a series of snapshots, such as those kept by a
+//! [`StatsCollector`](crate::StatsCollector), to min/max/average values.
+
+use std::time::SystemTime;
+
+/// Link quality of a connected Wi-Fi device at one point in time.
+///
+/// Fields read from wpa_supplicant's `SignalPoll` are `None` when it is not
+/// reachable or the driver does not report them; the receive bitrate and
+/// the counters need wpa_supplicant 2.11 or later.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkStats {
+    /// Interface name (e.g. `"wlan0"`).
+    pub interface: String,
+    /// SSID of the associated access point.
+    pub ssid: String,
+    /// BSSID of the associated access point.
+    pub bssid: String,
+    /// Operating frequency in MHz.
+    pub frequency_mhz: u32,
+    /// Signal quality percentage (0–100) as computed by NetworkManager.
+    pub strength: u8,
+    /// Current transmit bitrate in Kbit/s.
+    pub tx_bitrate_kbps: u32,
+    /// Signal level in dBm, or `None` if wpa_supplicant is not reachable.
+    pub signal_dbm: Option<i32>,
+    /// Driver-averaged signal level in dBm, if reported.
+    pub avg_signal_dbm: Option<i32>,
+    /// Noise floor in dBm, if the driver reports it.
+    pub noise_dbm: Option<i32>,
+    /// Receive bitrate of the last received frame in Kbit/s.
+    pub rx_bitrate_kbps: Option<u32>,
+    /// Transmit retries since association.
+    pub tx_retries: Option<u32>,
+    /// Frames that failed to transmit after all retries, since association.
+    pub tx_failed: Option<u32>,
+    /// Beacons missed from the access point since association.
+    pub beacon_losses: Option<u32>,
+    /// When this snapshot was taken.
+    pub sampled_at: SystemTime,
+}
+
+impl LinkStats {
+    /// Signal-to-noise ratio in dB, when both levels are known.
+    #[must_use]
+    pub fn snr_db(&self) -> Option<i32> {
+        Some(self.signal_dbm? - self.noise_dbm?)
+    }
+}
+
+/// Minimum, maximum and mean of one metric over a set of samples.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MetricSummary {
+    /// Smallest value.
+    pub min: f64,
+    /// Largest value.
+    pub max: f64,
+    /// Arithmetic mean.
+    pub avg: f64,
+}
+
+impl MetricSummary {
+    /// Summarizes `values`, or `None` if there are none.
+    fn of(values: impl IntoIterator<Item = f64>) -> Option<Self> {
+        let mut count = 0u32;
+        let mut sum = 0.0;
+        let mut min = f64::INFINITY;
+        let mut max = f64::NEG_INFINITY;
+        for v in values {
+            count += 1;
+            sum += v;
+            min = min.min(v);
+            max = max.max(v);
+        }
+        (count > 0).then(|| Self {
+            min,
+            max,
+            avg: sum / f64::from(count),
+        })
+    }
+}
+
+/// Aggregate link quality over a series of [`LinkStats`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinkSummary {
+    /// Number of samples summarized.
+    pub samples: usize,
+    /// Signal quality percentage.
+    pub strength: MetricSummary,
+    /// Transmit bitrate in Kbit/s.
+    pub tx_bitrate_kbps: MetricSummary,
+    /// Signal level in dBm, over the samples that have one.
+    pub signal_dbm: Option<MetricSummary>,
+    /// Receive bitrate in Kbit/s, over the samples that have one.
+    pub rx_bitrate_kbps: Option<MetricSummary>,
+}
+
+impl LinkSummary {
+    /// Summarizes `samples`, or `None` if there are none.
+    #[must_use]
+    pub fn from_samples<'a>(samples: impl IntoIterator<Item = &'a LinkStats>) -> Option<Self> {
+        let samples: Vec<&LinkStats> = samples.into_iter().collect();
+        Some(Self {
+            samples: samples.len(),
+            strength: MetricSummary::of(samples.iter().map(|s| f64::from(s.strength)))?,
+            tx_bitrate_kbps: MetricSummary::of(
+                samples.iter().map(|s| f64::from(s.tx_bitrate_kbps)),
+            )?,
+            signal_dbm: MetricSummary::of(
+                samples.iter().filter_map(|s| s.signal_dbm).map(f64::from),
+            ),
+            rx_bitrate_kbps: MetricSummary::of(
+                samples
+                    .iter()
+                    .filter_map(|s| s.rx_bitrate_kbps)
+                    .map(f64::from),
+            ),
+        })
+    }
+}
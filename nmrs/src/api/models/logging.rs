@@ -0,0 +1,142 @@
+//! NetworkManager daemon logging configuration.
+//!
+//! NetworkManager can change its own log verbosity at runtime through the
+//! `SetLogging` / `GetLogging` D-Bus methods. Support tooling can use
+//! [`crate::NetworkManager::set_nm_logging`] to temporarily enable `TRACE`
+//! for a few domains (e.g. `WIFI`, `DHCP4`) while reproducing an issue, then
+//! restore the previous [`NmLogging`] snapshot.
+
+use std::fmt;
+
+/// NetworkManager log level.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum NmLogLevel {
+    /// Logging disabled.
+    Off,
+    /// Errors only.
+    Err,
+    /// Warnings and errors.
+    Warn,
+    /// Informational messages (NM's default).
+    Info,
+    /// Debug messages.
+    Debug,
+    /// Everything, including very verbose tracing.
+    Trace,
+    /// Leave the current level unchanged (only valid when setting).
+    Keep,
+}
+
+impl NmLogLevel {
+    /// The string NetworkManager uses for this level.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Off => "OFF",
+            Self::Err => "ERR",
+            Self::Warn => "WARN",
+            Self::Info => "INFO",
+            Self::Debug => "DEBUG",
+            Self::Trace => "TRACE",
+            Self::Keep => "KEEP",
+        }
+    }
+
+    /// Parses a level as reported by NetworkManager (case-insensitive).
+    ///
+    /// Returns `None` for unrecognized values.
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_uppercase().as_str() {
+            "OFF" => Some(Self::Off),
+            "ERR" | "ERROR" => Some(Self::Err),
+            "WARN" | "WARNING" => Some(Self::Warn),
+            "INFO" => Some(Self::Info),
+            "DEBUG" => Some(Self::Debug),
+            "TRACE" => Some(Self::Trace),
+            "KEEP" => Some(Self::Keep),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for NmLogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Snapshot of NetworkManager's logging configuration.
+///
+/// Returned by [`crate::NetworkManager::get_nm_logging`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct NmLogging {
+    /// Global log level. `None` if NM reported a level nmrs does not know.
+    pub level: Option<NmLogLevel>,
+    /// Enabled log domains as reported by NM (e.g. `"WIFI"`, `"DHCP4:TRACE"`).
+    ///
+    /// Entries may carry a per-domain level suffix after a colon.
+    pub domains: Vec<String>,
+}
+
+impl NmLogging {
+    /// Builds a snapshot from the raw `GetLogging` reply.
+    #[must_use]
+    pub fn from_raw(level: &str, domains: &str) -> Self {
+        Self {
+            level: NmLogLevel::parse(level),
+            domains: split_domains(domains),
+        }
+    }
+
+    /// Domains joined back into NM's comma-separated form.
+    #[must_use]
+    pub fn domains_string(&self) -> String {
+        self.domains.join(",")
+    }
+}
+
+fn split_domains(domains: &str) -> Vec<String> {
+    domains
+        .split(',')
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn level_round_trip() {
+        for level in [
+            NmLogLevel::Off,
+            NmLogLevel::Err,
+            NmLogLevel::Warn,
+            NmLogLevel::Info,
+            NmLogLevel::Debug,
+            NmLogLevel::Trace,
+            NmLogLevel::Keep,
+        ] {
+            assert_eq!(NmLogLevel::parse(level.as_str()), Some(level));
+        }
+    }
+
+    #[test]
+    fn level_parse_is_case_insensitive() {
+        assert_eq!(NmLogLevel::parse("trace"), Some(NmLogLevel::Trace));
+        assert_eq!(NmLogLevel::parse("bogus"), None);
+    }
+
+    #[test]
+    fn from_raw_splits_domains() {
+        let logging = NmLogging::from_raw("INFO", "PLATFORM,WIFI:TRACE, DHCP4 ,");
+        assert_eq!(logging.level, Some(NmLogLevel::Info));
+        assert_eq!(logging.domains, vec!["PLATFORM", "WIFI:TRACE", "DHCP4"]);
+        assert_eq!(logging.domains_string(), "PLATFORM,WIFI:TRACE,DHCP4");
+    }
+}
@@ -1,17 +1,36 @@
 pub(crate) mod access_point;
+mod autoconnect;
 mod bluetooth;
+mod bridge;
+mod checkpoint;
 mod config;
 mod connection_state;
 mod connectivity;
 mod device;
 mod error;
+mod event;
+mod failure;
+mod gsm;
+mod ikev2;
+mod ip;
+mod link_stats;
+mod logging;
 mod openvpn;
+mod p2p;
+mod permissions;
 mod radio;
 mod saved_connection;
+mod scan;
+mod secret;
+mod secrets;
+mod sharing;
+mod snapshot;
 mod state_reason;
+mod traffic;
 mod vlan;
 mod vpn;
 mod wifi;
+mod wired;
 mod wireguard;
 
 #[cfg(test)]
@@ -19,17 +38,36 @@ mod wireguard;
 mod tests;
 
 pub use access_point::*;
+pub use autoconnect::*;
 pub use bluetooth::*;
+pub use bridge::*;
+pub use checkpoint::*;
 pub use config::*;
 pub use connection_state::*;
 pub use connectivity::*;
 pub use device::*;
 pub use error::*;
+pub use event::*;
+pub use failure::*;
+pub use gsm::*;
+pub use ikev2::*;
+pub use ip::*;
+pub use link_stats::*;
+pub use logging::*;
 pub use openvpn::*;
+pub use p2p::*;
+pub use permissions::*;
 pub use radio::*;
 pub use saved_connection::*;
+pub use scan::*;
+pub use secret::*;
+pub use secrets::*;
+pub use sharing::*;
+pub use snapshot::*;
 pub use state_reason::*;
+pub use traffic::*;
 pub use vlan::*;
 pub use vpn::*;
 pub use wifi::*;
+pub use wired::*;
 pub use wireguard::*;
@@ -0,0 +1,31 @@
+use zvariant::OwnedObjectPath;
+
+/// A Wi-Fi Direct (P2P) peer seen by a Wi-Fi P2P device.
+///
+/// Returned by [`NetworkManager::list_p2p_peers`](crate::NetworkManager::list_p2p_peers)
+/// and passed back to
+/// [`NetworkManager::connect_p2p`](crate::NetworkManager::connect_p2p).
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct P2pPeer {
+    /// D-Bus path of the peer object.
+    pub path: OwnedObjectPath,
+    /// D-Bus path of the P2P device that sees the peer.
+    pub device_path: OwnedObjectPath,
+    /// Interface name of that device (e.g. `"p2p-dev-wlan0"`).
+    pub interface: String,
+    /// Device name advertised by the peer.
+    pub name: String,
+    /// MAC address of the peer.
+    pub hw_address: String,
+    /// Manufacturer advertised by the peer.
+    pub manufacturer: String,
+    /// Model advertised by the peer.
+    pub model: String,
+    /// Wi-Fi Display information elements, empty if none.
+    pub wfd_ies: Vec<u8>,
+    /// Signal strength percentage (0–100).
+    pub strength: u8,
+    /// Monotonic seconds since boot when last seen, or `None` if never.
+    pub last_seen_secs: Option<i64>,
+}
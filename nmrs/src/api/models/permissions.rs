@@ -0,0 +1,275 @@
+//! Polkit permissions of the calling user.
+//!
+//! NetworkManager authorizes privileged calls through polkit.
+//! `GetPermissions` reports, for each action, whether the caller may
+//! perform it outright, only after authenticating, or not at all, so a GUI
+//! can disable controls up front instead of failing with
+//! [`PermissionDenied`](crate::ConnectionError::PermissionDenied) later.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+const ACTION_PREFIX: &str = "org.freedesktop.NetworkManager.";
+
+/// A NetworkManager polkit action.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Permission {
+    /// Turn networking on or off (`enable-disable-network`).
+    EnableDisableNetwork,
+    /// Turn Wi-Fi on or off (`enable-disable-wifi`).
+    EnableDisableWifi,
+    /// Turn mobile broadband on or off (`enable-disable-wwan`).
+    EnableDisableWwan,
+    /// Turn WiMAX on or off (`enable-disable-wimax`).
+    EnableDisableWimax,
+    /// Put NetworkManager to sleep or wake it (`sleep-wake`).
+    SleepWake,
+    /// Activate and deactivate connections (`network-control`).
+    NetworkControl,
+    /// Share a connection through a protected hotspot (`wifi.share.protected`).
+    WifiShareProtected,
+    /// Share a connection through an open hotspot (`wifi.share.open`).
+    WifiShareOpen,
+    /// Change system-wide profiles (`settings.modify.system`).
+    SettingsModifySystem,
+    /// Change the user's own profiles (`settings.modify.own`).
+    SettingsModifyOwn,
+    /// Change the persistent hostname (`settings.modify.hostname`).
+    SettingsModifyHostname,
+    /// Change the global DNS configuration (`settings.modify.global-dns`).
+    SettingsModifyGlobalDns,
+    /// Reload NetworkManager's configuration (`reload`).
+    Reload,
+    /// Create and roll back checkpoints (`checkpoint-rollback`).
+    CheckpointRollback,
+    /// Turn device statistics on or off (`enable-disable-statistics`).
+    EnableDisableStatistics,
+    /// Turn connectivity checking on or off
+    /// (`enable-disable-connectivity-check`).
+    EnableDisableConnectivityCheck,
+    /// Request Wi-Fi scans (`wifi.scan`).
+    WifiScan,
+    /// An action nmrs does not know, by full polkit action id.
+    Other(String),
+}
+
+impl Permission {
+    /// Parses a polkit action id such as
+    /// `org.freedesktop.NetworkManager.network-control`.
+    #[must_use]
+    pub fn from_action_id(id: &str) -> Self {
+        let Some(action) = id.strip_prefix(ACTION_PREFIX) else {
+            return Self::Other(id.to_string());
+        };
+        match action {
+            "enable-disable-network" => Self::EnableDisableNetwork,
+            "enable-disable-wifi" => Self::EnableDisableWifi,
+            "enable-disable-wwan" => Self::EnableDisableWwan,
+            "enable-disable-wimax" => Self::EnableDisableWimax,
+            "sleep-wake" => Self::SleepWake,
+            "network-control" => Self::NetworkControl,
+            "wifi.share.protected" => Self::WifiShareProtected,
+            "wifi.share.open" => Self::WifiShareOpen,
+            "settings.modify.system" => Self::SettingsModifySystem,
+            "settings.modify.own" => Self::SettingsModifyOwn,
+            "settings.modify.hostname" => Self::SettingsModifyHostname,
+            "settings.modify.global-dns" => Self::SettingsModifyGlobalDns,
+            "reload" => Self::Reload,
+            "checkpoint-rollback" => Self::CheckpointRollback,
+            "enable-disable-statistics" => Self::EnableDisableStatistics,
+            "enable-disable-connectivity-check" => Self::EnableDisableConnectivityCheck,
+            "wifi.scan" => Self::WifiScan,
+            _ => Self::Other(id.to_string()),
+        }
+    }
+
+    /// The full polkit action id.
+    #[must_use]
+    pub fn action_id(&self) -> String {
+        let action = match self {
+            Self::EnableDisableNetwork => "enable-disable-network",
+            Self::EnableDisableWifi => "enable-disable-wifi",
+            Self::EnableDisableWwan => "enable-disable-wwan",
+            Self::EnableDisableWimax => "enable-disable-wimax",
+            Self::SleepWake => "sleep-wake",
+            Self::NetworkControl => "network-control",
+            Self::WifiShareProtected => "wifi.share.protected",
+            Self::WifiShareOpen => "wifi.share.open",
+            Self::SettingsModifySystem => "settings.modify.system",
+            Self::SettingsModifyOwn => "settings.modify.own",
+            Self::SettingsModifyHostname => "settings.modify.hostname",
+            Self::SettingsModifyGlobalDns => "settings.modify.global-dns",
+            Self::Reload => "reload",
+            Self::CheckpointRollback => "checkpoint-rollback",
+            Self::EnableDisableStatistics => "enable-disable-statistics",
+            Self::EnableDisableConnectivityCheck => "enable-disable-connectivity-check",
+            Self::WifiScan => "wifi.scan",
+            Self::Other(id) => return id.clone(),
+        };
+        format!("{ACTION_PREFIX}{action}")
+    }
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.action_id())
+    }
+}
+
+/// Whether the caller may perform a [`Permission`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PermissionResult {
+    /// Allowed without asking.
+    Yes,
+    /// Allowed once the user authenticates through a polkit agent.
+    Auth,
+    /// Not allowed.
+    No,
+}
+
+impl PermissionResult {
+    /// Parses `"yes"`, `"auth"` or `"no"` as reported by NetworkManager.
+    ///
+    /// Returns `None` for unrecognized values.
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "yes" => Some(Self::Yes),
+            "auth" => Some(Self::Auth),
+            "no" => Some(Self::No),
+            _ => None,
+        }
+    }
+
+    /// Whether the action can succeed, possibly after authenticating.
+    #[must_use]
+    pub fn is_possible(self) -> bool {
+        matches!(self, Self::Yes | Self::Auth)
+    }
+}
+
+/// The caller's polkit permissions, as reported by `GetPermissions`.
+///
+/// Returned by [`crate::NetworkManager::permissions`]. Actions
+/// NetworkManager did not report count as [`PermissionResult::No`].
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use nmrs::{Permission, PermissionResult, Permissions};
+///
+/// let raw = HashMap::from([(
+///     "org.freedesktop.NetworkManager.settings.modify.system".to_string(),
+///     "auth".to_string(),
+/// )]);
+/// let permissions = Permissions::from_raw(&raw);
+/// assert_eq!(permissions.get(&Permission::SettingsModifySystem), PermissionResult::Auth);
+/// assert!(permissions.can_modify_system());
+/// assert!(!permissions.can_connect());
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Permissions {
+    /// Result for each reported action.
+    pub entries: BTreeMap<Permission, PermissionResult>,
+}
+
+impl Permissions {
+    /// Builds the map from the raw `GetPermissions` reply, skipping entries
+    /// with an unrecognized result.
+    #[must_use]
+    pub fn from_raw(raw: &HashMap<String, String>) -> Self {
+        let entries = raw
+            .iter()
+            .filter_map(|(id, result)| {
+                Some((
+                    Permission::from_action_id(id),
+                    PermissionResult::parse(result)?,
+                ))
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// The result for `permission`; [`PermissionResult::No`] if it was not
+    /// reported.
+    #[must_use]
+    pub fn get(&self, permission: &Permission) -> PermissionResult {
+        self.entries
+            .get(permission)
+            .copied()
+            .unwrap_or(PermissionResult::No)
+    }
+
+    /// Whether connections can be activated and deactivated
+    /// ([`Permission::NetworkControl`]), possibly after authenticating.
+    #[must_use]
+    pub fn can_connect(&self) -> bool {
+        self.get(&Permission::NetworkControl).is_possible()
+    }
+
+    /// Whether system-wide profiles can be added, changed or deleted
+    /// ([`Permission::SettingsModifySystem`]), possibly after
+    /// authenticating.
+    #[must_use]
+    pub fn can_modify_system(&self) -> bool {
+        self.get(&Permission::SettingsModifySystem).is_possible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn action_ids_round_trip() {
+        for permission in [
+            Permission::EnableDisableNetwork,
+            Permission::NetworkControl,
+            Permission::WifiShareOpen,
+            Permission::SettingsModifyGlobalDns,
+            Permission::EnableDisableConnectivityCheck,
+            Permission::WifiScan,
+            Permission::Other("org.example.custom".into()),
+        ] {
+            assert_eq!(
+                Permission::from_action_id(&permission.action_id()),
+                permission
+            );
+        }
+        assert_eq!(
+            Permission::NetworkControl.to_string(),
+            "org.freedesktop.NetworkManager.network-control"
+        );
+    }
+
+    #[test]
+    fn from_raw_maps_results() {
+        let raw = HashMap::from([
+            (
+                "org.freedesktop.NetworkManager.network-control".to_string(),
+                "yes".to_string(),
+            ),
+            (
+                "org.freedesktop.NetworkManager.settings.modify.system".to_string(),
+                "no".to_string(),
+            ),
+            (
+                "org.freedesktop.NetworkManager.reload".to_string(),
+                "maybe".to_string(),
+            ),
+        ]);
+        let permissions = Permissions::from_raw(&raw);
+        assert_eq!(permissions.entries.len(), 2);
+        assert!(permissions.can_connect());
+        assert!(!permissions.can_modify_system());
+        assert_eq!(permissions.get(&Permission::Reload), PermissionResult::No);
+        assert_eq!(
+            permissions.get(&Permission::SleepWake),
+            PermissionResult::No
+        );
+    }
+}
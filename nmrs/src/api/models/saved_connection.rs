@@ -8,7 +8,9 @@
 //! when a [secret agent](crate::agent) is registered. See feature `01-secret-agent`.
 
 use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use serde::{Deserialize, Serialize};
 use zvariant::{OwnedObjectPath, OwnedValue};
 
 /// Full saved profile with a structured [`SettingsSummary`].
@@ -41,6 +43,14 @@ pub struct SavedConnection {
     pub summary: SettingsSummary,
 }
 
+impl SavedConnection {
+    /// Time the profile was last activated, or `None` if it never was.
+    #[must_use]
+    pub fn last_used(&self) -> Option<SystemTime> {
+        (self.timestamp_unix != 0).then(|| UNIX_EPOCH + Duration::from_secs(self.timestamp_unix))
+    }
+}
+
 /// Cheap listing: path plus `connection` identity fields only (still one `GetSettings` per profile).
 #[non_exhaustive]
 #[derive(Debug, Clone)]
@@ -71,9 +81,65 @@ pub struct SettingsPatch {
     pub raw_overlay: Option<HashMap<String, HashMap<String, OwnedValue>>>,
 }
 
+impl SettingsPatch {
+    /// Creates an empty patch.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets `connection.autoconnect`.
+    #[must_use]
+    pub fn with_autoconnect(mut self, autoconnect: bool) -> Self {
+        self.autoconnect = Some(autoconnect);
+        self
+    }
+
+    /// Sets `connection.autoconnect-priority`.
+    #[must_use]
+    pub fn with_autoconnect_priority(mut self, priority: i32) -> Self {
+        self.autoconnect_priority = Some(priority);
+        self
+    }
+
+    /// Renames the profile (`connection.id`).
+    #[must_use]
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Binds the profile to an interface, or unbinds it with `None`.
+    #[must_use]
+    pub fn with_interface_name(mut self, interface: Option<String>) -> Self {
+        self.interface_name = Some(interface);
+        self
+    }
+
+    /// Sets raw keys merged after the typed fields.
+    #[must_use]
+    pub fn with_raw_overlay(
+        mut self,
+        overlay: HashMap<String, HashMap<String, OwnedValue>>,
+    ) -> Self {
+        self.raw_overlay = Some(overlay);
+        self
+    }
+
+    /// True if the patch changes nothing.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.autoconnect.is_none()
+            && self.autoconnect_priority.is_none()
+            && self.id.is_none()
+            && self.interface_name.is_none()
+            && self.raw_overlay.is_none()
+    }
+}
+
 /// NM `password-flags` / `psk-flags` style bitmask (subset used for summaries).
 #[non_exhaustive]
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct VpnSecretFlags(pub u32);
 
 impl VpnSecretFlags {
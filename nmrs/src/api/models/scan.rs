@@ -0,0 +1,48 @@
+//! Wi-Fi scan request options.
+
+/// Options for [`NetworkManager::scan_networks_with_options`](crate::NetworkManager::scan_networks_with_options).
+///
+/// Hidden networks do not answer broadcast probes, so they only show up in
+/// scan results when the scan probes for their SSID directly. List the
+/// SSIDs of known hidden networks here before connecting to them.
+///
+/// # Examples
+///
+/// ```rust
+/// use nmrs::ScanOptions;
+///
+/// let opts = ScanOptions::new().with_ssid("CorpHidden");
+/// assert_eq!(opts.ssids, ["CorpHidden"]);
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScanOptions {
+    /// SSIDs to probe for (1–32 bytes each). Empty means a broadcast scan.
+    pub ssids: Vec<String>,
+}
+
+impl ScanOptions {
+    /// Options for a plain broadcast scan.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an SSID to probe for.
+    #[must_use]
+    pub fn with_ssid(mut self, ssid: impl Into<String>) -> Self {
+        self.ssids.push(ssid.into());
+        self
+    }
+
+    /// Adds several SSIDs to probe for.
+    #[must_use]
+    pub fn with_ssids<I, S>(mut self, ssids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.ssids.extend(ssids.into_iter().map(Into::into));
+        self
+    }
+}
@@ -0,0 +1,148 @@
+//! Credential wrapper that keeps secrets out of logs and memory dumps.
+
+use std::fmt::{self, Debug, Display, Formatter};
+
+use serde::{Deserialize, Deserializer};
+use zeroize::Zeroize;
+
+/// A credential such as a PSK, EAP password or WireGuard private key.
+///
+/// The value is wiped from memory when the `Secret` is dropped, and
+/// `Debug` and `Display` print `<redacted>` instead of it. Read it with
+/// [`expose`](Self::expose) where it is actually needed. Equality is
+/// checked in constant time for secrets of the same length.
+///
+/// The credential fields of [`WifiSecurity`](crate::WifiSecurity),
+/// [`EapOptions`](crate::EapOptions) and
+/// [`WireGuardConfig`](crate::WireGuardConfig) stay plain `String`s and
+/// are **not** wiped: their constructors move a `Secret`'s value into the
+/// field. Only their `Debug` output is redacted the same way. Moving those
+/// fields to `Secret` is a breaking change, planned for the next major
+/// release behind a deprecation.
+///
+/// `Secret` deserializes from a string but deliberately does not implement
+/// `Serialize`; fields holding one are skipped when serializing.
+///
+/// Copies handed to D-Bus or made by [`expose`](Self::expose) callers are
+/// not wiped either.
+///
+/// # Examples
+///
+/// ```rust
+/// use nmrs::{Secret, WifiSecurity};
+///
+/// let security = WifiSecurity::wpa_psk(Secret::new("hunter22"));
+/// assert!(!format!("{security:?}").contains("hunter22"));
+///
+/// let psk = Secret::new("hunter22");
+/// assert_eq!(psk.expose(), "hunter22");
+/// assert_eq!(psk.to_string(), "<redacted>");
+/// ```
+#[derive(Clone, Default)]
+pub struct Secret(String);
+
+impl Secret {
+    /// Wraps a secret value.
+    #[must_use]
+    pub fn new(value: impl Into<String>) -> Self {
+        Self(value.into())
+    }
+
+    /// The secret value.
+    #[must_use]
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns `true` if the secret is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Length of the secret in bytes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl PartialEq for Secret {
+    fn eq(&self, other: &Self) -> bool {
+        let (a, b) = (self.0.as_bytes(), other.0.as_bytes());
+        a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+    }
+}
+
+impl Eq for Secret {}
+
+impl Drop for Secret {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Debug for Secret {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret(<redacted>)")
+    }
+}
+
+impl Display for Secret {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("<redacted>")
+    }
+}
+
+/// Unwraps the value, e.g. to fill a `String` credential field. The
+/// returned `String` is not wiped on drop.
+impl From<Secret> for String {
+    fn from(mut secret: Secret) -> Self {
+        std::mem::take(&mut secret.0)
+    }
+}
+
+impl From<String> for Secret {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for Secret {
+    fn from(value: &str) -> Self {
+        Self(value.to_owned())
+    }
+}
+
+/// Stand-in printed by `Debug` impls in place of a secret value.
+pub(crate) struct Redacted;
+
+impl Debug for Redacted {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("<redacted>")
+    }
+}
+
+impl<'de> Deserialize<'de> for Secret {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_by_value() {
+        assert_eq!(Secret::new("hunter22"), Secret::new("hunter22"));
+        assert_ne!(Secret::new("hunter22"), Secret::new("hunter23"));
+        assert_ne!(Secret::new("hunter22"), Secret::new("hunter2"));
+        assert_eq!(Secret::default(), Secret::new(""));
+    }
+
+    #[test]
+    fn unwraps_into_string() {
+        assert_eq!(String::from(Secret::new("hunter22")), "hunter22");
+    }
+}
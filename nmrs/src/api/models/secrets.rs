@@ -0,0 +1,61 @@
+//! Secrets stored in saved connection profiles.
+//!
+//! Returned by [`crate::NetworkManager::get_secrets`]. NetworkManager only
+//! hands out secrets the caller is authorized to read; depending on polkit
+//! policy this may prompt the user. Agent-owned secrets (stored in a keyring
+//! rather than by NM) are absent.
+
+use std::collections::HashMap;
+
+/// Secrets of one setting in a saved profile.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionSecrets {
+    /// `802-11-wireless-security` secrets.
+    Wifi(WifiSecrets),
+    /// `802-1x` (enterprise) secrets.
+    Eap(EapSecrets),
+    /// `vpn` plugin secrets.
+    Vpn(VpnSecrets),
+    /// Any other setting, with string-valued secrets only.
+    Other {
+        /// Setting name that was requested.
+        setting: String,
+        /// Secret keys and values.
+        values: HashMap<String, String>,
+    },
+}
+
+/// Wi-Fi security secrets.
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WifiSecrets {
+    /// WPA/WPA2/WPA3 personal pre-shared key.
+    pub psk: Option<String>,
+    /// WEP keys `wep-key0` to `wep-key3`, by index.
+    pub wep_keys: [Option<String>; 4],
+    /// LEAP password.
+    pub leap_password: Option<String>,
+}
+
+/// 802.1X secrets.
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EapSecrets {
+    /// EAP password.
+    pub password: Option<String>,
+    /// Password for the client private key.
+    pub private_key_password: Option<String>,
+    /// Password for the phase 2 client private key.
+    pub phase2_private_key_password: Option<String>,
+    /// Smart card PIN.
+    pub pin: Option<String>,
+}
+
+/// VPN plugin secrets from `vpn.secrets`.
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VpnSecrets {
+    /// Plugin-specific secret keys and values (e.g. `password`, `cert-pass`).
+    pub secrets: HashMap<String, String>,
+}
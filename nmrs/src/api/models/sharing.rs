@@ -0,0 +1,102 @@
+//! Internet connection sharing.
+//!
+//! Types for
+//! [`NetworkManager::share_connection`](crate::NetworkManager::share_connection),
+//! which hands out addresses on an Ethernet port or a Wi-Fi hotspot and
+//! routes its clients through the current uplink.
+
+use zvariant::OwnedObjectPath;
+
+use super::error::ConnectionError;
+use super::secret::Secret;
+use super::wifi::WifiSecurity;
+use crate::util::validation::{validate_ssid, validate_wifi_security};
+
+/// Hotspot settings for sharing over Wi-Fi.
+///
+/// Ignored when sharing over Ethernet. Unset fields get a network named
+/// `Hotspot-<interface>` and a generated WPA2 passphrase, which is returned
+/// in [`SharedConnection::psk`].
+///
+/// # Examples
+///
+/// ```rust
+/// use nmrs::ShareOptions;
+///
+/// let options = ShareOptions::new()
+///     .with_ssid("Workshop")
+///     .with_psk("correct horse");
+/// assert!(options.validate().is_ok());
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShareOptions {
+    /// SSID of the hotspot.
+    pub ssid: Option<String>,
+    /// WPA2 passphrase of the hotspot, 8 to 63 characters.
+    pub psk: Option<Secret>,
+}
+
+impl ShareOptions {
+    /// Options with a default SSID and a generated passphrase.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the hotspot SSID.
+    #[must_use]
+    pub fn with_ssid(mut self, ssid: impl Into<String>) -> Self {
+        self.ssid = Some(ssid.into());
+        self
+    }
+
+    /// Sets the hotspot passphrase.
+    #[must_use]
+    pub fn with_psk(mut self, psk: impl Into<Secret>) -> Self {
+        self.psk = Some(psk.into());
+        self
+    }
+
+    /// Validates the options.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConnectionError::InvalidAddress` for an empty or over-long
+    /// SSID or a passphrase outside 8 to 63 characters.
+    pub fn validate(&self) -> Result<(), ConnectionError> {
+        if let Some(ssid) = &self.ssid {
+            validate_ssid(ssid)?;
+        }
+        if let Some(psk) = &self.psk {
+            if psk.is_empty() {
+                return Err(ConnectionError::InvalidAddress(
+                    "hotspot passphrase cannot be empty".to_string(),
+                ));
+            }
+            validate_wifi_security(&WifiSecurity::wpa_psk(psk.clone()))?;
+        }
+        Ok(())
+    }
+}
+
+/// A profile sharing the uplink over an interface (`ipv4.method=shared`).
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SharedConnection {
+    /// Interface clients connect through.
+    pub interface: String,
+    /// D-Bus path of the saved profile.
+    pub path: OwnedObjectPath,
+    /// Profile name.
+    pub id: String,
+    /// Profile UUID.
+    pub uuid: String,
+    /// Hotspot SSID; `None` when sharing over Ethernet.
+    pub ssid: Option<String>,
+    /// Hotspot passphrase, known only for a profile `share_connection`
+    /// just created.
+    pub psk: Option<Secret>,
+    /// Whether the profile is active on [`interface`](Self::interface).
+    pub active: bool,
+}
@@ -0,0 +1,39 @@
+//! Whole-state snapshot of NetworkManager.
+
+use super::connection_state::ActiveConnection;
+use super::device::Device;
+use super::wifi::Network;
+
+/// Devices, visible networks and active connections read together.
+///
+/// Returned by [`NetworkManager::snapshot`](crate::NetworkManager::snapshot),
+/// which reads all three from a single `GetManagedObjects` call, so they
+/// describe the same moment.
+///
+/// # Example
+///
+/// ```no_run
+/// use nmrs::NetworkManager;
+///
+/// # async fn example() -> nmrs::Result<()> {
+/// let nm = NetworkManager::new().await?;
+/// let snapshot = nm.snapshot().await?;
+/// println!(
+///     "{} devices, {} networks, {} active connections",
+///     snapshot.devices.len(),
+///     snapshot.networks.len(),
+///     snapshot.active_connections.len()
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct NetworkSnapshot {
+    /// Every device, as returned by `list_devices`.
+    pub devices: Vec<Device>,
+    /// Visible Wi-Fi networks, as returned by `list_networks`.
+    pub networks: Vec<Network>,
+    /// Active connections in NetworkManager's order.
+    pub active_connections: Vec<ActiveConnection>,
+}
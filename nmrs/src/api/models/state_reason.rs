@@ -1,26 +1,36 @@
 use std::fmt::{Display, Formatter};
 
+use super::device::UnmanagedReason;
 use super::error::ConnectionError;
+use crate::types::constants::device_state_reason as code;
+use serde::{Deserialize, Serialize};
 
 /// NetworkManager device state reason codes.
 ///
 /// These values come from the NM D-Bus API and indicate why a device
 /// transitioned to its current state. Use `StateReason::from(code)` to
-/// convert from the raw u32 values returned by NetworkManager.
+/// convert from the raw u32 values returned by NetworkManager; codes follow
+/// `NMDeviceStateReason` in `nm-dbus-interface.h`.
 #[non_exhaustive]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum StateReason {
-    /// The reason is unknown.
-    Unknown,
     /// No specific reason given.
     None,
-    /// The user disconnected the device.
-    UserDisconnected,
-    /// The device was disconnected by the system.
-    DeviceDisconnected,
-    /// The carrier/link status changed (e.g., cable unplugged).
-    CarrierChanged,
-    /// The Wi-Fi supplicant disconnected unexpectedly.
+    /// The reason is unknown.
+    Unknown,
+    /// The device is now managed.
+    NowManaged,
+    /// The device is no longer managed.
+    NowUnmanaged,
+    /// The device could not be readied for configuration.
+    ConfigFailed,
+    /// No IP configuration could be obtained.
+    IpConfigUnavailable,
+    /// The IP configuration is no longer valid.
+    IpConfigExpired,
+    /// Secrets were required but not provided.
+    NoSecrets,
+    /// The Wi-Fi supplicant disconnected, often after rejecting credentials.
     SupplicantDisconnected,
     /// The Wi-Fi supplicant's configuration failed.
     SupplicantConfigFailed,
@@ -30,24 +40,38 @@ pub enum StateReason {
     SupplicantTimeout,
     /// PPP connection start failed.
     PppStartFailed,
+    /// The PPP service disconnected.
+    PppDisconnected,
+    /// The PPP service failed.
+    PppFailed,
     /// DHCP client failed to start.
     DhcpStartFailed,
     /// DHCP client encountered an error.
     DhcpError,
     /// DHCP client failed to obtain an IP address.
     DhcpFailed,
-    /// Modem connection failed.
+    /// The connection sharing service failed to start.
+    SharedStartFailed,
+    /// The connection sharing service failed.
+    SharedFailed,
+    /// The link-local (AutoIP) service failed to start.
+    AutoIpStartFailed,
+    /// The link-local (AutoIP) service reported an error.
+    AutoIpError,
+    /// The link-local (AutoIP) service failed.
+    AutoIpFailed,
+    /// The modem line was busy.
+    ModemBusy,
+    /// The modem had no dial tone.
+    ModemNoDialTone,
+    /// The modem could not establish a carrier.
+    ModemNoCarrier,
+    /// The modem timed out while dialing.
+    ModemDialTimeout,
+    /// The modem failed to dial.
     ModemConnectionFailed,
     /// Modem initialization failed.
     ModemInitFailed,
-    /// InfiniBand device mode mismatch.
-    InfinibandMode,
-    /// A dependency connection failed.
-    DependencyFailed,
-    /// BR2684 bridge setup failed.
-    Br2684Failed,
-    /// Failed to set the device mode (e.g., AP mode).
-    ModeSetFailed,
     /// GSM modem APN selection failed.
     GsmApnSelectFailed,
     /// GSM modem is not searching for networks.
@@ -88,6 +112,14 @@ pub enum StateReason {
     GsmSimPukRequired,
     /// Wrong GSM SIM card inserted.
     GsmSimWrong,
+    /// InfiniBand device mode mismatch.
+    InfinibandMode,
+    /// A dependency connection failed.
+    DependencyFailed,
+    /// BR2684 bridge setup failed.
+    Br2684Failed,
+    /// ModemManager is not running.
+    ModemManagerUnavailable,
     /// The requested SSID was not found.
     SsidNotFound,
     /// A secondary connection failed.
@@ -104,93 +136,163 @@ pub enum StateReason {
     SimPinIncorrect,
     /// A new connection activation was queued.
     NewActivationEnqueued,
-    /// Parent device became unreachable.
-    ParentUnreachable,
     /// Parent device changed.
     ParentChanged,
+    /// The parent device's management changed.
+    ParentManagedChanged,
+    /// Communication with Open vSwitch failed.
+    OvsdbFailed,
+    /// An IP address is already in use on the network.
+    IpAddressDuplicate,
+    /// The IP method is not supported by the device.
+    IpMethodUnsupported,
+    /// SR-IOV configuration failed.
+    SriovConfigurationFailed,
+    /// The Wi-Fi P2P peer could not be found.
+    PeerNotFound,
+    /// The device handler dispatcher script failed.
+    DeviceHandlerFailed,
+    /// The device is unmanaged, for the given reason (NetworkManager 1.48+).
+    Unmanaged(UnmanagedReason),
+    /// The user disconnected the device.
+    #[deprecated(note = "not an NMDeviceStateReason code; never produced")]
+    UserDisconnected,
+    /// The device was disconnected by the system.
+    #[deprecated(note = "not an NMDeviceStateReason code; never produced")]
+    DeviceDisconnected,
+    /// The carrier/link status changed (e.g., cable unplugged).
+    #[deprecated(note = "not an NMDeviceStateReason code; never produced")]
+    CarrierChanged,
+    /// Failed to set the device mode (e.g., AP mode).
+    #[deprecated(note = "not an NMDeviceStateReason code; never produced")]
+    ModeSetFailed,
+    /// Parent device became unreachable.
+    #[deprecated(note = "not an NMDeviceStateReason code; never produced")]
+    ParentUnreachable,
     /// Unknown reason code not mapped to a specific variant.
     Other(u32),
 }
 
 impl From<u32> for StateReason {
-    fn from(code: u32) -> Self {
-        match code {
-            0 => Self::Unknown,
-            1 => Self::None,
-            2 => Self::UserDisconnected,
-            3 => Self::DeviceDisconnected,
-            4 => Self::CarrierChanged,
-            7 => Self::SupplicantDisconnected,
-            8 => Self::SupplicantConfigFailed,
-            9 => Self::SupplicantFailed,
-            10 => Self::SupplicantTimeout,
-            11 => Self::PppStartFailed,
-            15 => Self::DhcpStartFailed,
-            16 => Self::DhcpError,
-            17 => Self::DhcpFailed,
-            24 => Self::ModemConnectionFailed,
-            25 => Self::ModemInitFailed,
-            42 => Self::InfinibandMode,
-            43 => Self::DependencyFailed,
-            44 => Self::Br2684Failed,
-            45 => Self::ModeSetFailed,
-            46 => Self::GsmApnSelectFailed,
-            47 => Self::GsmNotSearching,
-            48 => Self::GsmRegistrationDenied,
-            49 => Self::GsmRegistrationTimeout,
-            50 => Self::GsmRegistrationFailed,
-            51 => Self::GsmPinCheckFailed,
-            52 => Self::FirmwareMissing,
-            53 => Self::DeviceRemoved,
-            54 => Self::Sleeping,
-            55 => Self::ConnectionRemoved,
-            56 => Self::UserRequested,
-            57 => Self::Carrier,
-            58 => Self::ConnectionAssumed,
-            59 => Self::SupplicantAvailable,
-            60 => Self::ModemNotFound,
-            61 => Self::BluetoothFailed,
-            62 => Self::GsmSimNotInserted,
-            63 => Self::GsmSimPinRequired,
-            64 => Self::GsmSimPukRequired,
-            65 => Self::GsmSimWrong,
-            70 => Self::SsidNotFound,
-            71 => Self::SecondaryConnectionFailed,
-            72 => Self::DcbFcoeFailed,
-            73 => Self::TeamdControlFailed,
-            74 => Self::ModemFailed,
-            75 => Self::ModemAvailable,
-            76 => Self::SimPinIncorrect,
-            77 => Self::NewActivationEnqueued,
-            78 => Self::ParentUnreachable,
-            79 => Self::ParentChanged,
+    fn from(reason: u32) -> Self {
+        match reason {
+            code::NONE => Self::None,
+            code::UNKNOWN => Self::Unknown,
+            code::NOW_MANAGED => Self::NowManaged,
+            code::NOW_UNMANAGED => Self::NowUnmanaged,
+            code::CONFIG_FAILED => Self::ConfigFailed,
+            code::IP_CONFIG_UNAVAILABLE => Self::IpConfigUnavailable,
+            code::IP_CONFIG_EXPIRED => Self::IpConfigExpired,
+            code::NO_SECRETS => Self::NoSecrets,
+            code::SUPPLICANT_DISCONNECT => Self::SupplicantDisconnected,
+            code::SUPPLICANT_CONFIG_FAILED => Self::SupplicantConfigFailed,
+            code::SUPPLICANT_FAILED => Self::SupplicantFailed,
+            code::SUPPLICANT_TIMEOUT => Self::SupplicantTimeout,
+            code::PPP_START_FAILED => Self::PppStartFailed,
+            code::PPP_DISCONNECT => Self::PppDisconnected,
+            code::PPP_FAILED => Self::PppFailed,
+            code::DHCP_START_FAILED => Self::DhcpStartFailed,
+            code::DHCP_ERROR => Self::DhcpError,
+            code::DHCP_FAILED => Self::DhcpFailed,
+            code::SHARED_START_FAILED => Self::SharedStartFailed,
+            code::SHARED_FAILED => Self::SharedFailed,
+            code::AUTOIP_START_FAILED => Self::AutoIpStartFailed,
+            code::AUTOIP_ERROR => Self::AutoIpError,
+            code::AUTOIP_FAILED => Self::AutoIpFailed,
+            code::MODEM_BUSY => Self::ModemBusy,
+            code::MODEM_NO_DIAL_TONE => Self::ModemNoDialTone,
+            code::MODEM_NO_CARRIER => Self::ModemNoCarrier,
+            code::MODEM_DIAL_TIMEOUT => Self::ModemDialTimeout,
+            code::MODEM_DIAL_FAILED => Self::ModemConnectionFailed,
+            code::MODEM_INIT_FAILED => Self::ModemInitFailed,
+            code::GSM_APN_FAILED => Self::GsmApnSelectFailed,
+            code::GSM_REGISTRATION_NOT_SEARCHING => Self::GsmNotSearching,
+            code::GSM_REGISTRATION_DENIED => Self::GsmRegistrationDenied,
+            code::GSM_REGISTRATION_TIMEOUT => Self::GsmRegistrationTimeout,
+            code::GSM_REGISTRATION_FAILED => Self::GsmRegistrationFailed,
+            code::GSM_PIN_CHECK_FAILED => Self::GsmPinCheckFailed,
+            code::FIRMWARE_MISSING => Self::FirmwareMissing,
+            code::REMOVED => Self::DeviceRemoved,
+            code::SLEEPING => Self::Sleeping,
+            code::CONNECTION_REMOVED => Self::ConnectionRemoved,
+            code::USER_REQUESTED => Self::UserRequested,
+            code::CARRIER => Self::Carrier,
+            code::CONNECTION_ASSUMED => Self::ConnectionAssumed,
+            code::SUPPLICANT_AVAILABLE => Self::SupplicantAvailable,
+            code::MODEM_NOT_FOUND => Self::ModemNotFound,
+            code::BT_FAILED => Self::BluetoothFailed,
+            code::GSM_SIM_NOT_INSERTED => Self::GsmSimNotInserted,
+            code::GSM_SIM_PIN_REQUIRED => Self::GsmSimPinRequired,
+            code::GSM_SIM_PUK_REQUIRED => Self::GsmSimPukRequired,
+            code::GSM_SIM_WRONG => Self::GsmSimWrong,
+            code::INFINIBAND_MODE => Self::InfinibandMode,
+            code::DEPENDENCY_FAILED => Self::DependencyFailed,
+            code::BR2684_FAILED => Self::Br2684Failed,
+            code::MODEM_MANAGER_UNAVAILABLE => Self::ModemManagerUnavailable,
+            code::SSID_NOT_FOUND => Self::SsidNotFound,
+            code::SECONDARY_CONNECTION_FAILED => Self::SecondaryConnectionFailed,
+            code::DCB_FCOE_FAILED => Self::DcbFcoeFailed,
+            code::TEAMD_CONTROL_FAILED => Self::TeamdControlFailed,
+            code::MODEM_FAILED => Self::ModemFailed,
+            code::MODEM_AVAILABLE => Self::ModemAvailable,
+            code::SIM_PIN_INCORRECT => Self::SimPinIncorrect,
+            code::NEW_ACTIVATION => Self::NewActivationEnqueued,
+            code::PARENT_CHANGED => Self::ParentChanged,
+            code::PARENT_MANAGED_CHANGED => Self::ParentManagedChanged,
+            code::OVSDB_FAILED => Self::OvsdbFailed,
+            code::IP_ADDRESS_DUPLICATE => Self::IpAddressDuplicate,
+            code::IP_METHOD_UNSUPPORTED => Self::IpMethodUnsupported,
+            code::SRIOV_CONFIGURATION_FAILED => Self::SriovConfigurationFailed,
+            code::PEER_NOT_FOUND => Self::PeerNotFound,
+            code::DEVICE_HANDLER_FAILED => Self::DeviceHandlerFailed,
+            code::UNMANAGED_BY_DEFAULT => Self::Unmanaged(UnmanagedReason::ByDefault),
+            code::UNMANAGED_EXTERNAL_DOWN => Self::Unmanaged(UnmanagedReason::ExternalDown),
+            code::UNMANAGED_LINK_NOT_INIT => Self::Unmanaged(UnmanagedReason::LinkNotInitialized),
+            code::UNMANAGED_QUITTING => Self::Unmanaged(UnmanagedReason::Quitting),
+            code::UNMANAGED_SLEEPING => Self::Unmanaged(UnmanagedReason::Sleeping),
+            code::UNMANAGED_USER_CONF => Self::Unmanaged(UnmanagedReason::UserConfig),
+            code::UNMANAGED_USER_EXPLICIT => Self::Unmanaged(UnmanagedReason::UserExplicit),
+            code::UNMANAGED_USER_SETTINGS => Self::Unmanaged(UnmanagedReason::UserSettings),
+            code::UNMANAGED_USER_UDEV => Self::Unmanaged(UnmanagedReason::Udev),
+            code::UNMANAGED_NETWORKING_OFF => Self::Unmanaged(UnmanagedReason::NetworkingOff),
             v => Self::Other(v),
         }
     }
 }
 
 impl Display for StateReason {
+    #[allow(deprecated)]
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Unknown => write!(f, "unknown"),
             Self::None => write!(f, "none"),
-            Self::UserDisconnected => write!(f, "user disconnected"),
-            Self::DeviceDisconnected => write!(f, "device disconnected"),
-            Self::CarrierChanged => write!(f, "carrier changed"),
+            Self::Unknown => write!(f, "unknown"),
+            Self::NowManaged => write!(f, "now managed"),
+            Self::NowUnmanaged => write!(f, "now unmanaged"),
+            Self::ConfigFailed => write!(f, "configuration failed"),
+            Self::IpConfigUnavailable => write!(f, "IP configuration unavailable"),
+            Self::IpConfigExpired => write!(f, "IP configuration expired"),
+            Self::NoSecrets => write!(f, "no secrets"),
             Self::SupplicantDisconnected => write!(f, "supplicant disconnected"),
             Self::SupplicantConfigFailed => write!(f, "supplicant config failed"),
             Self::SupplicantFailed => write!(f, "supplicant failed"),
             Self::SupplicantTimeout => write!(f, "supplicant timeout"),
             Self::PppStartFailed => write!(f, "PPP start failed"),
+            Self::PppDisconnected => write!(f, "PPP disconnected"),
+            Self::PppFailed => write!(f, "PPP failed"),
             Self::DhcpStartFailed => write!(f, "DHCP start failed"),
             Self::DhcpError => write!(f, "DHCP error"),
             Self::DhcpFailed => write!(f, "DHCP failed"),
+            Self::SharedStartFailed => write!(f, "shared connection start failed"),
+            Self::SharedFailed => write!(f, "shared connection failed"),
+            Self::AutoIpStartFailed => write!(f, "AutoIP start failed"),
+            Self::AutoIpError => write!(f, "AutoIP error"),
+            Self::AutoIpFailed => write!(f, "AutoIP failed"),
+            Self::ModemBusy => write!(f, "modem busy"),
+            Self::ModemNoDialTone => write!(f, "modem no dial tone"),
+            Self::ModemNoCarrier => write!(f, "modem no carrier"),
+            Self::ModemDialTimeout => write!(f, "modem dial timeout"),
             Self::ModemConnectionFailed => write!(f, "modem connection failed"),
             Self::ModemInitFailed => write!(f, "modem init failed"),
-            Self::InfinibandMode => write!(f, "infiniband mode"),
-            Self::DependencyFailed => write!(f, "dependency failed"),
-            Self::Br2684Failed => write!(f, "BR2684 failed"),
-            Self::ModeSetFailed => write!(f, "mode set failed"),
             Self::GsmApnSelectFailed => write!(f, "GSM APN select failed"),
             Self::GsmNotSearching => write!(f, "GSM not searching"),
             Self::GsmRegistrationDenied => write!(f, "GSM registration denied"),
@@ -211,6 +313,10 @@ impl Display for StateReason {
             Self::GsmSimPinRequired => write!(f, "GSM SIM PIN required"),
             Self::GsmSimPukRequired => write!(f, "GSM SIM PUK required"),
             Self::GsmSimWrong => write!(f, "GSM SIM wrong"),
+            Self::InfinibandMode => write!(f, "infiniband mode"),
+            Self::DependencyFailed => write!(f, "dependency failed"),
+            Self::Br2684Failed => write!(f, "BR2684 failed"),
+            Self::ModemManagerUnavailable => write!(f, "ModemManager unavailable"),
             Self::SsidNotFound => write!(f, "SSID not found"),
             Self::SecondaryConnectionFailed => write!(f, "secondary connection failed"),
             Self::DcbFcoeFailed => write!(f, "DCB/FCoE setup failed"),
@@ -219,8 +325,20 @@ impl Display for StateReason {
             Self::ModemAvailable => write!(f, "modem available"),
             Self::SimPinIncorrect => write!(f, "SIM PIN incorrect"),
             Self::NewActivationEnqueued => write!(f, "new activation enqueued"),
-            Self::ParentUnreachable => write!(f, "parent device unreachable"),
             Self::ParentChanged => write!(f, "parent device changed"),
+            Self::ParentManagedChanged => write!(f, "parent device management changed"),
+            Self::OvsdbFailed => write!(f, "OVSDB failed"),
+            Self::IpAddressDuplicate => write!(f, "duplicate IP address"),
+            Self::IpMethodUnsupported => write!(f, "IP method unsupported"),
+            Self::SriovConfigurationFailed => write!(f, "SR-IOV configuration failed"),
+            Self::PeerNotFound => write!(f, "peer not found"),
+            Self::DeviceHandlerFailed => write!(f, "device handler failed"),
+            Self::Unmanaged(reason) => write!(f, "unmanaged: {reason}"),
+            Self::UserDisconnected => write!(f, "user disconnected"),
+            Self::DeviceDisconnected => write!(f, "device disconnected"),
+            Self::CarrierChanged => write!(f, "carrier changed"),
+            Self::ModeSetFailed => write!(f, "mode set failed"),
+            Self::ParentUnreachable => write!(f, "parent device unreachable"),
             Self::Other(v) => write!(f, "unknown reason ({v})"),
         }
     }
@@ -236,6 +354,7 @@ pub fn reason_to_error(code: u32) -> ConnectionError {
     match reason {
         StateReason::SupplicantFailed
         | StateReason::SupplicantDisconnected
+        | StateReason::NoSecrets
         | StateReason::SimPinIncorrect
         | StateReason::GsmPinCheckFailed => ConnectionError::AuthFailed,
 
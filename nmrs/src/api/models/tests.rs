@@ -8,6 +8,8 @@ use super::config::*;
 use super::connection_state::*;
 use super::device::*;
 use super::error::*;
+use super::link_stats::*;
+use super::secret::Secret;
 use super::state_reason::*;
 use super::vpn::*;
 use super::wifi::*;
@@ -18,6 +20,7 @@ use crate::api::models::DeviceType;
 fn device_type_from_u32_all_variants() {
     assert_eq!(DeviceType::from(1), DeviceType::Ethernet);
     assert_eq!(DeviceType::from(2), DeviceType::Wifi);
+    assert_eq!(DeviceType::from(8), DeviceType::Modem);
     assert_eq!(DeviceType::from(11), DeviceType::Vlan);
     assert_eq!(DeviceType::from(30), DeviceType::WifiP2P);
     assert_eq!(DeviceType::from(32), DeviceType::Loopback);
@@ -43,6 +46,7 @@ fn device_type_display() {
     assert_eq!(format!("{}", DeviceType::WifiP2P), "Wi-Fi P2P");
     assert_eq!(format!("{}", DeviceType::Loopback), "Loopback");
     assert_eq!(format!("{}", DeviceType::Vlan), "VLAN");
+    assert_eq!(format!("{}", DeviceType::Modem), "Modem");
     assert_eq!(format!("{}", DeviceType::Other(42)), "Other(42)");
 }
 
@@ -88,6 +92,7 @@ fn device_type_requires_specific_object_registry() {
 #[test]
 fn device_type_has_global_enabled_state() {
     assert!(DeviceType::Wifi.has_global_enabled_state());
+    assert!(DeviceType::Modem.has_global_enabled_state());
     assert!(!DeviceType::Ethernet.has_global_enabled_state());
     assert!(!DeviceType::WifiP2P.has_global_enabled_state());
 }
@@ -105,6 +110,7 @@ fn device_type_connection_type_str() {
     assert_eq!(DeviceType::WifiP2P.connection_type_str(), "wifi-p2p");
     assert_eq!(DeviceType::Loopback.connection_type_str(), "loopback");
     assert_eq!(DeviceType::Vlan.connection_type_str(), "vlan");
+    assert_eq!(DeviceType::Modem.connection_type_str(), "gsm");
 }
 
 #[test]
@@ -120,6 +126,7 @@ fn device_type_to_code() {
     assert_eq!(DeviceType::Ethernet.to_code(), 1);
     assert_eq!(DeviceType::Wifi.to_code(), 2);
     assert_eq!(DeviceType::Vlan.to_code(), 11);
+    assert_eq!(DeviceType::Modem.to_code(), 8);
     assert_eq!(DeviceType::WifiP2P.to_code(), 30);
     assert_eq!(DeviceType::Loopback.to_code(), 32);
     assert_eq!(DeviceType::Other(999).to_code(), 999);
@@ -189,6 +196,11 @@ fn wifi_security_eap() {
             domain_suffix_match: None,
             ca_cert_path: None,
             system_ca_certs: false,
+            client_cert_path: None,
+            private_key_path: None,
+            private_key_password: None,
+            fast_provisioning: None,
+            pac_file: None,
             method: EapMethod::Peap,
             phase2: Phase2::Mschapv2,
         },
@@ -200,16 +212,191 @@ fn wifi_security_eap() {
 
 #[test]
 fn state_reason_from_u32_known_codes() {
-    assert_eq!(StateReason::from(0), StateReason::Unknown);
-    assert_eq!(StateReason::from(1), StateReason::None);
-    assert_eq!(StateReason::from(7), StateReason::SupplicantDisconnected);
-    assert_eq!(StateReason::from(8), StateReason::SupplicantConfigFailed);
-    assert_eq!(StateReason::from(9), StateReason::SupplicantFailed);
-    assert_eq!(StateReason::from(10), StateReason::SupplicantTimeout);
+    assert_eq!(StateReason::from(0), StateReason::None);
+    assert_eq!(StateReason::from(1), StateReason::Unknown);
+    assert_eq!(StateReason::from(7), StateReason::NoSecrets);
+    assert_eq!(StateReason::from(8), StateReason::SupplicantDisconnected);
+    assert_eq!(StateReason::from(9), StateReason::SupplicantConfigFailed);
+    assert_eq!(StateReason::from(10), StateReason::SupplicantFailed);
+    assert_eq!(StateReason::from(11), StateReason::SupplicantTimeout);
     assert_eq!(StateReason::from(16), StateReason::DhcpError);
     assert_eq!(StateReason::from(17), StateReason::DhcpFailed);
-    assert_eq!(StateReason::from(70), StateReason::SsidNotFound);
-    assert_eq!(StateReason::from(76), StateReason::SimPinIncorrect);
+    assert_eq!(StateReason::from(53), StateReason::SsidNotFound);
+    assert_eq!(StateReason::from(59), StateReason::SimPinIncorrect);
+}
+
+/// Every `NM_DEVICE_STATE_REASON_*` value from NetworkManager's
+/// `nm-dbus-interface.h`, with the variant it decodes to.
+#[test]
+fn state_reason_codes_match_nm_dbus_interface_h() {
+    use crate::types::constants::device_state_reason::*;
+
+    let header = [
+        (NONE, 0, StateReason::None),
+        (UNKNOWN, 1, StateReason::Unknown),
+        (NOW_MANAGED, 2, StateReason::NowManaged),
+        (NOW_UNMANAGED, 3, StateReason::NowUnmanaged),
+        (CONFIG_FAILED, 4, StateReason::ConfigFailed),
+        (IP_CONFIG_UNAVAILABLE, 5, StateReason::IpConfigUnavailable),
+        (IP_CONFIG_EXPIRED, 6, StateReason::IpConfigExpired),
+        (NO_SECRETS, 7, StateReason::NoSecrets),
+        (
+            SUPPLICANT_DISCONNECT,
+            8,
+            StateReason::SupplicantDisconnected,
+        ),
+        (
+            SUPPLICANT_CONFIG_FAILED,
+            9,
+            StateReason::SupplicantConfigFailed,
+        ),
+        (SUPPLICANT_FAILED, 10, StateReason::SupplicantFailed),
+        (SUPPLICANT_TIMEOUT, 11, StateReason::SupplicantTimeout),
+        (PPP_START_FAILED, 12, StateReason::PppStartFailed),
+        (PPP_DISCONNECT, 13, StateReason::PppDisconnected),
+        (PPP_FAILED, 14, StateReason::PppFailed),
+        (DHCP_START_FAILED, 15, StateReason::DhcpStartFailed),
+        (DHCP_ERROR, 16, StateReason::DhcpError),
+        (DHCP_FAILED, 17, StateReason::DhcpFailed),
+        (SHARED_START_FAILED, 18, StateReason::SharedStartFailed),
+        (SHARED_FAILED, 19, StateReason::SharedFailed),
+        (AUTOIP_START_FAILED, 20, StateReason::AutoIpStartFailed),
+        (AUTOIP_ERROR, 21, StateReason::AutoIpError),
+        (AUTOIP_FAILED, 22, StateReason::AutoIpFailed),
+        (MODEM_BUSY, 23, StateReason::ModemBusy),
+        (MODEM_NO_DIAL_TONE, 24, StateReason::ModemNoDialTone),
+        (MODEM_NO_CARRIER, 25, StateReason::ModemNoCarrier),
+        (MODEM_DIAL_TIMEOUT, 26, StateReason::ModemDialTimeout),
+        (MODEM_DIAL_FAILED, 27, StateReason::ModemConnectionFailed),
+        (MODEM_INIT_FAILED, 28, StateReason::ModemInitFailed),
+        (GSM_APN_FAILED, 29, StateReason::GsmApnSelectFailed),
+        (
+            GSM_REGISTRATION_NOT_SEARCHING,
+            30,
+            StateReason::GsmNotSearching,
+        ),
+        (
+            GSM_REGISTRATION_DENIED,
+            31,
+            StateReason::GsmRegistrationDenied,
+        ),
+        (
+            GSM_REGISTRATION_TIMEOUT,
+            32,
+            StateReason::GsmRegistrationTimeout,
+        ),
+        (
+            GSM_REGISTRATION_FAILED,
+            33,
+            StateReason::GsmRegistrationFailed,
+        ),
+        (GSM_PIN_CHECK_FAILED, 34, StateReason::GsmPinCheckFailed),
+        (FIRMWARE_MISSING, 35, StateReason::FirmwareMissing),
+        (REMOVED, 36, StateReason::DeviceRemoved),
+        (SLEEPING, 37, StateReason::Sleeping),
+        (CONNECTION_REMOVED, 38, StateReason::ConnectionRemoved),
+        (USER_REQUESTED, 39, StateReason::UserRequested),
+        (CARRIER, 40, StateReason::Carrier),
+        (CONNECTION_ASSUMED, 41, StateReason::ConnectionAssumed),
+        (SUPPLICANT_AVAILABLE, 42, StateReason::SupplicantAvailable),
+        (MODEM_NOT_FOUND, 43, StateReason::ModemNotFound),
+        (BT_FAILED, 44, StateReason::BluetoothFailed),
+        (GSM_SIM_NOT_INSERTED, 45, StateReason::GsmSimNotInserted),
+        (GSM_SIM_PIN_REQUIRED, 46, StateReason::GsmSimPinRequired),
+        (GSM_SIM_PUK_REQUIRED, 47, StateReason::GsmSimPukRequired),
+        (GSM_SIM_WRONG, 48, StateReason::GsmSimWrong),
+        (INFINIBAND_MODE, 49, StateReason::InfinibandMode),
+        (DEPENDENCY_FAILED, 50, StateReason::DependencyFailed),
+        (BR2684_FAILED, 51, StateReason::Br2684Failed),
+        (
+            MODEM_MANAGER_UNAVAILABLE,
+            52,
+            StateReason::ModemManagerUnavailable,
+        ),
+        (SSID_NOT_FOUND, 53, StateReason::SsidNotFound),
+        (
+            SECONDARY_CONNECTION_FAILED,
+            54,
+            StateReason::SecondaryConnectionFailed,
+        ),
+        (DCB_FCOE_FAILED, 55, StateReason::DcbFcoeFailed),
+        (TEAMD_CONTROL_FAILED, 56, StateReason::TeamdControlFailed),
+        (MODEM_FAILED, 57, StateReason::ModemFailed),
+        (MODEM_AVAILABLE, 58, StateReason::ModemAvailable),
+        (SIM_PIN_INCORRECT, 59, StateReason::SimPinIncorrect),
+        (NEW_ACTIVATION, 60, StateReason::NewActivationEnqueued),
+        (PARENT_CHANGED, 61, StateReason::ParentChanged),
+        (
+            PARENT_MANAGED_CHANGED,
+            62,
+            StateReason::ParentManagedChanged,
+        ),
+        (OVSDB_FAILED, 63, StateReason::OvsdbFailed),
+        (IP_ADDRESS_DUPLICATE, 64, StateReason::IpAddressDuplicate),
+        (IP_METHOD_UNSUPPORTED, 65, StateReason::IpMethodUnsupported),
+        (
+            SRIOV_CONFIGURATION_FAILED,
+            66,
+            StateReason::SriovConfigurationFailed,
+        ),
+        (PEER_NOT_FOUND, 67, StateReason::PeerNotFound),
+        (DEVICE_HANDLER_FAILED, 68, StateReason::DeviceHandlerFailed),
+        (
+            UNMANAGED_BY_DEFAULT,
+            69,
+            StateReason::Unmanaged(UnmanagedReason::ByDefault),
+        ),
+        (
+            UNMANAGED_EXTERNAL_DOWN,
+            70,
+            StateReason::Unmanaged(UnmanagedReason::ExternalDown),
+        ),
+        (
+            UNMANAGED_LINK_NOT_INIT,
+            71,
+            StateReason::Unmanaged(UnmanagedReason::LinkNotInitialized),
+        ),
+        (
+            UNMANAGED_QUITTING,
+            72,
+            StateReason::Unmanaged(UnmanagedReason::Quitting),
+        ),
+        (
+            UNMANAGED_SLEEPING,
+            73,
+            StateReason::Unmanaged(UnmanagedReason::Sleeping),
+        ),
+        (
+            UNMANAGED_USER_CONF,
+            74,
+            StateReason::Unmanaged(UnmanagedReason::UserConfig),
+        ),
+        (
+            UNMANAGED_USER_EXPLICIT,
+            75,
+            StateReason::Unmanaged(UnmanagedReason::UserExplicit),
+        ),
+        (
+            UNMANAGED_USER_SETTINGS,
+            76,
+            StateReason::Unmanaged(UnmanagedReason::UserSettings),
+        ),
+        (
+            UNMANAGED_USER_UDEV,
+            77,
+            StateReason::Unmanaged(UnmanagedReason::Udev),
+        ),
+        (
+            UNMANAGED_NETWORKING_OFF,
+            78,
+            StateReason::Unmanaged(UnmanagedReason::NetworkingOff),
+        ),
+    ];
+    for (constant, value, reason) in header {
+        assert_eq!(constant, value, "constant for {reason:?}");
+        assert_eq!(StateReason::from(value), reason, "code {value}");
+    }
+    assert_eq!(StateReason::from(79), StateReason::Other(79));
 }
 
 #[test]
@@ -235,16 +422,17 @@ fn state_reason_display() {
 
 #[test]
 fn reason_to_error_auth_failures() {
-    assert!(matches!(reason_to_error(9), ConnectionError::AuthFailed));
+    assert!(matches!(reason_to_error(10), ConnectionError::AuthFailed));
+    assert!(matches!(reason_to_error(8), ConnectionError::AuthFailed));
     assert!(matches!(reason_to_error(7), ConnectionError::AuthFailed));
-    assert!(matches!(reason_to_error(76), ConnectionError::AuthFailed));
-    assert!(matches!(reason_to_error(51), ConnectionError::AuthFailed));
+    assert!(matches!(reason_to_error(59), ConnectionError::AuthFailed));
+    assert!(matches!(reason_to_error(34), ConnectionError::AuthFailed));
 }
 
 #[test]
 fn reason_to_error_supplicant_config() {
     assert!(matches!(
-        reason_to_error(8),
+        reason_to_error(9),
         ConnectionError::SupplicantConfigFailed
     ));
 }
@@ -252,7 +440,7 @@ fn reason_to_error_supplicant_config() {
 #[test]
 fn reason_to_error_supplicant_timeout() {
     assert!(matches!(
-        reason_to_error(10),
+        reason_to_error(11),
         ConnectionError::SupplicantTimeout
     ));
 }
@@ -266,14 +454,14 @@ fn reason_to_error_dhcp_failures() {
 
 #[test]
 fn reason_to_error_network_not_found() {
-    assert!(matches!(reason_to_error(70), ConnectionError::NotFound));
+    assert!(matches!(reason_to_error(53), ConnectionError::NotFound));
 }
 
 #[test]
 fn reason_to_error_generic_failure() {
-    match reason_to_error(2) {
+    match reason_to_error(36) {
         ConnectionError::DeviceFailed(reason) => {
-            assert_eq!(reason, StateReason::UserDisconnected);
+            assert_eq!(reason, StateReason::DeviceRemoved);
         }
         _ => panic!("expected ConnectionError::Failed"),
     }
@@ -305,9 +493,9 @@ fn connection_error_display() {
     assert_eq!(
         format!(
             "{}",
-            ConnectionError::DeviceFailed(StateReason::CarrierChanged)
+            ConnectionError::DeviceFailed(StateReason::IpConfigUnavailable)
         ),
-        "connection failed: carrier changed"
+        "connection failed: IP configuration unavailable"
     );
 }
 
@@ -339,6 +527,18 @@ fn active_connection_state_from_u32() {
     );
 }
 
+#[test]
+fn uplink_kind_from_connection_type() {
+    assert_eq!(UplinkKind::from("802-11-wireless"), UplinkKind::Wifi);
+    assert_eq!(UplinkKind::from("802-3-ethernet"), UplinkKind::Ethernet);
+    assert_eq!(UplinkKind::from("gsm"), UplinkKind::Wwan);
+    assert_eq!(UplinkKind::from("cdma"), UplinkKind::Wwan);
+    assert_eq!(UplinkKind::from("wireguard"), UplinkKind::Vpn);
+    assert_eq!(UplinkKind::from("bluetooth"), UplinkKind::Bluetooth);
+    assert_eq!(UplinkKind::from("bond"), UplinkKind::Other("bond".into()));
+    assert_eq!(UplinkKind::Wwan.to_string(), "mobile broadband");
+}
+
 #[test]
 fn active_connection_state_display() {
     assert_eq!(format!("{}", ActiveConnectionState::Unknown), "unknown");
@@ -457,6 +657,43 @@ fn connection_state_reason_to_error_generic() {
     }
 }
 
+#[test]
+fn vpn_state_reason_from_u32() {
+    assert_eq!(VpnStateReason::from(8), VpnStateReason::ServiceStartFailed);
+    assert_eq!(VpnStateReason::from(10), VpnStateReason::LoginFailed);
+    assert_eq!(VpnStateReason::from(42), VpnStateReason::Other(42));
+}
+
+#[test]
+fn vpn_state_reason_to_error_mapping() {
+    assert!(matches!(
+        vpn_state_reason_to_error(9),
+        ConnectionError::VpnAuthFailed(VpnStateReason::NoSecrets)
+    ));
+    assert!(matches!(
+        vpn_state_reason_to_error(10),
+        ConnectionError::VpnAuthFailed(VpnStateReason::LoginFailed)
+    ));
+    for code in [4, 7, 8] {
+        assert!(matches!(
+            vpn_state_reason_to_error(code),
+            ConnectionError::VpnServiceFailed(_)
+        ));
+    }
+    assert!(matches!(
+        vpn_state_reason_to_error(6),
+        ConnectionError::Timeout
+    ));
+    assert!(matches!(
+        vpn_state_reason_to_error(5),
+        ConnectionError::VpnFailed(_)
+    ));
+    assert_eq!(
+        vpn_state_reason_to_error(8).to_string(),
+        "VPN service failed: VPN service failed to start"
+    );
+}
+
 #[test]
 fn connection_failed_error_display() {
     assert_eq!(
@@ -568,10 +805,12 @@ fn test_device_is_bluetooth() {
         identity: DeviceIdentity::new("00:1A:7D:DA:71:13".into(), "00:1A:7D:DA:71:13".into()),
         device_type: DeviceType::Bluetooth,
         state: DeviceState::Activated,
+        state_reason: StateReason::None,
         managed: Some(true),
         driver: Some("btusb".into()),
         ip4_address: None,
         ip6_address: None,
+        unavailable_reason: None,
     };
 
     assert!(bt_device.is_bluetooth());
@@ -954,6 +1193,25 @@ fn test_eap_options_builder_missing_phase2() {
     assert!(matches!(err, ConnectionError::IncompleteBuilder(_)));
 }
 
+#[test]
+fn test_eap_options_builder_tls_without_password() {
+    let opts = EapOptions::builder()
+        .identity("host/laptop")
+        .method(EapMethod::Tls)
+        .client_cert_path("file:///etc/pki/laptop.crt")
+        .private_key_path("file:///etc/pki/laptop.key")
+        .build()
+        .unwrap();
+    assert_eq!(
+        opts,
+        EapOptions::tls(
+            "host/laptop",
+            "file:///etc/pki/laptop.crt",
+            "file:///etc/pki/laptop.key"
+        )
+    );
+}
+
 #[test]
 fn test_vpn_credentials_builder_equivalence_to_new() {
     let peer = WireGuardPeer::new(
@@ -1066,6 +1324,172 @@ fn test_timeout_config_copy() {
     assert_eq!(config2.connection_timeout, Duration::from_secs(120));
 }
 
+#[test]
+fn test_timeout_config_scan_retries_default_disabled() {
+    let config = TimeoutConfig::default();
+    assert_eq!(config.scan_retries, 0);
+    assert_eq!(config.scan_retry_delay, Duration::from_secs(2));
+}
+
+#[test]
+fn test_timeout_config_scan_retry_wait_doubles() {
+    let config = TimeoutConfig::new()
+        .with_scan_retries(3)
+        .with_scan_retry_delay(Duration::from_millis(500));
+    assert_eq!(config.scan_retries, 3);
+    assert_eq!(config.scan_retry_wait(1), Duration::from_millis(500));
+    assert_eq!(config.scan_retry_wait(2), Duration::from_secs(1));
+    assert_eq!(config.scan_retry_wait(3), Duration::from_secs(2));
+}
+
+#[test]
+fn test_activation_stage_from_device_state() {
+    assert_eq!(
+        ActivationStage::from_device_state(DeviceState::Prepare),
+        Some(ActivationStage::Prepare)
+    );
+    assert_eq!(
+        ActivationStage::from_device_state(DeviceState::IpConfig),
+        Some(ActivationStage::IpConfig)
+    );
+    assert_eq!(
+        ActivationStage::from_device_state(DeviceState::Activated),
+        Some(ActivationStage::Activated)
+    );
+    assert_eq!(
+        ActivationStage::from_device_state(DeviceState::Disconnected),
+        None
+    );
+    assert_eq!(
+        ActivationStage::from_device_state(DeviceState::Deactivating),
+        None
+    );
+    assert_eq!(
+        ActivationStage::from_device_state(DeviceState::Failed),
+        None
+    );
+}
+
+#[test]
+fn test_activation_stage_display() {
+    assert_eq!(
+        ActivationStage::IpConfig.to_string(),
+        "requesting IP address"
+    );
+    assert_eq!(
+        ActivationStage::NeedAuth.to_string(),
+        "waiting for credentials"
+    );
+}
+
+#[test]
+fn test_connect_progress_from_activation_stage() {
+    assert!(matches!(
+        ConnectProgress::from(ActivationStage::Config),
+        ConnectProgress::Associating
+    ));
+    assert!(matches!(
+        ConnectProgress::from(ActivationStage::NeedAuth),
+        ConnectProgress::Authenticating
+    ));
+    assert!(matches!(
+        ConnectProgress::from(ActivationStage::IpCheck),
+        ConnectProgress::GettingIp
+    ));
+    assert!(ConnectProgress::from(ActivationStage::Activated).is_terminal());
+    assert!(ConnectProgress::Failed(ConnectionError::NotFound).is_terminal());
+    assert!(!ConnectProgress::Scanning.is_terminal());
+}
+
+#[test]
+fn test_unmanaged_reason_from_state_reason() {
+    let eth = DeviceType::Ethernet;
+    assert_eq!(
+        UnmanagedReason::from_state_reason(77, &eth),
+        UnmanagedReason::Udev
+    );
+    assert_eq!(
+        UnmanagedReason::from_state_reason(74, &eth),
+        UnmanagedReason::UserConfig
+    );
+    assert_eq!(
+        UnmanagedReason::from_state_reason(75, &eth),
+        UnmanagedReason::UserExplicit
+    );
+    assert_eq!(
+        UnmanagedReason::from_state_reason(69, &eth),
+        UnmanagedReason::ByDefault
+    );
+    assert_eq!(
+        UnmanagedReason::from_state_reason(37, &eth),
+        UnmanagedReason::Sleeping
+    );
+    assert_eq!(
+        UnmanagedReason::from_state_reason(73, &eth),
+        UnmanagedReason::Sleeping
+    );
+    assert_eq!(
+        UnmanagedReason::from_state_reason(79, &eth),
+        UnmanagedReason::Other(79)
+    );
+    assert_eq!(
+        UnmanagedReason::from_state_reason(3, &eth),
+        UnmanagedReason::Unknown
+    );
+    assert_eq!(
+        UnmanagedReason::from_state_reason(3, &DeviceType::Loopback),
+        UnmanagedReason::ByDefault
+    );
+    assert_eq!(
+        UnmanagedReason::from_state_reason(500, &eth),
+        UnmanagedReason::Other(500)
+    );
+}
+
+#[test]
+fn test_unavailable_reason_classify() {
+    assert_eq!(
+        UnavailableReason::classify(true, true, Some((false, false)), None),
+        UnavailableReason::FirmwareMissing
+    );
+    assert_eq!(
+        UnavailableReason::classify(false, true, None, None),
+        UnavailableReason::PluginMissing
+    );
+    assert_eq!(
+        UnavailableReason::classify(false, false, Some((true, false)), None),
+        UnavailableReason::RadioHardwareBlocked
+    );
+    assert_eq!(
+        UnavailableReason::classify(false, false, Some((false, true)), None),
+        UnavailableReason::RadioDisabled
+    );
+    assert_eq!(
+        UnavailableReason::classify(false, false, None, Some(false)),
+        UnavailableReason::NoCarrier
+    );
+    assert_eq!(
+        UnavailableReason::classify(false, false, Some((true, true)), None),
+        UnavailableReason::Unknown
+    );
+}
+
+#[test]
+fn test_device_identity_same_hardware() {
+    let before = DeviceIdentity::new("AA:BB:CC:DD:EE:FF".into(), "12:34:56:78:9A:BC".into());
+    // Re-plugged with a new randomized MAC: still the same hardware.
+    let after = DeviceIdentity::new("aa:bb:cc:dd:ee:ff".into(), "02:00:00:00:00:01".into());
+    assert!(before.same_hardware(&after));
+
+    let other = DeviceIdentity::new("11:22:33:44:55:66".into(), "12:34:56:78:9A:BC".into());
+    assert!(!before.same_hardware(&other));
+
+    // No permanent MAC on either side: fall back to the current MAC.
+    let virt_a = DeviceIdentity::new("".into(), "52:54:00:12:34:56".into());
+    let virt_b = DeviceIdentity::new("00:00:00:00:00:00".into(), "52:54:00:12:34:56".into());
+    assert!(virt_a.same_hardware(&virt_b));
+}
+
 #[test]
 fn test_device_state_is_transitional() {
     let transitional = [
@@ -1107,3 +1531,252 @@ fn test_device_state_from_u32_intermediate_states() {
     assert_eq!(DeviceState::from(90), DeviceState::Secondaries);
     assert_eq!(DeviceState::from(110), DeviceState::Deactivating);
 }
+
+#[test]
+fn test_match_options_default_is_exact() {
+    let opts = MatchOptions::default();
+    assert!(opts.matches("Home", "Home"));
+    assert!(!opts.matches("home", "Home"));
+    assert!(!opts.matches("Home ", "Home"));
+}
+
+#[test]
+fn test_match_options_normalization() {
+    let opts = MatchOptions::new()
+        .with_case_insensitive(true)
+        .with_trim_whitespace(true);
+    assert!(opts.matches("  home\t", "HOME"));
+    assert!(!opts.matches("home2", "HOME"));
+}
+
+#[test]
+fn test_match_options_ssid_bytes() {
+    let opts = MatchOptions::default();
+    assert!(opts.matches_ssid(b"Cafe", "Cafe"));
+    assert!(!opts.matches_ssid(b"cafe", "Cafe"));
+    assert!(
+        MatchOptions::new()
+            .with_case_insensitive(true)
+            .matches_ssid(b"cafe", "Cafe")
+    );
+    assert!(
+        !MatchOptions::new()
+            .with_ssid_match(false)
+            .matches_ssid(b"Cafe", "Cafe")
+    );
+}
+
+fn link_sample(strength: u8, signal_dbm: Option<i32>, age: Duration) -> LinkStats {
+    LinkStats {
+        interface: "wlan0".into(),
+        ssid: "Mesh".into(),
+        bssid: "AA:BB:CC:DD:EE:FF".into(),
+        frequency_mhz: 5180,
+        strength,
+        tx_bitrate_kbps: 433_000,
+        signal_dbm,
+        avg_signal_dbm: None,
+        noise_dbm: Some(-95),
+        rx_bitrate_kbps: signal_dbm.map(|_| 390_000),
+        tx_retries: None,
+        tx_failed: None,
+        beacon_losses: None,
+        sampled_at: std::time::SystemTime::now() - age,
+    }
+}
+
+#[test]
+fn link_summary_aggregates_samples() {
+    let samples = [
+        link_sample(40, Some(-70), Duration::ZERO),
+        link_sample(80, None, Duration::ZERO),
+        link_sample(60, Some(-50), Duration::ZERO),
+    ];
+    let summary = LinkSummary::from_samples(&samples).unwrap();
+    assert_eq!(summary.samples, 3);
+    assert_eq!(
+        (
+            summary.strength.min,
+            summary.strength.max,
+            summary.strength.avg
+        ),
+        (40.0, 80.0, 60.0)
+    );
+    let signal = summary.signal_dbm.unwrap();
+    assert_eq!((signal.min, signal.max, signal.avg), (-70.0, -50.0, -60.0));
+    assert_eq!(summary.rx_bitrate_kbps.unwrap().avg, 390_000.0);
+    assert_eq!(samples[0].snr_db(), Some(25));
+    assert!(LinkSummary::from_samples(&[]).is_none());
+}
+
+#[test]
+fn stats_collector_evicts_and_windows() {
+    let collector = crate::StatsCollector::new(2);
+    collector.record(link_sample(10, None, Duration::from_secs(600)));
+    collector.record(link_sample(20, None, Duration::from_secs(300)));
+    collector.record(link_sample(30, None, Duration::ZERO));
+
+    let strengths: Vec<u8> = collector.samples().iter().map(|s| s.strength).collect();
+    assert_eq!(strengths, [20, 30]);
+    assert_eq!(collector.latest().unwrap().strength, 30);
+
+    let recent = collector.summary(Duration::from_secs(60)).unwrap();
+    assert_eq!(recent.samples, 1);
+    assert!(recent.signal_dbm.is_none());
+
+    collector.clear();
+    assert!(collector.summary(Duration::from_secs(3600)).is_none());
+}
+
+#[test]
+fn models_round_trip_through_json() {
+    let device = Device {
+        path: "/org/freedesktop/NetworkManager/Devices/2".into(),
+        interface: "wlan0".into(),
+        identity: DeviceIdentity::new("aa:bb:cc:dd:ee:ff".into(), "aa:bb:cc:dd:ee:ff".into()),
+        device_type: DeviceType::Wifi,
+        state: DeviceState::Other(99),
+        state_reason: StateReason::from(0),
+        managed: Some(true),
+        driver: Some("iwlwifi".into()),
+        ip4_address: None,
+        ip6_address: None,
+        unavailable_reason: None,
+    };
+    let json = serde_json::to_string(&device).unwrap();
+    let parsed: Device = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.interface, "wlan0");
+    assert_eq!(parsed.state, DeviceState::Other(99));
+
+    let opts: ConnectionOptions =
+        serde_json::from_str(r#"{"autoconnect": false, "trust_level": "Work"}"#).unwrap();
+    assert!(!opts.autoconnect);
+    assert_eq!(opts.trust_level, Some(TrustLevel::Work));
+    assert!(opts.ipv4.is_none());
+}
+
+#[test]
+fn secrets_are_not_serialized() {
+    let eap = EapOptions::tls("host/laptop", "file:///c.pem", "file:///k.pem")
+        .with_private_key_password("key secret");
+    let eap = EapOptions {
+        password: "hunter22".into(),
+        ..eap
+    };
+    let json = serde_json::to_string(&eap).unwrap();
+    assert!(!json.contains("hunter22") && !json.contains("key secret"));
+    let parsed: EapOptions = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.identity, "host/laptop");
+    assert!(parsed.password.is_empty() && parsed.private_key_password.is_none());
+
+    let wg = VpnType::WireGuard {
+        private_key: Some("c2VjcmV0".into()),
+        peer_public_key: None,
+        endpoint: Some("vpn.example.com:51820".into()),
+        allowed_ips: vec!["0.0.0.0/0".into()],
+        persistent_keepalive: None,
+    };
+    let json = serde_json::to_string(&wg).unwrap();
+    assert!(!json.contains("c2VjcmV0") && json.contains("vpn.example.com"));
+}
+
+#[test]
+fn connection_error_serializes_kind_and_message() {
+    let json = |e: ConnectionError| serde_json::to_value(e).unwrap();
+    assert_eq!(
+        json(ConnectionError::AuthFailed),
+        serde_json::json!({"kind": "AuthFailed", "message": "authentication failed"})
+    );
+    assert_eq!(
+        json(ConnectionError::InvalidVlanId { id: 0 })["kind"],
+        "InvalidVlanId"
+    );
+    assert_eq!(
+        json(ConnectionError::DeviceFailed(StateReason::DhcpFailed))["kind"],
+        "DeviceFailed"
+    );
+}
+
+#[test]
+fn secrets_are_redacted_in_debug_output() {
+    let psk = WifiSecurity::WpaPsk {
+        psk: "hunter22".into(),
+    };
+    assert!(!format!("{psk:?}").contains("hunter22"));
+    assert_eq!(WifiSecurity::wpa_psk(Secret::new("hunter22")), psk);
+
+    let eap = EapOptions::new("alice", "s3cr3t-pass").with_private_key_password("key-pass");
+    let debug = format!("{eap:?}");
+    assert!(!debug.contains("s3cr3t-pass") && !debug.contains("key-pass"));
+    assert!(debug.contains("alice"));
+
+    let config = WireGuardConfig::new(
+        "wg",
+        "vpn.example.com:51820",
+        Secret::new("YBk6X3pP8KjKz7+HFWzVHNqL3qTZq8hX9VxFQJ4zVmM="),
+        "10.0.0.2/24",
+        Vec::new(),
+    );
+    assert!(!format!("{config:?}").contains("YBk6X3pP8KjKz7"));
+
+    let creds = VpnCredentials::new(
+        VpnKind::WireGuard,
+        "wg",
+        "vpn.example.com:51820",
+        "YBk6X3pP8KjKz7+HFWzVHNqL3qTZq8hX9VxFQJ4zVmM=",
+        "10.0.0.2/24",
+        Vec::new(),
+    );
+    assert!(!format!("{creds:?}").contains("YBk6X3pP8KjKz7"));
+}
+
+#[test]
+fn connect_policy_retries_transient_errors_with_backoff() {
+    let policy = ConnectPolicy::new()
+        .with_max_attempts(4)
+        .with_backoff(Duration::from_secs(1), Duration::from_secs(3));
+    assert!(policy.should_retry(&ConnectionError::Timeout, 1));
+    assert!(policy.should_retry(&ConnectionError::DhcpFailed, 3));
+    assert!(!policy.should_retry(&ConnectionError::DhcpFailed, 4));
+    assert!(!policy.should_retry(&ConnectionError::AuthFailed, 1));
+    assert!(!policy.should_retry(&ConnectionError::NotFound, 1));
+    assert!(!policy.should_retry(&ConnectionError::Cancelled, 1));
+
+    let delays: Vec<u64> = (1..=4).map(|a| policy.delay(a).as_secs()).collect();
+    assert_eq!(delays, [1, 2, 3, 3]);
+
+    assert_eq!(
+        ConnectionError::SupplicantTimeout.class(),
+        ErrorClass::SupplicantTimeout
+    );
+    assert_eq!(
+        ConnectionError::DeviceFailed(StateReason::Unknown).class(),
+        ErrorClass::Activation
+    );
+}
+
+#[test]
+fn wifi_options_select_band_and_channel() {
+    use crate::api::builders::WifiBand;
+
+    let any = WifiOptions::new();
+    assert!(any.allows(2412) && any.allows(5180));
+
+    let five = WifiOptions::new().with_band(WifiBand::A);
+    assert!(five.allows(5180) && !five.allows(2437));
+
+    let ch6 = WifiOptions::new().with_band(WifiBand::Bg).with_channel(6);
+    assert!(ch6.validate().is_ok());
+    assert!(ch6.allows(2437) && !ch6.allows(2412) && !ch6.allows(5180));
+
+    for bad in [
+        WifiOptions::new().with_channel(36),
+        WifiOptions::new().with_band(WifiBand::Bg).with_channel(36),
+        WifiOptions::new().with_band(WifiBand::A).with_channel(0),
+    ] {
+        assert!(matches!(
+            bad.validate(),
+            Err(ConnectionError::InvalidInput { .. })
+        ));
+    }
+}
@@ -0,0 +1,50 @@
+//! Device traffic counters and throughput.
+
+use std::time::Duration;
+
+/// Byte counters of a device from `Device.Statistics`.
+///
+/// Counters start when the device appears and reset when it is recreated.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrafficStats {
+    /// Interface name (e.g. `"wlan0"`).
+    pub interface: String,
+    /// Bytes received.
+    pub rx_bytes: u64,
+    /// Bytes transmitted.
+    pub tx_bytes: u64,
+}
+
+/// Traffic of a device between two consecutive readings.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrafficDelta {
+    /// Interface name (e.g. `"wlan0"`).
+    pub interface: String,
+    /// Bytes received since the previous reading.
+    pub rx_bytes: u64,
+    /// Bytes transmitted since the previous reading.
+    pub tx_bytes: u64,
+    /// Time since the previous reading.
+    pub elapsed: Duration,
+}
+
+impl TrafficDelta {
+    /// Receive throughput in bytes per second.
+    #[must_use]
+    pub fn rx_bytes_per_sec(&self) -> f64 {
+        per_sec(self.rx_bytes, self.elapsed)
+    }
+
+    /// Transmit throughput in bytes per second.
+    #[must_use]
+    pub fn tx_bytes_per_sec(&self) -> f64 {
+        per_sec(self.tx_bytes, self.elapsed)
+    }
+}
+
+fn per_sec(bytes: u64, elapsed: Duration) -> f64 {
+    let secs = elapsed.as_secs_f64();
+    if secs > 0.0 { bytes as f64 / secs } else { 0.0 }
+}
@@ -6,11 +6,16 @@
 //! protocol-specific metadata decoded from NM settings.
 
 use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
 
+use super::connection_state::ConnectionStateReason;
 use super::device::DeviceState;
+use super::error::ConnectionError;
+use super::ikev2::Ikev2Config;
 use super::openvpn::OpenVpnConfig;
 use super::saved_connection::VpnSecretFlags;
 use super::wireguard::WireGuardConfig;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 pub(crate) mod sealed {
@@ -19,7 +24,7 @@ pub(crate) mod sealed {
 
 /// Whether a VPN connection is a NM-plugin VPN or kernel WireGuard.
 #[non_exhaustive]
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum VpnKind {
     /// NM VPN plugin (OpenVPN, strongSwan, OpenConnect, PPTP, L2TP, …).
     Plugin,
@@ -29,7 +34,7 @@ pub enum VpnKind {
 
 /// OpenVPN authentication/connection type.
 #[non_exhaustive]
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum OpenVpnConnectionType {
     /// Pure TLS certificate authentication.
     Tls,
@@ -61,11 +66,13 @@ impl OpenVpnConnectionType {
 /// Each variant carries the fields an applet typically needs to render a VPN
 /// list entry.
 #[non_exhaustive]
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum VpnType {
     /// Kernel WireGuard tunnel.
     WireGuard {
-        /// Interface private key (often agent-owned and absent).
+        /// Interface private key (often agent-owned and absent). Not
+        /// serialized.
+        #[serde(skip_serializing, default)]
         private_key: Option<String>,
         /// First peer's public key.
         peer_public_key: Option<String>,
@@ -146,6 +153,8 @@ pub enum VpnType {
         /// Raw `vpn.data` key-value pairs.
         data: HashMap<String, String>,
         /// Raw `vpn.secrets` key-value pairs (often empty without agent).
+        /// Not serialized.
+        #[serde(skip_serializing, default)]
         secrets: HashMap<String, String>,
         /// VPN-level user name.
         user_name: Option<String>,
@@ -164,6 +173,8 @@ pub enum VpnConfiguration {
     WireGuard(WireGuardConfig),
     /// OpenVPN configuration
     OpenVpn(Box<OpenVpnConfig>),
+    /// IKEv2 configuration (strongSwan plugin).
+    Ikev2(Box<Ikev2Config>),
 }
 
 impl From<WireGuardConfig> for VpnConfiguration {
@@ -178,13 +189,19 @@ impl From<OpenVpnConfig> for VpnConfiguration {
     }
 }
 
+impl From<Ikev2Config> for VpnConfiguration {
+    fn from(config: Ikev2Config) -> Self {
+        Self::Ikev2(Box::new(config))
+    }
+}
+
 impl sealed::Sealed for VpnConfiguration {}
 
 impl VpnConfig for VpnConfiguration {
     fn vpn_kind(&self) -> VpnKind {
         match self {
             Self::WireGuard(_) => VpnKind::WireGuard,
-            Self::OpenVpn(_) => VpnKind::Plugin,
+            Self::OpenVpn(_) | Self::Ikev2(_) => VpnKind::Plugin,
         }
     }
 
@@ -192,6 +209,7 @@ impl VpnConfig for VpnConfiguration {
         match self {
             Self::WireGuard(c) => &c.name,
             Self::OpenVpn(c) => &c.name,
+            Self::Ikev2(c) => &c.name,
         }
     }
 
@@ -199,6 +217,7 @@ impl VpnConfig for VpnConfiguration {
         match self {
             Self::WireGuard(c) => c.dns.as_deref(),
             Self::OpenVpn(c) => c.dns.as_deref(),
+            Self::Ikev2(c) => c.dns.as_deref(),
         }
     }
 
@@ -206,6 +225,7 @@ impl VpnConfig for VpnConfiguration {
         match self {
             Self::WireGuard(c) => c.mtu,
             Self::OpenVpn(c) => c.mtu,
+            Self::Ikev2(c) => c.mtu,
         }
     }
 
@@ -213,6 +233,7 @@ impl VpnConfig for VpnConfiguration {
         match self {
             Self::WireGuard(c) => c.uuid,
             Self::OpenVpn(c) => c.uuid,
+            Self::Ikev2(c) => c.uuid,
         }
     }
 }
@@ -220,7 +241,8 @@ impl VpnConfig for VpnConfiguration {
 /// Common metadata shared by VPN connection configurations.
 ///
 /// This trait is sealed and cannot be implemented outside of this crate.
-/// Use [`WireGuardConfig`], [`OpenVpnConfig`], or [`VpnConfiguration`] instead.
+/// Use [`WireGuardConfig`], [`OpenVpnConfig`], [`Ikev2Config`], or
+/// [`VpnConfiguration`] instead.
 pub trait VpnConfig: sealed::Sealed + Send + Sync + std::fmt::Debug {
     /// Returns whether this is a plugin VPN or kernel WireGuard.
     fn vpn_kind(&self) -> VpnKind;
@@ -250,7 +272,7 @@ pub trait VpnConfig: sealed::Sealed + Send + Sync + std::fmt::Debug {
 /// println!("{} ({:?}) active={}", vpn.id, vpn.kind, vpn.active);
 /// ```
 #[non_exhaustive]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VpnConnection {
     /// NM connection UUID.
     pub uuid: String,
@@ -274,6 +296,21 @@ pub struct VpnConnection {
     pub service_type: String,
     /// Plugin-based vs kernel WireGuard.
     pub kind: VpnKind,
+    /// When NetworkManager last stamped the profile as activated
+    /// (`connection.timestamp`), if it is active.
+    pub activated_at: Option<SystemTime>,
+}
+
+impl VpnConnection {
+    /// Time since [`activated_at`](Self::activated_at), or `None` if the
+    /// VPN is not active.
+    ///
+    /// NetworkManager only stamps the profile at whole seconds, so this is
+    /// accurate to about a second.
+    #[must_use]
+    pub fn uptime(&self) -> Option<Duration> {
+        SystemTime::now().duration_since(self.activated_at?).ok()
+    }
 }
 
 /// Protocol-specific details for an active VPN connection.
@@ -281,7 +318,7 @@ pub struct VpnConnection {
 /// Provides configuration details extracted from the NetworkManager connection
 /// profile, varying by VPN type.
 #[non_exhaustive]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum VpnDetails {
     /// WireGuard-specific connection details.
     WireGuard {
@@ -307,6 +344,66 @@ pub enum VpnDetails {
     },
 }
 
+/// What a [`VpnSupervisor`](crate::VpnSupervisor) does when its VPN drops
+/// without the user disconnecting it.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VpnSupervisorPolicy {
+    /// Re-activate the VPN, backing off between attempts. Traffic keeps
+    /// flowing over the plain uplink in the meantime.
+    #[default]
+    Reconnect,
+    /// Disconnect the Ethernet, Wi-Fi and mobile broadband connections that
+    /// were up alongside the VPN, then bring them back together with the
+    /// VPN, backing off between attempts.
+    ///
+    /// The uplinks stay down between attempts and after giving up. Each
+    /// attempt brings them up just before activating the VPN over them, so
+    /// traffic can leave outside the tunnel for that short window; if the
+    /// activation fails they are disconnected again.
+    KillSwitch,
+}
+
+/// Progress of a [`VpnSupervisor`](crate::VpnSupervisor).
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub enum VpnSupervisorEvent {
+    /// The VPN went down unexpectedly.
+    Dropped {
+        /// Why NetworkManager deactivated it.
+        reason: ConnectionStateReason,
+    },
+    /// [`KillSwitch`](VpnSupervisorPolicy::KillSwitch) disconnected these
+    /// interfaces to stop traffic outside the tunnel.
+    TrafficBlocked {
+        /// Interfaces that were disconnected.
+        interfaces: Vec<String>,
+    },
+    /// A reconnection attempt starts after `delay`.
+    Reconnecting {
+        /// 1-based attempt number.
+        attempt: u32,
+        /// Backoff before this attempt.
+        delay: Duration,
+    },
+    /// A reconnection attempt failed.
+    ReconnectFailed {
+        /// 1-based attempt number.
+        attempt: u32,
+        /// Why the attempt failed.
+        error: ConnectionError,
+    },
+    /// The VPN is up again (and, with a kill switch, so are its uplinks).
+    Reconnected,
+    /// The attempt limit was reached; supervision ends.
+    GaveUp {
+        /// Number of failed attempts.
+        attempts: u32,
+    },
+    /// The VPN was disconnected on purpose; supervision ends.
+    Stopped,
+}
+
 /// Detailed VPN connection information and statistics.
 ///
 /// Provides comprehensive information about an active VPN connection,
@@ -322,7 +419,7 @@ pub enum VpnDetails {
 /// }
 /// ```
 #[non_exhaustive]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VpnConnectionInfo {
     /// The connection name/identifier.
     pub name: String,
@@ -1,7 +1,16 @@
+use std::fmt;
+use std::time::{Duration, SystemTime};
+
 use serde::{Deserialize, Serialize};
 
-use super::access_point::SecurityFeatures;
+use super::access_point::{
+    AccessPoint, ApMode, CipherSuites, SecurityFeatures, WifiStandard, WpaGeneration, age_of,
+};
 use super::error::ConnectionError;
+use super::secret::{Redacted, Secret};
+use crate::api::builders::WifiBand;
+use crate::types::constants::frequency;
+use crate::util::utils::channel_from_freq;
 
 /// Represents a Wi-Fi network discovered during a scan.
 ///
@@ -73,6 +82,54 @@ pub struct Network {
     /// Decoded security capabilities from NM flag triplet.
     #[serde(default)]
     pub security_features: SecurityFeatures,
+    /// Operating mode of the access points advertising this SSID.
+    ///
+    /// Ad-hoc and mesh beacons are listed separately from infrastructure
+    /// networks with the same SSID; see [`is_joinable`](Self::is_joinable).
+    #[serde(default)]
+    pub mode: ApMode,
+    /// Wall-clock time of the most recent scan that saw any of this
+    /// network's access points, or `None` if unknown. See [`age`](Self::age).
+    #[serde(default)]
+    pub seen: Option<SystemTime>,
+}
+
+/// A visible Wi-Fi network joined with its saved profiles.
+///
+/// Returned by
+/// [`NetworkManager::known_networks`](crate::NetworkManager::known_networks)
+/// so a UI can tell remembered networks from merely visible ones.
+///
+/// # Examples
+///
+/// ```no_run
+/// use nmrs::NetworkManager;
+///
+/// # async fn example() -> nmrs::Result<()> {
+/// let nm = NetworkManager::new().await?;
+/// for known in nm.known_networks(None).await? {
+///     if known.saved {
+///         println!(
+///             "{} (autoconnect: {}, last used: {:?})",
+///             known.network.ssid, known.autoconnect, known.last_connected
+///         );
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct KnownNetwork {
+    /// The network as returned by `list_networks`.
+    pub network: Network,
+    /// `true` if a saved Wi-Fi profile exists for the SSID.
+    pub saved: bool,
+    /// `true` if any saved profile for the SSID may be auto-activated.
+    pub autoconnect: bool,
+    /// Most recent activation of any saved profile for the SSID
+    /// (`connection.timestamp`), or `None` if never connected.
+    pub last_connected: Option<SystemTime>,
 }
 
 /// Detailed information about a Wi-Fi network.
@@ -125,6 +182,8 @@ pub struct NetworkInfo {
     pub bars: String,
     /// Security type description
     pub security: String,
+    /// Pairwise/group ciphers and management frame protection status
+    pub ciphers: super::CipherSuites,
     /// Connection status
     pub status: String,
     /// Assigned IPv4 address with CIDR notation (only present when connected)
@@ -133,11 +192,63 @@ pub struct NetworkInfo {
     pub ip6_address: Option<String>,
 }
 
+/// Everything NetworkManager knows about one access point of a network,
+/// comparable to `nmcli -f all dev wifi list`.
+///
+/// Returned by
+/// [`NetworkManager::network_details`](crate::NetworkManager::network_details),
+/// which describes the access point in use, or else the strongest one.
+///
+/// # Examples
+///
+/// ```no_run
+/// use nmrs::NetworkManager;
+///
+/// # async fn example() -> nmrs::Result<()> {
+/// let nm = NetworkManager::new().await?;
+/// for net in nm.list_networks(None).await? {
+///     let details = nm.network_details(&net).await?;
+///     println!(
+///         "{} {} {} {}{}",
+///         details.access_point.bssid,
+///         details.wpa,
+///         details.standard.map(|s| s.to_string()).unwrap_or_default(),
+///         details.ciphers,
+///         if details.saved { " (saved)" } else { "" },
+///     );
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkDetails {
+    /// The access point described, with BSSID, frequency, bitrate,
+    /// strength and when it was last seen.
+    pub access_point: AccessPoint,
+    /// Pairwise/group ciphers and management frame protection status.
+    pub ciphers: CipherSuites,
+    /// Highest WPA generation offered.
+    pub wpa: WpaGeneration,
+    /// Likely 802.11 standard, guessed from band and maximum bitrate.
+    pub standard: Option<WifiStandard>,
+    /// `true` if a saved connection profile exists for the SSID.
+    pub saved: bool,
+}
+
+impl NetworkDetails {
+    /// Time since a scan last saw the access point.
+    #[must_use]
+    pub fn age(&self) -> Option<Duration> {
+        self.access_point.age()
+    }
+}
+
 /// EAP (Extensible Authentication Protocol) method for WPA-Enterprise Wi-Fi.
 ///
 /// These are the outer authentication methods used in 802.1X authentication.
 #[non_exhaustive]
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EapMethod {
     /// Protected EAP (PEAPv0) - tunnels inner authentication in TLS.
     /// Most commonly used with MSCHAPv2 inner authentication.
@@ -145,6 +256,22 @@ pub enum EapMethod {
     /// Tunneled TLS (EAP-TTLS) - similar to PEAP but more flexible.
     /// Can use various inner authentication methods like PAP or MSCHAPv2.
     Ttls,
+    /// EAP-TLS - mutual certificate authentication, no password.
+    /// Requires a client certificate and private key; Phase 2 is not used.
+    Tls,
+    /// EAP-pwd - password authentication without certificates.
+    /// Phase 2 is not used.
+    Pwd,
+    /// EAP-FAST - Cisco's tunnel built from a Protected Access Credential
+    /// (PAC), usually with GTC or MSCHAPv2 inner authentication.
+    Fast,
+}
+
+impl EapMethod {
+    /// Whether the method runs a Phase 2 (inner) authentication.
+    pub(crate) fn uses_phase2(&self) -> bool {
+        matches!(self, Self::Peap | Self::Ttls | Self::Fast)
+    }
 }
 
 /// Phase 2 (inner) authentication methods for EAP connections.
@@ -152,7 +279,7 @@ pub enum EapMethod {
 /// These methods run inside the TLS tunnel established by the outer
 /// EAP method (PEAP or TTLS).
 #[non_exhaustive]
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Phase2 {
     /// Microsoft Challenge Handshake Authentication Protocol v2.
     /// More secure than PAP, commonly used with PEAP.
@@ -161,6 +288,38 @@ pub enum Phase2 {
     /// Simple plaintext password (protected by TLS tunnel).
     /// Often used with TTLS.
     Pap,
+    /// Generic Token Card.
+    /// Used for one-time passwords and tokens, commonly with EAP-FAST.
+    Gtc,
+}
+
+/// How EAP-FAST obtains its Protected Access Credential (PAC).
+///
+/// Maps to `802-1x.phase1-fast-provisioning`.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FastProvisioning {
+    /// No in-band provisioning; an existing PAC file is required.
+    Disabled,
+    /// Anonymous provisioning (unauthenticated Diffie-Hellman).
+    Unauthenticated,
+    /// Provisioning inside a server-authenticated TLS tunnel.
+    Authenticated,
+    /// Allow both anonymous and authenticated provisioning.
+    Both,
+}
+
+impl FastProvisioning {
+    /// Value of the `phase1-fast-provisioning` property.
+    #[must_use]
+    pub fn as_nm_str(self) -> &'static str {
+        match self {
+            Self::Disabled => "0",
+            Self::Unauthenticated => "1",
+            Self::Authenticated => "2",
+            Self::Both => "3",
+        }
+    }
 }
 
 /// EAP options for WPA-EAP (Enterprise) Wi-Fi connections.
@@ -193,12 +352,46 @@ pub enum Phase2 {
 ///     .with_method(EapMethod::Ttls)
 ///     .with_phase2(Phase2::Pap);
 /// ```
+///
+/// ## TLS with a Machine Certificate
+///
+/// ```rust
+/// use nmrs::EapOptions;
+///
+/// let opts = EapOptions::tls(
+///     "host/laptop.company.com",
+///     "file:///etc/pki/wifi/laptop.crt",
+///     "file:///etc/pki/wifi/laptop.key",
+/// )
+/// .with_private_key_password("key passphrase")
+/// .with_ca_cert_path("file:///etc/pki/wifi/ca.pem");
+/// ```
+///
+/// ## FAST with GTC (Cisco)
+///
+/// ```rust
+/// use nmrs::{EapMethod, EapOptions, FastProvisioning, Phase2};
+///
+/// let opts = EapOptions::new("jdoe", "token-code")
+///     .with_method(EapMethod::Fast)
+///     .with_phase2(Phase2::Gtc)
+///     .with_fast_provisioning(FastProvisioning::Unauthenticated)
+///     .with_pac_file("/var/lib/NetworkManager/corp.pac");
+/// ```
+///
+/// # Serialization
+///
+/// `password` and `private_key_password` are never serialized, so options
+/// can be logged or written out as JSON without leaking them. They are
+/// empty / `None` after deserializing such output.
 #[non_exhaustive]
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct EapOptions {
     /// User identity (usually email or username)
     pub identity: String,
-    /// Password for authentication
+    /// Password for authentication (unused for [`EapMethod::Tls`])
+    #[serde(skip_serializing)]
     pub password: String,
     /// Anonymous outer identity (for privacy)
     pub anonymous_identity: Option<String>,
@@ -208,9 +401,20 @@ pub struct EapOptions {
     pub ca_cert_path: Option<String>,
     /// Use system CA certificate store
     pub system_ca_certs: bool,
-    /// EAP method (PEAP or TTLS)
+    /// Path to the client certificate (file:// URL), for EAP-TLS
+    pub client_cert_path: Option<String>,
+    /// Path to the client private key (file:// URL), for EAP-TLS
+    pub private_key_path: Option<String>,
+    /// Password protecting the private key
+    #[serde(skip_serializing)]
+    pub private_key_password: Option<String>,
+    /// PAC provisioning mode, for EAP-FAST
+    pub fast_provisioning: Option<FastProvisioning>,
+    /// Path to the PAC file, for EAP-FAST
+    pub pac_file: Option<String>,
+    /// EAP method (PEAP, TTLS, TLS, PWD or FAST)
     pub method: EapMethod,
-    /// Phase 2 inner authentication method
+    /// Phase 2 inner authentication method (ignored for TLS and PWD)
     pub phase2: Phase2,
 }
 
@@ -223,12 +427,40 @@ impl Default for EapOptions {
             domain_suffix_match: None,
             ca_cert_path: None,
             system_ca_certs: false,
+            client_cert_path: None,
+            private_key_path: None,
+            private_key_password: None,
+            fast_provisioning: None,
+            pac_file: None,
             method: EapMethod::Peap,
             phase2: Phase2::Mschapv2,
         }
     }
 }
 
+impl fmt::Debug for EapOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EapOptions")
+            .field("identity", &self.identity)
+            .field("password", &Redacted)
+            .field("anonymous_identity", &self.anonymous_identity)
+            .field("domain_suffix_match", &self.domain_suffix_match)
+            .field("ca_cert_path", &self.ca_cert_path)
+            .field("system_ca_certs", &self.system_ca_certs)
+            .field("client_cert_path", &self.client_cert_path)
+            .field("private_key_path", &self.private_key_path)
+            .field(
+                "private_key_password",
+                &self.private_key_password.as_ref().map(|_| Redacted),
+            )
+            .field("fast_provisioning", &self.fast_provisioning)
+            .field("pac_file", &self.pac_file)
+            .field("method", &self.method)
+            .field("phase2", &self.phase2)
+            .finish()
+    }
+}
+
 impl EapOptions {
     /// Creates a new `EapOptions` with the minimum required fields.
     ///
@@ -249,6 +481,36 @@ impl EapOptions {
         }
     }
 
+    /// Creates `EapOptions` for EAP-TLS certificate authentication.
+    ///
+    /// `client_cert_path` and `private_key_path` must be `file://` URLs.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nmrs::{EapMethod, EapOptions};
+    ///
+    /// let opts = EapOptions::tls(
+    ///     "host/laptop.company.com",
+    ///     "file:///etc/pki/wifi/laptop.crt",
+    ///     "file:///etc/pki/wifi/laptop.key",
+    /// );
+    /// assert_eq!(opts.method, EapMethod::Tls);
+    /// ```
+    pub fn tls(
+        identity: impl Into<String>,
+        client_cert_path: impl Into<String>,
+        private_key_path: impl Into<String>,
+    ) -> Self {
+        Self {
+            identity: identity.into(),
+            client_cert_path: Some(client_cert_path.into()),
+            private_key_path: Some(private_key_path.into()),
+            method: EapMethod::Tls,
+            ..Default::default()
+        }
+    }
+
     /// Creates a new `EapOptions` builder.
     ///
     /// This provides an alternative way to construct EAP options with a fluent API,
@@ -302,7 +564,42 @@ impl EapOptions {
         self
     }
 
-    /// Sets the EAP method (PEAP or TTLS).
+    /// Sets the path to the client certificate (must start with `file://`).
+    #[must_use]
+    pub fn with_client_cert_path(mut self, path: impl Into<String>) -> Self {
+        self.client_cert_path = Some(path.into());
+        self
+    }
+
+    /// Sets the path to the client private key (must start with `file://`).
+    #[must_use]
+    pub fn with_private_key_path(mut self, path: impl Into<String>) -> Self {
+        self.private_key_path = Some(path.into());
+        self
+    }
+
+    /// Sets the password that decrypts the private key.
+    #[must_use]
+    pub fn with_private_key_password(mut self, password: impl Into<String>) -> Self {
+        self.private_key_password = Some(password.into());
+        self
+    }
+
+    /// Sets how EAP-FAST provisions its PAC.
+    #[must_use]
+    pub fn with_fast_provisioning(mut self, provisioning: FastProvisioning) -> Self {
+        self.fast_provisioning = Some(provisioning);
+        self
+    }
+
+    /// Sets the PAC file EAP-FAST reads and stores its credential in.
+    #[must_use]
+    pub fn with_pac_file(mut self, path: impl Into<String>) -> Self {
+        self.pac_file = Some(path.into());
+        self
+    }
+
+    /// Sets the EAP method.
     #[must_use]
     pub fn with_method(mut self, method: EapMethod) -> Self {
         self.method = method;
@@ -358,11 +655,16 @@ impl EapOptions {
 #[derive(Debug, Default)]
 pub struct EapOptionsBuilder {
     identity: Option<String>,
-    password: Option<String>,
+    password: Option<Secret>,
     anonymous_identity: Option<String>,
     domain_suffix_match: Option<String>,
     ca_cert_path: Option<String>,
     system_ca_certs: bool,
+    client_cert_path: Option<String>,
+    private_key_path: Option<String>,
+    private_key_password: Option<Secret>,
+    fast_provisioning: Option<FastProvisioning>,
+    pac_file: Option<String>,
     method: Option<EapMethod>,
     phase2: Option<Phase2>,
 }
@@ -379,10 +681,10 @@ impl EapOptionsBuilder {
 
     /// Sets the password for authentication.
     ///
-    /// This is a required field.
+    /// This is a required field, except for [`EapMethod::Tls`].
     #[must_use]
     pub fn password(mut self, password: impl Into<String>) -> Self {
-        self.password = Some(password.into());
+        self.password = Some(Secret::new(password));
         self
     }
 
@@ -461,10 +763,50 @@ impl EapOptionsBuilder {
         self
     }
 
-    /// Sets the EAP method (PEAP or TTLS).
+    /// Sets the client certificate path for EAP-TLS.
+    ///
+    /// The path must start with `file://`.
+    #[must_use]
+    pub fn client_cert_path(mut self, path: impl Into<String>) -> Self {
+        self.client_cert_path = Some(path.into());
+        self
+    }
+
+    /// Sets the client private key path for EAP-TLS.
+    ///
+    /// The path must start with `file://`.
+    #[must_use]
+    pub fn private_key_path(mut self, path: impl Into<String>) -> Self {
+        self.private_key_path = Some(path.into());
+        self
+    }
+
+    /// Sets the password that decrypts the private key.
+    #[must_use]
+    pub fn private_key_password(mut self, password: impl Into<String>) -> Self {
+        self.private_key_password = Some(Secret::new(password));
+        self
+    }
+
+    /// Sets how EAP-FAST provisions its PAC.
+    #[must_use]
+    pub fn fast_provisioning(mut self, provisioning: FastProvisioning) -> Self {
+        self.fast_provisioning = Some(provisioning);
+        self
+    }
+
+    /// Sets the PAC file used by EAP-FAST.
+    #[must_use]
+    pub fn pac_file(mut self, path: impl Into<String>) -> Self {
+        self.pac_file = Some(path.into());
+        self
+    }
+
+    /// Sets the EAP method.
     ///
     /// This is a required field. PEAP is more common in corporate environments,
     /// while TTLS offers more flexibility in inner authentication methods.
+    /// TLS authenticates with a client certificate instead of a password.
     ///
     /// # Examples
     ///
@@ -482,8 +824,9 @@ impl EapOptionsBuilder {
 
     /// Sets the Phase 2 (inner) authentication method.
     ///
-    /// This is a required field. MSCHAPv2 is commonly used with PEAP,
-    /// while PAP is often used with TTLS.
+    /// This is a required field, except for [`EapMethod::Tls`] and
+    /// [`EapMethod::Pwd`]. MSCHAPv2 is commonly used with PEAP, PAP with
+    /// TTLS and GTC with FAST.
     ///
     /// # Examples
     ///
@@ -521,29 +864,45 @@ impl EapOptionsBuilder {
     /// ```
     #[must_use = "use the EAP options with WifiSecurity::WpaEap or handle the error"]
     pub fn build(self) -> Result<EapOptions, ConnectionError> {
+        let method = self.method.ok_or_else(|| {
+            ConnectionError::IncompleteBuilder("EAP method is required (use .method())".into())
+        })?;
+        let tls = method == EapMethod::Tls;
+        let needs_phase2 = method.uses_phase2();
         Ok(EapOptions {
             identity: self.identity.ok_or_else(|| {
                 ConnectionError::IncompleteBuilder(
                     "EAP identity is required (use .identity())".into(),
                 )
             })?,
-            password: self.password.ok_or_else(|| {
-                ConnectionError::IncompleteBuilder(
-                    "EAP password is required (use .password())".into(),
-                )
-            })?,
+            password: match self.password {
+                Some(password) => password.into(),
+                None if tls => String::new(),
+                None => {
+                    return Err(ConnectionError::IncompleteBuilder(
+                        "EAP password is required (use .password())".into(),
+                    ));
+                }
+            },
             anonymous_identity: self.anonymous_identity,
             domain_suffix_match: self.domain_suffix_match,
             ca_cert_path: self.ca_cert_path,
             system_ca_certs: self.system_ca_certs,
-            method: self.method.ok_or_else(|| {
-                ConnectionError::IncompleteBuilder("EAP method is required (use .method())".into())
-            })?,
-            phase2: self.phase2.ok_or_else(|| {
-                ConnectionError::IncompleteBuilder(
-                    "EAP phase 2 method is required (use .phase2())".into(),
-                )
-            })?,
+            client_cert_path: self.client_cert_path,
+            private_key_path: self.private_key_path,
+            private_key_password: self.private_key_password.map(String::from),
+            fast_provisioning: self.fast_provisioning,
+            pac_file: self.pac_file,
+            method,
+            phase2: match self.phase2 {
+                Some(phase2) => phase2,
+                None if !needs_phase2 => Phase2::Mschapv2,
+                None => {
+                    return Err(ConnectionError::IncompleteBuilder(
+                        "EAP phase 2 method is required (use .phase2())".into(),
+                    ));
+                }
+            },
         })
     }
 }
@@ -604,7 +963,7 @@ impl EapOptionsBuilder {
 /// # }
 /// ```
 #[non_exhaustive]
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub enum WifiSecurity {
     /// Open network (no authentication)
     Open,
@@ -620,7 +979,34 @@ pub enum WifiSecurity {
     },
 }
 
+impl fmt::Debug for WifiSecurity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Open => f.write_str("Open"),
+            Self::WpaPsk { .. } => f.debug_struct("WpaPsk").field("psk", &Redacted).finish(),
+            Self::WpaEap { opts } => f.debug_struct("WpaEap").field("opts", opts).finish(),
+        }
+    }
+}
+
 impl WifiSecurity {
+    /// WPA-PSK security with the given pre-shared key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nmrs::{Secret, WifiSecurity};
+    ///
+    /// let security = WifiSecurity::wpa_psk(Secret::new("hunter22"));
+    /// assert!(security.is_psk());
+    /// ```
+    #[must_use]
+    pub fn wpa_psk(psk: impl Into<Secret>) -> Self {
+        Self::WpaPsk {
+            psk: psk.into().into(),
+        }
+    }
+
     /// Returns `true` if this security type requires authentication.
     #[must_use]
     pub fn secured(&self) -> bool {
@@ -640,7 +1026,125 @@ impl WifiSecurity {
     }
 }
 
+/// Radio preferences for a Wi-Fi connection.
+///
+/// Passed to
+/// [`NetworkManager::connect_with_wifi_options`](crate::NetworkManager::connect_with_wifi_options)
+/// to join a dual-band network on a specific band or channel, for example
+/// the 5 GHz access point when the 2.4 GHz one has the stronger signal.
+/// Only access points on the requested band and channel are joined, and
+/// a newly created profile stores them as `802-11-wireless.band` and
+/// `802-11-wireless.channel`.
+///
+/// # Examples
+///
+/// ```rust
+/// use nmrs::WifiOptions;
+/// use nmrs::builders::WifiBand;
+///
+/// let options = WifiOptions::new().with_band(WifiBand::A).with_channel(36);
+/// assert!(options.validate().is_ok());
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WifiOptions {
+    /// Band to connect on; any band when `None`.
+    pub band: Option<WifiBand>,
+    /// Channel to connect on, which requires [`band`](Self::band); any
+    /// channel when `None`.
+    pub channel: Option<u16>,
+}
+
+impl WifiOptions {
+    /// Options that accept any band and channel.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restricts the connection to `band`.
+    #[must_use]
+    pub fn with_band(mut self, band: WifiBand) -> Self {
+        self.band = Some(band);
+        self
+    }
+
+    /// Restricts the connection to `channel` of the configured band.
+    #[must_use]
+    pub fn with_channel(mut self, channel: u16) -> Self {
+        self.channel = Some(channel);
+        self
+    }
+
+    /// Validates the options.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConnectionError::InvalidInput` if a channel is set without a
+    /// band, or is not a channel of that band.
+    pub fn validate(&self) -> Result<(), ConnectionError> {
+        let Some(channel) = self.channel else {
+            return Ok(());
+        };
+        let valid = match self.band {
+            None => {
+                return Err(ConnectionError::InvalidInput {
+                    field: "channel".to_string(),
+                    reason: "a channel requires a band".to_string(),
+                });
+            }
+            Some(WifiBand::Bg) => (1..=14).contains(&channel),
+            Some(WifiBand::A) => (7..=196).contains(&channel),
+        };
+        if !valid {
+            return Err(ConnectionError::InvalidInput {
+                field: "channel".to_string(),
+                reason: format!("{channel} is not a channel of the selected band"),
+            });
+        }
+        Ok(())
+    }
+
+    /// Whether an access point on `frequency_mhz` satisfies the options.
+    pub(crate) fn allows(&self, frequency_mhz: u32) -> bool {
+        let band_ok = match self.band {
+            None => true,
+            Some(WifiBand::Bg) => matches!(
+                frequency_mhz,
+                frequency::BAND_2_4_START..=frequency::BAND_2_4_END | frequency::BAND_2_4_CH14
+            ),
+            Some(WifiBand::A) => {
+                (frequency::BAND_5_START..=frequency::BAND_5_END).contains(&frequency_mhz)
+            }
+        };
+        band_ok
+            && self
+                .channel
+                .is_none_or(|channel| channel_from_freq(frequency_mhz) == Some(channel))
+    }
+}
+
 impl Network {
+    /// Returns `true` if this network can be joined with a normal
+    /// [`connect`](crate::NetworkManager::connect) (infrastructure or AP mode).
+    ///
+    /// Ad-hoc (IBSS) and mesh networks need a dedicated profile and are
+    /// better filtered out of pickers.
+    #[must_use]
+    pub fn is_joinable(&self) -> bool {
+        matches!(self.mode, ApMode::Infrastructure | ApMode::Ap)
+    }
+
+    /// Time since a scan last saw this network.
+    ///
+    /// NetworkManager keeps access points listed for a while after they
+    /// go out of range, so callers can drop entries older than, say, a
+    /// couple of scan intervals.
+    #[must_use]
+    pub fn age(&self) -> Option<Duration> {
+        age_of(self.seen)
+    }
+
     /// Merges another access point's information into this network.
     ///
     /// When multiple access points share the same SSID (e.g., mesh networks),
@@ -667,6 +1171,7 @@ impl Network {
         self.is_hotspot |= other.is_hotspot;
         self.is_active |= other.is_active;
         self.known |= other.known;
+        self.seen = self.seen.max(other.seen);
 
         if self.ip4_address.is_none() {
             self.ip4_address.clone_from(&other.ip4_address);
@@ -683,6 +1188,7 @@ impl Network {
 #[cfg(test)]
 mod network_merge_tests {
     use super::Network;
+    use std::time::{Duration, SystemTime};
 
     #[test]
     fn merge_ap_keeps_ip_and_device_when_stronger_ap_has_none() {
@@ -703,6 +1209,8 @@ mod network_merge_tests {
             is_active: true,
             known: false,
             security_features: Default::default(),
+            mode: Default::default(),
+            seen: None,
         };
         let stronger = Network {
             device: String::new(),
@@ -721,6 +1229,8 @@ mod network_merge_tests {
             is_active: false,
             known: false,
             security_features: Default::default(),
+            mode: Default::default(),
+            seen: None,
         };
         weaker_connected.merge_ap(&stronger);
         assert_eq!(weaker_connected.strength, Some(90));
@@ -732,4 +1242,57 @@ mod network_merge_tests {
         assert!(weaker_connected.is_active);
         assert_eq!(weaker_connected.bssids.len(), 2);
     }
+
+    #[test]
+    fn merge_ap_keeps_most_recent_sighting() {
+        let now = SystemTime::now();
+        let mut net = sample_network();
+        net.seen = Some(now - Duration::from_secs(300));
+        let mut other = sample_network();
+        other.seen = Some(now);
+
+        net.merge_ap(&other);
+        assert_eq!(net.seen, Some(now));
+        net.merge_ap(&sample_network());
+        assert_eq!(net.seen, Some(now));
+        assert!(net.age().unwrap() < Duration::from_secs(60));
+    }
+
+    #[test]
+    fn is_joinable_by_mode() {
+        use super::ApMode;
+
+        let mut net: Network = sample_network();
+        assert_eq!(net.mode, ApMode::Infrastructure);
+        assert!(net.is_joinable());
+        net.mode = ApMode::Ap;
+        assert!(net.is_joinable());
+        net.mode = ApMode::Adhoc;
+        assert!(!net.is_joinable());
+        net.mode = ApMode::Mesh;
+        assert!(!net.is_joinable());
+    }
+
+    fn sample_network() -> Network {
+        Network {
+            device: String::new(),
+            ssid: "mesh".into(),
+            bssid: None,
+            strength: None,
+            frequency: None,
+            secured: false,
+            is_psk: false,
+            is_eap: false,
+            is_hotspot: false,
+            ip4_address: None,
+            ip6_address: None,
+            best_bssid: String::new(),
+            bssids: Vec::new(),
+            is_active: false,
+            known: false,
+            security_features: Default::default(),
+            mode: Default::default(),
+            seen: None,
+        }
+    }
 }
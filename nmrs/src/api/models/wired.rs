@@ -0,0 +1,195 @@
+//! Ethernet link and addressing configuration.
+//!
+//! [`WiredOptions`] describes the profile created by
+//! [`NetworkManager::connect_wired_on`](crate::NetworkManager::connect_wired_on):
+//! IP configuration, MTU, a cloned MAC address and a fixed link mode.
+
+use super::error::ConnectionError;
+use super::ip::IpSettings;
+use crate::util::validation::validate_bssid;
+
+/// Ethernet duplex mode.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Duplex {
+    /// Half duplex.
+    Half,
+    /// Full duplex.
+    Full,
+}
+
+impl Duplex {
+    /// Value of the `802-3-ethernet.duplex` property.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Half => "half",
+            Self::Full => "full",
+        }
+    }
+}
+
+/// Configuration of an Ethernet profile bound to one interface.
+///
+/// Everything is optional; an empty `WiredOptions` gives a DHCP profile
+/// with the kernel's MTU, the permanent MAC and auto-negotiation.
+///
+/// # Examples
+///
+/// ```rust
+/// use nmrs::{Duplex, IpSettings, WiredOptions};
+/// use nmrs::builders::IpConfig;
+///
+/// let options = WiredOptions::new()
+///     .with_ipv4(IpSettings::manual(vec![IpConfig::new("10.0.0.10", 24)]))
+///     .with_mtu(9000)
+///     .with_cloned_mac("stable")
+///     .with_link_mode(1000, Duplex::Full);
+/// assert!(options.validate().is_ok());
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Default)]
+pub struct WiredOptions {
+    /// IPv4 configuration; DHCP when `None`.
+    pub ipv4: Option<IpSettings>,
+    /// IPv6 configuration; automatic when `None`.
+    pub ipv6: Option<IpSettings>,
+    /// MTU in bytes; the device default when `None`.
+    pub mtu: Option<u32>,
+    /// MAC address to present on the link: an address such as
+    /// `"02:00:00:00:00:01"`, or one of `"preserve"`, `"permanent"`,
+    /// `"random"` and `"stable"`.
+    pub cloned_mac: Option<String>,
+    /// Fixed link speed in Mb/s; auto-negotiated when `None`.
+    pub speed: Option<u32>,
+    /// Fixed duplex mode, set together with [`speed`](Self::speed).
+    pub duplex: Option<Duplex>,
+}
+
+impl WiredOptions {
+    /// Options with every setting left at its default.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the IPv4 configuration.
+    #[must_use]
+    pub fn with_ipv4(mut self, ipv4: IpSettings) -> Self {
+        self.ipv4 = Some(ipv4);
+        self
+    }
+
+    /// Sets the IPv6 configuration.
+    #[must_use]
+    pub fn with_ipv6(mut self, ipv6: IpSettings) -> Self {
+        self.ipv6 = Some(ipv6);
+        self
+    }
+
+    /// Sets the MTU.
+    #[must_use]
+    pub fn with_mtu(mut self, mtu: u32) -> Self {
+        self.mtu = Some(mtu);
+        self
+    }
+
+    /// Sets the cloned MAC address or MAC policy.
+    #[must_use]
+    pub fn with_cloned_mac(mut self, mac: impl Into<String>) -> Self {
+        self.cloned_mac = Some(mac.into());
+        self
+    }
+
+    /// Disables auto-negotiation and fixes the link at `speed` Mb/s and
+    /// `duplex`.
+    #[must_use]
+    pub fn with_link_mode(mut self, speed: u32, duplex: Duplex) -> Self {
+        self.speed = Some(speed);
+        self.duplex = Some(duplex);
+        self
+    }
+
+    /// Validates the options.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConnectionError::InvalidInput` if the MTU or speed is zero,
+    /// only one of speed and duplex is set, or the cloned MAC is neither an
+    /// address nor a known policy.
+    pub fn validate(&self) -> Result<(), ConnectionError> {
+        if self.mtu == Some(0) {
+            return Err(ConnectionError::InvalidInput {
+                field: "mtu".to_string(),
+                reason: "MTU must be greater than zero".to_string(),
+            });
+        }
+        match (self.speed, self.duplex) {
+            (Some(0), _) => {
+                return Err(ConnectionError::InvalidInput {
+                    field: "speed".to_string(),
+                    reason: "link speed must be greater than zero".to_string(),
+                });
+            }
+            (Some(_), None) | (None, Some(_)) => {
+                return Err(ConnectionError::InvalidInput {
+                    field: "speed".to_string(),
+                    reason: "speed and duplex must be set together".to_string(),
+                });
+            }
+            _ => {}
+        }
+        if let Some(mac) = &self.cloned_mac
+            && !matches!(mac.as_str(), "preserve" | "permanent" | "random" | "stable")
+            && validate_bssid(mac).is_err()
+        {
+            return Err(ConnectionError::InvalidInput {
+                field: "cloned_mac".to_string(),
+                reason: format!("'{mac}' is not a MAC address or MAC policy"),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_options_are_valid() {
+        assert!(WiredOptions::new().validate().is_ok());
+    }
+
+    #[test]
+    fn validates_cloned_mac() {
+        for mac in ["stable", "random", "02:00:00:aa:BB:01"] {
+            assert!(WiredOptions::new().with_cloned_mac(mac).validate().is_ok());
+        }
+        assert!(
+            WiredOptions::new()
+                .with_cloned_mac("02:00:00")
+                .validate()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn speed_requires_duplex() {
+        let mut options = WiredOptions::new().with_link_mode(100, Duplex::Half);
+        assert!(options.validate().is_ok());
+        options.duplex = None;
+        assert!(options.validate().is_err());
+        assert!(
+            WiredOptions::new()
+                .with_link_mode(0, Duplex::Full)
+                .validate()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn rejects_zero_mtu() {
+        assert!(WiredOptions::new().with_mtu(0).validate().is_err());
+    }
+}
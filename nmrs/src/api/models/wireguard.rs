@@ -1,6 +1,9 @@
 #![allow(deprecated)]
 
+use std::fmt;
+
 use super::error::ConnectionError;
+use super::secret::{Redacted, Secret};
 use super::vpn::{VpnConfig, VpnKind};
 use uuid::Uuid;
 
@@ -39,7 +42,7 @@ use uuid::Uuid;
 /// ).with_dns(vec!["1.1.1.1".into()]);
 /// ```
 #[non_exhaustive]
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct WireGuardConfig {
     /// Unique name for the connection profile.
     pub name: String,
@@ -122,6 +125,21 @@ impl WireGuardConfig {
     }
 }
 
+impl fmt::Debug for WireGuardConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WireGuardConfig")
+            .field("name", &self.name)
+            .field("gateway", &self.gateway)
+            .field("private_key", &Redacted)
+            .field("address", &self.address)
+            .field("peers", &self.peers)
+            .field("dns", &self.dns)
+            .field("mtu", &self.mtu)
+            .field("uuid", &self.uuid)
+            .finish()
+    }
+}
+
 impl super::vpn::sealed::Sealed for WireGuardConfig {}
 
 impl VpnConfig for WireGuardConfig {
@@ -182,7 +200,7 @@ impl From<VpnCredentials> for WireGuardConfig {
 /// Prefer [`WireGuardConfig`] for new WireGuard connections.
 #[deprecated(note = "Use WireGuardConfig instead.")]
 #[non_exhaustive]
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct VpnCredentials {
     /// The type of VPN (currently only WireGuard).
     pub vpn_type: VpnKind,
@@ -204,6 +222,22 @@ pub struct VpnCredentials {
     pub uuid: Option<Uuid>,
 }
 
+impl fmt::Debug for VpnCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VpnCredentials")
+            .field("vpn_type", &self.vpn_type)
+            .field("name", &self.name)
+            .field("gateway", &self.gateway)
+            .field("private_key", &Redacted)
+            .field("address", &self.address)
+            .field("peers", &self.peers)
+            .field("dns", &self.dns)
+            .field("mtu", &self.mtu)
+            .field("uuid", &self.uuid)
+            .finish()
+    }
+}
+
 impl VpnCredentials {
     /// Creates new `VpnCredentials` with the required fields.
     ///
@@ -361,7 +395,7 @@ pub struct VpnCredentialsBuilder {
     vpn_type: Option<VpnKind>,
     name: Option<String>,
     gateway: Option<String>,
-    private_key: Option<String>,
+    private_key: Option<Secret>,
     address: Option<String>,
     peers: Vec<WireGuardPeer>,
     dns: Option<Vec<String>>,
@@ -411,7 +445,7 @@ impl VpnCredentialsBuilder {
     /// The private key should be base64 encoded.
     #[must_use]
     pub fn private_key(mut self, private_key: impl Into<String>) -> Self {
-        self.private_key = Some(private_key.into());
+        self.private_key = Some(Secret::new(private_key));
         self
     }
 
@@ -537,7 +571,7 @@ impl VpnCredentialsBuilder {
             vpn_type,
             name,
             gateway,
-            private_key,
+            private_key: private_key.into(),
             address,
             peers: self.peers,
             dns: self.dns,
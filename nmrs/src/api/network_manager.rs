@@ -1,40 +1,86 @@
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
 
+use futures::StreamExt;
 use tokio::sync::watch;
+use uuid::Uuid;
 use zbus::Connection;
 use zvariant::OwnedValue;
 
 use crate::Result;
+use crate::agent::{SecretAgent, SecretAgentHandle, SecretProvider};
+use crate::api::builders::Route;
+use crate::api::builders::bridge::{build_bond_connection, build_bridge_connection};
+use crate::api::connect_handle::ConnectHandle;
+use crate::api::device_handle::{DeviceHandle, DeviceRef};
 use crate::api::models::access_point::AccessPoint;
 use crate::api::models::{
-    AirplaneModeState, Device, Network, NetworkInfo, RadioState, SavedConnection,
-    SavedConnectionBrief, SettingsPatch, WifiDevice, WifiSecurity,
+    ActivationStage, ActiveConnection, ActiveConnectionDetails, AirplaneModeState,
+    AutoconnectStatus, BondMode, BridgeOptions, ConnectPolicy, ConnectProgress, ConnectionFailure,
+    ConnectionOptions, ConnectionSecrets, Device, DeviceIdentity, DhcpInfo, DnsConfig,
+    GsmCredentials, KnownNetwork, LinkStats, Network, NetworkDetails, NetworkEvent, NetworkInfo,
+    NetworkSnapshot, P2pPeer, PrimaryConnection, RadioState, SavedConnection, SavedConnectionBrief,
+    ScanOptions, SettingsPatch, ShareOptions, SharedConnection, SlaveType, TrafficDelta,
+    TrafficStats, UnmanagedDevice, WifiDevice, WifiOptions, WifiSecurity, WireGuardPeer,
+    WiredOptions,
 };
+use crate::api::scan_scheduler::ScanScheduler;
+use crate::api::stats_collector::StatsCollector;
+use crate::api::vpn_supervisor::VpnSupervisor;
 use crate::api::wifi_scope::WifiScope;
+use crate::backend::{DbusBackend, SettingsCache};
+use crate::core::active_connection::{
+    list_active_connections, primary_connection, watch_primary_connection,
+};
 use crate::core::airplane;
-use crate::core::bluetooth::connect_bluetooth;
+use crate::core::autoconnect::{
+    autoconnect_order, autoconnect_status, clear_autoconnect_block, connect_best_available,
+    update_autoconnect,
+};
+use crate::core::bluetooth::{connect_bluetooth, disconnect_bluetooth};
+use crate::core::bridge::{create_master, enslave_connection};
 use crate::core::connection::{
-    connect, connect_to_bssid, connect_wired, disconnect, forget_by_name_and_type,
-    get_device_by_interface, is_connected,
+    ConnectStep, connect, connect_to_bssid, connect_wired, connect_wired_on, connect_wired_profile,
+    connect_with_policy, connect_with_stages, disconnect, disconnect_and_block, disconnect_wired,
+    forget_by_name_and_type, get_device_by_interface, is_connected, list_wired_profiles,
 };
-use crate::core::connection_settings::{get_saved_connection_path, has_saved_connection};
+use crate::core::connection_settings::{find_saved_connection, get_saved_psk, get_secrets};
 use crate::core::device::{
-    is_connecting, list_bluetooth_devices, list_devices, wait_for_wifi_ready,
+    find_device_by_identity, is_connecting, list_bluetooth_devices, list_devices,
+    list_unmanaged_devices, wait_for_wifi_ready,
 };
+use crate::core::dhcp::dhcp_info;
+use crate::core::dns::set_dns;
+use crate::core::ip_config::active_connection_details;
+use crate::core::limiter::{DEFAULT_MAX_CONCURRENT, Limiter, coalesce_key};
+use crate::core::networking;
+use crate::core::roaming::{list_bssids, roam_to};
+use crate::core::routes::{active_routes, add_route, remove_route};
 use crate::core::saved_connection as saved_profiles;
-use crate::core::scan::{current_network, list_access_points, list_networks, scan_networks};
+use crate::core::scan::{
+    current_network, known_networks, list_access_points, list_networks, scan_and_wait,
+    scan_networks, scan_networks_with_options,
+};
+use crate::core::sharing::{share_connection, shared_connection, stop_sharing};
+use crate::core::snapshot::snapshot;
 use crate::core::vpn::{
-    active_vpn_connections, connect_vpn, connect_vpn_by_id, connect_vpn_by_uuid, disconnect_vpn,
-    disconnect_vpn_by_uuid, get_vpn_info, list_vpn_connections,
+    active_vpn_connections, connect_vpn, connect_vpn_by_id, connect_vpn_by_uuid, current_vpn,
+    disconnect_vpn, disconnect_vpn_by_uuid, get_vpn_info, list_vpn_connections_with,
 };
 use crate::core::wifi_device::{list_wifi_devices, set_wifi_enabled_for_interface};
+use crate::core::wps::connect_wps;
 use crate::models::{
     BluetoothDevice, BluetoothIdentity, VpnConfig, VpnConfiguration, VpnConnection,
     VpnConnectionInfo,
 };
+use crate::monitoring::checkpoint as checkpoint_monitor;
 use crate::monitoring::device as device_monitor;
-use crate::monitoring::info::show_details;
+use crate::monitoring::events as events_monitor;
+use crate::monitoring::info::{network_details, show_details};
 use crate::monitoring::network as network_monitor;
+use crate::monitoring::resume as resume_monitor;
+#[cfg(feature = "usage-history")]
+use crate::monitoring::usage as usage_monitor;
 use crate::monitoring::wifi::{current_connection_info, current_ssid};
 use crate::types::constants::device_type;
 
@@ -143,6 +189,172 @@ use crate::types::constants::device_type;
 pub struct NetworkManager {
     conn: Connection,
     timeout_config: crate::api::models::TimeoutConfig,
+    uuid_namespace: Option<Uuid>,
+    autoconnect: Option<bool>,
+    match_options: crate::api::models::MatchOptions,
+    limiter: Arc<Limiter>,
+    /// Radio state saved by [`airplane_mode(true)`](Self::airplane_mode).
+    airplane_snapshot: Arc<Mutex<Option<AirplaneModeState>>>,
+    /// Saved profile settings read by lookups, shared by clones.
+    settings_cache: SettingsCache,
+}
+
+/// Builder for [`NetworkManager`] with crate-wide settings.
+///
+/// Use this when you need more than [`with_config`](NetworkManager::with_config)
+/// offers, e.g. a UUID namespace for deterministic profile UUIDs.
+///
+/// # Examples
+///
+/// ```no_run
+/// use nmrs::{NetworkManager, TimeoutConfig};
+/// use std::time::Duration;
+/// use uuid::Uuid;
+///
+/// # async fn example() -> nmrs::Result<()> {
+/// let fleet = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
+/// let nm = NetworkManager::builder()
+///     .timeout_config(TimeoutConfig::new().with_connection_timeout(Duration::from_secs(60)))
+///     .uuid_namespace(fleet)
+///     .build()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct NetworkManagerBuilder {
+    timeout_config: crate::api::models::TimeoutConfig,
+    uuid_namespace: Option<Uuid>,
+    autoconnect: Option<bool>,
+    match_options: crate::api::models::MatchOptions,
+    max_concurrent_requests: usize,
+    bus: Bus,
+}
+
+/// Which D-Bus the builder connects to.
+#[derive(Debug, Clone)]
+enum Bus {
+    System,
+    Address(String),
+    Connection(Connection),
+}
+
+impl Default for NetworkManagerBuilder {
+    fn default() -> Self {
+        Self {
+            timeout_config: crate::api::models::TimeoutConfig::default(),
+            uuid_namespace: None,
+            autoconnect: None,
+            match_options: crate::api::models::MatchOptions::default(),
+            max_concurrent_requests: DEFAULT_MAX_CONCURRENT,
+            bus: Bus::System,
+        }
+    }
+}
+
+impl NetworkManagerBuilder {
+    /// Sets the timeout configuration.
+    #[must_use]
+    pub fn timeout_config(mut self, timeout_config: crate::api::models::TimeoutConfig) -> Self {
+        self.timeout_config = timeout_config;
+        self
+    }
+
+    /// Derives the UUID of every profile this instance creates from `namespace`.
+    ///
+    /// Profiles created by [`connect`](NetworkManager::connect),
+    /// [`connect_wired`](NetworkManager::connect_wired),
+    /// [`connect_bluetooth`](NetworkManager::connect_bluetooth) and
+    /// [`connect_vpn`](NetworkManager::connect_vpn) get a v5 UUID computed from
+    /// the namespace, connection type and name (see
+    /// [`profile_uuid`](crate::builders::profile_uuid)), so fleet-provisioned
+    /// machines end up with identical UUIDs for the same profile.
+    #[must_use]
+    pub fn uuid_namespace(mut self, namespace: Uuid) -> Self {
+        self.uuid_namespace = Some(namespace);
+        self
+    }
+
+    /// Sets `connection.autoconnect` on every profile this instance creates.
+    ///
+    /// By default Wi-Fi, wired and mobile profiles autoconnect while VPN and
+    /// Bluetooth profiles do not. `autoconnect(false)` opts out globally,
+    /// e.g. for kiosk or test setups where only explicit connect calls
+    /// should bring a link up. Options passed explicitly to a
+    /// `*_with_options` call are used as given.
+    #[must_use]
+    pub fn autoconnect(mut self, autoconnect: bool) -> Self {
+        self.autoconnect = Some(autoconnect);
+        self
+    }
+
+    /// Sets how saved profiles are matched by name or SSID.
+    ///
+    /// Applies to [`has_saved_connection`](NetworkManager::has_saved_connection),
+    /// [`get_saved_connection_path`](NetworkManager::get_saved_connection_path),
+    /// [`forget`](NetworkManager::forget) and
+    /// [`forget_bluetooth`](NetworkManager::forget_bluetooth).
+    #[must_use]
+    pub fn match_options(mut self, options: crate::api::models::MatchOptions) -> Self {
+        self.match_options = options;
+        self
+    }
+
+    /// Caps how many expensive D-Bus operations run at once (default 4).
+    ///
+    /// Scans and listings ([`list_devices`](NetworkManager::list_devices),
+    /// [`list_networks`](NetworkManager::list_networks),
+    /// [`list_access_points`](NetworkManager::list_access_points),
+    /// [`scan_networks`](NetworkManager::scan_networks)) wait for a free slot
+    /// when the limit is reached, and identical concurrent calls share one
+    /// in-flight request. Values below 1 are treated as 1.
+    #[must_use]
+    pub fn max_concurrent_requests(mut self, max: usize) -> Self {
+        self.max_concurrent_requests = max;
+        self
+    }
+
+    /// Uses an existing bus connection instead of the system bus.
+    #[must_use]
+    pub fn connection(mut self, conn: Connection) -> Self {
+        self.bus = Bus::Connection(conn);
+        self
+    }
+
+    /// Connects to the bus at `address` (e.g.
+    /// `"unix:path=/run/host/dbus/system_bus_socket"`) instead of the system
+    /// bus.
+    #[must_use]
+    pub fn address(mut self, address: impl Into<String>) -> Self {
+        self.bus = Bus::Address(address.into());
+        self
+    }
+
+    /// Connects to the configured D-Bus (the system bus by default) and
+    /// builds the [`NetworkManager`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidInput`](crate::ConnectionError::InvalidInput) for a
+    /// malformed [`address`](Self::address), or a D-Bus error if the bus
+    /// cannot be reached.
+    pub async fn build(self) -> Result<NetworkManager> {
+        let conn = match self.bus {
+            Bus::System => Connection::system().await?,
+            Bus::Address(address) => connect_address(&address).await?,
+            Bus::Connection(conn) => conn,
+        };
+        Ok(NetworkManager {
+            settings_cache: SettingsCache::new(conn.clone()),
+            conn,
+            timeout_config: self.timeout_config,
+            uuid_namespace: self.uuid_namespace,
+            autoconnect: self.autoconnect,
+            match_options: self.match_options,
+            limiter: Arc::new(Limiter::new(self.max_concurrent_requests)),
+            airplane_snapshot: Arc::default(),
+        })
+    }
 }
 
 impl NetworkManager {
@@ -151,11 +363,59 @@ impl NetworkManager {
     /// Uses default timeouts of 30 seconds for connection and 10 seconds for disconnection.
     /// To customize timeouts, use [`with_config()`](Self::with_config) instead.
     pub async fn new() -> Result<Self> {
-        let conn = Connection::system().await?;
-        Ok(Self {
+        Ok(Self::with_connection(Connection::system().await?))
+    }
+
+    /// Creates a `NetworkManager` on an existing bus connection.
+    ///
+    /// Useful for tests against a mocked NetworkManager on a private or
+    /// session bus (e.g. python-dbusmock). Uses the default configuration;
+    /// see [`NetworkManagerBuilder::connection`] to combine with other
+    /// settings.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let conn = zbus::Connection::session().await?;
+    /// let nm = NetworkManager::with_connection(conn);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_connection(conn: Connection) -> Self {
+        Self {
+            settings_cache: SettingsCache::new(conn.clone()),
             conn,
             timeout_config: crate::api::models::TimeoutConfig::default(),
-        })
+            uuid_namespace: None,
+            autoconnect: None,
+            match_options: crate::api::models::MatchOptions::default(),
+            limiter: Arc::default(),
+            airplane_snapshot: Arc::default(),
+        }
+    }
+
+    /// Creates a `NetworkManager` on the bus at `address`.
+    ///
+    /// For containers with a bridged host bus, or a test bus started by the
+    /// caller. `address` is a D-Bus address such as
+    /// `"unix:path=/run/host/dbus/system_bus_socket"`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidInput`](crate::ConnectionError::InvalidInput) if
+    /// `address` is malformed, or a D-Bus error if the bus cannot be reached.
+    pub async fn from_address(address: &str) -> Result<Self> {
+        Ok(Self::with_connection(connect_address(address).await?))
+    }
+
+    /// Returns a [`NetworkManagerBuilder`] for configuring crate-wide settings.
+    #[must_use]
+    pub fn builder() -> NetworkManagerBuilder {
+        NetworkManagerBuilder::default()
     }
 
     /// Creates a new `NetworkManager` with custom timeout configuration.
@@ -182,8 +442,14 @@ impl NetworkManager {
     pub async fn with_config(timeout_config: crate::api::models::TimeoutConfig) -> Result<Self> {
         let conn = Connection::system().await?;
         Ok(Self {
+            settings_cache: SettingsCache::new(conn.clone()),
             conn,
             timeout_config,
+            uuid_namespace: None,
+            autoconnect: None,
+            match_options: crate::api::models::MatchOptions::default(),
+            limiter: Arc::default(),
+            airplane_snapshot: Arc::default(),
         })
     }
 
@@ -206,14 +472,39 @@ impl NetworkManager {
         self.timeout_config
     }
 
+    /// Returns the namespace used for deterministic profile UUIDs, if set.
+    #[must_use]
+    pub fn uuid_namespace(&self) -> Option<Uuid> {
+        self.uuid_namespace
+    }
+
+    /// Returns the crate-level autoconnect policy, if one was set with
+    /// [`NetworkManagerBuilder::autoconnect`].
+    #[must_use]
+    pub fn autoconnect(&self) -> Option<bool> {
+        self.autoconnect
+    }
+
+    /// Returns how saved profiles are matched by name or SSID.
+    #[must_use]
+    pub fn match_options(&self) -> crate::api::models::MatchOptions {
+        self.match_options
+    }
+
     /// List all network devices managed by NetworkManager.
     pub async fn list_devices(&self) -> Result<Vec<Device>> {
-        list_devices(&self.conn).await
+        let conn = self.conn.clone();
+        self.limiter
+            .coalesce(
+                "list_devices".into(),
+                async move { list_devices(&conn).await },
+            )
+            .await
     }
 
     /// List all bluetooth devices.
     pub async fn list_bluetooth_devices(&self) -> Result<Vec<BluetoothDevice>> {
-        list_bluetooth_devices(&self.conn).await
+        self.limiter.run(list_bluetooth_devices(&self.conn)).await
     }
 
     /// Lists all network devices managed by NetworkManager.
@@ -228,6 +519,39 @@ impl NetworkManager {
         Ok(devices.into_iter().filter(|d| d.is_wired()).collect())
     }
 
+    /// Finds a device by hardware identity rather than interface name.
+    ///
+    /// Matches on the permanent MAC (see
+    /// [`DeviceIdentity::same_hardware`](crate::models::DeviceIdentity::same_hardware)),
+    /// so it keeps working after the interface is renamed. Returns `None` if
+    /// the device is not currently present.
+    pub async fn device_by_identity(&self, identity: &DeviceIdentity) -> Result<Option<Device>> {
+        find_device_by_identity(&self.conn, identity).await
+    }
+
+    /// Lists devices NetworkManager knows about but does not manage.
+    ///
+    /// Each entry carries an [`UnmanagedReason`](crate::UnmanagedReason)
+    /// explaining why (udev rule, `NetworkManager.conf`, unmanaged-by-default
+    /// type, ...). Useful for debugging "my interface doesn't show up".
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// for entry in nm.list_unmanaged_devices().await? {
+    ///     println!("{}: {}", entry.device.interface, entry.reason);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_unmanaged_devices(&self) -> Result<Vec<UnmanagedDevice>> {
+        list_unmanaged_devices(&self.conn).await
+    }
+
     /// Lists all visible Wi-Fi networks.
     ///
     /// Networks sharing an SSID on the same device are grouped, keeping the
@@ -240,7 +564,42 @@ impl NetworkManager {
     /// **3.0 break:** added the `interface` parameter. For old behavior,
     /// pass `None`.
     pub async fn list_networks(&self, interface: Option<&str>) -> Result<Vec<Network>> {
-        list_networks(&self.conn, interface).await
+        let conn = self.conn.clone();
+        let iface = interface.map(str::to_owned);
+        self.limiter
+            .coalesce(coalesce_key("list_networks", interface), async move {
+                list_networks(&conn, iface.as_deref()).await
+            })
+            .await
+    }
+
+    /// Lists visible Wi-Fi networks together with whether each has a saved
+    /// profile, may autoconnect, and when it was last connected.
+    ///
+    /// `interface` restricts the scan results as in
+    /// [`list_networks`](Self::list_networks). Saved profiles of networks
+    /// out of range are not included; use
+    /// [`list_saved_connections`](Self::list_saved_connections) for those.
+    pub async fn known_networks(&self, interface: Option<&str>) -> Result<Vec<KnownNetwork>> {
+        known_networks(&self.conn, interface).await
+    }
+
+    /// Reads devices, visible networks and active connections at once.
+    ///
+    /// Everything is read from a single `GetManagedObjects` call, so it is
+    /// consistent and far cheaper than calling
+    /// [`list_devices`](Self::list_devices) and
+    /// [`list_networks`](Self::list_networks) separately, which suits UIs
+    /// that refresh their whole view. Unlike `list_networks`, access points
+    /// carry no [`BssLoad`](crate::BssLoad).
+    pub async fn snapshot(&self) -> Result<NetworkSnapshot> {
+        let conn = self.conn.clone();
+        let profiles = self.settings_cache.clone();
+        self.limiter
+            .coalesce(coalesce_key("snapshot", None), async move {
+                snapshot(&conn, &profiles).await
+            })
+            .await
     }
 
     /// Lists every managed Wi-Fi device on the system.
@@ -248,7 +607,7 @@ impl NetworkManager {
     /// Each [`WifiDevice`] includes its interface name, MAC, current state,
     /// and the SSID of any active connection.
     pub async fn list_wifi_devices(&self) -> Result<Vec<WifiDevice>> {
-        list_wifi_devices(&self.conn).await
+        self.limiter.run(list_wifi_devices(&self.conn)).await
     }
 
     /// Look up a single Wi-Fi device by interface name.
@@ -292,10 +651,74 @@ impl NetworkManager {
         WifiScope {
             conn: self.conn.clone(),
             interface: interface.into(),
+            device: None,
             timeout_config: self.timeout_config,
+            uuid_namespace: self.uuid_namespace,
+            autoconnect: self.autoconnect,
+            match_options: self.match_options,
+            limiter: Arc::clone(&self.limiter),
+            settings_cache: self.settings_cache.clone(),
+        }
+    }
+
+    /// Returns a [`WifiScope`] that follows `device` across interface
+    /// renames and re-plugs.
+    ///
+    /// Each operation resolves the handle to the device's current interface
+    /// name first, and fails with
+    /// [`NotFound`](crate::ConnectionError::NotFound) while it is absent.
+    #[must_use]
+    pub fn wifi_device(&self, device: &DeviceHandle) -> WifiScope {
+        WifiScope {
+            device: Some(device.clone()),
+            ..self.wifi(device.interface().unwrap_or_default())
         }
     }
 
+    /// Returns a [`DeviceHandle`] for the device currently named
+    /// `interface`.
+    ///
+    /// The handle tracks the device by permanent MAC, so it keeps working
+    /// when the interface is renamed or the device is re-plugged.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// let dongle = nm.device_handle("wlan1").await?;
+    /// println!("tracking {}", dongle.identity().permanent_mac);
+    ///
+    /// let stats = nm.traffic_stats(&dongle).await?;
+    /// println!("{} now: rx {} B", dongle.resolve().await?, stats.rx_bytes);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NotFound`](crate::ConnectionError::NotFound) if no device
+    /// is named `interface`.
+    pub async fn device_handle(&self, interface: &str) -> Result<DeviceHandle> {
+        DeviceHandle::new(&self.conn, interface).await
+    }
+
+    /// A [`DbusBackend`] sharing this manager's bus connection.
+    #[must_use]
+    pub fn backend(&self) -> DbusBackend {
+        DbusBackend::from_connection(self.conn.clone())
+    }
+
+    /// Build a [`ScanScheduler`] for periodic background scanning.
+    ///
+    /// See the [`ScanScheduler`] documentation for an example.
+    #[must_use]
+    pub fn scan_scheduler(&self) -> ScanScheduler {
+        ScanScheduler::new(self.conn.clone())
+    }
+
     /// Lists all visible access points, one entry per BSSID.
     ///
     /// Unlike [`list_networks`](Self::list_networks), this preserves
@@ -322,14 +745,91 @@ impl NetworkManager {
     /// # }
     /// ```
     pub async fn list_access_points(&self, interface: Option<&str>) -> Result<Vec<AccessPoint>> {
-        list_access_points(&self.conn, interface).await
+        let conn = self.conn.clone();
+        let iface = interface.map(str::to_owned);
+        self.limiter
+            .coalesce(coalesce_key("list_access_points", interface), async move {
+                list_access_points(&conn, iface.as_deref()).await
+            })
+            .await
+    }
+
+    /// Lists every visible access point broadcasting `ssid`, strongest
+    /// first.
+    ///
+    /// Mesh and multi-AP networks show up as one entry in
+    /// [`list_networks`](Self::list_networks); this returns each BSSID with
+    /// its own strength and frequency, e.g. to pick a target for
+    /// [`roam_to`](Self::roam_to).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// for ap in nm.list_bssids("HomeMesh").await? {
+    ///     let marker = if ap.is_active { "*" } else { " " };
+    ///     println!("{marker} {}  {:>3}%  {} MHz", ap.bssid, ap.strength, ap.frequency_mhz);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_bssids(&self, ssid: &str) -> Result<Vec<AccessPoint>> {
+        list_bssids(&self.conn, ssid).await
+    }
+
+    /// Moves a connected Wi-Fi device to another access point of the same
+    /// network.
+    ///
+    /// The device that sees `bssid` re-activates its current profile with
+    /// that AP as the specific object, which makes it re-associate. Useful
+    /// for diagnosing clients that stick to a distant AP in a mesh. Does
+    /// nothing if the device is already on `bssid`.
+    ///
+    /// A profile locked to a BSSID (`802-11-wireless.bssid`) cannot roam
+    /// and the activation fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidBssid`](crate::ConnectionError::InvalidBssid) for a
+    /// malformed BSSID,
+    /// [`ApBssidNotFound`](crate::ConnectionError::ApBssidNotFound) if no
+    /// device sees it, and
+    /// [`InvalidInput`](crate::ConnectionError::InvalidInput) if that
+    /// device is not connected or is connected to a different SSID.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// if let Some(best) = nm.list_bssids("HomeMesh").await?.first() {
+    ///     nm.roam_to(&best.bssid).await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn roam_to(&self, bssid: &str) -> Result<()> {
+        roam_to(
+            &self.conn,
+            bssid,
+            None,
+            Some(self.timeout_config.connection_timeout),
+        )
+        .await
     }
 
     /// Connects to a specific access point by SSID and optional BSSID.
     ///
     /// If `bssid` is `Some`, the connection targets that specific AP rather
-    /// than the strongest match for the SSID. If `None`, behaves identically
-    /// to [`connect`](Self::connect).
+    /// than the strongest match for the SSID, and a newly created profile
+    /// stores it in `802-11-wireless.bssid` so NM will not roam to another
+    /// AP in a mesh. If `None`, behaves identically to
+    /// [`connect`](Self::connect).
     ///
     /// **3.0 break:** added the `interface` parameter (3rd argument). Pass
     /// `None` for the previous behavior of using the first available Wi-Fi
@@ -378,6 +878,82 @@ impl NetworkManager {
             creds,
             interface,
             Some(self.timeout_config),
+            &self.profile_options(None),
+        )
+        .await
+    }
+
+    /// Joins a network using WPS push-button.
+    ///
+    /// Picks the strongest access point whose WPS button is currently
+    /// pressed and creates a profile whose key is provisioned through WPS.
+    /// Pass `interface` to use a specific adapter, or `None` for the first
+    /// Wi-Fi device.
+    ///
+    /// Depending on the NetworkManager version, a secret agent may be asked
+    /// to confirm the push-button request while enrollment runs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NotFound`](crate::ConnectionError::NotFound) if no access
+    /// point has push-button WPS active, and the usual activation errors if
+    /// enrollment does not finish within the connection timeout.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// // Press the WPS button on the router, then:
+    /// nm.connect_wps_pbc(Some("wlan0")).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect_wps_pbc(&self, interface: Option<&str>) -> Result<()> {
+        connect_wps(
+            &self.conn,
+            interface,
+            None,
+            Some(self.timeout_config),
+            &self.profile_options(None),
+        )
+        .await
+    }
+
+    /// Joins a network using a WPS PIN.
+    ///
+    /// Picks the strongest access point advertising PIN-based WPS. `pin` is
+    /// either the 4-digit short form or an 8-digit PIN with a valid
+    /// checksum, as printed on the device label or entered in the router's
+    /// admin page. Raise the connection timeout if the PIN has to be typed
+    /// into the router while this call is waiting.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidInput`](crate::ConnectionError::InvalidInput) for a
+    /// malformed PIN and [`NotFound`](crate::ConnectionError::NotFound) if
+    /// no access point offers PIN-based WPS.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// nm.connect_wps_pin(None, "12345670").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect_wps_pin(&self, interface: Option<&str>, pin: &str) -> Result<()> {
+        connect_wps(
+            &self.conn,
+            interface,
+            Some(pin),
+            Some(self.timeout_config),
+            &self.profile_options(None),
         )
         .await
     }
@@ -406,127 +982,2176 @@ impl NetworkManager {
             creds,
             interface,
             Some(self.timeout_config),
+            &self.profile_options(None),
         )
         .await
     }
 
-    /// Connects to a wired (Ethernet) device.
+    /// Prepares a Wi-Fi connection that can be cancelled.
     ///
-    /// Finds the first available wired device and either activates an existing
-    /// saved connection or creates a new one. The connection will activate
-    /// when a cable is plugged in.
-    ///
-    /// # Errors
-    ///
-    /// Returns `ConnectionError::NoWiredDevice` if no wired device is found.
-    pub async fn connect_wired(&self) -> Result<()> {
-        connect_wired(&self.conn, Some(self.timeout_config)).await
-    }
-
-    /// Connects to a bluetooth device using the provided identity.
+    /// Returns a [`ConnectHandle`] and the attempt, a future running
+    /// [`connect`](Self::connect) that the caller drives on any executor.
+    /// Calling [`ConnectHandle::cancel`] deactivates the pending activation
+    /// and deletes a profile created for this attempt, rather than leaving
+    /// it half-activated as dropping the `connect` future would.
     ///
     /// # Example
     ///
     /// ```no_run
-    /// use nmrs::{NetworkManager, models::BluetoothIdentity, models::BluetoothNetworkRole};
+    /// use nmrs::{NetworkManager, WifiSecurity};
+    /// use std::time::Duration;
     ///
     /// # async fn example() -> nmrs::Result<()> {
-    ///    let nm = NetworkManager::new().await?;
-    ///
-    ///    let identity = BluetoothIdentity::new(
-    ///         "C8:1F:E8:F0:51:57".into(),
-    ///         BluetoothNetworkRole::PanU,
-    ///     )?;
-    ///
-    ///    nm.connect_bluetooth("My Phone", &identity).await?;
-    ///    Ok(())
-    /// }
+    /// let nm = NetworkManager::new().await?;
+    /// let (handle, attempt) = nm.connect_cancellable("CafeWiFi", None, WifiSecurity::Open);
+    /// let attempt = tokio::spawn(attempt);
+    /// tokio::time::sleep(Duration::from_secs(5)).await;
+    /// handle.cancel();
+    /// attempt.await.ok();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn connect_cancellable(
+        &self,
+        ssid: &str,
+        interface: Option<&str>,
+        creds: WifiSecurity,
+    ) -> (
+        ConnectHandle,
+        impl Future<Output = Result<()>> + Send + 'static,
+    ) {
+        let conn = self.conn.clone();
+        let ssid = ssid.to_owned();
+        let iface = interface.map(str::to_owned);
+        let timeout_config = self.timeout_config;
+        let options = self.profile_options(None);
+        ConnectHandle::new(self.conn.clone(), ssid.clone(), async move {
+            connect(
+                &conn,
+                &ssid,
+                creds,
+                iface.as_deref(),
+                Some(timeout_config),
+                &options,
+            )
+            .await
+        })
+    }
+
+    /// Connects to a Wi-Fi network, creating the profile with `options`.
+    ///
+    /// Like [`connect`](Self::connect), but a newly created profile uses
+    /// `options` (autoconnect, priority, trust level, static IPv4/IPv6
+    /// configuration). When a saved profile is reused, it is activated
+    /// unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use nmrs::{ConnectionOptions, IpSettings, NetworkManager, WifiSecurity};
+    /// use nmrs::builders::IpConfig;
     ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// let opts = ConnectionOptions::new(true)
+    ///     .with_ipv4(IpSettings::manual(vec![IpConfig::new("192.168.1.50", 24)]));
+    /// nm.connect_with_options(
+    ///     "HomeWiFi",
+    ///     None,
+    ///     WifiSecurity::WpaPsk { psk: "password".into() },
+    ///     opts,
+    /// ).await?;
+    /// # Ok(())
+    /// # }
     /// ```
-    pub async fn connect_bluetooth(&self, name: &str, identity: &BluetoothIdentity) -> Result<()> {
-        connect_bluetooth(&self.conn, name, identity, Some(self.timeout_config)).await
+    pub async fn connect_with_options(
+        &self,
+        ssid: &str,
+        interface: Option<&str>,
+        creds: WifiSecurity,
+        options: ConnectionOptions,
+    ) -> Result<()> {
+        connect(
+            &self.conn,
+            ssid,
+            creds,
+            interface,
+            Some(self.timeout_config),
+            &self.profile_options(Some(options)),
+        )
+        .await
     }
 
-    /// Connects to a VPN using the provided configuration.
+    /// Connects to a Wi-Fi network on a specific band or channel.
     ///
-    /// Supports WireGuard and OpenVPN connections. The function checks for an
-    /// existing saved VPN connection by name. If found, it activates the saved
-    /// connection. If not found, it creates a new VPN connection with the provided
-    /// configuration.
+    /// Like [`connect`](Self::connect), but only access points on the band
+    /// and channel in `wifi` are joined, and a newly created profile keeps
+    /// them. Use it to force 5 GHz on a dual-band network whose 2.4 GHz
+    /// access point has the stronger signal. An existing connection to
+    /// `ssid` is re-established so the restriction applies.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConnectionError::InvalidInput` if `wifi` fails
+    /// [`WifiOptions::validate`], `ConnectionError::NotFound` if the network
+    /// is not visible on the requested band and channel, or the errors of
+    /// [`connect`](Self::connect).
     ///
     /// # Examples
     ///
-    /// ## WireGuard
+    /// ```no_run
+    /// use nmrs::builders::WifiBand;
+    /// use nmrs::{NetworkManager, WifiOptions, WifiSecurity};
     ///
-    /// ```rust
-    /// use nmrs::{NetworkManager, WireGuardConfig, WireGuardPeer};
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// nm.connect_with_wifi_options(
+    ///     "HomeWiFi",
+    ///     None,
+    ///     WifiSecurity::WpaPsk { psk: "password".into() },
+    ///     WifiOptions::new().with_band(WifiBand::A),
+    /// ).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect_with_wifi_options(
+        &self,
+        ssid: &str,
+        interface: Option<&str>,
+        creds: WifiSecurity,
+        wifi: WifiOptions,
+    ) -> Result<()> {
+        connect_with_stages(
+            &self.conn,
+            ssid,
+            creds,
+            interface,
+            Some(self.timeout_config),
+            &self.profile_options(None),
+            &wifi,
+            None,
+        )
+        .await
+    }
+
+    /// Connects to a Wi-Fi network, retrying transient failures.
+    ///
+    /// Runs [`connect`](Self::connect) up to `policy.max_attempts` times,
+    /// scanning again before each attempt and backing off in between.
+    /// Only errors whose [`class`](crate::ConnectionError::class) is in
+    /// `policy.retry_on` are retried; by default that is timeouts and DHCP
+    /// failures, but not rejected credentials.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the last attempt, or the first error that is
+    /// not retried.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use nmrs::{ConnectPolicy, NetworkManager, WifiSecurity};
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// let policy = ConnectPolicy::new().with_max_attempts(5);
+    /// nm.connect_with_policy("CafeWiFi", None, WifiSecurity::Open, policy)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect_with_policy(
+        &self,
+        ssid: &str,
+        interface: Option<&str>,
+        creds: WifiSecurity,
+        policy: ConnectPolicy,
+    ) -> Result<()> {
+        connect_with_policy(
+            &self.conn,
+            ssid,
+            creds,
+            interface,
+            Some(self.timeout_config),
+            &self.profile_options(None),
+            &policy,
+        )
+        .await
+    }
+
+    /// Connects to the best saved Wi-Fi network in range.
+    ///
+    /// Scans, matches the access points found against saved Wi-Fi profiles
+    /// that may autoconnect, and activates the winner: the highest
+    /// profile priority, then the strongest signal, then 5 or 6 GHz over
+    /// 2.4 GHz. Returns the profile used; if that network is already
+    /// connected, nothing is activated.
+    ///
+    /// `interface` restricts the scan and the choice to one Wi-Fi device.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NotFound`](crate::ConnectionError::NotFound) if no saved
+    /// network is in range, or the activation error otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
     ///
     /// # async fn example() -> nmrs::Result<()> {
     /// let nm = NetworkManager::new().await?;
+    /// let profile = nm.connect_best_available(None).await?;
+    /// println!("Connected with '{}'", profile.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect_best_available(&self, interface: Option<&str>) -> Result<SavedConnection> {
+        connect_best_available(&self.conn, interface, Some(self.timeout_config)).await
+    }
+
+    /// Connects to a Wi-Fi network, reporting each activation stage.
     ///
-    /// let peer = WireGuardPeer::new(
-    ///     "peer_public_key",
-    ///     "vpn.example.com:51820",
-    ///     vec!["0.0.0.0/0".into()],
-    /// ).with_persistent_keepalive(25);
+    /// Behaves like [`connect`](Self::connect), but calls `on_stage` as the
+    /// device moves through NetworkManager's intermediate states (prepare,
+    /// config, need-auth, ip-config, ip-check, secondaries, activated).
+    /// Consecutive duplicates are collapsed.
     ///
-    /// let config = WireGuardConfig::new(
-    ///     "MyVPN",
-    ///     "vpn.example.com:51820",
-    ///     "your_private_key",
-    ///     "10.0.0.2/24",
-    ///     vec![peer],
-    /// ).with_dns(vec!["1.1.1.1".into()]);
+    /// # Errors
+    ///
+    /// Same as [`connect`](Self::connect).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use nmrs::{NetworkManager, WifiSecurity};
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// nm.connect_with_stages(
+    ///     "HomeWiFi",
+    ///     None,
+    ///     WifiSecurity::WpaPsk { psk: "password".into() },
+    ///     |stage| println!("Connecting: {stage}"),
+    /// ).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect_with_stages<F>(
+        &self,
+        ssid: &str,
+        interface: Option<&str>,
+        creds: WifiSecurity,
+        on_stage: F,
+    ) -> Result<()>
+    where
+        F: Fn(ActivationStage) + Send + Sync,
+    {
+        let on_step = |step| {
+            if let ConnectStep::Stage(stage) = step {
+                on_stage(stage);
+            }
+        };
+        connect_with_stages(
+            &self.conn,
+            ssid,
+            creds,
+            interface,
+            Some(self.timeout_config),
+            &self.profile_options(None),
+            &WifiOptions::default(),
+            Some(&on_step),
+        )
+        .await
+    }
+
+    /// Connects to a Wi-Fi network, streaming its progress.
+    ///
+    /// Same flow as [`connect`](Self::connect). The stream yields
+    /// [`ConnectProgress`] steps as the network is scanned for and the
+    /// device activates, derived from the device's `StateChanged` signals,
+    /// and ends after [`Activated`](ConnectProgress::Activated) or
+    /// [`Failed`](ConnectProgress::Failed). Consecutive duplicates are
+    /// collapsed. The connection is attempted only while the stream is
+    /// polled; dropping it abandons the attempt like dropping the
+    /// [`connect`](Self::connect) future.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use futures::StreamExt;
+    /// use nmrs::{ConnectProgress, NetworkManager, WifiSecurity};
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// let mut progress = nm.connect_with_progress(
+    ///     "HomeWiFi",
+    ///     None,
+    ///     WifiSecurity::WpaPsk { psk: "password".into() },
+    /// );
+    /// while let Some(step) = progress.next().await {
+    ///     match step {
+    ///         ConnectProgress::Failed(e) => return Err(e),
+    ///         step => println!("{step}"),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn connect_with_progress(
+        &self,
+        ssid: &str,
+        interface: Option<&str>,
+        creds: WifiSecurity,
+    ) -> impl futures::Stream<Item = ConnectProgress> + Send + Unpin + 'static {
+        let conn = self.conn.clone();
+        let ssid = ssid.to_owned();
+        let iface = interface.map(str::to_owned);
+        let timeout_config = self.timeout_config;
+        let options = self.profile_options(None);
+        let (tx, rx) = futures::channel::mpsc::unbounded();
+
+        let attempt = async move {
+            let last = std::sync::Mutex::new(None::<std::mem::Discriminant<ConnectProgress>>);
+            let send = |progress: ConnectProgress| {
+                let kind = std::mem::discriminant(&progress);
+                let mut last = last.lock().unwrap_or_else(|e| e.into_inner());
+                if *last != Some(kind) {
+                    *last = Some(kind);
+                    let _ = tx.unbounded_send(progress);
+                }
+            };
+            let on_step = |step| {
+                send(match step {
+                    ConnectStep::Scanning => ConnectProgress::Scanning,
+                    ConnectStep::ApFound => ConnectProgress::ApFound,
+                    ConnectStep::Stage(stage) => ConnectProgress::from(stage),
+                });
+            };
+            let outcome = connect_with_stages(
+                &conn,
+                &ssid,
+                creds,
+                iface.as_deref(),
+                Some(timeout_config),
+                &options,
+                &WifiOptions::default(),
+                Some(&on_step),
+            )
+            .await;
+            send(match outcome {
+                Ok(()) => ConnectProgress::Activated,
+                Err(e) => ConnectProgress::Failed(e),
+            });
+        };
+
+        // Every item goes through the channel so the terminal one comes last;
+        // the attempt itself only drives the connect.
+        Box::pin(futures::stream::select(
+            rx,
+            futures::stream::once(attempt).filter_map(|()| std::future::ready(None)),
+        ))
+    }
+
+    /// Connects to a wired (Ethernet) device.
+    ///
+    /// Finds the first available wired device and either activates an existing
+    /// saved connection or creates a new one. The connection will activate
+    /// when a cable is plugged in.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConnectionError::NoWiredDevice` if no wired device is found.
+    pub async fn connect_wired(&self) -> Result<()> {
+        connect_wired(
+            &self.conn,
+            Some(self.timeout_config),
+            &self.profile_options(None),
+        )
+        .await
+    }
+
+    /// Connects to a wired device, creating the profile with `options`.
+    ///
+    /// Like [`connect_wired`](Self::connect_wired), but a newly created
+    /// profile uses `options`, e.g. a static address via
+    /// [`ConnectionOptions::with_ipv4`]. An existing saved profile is
+    /// activated unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use nmrs::{ConnectionOptions, IpSettings, NetworkManager};
+    /// use nmrs::builders::IpConfig;
+    /// use std::net::{IpAddr, Ipv4Addr};
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// let ipv4 = IpSettings::manual(vec![IpConfig::new("10.0.0.10", 24)])
+    ///     .with_gateway(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)));
+    /// nm.connect_wired_with_options(ConnectionOptions::new(true).with_ipv4(ipv4))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect_wired_with_options(&self, options: ConnectionOptions) -> Result<()> {
+        connect_wired(
+            &self.conn,
+            Some(self.timeout_config),
+            &self.profile_options(Some(options)),
+        )
+        .await
+    }
+
+    /// Connects the wired device `interface` with `options`.
+    ///
+    /// For hosts with several NICs: the device is picked by name instead of
+    /// taking the first Ethernet device, and the profile is bound to it. A
+    /// saved profile named after the interface is replaced, so the options
+    /// always take effect. Autoconnect and the UUID namespace follow this
+    /// instance's defaults.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use nmrs::{Duplex, IpSettings, NetworkManager, WiredOptions};
+    /// use nmrs::builders::IpConfig;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// let options = WiredOptions::new()
+    ///     .with_ipv4(IpSettings::manual(vec![IpConfig::new("10.0.1.5", 24)]))
+    ///     .with_mtu(9000)
+    ///     .with_link_mode(1000, Duplex::Full);
+    /// nm.connect_wired_on("enp4s0", options).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConnectionError::InvalidInput` for invalid options,
+    /// [`WiredInterfaceNotFound`](crate::ConnectionError::WiredInterfaceNotFound)
+    /// or [`NotAWiredDevice`](crate::ConnectionError::NotAWiredDevice) if
+    /// `interface` is missing or not Ethernet, and otherwise fails like
+    /// [`connect_wired`](Self::connect_wired).
+    pub async fn connect_wired_on(
+        &self,
+        interface: impl Into<DeviceRef<'_>>,
+        options: WiredOptions,
+    ) -> Result<()> {
+        let interface = interface.into().interface().await?;
+        connect_wired_on(
+            &self.conn,
+            &interface,
+            Some(self.timeout_config),
+            &self.profile_options(None),
+            &options,
+        )
+        .await
+    }
+
+    /// Disconnects the wired device `interface`.
+    ///
+    /// The device is not reconnected automatically; activate a profile on
+    /// it (e.g. with [`connect_wired_on`](Self::connect_wired_on)) to bring
+    /// it back. Returns `Ok(())` if it is already disconnected.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WiredInterfaceNotFound`](crate::ConnectionError::WiredInterfaceNotFound)
+    /// or [`NotAWiredDevice`](crate::ConnectionError::NotAWiredDevice) if
+    /// `interface` is missing or not Ethernet.
+    pub async fn disconnect_wired(&self, interface: impl Into<DeviceRef<'_>>) -> Result<()> {
+        let interface = interface.into().interface().await?;
+        disconnect_wired(&self.conn, &interface, Some(self.timeout_config)).await
+    }
+
+    /// Creates the bridge `name` with the Ethernet interfaces `slaves` as
+    /// ports, and activates it.
+    ///
+    /// The bridge gets its own profile and each port a
+    /// `bridge-slave-<interface>` profile; saved profiles with those names
+    /// are replaced. The bridge is addressed according to `options`
+    /// (DHCP by default) and its ports are not addressed themselves.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use nmrs::{BridgeOptions, NetworkManager};
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// nm.create_bridge("br0", &["enp3s0", "enp4s0"], BridgeOptions::new().with_stp(false))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConnectionError::InvalidInput` for an invalid name or
+    /// options, [`WiredInterfaceNotFound`](crate::ConnectionError::WiredInterfaceNotFound)
+    /// or [`NotAWiredDevice`](crate::ConnectionError::NotAWiredDevice) for
+    /// a slave that is missing or not Ethernet (before anything is
+    /// created), and an activation error if the bridge or a port fails to
+    /// come up.
+    pub async fn create_bridge(
+        &self,
+        name: &str,
+        slaves: &[&str],
+        options: BridgeOptions,
+    ) -> Result<()> {
+        let profile_options = self.profile_options(None);
+        let settings = build_bridge_connection(name, &options, &profile_options)?;
+        create_master(
+            &self.conn,
+            name,
+            settings,
+            SlaveType::Bridge,
+            slaves,
+            &profile_options,
+            Some(self.timeout_config),
+        )
+        .await
+    }
+
+    /// Creates the bond `name` in `mode` over the Ethernet interfaces
+    /// `slaves`, and activates it.
+    ///
+    /// Like [`create_bridge`](Self::create_bridge); the slave profiles are
+    /// named `bond-slave-<interface>` and the bond uses automatic
+    /// addressing.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use nmrs::{BondMode, NetworkManager};
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// nm.create_bond("bond0", BondMode::ActiveBackup, &["enp3s0", "enp4s0"])
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails like [`create_bridge`](Self::create_bridge).
+    pub async fn create_bond(&self, name: &str, mode: BondMode, slaves: &[&str]) -> Result<()> {
+        let profile_options = self.profile_options(None);
+        let settings = build_bond_connection(name, mode, &profile_options)?;
+        create_master(
+            &self.conn,
+            name,
+            settings,
+            SlaveType::Bond,
+            slaves,
+            &profile_options,
+            Some(self.timeout_config),
+        )
+        .await
+    }
+
+    /// Attaches the saved Ethernet profile `uuid` to the bridge or bond
+    /// interface `master`.
+    ///
+    /// The profile keeps its name and other settings but loses its IP
+    /// configuration. The change applies the next time it is activated.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConnectionError::InvalidInput` if `master` is not a valid
+    /// interface name, and otherwise fails like
+    /// [`update_saved_connection`](Self::update_saved_connection).
+    pub async fn enslave_connection(
+        &self,
+        uuid: &str,
+        master: &str,
+        slave_type: SlaveType,
+    ) -> Result<()> {
+        enslave_connection(&self.conn, uuid, master, slave_type).await
+    }
+
+    /// Shares this machine's internet connection over `via_interface`.
+    ///
+    /// Same as [`share_connection_with_options`](Self::share_connection_with_options)
+    /// with default [`ShareOptions`]: an existing shared profile for the
+    /// interface is reused, and a new hotspot gets the SSID
+    /// `Hotspot-<interface>` and a generated passphrase.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// // Share the Wi-Fi uplink with a laptop plugged into the Ethernet port.
+    /// nm.share_connection("enp3s0").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails like
+    /// [`share_connection_with_options`](Self::share_connection_with_options).
+    pub async fn share_connection(&self, via_interface: &str) -> Result<SharedConnection> {
+        self.share_connection_with_options(via_interface, ShareOptions::new())
+            .await
+    }
+
+    /// Shares this machine's internet connection over `via_interface`,
+    /// creating the shared profile with `options`.
+    ///
+    /// The profile uses `ipv4.method=shared`: NetworkManager hands out
+    /// addresses on the interface and routes its clients through the
+    /// current uplink. On an Ethernet device the profile is bound to the
+    /// port; on a Wi-Fi device it is a WPA2 access point. It is named
+    /// `shared-<interface>`, replacing an earlier one, and does not
+    /// autoconnect.
+    ///
+    /// With default options an existing shared profile bound to the
+    /// interface is activated instead of creating one; its passphrase is
+    /// not read back, so [`SharedConnection::psk`] is `None` then.
+    ///
+    /// Undo with [`stop_sharing`](Self::stop_sharing).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use nmrs::{NetworkManager, ShareOptions};
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// let hotspot = nm
+    ///     .share_connection_with_options("wlan0", ShareOptions::new().with_ssid("Workshop"))
+    ///     .await?;
+    /// if let Some(psk) = &hotspot.psk {
+    ///     println!("Join '{}' with {}", hotspot.id, psk.expose());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConnectionError::InvalidInput` if `via_interface` is not an
+    /// Ethernet or Wi-Fi device, `ConnectionError::InvalidAddress` if
+    /// `options` fail [`ShareOptions::validate`], or an activation error.
+    pub async fn share_connection_with_options(
+        &self,
+        via_interface: &str,
+        options: ShareOptions,
+    ) -> Result<SharedConnection> {
+        share_connection(
+            &self.conn,
+            via_interface,
+            &options,
+            Some(self.timeout_config),
+        )
+        .await
+    }
+
+    /// Returns the shared profile bound to `via_interface`, if any, and
+    /// whether it is active.
+    ///
+    /// Finds profiles created by [`share_connection`](Self::share_connection)
+    /// as well as other shared profiles bound to the interface, such as a
+    /// hotspot set up in a desktop's settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConnectionError::InvalidInput` if `via_interface` is not an
+    /// Ethernet or Wi-Fi device.
+    pub async fn shared_connection(&self, via_interface: &str) -> Result<Option<SharedConnection>> {
+        shared_connection(&self.conn, via_interface).await
+    }
+
+    /// Stops sharing the internet connection over `via_interface`.
+    ///
+    /// Deactivates the shared profile active on the interface and returns
+    /// `true`, or returns `false` if none is. The profile is kept, so
+    /// [`share_connection`](Self::share_connection) reuses it; delete it
+    /// with [`delete_saved_connection`](Self::delete_saved_connection) to
+    /// forget it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConnectionError::InvalidInput` if `via_interface` is not an
+    /// Ethernet or Wi-Fi device, or a D-Bus error if deactivation fails.
+    pub async fn stop_sharing(&self, via_interface: &str) -> Result<bool> {
+        stop_sharing(&self.conn, via_interface).await
+    }
+
+    /// Options for profiles created by connect calls, falling back to this
+    /// instance's UUID namespace.
+    fn profile_options(&self, options: Option<ConnectionOptions>) -> ConnectionOptions {
+        let mut options = options.unwrap_or_else(|| self.default_options(true));
+        if options.uuid_namespace.is_none() {
+            options.uuid_namespace = self.uuid_namespace;
+        }
+        options
+    }
+
+    /// Default options for a profile kind that autoconnects when
+    /// `autoconnect`, unless the crate-level policy says otherwise.
+    fn default_options(&self, autoconnect: bool) -> ConnectionOptions {
+        ConnectionOptions::new(self.autoconnect.unwrap_or(autoconnect))
+    }
+
+    /// Lists saved Ethernet profiles that can be activated on `interface`.
+    ///
+    /// Useful on docks or VLAN setups where several wired profiles exist and
+    /// [`connect_wired`](Self::connect_wired) would just pick the one named
+    /// after the interface.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// for profile in nm.list_wired_profiles("eth0").await? {
+    ///     println!("{} ({})", profile.id, profile.uuid);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WiredInterfaceNotFound`](crate::ConnectionError::WiredInterfaceNotFound)
+    /// or [`NotAWiredDevice`](crate::ConnectionError::NotAWiredDevice) if
+    /// `interface` is missing or not Ethernet.
+    pub async fn list_wired_profiles(&self, interface: &str) -> Result<Vec<SavedConnectionBrief>> {
+        list_wired_profiles(&self.conn, interface).await
+    }
+
+    /// Activates a specific saved Ethernet profile on `interface`.
+    ///
+    /// `profile_name` is matched against the profile's name, then its UUID.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// nm.connect_wired_profile("eth0", "Office Dock").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SavedConnectionNotFound`](crate::ConnectionError::SavedConnectionNotFound)
+    /// if no matching profile is available on the interface, in addition to
+    /// the errors of [`list_wired_profiles`](Self::list_wired_profiles).
+    pub async fn connect_wired_profile(
+        &self,
+        interface: impl Into<DeviceRef<'_>>,
+        profile_name: &str,
+    ) -> Result<()> {
+        let interface = interface.into().interface().await?;
+        connect_wired_profile(
+            &self.conn,
+            &interface,
+            profile_name,
+            Some(self.timeout_config),
+        )
+        .await
+    }
+
+    /// Connects to a bluetooth device using the provided identity.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::{NetworkManager, models::BluetoothIdentity, models::BluetoothNetworkRole};
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    ///    let nm = NetworkManager::new().await?;
+    ///
+    ///    let identity = BluetoothIdentity::new(
+    ///         "C8:1F:E8:F0:51:57".into(),
+    ///         BluetoothNetworkRole::PanU,
+    ///     )?;
+    ///
+    ///    nm.connect_bluetooth("My Phone", &identity).await?;
+    ///    Ok(())
+    /// }
+    ///
+    /// ```
+    pub async fn connect_bluetooth(&self, name: &str, identity: &BluetoothIdentity) -> Result<()> {
+        let options = self.default_options(false);
+        self.connect_bluetooth_with_options(name, identity, options)
+            .await
+    }
+
+    /// Connects to a Bluetooth device, creating the profile with `options`.
+    ///
+    /// Like [`connect_bluetooth`](Self::connect_bluetooth), but a newly
+    /// created profile uses `options` instead of the defaults (which do not
+    /// autoconnect). An existing saved profile is activated unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::models::{BluetoothIdentity, BluetoothNetworkRole};
+    /// use nmrs::{ConnectionOptions, NetworkManager};
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// let identity = BluetoothIdentity::new("C8:1F:E8:F0:51:57".into(), BluetoothNetworkRole::PanU)?;
+    /// nm.connect_bluetooth_with_options("My Phone", &identity, ConnectionOptions::new(true))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect_bluetooth_with_options(
+        &self,
+        name: &str,
+        identity: &BluetoothIdentity,
+        options: ConnectionOptions,
+    ) -> Result<()> {
+        connect_bluetooth(
+            &self.conn,
+            name,
+            identity,
+            Some(self.timeout_config),
+            &self.profile_options(Some(options)),
+        )
+        .await
+    }
+
+    /// Starts Wi-Fi Direct (P2P) peer discovery.
+    ///
+    /// Peers show up in [`list_p2p_peers`](Self::list_p2p_peers) as they are
+    /// found. NetworkManager stops discovery after `timeout` (30 seconds
+    /// when `None`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NoWifiP2pDevice`](crate::ConnectionError::NoWifiP2pDevice)
+    /// if no Wi-Fi card supports P2P.
+    pub async fn start_p2p_find(&self, timeout: Option<std::time::Duration>) -> Result<()> {
+        crate::core::p2p::start_p2p_find(&self.conn, timeout).await
+    }
+
+    /// Lists Wi-Fi Direct (P2P) peers found by discovery.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// nm.start_p2p_find(Some(Duration::from_secs(10))).await?;
+    /// tokio::time::sleep(Duration::from_secs(10)).await;
+    /// for peer in nm.list_p2p_peers().await? {
+    ///     println!("{} ({}) {}%", peer.name, peer.hw_address, peer.strength);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NoWifiP2pDevice`](crate::ConnectionError::NoWifiP2pDevice)
+    /// if no Wi-Fi card supports P2P.
+    pub async fn list_p2p_peers(&self) -> Result<Vec<P2pPeer>> {
+        crate::core::p2p::list_p2p_peers(&self.conn).await
+    }
+
+    /// Connects to a Wi-Fi Direct peer from [`list_p2p_peers`](Self::list_p2p_peers).
+    ///
+    /// The `wifi-p2p` profile is kept in memory only, so P2P sessions do
+    /// not accumulate in the saved connection list.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the peer has gone away or activation fails or
+    /// times out.
+    pub async fn connect_p2p(&self, peer: &P2pPeer) -> Result<()> {
+        crate::core::p2p::connect_p2p(
+            &self.conn,
+            peer,
+            Some(self.timeout_config),
+            &self.profile_options(Some(self.default_options(false))),
+        )
+        .await
+    }
+
+    /// Disconnects all Wi-Fi Direct (P2P) connections.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NoWifiP2pDevice`](crate::ConnectionError::NoWifiP2pDevice)
+    /// if no Wi-Fi card supports P2P, or a timeout if a device does not go
+    /// down in time.
+    pub async fn disconnect_p2p(&self) -> Result<()> {
+        crate::core::p2p::disconnect_p2p(&self.conn, Some(self.timeout_config)).await
+    }
+
+    /// Connects the mobile broadband modem using the profile `name`.
+    ///
+    /// Activates the saved profile called `name` if there is one; otherwise
+    /// creates it from `creds`. The modem must be managed by NetworkManager
+    /// through ModemManager.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::{GsmCredentials, NetworkManager};
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// let creds = GsmCredentials::new("internet").with_pin("1234");
+    /// nm.connect_mobile("Carrier LTE", &creds).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NoModemDevice`](crate::ConnectionError::NoModemDevice) if
+    /// there is no modem, or an error if activation fails or times out.
+    pub async fn connect_mobile(&self, name: &str, creds: &GsmCredentials) -> Result<()> {
+        crate::core::mobile::connect_mobile(
+            &self.conn,
+            name,
+            creds,
+            Some(self.timeout_config),
+            &self.profile_options(None),
+        )
+        .await
+    }
+
+    /// Disconnects the mobile broadband modem, keeping its profile.
+    ///
+    /// Returns `Ok(())` if the modem is already disconnected.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NoModemDevice`](crate::ConnectionError::NoModemDevice) if
+    /// there is no modem, or a timeout if it does not go down in time.
+    pub async fn disconnect_mobile(&self) -> Result<()> {
+        crate::core::mobile::disconnect_mobile(&self.conn, Some(self.timeout_config)).await
+    }
+
+    /// Disconnects the Bluetooth device with address `bdaddr`.
+    ///
+    /// The saved profile is kept, so a later
+    /// [`connect_bluetooth`](Self::connect_bluetooth) reuses it; use
+    /// [`forget_bluetooth`](Self::forget_bluetooth) to delete it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bdaddr` is not a valid Bluetooth address,
+    /// [`NoBluetoothDevice`](crate::ConnectionError::NoBluetoothDevice) if
+    /// NetworkManager has no device with that address, or a timeout if the
+    /// device does not disconnect in time.
+    pub async fn disconnect_bluetooth(&self, bdaddr: &str) -> Result<()> {
+        disconnect_bluetooth(&self.conn, bdaddr, Some(self.timeout_config)).await
+    }
+
+    /// Connects to a VPN using the provided configuration.
+    ///
+    /// Supports WireGuard and OpenVPN connections. The function checks for an
+    /// existing saved VPN connection by name. If found, it activates the saved
+    /// connection. If not found, it creates a new VPN connection with the provided
+    /// configuration.
+    ///
+    /// # Examples
+    ///
+    /// ## WireGuard
+    ///
+    /// ```rust
+    /// use nmrs::{NetworkManager, WireGuardConfig, WireGuardPeer};
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    ///
+    /// let peer = WireGuardPeer::new(
+    ///     "peer_public_key",
+    ///     "vpn.example.com:51820",
+    ///     vec!["0.0.0.0/0".into()],
+    /// ).with_persistent_keepalive(25);
+    ///
+    /// let config = WireGuardConfig::new(
+    ///     "MyVPN",
+    ///     "vpn.example.com:51820",
+    ///     "your_private_key",
+    ///     "10.0.0.2/24",
+    ///     vec![peer],
+    /// ).with_dns(vec!["1.1.1.1".into()]);
+    ///
+    /// nm.connect_vpn(config).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ## OpenVPN
+    ///
+    /// ```rust
+    /// use nmrs::{NetworkManager, OpenVpnConfig, OpenVpnAuthType};
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    ///
+    /// let config = OpenVpnConfig::new("CorpVPN", "vpn.example.com", 1194, false)
+    ///     .with_auth_type(OpenVpnAuthType::PasswordTls)
+    ///     .with_username("user")
+    ///     .with_password("secret")
+    ///     .with_ca_cert("/etc/openvpn/ca.crt")
+    ///     .with_client_cert("/etc/openvpn/client.crt")
+    ///     .with_client_key("/etc/openvpn/client.key");
+    ///
+    /// nm.connect_vpn(config).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ## IKEv2 (strongSwan)
+    ///
+    /// ```no_run
+    /// use nmrs::{Ikev2Auth, Ikev2Config, NetworkManager};
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    ///
+    /// let config = Ikev2Config::new(
+    ///     "WorkVPN",
+    ///     "vpn.example.com",
+    ///     Ikev2Auth::Eap {
+    ///         username: "alice".into(),
+    ///         password: Some("secret".into()),
+    ///     },
+    /// )
+    /// .with_ca_cert("/etc/ipsec.d/cacerts/ca.pem");
+    ///
+    /// nm.connect_vpn(config).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - NetworkManager is not running or accessible
+    /// - The configuration is invalid or incomplete
+    /// - The VPN connection fails to activate
+    pub async fn connect_vpn<C>(&self, config: C) -> Result<()>
+    where
+        C: VpnConfig + Into<VpnConfiguration>,
+    {
+        let options = self.default_options(false);
+        self.connect_vpn_with_options(config, options).await
+    }
+
+    /// Prepares a VPN connection that can be cancelled.
+    ///
+    /// Returns a [`ConnectHandle`] and the attempt, a future running
+    /// [`connect_vpn`](Self::connect_vpn); see
+    /// [`connect_cancellable`](Self::connect_cancellable) for what
+    /// [`ConnectHandle::cancel`] undoes.
+    pub fn connect_vpn_cancellable<C>(
+        &self,
+        config: C,
+    ) -> (
+        ConnectHandle,
+        impl Future<Output = Result<()>> + Send + 'static,
+    )
+    where
+        C: VpnConfig + Into<VpnConfiguration>,
+    {
+        let conn = self.conn.clone();
+        let name = config.name().to_owned();
+        let config = config.into();
+        let timeout_config = self.timeout_config;
+        let options = self.profile_options(Some(self.default_options(false)));
+        ConnectHandle::new(self.conn.clone(), name, async move {
+            connect_vpn(&conn, config, None, Some(timeout_config), &options).await
+        })
+    }
+
+    /// Connects to a VPN, creating the profile with `options`.
+    ///
+    /// Like [`connect_vpn`](Self::connect_vpn), but a newly created profile
+    /// uses `options` instead of the defaults (which do not autoconnect). An
+    /// existing saved profile is activated unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::{ConnectionOptions, NetworkManager, OpenVpnConfig};
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// let config = OpenVpnConfig::new("WorkVPN", "vpn.example.com", 1194, false);
+    /// nm.connect_vpn_with_options(config, ConnectionOptions::new(true).with_priority(5))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails like [`connect_vpn`](Self::connect_vpn).
+    pub async fn connect_vpn_with_options<C>(
+        &self,
+        config: C,
+        options: ConnectionOptions,
+    ) -> Result<()>
+    where
+        C: VpnConfig + Into<VpnConfiguration>,
+    {
+        connect_vpn(
+            &self.conn,
+            config.into(),
+            None,
+            Some(self.timeout_config),
+            &self.profile_options(Some(options)),
+        )
+        .await
+    }
+
+    /// Connects to a VPN whose traffic is carried only over `interface`.
+    ///
+    /// Like [`connect_vpn`](Self::connect_vpn), but the tunnel is
+    /// established on top of the connection currently active on the named
+    /// device, e.g. `wlan0` rather than a metered WWAN modem that happens to
+    /// hold the default route.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::{NetworkManager, OpenVpnConfig};
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// let config = OpenVpnConfig::new("WorkVPN", "vpn.example.com", 1194, false);
+    /// nm.connect_vpn_via(config, "wlan0").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConnectionError::InvalidInput`](crate::ConnectionError::InvalidInput) if no device is named
+    /// `interface`, the device has no active connection, or `config` is a
+    /// WireGuard tunnel (which has no base connection to bind). Otherwise
+    /// fails like [`connect_vpn`](Self::connect_vpn).
+    pub async fn connect_vpn_via<C>(
+        &self,
+        config: C,
+        interface: impl Into<DeviceRef<'_>>,
+    ) -> Result<()>
+    where
+        C: VpnConfig + Into<VpnConfiguration>,
+    {
+        let interface = interface.into().interface().await?;
+        connect_vpn(
+            &self.conn,
+            config.into(),
+            Some(&interface),
+            Some(self.timeout_config),
+            &self.profile_options(Some(self.default_options(false))),
+        )
+        .await
+    }
+
+    /// Imports a `.ovpn` file and activates the OpenVPN connection.
+    ///
+    /// Parses the file, persists any inline certificates, builds the
+    /// connection profile, and activates it through NetworkManager.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` — Path to the `.ovpn` configuration file
+    /// * `username` — Optional username for password-based authentication
+    /// * `password` — Optional password for password-based authentication
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// nm.import_ovpn("corp.ovpn", Some("user"), Some("secret")).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The file cannot be read or parsed
+    /// - Inline certificate storage fails
+    /// - The configuration is incomplete (e.g. TLS auth without certs)
+    /// - The VPN connection fails to activate
+    pub async fn import_ovpn(
+        &self,
+        path: impl AsRef<std::path::Path>,
+        username: Option<&str>,
+        password: Option<&str>,
+    ) -> Result<()> {
+        use crate::builders::OpenVpnBuilder;
+
+        let mut builder = OpenVpnBuilder::from_ovpn_file(path)?;
+        if let Some(u) = username {
+            builder = builder.username(u);
+        }
+        if let Some(p) = password {
+            builder = builder.password(p);
+        }
+        let config = builder.build()?;
+        self.connect_vpn(config).await
+    }
+
+    /// Disconnects from an active VPN connection by name.
+    ///
+    /// Searches through active connections for a VPN matching the given name.
+    /// If found, deactivates the connection. If not found or already disconnected,
+    /// returns success.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// nm.disconnect_vpn("MyVPN").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn disconnect_vpn(&self, name: &str) -> Result<()> {
+        disconnect_vpn(&self.conn, name).await
+    }
+
+    /// Lists all saved VPN connections.
+    ///
+    /// Returns a list of all VPN connection profiles saved in NetworkManager,
+    /// including their name, type, and current state. Returns WireGuard and
+    /// OpenVPN connections.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// let vpns = nm.list_vpn_connections().await?;
+    ///
+    /// for vpn in vpns {
+    ///     println!("{}: {:?}", vpn.name, vpn.vpn_type);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_vpn_connections(&self) -> Result<Vec<VpnConnection>> {
+        list_vpn_connections_with(&self.conn, &self.settings_cache).await
+    }
+
+    /// Only active VPNs (subset of `list_vpn_connections` with `active = true`).
+    pub async fn active_vpn_connections(&self) -> Result<Vec<VpnConnection>> {
+        active_vpn_connections(&self.conn).await
+    }
+
+    /// Build a [`VpnSupervisor`] that keeps the active VPN `id` (name or
+    /// UUID) up.
+    ///
+    /// See the [`VpnSupervisor`] documentation for an example.
+    #[must_use]
+    pub fn vpn_supervisor(&self, id: &str) -> VpnSupervisor {
+        VpnSupervisor::new(
+            self.conn.clone(),
+            id,
+            self.timeout_config.connection_timeout,
+        )
+    }
+
+    /// Returns the active VPN, if any.
+    ///
+    /// The VPN counterpart of [`current_ssid`](Self::current_ssid). When
+    /// several VPNs are up, the most recently activated one is returned;
+    /// use [`active_vpn_connections`](Self::active_vpn_connections) to see
+    /// all of them.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// if let Some(vpn) = nm.current_vpn().await? {
+    ///     println!(
+    ///         "{} on {:?}, up for {:?}",
+    ///         vpn.id,
+    ///         vpn.interface,
+    ///         vpn.uptime()
+    ///     );
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn current_vpn(&self) -> Result<Option<VpnConnection>> {
+        current_vpn(&self.conn).await
+    }
+
+    /// Activate a saved VPN by UUID.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// nm.connect_vpn_by_uuid("2c3f1234-abcd-5678-ef01-234567890abc").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect_vpn_by_uuid(&self, uuid: &str) -> Result<()> {
+        connect_vpn_by_uuid(&self.conn, uuid, Some(self.timeout_config)).await
+    }
+
+    /// Activate a saved VPN by connection display name.
+    ///
+    /// Fails with [`VpnIdAmbiguous`](crate::ConnectionError::VpnIdAmbiguous)
+    /// if multiple VPNs share the same name.
+    pub async fn connect_vpn_by_id(&self, id: &str) -> Result<()> {
+        connect_vpn_by_id(&self.conn, id, Some(self.timeout_config)).await
+    }
+
+    /// Disconnect a VPN by UUID.
+    pub async fn disconnect_vpn_by_uuid(&self, uuid: &str) -> Result<()> {
+        disconnect_vpn_by_uuid(&self.conn, uuid).await
+    }
+
+    /// Forgets (deletes) a saved VPN connection by name.
+    ///
+    /// Searches through saved connections for a VPN matching the given name.
+    /// If found, deletes the connection profile. If currently connected, the
+    /// VPN will be disconnected first before deletion.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// nm.forget_vpn("MyVPN").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error only if the operation fails unexpectedly.
+    /// Returns `Ok(())` if no matching VPN connection is found.
+    pub async fn forget_vpn(&self, name: &str) -> Result<()> {
+        crate::core::vpn::forget_vpn(&self.conn, name).await
+    }
+
+    /// Replaces the peer list of the saved WireGuard connection `name`
+    /// (name or UUID).
+    ///
+    /// If the connection is active, the new peers are reapplied to its
+    /// device in place (`Device.Reapply`), so split-tunnel changes to
+    /// `allowed_ips` take effect without a disconnect/reconnect cycle.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::{NetworkManager, WireGuardPeer};
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// let peer = WireGuardPeer::new(
+    ///     "HIgo9xNzJMWLKAShlKl6/bUT1VI9Q0SDBXGtLXkPFXc=",
+    ///     "vpn.example.com:51820",
+    ///     vec!["10.0.0.0/8".into(), "192.168.50.0/24".into()],
+    /// );
+    /// nm.update_wireguard_peers("MyVPN", vec![peer]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if `peers` is empty or a peer is invalid,
+    /// `SavedConnectionNotFound` if no profile matches `name`, and
+    /// `InvalidInput` if the profile is not a WireGuard connection.
+    pub async fn update_wireguard_peers(
+        &self,
+        name: &str,
+        peers: Vec<WireGuardPeer>,
+    ) -> Result<()> {
+        crate::core::vpn::update_wireguard_peers(&self.conn, name, peers).await
+    }
+
+    /// Gets detailed information about an active VPN connection.
+    ///
+    /// Retrieves comprehensive information about a VPN connection, including
+    /// IP configuration, DNS servers, gateway, interface, and connection state.
+    /// The VPN must be actively connected to retrieve this information.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// let info = nm.get_vpn_info("MyVPN").await?;
+    ///
+    /// println!("VPN: {}", info.name);
+    /// println!("Interface: {:?}", info.interface);
+    /// println!("IP Address: {:?}", info.ip4_address);
+    /// println!("DNS Servers: {:?}", info.dns_servers);
+    /// println!("State: {:?}", info.state);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConnectionError::NoVpnConnection` if the VPN is not found
+    /// or not currently active.
+    pub async fn get_vpn_info(&self, name: &str) -> Result<VpnConnectionInfo> {
+        get_vpn_info(&self.conn, name).await
+    }
+
+    /// Returns the combined software/hardware state of the Wi-Fi radio.
+    ///
+    /// See [`RadioState`] for the distinction between `enabled` (software)
+    /// and `hardware_enabled` (rfkill). The `present` flag reflects whether
+    /// NetworkManager has a Wi-Fi device object; if device enumeration fails,
+    /// `present` defaults to `true` so callers are not misled into thinking
+    /// Wi-Fi is absent.
+    pub async fn wifi_state(&self) -> Result<RadioState> {
+        let present_types = airplane::fetch_present_device_types(&self.conn).await;
+        airplane::wifi_state(&self.conn, present_types.as_ref()).await
+    }
+
+    /// Returns the combined software/hardware state of the WWAN radio.
+    ///
+    /// The `present` flag reflects whether NetworkManager has a modem device
+    /// object; if device enumeration fails, `present` defaults to `true`.
+    pub async fn wwan_state(&self) -> Result<RadioState> {
+        let present_types = airplane::fetch_present_device_types(&self.conn).await;
+        airplane::wwan_state(&self.conn, present_types.as_ref()).await
+    }
+
+    /// Returns the combined software/hardware state of the Bluetooth radio.
+    ///
+    /// Reads power state from all BlueZ adapters and cross-references rfkill.
+    /// If BlueZ is not running or no adapters exist, returns a [`RadioState`]
+    /// with `present = false` so callers can ignore Bluetooth on hosts that
+    /// don't have it.
+    pub async fn bluetooth_radio_state(&self) -> Result<RadioState> {
+        airplane::bluetooth_radio_state(&self.conn).await
+    }
+
+    /// Returns the aggregated airplane-mode state across all radios.
+    ///
+    /// Fans out to Wi-Fi, WWAN, and Bluetooth concurrently and returns
+    /// an [`AirplaneModeState`] snapshot. Radios that are not actually
+    /// present on the host (no Wi-Fi card, no modem, no BlueZ) are reported
+    /// with `present = false` and are ignored by
+    /// [`AirplaneModeState::is_airplane_mode`] /
+    /// [`AirplaneModeState::any_hardware_killed`].
+    pub async fn airplane_mode_state(&self) -> Result<AirplaneModeState> {
+        airplane::airplane_mode_state(&self.conn).await
+    }
+
+    /// Enables or disables the Wi-Fi radio (software toggle).
+    ///
+    /// This replaces the deprecated [`set_wifi_enabled`](Self::set_wifi_enabled).
+    /// If the radio is hardware-killed, NM accepts the write but the radio
+    /// remains off until hardware is unkilled.
+    ///
+    /// Note: NetworkManager implements this by writing rfkill soft blocks,
+    /// which most distributions persist across reboots via `rfkill-restore`
+    /// or systemd. A wifi disabled this way will remain disabled until it
+    /// is explicitly re-enabled.
+    pub async fn set_wireless_enabled(&self, enabled: bool) -> Result<()> {
+        airplane::set_wireless_enabled(&self.conn, enabled).await
+    }
+
+    /// Enables or disables the WWAN (mobile broadband) radio.
+    ///
+    /// Writes the `WwanEnabled` property on NetworkManager.
+    pub async fn set_wwan_enabled(&self, enabled: bool) -> Result<()> {
+        airplane::set_wwan_enabled(&self.conn, enabled).await
+    }
+
+    /// Enables or disables the Bluetooth radio by toggling all BlueZ adapters.
+    ///
+    /// Returns [`BluezUnavailable`](crate::ConnectionError::BluezUnavailable) if BlueZ is not running
+    /// or no adapters exist, or [`BluetoothToggleFailed`](crate::ConnectionError::BluetoothToggleFailed)
+    /// if any adapter could not be toggled or did not reach the requested power state.
+    ///
+    /// Uses kernel rfkill (`rfkill block/unblock bluetooth`) as the primary
+    /// mechanism, then also toggles BlueZ adapter `Powered` properties.
+    pub async fn set_bluetooth_radio_enabled(&self, enabled: bool) -> Result<()> {
+        airplane::set_bluetooth_radio_enabled(&self.conn, enabled).await
+    }
+
+    /// Flips all three radios in one call.
+    ///
+    /// **`enabled = true` means airplane mode is on, i.e. radios are off.**
+    ///
+    /// Wi-Fi and WWAN are toggled via NetworkManager properties. Bluetooth
+    /// is toggled via kernel rfkill plus BlueZ adapter `Powered`, ensuring
+    /// the soft-block state is visible to other components that read rfkill
+    /// to determine airplane-mode status.
+    ///
+    /// Does not fail fast: attempts all three toggles concurrently and
+    /// returns the first error at the end, if any. A missing Bluetooth
+    /// stack (BlueZ not running or no adapters) is treated as a successful
+    /// no-op rather than as an error so that flipping airplane mode on a
+    /// wifi-only host still succeeds and leaves the toggle in the expected
+    /// state. Bluetooth adapter toggle/settle failures are treated as
+    /// non-fatal for this aggregate operation when at least one Wi-Fi or
+    /// WWAN device is present and succeeds; such failures are logged while
+    /// Wi-Fi/WWAN success still yields `Ok(())`. If no Wi-Fi or WWAN device
+    /// is detected, the aggregate call can still return
+    /// [`BluetoothToggleFailed`](crate::ConnectionError::BluetoothToggleFailed)
+    /// for Bluetooth toggle/settle failures.
+    pub async fn set_airplane_mode(&self, enabled: bool) -> Result<()> {
+        airplane::set_airplane_mode(&self.conn, enabled).await
+    }
+
+    /// Whether the WWAN (mobile broadband) software toggle is on.
+    ///
+    /// Use [`wwan_state`](Self::wwan_state) to also read the hardware
+    /// kill-switch.
+    pub async fn wwan_enabled(&self) -> Result<bool> {
+        airplane::wwan_enabled(&self.conn).await
+    }
+
+    /// Whether networking is enabled as a whole.
+    ///
+    /// `false` after [`set_networking_enabled(false)`](Self::set_networking_enabled),
+    /// independent of the per-radio toggles.
+    pub async fn networking_enabled(&self) -> Result<bool> {
+        networking::networking_enabled(&self.conn).await
+    }
+
+    /// Enables or disables all networking (NetworkManager's `Enable`).
+    ///
+    /// Disabling deactivates every device, wired ones included, until
+    /// networking is enabled again; radio toggles are left as they are.
+    /// Requesting the current state is a no-op.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PermissionDenied`](crate::ConnectionError::PermissionDenied)
+    /// without the `org.freedesktop.NetworkManager.enable-disable-network`
+    /// polkit permission, or
+    /// [`DbusOperation`](crate::ConnectionError::DbusOperation) if NM
+    /// rejects the call otherwise.
+    pub async fn set_networking_enabled(&self, enabled: bool) -> Result<()> {
+        networking::set_networking_enabled(&self.conn, enabled).await
+    }
+
+    /// Puts NetworkManager to sleep, taking all devices down.
+    ///
+    /// For power-management integrations that suspend the system without
+    /// systemd-logind, which NetworkManager otherwise follows on its own.
+    /// Call [`wake`](Self::wake) after resume. A no-op if already asleep.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// nm.sleep().await?;
+    /// // ... suspend and resume ...
+    /// nm.wake().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PermissionDenied`](crate::ConnectionError::PermissionDenied)
+    /// without the `org.freedesktop.NetworkManager.sleep-wake` polkit
+    /// permission, or
+    /// [`DbusOperation`](crate::ConnectionError::DbusOperation) if NM
+    /// rejects the call otherwise.
+    pub async fn sleep(&self) -> Result<()> {
+        networking::set_sleeping(&self.conn, true).await
+    }
+
+    /// Wakes NetworkManager after [`sleep`](Self::sleep), letting devices
+    /// reconnect. A no-op if already awake.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`sleep`](Self::sleep).
+    pub async fn wake(&self) -> Result<()> {
+        networking::set_sleeping(&self.conn, false).await
+    }
+
+    /// Turns airplane mode on or off, remembering which radios were on.
+    ///
+    /// Turning it on saves the current [`AirplaneModeState`] and switches
+    /// Wi-Fi, WWAN and Bluetooth off as
+    /// [`set_airplane_mode(true)`](Self::set_airplane_mode) does. Turning it
+    /// off switches back on only the radios that were on at that point, so
+    /// a Bluetooth radio the user had disabled stays disabled. Without a
+    /// saved snapshot (e.g. airplane mode was entered by another program),
+    /// every radio is switched on.
+    ///
+    /// The snapshot is shared between clones of this `NetworkManager` and
+    /// lost when the last one is dropped; see
+    /// [`airplane_mode_snapshot`](Self::airplane_mode_snapshot).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// nm.airplane_mode(true).await?;
+    /// // ... take off, land ...
+    /// nm.airplane_mode(false).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [`set_airplane_mode`](Self::set_airplane_mode). If restoring
+    /// fails, the snapshot is kept so the call can be retried.
+    pub async fn airplane_mode(&self, on: bool) -> Result<()> {
+        if on {
+            // Already in airplane mode: keep the earlier snapshot rather
+            // than replacing it with one that has every radio off.
+            let before = airplane::airplane_mode_state(&self.conn).await?;
+            if !before.is_airplane_mode() {
+                *self.lock_airplane_snapshot() = Some(before);
+            }
+            return airplane::set_airplane_mode(&self.conn, true).await;
+        }
+
+        let snapshot = self.lock_airplane_snapshot().take();
+        let Some(before) = snapshot else {
+            return airplane::set_airplane_mode(&self.conn, false).await;
+        };
+        let result = airplane::restore_radios(&self.conn, &before).await;
+        if result.is_err() {
+            self.lock_airplane_snapshot().get_or_insert(before);
+        }
+        result
+    }
+
+    /// Radio state saved by the last [`airplane_mode(true)`](Self::airplane_mode)
+    /// that has not been restored yet.
+    ///
+    /// Lists the radios the next `airplane_mode(false)` switches back on.
+    #[must_use]
+    pub fn airplane_mode_snapshot(&self) -> Option<AirplaneModeState> {
+        *self.lock_airplane_snapshot()
+    }
+
+    fn lock_airplane_snapshot(&self) -> MutexGuard<'_, Option<AirplaneModeState>> {
+        self.airplane_snapshot
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Current connectivity state as NM sees it (single property read).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// let state = nm.connectivity().await?;
+    /// println!("{state:?}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connectivity(&self) -> Result<crate::ConnectivityState> {
+        crate::core::connectivity::connectivity(&self.conn).await
+    }
+
+    /// Forces NM to re-check connectivity by probing the configured URI.
+    ///
+    /// Returns the new state once the check completes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConnectivityCheckDisabled`](crate::ConnectionError::ConnectivityCheckDisabled)
+    /// if NM's connectivity checks are turned off.
+    pub async fn check_connectivity(&self) -> Result<crate::ConnectivityState> {
+        crate::core::connectivity::check_connectivity(&self.conn).await
+    }
+
+    /// Whether NM can check connectivity at all.
+    ///
+    /// `false` means no check URI is configured, so the state stays
+    /// [`Unknown`](crate::ConnectivityState::Unknown) regardless of
+    /// [`connectivity_check_enabled`](Self::connectivity_check_enabled).
+    pub async fn connectivity_check_available(&self) -> Result<bool> {
+        crate::core::connectivity::connectivity_check_available(&self.conn).await
+    }
+
+    /// Whether NM is allowed to probe its check URI.
+    pub async fn connectivity_check_enabled(&self) -> Result<bool> {
+        crate::core::connectivity::connectivity_check_enabled(&self.conn).await
+    }
+
+    /// Allows or forbids NM's periodic connectivity probes.
+    ///
+    /// Disabling stops NM from contacting the check URI; connectivity then
+    /// reports [`Unknown`](crate::ConnectivityState::Unknown) until the probes
+    /// are enabled again. The setting is persisted by NetworkManager. Enabling
+    /// has no effect while
+    /// [`connectivity_check_available`](Self::connectivity_check_available)
+    /// is `false`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// nm.set_connectivity_check_enabled(false).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PermissionDenied`](crate::ConnectionError::PermissionDenied)
+    /// without the
+    /// `org.freedesktop.NetworkManager.enable-disable-connectivity-check`
+    /// polkit permission, or
+    /// [`DbusOperation`](crate::ConnectionError::DbusOperation) if NM
+    /// rejects the change otherwise.
+    pub async fn set_connectivity_check_enabled(&self, enabled: bool) -> Result<()> {
+        crate::core::connectivity::set_connectivity_check_enabled(&self.conn, enabled).await
+    }
+
+    /// Full connectivity report including check URI and captive-portal URL.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// let report = nm.connectivity_report().await?;
+    /// println!("{:?} portal={:?}", report.state, report.captive_portal_url);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connectivity_report(&self) -> Result<crate::ConnectivityReport> {
+        crate::core::connectivity::connectivity_report(&self.conn).await
+    }
+
+    /// Captive-portal URL detected by NM, if state is `Portal`.
+    ///
+    /// Returns `None` if NM is not in `Portal` state or if this NM version
+    /// does not expose the URL.
+    pub async fn captive_portal_url(&self) -> Result<Option<String>> {
+        let report = crate::core::connectivity::connectivity_report(&self.conn).await?;
+        Ok(report.captive_portal_url)
+    }
+
+    /// Returns the most recent activation failure on `interface`.
+    ///
+    /// Failed [`connect`](Self::connect), [`connect_to_bssid`](Self::connect_to_bssid)
+    /// and [`connect_wired`](Self::connect_wired) calls are remembered per
+    /// device for the life of the process, with the error, profile and
+    /// time; a later successful connect on the device clears the entry.
+    /// Without a recorded failure, the device's last state reason is
+    /// checked, so a UI started after a failed autoconnect can still explain
+    /// it.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// if let Some(failure) = nm.last_failure("wlan0").await? {
+    ///     println!("{:?} failed: {}", failure.profile, failure.reason);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NotFound`](crate::ConnectionError::NotFound) if no device
+    /// is named `interface` and nothing was recorded for it.
+    pub async fn last_failure(&self, interface: &str) -> Result<Option<ConnectionFailure>> {
+        crate::core::failures::last_failure(&self.conn, interface).await
+    }
+
+    /// Checks for a captive portal and returns its login URL, if any.
+    ///
+    /// Forces a connectivity check and, when NetworkManager reports
+    /// [`Portal`](crate::ConnectivityState::Portal), fetches `probe` to find
+    /// where the portal redirects. With NM's checks disabled, the probe
+    /// alone decides.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    /// use nmrs::captive_portal::PortalProbe;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// if let Some(url) = nm.detect_captive_portal(&PortalProbe::default()).await? {
+    ///     println!("open {url} to log in");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connectivity check fails, or if checks are
+    /// disabled and the probe itself fails.
+    #[cfg(feature = "captive-portal")]
+    pub async fn detect_captive_portal(
+        &self,
+        probe: &crate::captive_portal::PortalProbe,
+    ) -> Result<Option<String>> {
+        crate::core::connectivity::detect_captive_portal(&self.conn, probe).await
+    }
+
+    /// Connects to a Wi-Fi network and fails if it sits behind a captive portal.
+    ///
+    /// Runs [`connect`](Self::connect), then
+    /// [`detect_captive_portal`](Self::detect_captive_portal). The
+    /// connection stays up when a portal is found, so the caller can open
+    /// the login page from the returned error.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::{ConnectionError, NetworkManager, WifiSecurity};
+    /// use nmrs::captive_portal::PortalProbe;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// let probe = PortalProbe::default();
+    /// match nm.connect_checking_portal("HotelWiFi", None, WifiSecurity::Open, &probe).await {
+    ///     Ok(()) => println!("online"),
+    ///     Err(ConnectionError::CaptivePortal { url }) => println!("log in at {url}"),
+    ///     Err(e) => return Err(e),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConnectionError::CaptivePortal`](crate::ConnectionError::CaptivePortal)
+    /// when a portal is detected, otherwise fails like
+    /// [`connect`](Self::connect). Failures of the portal check itself are
+    /// logged and ignored, since the connection did come up.
+    #[cfg(feature = "captive-portal")]
+    pub async fn connect_checking_portal(
+        &self,
+        ssid: &str,
+        interface: Option<&str>,
+        creds: WifiSecurity,
+        probe: &crate::captive_portal::PortalProbe,
+    ) -> Result<()> {
+        self.connect(ssid, interface, creds).await?;
+        match crate::core::connectivity::detect_captive_portal(&self.conn, probe).await {
+            Ok(Some(url)) => Err(crate::ConnectionError::CaptivePortal { url }),
+            Ok(None) => Ok(()),
+            Err(e) => {
+                log::debug!("Captive portal check after connecting to {ssid} failed: {e}");
+                Ok(())
+            }
+        }
+    }
+
+    /// Changes the NetworkManager daemon's log level and domains.
+    ///
+    /// Wraps NM's `SetLogging`. Pass an empty `domains` slice to keep the
+    /// current domains, or [`NmLogLevel::Keep`](crate::NmLogLevel::Keep) to
+    /// change only the domains. Domains accept NM's per-domain level syntax
+    /// (e.g. `"WIFI:TRACE"`). Requires root; NM rejects the call otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::{NetworkManager, NmLogLevel};
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// let previous = nm.get_nm_logging().await?;
+    ///
+    /// nm.set_nm_logging(NmLogLevel::Trace, &["WIFI", "DHCP4", "SUPPLICANT"]).await?;
+    /// // ... reproduce the issue ...
+    ///
+    /// let level = previous.level.unwrap_or(NmLogLevel::Info);
+    /// let domains: Vec<&str> = previous.domains.iter().map(String::as_str).collect();
+    /// nm.set_nm_logging(level, &domains).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn set_nm_logging(&self, level: crate::NmLogLevel, domains: &[&str]) -> Result<()> {
+        crate::core::logging::set_logging(&self.conn, level, domains).await
+    }
+
+    /// Reads the NetworkManager daemon's current log level and domains.
+    ///
+    /// Wraps NM's `GetLogging`.
+    pub async fn get_nm_logging(&self) -> Result<crate::NmLogging> {
+        crate::core::logging::get_logging(&self.conn).await
+    }
+
+    /// Reads the caller's polkit permissions for NetworkManager actions.
+    ///
+    /// Wraps NM's `GetPermissions`. Lets a GUI disable controls the user
+    /// cannot use instead of failing with
+    /// [`PermissionDenied`](crate::ConnectionError::PermissionDenied) after
+    /// the fact.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::{NetworkManager, Permission, PermissionResult};
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// let permissions = nm.permissions().await?;
+    ///
+    /// let connect_enabled = permissions.can_connect();
+    /// let edit_enabled = permissions.can_modify_system();
+    /// if permissions.get(&Permission::EnableDisableWifi) == PermissionResult::Auth {
+    ///     println!("toggling Wi-Fi will ask for a password");
+    /// }
+    /// # let _ = (connect_enabled, edit_enabled);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn permissions(&self) -> Result<crate::Permissions> {
+        crate::core::permissions::get_permissions(&self.conn).await
+    }
+
+    /// Disable or re-enable a single Wi-Fi interface.
+    ///
+    /// Sets `Device.Autoconnect = enabled` and, when disabling, calls
+    /// `Device.Disconnect()`. This is independent of the global wireless
+    /// killswitch ([`set_wireless_enabled`](Self::set_wireless_enabled)) and
+    /// safe to use on multi-radio systems.
+    ///
+    /// # Errors
+    ///
+    /// Returns
+    /// [`WifiInterfaceNotFound`](crate::ConnectionError::WifiInterfaceNotFound)
+    /// if no device with that name exists, or
+    /// [`NotAWifiDevice`](crate::ConnectionError::NotAWifiDevice) if the
+    /// interface isn't a Wi-Fi device.
+    pub async fn set_wifi_enabled(&self, interface: &str, enabled: bool) -> Result<()> {
+        set_wifi_enabled_for_interface(&self.conn, interface, enabled).await
+    }
+
+    /// Waits for a Wi-Fi device to become ready (disconnected or activated).
+    pub async fn wait_for_wifi_ready(&self) -> Result<()> {
+        wait_for_wifi_ready(&self.conn).await
+    }
+
+    /// Triggers a Wi-Fi scan.
+    ///
+    /// **3.0 break:** added the `interface` parameter. Pass `None` to scan
+    /// every Wi-Fi device, or `Some("wlan0")` to scan one. See
+    /// [`wifi`](Self::wifi) for an ergonomic per-interface API.
+    pub async fn scan_networks(&self, interface: Option<&str>) -> Result<()> {
+        let conn = self.conn.clone();
+        let iface = interface.map(str::to_owned);
+        self.limiter
+            .coalesce(coalesce_key("scan_networks", interface), async move {
+                scan_networks(&conn, iface.as_deref()).await
+            })
+            .await
+    }
+
+    /// Scans and returns the networks found once the scan has completed.
+    ///
+    /// Unlike [`scan_networks`](Self::scan_networks) followed by a sleep,
+    /// this watches each device's `LastScan` property and lists networks as
+    /// soon as fresh results are in. If a device never reports completion
+    /// (or NetworkManager declines the scan because it just ran one), the
+    /// wait gives up after a few seconds and returns what is known.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// for net in nm.scan_and_wait(Some("wlan0")).await? {
+    ///     println!("{} {}%", net.ssid, net.strength.unwrap_or(0));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns
+    /// [`WifiInterfaceNotFound`](crate::ConnectionError::WifiInterfaceNotFound)
+    /// if `interface` names no Wi-Fi device.
+    pub async fn scan_and_wait(&self, interface: Option<&str>) -> Result<Vec<Network>> {
+        let conn = self.conn.clone();
+        let iface = interface.map(str::to_owned);
+        self.limiter
+            .coalesce(coalesce_key("scan_and_wait", interface), async move {
+                scan_and_wait(&conn, iface.as_deref()).await
+            })
+            .await
+    }
+
+    /// Triggers a Wi-Fi scan with [`ScanOptions`].
+    ///
+    /// With SSIDs in `options`, the scan probes for them directly so hidden
+    /// networks show up in [`list_networks`](Self::list_networks) and can be
+    /// connected to reliably. Without, this is
+    /// [`scan_networks`](Self::scan_networks).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::{NetworkManager, ScanOptions};
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// nm.scan_networks_with_options(None, ScanOptions::new().with_ssid("CorpHidden"))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidInput`](crate::ConnectionError::InvalidInput) for an
+    /// SSID that is empty or longer than 32 bytes, and fails like
+    /// [`scan_networks`](Self::scan_networks) otherwise.
+    pub async fn scan_networks_with_options(
+        &self,
+        interface: Option<&str>,
+        options: ScanOptions,
+    ) -> Result<()> {
+        if options.ssids.is_empty() {
+            return self.scan_networks(interface).await;
+        }
+        self.limiter
+            .run(scan_networks_with_options(&self.conn, interface, &options))
+            .await
+    }
+
+    /// Returns whether any network device is currently in a transitional state.
+    ///
+    /// A device is considered "connecting" when its state is one of:
+    /// Prepare, Config, NeedAuth, IpConfig, IpCheck, Secondaries, or Deactivating.
+    ///
+    /// Use this to guard against concurrent connection attempts, which are
+    /// not supported and may cause undefined behavior.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    ///
+    /// if nm.is_connecting().await? {
+    ///     eprintln!("A connection operation is already in progress");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn is_connecting(&self) -> Result<bool> {
+        is_connecting(&self.conn).await
+    }
+
+    /// Check if a network is connected
+    pub async fn is_connected(&self, ssid: &str) -> Result<bool> {
+        is_connected(&self.conn, ssid).await
+    }
+
+    /// Disconnects from the current Wi-Fi network.
+    ///
+    /// If currently connected to a Wi-Fi network, this deactivates the
+    /// active connection on the targeted device and waits for it to go
+    /// down. Neither the device's nor the profile's autoconnect setting is
+    /// changed, so NetworkManager may join another known network on its
+    /// own. Use
+    /// [`disconnect_and_block`](Self::disconnect_and_block) to keep a
+    /// network from being rejoined, or
+    /// [`set_wireless_enabled`](Self::set_wireless_enabled) to turn the
+    /// radio off.
+    ///
+    /// **3.0 break:** added the `interface` parameter. Pass `None` for the
+    /// previous behavior (first Wi-Fi device), or `Some("wlan1")` to target
+    /// a specific interface.
     ///
-    /// nm.connect_vpn(config).await?;
-    /// # Ok(())
-    /// # }
-    /// ```
+    /// Returns `Ok(())` if disconnected successfully or if no active
+    /// connection exists.
     ///
-    /// ## OpenVPN
+    /// # Example
     ///
-    /// ```rust
-    /// use nmrs::{NetworkManager, OpenVpnConfig, OpenVpnAuthType};
+    /// ```no_run
+    /// use nmrs::NetworkManager;
     ///
     /// # async fn example() -> nmrs::Result<()> {
     /// let nm = NetworkManager::new().await?;
-    ///
-    /// let config = OpenVpnConfig::new("CorpVPN", "vpn.example.com", 1194, false)
-    ///     .with_auth_type(OpenVpnAuthType::PasswordTls)
-    ///     .with_username("user")
-    ///     .with_password("secret")
-    ///     .with_ca_cert("/etc/openvpn/ca.crt")
-    ///     .with_client_cert("/etc/openvpn/client.crt")
-    ///     .with_client_key("/etc/openvpn/client.key");
-    ///
-    /// nm.connect_vpn(config).await?;
+    /// nm.disconnect(None).await?;
+    /// nm.disconnect(Some("wlan1")).await?;
     /// # Ok(())
     /// # }
     /// ```
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if:
-    /// - NetworkManager is not running or accessible
-    /// - The configuration is invalid or incomplete
-    /// - The VPN connection fails to activate
-    pub async fn connect_vpn<C>(&self, config: C) -> Result<()>
-    where
-        C: VpnConfig + Into<VpnConfiguration>,
-    {
-        connect_vpn(&self.conn, config.into(), Some(self.timeout_config)).await
+    pub async fn disconnect(&self, interface: Option<&str>) -> Result<()> {
+        disconnect(&self.conn, interface, Some(self.timeout_config)).await
     }
 
-    /// Imports a `.ovpn` file and activates the OpenVPN connection.
-    ///
-    /// Parses the file, persists any inline certificates, builds the
-    /// connection profile, and activates it through NetworkManager.
-    ///
-    /// # Arguments
+    /// Disconnects from the Wi-Fi network `ssid` and stops NetworkManager
+    /// from rejoining it.
     ///
-    /// * `path` — Path to the `.ovpn` configuration file
-    /// * `username` — Optional username for password-based authentication
-    /// * `password` — Optional password for password-based authentication
+    /// Sets `connection.autoconnect` to `false` on the saved profile, then
+    /// deactivates it wherever it is active. The setting persists, so the
+    /// network is only joined again through an explicit connect; undo it
+    /// with [`set_autoconnect`](Self::set_autoconnect). Profiles are matched
+    /// by name or SSID with the configured
+    /// [`MatchOptions`](crate::MatchOptions).
     ///
     /// # Example
     ///
@@ -535,42 +3160,29 @@ impl NetworkManager {
     ///
     /// # async fn example() -> nmrs::Result<()> {
     /// let nm = NetworkManager::new().await?;
-    /// nm.import_ovpn("corp.ovpn", Some("user"), Some("secret")).await?;
+    /// nm.disconnect_and_block("CafeWiFi").await?;
     /// # Ok(())
     /// # }
     /// ```
     ///
     /// # Errors
     ///
-    /// Returns an error if:
-    /// - The file cannot be read or parsed
-    /// - Inline certificate storage fails
-    /// - The configuration is incomplete (e.g. TLS auth without certs)
-    /// - The VPN connection fails to activate
-    pub async fn import_ovpn(
-        &self,
-        path: impl AsRef<std::path::Path>,
-        username: Option<&str>,
-        password: Option<&str>,
-    ) -> Result<()> {
-        use crate::builders::OpenVpnBuilder;
-
-        let mut builder = OpenVpnBuilder::from_ovpn_file(path)?;
-        if let Some(u) = username {
-            builder = builder.username(u);
-        }
-        if let Some(p) = password {
-            builder = builder.password(p);
-        }
-        let config = builder.build()?;
-        self.connect_vpn(config).await
+    /// [`NoSavedConnection`](crate::ConnectionError::NoSavedConnection) if no
+    /// profile matches `ssid`.
+    pub async fn disconnect_and_block(&self, ssid: &str) -> Result<()> {
+        disconnect_and_block(
+            &self.conn,
+            ssid,
+            &self.match_options,
+            Some(self.timeout_config),
+        )
+        .await
     }
 
-    /// Disconnects from an active VPN connection by name.
+    /// Returns the full `Network` object for the currently connected WiFi network.
     ///
-    /// Searches through active connections for a VPN matching the given name.
-    /// If found, deactivates the connection. If not found or already disconnected,
-    /// returns success.
+    /// This provides detailed information about the active connection including
+    /// signal strength, frequency, security type, and BSSID.
     ///
     /// # Example
     ///
@@ -579,19 +3191,25 @@ impl NetworkManager {
     ///
     /// # async fn example() -> nmrs::Result<()> {
     /// let nm = NetworkManager::new().await?;
-    /// nm.disconnect_vpn("MyVPN").await?;
+    /// if let Some(network) = nm.current_network().await? {
+    ///     println!("Connected to: {} ({}%)", network.ssid, network.strength.unwrap_or(0));
+    /// } else {
+    ///     println!("Not connected");
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn disconnect_vpn(&self, name: &str) -> Result<()> {
-        disconnect_vpn(&self.conn, name).await
+    pub async fn current_network(&self) -> Result<Option<Network>> {
+        current_network(&self.conn).await
     }
 
-    /// Lists all saved VPN connections.
+    /// Lists all saved connection profiles with decoded [`SavedConnection`] summaries.
     ///
-    /// Returns a list of all VPN connection profiles saved in NetworkManager,
-    /// including their name, type, and current state. Returns WireGuard and
-    /// OpenVPN connections.
+    /// Secrets are not included; use a [secret agent](crate::agent) with
+    /// `GetSecrets` for passwords and keys.
+    ///
+    /// For a lighter call that only resolves `uuid`, `id`, and `type`, see
+    /// [`Self::list_saved_connections_brief`].
     ///
     /// # Example
     ///
@@ -600,213 +3218,246 @@ impl NetworkManager {
     ///
     /// # async fn example() -> nmrs::Result<()> {
     /// let nm = NetworkManager::new().await?;
-    /// let vpns = nm.list_vpn_connections().await?;
-    ///
-    /// for vpn in vpns {
-    ///     println!("{}: {:?}", vpn.name, vpn.vpn_type);
+    /// for c in nm.list_saved_connections().await? {
+    ///     println!("{}  {}  {}", c.id, c.connection_type, c.uuid);
     /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn list_vpn_connections(&self) -> Result<Vec<VpnConnection>> {
-        list_vpn_connections(&self.conn).await
+    pub async fn list_saved_connections(&self) -> Result<Vec<SavedConnection>> {
+        saved_profiles::list_saved_connections(&self.conn).await
     }
 
-    /// Only active VPNs (subset of `list_vpn_connections` with `active = true`).
-    pub async fn active_vpn_connections(&self) -> Result<Vec<VpnConnection>> {
-        active_vpn_connections(&self.conn).await
+    /// Lists saved profiles with only `connection.uuid`, `id`, and `type` (still one
+    /// `GetSettings` per profile, but skips building [`SettingsSummary`](crate::SettingsSummary)).
+    pub async fn list_saved_connections_brief(&self) -> Result<Vec<SavedConnectionBrief>> {
+        saved_profiles::list_saved_connections_brief(&self.conn).await
     }
 
-    /// Activate a saved VPN by UUID.
+    /// Returns the human-visible names (`connection.id`) of all saved profiles.
+    ///
+    /// Convenience over `list_saved_connections().map(|v| v.into_iter().map(|c| c.id).collect())`.
+    pub async fn list_saved_connection_ids(&self) -> Result<Vec<String>> {
+        Ok(saved_profiles::list_saved_connections_brief(&self.conn)
+            .await?
+            .into_iter()
+            .map(|c| c.id)
+            .collect())
+    }
+
+    /// Loads one saved profile by UUID with full [`SavedConnection`] decode.
+    ///
+    /// # Errors
+    ///
+    /// [`SavedConnectionNotFound`](crate::ConnectionError::SavedConnectionNotFound) if
+    /// the UUID does not exist.
+    pub async fn get_saved_connection(&self, uuid: &str) -> Result<SavedConnection> {
+        saved_profiles::get_saved_connection(&self.conn, uuid).await
+    }
+
+    /// Raw `GetSettings` map for advanced consumers.
+    pub async fn get_saved_connection_raw(
+        &self,
+        uuid: &str,
+    ) -> Result<HashMap<String, HashMap<String, OwnedValue>>> {
+        saved_profiles::get_saved_connection_raw(&self.conn, uuid).await
+    }
+
+    /// Deletes a saved profile by UUID (`Settings.Connection.Delete`).
+    pub async fn delete_saved_connection(&self, uuid: &str) -> Result<()> {
+        saved_profiles::delete_saved_connection(&self.conn, uuid).await
+    }
+
+    /// Exports a saved profile as `.nmconnection` keyfile text.
+    ///
+    /// The output can be written to `/etc/NetworkManager/system-connections/`
+    /// or passed to [`import_profile`](Self::import_profile) on another
+    /// machine. Secrets are not exported; NetworkManager does not hand them
+    /// out through `GetSettings`.
     ///
     /// # Example
     ///
     /// ```no_run
     /// use nmrs::NetworkManager;
     ///
-    /// # async fn example() -> nmrs::Result<()> {
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let nm = NetworkManager::new().await?;
-    /// nm.connect_vpn_by_uuid("2c3f1234-abcd-5678-ef01-234567890abc").await?;
+    /// let keyfile = nm.export_profile("6c7b5a6e-3a55-4c2e-9e59-2f7c4f1f0c11").await?;
+    /// std::fs::write("backup.nmconnection", keyfile)?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn connect_vpn_by_uuid(&self, uuid: &str) -> Result<()> {
-        connect_vpn_by_uuid(&self.conn, uuid, Some(self.timeout_config)).await
-    }
-
-    /// Activate a saved VPN by connection display name.
     ///
-    /// Fails with [`VpnIdAmbiguous`](crate::ConnectionError::VpnIdAmbiguous)
-    /// if multiple VPNs share the same name.
-    pub async fn connect_vpn_by_id(&self, id: &str) -> Result<()> {
-        connect_vpn_by_id(&self.conn, id, Some(self.timeout_config)).await
-    }
-
-    /// Disconnect a VPN by UUID.
-    pub async fn disconnect_vpn_by_uuid(&self, uuid: &str) -> Result<()> {
-        disconnect_vpn_by_uuid(&self.conn, uuid).await
+    /// # Errors
+    ///
+    /// [`SavedConnectionNotFound`](crate::ConnectionError::SavedConnectionNotFound) if
+    /// the UUID does not exist.
+    pub async fn export_profile(&self, uuid: &str) -> Result<String> {
+        saved_profiles::export_profile(&self.conn, uuid).await
     }
 
-    /// Forgets (deletes) a saved VPN connection by name.
+    /// Adds a saved profile from `.nmconnection` keyfile text and returns
+    /// its UUID.
     ///
-    /// Searches through saved connections for a VPN matching the given name.
-    /// If found, deletes the connection profile. If currently connected, the
-    /// VPN will be disconnected first before deletion.
+    /// The keyfile's UUID is kept, so importing the same file twice fails;
+    /// one is generated if the keyfile has none. The profile is persisted
+    /// but not activated.
     ///
     /// # Example
     ///
     /// ```no_run
     /// use nmrs::NetworkManager;
     ///
-    /// # async fn example() -> nmrs::Result<()> {
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let nm = NetworkManager::new().await?;
-    /// nm.forget_vpn("MyVPN").await?;
+    /// let keyfile = std::fs::read_to_string("backup.nmconnection")?;
+    /// let uuid = nm.import_profile(&keyfile).await?;
+    /// println!("imported {uuid}");
     /// # Ok(())
     /// # }
     /// ```
     ///
     /// # Errors
     ///
-    /// Returns an error only if the operation fails unexpectedly.
-    /// Returns `Ok(())` if no matching VPN connection is found.
-    pub async fn forget_vpn(&self, name: &str) -> Result<()> {
-        crate::core::vpn::forget_vpn(&self.conn, name).await
+    /// - [`KeyfileParse`](crate::ConnectionError::KeyfileParse) if the text is
+    ///   not a valid keyfile or a value does not match its key's type
+    /// - [`ConnectionInvalid`](crate::ConnectionError::ConnectionInvalid) if
+    ///   NetworkManager rejects the profile's settings, or
+    ///   [`DbusOperation`](crate::ConnectionError::DbusOperation) if it
+    ///   refuses it otherwise
+    pub async fn import_profile(&self, keyfile: &str) -> Result<String> {
+        saved_profiles::import_profile(&self.conn, keyfile).await
     }
 
-    /// Gets detailed information about an active VPN connection.
+    /// Overrides the DNS settings of a saved profile.
     ///
-    /// Retrieves comprehensive information about a VPN connection, including
-    /// IP configuration, DNS servers, gateway, interface, and connection state.
-    /// The VPN must be actively connected to retrieve this information.
+    /// `connection_id` is the profile's UUID or name. Servers, search
+    /// domains and `ignore-auto-dns` are written to its IPv4 and IPv6
+    /// settings; everything else in the profile is kept. An empty server
+    /// list clears the override. The change takes effect on the next
+    /// activation, or immediately if `reactivate` is set and the profile is
+    /// active.
     ///
     /// # Example
     ///
     /// ```no_run
-    /// use nmrs::NetworkManager;
+    /// use nmrs::{DnsConfig, NetworkManager};
+    /// use std::net::{IpAddr, Ipv4Addr};
     ///
     /// # async fn example() -> nmrs::Result<()> {
     /// let nm = NetworkManager::new().await?;
-    /// let info = nm.get_vpn_info("MyVPN").await?;
-    ///
-    /// println!("VPN: {}", info.name);
-    /// println!("Interface: {:?}", info.interface);
-    /// println!("IP Address: {:?}", info.ip4_address);
-    /// println!("DNS Servers: {:?}", info.dns_servers);
-    /// println!("State: {:?}", info.state);
+    /// let dns = DnsConfig::new(vec![IpAddr::V4(Ipv4Addr::new(9, 9, 9, 9))])
+    ///     .with_ignore_auto_dns(true);
+    /// nm.set_dns("Home WiFi", dns, true).await?;
     /// # Ok(())
     /// # }
     /// ```
     ///
     /// # Errors
     ///
-    /// Returns `ConnectionError::NoVpnConnection` if the VPN is not found
-    /// or not currently active.
-    pub async fn get_vpn_info(&self, name: &str) -> Result<VpnConnectionInfo> {
-        get_vpn_info(&self.conn, name).await
+    /// Returns [`SavedConnectionNotFound`](crate::ConnectionError::SavedConnectionNotFound)
+    /// if no profile matches, and `ConnectionError::InvalidInput` if a
+    /// server's address family is disabled on the profile.
+    pub async fn set_dns(
+        &self,
+        connection_id: &str,
+        dns: DnsConfig,
+        reactivate: bool,
+    ) -> Result<()> {
+        set_dns(
+            &self.conn,
+            connection_id,
+            &dns,
+            reactivate,
+            Some(self.timeout_config),
+        )
+        .await
     }
 
-    /// Returns the combined software/hardware state of the Wi-Fi radio.
+    /// Adds a static route to a saved profile.
     ///
-    /// See [`RadioState`] for the distinction between `enabled` (software)
-    /// and `hardware_enabled` (rfkill). The `present` flag reflects whether
-    /// NetworkManager has a Wi-Fi device object; if device enumeration fails,
-    /// `present` defaults to `true` so callers are not misled into thinking
-    /// Wi-Fi is absent.
-    pub async fn wifi_state(&self) -> Result<RadioState> {
-        let present_types = airplane::fetch_present_device_types(&self.conn).await;
-        airplane::wifi_state(&self.conn, present_types.as_ref()).await
-    }
-
-    /// Returns the combined software/hardware state of the WWAN radio.
+    /// `connection_id` is the profile's UUID or name; the address family is
+    /// taken from the route's destination. A saved route with the same
+    /// destination and next hop is replaced. The route is installed the
+    /// next time the profile is activated.
     ///
-    /// The `present` flag reflects whether NetworkManager has a modem device
-    /// object; if device enumeration fails, `present` defaults to `true`.
-    pub async fn wwan_state(&self) -> Result<RadioState> {
-        let present_types = airplane::fetch_present_device_types(&self.conn).await;
-        airplane::wwan_state(&self.conn, present_types.as_ref()).await
-    }
-
-    /// Returns the combined software/hardware state of the Bluetooth radio.
+    /// # Example
     ///
-    /// Reads power state from all BlueZ adapters and cross-references rfkill.
-    /// If BlueZ is not running or no adapters exist, returns a [`RadioState`]
-    /// with `present = false` so callers can ignore Bluetooth on hosts that
-    /// don't have it.
-    pub async fn bluetooth_radio_state(&self) -> Result<RadioState> {
-        airplane::bluetooth_radio_state(&self.conn).await
-    }
-
-    /// Returns the aggregated airplane-mode state across all radios.
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    /// use nmrs::builders::Route;
     ///
-    /// Fans out to Wi-Fi, WWAN, and Bluetooth concurrently and returns
-    /// an [`AirplaneModeState`] snapshot. Radios that are not actually
-    /// present on the host (no Wi-Fi card, no modem, no BlueZ) are reported
-    /// with `present = false` and are ignored by
-    /// [`AirplaneModeState::is_airplane_mode`] /
-    /// [`AirplaneModeState::any_hardware_killed`].
-    pub async fn airplane_mode_state(&self) -> Result<AirplaneModeState> {
-        airplane::airplane_mode_state(&self.conn).await
-    }
-
-    /// Enables or disables the Wi-Fi radio (software toggle).
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// // Send only the corporate range through the VPN.
+    /// nm.add_route("Work VPN", Route::new("10.0.0.0", 8).metric(50))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
     ///
-    /// This replaces the deprecated [`set_wifi_enabled`](Self::set_wifi_enabled).
-    /// If the radio is hardware-killed, NM accepts the write but the radio
-    /// remains off until hardware is unkilled.
+    /// # Errors
     ///
-    /// Note: NetworkManager implements this by writing rfkill soft blocks,
-    /// which most distributions persist across reboots via `rfkill-restore`
-    /// or systemd. A wifi disabled this way will remain disabled until it
-    /// is explicitly re-enabled.
-    pub async fn set_wireless_enabled(&self, enabled: bool) -> Result<()> {
-        airplane::set_wireless_enabled(&self.conn, enabled).await
+    /// Returns [`SavedConnectionNotFound`](crate::ConnectionError::SavedConnectionNotFound)
+    /// if no profile matches, and `ConnectionError::InvalidInput` for a
+    /// malformed route or one whose address family is disabled on the
+    /// profile.
+    pub async fn add_route(&self, connection_id: &str, route: Route) -> Result<()> {
+        add_route(&self.conn, connection_id, &route).await
     }
 
-    /// Enables or disables the WWAN (mobile broadband) radio.
+    /// Removes static routes from a saved profile.
     ///
-    /// Writes the `WwanEnabled` property on NetworkManager.
-    pub async fn set_wwan_enabled(&self, enabled: bool) -> Result<()> {
-        airplane::set_wwan_enabled(&self.conn, enabled).await
-    }
-
-    /// Enables or disables the Bluetooth radio by toggling all BlueZ adapters.
+    /// Routes with the same destination and prefix are removed; next hop
+    /// and metric only need to match if set in `route`. Returns whether any
+    /// route was removed.
     ///
-    /// Returns [`BluezUnavailable`](crate::ConnectionError::BluezUnavailable) if BlueZ is not running
-    /// or no adapters exist, or [`BluetoothToggleFailed`](crate::ConnectionError::BluetoothToggleFailed)
-    /// if any adapter could not be toggled or did not reach the requested power state.
+    /// # Errors
     ///
-    /// Uses kernel rfkill (`rfkill block/unblock bluetooth`) as the primary
-    /// mechanism, then also toggles BlueZ adapter `Powered` properties.
-    pub async fn set_bluetooth_radio_enabled(&self, enabled: bool) -> Result<()> {
-        airplane::set_bluetooth_radio_enabled(&self.conn, enabled).await
+    /// Fails like [`add_route`](Self::add_route).
+    pub async fn remove_route(&self, connection_id: &str, route: Route) -> Result<bool> {
+        remove_route(&self.conn, connection_id, &route).await
     }
 
-    /// Flips all three radios in one call.
+    /// Lists the routes installed for an active connection.
+    ///
+    /// Read from the connection's current `IP4Config` / `IP6Config`, so this
+    /// includes routes learned from DHCP, router advertisements or the VPN,
+    /// not only saved static routes. Returns an empty list if the profile
+    /// (UUID or name) is not active.
+    pub async fn list_routes(&self, connection_id: &str) -> Result<Vec<Route>> {
+        active_routes(&self.conn, connection_id).await
+    }
+
+    /// Merges a [`SettingsPatch`] into an existing profile (`Update` / `UpdateUnsaved`).
     ///
-    /// **`enabled = true` means airplane mode is on, i.e. radios are off.**
+    /// The patch is applied on top of the profile's current settings, so keys
+    /// it does not mention are preserved.
     ///
-    /// Wi-Fi and WWAN are toggled via NetworkManager properties. Bluetooth
-    /// is toggled via kernel rfkill plus BlueZ adapter `Powered`, ensuring
-    /// the soft-block state is visible to other components that read rfkill
-    /// to determine airplane-mode status.
+    /// # Example
     ///
-    /// Does not fail fast: attempts all three toggles concurrently and
-    /// returns the first error at the end, if any. A missing Bluetooth
-    /// stack (BlueZ not running or no adapters) is treated as a successful
-    /// no-op rather than as an error so that flipping airplane mode on a
-    /// wifi-only host still succeeds and leaves the toggle in the expected
-    /// state. Bluetooth adapter toggle/settle failures are treated as
-    /// non-fatal for this aggregate operation when at least one Wi-Fi or
-    /// WWAN device is present and succeeds; such failures are logged while
-    /// Wi-Fi/WWAN success still yields `Ok(())`. If no Wi-Fi or WWAN device
-    /// is detected, the aggregate call can still return
-    /// [`BluetoothToggleFailed`](crate::ConnectionError::BluetoothToggleFailed)
-    /// for Bluetooth toggle/settle failures.
-    pub async fn set_airplane_mode(&self, enabled: bool) -> Result<()> {
-        airplane::set_airplane_mode(&self.conn, enabled).await
+    /// ```no_run
+    /// use nmrs::{NetworkManager, SettingsPatch};
+    ///
+    /// # async fn example(uuid: &str) -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// let patch = SettingsPatch::new()
+    ///     .with_autoconnect(false)
+    ///     .with_id("Office (manual)");
+    /// nm.update_saved_connection(uuid, patch).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn update_saved_connection(&self, uuid: &str, patch: SettingsPatch) -> Result<()> {
+        saved_profiles::update_saved_connection(&self.conn, uuid, &patch).await
     }
 
-    /// Current connectivity state as NM sees it (single property read).
+    /// Reports whether the saved profile `name` is blocked from autoconnecting.
+    ///
+    /// Use this to tell users why a known network does not reconnect on its
+    /// own: autoconnect turned off, a manual disconnect, missing secrets or
+    /// repeated failures. Profiles are matched with the configured
+    /// [`MatchOptions`](crate::MatchOptions).
     ///
     /// # Example
     ///
@@ -815,28 +3466,44 @@ impl NetworkManager {
     ///
     /// # async fn example() -> nmrs::Result<()> {
     /// let nm = NetworkManager::new().await?;
-    /// let state = nm.connectivity().await?;
-    /// println!("{state:?}");
+    /// let status = nm.autoconnect_status("HomeWiFi").await?;
+    /// if let Some(reason) = status.blocked {
+    ///     println!("{} won't reconnect: {reason}", status.id);
+    ///     nm.clear_autoconnect_block("HomeWiFi").await?;
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn connectivity(&self) -> Result<crate::ConnectivityState> {
-        crate::core::connectivity::connectivity(&self.conn).await
+    ///
+    /// # Errors
+    ///
+    /// [`NoSavedConnection`](crate::ConnectionError::NoSavedConnection) if no
+    /// profile matches `name`.
+    pub async fn autoconnect_status(&self, name: &str) -> Result<AutoconnectStatus> {
+        autoconnect_status(&self.conn, name, &self.match_options).await
     }
 
-    /// Forces NM to re-check connectivity by probing the configured URI.
+    /// Lets NetworkManager autoconnect the saved profile `name` again.
     ///
-    /// Returns the new state once the check completes.
+    /// Resets NetworkManager's failure counter for the profile and re-enables
+    /// autoconnect on devices where a manual disconnect turned it off. A
+    /// profile with `connection.autoconnect` off stays off; change that with
+    /// [`set_autoconnect`](Self::set_autoconnect).
     ///
     /// # Errors
     ///
-    /// Returns [`ConnectivityCheckDisabled`](crate::ConnectionError::ConnectivityCheckDisabled)
-    /// if NM's connectivity checks are turned off.
-    pub async fn check_connectivity(&self) -> Result<crate::ConnectivityState> {
-        crate::core::connectivity::check_connectivity(&self.conn).await
+    /// [`NoSavedConnection`](crate::ConnectionError::NoSavedConnection) if no
+    /// profile matches `name`.
+    pub async fn clear_autoconnect_block(&self, name: &str) -> Result<()> {
+        clear_autoconnect_block(&self.conn, name, &self.match_options).await
     }
 
-    /// Full connectivity report including check URI and captive-portal URL.
+    /// Turns `connection.autoconnect` on or off for the saved profile `name`.
+    ///
+    /// Profiles are matched by name or SSID with the configured
+    /// [`MatchOptions`](crate::MatchOptions). Unlike
+    /// [`clear_autoconnect_block`](Self::clear_autoconnect_block), this
+    /// changes the stored profile.
     ///
     /// # Example
     ///
@@ -845,63 +3512,41 @@ impl NetworkManager {
     ///
     /// # async fn example() -> nmrs::Result<()> {
     /// let nm = NetworkManager::new().await?;
-    /// let report = nm.connectivity_report().await?;
-    /// println!("{:?} portal={:?}", report.state, report.captive_portal_url);
+    /// // Only join the café network when asked to.
+    /// nm.set_autoconnect("CafeWiFi", false).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn connectivity_report(&self) -> Result<crate::ConnectivityReport> {
-        crate::core::connectivity::connectivity_report(&self.conn).await
-    }
-
-    /// Captive-portal URL detected by NM, if state is `Portal`.
     ///
-    /// Returns `None` if NM is not in `Portal` state or if this NM version
-    /// does not expose the URL.
-    pub async fn captive_portal_url(&self) -> Result<Option<String>> {
-        let report = crate::core::connectivity::connectivity_report(&self.conn).await?;
-        Ok(report.captive_portal_url)
+    /// # Errors
+    ///
+    /// [`NoSavedConnection`](crate::ConnectionError::NoSavedConnection) if no
+    /// profile matches `name`.
+    pub async fn set_autoconnect(&self, name: &str, enabled: bool) -> Result<()> {
+        let patch = SettingsPatch::new().with_autoconnect(enabled);
+        update_autoconnect(&self.conn, name, &self.match_options, &patch).await
     }
 
-    /// Disable or re-enable a single Wi-Fi interface.
+    /// Sets `connection.autoconnect-priority` of the saved profile `name`.
     ///
-    /// Sets `Device.Autoconnect = enabled` and, when disabling, calls
-    /// `Device.Disconnect()`. This is independent of the global wireless
-    /// killswitch ([`set_wireless_enabled`](Self::set_wireless_enabled)) and
-    /// safe to use on multi-radio systems.
+    /// When several known networks are in range, NetworkManager activates
+    /// the one with the highest priority first (default `0`).
     ///
     /// # Errors
     ///
-    /// Returns
-    /// [`WifiInterfaceNotFound`](crate::ConnectionError::WifiInterfaceNotFound)
-    /// if no device with that name exists, or
-    /// [`NotAWifiDevice`](crate::ConnectionError::NotAWifiDevice) if the
-    /// interface isn't a Wi-Fi device.
-    pub async fn set_wifi_enabled(&self, interface: &str, enabled: bool) -> Result<()> {
-        set_wifi_enabled_for_interface(&self.conn, interface, enabled).await
-    }
-
-    /// Waits for a Wi-Fi device to become ready (disconnected or activated).
-    pub async fn wait_for_wifi_ready(&self) -> Result<()> {
-        wait_for_wifi_ready(&self.conn).await
-    }
-
-    /// Triggers a Wi-Fi scan.
-    ///
-    /// **3.0 break:** added the `interface` parameter. Pass `None` to scan
-    /// every Wi-Fi device, or `Some("wlan0")` to scan one. See
-    /// [`wifi`](Self::wifi) for an ergonomic per-interface API.
-    pub async fn scan_networks(&self, interface: Option<&str>) -> Result<()> {
-        scan_networks(&self.conn, interface).await
+    /// [`NoSavedConnection`](crate::ConnectionError::NoSavedConnection) if no
+    /// profile matches `name`.
+    pub async fn set_priority(&self, name: &str, priority: i32) -> Result<()> {
+        let patch = SettingsPatch::new().with_autoconnect_priority(priority);
+        update_autoconnect(&self.conn, name, &self.match_options, &patch).await
     }
 
-    /// Returns whether any network device is currently in a transitional state.
+    /// Lists saved profiles that may autoconnect, in the order NetworkManager
+    /// tries them.
     ///
-    /// A device is considered "connecting" when its state is one of:
-    /// Prepare, Config, NeedAuth, IpConfig, IpCheck, Secondaries, or Deactivating.
-    ///
-    /// Use this to guard against concurrent connection attempts, which are
-    /// not supported and may cause undefined behavior.
+    /// Profiles with `connection.autoconnect` off are left out. The rest are
+    /// sorted by descending `autoconnect-priority`, ties broken by the most
+    /// recently used profile, as NetworkManager does.
     ///
     /// # Example
     ///
@@ -910,34 +3555,63 @@ impl NetworkManager {
     ///
     /// # async fn example() -> nmrs::Result<()> {
     /// let nm = NetworkManager::new().await?;
-    ///
-    /// if nm.is_connecting().await? {
-    ///     eprintln!("A connection operation is already in progress");
+    /// for profile in nm.list_autoconnect_order().await? {
+    ///     println!("{:>4}  {}", profile.autoconnect_priority, profile.id);
     /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn is_connecting(&self) -> Result<bool> {
-        is_connecting(&self.conn).await
+    pub async fn list_autoconnect_order(&self) -> Result<Vec<SavedConnection>> {
+        autoconnect_order(&self.conn).await
     }
 
-    /// Check if a network is connected
-    pub async fn is_connected(&self, ssid: &str) -> Result<bool> {
-        is_connected(&self.conn, ssid).await
+    /// Registers a secret agent that answers NetworkManager's password
+    /// prompts with `provider`.
+    ///
+    /// Lets profiles whose secrets are agent-owned (not stored by
+    /// NetworkManager) be activated: when NetworkManager needs them, it asks
+    /// the agent and `provider` supplies them, e.g. from a dialog or keyring.
+    /// The agent uses this instance's bus and the default identifier; use
+    /// [`SecretAgentBuilder::register_provider`](crate::agent::SecretAgentBuilder::register_provider)
+    /// to choose another. Must be called within a Tokio runtime.
+    ///
+    /// See [`SecretProvider`] for an example.
+    ///
+    /// # Errors
+    ///
+    /// [`AgentRegistration`](crate::ConnectionError::AgentRegistration) if the
+    /// agent's bus name is taken or NetworkManager rejects the agent.
+    pub async fn register_secret_agent<P: SecretProvider>(
+        &self,
+        provider: P,
+    ) -> Result<SecretAgentHandle> {
+        SecretAgent::builder()
+            .with_connection(self.conn.clone())
+            .register_provider(provider)
+            .await
     }
 
-    /// Disconnects from the current Wi-Fi network.
+    /// Reads the secrets of one setting of a saved profile (`GetSecrets`).
     ///
-    /// If currently connected to a Wi-Fi network, this deactivates the
-    /// active connection on the targeted device and waits for it to reach
-    /// the disconnected state.
+    /// `setting_name` is an NM setting such as `802-11-wireless-security`,
+    /// `802-1x` or `vpn`. NetworkManager checks polkit authorization and may
+    /// prompt the user; agent-owned secrets are not returned.
     ///
-    /// **3.0 break:** added the `interface` parameter. Pass `None` for the
-    /// previous behavior (first Wi-Fi device), or `Some("wlan1")` to target
-    /// a specific interface.
+    /// # Errors
     ///
-    /// Returns `Ok(())` if disconnected successfully or if no active
-    /// connection exists.
+    /// [`SavedConnectionNotFound`](crate::ConnectionError::SavedConnectionNotFound) if
+    /// the UUID does not exist, or
+    /// [`PermissionDenied`](crate::ConnectionError::PermissionDenied) or
+    /// [`DbusOperation`](crate::ConnectionError::DbusOperation) if NM refuses.
+    pub async fn get_secrets(&self, uuid: &str, setting_name: &str) -> Result<ConnectionSecrets> {
+        get_secrets(&self.conn, uuid, setting_name).await
+    }
+
+    /// Returns the stored Wi-Fi password of the saved profile for `ssid`.
+    ///
+    /// Returns `Ok(None)` for profiles without a PSK NM can hand out (open
+    /// or enterprise networks, agent-owned secrets). Profiles are matched
+    /// with the configured [`MatchOptions`](crate::MatchOptions).
     ///
     /// # Example
     ///
@@ -946,19 +3620,27 @@ impl NetworkManager {
     ///
     /// # async fn example() -> nmrs::Result<()> {
     /// let nm = NetworkManager::new().await?;
-    /// nm.disconnect(None).await?;
-    /// nm.disconnect(Some("wlan1")).await?;
+    /// if let Some(psk) = nm.get_saved_psk("HomeWiFi").await? {
+    ///     println!("password: {psk}");
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn disconnect(&self, interface: Option<&str>) -> Result<()> {
-        disconnect(&self.conn, interface, Some(self.timeout_config)).await
+    ///
+    /// # Errors
+    ///
+    /// [`NoSavedConnection`](crate::ConnectionError::NoSavedConnection) if no
+    /// profile matches `ssid`.
+    pub async fn get_saved_psk(&self, ssid: &str) -> Result<Option<String>> {
+        get_saved_psk(&self.conn, ssid, &self.match_options).await
     }
 
-    /// Returns the full `Network` object for the currently connected WiFi network.
+    /// Returns a QR code for sharing the saved Wi-Fi network `ssid`.
     ///
-    /// This provides detailed information about the active connection including
-    /// signal strength, frequency, security type, and BSSID.
+    /// The PSK is read from the profile's stored secrets, so NetworkManager
+    /// may ask polkit for authorization. The profile is looked up with the
+    /// configured [`MatchOptions`](crate::MatchOptions). See the
+    /// [`qr`](crate::qr) module for rendering and parsing codes.
     ///
     /// # Example
     ///
@@ -967,25 +3649,39 @@ impl NetworkManager {
     ///
     /// # async fn example() -> nmrs::Result<()> {
     /// let nm = NetworkManager::new().await?;
-    /// if let Some(network) = nm.current_network().await? {
-    ///     println!("Connected to: {} ({}%)", network.ssid, network.strength.unwrap_or(0));
-    /// } else {
-    ///     println!("Not connected");
-    /// }
+    /// let code = nm.wifi_qr_code("HomeWiFi").await?;
+    /// println!("{code}"); // WIFI:S:HomeWiFi;T:WPA;P:...;;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn current_network(&self) -> Result<Option<Network>> {
-        current_network(&self.conn).await
+    ///
+    /// # Errors
+    ///
+    /// [`NoSavedConnection`](crate::ConnectionError::NoSavedConnection) if no
+    /// profile matches `ssid`,
+    /// [`MissingPassword`](crate::ConnectionError::MissingPassword) if its
+    /// PSK is not stored (e.g. agent-owned), and
+    /// [`InvalidInput`](crate::ConnectionError::InvalidInput) for enterprise,
+    /// WEP or non-Wi-Fi profiles.
+    #[cfg(feature = "qr")]
+    pub async fn wifi_qr_code(&self, ssid: &str) -> Result<crate::qr::WifiQrCode> {
+        crate::core::connection_settings::saved_wifi_qr_code(
+            &self.conn,
+            &self.settings_cache,
+            ssid,
+            &self.match_options,
+        )
+        .await
     }
 
-    /// Lists all saved connection profiles with decoded [`SavedConnection`] summaries.
-    ///
-    /// Secrets are not included; use a [secret agent](crate::agent) with
-    /// `GetSecrets` for passwords and keys.
+    /// Calls `ReloadConnections` so NM re-reads profiles from disk.
+    pub async fn reload_saved_connections(&self) -> Result<()> {
+        saved_profiles::reload_saved_connections(&self.conn).await
+    }
+
+    /// Finds a device by its interface name (e.g., "wlan0", "eth0").
     ///
-    /// For a lighter call that only resolves `uuid`, `id`, and `type`, see
-    /// [`Self::list_saved_connections_brief`].
+    /// Returns the D-Bus object path of the device if found.
     ///
     /// # Example
     ///
@@ -994,96 +3690,138 @@ impl NetworkManager {
     ///
     /// # async fn example() -> nmrs::Result<()> {
     /// let nm = NetworkManager::new().await?;
-    /// for c in nm.list_saved_connections().await? {
-    ///     println!("{}  {}  {}", c.id, c.connection_type, c.uuid);
-    /// }
+    /// let device_path = nm.get_device_by_interface("wlan0").await?;
+    /// println!("Device path: {}", device_path.as_str());
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn list_saved_connections(&self) -> Result<Vec<SavedConnection>> {
-        saved_profiles::list_saved_connections(&self.conn).await
+    pub async fn get_device_by_interface(&self, name: &str) -> Result<zvariant::OwnedObjectPath> {
+        get_device_by_interface(&self.conn, name).await
     }
 
-    /// Lists saved profiles with only `connection.uuid`, `id`, and `type` (still one
-    /// `GetSettings` per profile, but skips building [`SettingsSummary`](crate::SettingsSummary)).
-    pub async fn list_saved_connections_brief(&self) -> Result<Vec<SavedConnectionBrief>> {
-        saved_profiles::list_saved_connections_brief(&self.conn).await
+    /// Returns the SSID of the currently connected network, if any.
+    #[must_use]
+    pub async fn current_ssid(&self) -> Option<String> {
+        current_ssid(&self.conn).await
     }
 
-    /// Returns the human-visible names (`connection.id`) of all saved profiles.
-    ///
-    /// Convenience over `list_saved_connections().map(|v| v.into_iter().map(|c| c.id).collect())`.
-    pub async fn list_saved_connection_ids(&self) -> Result<Vec<String>> {
-        Ok(saved_profiles::list_saved_connections_brief(&self.conn)
-            .await?
-            .into_iter()
-            .map(|c| c.id)
-            .collect())
+    /// Returns the SSID and frequency of the current connection, if any.
+    #[must_use]
+    pub async fn current_connection_info(&self) -> Option<(String, Option<u32>)> {
+        current_connection_info(&self.conn).await
     }
 
-    /// Loads one saved profile by UUID with full [`SavedConnection`] decode.
+    /// Lists the connections NetworkManager is activating, has activated or
+    /// is deactivating, in its own order.
     ///
-    /// # Errors
+    /// # Examples
     ///
-    /// [`SavedConnectionNotFound`](crate::ConnectionError::SavedConnectionNotFound) if
-    /// the UUID does not exist.
-    pub async fn get_saved_connection(&self, uuid: &str) -> Result<SavedConnection> {
-        saved_profiles::get_saved_connection(&self.conn, uuid).await
-    }
-
-    /// Raw `GetSettings` map for advanced consumers.
-    pub async fn get_saved_connection_raw(
-        &self,
-        uuid: &str,
-    ) -> Result<HashMap<String, HashMap<String, OwnedValue>>> {
-        saved_profiles::get_saved_connection_raw(&self.conn, uuid).await
-    }
-
-    /// Deletes a saved profile by UUID (`Settings.Connection.Delete`).
-    pub async fn delete_saved_connection(&self, uuid: &str) -> Result<()> {
-        saved_profiles::delete_saved_connection(&self.conn, uuid).await
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// for active in nm.list_active_connections().await? {
+    ///     let route = if active.default { " (default route)" } else { "" };
+    ///     println!("{} [{}]: {}{route}", active.id, active.conn_type, active.state);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_active_connections(&self) -> Result<Vec<ActiveConnection>> {
+        list_active_connections(&self.conn).await
     }
 
-    /// Merges a [`SettingsPatch`] into an existing profile (`Update` / `UpdateUnsaved`).
-    pub async fn update_saved_connection(&self, uuid: &str, patch: SettingsPatch) -> Result<()> {
-        saved_profiles::update_saved_connection(&self.conn, uuid, &patch).await
+    /// Returns the connection NetworkManager uses as the default uplink,
+    /// or `None` when there is none.
+    ///
+    /// [`PrimaryConnection::kind`](crate::PrimaryConnection::kind) tells
+    /// whether the uplink is Wi-Fi, Ethernet, mobile broadband or a VPN,
+    /// without listing devices or connections.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// match nm.primary_connection().await? {
+    ///     Some(primary) => println!("Online via {} ({})", primary.kind, primary.connection.id),
+    ///     None => println!("No uplink"),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn primary_connection(&self) -> Result<Option<PrimaryConnection>> {
+        primary_connection(&self.conn).await
     }
 
-    /// Calls `ReloadConnections` so NM re-reads profiles from disk.
-    pub async fn reload_saved_connections(&self) -> Result<()> {
-        saved_profiles::reload_saved_connections(&self.conn).await
+    /// Streams the primary connection: the current one first, then each
+    /// time NetworkManager switches to another one (e.g. from Wi-Fi to
+    /// Ethernet when a cable is plugged in). `None` means there is no
+    /// uplink.
+    pub async fn watch_primary_connection(
+        &self,
+    ) -> Result<impl futures::Stream<Item = Option<PrimaryConnection>> + Send + Unpin + 'static>
+    {
+        watch_primary_connection(&self.conn).await
     }
 
-    /// Finds a device by its interface name (e.g., "wlan0", "eth0").
+    /// Returns the addresses, gateway, DNS servers, domains and routes of
+    /// every active connection.
     ///
-    /// Returns the D-Bus object path of the device if found.
+    /// The values come from NetworkManager's runtime `IP4Config` /
+    /// `IP6Config` objects, so they include DHCP-assigned data.
     ///
-    /// # Example
+    /// # Examples
     ///
     /// ```no_run
     /// use nmrs::NetworkManager;
     ///
     /// # async fn example() -> nmrs::Result<()> {
     /// let nm = NetworkManager::new().await?;
-    /// let device_path = nm.get_device_by_interface("wlan0").await?;
-    /// println!("Device path: {}", device_path.as_str());
+    /// for details in nm.active_connection_details().await? {
+    ///     if let Some(v4) = &details.ipv4 {
+    ///         println!("{}: gateway {:?}, dns {:?}", details.id, v4.gateway, v4.nameservers);
+    ///     }
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn get_device_by_interface(&self, name: &str) -> Result<zvariant::OwnedObjectPath> {
-        get_device_by_interface(&self.conn, name).await
-    }
-
-    /// Returns the SSID of the currently connected network, if any.
-    #[must_use]
-    pub async fn current_ssid(&self) -> Option<String> {
-        current_ssid(&self.conn).await
+    pub async fn active_connection_details(&self) -> Result<Vec<ActiveConnectionDetails>> {
+        active_connection_details(&self.conn).await
     }
 
-    /// Returns the SSID and frequency of the current connection, if any.
-    #[must_use]
-    pub async fn current_connection_info(&self) -> Option<(String, Option<u32>)> {
-        current_connection_info(&self.conn).await
+    /// Returns the DHCPv4 and DHCPv6 leases `interface` currently holds.
+    ///
+    /// Each lease carries the server identifier, lease time, domain and NTP
+    /// servers, plus the full option map the DHCP client reported.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// if let Some(lease) = nm.dhcp_info("eth0").await?.ipv4 {
+    ///     println!("server {:?}, lease {:?}", lease.server_identifier, lease.lease_time);
+    ///     for (name, value) in &lease.options {
+    ///         println!("  {name} = {value}");
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NotFound`](crate::ConnectionError::NotFound) if no device
+    /// is named `interface`.
+    pub async fn dhcp_info(&self, interface: impl Into<DeviceRef<'_>>) -> Result<DhcpInfo> {
+        let interface = interface.into().interface().await?;
+        dhcp_info(&self.conn, &interface).await
     }
 
     /// Returns detailed information about a specific network.
@@ -1091,17 +3829,38 @@ impl NetworkManager {
         show_details(&self.conn, net).await
     }
 
+    /// Returns the full details of a network's access point: ciphers, WPA
+    /// generation, likely 802.11 standard, when it was last seen and
+    /// whether the SSID has a saved profile.
+    ///
+    /// The access point in use is described, or else the strongest one.
+    /// See [`NetworkDetails`] for an example.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NotFound`](crate::ConnectionError::NotFound) if no access point of the
+    /// network is visible anymore.
+    pub async fn network_details(&self, net: &Network) -> Result<NetworkDetails> {
+        network_details(&self.conn, net).await
+    }
+
     /// Returns whether a saved connection exists for the given SSID.
+    ///
+    /// Profiles match by connection ID or SSID according to the
+    /// configured [`MatchOptions`](crate::MatchOptions).
     pub async fn has_saved_connection(&self, ssid: &str) -> Result<bool> {
-        has_saved_connection(&self.conn, ssid).await
+        Ok(self.get_saved_connection_path(ssid).await?.is_some())
     }
 
     /// Returns the D-Bus object path of a saved connection for the given SSID.
+    ///
+    /// Profiles match by connection ID or SSID according to the
+    /// configured [`MatchOptions`](crate::MatchOptions).
     pub async fn get_saved_connection_path(
         &self,
         ssid: &str,
     ) -> Result<Option<zvariant::OwnedObjectPath>> {
-        get_saved_connection_path(&self.conn, ssid).await
+        find_saved_connection(&self.settings_cache, ssid, &self.match_options).await
     }
 
     /// Forgets (deletes) a saved WiFi connection for the given SSID.
@@ -1116,8 +3875,10 @@ impl NetworkManager {
     pub async fn forget(&self, ssid: &str) -> Result<()> {
         forget_by_name_and_type(
             &self.conn,
+            &self.settings_cache,
             ssid,
             Some(device_type::WIFI),
+            &self.match_options,
             Some(self.timeout_config),
         )
         .await
@@ -1139,17 +3900,235 @@ impl NetworkManager {
     pub async fn forget_bluetooth(&self, name: &str) -> Result<()> {
         forget_by_name_and_type(
             &self.conn,
+            &self.settings_cache,
             name,
             Some(device_type::BLUETOOTH),
+            &self.match_options,
             Some(self.timeout_config),
         )
         .await
     }
+
+    /// Returns a stream of typed network events.
+    ///
+    /// Merges device added/removed, access point added/removed, device
+    /// connection state, Wi-Fi radio, connectivity and checkpoint signals
+    /// into one [`Stream`](futures::Stream) of [`NetworkEvent`]s. Devices
+    /// that appear later are subscribed to automatically. If the
+    /// NetworkManager daemon restarts, [`NetworkEvent::Daemon`] reports it
+    /// and the stream re-subscribes to the new instance, so no reconnect
+    /// loop is needed.
+    /// Unlike the `monitor_*` callbacks, the stream composes with
+    /// `StreamExt` combinators and `tokio::select!`; dropping it
+    /// unsubscribes.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use futures::StreamExt;
+    /// use nmrs::{NetworkEvent, NetworkManager};
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// let mut events = nm.events().await?;
+    /// while let Some(event) = events.next().await {
+    ///     match event {
+    ///         NetworkEvent::ConnectionStateChanged { interface, state, .. } => {
+    ///             println!("{interface}: {state}");
+    ///         }
+    ///         NetworkEvent::ConnectivityChanged(c) => println!("connectivity: {c:?}"),
+    ///         _ => {}
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn events(
+        &self,
+    ) -> Result<impl futures::Stream<Item = NetworkEvent> + Send + Unpin + 'static> {
+        events_monitor::network_events(&self.conn).await
+    }
+
+    /// Returns a stream of signal strength updates (0-100).
+    ///
+    /// `ssid_or_interface` is a Wi-Fi interface name such as `"wlan0"` or the
+    /// SSID of an active Wi-Fi connection. The stream yields the current
+    /// strength first and then each change reported by the access point, so
+    /// a UI can show live bars without re-running
+    /// [`list_networks`](Self::list_networks). Roams to another access point
+    /// are followed; nothing is yielded while the device is disconnected.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use futures::StreamExt;
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// let mut strength = nm.watch_signal_strength("wlan0").await?;
+    /// while let Some(s) = strength.next().await {
+    ///     println!("signal: {s}%");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NotAWifiDevice`](crate::ConnectionError::NotAWifiDevice) if
+    /// the interface is not Wi-Fi, and [`NotFound`](crate::ConnectionError::NotFound)
+    /// if it names neither an interface nor a connected SSID.
+    pub async fn watch_signal_strength(
+        &self,
+        ssid_or_interface: &str,
+    ) -> Result<impl futures::Stream<Item = u8> + Send + Unpin + 'static> {
+        crate::monitoring::signal::signal_strength(&self.conn, ssid_or_interface).await
+    }
+
+    ///
+    /// Returns the current link quality of Wi-Fi device `interface`.
+    ///
+    /// Alongside NetworkManager's 0–100 strength and the transmit bitrate,
+    /// the signal and noise levels in dBm, the receive bitrate and the
+    /// retry and beacon-loss counters are read from wpa_supplicant; those
+    /// fields are `None` if it cannot be reached or does not report them.
+    /// Returns `None` when the device is not associated with an access
+    /// point.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// if let Some(stats) = nm.link_stats("wlan0").await? {
+    ///     println!(
+    ///         "{} {}% {:?} dBm, {} Mbit/s",
+    ///         stats.bssid,
+    ///         stats.strength,
+    ///         stats.signal_dbm,
+    ///         stats.tx_bitrate_kbps / 1000
+    ///     );
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiInterfaceNotFound`](crate::ConnectionError::WifiInterfaceNotFound)
+    /// or [`NotAWifiDevice`](crate::ConnectionError::NotAWifiDevice) if
+    /// `interface` is not a Wi-Fi device.
+    pub async fn link_stats(
+        &self,
+        interface: impl Into<DeviceRef<'_>>,
+    ) -> Result<Option<LinkStats>> {
+        let interface = interface.into().interface().await?;
+        crate::monitoring::link_stats::link_stats(&self.conn, &interface).await
+    }
+
+    /// Records [`LinkStats`] samples of Wi-Fi device `interface` into
+    /// `collector`.
+    ///
+    /// A sample is taken immediately and then whenever the signal strength
+    /// of the access point or the device's bitrate changes, following roams.
+    /// Nothing is recorded while the device is disconnected.
+    ///
+    /// This function runs until the device goes away or the bus connection
+    /// closes. Run it in a background task and query a clone of
+    /// `collector`; see [`StatsCollector`] for an example.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiInterfaceNotFound`](crate::ConnectionError::WifiInterfaceNotFound)
+    /// or [`NotAWifiDevice`](crate::ConnectionError::NotAWifiDevice) if
+    /// `interface` is not a Wi-Fi device.
+    pub async fn collect_link_stats(
+        &self,
+        interface: impl Into<DeviceRef<'_>>,
+        collector: StatsCollector,
+    ) -> Result<()> {
+        let interface = interface.into().interface().await?;
+        crate::monitoring::link_stats::collect_link_stats(&self.conn, &interface, collector).await
+    }
+
+    /// Returns the received and transmitted byte counters of `interface`.
+    ///
+    /// Reads `Device.Statistics`. NetworkManager only updates the counters
+    /// while refresh is enabled on the device, so a refresh interval of one
+    /// second is set if it is off.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// let stats = nm.traffic_stats("wlan0").await?;
+    /// println!("rx {} B, tx {} B", stats.rx_bytes, stats.tx_bytes);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NotFound`](crate::ConnectionError::NotFound) if no device
+    /// is named `interface`.
+    pub async fn traffic_stats(&self, interface: impl Into<DeviceRef<'_>>) -> Result<TrafficStats> {
+        let interface = interface.into().interface().await?;
+        crate::monitoring::traffic::traffic_stats(&self.conn, &interface).await
+    }
+
+    /// Returns a stream of the traffic on `interface`, one
+    /// [`TrafficDelta`] per second.
     ///
+    /// Each item holds the bytes received and transmitted since the
+    /// previous one, e.g. to draw a live throughput graph. An interval in
+    /// which the counters were reset is skipped. The stream ends when the
+    /// device goes away.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use futures::StreamExt;
+    /// use nmrs::NetworkManager;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// let mut traffic = nm.watch_traffic("wlan0").await?;
+    /// while let Some(delta) = traffic.next().await {
+    ///     println!(
+    ///         "down {:.1} KiB/s, up {:.1} KiB/s",
+    ///         delta.rx_bytes_per_sec() / 1024.0,
+    ///         delta.tx_bytes_per_sec() / 1024.0
+    ///     );
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NotFound`](crate::ConnectionError::NotFound) if no device
+    /// is named `interface`.
+    pub async fn watch_traffic(
+        &self,
+        interface: impl Into<DeviceRef<'_>>,
+    ) -> Result<impl futures::Stream<Item = TrafficDelta> + Send + Unpin + 'static> {
+        let interface = interface.into().interface().await?;
+        crate::monitoring::traffic::watch_traffic(&self.conn, &interface).await
+    }
+
     /// Subscribes to D-Bus signals for access point additions, removals, and
     /// signal strength changes on all Wi-Fi devices. Invokes the callback
     /// whenever the network list or signal data changes, enabling live UI
-    /// updates without polling.
+    /// updates without polling. Use
+    /// [`WifiScope::monitor_network_changes`](crate::WifiScope::monitor_network_changes)
+    /// to watch a single adapter. Subscriptions are re-established when
+    /// NetworkManager restarts.
     ///
     /// This function runs indefinitely until an error occurs. Run it in a
     /// background task.
@@ -1175,7 +4154,7 @@ impl NetworkManager {
         F: Fn() + Send + 'static,
     {
         let (_tx, rx) = watch::channel(());
-        network_monitor::monitor_network_changes(&self.conn, rx, callback).await
+        network_monitor::monitor_network_changes(&self.conn, None, rx, callback).await
     }
 
     /// Monitors device state changes in real-time.
@@ -1183,7 +4162,8 @@ impl NetworkManager {
     /// Subscribes to D-Bus signals for device state changes on all network
     /// devices (both wired and wireless). Invokes the callback whenever a
     /// device state changes (e.g., cable plugged in, device activated),
-    /// enabling live UI updates without polling.
+    /// enabling live UI updates without polling. Subscriptions are
+    /// re-established when NetworkManager restarts.
     ///
     /// This function runs indefinitely until an error occurs. Run it in a
     /// background task.
@@ -1211,4 +4191,221 @@ impl NetworkManager {
         let (_tx, rx) = watch::channel(());
         device_monitor::monitor_device_changes(&self.conn, rx, callback).await
     }
+
+    /// Revalidates network state every time the system resumes from suspend.
+    ///
+    /// Listens for systemd-logind's `PrepareForSleep(false)` signal. On each
+    /// resume it forces a Wi-Fi rescan, re-probes connectivity and re-reads
+    /// the primary connection, then calls `callback` with a
+    /// [`ResumeReport`](crate::ResumeReport). Use
+    /// [`is_stale`](crate::ResumeReport::is_stale) to decide whether to
+    /// reconnect.
+    ///
+    /// This function runs indefinitely until an error occurs. Run it in a
+    /// background task. Requires systemd-logind on the system bus.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # use nmrs::NetworkManager;
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    ///
+    /// let nm_clone = nm.clone();
+    /// tokio::spawn(async move {
+    ///     nm_clone.resume_hook(|report| {
+    ///         if report.is_stale() {
+    ///             println!("Connection lost over suspend: {report:?}");
+    ///         }
+    ///     }).await
+    /// });
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn resume_hook<F>(&self, callback: F) -> Result<()>
+    where
+        F: Fn(crate::ResumeReport) + Send + 'static,
+    {
+        let (_tx, rx) = watch::channel(());
+        resume_monitor::resume_hook(&self.conn, rx, callback).await
+    }
+
+    /// Runs the post-resume checks of [`resume_hook`](Self::resume_hook) once, immediately.
+    pub async fn check_after_resume(&self) -> crate::ResumeReport {
+        resume_monitor::check_after_resume(&self.conn).await
+    }
+
+    /// Monitors NetworkManager checkpoints and their rollbacks.
+    ///
+    /// Calls `callback` when a checkpoint is created, when NM rolls one back
+    /// because its timeout expired, and when one is destroyed earlier. Admin
+    /// tools can use this to learn that an automatic rollback reverted their
+    /// changes. When NetworkManager restarts, the checkpoints of the old
+    /// daemon are reported as destroyed or rolled back.
+    ///
+    /// This function runs indefinitely until an error occurs. Run it in a
+    /// background task.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # use nmrs::{CheckpointEvent, NetworkManager};
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    ///
+    /// let nm_clone = nm.clone();
+    /// tokio::spawn(async move {
+    ///     nm_clone.monitor_checkpoints(|event| {
+    ///         if let CheckpointEvent::RolledBack { path } = event {
+    ///             eprintln!("{path} expired and was rolled back");
+    ///         }
+    ///     }).await
+    /// });
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn monitor_checkpoints<F>(&self, callback: F) -> Result<()>
+    where
+        F: Fn(crate::CheckpointEvent) + Send + 'static,
+    {
+        let (_tx, rx) = watch::channel(());
+        checkpoint_monitor::monitor_checkpoints(&self.conn, rx, callback).await
+    }
+
+    /// Monitors one device by hardware identity, surviving interface renames.
+    ///
+    /// Unlike interface names, the permanent MAC in a
+    /// [`DeviceIdentity`](crate::models::DeviceIdentity) stays the same when a
+    /// USB adapter re-enumerates as `wlan1` after a re-plug. The monitor
+    /// re-binds to the device's new D-Bus path automatically and calls
+    /// `callback` with the refreshed [`Device`] on every state change, or
+    /// `None` while the device is unplugged. It also re-binds when
+    /// NetworkManager restarts.
+    ///
+    /// This function runs indefinitely until an error occurs. Run it in a
+    /// background task.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # use nmrs::NetworkManager;
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// let devices = nm.list_devices().await?;
+    /// let dongle = devices.into_iter().find(|d| d.interface == "wlan1").unwrap();
+    ///
+    /// let nm_clone = nm.clone();
+    /// tokio::spawn(async move {
+    ///     nm_clone.monitor_device(dongle.identity, |dev| match dev {
+    ///         Some(dev) => println!("{} is {}", dev.interface, dev.state),
+    ///         None => println!("adapter unplugged"),
+    ///     }).await
+    /// });
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn monitor_device<F>(&self, identity: DeviceIdentity, callback: F) -> Result<()>
+    where
+        F: Fn(Option<Device>) + Send + 'static,
+    {
+        let (_tx, rx) = watch::channel(());
+        device_monitor::monitor_device_by_identity(&self.conn, identity, rx, callback).await
+    }
+
+    /// Writes every [`NetworkEvent`] to `writer` as a line of JSON.
+    ///
+    /// Runs until writing fails, e.g. because the reader closed a pipe or
+    /// socket. See the [`event_export`](crate::event_export) module for the
+    /// line format.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nmrs::NetworkManager;
+    /// use tokio::net::UnixStream;
+    ///
+    /// # async fn example() -> nmrs::Result<()> {
+    /// let nm = NetworkManager::new().await?;
+    /// // A status bar listening on its own socket.
+    /// let bar = UnixStream::connect("/run/user/1000/bar-events.sock")
+    ///     .await
+    ///     .expect("status bar is listening");
+    /// nm.export_events(bar).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EventExportFailed`](crate::ConnectionError::EventExportFailed)
+    /// once a write fails, or a D-Bus error if subscribing fails.
+    #[cfg(feature = "event-export")]
+    pub async fn export_events<W>(&self, writer: W) -> Result<()>
+    where
+        W: tokio::io::AsyncWrite + Unpin,
+    {
+        let events = self.events().await?;
+        crate::event_export::write_events(events, writer)
+            .await
+            .map_err(|e| crate::ConnectionError::EventExportFailed(e.to_string()))
+    }
+
+    /// Serves the JSON event stream to every client of `listener`.
+    ///
+    /// Each client that connects gets its own subscription, starting with
+    /// events that happen after it connected; a client that disconnects only
+    /// ends its own export. Runs until accepting a client fails.
+    ///
+    /// See the [`event_export`](crate::event_export) module for an example.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EventExportFailed`](crate::ConnectionError::EventExportFailed)
+    /// if the listener stops accepting connections.
+    #[cfg(feature = "event-export")]
+    pub async fn serve_events(&self, listener: tokio::net::UnixListener) -> Result<()> {
+        loop {
+            let (stream, _) = listener
+                .accept()
+                .await
+                .map_err(|e| crate::ConnectionError::EventExportFailed(e.to_string()))?;
+            let nm = self.clone();
+            tokio::spawn(async move {
+                if let Err(e) = nm.export_events(stream).await {
+                    log::debug!("Event export client went away: {e}");
+                }
+            });
+        }
+    }
+
+    /// Records per-profile data usage into `store` every `interval`.
+    ///
+    /// Each round reads the `Device.Statistics` counters of every device
+    /// with an active connection and adds the traffic since the previous
+    /// round to today's total for that connection's profile, then flushes
+    /// the store. Counter refresh is enabled on devices where it is off.
+    ///
+    /// This function runs indefinitely until an error occurs. Run it in a
+    /// background task and query a clone of `store`. See the
+    /// [`usage`](crate::usage) module for an example.
+    #[cfg(feature = "usage-history")]
+    pub async fn track_usage(
+        &self,
+        store: crate::usage::UsageStore,
+        interval: std::time::Duration,
+    ) -> Result<()> {
+        let (_tx, rx) = watch::channel(());
+        usage_monitor::track_usage(&self.conn, store, interval, rx).await
+    }
+}
+
+/// Opens a connection to the bus at `address`.
+async fn connect_address(address: &str) -> Result<Connection> {
+    let builder = zbus::connection::Builder::address(address).map_err(|e| {
+        crate::ConnectionError::InvalidInput {
+            field: "address".into(),
+            reason: e.to_string(),
+        }
+    })?;
+    Ok(builder.build().await?)
 }
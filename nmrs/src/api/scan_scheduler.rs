@@ -0,0 +1,107 @@
+//! Background Wi-Fi scanning with debounced network snapshots.
+//!
+//! [`ScanScheduler`] runs the scan/merge/refresh loop that network pickers
+//! otherwise reimplement: it requests a scan on a fixed interval, keeps a
+//! deduplicated table of visible access points from
+//! `AccessPointAdded`/`AccessPointRemoved` signals, and yields a fresh
+//! `Vec<Network>` whenever that table changes. Build it with
+//! [`NetworkManager::scan_scheduler`](crate::NetworkManager::scan_scheduler):
+//!
+//! ```no_run
+//! use futures::StreamExt;
+//! use nmrs::NetworkManager;
+//! use std::time::Duration;
+//!
+//! # async fn example() -> nmrs::Result<()> {
+//! let nm = NetworkManager::new().await?;
+//! let mut snapshots = nm
+//!     .scan_scheduler()
+//!     .with_interval(Duration::from_secs(20))
+//!     .snapshots()
+//!     .await?;
+//! while let Some(networks) = snapshots.next().await {
+//!     println!("{} networks visible", networks.len());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::time::Duration;
+
+use futures::Stream;
+
+use crate::Result;
+use crate::api::models::Network;
+use crate::core::scan::scan_snapshots;
+
+/// Periodic Wi-Fi scanner streaming debounced network lists.
+///
+/// Created via [`NetworkManager::scan_scheduler`](crate::NetworkManager::scan_scheduler).
+/// Defaults to a scan every 30 seconds, all Wi-Fi devices, and a 500 ms
+/// debounce window.
+#[derive(Debug, Clone)]
+pub struct ScanScheduler {
+    pub(crate) conn: zbus::Connection,
+    pub(crate) interface: Option<String>,
+    pub(crate) interval: Duration,
+    pub(crate) debounce: Duration,
+}
+
+impl ScanScheduler {
+    pub(crate) fn new(conn: zbus::Connection) -> Self {
+        Self {
+            conn,
+            interface: None,
+            interval: Duration::from_secs(30),
+            debounce: Duration::from_millis(500),
+        }
+    }
+
+    /// Sets how often a scan is requested (at least one second).
+    #[must_use]
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Sets how long to collect further changes before yielding a snapshot.
+    ///
+    /// A scan adds and removes many access points in quick succession;
+    /// this window turns that burst into one snapshot.
+    #[must_use]
+    pub fn with_debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// Restricts scanning and snapshots to one Wi-Fi interface.
+    #[must_use]
+    pub fn with_interface(mut self, interface: impl Into<String>) -> Self {
+        self.interface = Some(interface.into());
+        self
+    }
+
+    /// Starts scanning and returns the stream of network snapshots.
+    ///
+    /// The current networks are yielded right away; later snapshots follow
+    /// access point changes. Each snapshot has the same shape as
+    /// [`NetworkManager::list_networks`](crate::NetworkManager::list_networks).
+    /// Dropping the stream stops the scheduler.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiInterfaceNotFound`](crate::ConnectionError::WifiInterfaceNotFound)
+    /// or [`NotAWifiDevice`](crate::ConnectionError::NotAWifiDevice) for a bad
+    /// interface, or a D-Bus error if signal subscription fails.
+    pub async fn snapshots(
+        &self,
+    ) -> Result<impl Stream<Item = Vec<Network>> + Send + Unpin + 'static> {
+        scan_snapshots(
+            &self.conn,
+            self.interface.as_deref(),
+            self.interval,
+            self.debounce,
+        )
+        .await
+    }
+}
@@ -0,0 +1,105 @@
+//! Ring buffer of Wi-Fi link quality samples.
+//!
+//! A [`StatsCollector`] is filled by
+//! [`NetworkManager::collect_link_stats`](crate::NetworkManager::collect_link_stats),
+//! which takes a [`LinkStats`] snapshot whenever the signal or bitrate of
+//! the device changes. Clones share the same buffer, so one clone can be
+//! handed to the sampling task while another is queried from the UI.
+//!
+//! ```no_run
+//! use std::time::Duration;
+//! use nmrs::{NetworkManager, StatsCollector};
+//!
+//! # async fn example() -> nmrs::Result<()> {
+//! let nm = NetworkManager::new().await?;
+//! let collector = StatsCollector::new(600);
+//!
+//! let sampler = collector.clone();
+//! let nm_clone = nm.clone();
+//! tokio::spawn(async move { nm_clone.collect_link_stats("wlan0", sampler).await });
+//!
+//! tokio::time::sleep(Duration::from_secs(60)).await;
+//! if let Some(summary) = collector.summary(Duration::from_secs(60)) {
+//!     println!(
+//!         "strength {:.0}–{:.0}% (avg {:.0}%) over {} samples",
+//!         summary.strength.min, summary.strength.max, summary.strength.avg, summary.samples
+//!     );
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime};
+
+use crate::api::models::{LinkStats, LinkSummary};
+
+/// Bounded, shareable history of [`LinkStats`] samples.
+///
+/// Once `capacity` samples are held, each new sample evicts the oldest.
+#[derive(Debug, Clone)]
+pub struct StatsCollector {
+    samples: Arc<Mutex<VecDeque<LinkStats>>>,
+    capacity: usize,
+}
+
+impl StatsCollector {
+    /// Creates an empty collector keeping at most `capacity` samples (at
+    /// least one).
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            samples: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+        }
+    }
+
+    /// Maximum number of samples kept.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Appends `stats`, evicting the oldest sample when full.
+    pub fn record(&self, stats: LinkStats) {
+        let mut samples = self.lock();
+        if samples.len() == self.capacity {
+            samples.pop_front();
+        }
+        samples.push_back(stats);
+    }
+
+    /// All samples held, oldest first.
+    #[must_use]
+    pub fn samples(&self) -> Vec<LinkStats> {
+        self.lock().iter().cloned().collect()
+    }
+
+    /// The most recent sample.
+    #[must_use]
+    pub fn latest(&self) -> Option<LinkStats> {
+        self.lock().back().cloned()
+    }
+
+    /// Min/max/average over the samples taken within the last `window`,
+    /// or `None` if there are none.
+    #[must_use]
+    pub fn summary(&self, window: Duration) -> Option<LinkSummary> {
+        let since = SystemTime::now()
+            .checked_sub(window)
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        let samples = self.lock();
+        LinkSummary::from_samples(samples.iter().filter(|s| s.sampled_at >= since))
+    }
+
+    /// Drops all samples, e.g. after roaming to another network.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, VecDeque<LinkStats>> {
+        self.samples.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -0,0 +1,116 @@
+//! Automatic VPN recovery.
+//!
+//! [`VpnSupervisor`] watches an active VPN and steps in when it drops
+//! without the user disconnecting it: it either re-activates the VPN with
+//! exponential backoff, or acts as a kill switch that takes the uplinks
+//! down until the tunnel is back (bringing them up only while each attempt
+//! activates the VPN, see [`VpnSupervisorPolicy::KillSwitch`]). Build it
+//! with [`NetworkManager::vpn_supervisor`](crate::NetworkManager::vpn_supervisor)
+//! and poll its event stream, e.g. to show "reconnecting VPN…":
+//!
+//! ```no_run
+//! use futures::StreamExt;
+//! use nmrs::{NetworkManager, VpnSupervisorEvent, VpnSupervisorPolicy};
+//!
+//! # async fn example() -> nmrs::Result<()> {
+//! let nm = NetworkManager::new().await?;
+//! let mut events = nm
+//!     .vpn_supervisor("Corp VPN")
+//!     .with_policy(VpnSupervisorPolicy::KillSwitch)
+//!     .events()
+//!     .await?;
+//! while let Some(event) = events.next().await {
+//!     if let VpnSupervisorEvent::Reconnecting { attempt, .. } = event {
+//!         println!("reconnecting VPN (attempt {attempt})…");
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::time::Duration;
+
+use futures::Stream;
+
+use crate::Result;
+use crate::api::models::{VpnSupervisorEvent, VpnSupervisorPolicy};
+use crate::core::vpn_supervisor::{SupervisorConfig, supervise};
+
+/// Reconnects or kill-switches an active VPN when it drops.
+///
+/// Created via [`NetworkManager::vpn_supervisor`](crate::NetworkManager::vpn_supervisor).
+/// Defaults to [`VpnSupervisorPolicy::Reconnect`], a backoff doubling from
+/// 1 second up to 1 minute, and unlimited attempts.
+#[derive(Debug, Clone)]
+pub struct VpnSupervisor {
+    pub(crate) conn: zbus::Connection,
+    pub(crate) id: String,
+    pub(crate) policy: VpnSupervisorPolicy,
+    pub(crate) initial_backoff: Duration,
+    pub(crate) max_backoff: Duration,
+    pub(crate) max_attempts: Option<u32>,
+    pub(crate) activation_timeout: Duration,
+}
+
+impl VpnSupervisor {
+    pub(crate) fn new(conn: zbus::Connection, id: &str, activation_timeout: Duration) -> Self {
+        Self {
+            conn,
+            id: id.to_string(),
+            policy: VpnSupervisorPolicy::default(),
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            max_attempts: None,
+            activation_timeout,
+        }
+    }
+
+    /// Sets what happens when the VPN drops.
+    #[must_use]
+    pub fn with_policy(mut self, policy: VpnSupervisorPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Sets the delay before the first attempt and the cap it doubles up to.
+    #[must_use]
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max.max(initial);
+        self
+    }
+
+    /// Gives up after `attempts` failed reconnections in a row.
+    ///
+    /// A kill switch that gives up leaves the uplinks down.
+    #[must_use]
+    pub fn with_max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = Some(attempts);
+        self
+    }
+
+    /// Starts supervising and returns the stream of supervisor events.
+    ///
+    /// The VPN is looked up among active connections by name or UUID.
+    /// Supervision runs while the stream is polled; dropping it stops the
+    /// supervisor. The stream ends after [`VpnSupervisorEvent::Stopped`]
+    /// or [`VpnSupervisorEvent::GaveUp`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NoVpnConnection`](crate::ConnectionError::NoVpnConnection)
+    /// if no active VPN matches, or a D-Bus error if signal subscription
+    /// fails.
+    pub async fn events(
+        &self,
+    ) -> Result<impl Stream<Item = VpnSupervisorEvent> + Send + Unpin + 'static> {
+        let config = SupervisorConfig {
+            policy: self.policy,
+            initial_backoff: self.initial_backoff,
+            max_backoff: self.max_backoff,
+            max_attempts: self.max_attempts,
+            activation_timeout: Some(self.activation_timeout),
+        };
+        supervise(&self.conn, &self.id, config).await
+    }
+}
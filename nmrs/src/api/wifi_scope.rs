@@ -19,12 +19,22 @@
 //! # }
 //! ```
 
+use std::borrow::Cow;
+
 use crate::Result;
+use crate::api::device_handle::{DeviceHandle, DeviceRef};
 use crate::api::models::access_point::AccessPoint;
-use crate::api::models::{Network, WifiSecurity};
+use crate::api::models::{ConnectionOptions, Network, ScanOptions, WifiSecurity};
+use crate::backend::SettingsCache;
 use crate::core::connection::{connect, connect_to_bssid, disconnect, forget_by_name_and_type};
-use crate::core::scan::{list_access_points, list_networks, scan_networks};
+use crate::core::limiter::{Limiter, coalesce_key};
+use crate::core::roaming::roam_to;
+use crate::core::scan::{
+    list_access_points, list_networks, scan_and_wait, scan_networks, scan_networks_with_options,
+};
 use crate::core::wifi_device::set_wifi_enabled_for_interface;
+use crate::core::wps::connect_wps;
+use crate::monitoring::network::monitor_network_changes;
 use crate::types::constants::device_type;
 
 /// Operations scoped to a single Wi-Fi interface.
@@ -35,39 +45,115 @@ use crate::types::constants::device_type;
 pub struct WifiScope {
     pub(crate) conn: zbus::Connection,
     pub(crate) interface: String,
+    pub(crate) device: Option<DeviceHandle>,
     pub(crate) timeout_config: crate::api::models::TimeoutConfig,
+    pub(crate) uuid_namespace: Option<uuid::Uuid>,
+    pub(crate) autoconnect: Option<bool>,
+    pub(crate) match_options: crate::api::models::MatchOptions,
+    pub(crate) limiter: std::sync::Arc<Limiter>,
+    pub(crate) settings_cache: SettingsCache,
 }
 
 impl WifiScope {
+    /// Options for profiles created through this scope.
+    fn profile_options(&self) -> ConnectionOptions {
+        ConnectionOptions {
+            uuid_namespace: self.uuid_namespace,
+            ..ConnectionOptions::new(self.autoconnect.unwrap_or(true))
+        }
+    }
+
     /// The interface name this scope is pinned to (e.g. `"wlan0"`).
+    ///
+    /// For a scope created from a [`DeviceHandle`] this is the name at
+    /// creation; operations re-resolve the device each time.
     #[must_use]
     pub fn interface(&self) -> &str {
         &self.interface
     }
 
+    /// The interface to operate on, re-resolved for handle-based scopes.
+    async fn iface(&self) -> Result<Cow<'_, str>> {
+        match &self.device {
+            Some(handle) => DeviceRef::Handle(handle).interface().await,
+            None => Ok(Cow::Borrowed(&self.interface)),
+        }
+    }
+
     /// Trigger a Wi-Fi scan on this interface only.
     pub async fn scan(&self) -> Result<()> {
-        scan_networks(&self.conn, Some(&self.interface)).await
+        let conn = self.conn.clone();
+        let iface = self.iface().await?.into_owned();
+        self.limiter
+            .coalesce(coalesce_key("scan_networks", Some(&iface)), async move {
+                scan_networks(&conn, Some(&iface)).await
+            })
+            .await
+    }
+
+    /// Trigger a scan on this interface that also probes for hidden SSIDs.
+    ///
+    /// See [`NetworkManager::scan_networks_with_options`](crate::NetworkManager::scan_networks_with_options).
+    pub async fn scan_with_options(&self, options: ScanOptions) -> Result<()> {
+        if options.ssids.is_empty() {
+            return self.scan().await;
+        }
+        let iface = self.iface().await?;
+        self.limiter
+            .run(scan_networks_with_options(
+                &self.conn,
+                Some(&iface),
+                &options,
+            ))
+            .await
+    }
+
+    /// Scan this interface and return its networks once the scan completes.
+    ///
+    /// See [`NetworkManager::scan_and_wait`](crate::NetworkManager::scan_and_wait).
+    pub async fn scan_and_wait(&self) -> Result<Vec<Network>> {
+        let conn = self.conn.clone();
+        let iface = self.iface().await?.into_owned();
+        self.limiter
+            .coalesce(coalesce_key("scan_and_wait", Some(&iface)), async move {
+                scan_and_wait(&conn, Some(&iface)).await
+            })
+            .await
     }
 
     /// List visible networks on this interface (grouped by SSID).
     pub async fn list_networks(&self) -> Result<Vec<Network>> {
-        list_networks(&self.conn, Some(&self.interface)).await
+        let conn = self.conn.clone();
+        let iface = self.iface().await?.into_owned();
+        self.limiter
+            .coalesce(coalesce_key("list_networks", Some(&iface)), async move {
+                list_networks(&conn, Some(&iface)).await
+            })
+            .await
     }
 
     /// List individual access points on this interface (one per BSSID).
     pub async fn list_access_points(&self) -> Result<Vec<AccessPoint>> {
-        list_access_points(&self.conn, Some(&self.interface)).await
+        let conn = self.conn.clone();
+        let iface = self.iface().await?.into_owned();
+        self.limiter
+            .coalesce(
+                coalesce_key("list_access_points", Some(&iface)),
+                async move { list_access_points(&conn, Some(&iface)).await },
+            )
+            .await
     }
 
     /// Connect this interface to the given SSID.
     pub async fn connect(&self, ssid: &str, creds: WifiSecurity) -> Result<()> {
+        let iface = self.iface().await?;
         connect(
             &self.conn,
             ssid,
             creds,
-            Some(&self.interface),
+            Some(&iface),
             Some(self.timeout_config),
+            &self.profile_options(),
         )
         .await
     }
@@ -79,20 +165,70 @@ impl WifiScope {
         bssid: Option<&str>,
         creds: WifiSecurity,
     ) -> Result<()> {
+        let iface = self.iface().await?;
         connect_to_bssid(
             &self.conn,
             ssid,
             bssid,
             creds,
-            Some(&self.interface),
+            Some(&iface),
+            Some(self.timeout_config),
+            &self.profile_options(),
+        )
+        .await
+    }
+
+    /// Join a network on this interface using WPS push-button.
+    ///
+    /// See [`NetworkManager::connect_wps_pbc`](crate::NetworkManager::connect_wps_pbc).
+    pub async fn connect_wps_pbc(&self) -> Result<()> {
+        let iface = self.iface().await?;
+        connect_wps(
+            &self.conn,
+            Some(&iface),
+            None,
             Some(self.timeout_config),
+            &self.profile_options(),
+        )
+        .await
+    }
+
+    /// Join a network on this interface using a WPS PIN.
+    ///
+    /// See [`NetworkManager::connect_wps_pin`](crate::NetworkManager::connect_wps_pin).
+    pub async fn connect_wps_pin(&self, pin: &str) -> Result<()> {
+        let iface = self.iface().await?;
+        connect_wps(
+            &self.conn,
+            Some(&iface),
+            Some(pin),
+            Some(self.timeout_config),
+            &self.profile_options(),
+        )
+        .await
+    }
+
+    /// Re-associate this interface with another access point of its
+    /// current network. See
+    /// [`NetworkManager::roam_to`](crate::NetworkManager::roam_to).
+    pub async fn roam_to(&self, bssid: &str) -> Result<()> {
+        let iface = self.iface().await?;
+        roam_to(
+            &self.conn,
+            bssid,
+            Some(&iface),
+            Some(self.timeout_config.connection_timeout),
         )
         .await
     }
 
     /// Disconnect this interface from its active network, if any.
+    ///
+    /// Autoconnect is left alone; see
+    /// [`NetworkManager::disconnect`](crate::NetworkManager::disconnect).
     pub async fn disconnect(&self) -> Result<()> {
-        disconnect(&self.conn, Some(&self.interface), Some(self.timeout_config)).await
+        let iface = self.iface().await?;
+        disconnect(&self.conn, Some(&iface), Some(self.timeout_config)).await
     }
 
     /// Enable or disable autoconnect on this interface only.
@@ -100,7 +236,23 @@ impl WifiScope {
     /// Independent of NetworkManager's global Wi-Fi killswitch
     /// ([`set_wireless_enabled`](crate::NetworkManager::set_wireless_enabled)).
     pub async fn set_enabled(&self, enabled: bool) -> Result<()> {
-        set_wifi_enabled_for_interface(&self.conn, &self.interface, enabled).await
+        let iface = self.iface().await?;
+        set_wifi_enabled_for_interface(&self.conn, &iface, enabled).await
+    }
+
+    /// Monitor access point and signal strength changes on this interface only.
+    ///
+    /// Runs until an error occurs, like
+    /// [`NetworkManager::monitor_network_changes`](crate::NetworkManager::monitor_network_changes).
+    /// Fails with `WifiInterfaceNotFound` or `NotAWifiDevice` if the
+    /// interface is not a Wi-Fi device.
+    pub async fn monitor_network_changes<F>(&self, callback: F) -> Result<()>
+    where
+        F: Fn() + Send + 'static,
+    {
+        let iface = self.iface().await?;
+        let (_tx, rx) = tokio::sync::watch::channel(());
+        monitor_network_changes(&self.conn, Some(&iface), rx, callback).await
     }
 
     /// Forget a saved Wi-Fi connection by SSID.
@@ -111,8 +263,10 @@ impl WifiScope {
     pub async fn forget(&self, ssid: &str) -> Result<()> {
         forget_by_name_and_type(
             &self.conn,
+            &self.settings_cache,
             ssid,
             Some(device_type::WIFI),
+            &self.match_options,
             Some(self.timeout_config),
         )
         .await
@@ -0,0 +1,594 @@
+//! In-memory [`NmBackend`] for tests (feature `test-util`).
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use zvariant::{OwnedObjectPath, OwnedValue, Value};
+
+use super::{
+    ActivationOptions, NewProfileSettings, NmBackend, ObjectProperties, ProfileSettings,
+    ScanRequestOptions,
+};
+use crate::Result;
+use crate::api::models::{ConnectionError, DeviceState, DeviceType};
+
+/// An activation recorded by [`MockBackend`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockActivation {
+    /// Active connection path handed out for this activation.
+    pub active: OwnedObjectPath,
+    /// Profile that was activated.
+    pub connection: OwnedObjectPath,
+    /// Device it was activated on.
+    pub device: OwnedObjectPath,
+    /// Specific object (access point or base connection), `/` if none.
+    pub specific_object: OwnedObjectPath,
+    /// Whether the profile was added by this activation.
+    pub added: bool,
+    /// `AddAndActivateConnection2` options, with values in text form;
+    /// empty for other activations.
+    pub options: BTreeMap<String, String>,
+}
+
+/// An access point seeded into [`MockBackend`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockAccessPoint {
+    /// SSID.
+    pub ssid: String,
+    /// BSSID, e.g. `AA:BB:CC:DD:EE:FF`.
+    pub bssid: String,
+    /// Frequency in MHz.
+    pub frequency: u32,
+    /// Signal strength in percent.
+    pub strength: u8,
+    /// Number of completed scans before the access point becomes visible.
+    pub visible_after_scans: u32,
+}
+
+impl MockAccessPoint {
+    /// An access point visible from the start.
+    #[must_use]
+    pub fn new(ssid: &str, bssid: &str, frequency: u32) -> Self {
+        Self {
+            ssid: ssid.to_string(),
+            bssid: bssid.to_string(),
+            frequency,
+            strength: 0,
+            visible_after_scans: 0,
+        }
+    }
+
+    /// Sets the signal strength in percent.
+    #[must_use]
+    pub fn with_strength(mut self, strength: u8) -> Self {
+        self.strength = strength;
+        self
+    }
+
+    /// Hides the access point until `scans` scans have completed.
+    #[must_use]
+    pub fn with_visible_after_scans(mut self, scans: u32) -> Self {
+        self.visible_after_scans = scans;
+        self
+    }
+}
+
+/// [`NmBackend`] keeping devices, access points and profiles in memory.
+///
+/// Devices, access points and saved profiles are seeded with the `with_*`
+/// builders. Scans always complete, and each advances the device's
+/// `LastScan` by a second. Access points are open infrastructure networks
+/// last seen by the latest scan. Activations succeed unless an error
+/// was set with [`with_activation_error`](Self::with_activation_error) or
+/// queued with [`with_activation_failure`](Self::with_activation_failure)
+/// or, for VPNs only,
+/// [`with_vpn_activation_failure`](Self::with_vpn_activation_failure);
+/// a successful activation moves the device to
+/// [`DeviceState::Activated`]. Activations on `/` (software devices such
+/// as bridges) need no seeded device.
+#[derive(Debug, Default)]
+pub struct MockBackend {
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    devices: Vec<Device>,
+    profiles: Vec<(OwnedObjectPath, ProfileSettings)>,
+    activations: Vec<MockActivation>,
+    deactivated: Vec<OwnedObjectPath>,
+    activation_error: Option<ConnectionError>,
+    activation_failures: VecDeque<ConnectionError>,
+    vpn_activation_failures: VecDeque<ConnectionError>,
+    access_points: Vec<AccessPointEntry>,
+    /// Position of each access point in `access_points`, by path.
+    access_point_index: HashMap<OwnedObjectPath, usize>,
+    next_id: u32,
+}
+
+#[derive(Debug)]
+struct AccessPointEntry {
+    path: OwnedObjectPath,
+    device: OwnedObjectPath,
+    ap: MockAccessPoint,
+}
+
+#[derive(Debug)]
+struct Device {
+    path: OwnedObjectPath,
+    interface: String,
+    device_type: u32,
+    state: u32,
+    scans: u32,
+}
+
+impl State {
+    fn next_path(&mut self, kind: &str) -> OwnedObjectPath {
+        self.next_id += 1;
+        let path = format!("/org/freedesktop/NetworkManager/{kind}/{}", self.next_id);
+        OwnedObjectPath::try_from(path).expect("generated object paths are valid")
+    }
+
+    fn device(&mut self, path: &OwnedObjectPath) -> Result<&mut Device> {
+        self.devices
+            .iter_mut()
+            .find(|d| &d.path == path)
+            .ok_or(ConnectionError::NotFound)
+    }
+
+    /// Checks that `path` is a known device, or `/` for none.
+    fn check_device(&mut self, path: &OwnedObjectPath) -> Result<()> {
+        if path.as_str() != "/" {
+            self.device(path)?;
+        }
+        Ok(())
+    }
+
+    /// Latest activation on `device` still up, if the device is activated.
+    fn activation_on(&self, device: &OwnedObjectPath) -> Option<&MockActivation> {
+        let activated = self
+            .devices
+            .iter()
+            .any(|d| &d.path == device && d.state == u32::from(DeviceState::Activated));
+        if !activated {
+            return None;
+        }
+        self.activations
+            .iter()
+            .rev()
+            .find(|a| &a.device == device && !self.deactivated.contains(&a.active))
+    }
+
+    fn access_point_entry(&self, path: &OwnedObjectPath) -> Result<&AccessPointEntry> {
+        self.access_point_index
+            .get(path)
+            .map(|&i| &self.access_points[i])
+            .ok_or(ConnectionError::NotFound)
+    }
+
+    fn access_point(&self, path: &OwnedObjectPath) -> Result<&MockAccessPoint> {
+        Ok(&self.access_point_entry(path)?.ap)
+    }
+
+    fn add_profile(&mut self, settings: NewProfileSettings) -> OwnedObjectPath {
+        let path = self.next_path("Settings");
+        let owned = settings
+            .into_iter()
+            .map(|(section, values)| {
+                let values = values
+                    .into_iter()
+                    .filter_map(|(k, v)| Some((k.to_string(), OwnedValue::try_from(v).ok()?)))
+                    .collect();
+                (section.to_string(), values)
+            })
+            .collect();
+        self.profiles.push((path.clone(), owned));
+        path
+    }
+}
+
+impl MockBackend {
+    /// Creates a backend with no devices or profiles.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a device.
+    #[must_use]
+    pub fn with_device(self, interface: &str, device_type: DeviceType, state: DeviceState) -> Self {
+        {
+            let mut s = self.lock();
+            let path = s.next_path("Devices");
+            s.devices.push(Device {
+                path,
+                interface: interface.to_string(),
+                device_type: device_type.into(),
+                state: state.into(),
+                scans: 0,
+            });
+        }
+        self
+    }
+
+    /// Adds an access point seen by the device `interface`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no device named `interface` was added.
+    #[must_use]
+    pub fn with_access_point(self, interface: &str, ap: MockAccessPoint) -> Self {
+        {
+            let mut s = self.lock();
+            let device = s
+                .devices
+                .iter()
+                .find(|d| d.interface == interface)
+                .map(|d| d.path.clone())
+                .expect("access points need a seeded device");
+            let path = s.next_path("AccessPoint");
+            let index = s.access_points.len();
+            s.access_point_index.insert(path.clone(), index);
+            s.access_points.push(AccessPointEntry { path, device, ap });
+        }
+        self
+    }
+
+    /// Adds a saved profile, e.g. one produced by the connection builders.
+    #[must_use]
+    pub fn with_profile(self, settings: NewProfileSettings) -> Self {
+        self.lock().add_profile(settings);
+        self
+    }
+
+    /// Makes every activation fail with `error`.
+    #[must_use]
+    pub fn with_activation_error(self, error: ConnectionError) -> Self {
+        self.lock().activation_error = Some(error);
+        self
+    }
+
+    /// Makes the next activation fail with `error`; queued failures are
+    /// used up in order before activations succeed again.
+    #[must_use]
+    pub fn with_activation_failure(self, error: ConnectionError) -> Self {
+        self.lock().activation_failures.push_back(error);
+        self
+    }
+
+    /// Makes the next VPN activation fail with `error`, leaving other
+    /// activations alone.
+    #[must_use]
+    pub fn with_vpn_activation_failure(self, error: ConnectionError) -> Self {
+        self.lock().vpn_activation_failures.push_back(error);
+        self
+    }
+
+    /// Number of scans the device `interface` completed so far.
+    #[must_use]
+    pub fn scans(&self, interface: &str) -> u32 {
+        self.lock()
+            .devices
+            .iter()
+            .find(|d| d.interface == interface)
+            .map_or(0, |d| d.scans)
+    }
+
+    /// Activations performed so far, oldest first.
+    #[must_use]
+    pub fn activations(&self) -> Vec<MockActivation> {
+        self.lock().activations.clone()
+    }
+
+    /// Active connections deactivated so far, oldest first.
+    #[must_use]
+    pub fn deactivations(&self) -> Vec<OwnedObjectPath> {
+        self.lock().deactivated.clone()
+    }
+
+    /// Saved profiles with their settings.
+    #[must_use]
+    pub fn profiles(&self) -> Vec<(OwnedObjectPath, ProfileSettings)> {
+        self.lock()
+            .profiles
+            .iter()
+            .map(|(path, settings)| {
+                let copy = settings
+                    .iter()
+                    .map(|(section, values)| {
+                        let values = values
+                            .iter()
+                            .filter_map(|(k, v)| Some((k.clone(), v.try_clone().ok()?)))
+                            .collect();
+                        (section.clone(), values)
+                    })
+                    .collect();
+                (path.clone(), copy)
+            })
+            .collect()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[async_trait]
+impl NmBackend for MockBackend {
+    async fn get_devices(&self) -> Result<Vec<OwnedObjectPath>> {
+        Ok(self.lock().devices.iter().map(|d| d.path.clone()).collect())
+    }
+
+    async fn device_type(&self, device: &OwnedObjectPath) -> Result<u32> {
+        Ok(self.lock().device(device)?.device_type)
+    }
+
+    async fn device_interface(&self, device: &OwnedObjectPath) -> Result<String> {
+        Ok(self.lock().device(device)?.interface.clone())
+    }
+
+    async fn device_state(&self, device: &OwnedObjectPath) -> Result<u32> {
+        Ok(self.lock().device(device)?.state)
+    }
+
+    async fn device_active_connection(&self, device: &OwnedObjectPath) -> Result<OwnedObjectPath> {
+        let mut s = self.lock();
+        s.device(device)?;
+        Ok(s.activation_on(device)
+            .map(|a| a.active.clone())
+            .unwrap_or_default())
+    }
+
+    async fn device_ip_addresses(
+        &self,
+        device: &OwnedObjectPath,
+    ) -> Result<(Option<String>, Option<String>)> {
+        self.lock().device(device)?;
+        Ok((None, None))
+    }
+
+    async fn disconnect_device(
+        &self,
+        device: &OwnedObjectPath,
+        _timeout: Option<Duration>,
+    ) -> Result<()> {
+        self.lock().device(device)?.state = DeviceState::Disconnected.into();
+        Ok(())
+    }
+
+    async fn access_points(&self, device: &OwnedObjectPath) -> Result<Vec<OwnedObjectPath>> {
+        let mut s = self.lock();
+        let scans = s.device(device)?.scans;
+        Ok(s.access_points
+            .iter()
+            .filter(|entry| &entry.device == device && entry.ap.visible_after_scans <= scans)
+            .map(|entry| entry.path.clone())
+            .collect())
+    }
+
+    async fn active_access_point(&self, device: &OwnedObjectPath) -> Result<OwnedObjectPath> {
+        let mut s = self.lock();
+        s.device(device)?;
+        Ok(s.activation_on(device)
+            .map(|a| &a.specific_object)
+            .filter(|ap| s.access_point(ap).is_ok())
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn last_scan(&self, device: &OwnedObjectPath) -> Result<i64> {
+        match self.lock().device(device)?.scans {
+            0 => Ok(-1),
+            scans => Ok(i64::from(scans) * 1000),
+        }
+    }
+
+    async fn access_point_properties(&self, ap: &OwnedObjectPath) -> Result<ObjectProperties> {
+        let mut s = self.lock();
+        let device = s.access_point_entry(ap)?.device.clone();
+        let last_seen = i32::try_from(s.device(&device)?.scans).unwrap_or(i32::MAX);
+        let ap = s.access_point(ap)?;
+        let props = [
+            ("Ssid", Value::from(ap.ssid.as_bytes().to_vec())),
+            ("HwAddress", Value::from(ap.bssid.clone())),
+            ("Frequency", Value::from(ap.frequency)),
+            ("Strength", Value::from(ap.strength)),
+            ("Flags", Value::from(0u32)),
+            ("WpaFlags", Value::from(0u32)),
+            ("RsnFlags", Value::from(0u32)),
+            ("Mode", Value::from(2u32)),
+            (
+                "LastSeen",
+                Value::from(if last_seen == 0 { -1 } else { last_seen }),
+            ),
+        ];
+        Ok(props
+            .into_iter()
+            .filter_map(|(key, value)| Some((key.to_string(), OwnedValue::try_from(value).ok()?)))
+            .collect())
+    }
+
+    async fn access_point_ssid(&self, ap: &OwnedObjectPath) -> Result<Vec<u8>> {
+        Ok(self.lock().access_point(ap)?.ssid.as_bytes().to_vec())
+    }
+
+    async fn access_point_bssid(&self, ap: &OwnedObjectPath) -> Result<String> {
+        Ok(self.lock().access_point(ap)?.bssid.clone())
+    }
+
+    async fn access_point_frequency(&self, ap: &OwnedObjectPath) -> Result<u32> {
+        Ok(self.lock().access_point(ap)?.frequency)
+    }
+
+    async fn request_scan(
+        &self,
+        device: &OwnedObjectPath,
+        _options: ScanRequestOptions,
+    ) -> Result<()> {
+        self.lock().device(device)?.scans += 1;
+        Ok(())
+    }
+
+    async fn request_scan_and_wait(
+        &self,
+        device: &OwnedObjectPath,
+        _limit: Duration,
+    ) -> Result<bool> {
+        self.request_scan(device, ScanRequestOptions::new()).await?;
+        Ok(true)
+    }
+
+    async fn list_connections(&self) -> Result<Vec<OwnedObjectPath>> {
+        Ok(self
+            .lock()
+            .profiles
+            .iter()
+            .map(|(p, _)| p.clone())
+            .collect())
+    }
+
+    async fn connection_by_uuid(&self, uuid: &str) -> Result<OwnedObjectPath> {
+        self.lock()
+            .profiles
+            .iter()
+            .find(|(_, settings)| {
+                settings
+                    .get("connection")
+                    .and_then(|section| section.get("uuid"))
+                    .and_then(|value| <&str>::try_from(value).ok())
+                    == Some(uuid)
+            })
+            .map(|(path, _)| path.clone())
+            .ok_or(ConnectionError::NotFound)
+    }
+
+    async fn add_connection(&self, settings: NewProfileSettings) -> Result<OwnedObjectPath> {
+        Ok(self.lock().add_profile(settings))
+    }
+
+    async fn connection_settings(&self, connection: &OwnedObjectPath) -> Result<ProfileSettings> {
+        self.profiles()
+            .into_iter()
+            .find(|(p, _)| p == connection)
+            .map(|(_, settings)| settings)
+            .ok_or(ConnectionError::NotFound)
+    }
+
+    async fn activate_connection(
+        &self,
+        connection: &OwnedObjectPath,
+        device: &OwnedObjectPath,
+        specific_object: &OwnedObjectPath,
+    ) -> Result<OwnedObjectPath> {
+        let mut s = self.lock();
+        s.check_device(device)?;
+        if !s.profiles.iter().any(|(p, _)| p == connection) {
+            return Err(ConnectionError::NotFound);
+        }
+        let active = s.next_path("ActiveConnection");
+        s.activations.push(MockActivation {
+            active: active.clone(),
+            connection: connection.clone(),
+            device: device.clone(),
+            specific_object: specific_object.clone(),
+            added: false,
+            options: BTreeMap::new(),
+        });
+        Ok(active)
+    }
+
+    async fn add_and_activate_connection(
+        &self,
+        settings: NewProfileSettings,
+        device: &OwnedObjectPath,
+        specific_object: &OwnedObjectPath,
+    ) -> Result<(OwnedObjectPath, OwnedObjectPath)> {
+        self.add_and_activate_connection2(settings, device, specific_object, Default::default())
+            .await
+    }
+
+    async fn add_and_activate_connection2(
+        &self,
+        settings: NewProfileSettings,
+        device: &OwnedObjectPath,
+        specific_object: &OwnedObjectPath,
+        options: ActivationOptions,
+    ) -> Result<(OwnedObjectPath, OwnedObjectPath)> {
+        let mut s = self.lock();
+        s.check_device(device)?;
+        let connection = s.add_profile(settings);
+        let active = s.next_path("ActiveConnection");
+        s.activations.push(MockActivation {
+            active: active.clone(),
+            connection: connection.clone(),
+            device: device.clone(),
+            specific_object: specific_object.clone(),
+            added: true,
+            options: options
+                .iter()
+                .map(|(k, v)| {
+                    let text = match v {
+                        Value::Str(s) => s.to_string(),
+                        other => other.to_string(),
+                    };
+                    ((*k).to_string(), text)
+                })
+                .collect(),
+        });
+        Ok((connection, active))
+    }
+
+    async fn deactivate_connection(&self, active: &OwnedObjectPath) -> Result<()> {
+        self.lock().deactivated.push(active.clone());
+        Ok(())
+    }
+
+    async fn delete_connection(&self, connection: &OwnedObjectPath) -> Result<()> {
+        let mut s = self.lock();
+        let before = s.profiles.len();
+        s.profiles.retain(|(p, _)| p != connection);
+        if s.profiles.len() == before {
+            return Err(ConnectionError::NotFound);
+        }
+        Ok(())
+    }
+
+    async fn wait_for_activation(
+        &self,
+        active: &OwnedObjectPath,
+        _timeout: Option<Duration>,
+    ) -> Result<()> {
+        let mut s = self.lock();
+        if let Some(error) = s.activation_error.clone() {
+            return Err(error);
+        }
+        if let Some(error) = s.activation_failures.pop_front() {
+            return Err(error);
+        }
+        let device = s
+            .activations
+            .iter()
+            .find(|a| &a.active == active)
+            .map(|a| a.device.clone())
+            .ok_or(ConnectionError::NotFound)?;
+        if device.as_str() != "/" {
+            s.device(&device)?.state = DeviceState::Activated.into();
+        }
+        Ok(())
+    }
+
+    async fn wait_for_vpn_activation(
+        &self,
+        active: &OwnedObjectPath,
+        timeout: Option<Duration>,
+    ) -> Result<()> {
+        if let Some(error) = self.lock().vpn_activation_failures.pop_front() {
+            return Err(error);
+        }
+        self.wait_for_activation(active, timeout).await
+    }
+}
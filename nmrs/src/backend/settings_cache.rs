@@ -0,0 +1,430 @@
+//! [`NmBackend`] that caches saved profile settings.
+//!
+//! Looking a profile up by name means reading the settings of every saved
+//! profile, one `GetSettings` call each, which takes seconds with hundreds
+//! of profiles. [`SettingsCache`] keeps the profile list and the settings
+//! read per profile path, and drops them as NetworkManager reports changes:
+//! `NewConnection` and `ConnectionRemoved` on `Settings`, `Updated` on a
+//! profile, and a daemon restart.
+//!
+//! The change signals are queued by the bus connection and applied before
+//! every read, so a profile changed through any client is never served
+//! stale once its signal has arrived. If the signals cannot be subscribed
+//! to, every read goes to NetworkManager.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::FutureExt;
+use futures::stream::{self, Stream, StreamExt};
+use log::debug;
+use zbus::message::Type as MessageType;
+use zbus::{Connection, MatchRule, MessageStream};
+use zvariant::OwnedObjectPath;
+
+use super::{
+    ActivationOptions, DbusBackend, NewProfileSettings, NmBackend, ObjectProperties,
+    ProfileSettings, ScanRequestOptions,
+};
+use crate::Result;
+use crate::api::models::BssLoad;
+use crate::core::daemon_watch::{NM_BUS_NAME, daemon_events};
+use crate::dbus::NMSettingsProxy;
+
+/// A change that makes cached entries stale.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Invalidation {
+    Added,
+    Removed(OwnedObjectPath),
+    Updated(OwnedObjectPath),
+    Restarted,
+}
+
+type Invalidations = Pin<Box<dyn Stream<Item = Invalidation> + Send>>;
+
+/// [`DbusBackend`] with cached `ListConnections` and `GetSettings` results.
+///
+/// Clones share the cache.
+#[derive(Clone)]
+pub(crate) struct SettingsCache {
+    backend: DbusBackend,
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Default)]
+struct State {
+    /// Pending changes; `None` until subscribed, and nothing is cached then.
+    invalidations: Option<Invalidations>,
+    /// Bumped on every change, so reads that raced one are not stored.
+    generation: u64,
+    paths: Option<Vec<OwnedObjectPath>>,
+    settings: HashMap<OwnedObjectPath, ProfileSettings>,
+}
+
+impl SettingsCache {
+    pub(crate) fn new(conn: Connection) -> Self {
+        Self {
+            backend: DbusBackend::from_connection(conn),
+            state: Arc::default(),
+        }
+    }
+
+    /// Applies pending changes and returns the generation reads are stored
+    /// under, or `None` if changes cannot be tracked.
+    async fn sync(&self) -> Option<u64> {
+        if self.lock().invalidations.is_none() {
+            match invalidations(&self.backend.conn).await {
+                Ok(stream) => {
+                    self.lock().invalidations.get_or_insert(stream);
+                }
+                Err(e) => {
+                    debug!("Not caching saved profiles: {e}");
+                    return None;
+                }
+            }
+        }
+        self.lock().drain()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl fmt::Debug for SettingsCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.lock();
+        f.debug_struct("SettingsCache")
+            .field("tracking", &state.invalidations.is_some())
+            .field("profiles", &state.settings.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl State {
+    /// Applies every queued change; see [`SettingsCache::sync`].
+    fn drain(&mut self) -> Option<u64> {
+        loop {
+            let pending = self.invalidations.as_mut()?.next().now_or_never();
+            match pending {
+                None => return Some(self.generation),
+                Some(Some(change)) => self.apply(change),
+                Some(None) => {
+                    debug!("Saved profile change signals ended, clearing cache");
+                    self.invalidations = None;
+                    self.apply(Invalidation::Restarted);
+                    return None;
+                }
+            }
+        }
+    }
+
+    fn apply(&mut self, change: Invalidation) {
+        self.generation += 1;
+        match change {
+            Invalidation::Added => self.paths = None,
+            Invalidation::Removed(path) => {
+                self.paths = None;
+                self.settings.remove(&path);
+            }
+            Invalidation::Updated(path) => {
+                self.settings.remove(&path);
+            }
+            Invalidation::Restarted => {
+                self.paths = None;
+                self.settings.clear();
+            }
+        }
+    }
+
+    /// Runs `store` unless a change arrived after `generation`.
+    fn store(&mut self, generation: u64, store: impl FnOnce(&mut Self)) {
+        if self.drain() == Some(generation) {
+            store(self);
+        }
+    }
+}
+
+/// Subscribes to every signal that invalidates cached profiles.
+async fn invalidations(conn: &Connection) -> Result<Invalidations> {
+    let settings = NMSettingsProxy::new(conn).await?;
+    let added = settings
+        .receive_new_connection()
+        .await?
+        .map(|_| Invalidation::Added);
+    let removed = settings
+        .receive_connection_removed()
+        .await?
+        .filter_map(
+            |signal| async move { Some(Invalidation::Removed(signal.args().ok()?.connection)) },
+        );
+
+    let rule = MatchRule::builder()
+        .msg_type(MessageType::Signal)
+        .sender(NM_BUS_NAME)?
+        .interface("org.freedesktop.NetworkManager.Settings.Connection")?
+        .member("Updated")?
+        .build();
+    let updated = MessageStream::for_match_rule(rule, conn, None)
+        .await?
+        .filter_map(|msg| async move {
+            let path = msg.ok()?.header().path()?.to_owned();
+            Some(Invalidation::Updated(path.into()))
+        });
+
+    let restarted = daemon_events(conn).await?.map(|_| Invalidation::Restarted);
+
+    let streams: Vec<Invalidations> = vec![
+        Box::pin(added),
+        Box::pin(removed),
+        Box::pin(updated),
+        Box::pin(restarted),
+    ];
+    Ok(Box::pin(stream::select_all(streams)))
+}
+
+#[async_trait]
+impl NmBackend for SettingsCache {
+    async fn get_devices(&self) -> Result<Vec<OwnedObjectPath>> {
+        self.backend.get_devices().await
+    }
+
+    async fn device_type(&self, device: &OwnedObjectPath) -> Result<u32> {
+        self.backend.device_type(device).await
+    }
+
+    async fn device_interface(&self, device: &OwnedObjectPath) -> Result<String> {
+        self.backend.device_interface(device).await
+    }
+
+    async fn device_state(&self, device: &OwnedObjectPath) -> Result<u32> {
+        self.backend.device_state(device).await
+    }
+
+    async fn device_active_connection(&self, device: &OwnedObjectPath) -> Result<OwnedObjectPath> {
+        self.backend.device_active_connection(device).await
+    }
+
+    async fn device_ip_addresses(
+        &self,
+        device: &OwnedObjectPath,
+    ) -> Result<(Option<String>, Option<String>)> {
+        self.backend.device_ip_addresses(device).await
+    }
+
+    async fn disconnect_device(
+        &self,
+        device: &OwnedObjectPath,
+        timeout: Option<Duration>,
+    ) -> Result<()> {
+        self.backend.disconnect_device(device, timeout).await
+    }
+
+    async fn access_points(&self, device: &OwnedObjectPath) -> Result<Vec<OwnedObjectPath>> {
+        self.backend.access_points(device).await
+    }
+
+    async fn active_access_point(&self, device: &OwnedObjectPath) -> Result<OwnedObjectPath> {
+        self.backend.active_access_point(device).await
+    }
+
+    async fn last_scan(&self, device: &OwnedObjectPath) -> Result<i64> {
+        self.backend.last_scan(device).await
+    }
+
+    async fn access_point_properties(&self, ap: &OwnedObjectPath) -> Result<ObjectProperties> {
+        self.backend.access_point_properties(ap).await
+    }
+
+    async fn bss_loads(&self, interface: &str) -> HashMap<String, BssLoad> {
+        self.backend.bss_loads(interface).await
+    }
+
+    async fn access_point_ssid(&self, ap: &OwnedObjectPath) -> Result<Vec<u8>> {
+        self.backend.access_point_ssid(ap).await
+    }
+
+    async fn access_point_bssid(&self, ap: &OwnedObjectPath) -> Result<String> {
+        self.backend.access_point_bssid(ap).await
+    }
+
+    async fn access_point_frequency(&self, ap: &OwnedObjectPath) -> Result<u32> {
+        self.backend.access_point_frequency(ap).await
+    }
+
+    async fn request_scan(
+        &self,
+        device: &OwnedObjectPath,
+        options: ScanRequestOptions,
+    ) -> Result<()> {
+        self.backend.request_scan(device, options).await
+    }
+
+    async fn request_scan_and_wait(
+        &self,
+        device: &OwnedObjectPath,
+        limit: Duration,
+    ) -> Result<bool> {
+        self.backend.request_scan_and_wait(device, limit).await
+    }
+
+    async fn list_connections(&self) -> Result<Vec<OwnedObjectPath>> {
+        let Some(generation) = self.sync().await else {
+            return self.backend.list_connections().await;
+        };
+        if let Some(paths) = self.lock().paths.clone() {
+            return Ok(paths);
+        }
+        let paths = self.backend.list_connections().await?;
+        self.lock()
+            .store(generation, |state| state.paths = Some(paths.clone()));
+        Ok(paths)
+    }
+
+    async fn connection_by_uuid(&self, uuid: &str) -> Result<OwnedObjectPath> {
+        self.backend.connection_by_uuid(uuid).await
+    }
+
+    async fn add_connection(&self, settings: NewProfileSettings) -> Result<OwnedObjectPath> {
+        self.backend.add_connection(settings).await
+    }
+
+    async fn connection_settings(&self, connection: &OwnedObjectPath) -> Result<ProfileSettings> {
+        let Some(generation) = self.sync().await else {
+            return self.backend.connection_settings(connection).await;
+        };
+        if let Some(settings) = self.lock().settings.get(connection).cloned() {
+            return Ok(settings);
+        }
+        let settings = self.backend.connection_settings(connection).await?;
+        self.lock().store(generation, |state| {
+            state.settings.insert(connection.clone(), settings.clone());
+        });
+        Ok(settings)
+    }
+
+    async fn activate_connection(
+        &self,
+        connection: &OwnedObjectPath,
+        device: &OwnedObjectPath,
+        specific_object: &OwnedObjectPath,
+    ) -> Result<OwnedObjectPath> {
+        self.backend
+            .activate_connection(connection, device, specific_object)
+            .await
+    }
+
+    async fn add_and_activate_connection(
+        &self,
+        settings: NewProfileSettings,
+        device: &OwnedObjectPath,
+        specific_object: &OwnedObjectPath,
+    ) -> Result<(OwnedObjectPath, OwnedObjectPath)> {
+        self.backend
+            .add_and_activate_connection(settings, device, specific_object)
+            .await
+    }
+
+    async fn add_and_activate_connection2(
+        &self,
+        settings: NewProfileSettings,
+        device: &OwnedObjectPath,
+        specific_object: &OwnedObjectPath,
+        options: ActivationOptions,
+    ) -> Result<(OwnedObjectPath, OwnedObjectPath)> {
+        self.backend
+            .add_and_activate_connection2(settings, device, specific_object, options)
+            .await
+    }
+
+    async fn deactivate_connection(&self, active: &OwnedObjectPath) -> Result<()> {
+        self.backend.deactivate_connection(active).await
+    }
+
+    async fn delete_connection(&self, connection: &OwnedObjectPath) -> Result<()> {
+        self.backend.delete_connection(connection).await
+    }
+
+    async fn wait_for_activation(
+        &self,
+        active: &OwnedObjectPath,
+        timeout: Option<Duration>,
+    ) -> Result<()> {
+        self.backend.wait_for_activation(active, timeout).await
+    }
+
+    async fn wait_for_vpn_activation(
+        &self,
+        active: &OwnedObjectPath,
+        timeout: Option<Duration>,
+    ) -> Result<()> {
+        self.backend.wait_for_vpn_activation(active, timeout).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(n: u32) -> OwnedObjectPath {
+        OwnedObjectPath::try_from(format!("/org/freedesktop/NetworkManager/Settings/{n}")).unwrap()
+    }
+
+    fn filled(changes: Vec<Invalidation>) -> State {
+        State {
+            invalidations: Some(Box::pin(stream::iter(changes).chain(stream::pending()))),
+            generation: 0,
+            paths: Some(vec![path(1), path(2)]),
+            settings: HashMap::from([(path(1), HashMap::new()), (path(2), HashMap::new())]),
+        }
+    }
+
+    #[test]
+    fn changes_drop_affected_entries() {
+        let mut state = filled(vec![Invalidation::Updated(path(1))]);
+        assert_eq!(state.drain(), Some(1));
+        assert_eq!(state.paths.as_ref().map(Vec::len), Some(2));
+        assert!(!state.settings.contains_key(&path(1)));
+        assert!(state.settings.contains_key(&path(2)));
+
+        let mut state = filled(vec![Invalidation::Removed(path(2)), Invalidation::Added]);
+        assert_eq!(state.drain(), Some(2));
+        assert!(state.paths.is_none());
+        assert!(!state.settings.contains_key(&path(2)));
+
+        let mut state = filled(vec![Invalidation::Restarted]);
+        state.drain();
+        assert!(state.paths.is_none() && state.settings.is_empty());
+    }
+
+    #[test]
+    fn reads_racing_a_change_are_not_stored() {
+        let (tx, rx) = futures::channel::mpsc::unbounded();
+        let mut state = State {
+            invalidations: Some(Box::pin(rx)),
+            ..State::default()
+        };
+        let generation = state.drain().unwrap();
+
+        tx.unbounded_send(Invalidation::Added).unwrap();
+        state.store(generation, |s| s.paths = Some(vec![path(1)]));
+        assert!(state.paths.is_none());
+
+        let generation = state.drain().unwrap();
+        state.store(generation, |s| s.paths = Some(vec![path(1)]));
+        assert_eq!(state.paths, Some(vec![path(1)]));
+    }
+
+    #[test]
+    fn ended_signals_disable_caching() {
+        let mut state = filled(Vec::new());
+        state.invalidations = Some(Box::pin(stream::empty()));
+        assert_eq!(state.drain(), None);
+        assert!(state.invalidations.is_none());
+        assert!(state.paths.is_none() && state.settings.is_empty());
+    }
+}
@@ -0,0 +1,216 @@
+//! Minimal HTTP/1.1 GET for the portal probe.
+//!
+//! Just enough to read a status line, headers and a small body from one
+//! plain-HTTP request; the crate deliberately carries no HTTP client.
+
+use std::io;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/// Replies larger than this are truncated; portals serve small pages.
+const MAX_REPLY: u64 = 64 * 1024;
+
+/// Host, port and path of an `http://` URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Target {
+    pub host: String,
+    pub port: u16,
+    pub path: String,
+}
+
+impl Target {
+    pub fn parse(url: &str) -> Option<Self> {
+        let rest = url.strip_prefix("http://")?;
+        let (authority, path) = match rest.find(['/', '?']) {
+            Some(i) if rest[i..].starts_with('/') => (&rest[..i], rest[i..].to_string()),
+            Some(i) => (&rest[..i], format!("/{}", &rest[i..])),
+            None => (rest, "/".to_string()),
+        };
+        let (host, port) = if let Some(end) = authority.find(']') {
+            let port = match &authority[end + 1..] {
+                "" => 80,
+                p => p.strip_prefix(':')?.parse().ok()?,
+            };
+            (&authority[..=end], port)
+        } else {
+            match authority.split_once(':') {
+                Some((h, p)) => (h, p.parse().ok()?),
+                None => (authority, 80),
+            }
+        };
+        if host.is_empty() || host.contains('@') {
+            return None;
+        }
+        Some(Self {
+            host: host.to_string(),
+            port,
+            path,
+        })
+    }
+
+    fn authority(&self) -> String {
+        if self.port == 80 {
+            self.host.clone()
+        } else {
+            format!("{}:{}", self.host, self.port)
+        }
+    }
+
+    /// Resolves a `Location` value, which may be relative, against this URL.
+    pub fn join(&self, location: &str) -> String {
+        if location.contains("://") {
+            location.to_string()
+        } else if let Some(rest) = location.strip_prefix("//") {
+            format!("http://{rest}")
+        } else if location.starts_with('/') {
+            format!("http://{}{location}", self.authority())
+        } else {
+            let dir = self.path.rsplit_once('/').map_or("", |(d, _)| d);
+            format!("http://{}{dir}/{location}", self.authority())
+        }
+    }
+}
+
+/// Sends `GET` and returns the raw reply, read until the server closes.
+pub(super) async fn get(target: &Target) -> io::Result<Vec<u8>> {
+    let host = target.host.trim_start_matches('[').trim_end_matches(']');
+    let mut stream = TcpStream::connect((host, target.port)).await?;
+    let request = format!(
+        "GET {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: nmrs\r\nAccept: */*\r\nConnection: close\r\n\r\n",
+        target.path,
+        target.authority()
+    );
+    stream.write_all(request.as_bytes()).await?;
+
+    let mut raw = Vec::new();
+    stream.take(MAX_REPLY).read_to_end(&mut raw).await?;
+    Ok(raw)
+}
+
+/// A parsed reply.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Response {
+    pub status: u16,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Response {
+    pub fn parse(raw: &[u8]) -> Option<Self> {
+        let split = raw.windows(4).position(|w| w == b"\r\n\r\n")?;
+        let head = std::str::from_utf8(&raw[..split]).ok()?;
+        let mut lines = head.split("\r\n");
+
+        let status = lines
+            .next()?
+            .strip_prefix("HTTP/1.")?
+            .split_whitespace()
+            .nth(1)?
+            .parse()
+            .ok()?;
+        let headers: Vec<(String, String)> = lines
+            .filter_map(|l| l.split_once(':'))
+            .map(|(k, v)| (k.trim().to_ascii_lowercase(), v.trim().to_string()))
+            .collect();
+
+        let mut reply = Self {
+            status,
+            headers,
+            body: raw[split + 4..].to_vec(),
+        };
+        if reply
+            .header("transfer-encoding")
+            .is_some_and(|v| v.eq_ignore_ascii_case("chunked"))
+        {
+            reply.body = dechunk(&reply.body)?;
+        }
+        Some(reply)
+    }
+
+    /// First value of header `name` (lowercase).
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn body_text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+}
+
+/// Decodes a chunked body up to its last chunk.
+///
+/// Returns `None` for an unreadable chunk size or a chunk running past the
+/// end of the reply; the sizes come from an untrusted server.
+fn dechunk(mut data: &[u8]) -> Option<Vec<u8>> {
+    let mut out = Vec::new();
+    while let Some(eol) = data.windows(2).position(|w| w == b"\r\n") {
+        let size = std::str::from_utf8(&data[..eol]).ok()?.split(';').next()?;
+        let size = usize::from_str_radix(size.trim(), 16).ok()?;
+        if size == 0 {
+            break;
+        }
+        let start = eol + 2;
+        let end = start.checked_add(size).filter(|&end| end <= data.len())?;
+        out.extend_from_slice(&data[start..end]);
+        data = data.get(end + 2..).unwrap_or_default();
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_targets() {
+        let t = Target::parse("http://example.com:8080/a/b?c=d").unwrap();
+        assert_eq!(
+            (t.host.as_str(), t.port, t.path.as_str()),
+            ("example.com", 8080, "/a/b?c=d")
+        );
+
+        let t = Target::parse("http://example.com").unwrap();
+        assert_eq!((t.port, t.path.as_str()), (80, "/"));
+
+        let t = Target::parse("http://[::1]:81/x").unwrap();
+        assert_eq!((t.host.as_str(), t.port), ("[::1]", 81));
+        assert_eq!(Target::parse("http://[fe80::1]/").unwrap().port, 80);
+
+        assert!(Target::parse("https://example.com/").is_none());
+        assert!(Target::parse("http://user@example.com/").is_none());
+    }
+
+    #[test]
+    fn joins_relative_locations() {
+        let t = Target::parse("http://example.com/dir/page").unwrap();
+        assert_eq!(t.join("other"), "http://example.com/dir/other");
+        assert_eq!(t.join("//cdn.example/x"), "http://cdn.example/x");
+    }
+
+    #[test]
+    fn decodes_chunked_body() {
+        let raw = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhello\r\n6\r\n world\r\n0\r\n\r\n";
+        let reply = Response::parse(raw).unwrap();
+        assert_eq!(reply.body_text(), "hello world");
+    }
+
+    #[test]
+    fn rejects_malformed_chunks() {
+        assert_eq!(dechunk(b"ffffffffffffffff\r\nhello\r\n0\r\n\r\n"), None);
+        assert_eq!(dechunk(b"5\r\nhello\r\nff\r\ntruncated"), None);
+        assert_eq!(dechunk(b"zz\r\nhello\r\n"), None);
+
+        let raw = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n10000\r\nshort\r\n";
+        assert!(Response::parse(raw).is_none());
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert!(Response::parse(b"not http at all").is_none());
+        assert!(Response::parse(b"SSH-2.0-OpenSSH\r\n\r\n").is_none());
+    }
+}
@@ -0,0 +1,224 @@
+//! Captive portal detection and login-page discovery.
+//!
+//! Enabled with the `captive-portal` feature. NetworkManager reports [`ConnectivityState::Portal`](crate::ConnectivityState::Portal)
+//! when its connectivity check is intercepted, but rarely knows where the
+//! login page is. A [`PortalProbe`](crate::captive_portal::PortalProbe)
+//! fetches a known plain-HTTP URL itself and follows what the portal does
+//! with the request: a redirect yields the login page, a rewritten body
+//! yields the probe URL (opening it in a browser lands on the portal).
+//!
+//! Use it through
+//! [`NetworkManager::detect_captive_portal`](crate::NetworkManager::detect_captive_portal)
+//! or
+//! [`NetworkManager::connect_checking_portal`](crate::NetworkManager::connect_checking_portal),
+//! which turns a portal into
+//! [`ConnectionError::CaptivePortal`](crate::ConnectionError::CaptivePortal).
+//!
+//! # Example
+//!
+//! ```no_run
+//! use nmrs::captive_portal::PortalProbe;
+//!
+//! # async fn example() -> nmrs::Result<()> {
+//! let probe = PortalProbe::default();
+//! if let Some(url) = probe.resolve().await? {
+//!     println!("log in at {url}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+mod http;
+
+use std::pin::pin;
+use std::time::Duration;
+
+use futures::future::{self, Either};
+use futures_timer::Delay;
+use log::debug;
+
+use crate::Result;
+use crate::api::models::ConnectionError;
+
+/// URL NetworkManager itself probes on most distributions.
+pub const DEFAULT_PROBE_URL: &str = "http://nmcheck.gnome.org/check_network_status.txt";
+
+/// Body served at [`DEFAULT_PROBE_URL`] when the network is not intercepted.
+pub const DEFAULT_EXPECTED_BODY: &str = "NetworkManager is online";
+
+/// A configurable HTTP probe for captive portals.
+///
+/// Only `http://` URLs are supported: portals cannot intercept TLS without
+/// breaking it, so an HTTPS probe would just fail.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortalProbe {
+    /// URL fetched by the probe.
+    pub url: String,
+    /// Body the URL serves when not intercepted. `None` accepts any 2xx reply.
+    pub expected_body: Option<String>,
+    /// Limit for connecting and reading the reply.
+    pub timeout: Duration,
+}
+
+impl Default for PortalProbe {
+    fn default() -> Self {
+        Self {
+            url: DEFAULT_PROBE_URL.into(),
+            expected_body: Some(DEFAULT_EXPECTED_BODY.into()),
+            timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+impl PortalProbe {
+    /// Probe fetching `url`, accepting any 2xx reply as "online".
+    ///
+    /// Suits `generate_204`-style endpoints; use
+    /// [`with_expected_body`](Self::with_expected_body) for endpoints that
+    /// serve fixed text.
+    #[must_use]
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            expected_body: None,
+            ..Self::default()
+        }
+    }
+
+    /// Treats any reply whose body differs from `body` (ignoring surrounding
+    /// whitespace) as intercepted.
+    #[must_use]
+    pub fn with_expected_body(mut self, body: impl Into<String>) -> Self {
+        self.expected_body = Some(body.into());
+        self
+    }
+
+    /// Sets the connect and read timeout.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Fetches the probe URL and returns the portal login URL, if any.
+    ///
+    /// Returns `Ok(None)` when the reply looks untouched.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConnectionError::InvalidInput`] for a URL that is not plain
+    /// `http://`, and [`ConnectionError::PortalProbeFailed`] when the
+    /// request cannot be completed (no route, DNS failure, timeout).
+    pub async fn resolve(&self) -> Result<Option<String>> {
+        let target =
+            http::Target::parse(&self.url).ok_or_else(|| ConnectionError::InvalidInput {
+                field: "url".into(),
+                reason: format!("'{}' is not a plain http:// URL", self.url),
+            })?;
+
+        let raw = match future::select(pin!(http::get(&target)), Delay::new(self.timeout)).await {
+            Either::Left((raw, _)) => {
+                raw.map_err(|e| ConnectionError::PortalProbeFailed(format!("{}: {e}", self.url)))?
+            }
+            Either::Right(_) => {
+                return Err(ConnectionError::PortalProbeFailed(format!(
+                    "{} timed out",
+                    self.url
+                )));
+            }
+        };
+
+        let reply = http::Response::parse(&raw).ok_or_else(|| {
+            ConnectionError::PortalProbeFailed(format!("{}: malformed HTTP reply", self.url))
+        })?;
+        debug!("Portal probe {} answered {}", self.url, reply.status);
+
+        Ok(self.classify(&target, &reply))
+    }
+
+    fn classify(&self, target: &http::Target, reply: &http::Response) -> Option<String> {
+        match reply.status {
+            300..=399 => Some(
+                reply
+                    .header("location")
+                    .map_or_else(|| self.url.clone(), |loc| target.join(loc)),
+            ),
+            200..=299 => match &self.expected_body {
+                Some(expected) if reply.body_text().trim() != expected.trim() => {
+                    Some(self.url.clone())
+                }
+                _ => None,
+            },
+            // 511 Network Authentication Required (RFC 6585).
+            511 => Some(self.url.clone()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn classify(probe: &PortalProbe, raw: &str) -> Option<String> {
+        let target = http::Target::parse(&probe.url).unwrap();
+        probe.classify(&target, &http::Response::parse(raw.as_bytes()).unwrap())
+    }
+
+    #[test]
+    fn untouched_reply_is_online() {
+        let raw = "HTTP/1.1 200 OK\r\nContent-Length: 25\r\n\r\nNetworkManager is online\n";
+        assert_eq!(classify(&PortalProbe::default(), raw), None);
+    }
+
+    #[test]
+    fn redirect_yields_location() {
+        let raw = "HTTP/1.1 302 Found\r\nLocation: https://login.hotel.example/?r=1\r\n\r\n";
+        assert_eq!(
+            classify(&PortalProbe::default(), raw).as_deref(),
+            Some("https://login.hotel.example/?r=1")
+        );
+    }
+
+    #[test]
+    fn relative_redirect_is_resolved_against_probe() {
+        let raw = "HTTP/1.1 303 See Other\r\nLocation: /portal/login\r\n\r\n";
+        assert_eq!(
+            classify(&PortalProbe::default(), raw).as_deref(),
+            Some("http://nmcheck.gnome.org/portal/login")
+        );
+    }
+
+    #[test]
+    fn rewritten_body_yields_probe_url() {
+        let raw = "HTTP/1.1 200 OK\r\n\r\n<html>Welcome to Airport Wi-Fi</html>";
+        assert_eq!(
+            classify(&PortalProbe::default(), raw).as_deref(),
+            Some(DEFAULT_PROBE_URL)
+        );
+    }
+
+    #[test]
+    fn any_success_accepted_without_expected_body() {
+        let probe = PortalProbe::new("http://connectivity.example/generate_204");
+        assert_eq!(classify(&probe, "HTTP/1.1 204 No Content\r\n\r\n"), None);
+        assert_eq!(
+            classify(
+                &probe,
+                "HTTP/1.1 511 Network Authentication Required\r\n\r\n"
+            )
+            .as_deref(),
+            Some("http://connectivity.example/generate_204")
+        );
+    }
+
+    #[tokio::test]
+    async fn https_probe_is_rejected() {
+        let err = PortalProbe::new("https://example.com/")
+            .resolve()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ConnectionError::InvalidInput { .. }));
+    }
+}
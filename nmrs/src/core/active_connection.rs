@@ -0,0 +1,198 @@
+//! Typed reads of NetworkManager's active connections.
+//!
+//! Each `Connection.Active` object is read with one `GetAll` call, the
+//! objects concurrently. The primary connection is the one NetworkManager
+//! treats as the default uplink.
+
+use std::collections::HashMap;
+
+use futures::stream::{self, Stream, StreamExt};
+use log::debug;
+use zbus::Connection;
+use zbus::fdo::PropertiesProxy;
+use zbus::names::InterfaceName;
+use zbus::proxy::CacheProperties;
+use zvariant::{OwnedObjectPath, OwnedValue};
+
+use crate::Result;
+use crate::api::models::{ActiveConnection, PrimaryConnection, UplinkKind};
+use crate::core::daemon_watch::NM_BUS_NAME;
+use crate::dbus::{NMProxy, dbus_operation};
+use crate::util::utils::property;
+
+/// D-Bus interface of NetworkManager active connection objects.
+pub(crate) const ACTIVE_INTERFACE: &str = "org.freedesktop.NetworkManager.Connection.Active";
+
+/// Active connections read concurrently.
+const IN_FLIGHT: usize = 16;
+
+/// Lists the active connections in NetworkManager's order.
+///
+/// Connections that go away between listing and reading are left out.
+pub(crate) async fn list_active_connections(conn: &Connection) -> Result<Vec<ActiveConnection>> {
+    let nm = NMProxy::new(conn).await?;
+    let paths = nm
+        .active_connections()
+        .await
+        .map_err(|e| dbus_operation("read ActiveConnections property", e))?;
+
+    Ok(stream::iter(paths)
+        .map(|path| read_active_connection(conn, path))
+        .buffered(IN_FLIGHT)
+        .filter_map(|active| async move { active })
+        .collect()
+        .await)
+}
+
+/// Reads the primary connection, or `None` if there is none.
+pub(crate) async fn primary_connection(conn: &Connection) -> Result<Option<PrimaryConnection>> {
+    let nm = NMProxy::new(conn).await?;
+    let path = nm
+        .primary_connection()
+        .await
+        .map_err(|e| dbus_operation("read PrimaryConnection property", e))?;
+    Ok(primary_at(conn, &nm, path).await)
+}
+
+/// Streams the primary connection, starting with the current one, and
+/// again whenever NetworkManager picks another one. `None` items mean
+/// there is no primary connection.
+pub(crate) async fn watch_primary_connection(
+    conn: &Connection,
+) -> Result<impl Stream<Item = Option<PrimaryConnection>> + Send + Unpin + 'static> {
+    let nm = NMProxy::new(conn).await?;
+    // Not skipped: the first item is the current value.
+    let changes = nm.receive_primary_connection_changed().await;
+
+    let updates = stream::unfold(
+        (conn.clone(), nm, changes, None::<OwnedObjectPath>),
+        |(conn, nm, mut changes, mut last)| async move {
+            loop {
+                let Ok(path) = changes.next().await?.get().await else {
+                    continue;
+                };
+                if last.as_ref() == Some(&path) {
+                    continue;
+                }
+                let primary = primary_at(&conn, &nm, path.clone()).await;
+                last = Some(path);
+                return Some((primary, (conn, nm, changes, last)));
+            }
+        },
+    );
+    Ok(Box::pin(updates))
+}
+
+/// The primary connection if `path` is one; `None` for `/` or a connection
+/// that went away.
+async fn primary_at(
+    conn: &Connection,
+    nm: &NMProxy<'_>,
+    path: OwnedObjectPath,
+) -> Option<PrimaryConnection> {
+    if path.as_str() == "/" {
+        return None;
+    }
+    let connection = read_active_connection(conn, path).await?;
+    let conn_type = match nm.primary_connection_type().await {
+        Ok(conn_type) if !conn_type.is_empty() => conn_type,
+        _ => connection.conn_type.clone(),
+    };
+    Some(PrimaryConnection {
+        kind: UplinkKind::from(conn_type.as_str()),
+        connection,
+    })
+}
+
+async fn read_active_connection(
+    conn: &Connection,
+    path: OwnedObjectPath,
+) -> Option<ActiveConnection> {
+    let read = async {
+        PropertiesProxy::builder(conn)
+            .destination(NM_BUS_NAME)?
+            .path(path.clone())?
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await?
+            .get_all(InterfaceName::from_static_str_unchecked(ACTIVE_INTERFACE))
+            .await
+            .map_err(zbus::Error::from)
+    };
+    match read.await {
+        Ok(props) => Some(active_connection_from_properties(path, &props)),
+        Err(e) => {
+            debug!("Skipping active connection {}: {e}", path.as_str());
+            None
+        }
+    }
+}
+
+/// Builds an [`ActiveConnection`] from its `Connection.Active` properties.
+/// Properties NetworkManager leaves out read as zero or empty.
+pub(crate) fn active_connection_from_properties(
+    path: OwnedObjectPath,
+    props: &HashMap<String, OwnedValue>,
+) -> ActiveConnection {
+    let flag = |key: &str| property(props, key).unwrap_or(false);
+    ActiveConnection {
+        path,
+        connection: property(props, "Connection").unwrap_or_default(),
+        id: property(props, "Id").unwrap_or_default(),
+        uuid: property(props, "Uuid").unwrap_or_default(),
+        conn_type: property(props, "Type").unwrap_or_default(),
+        state: property::<u32>(props, "State").unwrap_or(0).into(),
+        devices: property(props, "Devices").unwrap_or_default(),
+        default: flag("Default"),
+        default6: flag("Default6"),
+        vpn: flag("Vpn"),
+        specific_object: property::<OwnedObjectPath>(props, "SpecificObject")
+            .filter(|p| p.as_str() != "/"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ActiveConnectionState;
+    use zvariant::Value;
+
+    fn path(p: &str) -> OwnedObjectPath {
+        OwnedObjectPath::try_from(p).unwrap()
+    }
+
+    #[test]
+    fn active_connection_from_get_all_properties() {
+        let props: HashMap<String, OwnedValue> = [
+            ("Id", Value::from("Office VPN")),
+            ("Uuid", Value::from("5e3b7c1a-0000-4000-8000-000000000001")),
+            ("Type", Value::from("vpn")),
+            ("State", Value::from(1u32)),
+            ("Vpn", Value::from(true)),
+            ("Default6", Value::from(true)),
+            ("SpecificObject", Value::from(path("/"))),
+            (
+                "Connection",
+                Value::from(path("/org/freedesktop/NetworkManager/Settings/4")),
+            ),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), OwnedValue::try_from(v).unwrap()))
+        .collect();
+
+        let active = active_connection_from_properties(
+            path("/org/freedesktop/NetworkManager/ActiveConnection/9"),
+            &props,
+        );
+        assert_eq!(active.id, "Office VPN");
+        assert_eq!(active.conn_type, "vpn");
+        assert_eq!(active.state, ActiveConnectionState::Activating);
+        assert!(active.vpn && active.default6 && !active.default);
+        assert_eq!(active.specific_object, None);
+        assert!(active.devices.is_empty());
+        assert_eq!(
+            active.connection,
+            path("/org/freedesktop/NetworkManager/Settings/4")
+        );
+    }
+}
@@ -148,6 +148,12 @@ pub(crate) async fn set_wireless_enabled(conn: &Connection, enabled: bool) -> Re
     Ok(nm.set_wireless_enabled(enabled).await?)
 }
 
+/// Reads the WWAN software toggle.
+pub(crate) async fn wwan_enabled(conn: &Connection) -> Result<bool> {
+    let nm = NMProxy::new(conn).await?;
+    Ok(nm.wwan_enabled().await?)
+}
+
 /// Enables or disables WWAN radio (software toggle).
 pub(crate) async fn set_wwan_enabled(conn: &Connection, enabled: bool) -> Result<()> {
     let nm = NMProxy::new(conn).await?;
@@ -295,6 +301,45 @@ pub(crate) async fn set_airplane_mode(conn: &Connection, enabled: bool) -> Resul
     finalize_airplane_toggle_results(wifi_res, wwan_res, bt_res, allow_nonfatal_bt_toggle_failed)
 }
 
+/// Switches back on the radios that were enabled in `before`, leaving the
+/// others off. Uses the same error semantics as [`set_airplane_mode`].
+pub(crate) async fn restore_radios(conn: &Connection, before: &AirplaneModeState) -> Result<()> {
+    let [wifi, wwan, bluetooth] = radios_to_restore(before);
+    let allow_nonfatal_bt_toggle_failed = wifi || wwan;
+
+    let (wifi_res, wwan_res, bt_res) = futures::future::join3(
+        async {
+            if wifi {
+                set_wireless_enabled(conn, true).await
+            } else {
+                Ok(())
+            }
+        },
+        async {
+            if wwan {
+                set_wwan_enabled(conn, true).await
+            } else {
+                Ok(())
+            }
+        },
+        async {
+            if bluetooth {
+                set_bluetooth_radio_enabled(conn, true).await
+            } else {
+                Ok(())
+            }
+        },
+    )
+    .await;
+
+    finalize_airplane_toggle_results(wifi_res, wwan_res, bt_res, allow_nonfatal_bt_toggle_failed)
+}
+
+/// Wi-Fi, WWAN and Bluetooth: whether each was on (and present) in `before`.
+fn radios_to_restore(before: &AirplaneModeState) -> [bool; 3] {
+    [before.wifi, before.wwan, before.bluetooth].map(|radio| radio.present && radio.enabled)
+}
+
 // Applies aggregate airplane-mode error semantics after all three toggle attempts complete.
 fn finalize_airplane_toggle_results(
     wifi_res: Result<()>,
@@ -419,8 +464,19 @@ async fn wait_for_powered_no_timeout(proxy: &BluezAdapterProxy<'_>, target: bool
 
 #[cfg(test)]
 mod tests {
-    use super::finalize_airplane_toggle_results;
+    use super::{finalize_airplane_toggle_results, radios_to_restore};
     use crate::ConnectionError;
+    use crate::api::models::{AirplaneModeState, RadioState};
+
+    #[test]
+    fn restores_only_radios_that_were_on() {
+        let before = AirplaneModeState::new(
+            RadioState::new(true, true),
+            RadioState::new(false, true),
+            RadioState::with_presence(true, true, false),
+        );
+        assert_eq!(radios_to_restore(&before), [true, false, false]);
+    }
 
     #[test]
     fn aggregate_toggle_treats_bluetooth_toggle_failed_as_non_fatal() {
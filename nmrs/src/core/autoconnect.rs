@@ -0,0 +1,467 @@
+//! Autoconnect state of saved profiles.
+//!
+//! NetworkManager stops auto-activating a profile after the user
+//! disconnects its device or after repeated failures, without exposing why
+//! over D-Bus. These helpers reconstruct the likely reason and undo the
+//! block.
+//!
+//! It also edits the `connection.autoconnect*` keys of existing profiles,
+//! reports the order NetworkManager tries them in, and picks the best saved
+//! Wi-Fi network in range to connect to.
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use log::debug;
+use zbus::Connection;
+use zvariant::{OwnedObjectPath, OwnedValue};
+
+use crate::Result;
+use crate::api::models::access_point::{AccessPoint, ApMode};
+use crate::api::models::{
+    AutoconnectBlockReason, AutoconnectStatus, ConnectionError, MatchOptions, SavedConnection,
+    SettingsPatch, SettingsSummary, TimeoutConfig,
+};
+use crate::core::connection_settings::get_saved_connection_path_with;
+use crate::core::saved_connection::{
+    fetch_one_brief, list_saved_connections, update_saved_connection,
+};
+use crate::core::scan::{list_access_points, wait_for_fresh_scan};
+use crate::core::state_wait::wait_for_connection_activation;
+use crate::dbus::{
+    NMActiveConnectionProxy, NMDeviceProxy, NMProxy, NMSettingsConnectionProxy, dbus_operation,
+};
+use crate::types::constants::{device_state, device_state_reason, frequency};
+
+/// Reports whether the saved profile matching `name` is blocked from
+/// autoconnecting, and why.
+pub(crate) async fn autoconnect_status(
+    conn: &Connection,
+    name: &str,
+    opts: &MatchOptions,
+) -> Result<AutoconnectStatus> {
+    let path = resolve_profile(conn, name, opts).await?;
+    let settings = settings_proxy(conn, &path)
+        .await?
+        .get_settings()
+        .await
+        .map_err(|e| dbus_operation(format!("failed to get settings for {}", path.as_str()), e))?;
+
+    let section = settings.get("connection");
+    let id = section
+        .and_then(|s| string_value(s, "id"))
+        .unwrap_or_else(|| name.to_string());
+    let uuid = section
+        .and_then(|s| string_value(s, "uuid"))
+        .unwrap_or_default();
+    let profile_autoconnect = section
+        .and_then(|s| s.get("autoconnect"))
+        .and_then(|v| bool::try_from(v.clone()).ok())
+        .unwrap_or(true);
+
+    let mut active = false;
+    let mut device_block = None;
+
+    let nm = NMProxy::new(conn).await?;
+    for dev_path in nm.get_devices().await? {
+        let Ok(dev) = NMDeviceProxy::builder(conn)
+            .path(dev_path.clone())?
+            .build()
+            .await
+        else {
+            continue;
+        };
+        let Ok(available) = dev.available_connections().await else {
+            continue;
+        };
+        if !available.contains(&path) {
+            continue;
+        }
+
+        if is_active_on(conn, &dev, &path).await {
+            active = true;
+            continue;
+        }
+
+        if device_block.is_none() {
+            let device_autoconnect = dev.autoconnect().await.unwrap_or(true);
+            let (state, reason) = dev.state_reason().await.unwrap_or((0, 0));
+            if let Some(r) = device_block_reason(device_autoconnect, state, reason) {
+                device_block = Some((r, dev.interface().await.ok()));
+            }
+        }
+    }
+
+    let (blocked, interface) = if !profile_autoconnect {
+        (Some(AutoconnectBlockReason::Disabled), None)
+    } else if active {
+        (None, None)
+    } else {
+        match device_block {
+            Some((r, iface)) => (Some(r), iface),
+            None => (None, None),
+        }
+    };
+
+    Ok(AutoconnectStatus {
+        id,
+        uuid,
+        active,
+        blocked,
+        interface,
+    })
+}
+
+/// Clears failure and user-disconnect blocks on the profile matching `name`.
+///
+/// Re-saves the profile unchanged, which makes NetworkManager reset its
+/// autoconnect retry counter, and turns device autoconnect back on where
+/// a user disconnect turned it off. `connection.autoconnect` itself is
+/// left alone.
+pub(crate) async fn clear_autoconnect_block(
+    conn: &Connection,
+    name: &str,
+    opts: &MatchOptions,
+) -> Result<()> {
+    let path = resolve_profile(conn, name, opts).await?;
+    let proxy = settings_proxy(conn, &path).await?;
+
+    let settings = proxy
+        .get_settings()
+        .await
+        .map_err(|e| dbus_operation(format!("failed to get settings for {}", path.as_str()), e))?;
+    let resave = if proxy.unsaved().await.unwrap_or(false) {
+        proxy.update_unsaved(settings).await
+    } else {
+        proxy.update(settings).await
+    };
+    resave.map_err(|e| dbus_operation(format!("failed to re-save {}", path.as_str()), e))?;
+
+    let nm = NMProxy::new(conn).await?;
+    for dev_path in nm.get_devices().await? {
+        let dev = NMDeviceProxy::builder(conn)
+            .path(dev_path.clone())?
+            .build()
+            .await?;
+        let available = dev.available_connections().await.unwrap_or_default();
+        if available.contains(&path) && !dev.autoconnect().await.unwrap_or(true) {
+            dev.set_autoconnect(true).await.map_err(|e| {
+                dbus_operation(
+                    format!("failed to enable autoconnect on {}", dev_path.as_str()),
+                    e,
+                )
+            })?;
+            debug!("Re-enabled autoconnect on {}", dev_path.as_str());
+        }
+    }
+
+    Ok(())
+}
+
+/// Applies `patch` to the saved profile matching `name`.
+///
+/// Used for the `connection.autoconnect` and `autoconnect-priority` keys;
+/// other keys of the profile are preserved.
+pub(crate) async fn update_autoconnect(
+    conn: &Connection,
+    name: &str,
+    opts: &MatchOptions,
+    patch: &SettingsPatch,
+) -> Result<()> {
+    let path = resolve_profile(conn, name, opts).await?;
+    let brief = fetch_one_brief(conn, path)
+        .await?
+        .ok_or(ConnectionError::NoSavedConnection)?;
+    update_saved_connection(conn, &brief.uuid, patch).await?;
+    debug!("Updated autoconnect settings of '{}'", brief.id);
+    Ok(())
+}
+
+/// Saved profiles with autoconnect enabled, in the order NetworkManager
+/// tries them: highest priority first, then most recently used.
+pub(crate) async fn autoconnect_order(conn: &Connection) -> Result<Vec<SavedConnection>> {
+    let mut profiles = list_saved_connections(conn).await?;
+    profiles.retain(|p| p.autoconnect);
+    profiles.sort_by_key(|p| Reverse((p.autoconnect_priority, p.timestamp_unix)));
+    Ok(profiles)
+}
+
+/// Scans, then activates the best saved Wi-Fi network in range.
+///
+/// Returns the activated profile. Nothing is activated if the best
+/// network is already connected.
+pub(crate) async fn connect_best_available(
+    conn: &Connection,
+    interface: Option<&str>,
+    timeout_config: Option<TimeoutConfig>,
+) -> Result<SavedConnection> {
+    wait_for_fresh_scan(conn, interface).await?;
+    let aps = list_access_points(conn, interface).await?;
+    let profiles = list_saved_connections(conn).await?;
+
+    let (ap, profile) = best_available(&aps, &profiles).ok_or(ConnectionError::NotFound)?;
+    if ap.is_active {
+        debug!("Best network '{}' is already connected", ap.ssid);
+        return Ok(profile.clone());
+    }
+
+    debug!(
+        "Activating '{}' for '{}' ({} MHz, {}%) on {}",
+        profile.id, ap.ssid, ap.frequency_mhz, ap.strength, ap.interface
+    );
+    let active = NMProxy::new(conn)
+        .await?
+        .activate_connection(
+            profile.path.clone(),
+            ap.device_path.clone(),
+            ap.path.clone(),
+        )
+        .await?;
+    let timeout = timeout_config.map(|c| c.connection_timeout);
+    wait_for_connection_activation(conn, &active, timeout).await?;
+    Ok(profile.clone())
+}
+
+/// Picks the access point and saved profile to connect to.
+///
+/// Only infrastructure access points with an autoconnect-enabled Wi-Fi
+/// profile for their SSID (and, if the profile is bound to one, their
+/// interface) count. They are ranked by profile priority, then signal
+/// strength, then band, preferring 5 and 6 GHz.
+fn best_available<'a>(
+    aps: &'a [AccessPoint],
+    profiles: &'a [SavedConnection],
+) -> Option<(&'a AccessPoint, &'a SavedConnection)> {
+    aps.iter()
+        .filter(|ap| ap.mode == ApMode::Infrastructure && !ap.ssid.trim().is_empty())
+        .filter_map(|ap| {
+            let profile = profiles
+                .iter()
+                .filter(|p| p.autoconnect && joins(p, ap))
+                .max_by_key(|p| (p.autoconnect_priority, p.timestamp_unix))?;
+            Some((ap, profile))
+        })
+        .max_by_key(|(ap, profile)| {
+            let fast_band = ap.frequency_mhz >= frequency::BAND_5_START;
+            (profile.autoconnect_priority, ap.strength, fast_band)
+        })
+}
+
+/// Whether `profile` is a client profile for `ap`'s network and device.
+fn joins(profile: &SavedConnection, ap: &AccessPoint) -> bool {
+    let SettingsSummary::Wifi { ssid, mode, .. } = &profile.summary else {
+        return false;
+    };
+    *ssid == ap.ssid
+        && mode.as_deref().is_none_or(|m| m == "infrastructure")
+        && profile
+            .interface_name
+            .as_deref()
+            .is_none_or(|iface| iface == ap.interface)
+}
+
+/// Device-level block reason from its `Autoconnect` flag and `StateReason`.
+pub(crate) fn device_block_reason(
+    device_autoconnect: bool,
+    state: u32,
+    reason: u32,
+) -> Option<AutoconnectBlockReason> {
+    use device_state_reason::*;
+
+    if !device_autoconnect {
+        return Some(AutoconnectBlockReason::UserDisconnected);
+    }
+    if state != device_state::DISCONNECTED && state != device_state::FAILED {
+        return None;
+    }
+    match reason {
+        NO_SECRETS => Some(AutoconnectBlockReason::NoSecrets),
+        CONFIG_FAILED
+        | IP_CONFIG_UNAVAILABLE
+        | SUPPLICANT_CONFIG_FAILED
+        | SUPPLICANT_FAILED
+        | SUPPLICANT_TIMEOUT
+        | DHCP_START_FAILED
+        | DHCP_ERROR
+        | DHCP_FAILED
+        | SSID_NOT_FOUND => Some(AutoconnectBlockReason::Failed),
+        _ => None,
+    }
+}
+
+async fn resolve_profile(
+    conn: &Connection,
+    name: &str,
+    opts: &MatchOptions,
+) -> Result<OwnedObjectPath> {
+    get_saved_connection_path_with(conn, name, opts)
+        .await?
+        .ok_or(ConnectionError::NoSavedConnection)
+}
+
+async fn settings_proxy<'a>(
+    conn: &'a Connection,
+    path: &OwnedObjectPath,
+) -> Result<NMSettingsConnectionProxy<'a>> {
+    NMSettingsConnectionProxy::builder(conn)
+        .path(path.clone())
+        .map_err(ConnectionError::Dbus)?
+        .build()
+        .await
+        .map_err(|e| dbus_operation("failed to build Settings.Connection proxy", e))
+}
+
+async fn is_active_on(conn: &Connection, dev: &NMDeviceProxy<'_>, path: &OwnedObjectPath) -> bool {
+    let Ok(ac_path) = dev.active_connection().await else {
+        return false;
+    };
+    if ac_path.as_str() == "/" {
+        return false;
+    }
+    let Ok(builder) = NMActiveConnectionProxy::builder(conn).path(ac_path) else {
+        return false;
+    };
+    match builder.build().await {
+        Ok(ac) => ac.connection().await.is_ok_and(|p| &p == path),
+        Err(_) => false,
+    }
+}
+
+fn string_value(section: &HashMap<String, OwnedValue>, key: &str) -> Option<String> {
+    String::try_from(section.get(key)?.clone()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn user_disconnect_wins_over_state() {
+        assert_eq!(
+            device_block_reason(false, device_state::ACTIVATED, 0),
+            Some(AutoconnectBlockReason::UserDisconnected)
+        );
+    }
+
+    #[test]
+    fn no_secrets_and_failures_only_when_disconnected() {
+        assert_eq!(
+            device_block_reason(
+                true,
+                device_state::DISCONNECTED,
+                device_state_reason::NO_SECRETS
+            ),
+            Some(AutoconnectBlockReason::NoSecrets)
+        );
+        assert_eq!(
+            device_block_reason(
+                true,
+                device_state::FAILED,
+                device_state_reason::SUPPLICANT_TIMEOUT
+            ),
+            Some(AutoconnectBlockReason::Failed)
+        );
+        assert_eq!(
+            device_block_reason(
+                true,
+                device_state::ACTIVATED,
+                device_state_reason::NO_SECRETS
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn ordinary_disconnect_is_not_a_block() {
+        // 39 = NM_DEVICE_STATE_REASON_USER_REQUESTED
+        assert_eq!(
+            device_block_reason(true, device_state::DISCONNECTED, 39),
+            None
+        );
+    }
+
+    fn ap(ssid: &str, strength: u8, frequency_mhz: u32) -> AccessPoint {
+        AccessPoint {
+            path: OwnedObjectPath::default(),
+            device_path: OwnedObjectPath::default(),
+            interface: "wlan0".into(),
+            ssid: ssid.into(),
+            ssid_bytes: ssid.as_bytes().to_vec(),
+            bssid: String::new(),
+            frequency_mhz,
+            max_bitrate_kbps: 0,
+            strength,
+            mode: ApMode::Infrastructure,
+            security: Default::default(),
+            last_seen_secs: None,
+            seen: None,
+            is_active: false,
+            device_state: crate::DeviceState::Disconnected,
+            bss_load: None,
+        }
+    }
+
+    fn profile(ssid: &str, priority: i32) -> SavedConnection {
+        SavedConnection {
+            path: OwnedObjectPath::default(),
+            uuid: String::new(),
+            id: ssid.into(),
+            connection_type: "802-11-wireless".into(),
+            interface_name: None,
+            autoconnect: true,
+            autoconnect_priority: priority,
+            timestamp_unix: 0,
+            permissions: Vec::new(),
+            unsaved: false,
+            filename: None,
+            summary: SettingsSummary::Wifi {
+                ssid: ssid.into(),
+                mode: None,
+                security: None,
+                band: None,
+                channel: None,
+                bssid: None,
+                hidden: false,
+                mac_randomization: None,
+            },
+        }
+    }
+
+    fn pick<'a>(aps: &'a [AccessPoint], profiles: &'a [SavedConnection]) -> Option<(&'a str, u32)> {
+        best_available(aps, profiles).map(|(ap, _)| (ap.ssid.as_str(), ap.frequency_mhz))
+    }
+
+    #[test]
+    fn best_available_ranks_priority_strength_then_band() {
+        let aps = [
+            ap("Home", 40, 2412),
+            ap("Cafe", 90, 2437),
+            ap("Home", 40, 5180),
+        ];
+        let profiles = [profile("Home", 10), profile("Cafe", 0)];
+        assert_eq!(pick(&aps, &profiles), Some(("Home", 5180)));
+
+        let profiles = [profile("Home", 0), profile("Cafe", 0)];
+        assert_eq!(pick(&aps, &profiles), Some(("Cafe", 2437)));
+
+        assert_eq!(pick(&aps, &[profile("Office", 0)]), None);
+    }
+
+    #[test]
+    fn best_available_skips_ineligible_networks() {
+        let mut adhoc = ap("Mesh", 90, 2412);
+        adhoc.mode = ApMode::Adhoc;
+        let aps = [adhoc, ap("Home", 90, 2412), ap("Lab", 20, 2412)];
+
+        let mut manual = profile("Home", 0);
+        manual.autoconnect = false;
+        let mut elsewhere = profile("Lab", 0);
+        elsewhere.interface_name = Some("wlan1".into());
+        assert_eq!(pick(&aps, &[profile("Mesh", 0), manual, elsewhere]), None);
+
+        assert_eq!(
+            pick(&aps, &[profile("Mesh", 0), profile("Lab", 0)]),
+            Some(("Lab", 2412))
+        );
+    }
+}
@@ -12,10 +12,12 @@ use zvariant::OwnedObjectPath;
 // use futures_timer::Delay;
 
 use crate::ConnectionError;
+use crate::backend::{DbusBackend, NmBackend};
 use crate::builders::bluetooth;
+use crate::core::connection::activation_options;
 use crate::core::connection_settings::get_saved_connection_path;
 use crate::core::state_wait::{wait_for_connection_activation, wait_for_device_disconnect};
-use crate::dbus::{BluezDeviceExtProxy, NMDeviceProxy};
+use crate::dbus::{BluezDeviceExtProxy, NMBluetoothProxy, NMDeviceProxy};
 use crate::monitoring::bluetooth::Bluetooth;
 use crate::monitoring::transport::ActiveTransport;
 use crate::types::constants::device_state;
@@ -25,7 +27,7 @@ use crate::util::validation::validate_bluetooth_address;
 use crate::{
     Result,
     dbus::NMProxy,
-    models::{BluetoothIdentity, TimeoutConfig},
+    models::{BluetoothIdentity, ConnectionOptions, TimeoutConfig},
 };
 
 /// Populated Bluetooth device information via BlueZ.
@@ -115,6 +117,7 @@ pub(crate) async fn connect_bluetooth(
     name: &str,
     settings: &BluetoothIdentity,
     timeout_config: Option<TimeoutConfig>,
+    options: &ConnectionOptions,
 ) -> Result<()> {
     debug!(
         "Connecting to '{}' (Bluetooth) | bdaddr={} type={:?}",
@@ -165,24 +168,20 @@ pub(crate) async fn connect_bluetooth(
         }
         None => {
             debug!("No saved connection found, creating new Bluetooth connection");
-            let opts = crate::api::models::ConnectionOptions {
-                autoconnect: false, // Bluetooth typically doesn't auto-connect
-                autoconnect_priority: None,
-                autoconnect_retries: None,
-            };
-
-            let connection_settings = bluetooth::build_bluetooth_connection(name, settings, &opts);
+            let connection_settings =
+                bluetooth::build_bluetooth_connection(name, settings, options);
 
             debug!(
                 "Creating Bluetooth connection with settings: {:#?}",
                 connection_settings
             );
 
-            let (_, active_conn) = nm
-                .add_and_activate_connection(
+            let (_, active_conn) = DbusBackend::from_connection(conn.clone())
+                .add_and_activate_connection2(
                     connection_settings,
-                    bt_device.clone(),
-                    specific_object,
+                    &bt_device,
+                    &specific_object,
+                    activation_options(options),
                 )
                 .await?;
 
@@ -236,6 +235,44 @@ pub(crate) async fn disconnect_bluetooth_and_wait(
     Ok(())
 }
 
+/// Disconnects the NetworkManager Bluetooth device with address `bdaddr`.
+///
+/// The saved profile is kept. Returns `NoBluetoothDevice` if NetworkManager
+/// has no device with that address.
+pub(crate) async fn disconnect_bluetooth(
+    conn: &Connection,
+    bdaddr: &str,
+    timeout_config: Option<TimeoutConfig>,
+) -> Result<()> {
+    validate_bluetooth_address(bdaddr)?;
+
+    let nm = NMProxy::new(conn).await?;
+    for dp in nm.get_devices().await? {
+        let dev = NMDeviceProxy::builder(conn)
+            .path(dp.clone())?
+            .build()
+            .await?;
+        if dev.device_type().await? != device_type::BLUETOOTH {
+            continue;
+        }
+
+        let bt = NMBluetoothProxy::builder(conn)
+            .path(dp.clone())?
+            .build()
+            .await?;
+        if bt
+            .hw_address()
+            .await
+            .is_ok_and(|addr| addr.eq_ignore_ascii_case(bdaddr))
+        {
+            debug!("Disconnecting Bluetooth device {bdaddr}");
+            return disconnect_bluetooth_and_wait(conn, &dp, timeout_config).await;
+        }
+    }
+
+    Err(ConnectionError::NoBluetoothDevice)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
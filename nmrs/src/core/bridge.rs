@@ -0,0 +1,188 @@
+//! Bridge and bond creation.
+//!
+//! The master profile is activated without a device, so NetworkManager
+//! creates the software interface; each slave profile is then activated on
+//! its Ethernet device. The master finishes activating once its slaves are
+//! attached and it has an address.
+
+use log::{debug, info};
+use std::collections::HashMap;
+use std::time::Duration;
+use zbus::Connection;
+use zvariant::{OwnedObjectPath, OwnedValue, Str};
+
+use crate::Result;
+use crate::api::builders::bridge::build_slave_connection;
+use crate::api::models::{ConnectionOptions, SettingsPatch, SlaveType, TimeoutConfig};
+use crate::backend::{DbusBackend, NewProfileSettings, NmBackend};
+use crate::core::connection::{find_wired_device, replace_profile};
+use crate::core::saved_connection::update_saved_connection;
+use crate::util::validation::validate_interface_name;
+
+/// Creates and activates the master `name` from `settings` with `slaves`
+/// attached.
+pub(crate) async fn create_master(
+    conn: &Connection,
+    name: &str,
+    settings: NewProfileSettings,
+    slave_type: SlaveType,
+    slaves: &[&str],
+    options: &ConnectionOptions,
+    timeout_config: Option<TimeoutConfig>,
+) -> Result<()> {
+    let backend = DbusBackend::from_connection(conn.clone());
+    let timeout = timeout_config.map(|c| c.connection_timeout);
+    activate_master(
+        &backend, name, settings, slave_type, slaves, options, timeout,
+    )
+    .await?;
+    info!("Created {} {name} with {slaves:?}", slave_type.as_str());
+    Ok(())
+}
+
+/// [`create_master`] against any [`NmBackend`].
+///
+/// Slave devices are resolved and their profiles built before anything is
+/// added, so a missing interface leaves no half-created master behind.
+/// Existing profiles with the same IDs are replaced.
+pub(crate) async fn activate_master<B: NmBackend + ?Sized>(
+    backend: &B,
+    name: &str,
+    settings: NewProfileSettings,
+    slave_type: SlaveType,
+    slaves: &[&str],
+    options: &ConnectionOptions,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    let mut ports = Vec::with_capacity(slaves.len());
+    for interface in slaves {
+        let device = find_wired_device(backend, interface).await?;
+        let port = build_slave_connection(interface, name, slave_type, options)?;
+        ports.push((device, port));
+    }
+
+    debug!("Activating {} master {name}", slave_type.as_str());
+    let (_, master) = replace_profile(backend, &OwnedObjectPath::default(), settings).await?;
+    let mut active = vec![master];
+    for (device, port) in ports {
+        debug!("Attaching {} to {name}", device.as_str());
+        active.push(replace_profile(backend, &device, port).await?.1);
+    }
+
+    for path in &active {
+        backend.wait_for_activation(path, timeout).await?;
+    }
+    Ok(())
+}
+
+/// Turns the saved profile `uuid` into a slave of `master`.
+///
+/// NetworkManager drops the profile's IP settings when it normalizes the
+/// update, since slaves are not addressed themselves.
+pub(crate) async fn enslave_connection(
+    conn: &Connection,
+    uuid: &str,
+    master: &str,
+    slave_type: SlaveType,
+) -> Result<()> {
+    validate_interface_name(master)?;
+    update_saved_connection(conn, uuid, &slave_patch(master, slave_type)).await?;
+    info!(
+        "Attached profile {uuid} to {} {master}",
+        slave_type.as_str()
+    );
+    Ok(())
+}
+
+fn slave_patch(master: &str, slave_type: SlaveType) -> SettingsPatch {
+    let connection = HashMap::from([
+        (
+            "master".to_string(),
+            OwnedValue::from(Str::from(master.to_string())),
+        ),
+        (
+            "slave-type".to_string(),
+            OwnedValue::from(Str::from(slave_type.as_str())),
+        ),
+    ]);
+    SettingsPatch::new().with_raw_overlay(HashMap::from([("connection".to_string(), connection)]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::builders::bridge::build_bridge_connection;
+    use crate::api::models::{BridgeOptions, ConnectionError, DeviceState, DeviceType};
+    use crate::backend::MockBackend;
+
+    fn bridge() -> NewProfileSettings {
+        build_bridge_connection("br0", &BridgeOptions::new(), &ConnectionOptions::new(true))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn activates_master_then_slaves() {
+        let backend = MockBackend::new()
+            .with_device("eth0", DeviceType::Ethernet, DeviceState::Disconnected)
+            .with_device("eth1", DeviceType::Ethernet, DeviceState::Disconnected);
+        let devices = backend.get_devices().await.unwrap();
+
+        activate_master(
+            &backend,
+            "br0",
+            bridge(),
+            SlaveType::Bridge,
+            &["eth0", "eth1"],
+            &ConnectionOptions::new(true),
+            None,
+        )
+        .await
+        .unwrap();
+
+        let activations = backend.activations();
+        assert_eq!(activations.len(), 3);
+        assert_eq!(activations[0].device, OwnedObjectPath::default());
+        assert_eq!(activations[1].device, devices[0]);
+        assert_eq!(activations[2].device, devices[1]);
+        assert_eq!(backend.profiles().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn missing_slave_creates_nothing() {
+        let backend = MockBackend::new()
+            .with_device("eth0", DeviceType::Ethernet, DeviceState::Disconnected)
+            .with_device("wlan0", DeviceType::Wifi, DeviceState::Disconnected);
+
+        let result = activate_master(
+            &backend,
+            "br0",
+            bridge(),
+            SlaveType::Bridge,
+            &["eth0", "wlan0"],
+            &ConnectionOptions::new(true),
+            None,
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(ConnectionError::NotAWiredDevice { ref interface }) if interface == "wlan0"
+        ));
+        assert!(backend.activations().is_empty());
+        assert!(backend.profiles().is_empty());
+    }
+
+    #[test]
+    fn slave_patch_sets_master_and_type() {
+        let patch = slave_patch("bond0", SlaveType::Bond);
+        let connection = &patch.raw_overlay.unwrap()["connection"];
+        assert_eq!(
+            String::try_from(connection["master"].try_clone().unwrap()).unwrap(),
+            "bond0"
+        );
+        assert_eq!(
+            String::try_from(connection["slave-type"].try_clone().unwrap()).unwrap(),
+            "bond"
+        );
+    }
+}
@@ -0,0 +1,106 @@
+//! Cancellation of in-flight connection attempts.
+//!
+//! Dropping a connect future stops nmrs from waiting but leaves
+//! NetworkManager mid-activation, possibly with a freshly added profile.
+//! [`cancellable`] records the active connections and saved profiles that
+//! exist before the attempt; on cancellation it deactivates the attempt's
+//! new active connection and deletes the profiles it added.
+
+use log::{debug, warn};
+use std::collections::HashSet;
+use std::future::Future;
+use zbus::Connection;
+use zvariant::OwnedObjectPath;
+
+use crate::Result;
+use crate::api::models::ConnectionError;
+use crate::core::active_connection::list_active_connections;
+use crate::core::connection_settings::delete_connection;
+use crate::dbus::{NMProxy, NMSettingsConnectionProxy, NMSettingsProxy};
+
+/// Active connections and profiles present before an attempt started.
+struct Baseline {
+    active: HashSet<OwnedObjectPath>,
+    profiles: HashSet<OwnedObjectPath>,
+}
+
+impl Baseline {
+    async fn capture(conn: &Connection) -> Result<Self> {
+        let nm = NMProxy::new(conn).await?;
+        let settings = NMSettingsProxy::new(conn).await?;
+        Ok(Self {
+            active: nm.active_connections().await?.into_iter().collect(),
+            profiles: settings.list_connections().await?.into_iter().collect(),
+        })
+    }
+}
+
+/// Runs `connect` until it finishes or `cancelled` resolves.
+///
+/// `id` is the connection ID of the profile being activated; only new
+/// active connections and profiles with that ID are rolled back, so
+/// unrelated changes made meanwhile are left alone. Returns
+/// [`ConnectionError::Cancelled`] once the rollback is done.
+pub(crate) async fn cancellable<F, C>(
+    conn: &Connection,
+    id: &str,
+    connect: F,
+    cancelled: C,
+) -> Result<()>
+where
+    F: Future<Output = Result<()>>,
+    C: Future<Output = ()>,
+{
+    let before = Baseline::capture(conn).await?;
+
+    tokio::select! {
+        outcome = connect => outcome,
+        () = cancelled => {
+            debug!("Connection attempt for '{id}' cancelled, rolling back");
+            roll_back(conn, id, &before).await;
+            Err(ConnectionError::Cancelled)
+        }
+    }
+}
+
+/// Deactivates and deletes what the cancelled attempt created. Failures are
+/// logged; the attempt is reported as cancelled either way.
+async fn roll_back(conn: &Connection, id: &str, before: &Baseline) {
+    let Ok(nm) = NMProxy::new(conn).await else {
+        return;
+    };
+
+    for active in list_active_connections(conn).await.unwrap_or_default() {
+        if before.active.contains(&active.path) || active.id != id {
+            continue;
+        }
+        match nm.deactivate_connection(active.path.clone()).await {
+            Ok(()) => debug!("Deactivated pending connection {}", active.path.as_str()),
+            Err(e) => warn!("Failed to deactivate {}: {e}", active.path.as_str()),
+        }
+    }
+
+    let Ok(settings) = NMSettingsProxy::new(conn).await else {
+        return;
+    };
+    for path in settings.list_connections().await.unwrap_or_default() {
+        if before.profiles.contains(&path) || profile_id(conn, &path).await.as_deref() != Some(id) {
+            continue;
+        }
+        if let Err(e) = delete_connection(conn, path.clone()).await {
+            warn!("Failed to delete profile added by cancelled attempt: {e}");
+        }
+    }
+}
+
+/// Reads `connection.id` of the profile at `path`.
+async fn profile_id(conn: &Connection, path: &OwnedObjectPath) -> Option<String> {
+    let profile = NMSettingsConnectionProxy::builder(conn)
+        .path(path.clone())
+        .ok()?
+        .build()
+        .await
+        .ok()?;
+    let settings = profile.get_settings().await.ok()?;
+    String::try_from(settings.get("connection")?.get("id")?.clone()).ok()
+}
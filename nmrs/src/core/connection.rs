@@ -1,20 +1,40 @@
+use futures::StreamExt;
 use futures_timer::Delay;
 use log::{debug, error, info, warn};
-use std::collections::HashMap;
+use std::time::Duration;
 use zbus::Connection;
-use zvariant::OwnedObjectPath;
+use zvariant::{OwnedObjectPath, Value};
 
 use crate::Result;
-use crate::api::builders::wifi::{build_ethernet_connection, build_wifi_connection};
-use crate::api::models::{ConnectionError, ConnectionOptions, TimeoutConfig, WifiSecurity};
-use crate::core::connection_settings::{delete_connection, get_saved_connection_path};
-use crate::core::state_wait::{wait_for_connection_activation, wait_for_device_disconnect};
-use crate::dbus::{NMAccessPointProxy, NMDeviceProxy, NMProxy, NMWiredProxy, NMWirelessProxy};
+use crate::api::builders::wifi::{
+    build_ethernet_connection, build_wifi_connection, build_wifi_connection_with_options,
+    build_wired_connection,
+};
+use crate::api::models::{
+    ActivationBinding, ActivationStage, ActiveConnectionState, ConnectPolicy, ConnectionError,
+    ConnectionOptions, ConnectionPersistence, MatchOptions, SavedConnectionBrief, SettingsPatch,
+    TimeoutConfig, WifiOptions, WifiSecurity, WiredOptions,
+};
+use crate::backend::{ActivationOptions, DbusBackend, NewProfileSettings, NmBackend};
+use crate::core::active_connection::list_active_connections;
+use crate::core::connection_settings::{
+    find_saved_connection, get_saved_connection_path_with, profile_matches, settings_values,
+};
+use crate::core::failures;
+use crate::core::saved_connection::{fetch_one_brief, update_saved_connection};
+use crate::core::state_wait::{
+    wait_for_connection_activation, wait_for_connection_deactivation, wait_for_device_disconnect,
+    with_stage_updates,
+};
+use crate::dbus::{
+    NMAccessPointProxy, NMActiveConnectionProxy, NMDeviceProxy, NMProxy, NMWiredProxy,
+    NMWirelessProxy, dbus_operation,
+};
 use crate::monitoring::info::current_ssid;
 use crate::monitoring::transport::ActiveTransport;
 use crate::monitoring::wifi::Wifi;
 use crate::types::constants::{device_state, device_type, timeouts};
-use crate::util::utils::{decode_ssid_or_empty, nm_proxy};
+use crate::util::utils::{RedactedSettings, decode_ssid_or_empty, nm_proxy};
 use crate::util::validation::{validate_bssid, validate_ssid, validate_wifi_security};
 
 /// Decision on whether to reuse a saved connection or create a fresh one.
@@ -42,10 +62,98 @@ pub(crate) async fn connect(
     creds: WifiSecurity,
     interface: Option<&str>,
     timeout_config: Option<TimeoutConfig>,
+    options: &ConnectionOptions,
 ) -> Result<()> {
+    connect_with_stages(
+        conn,
+        ssid,
+        creds,
+        interface,
+        timeout_config,
+        options,
+        &WifiOptions::default(),
+        None,
+    )
+    .await
+}
+
+/// Connects to a Wi-Fi network, retrying failures `policy` deems transient.
+///
+/// Each attempt runs the full [`connect`] flow, including a fresh scan.
+pub(crate) async fn connect_with_policy(
+    conn: &Connection,
+    ssid: &str,
+    creds: WifiSecurity,
+    interface: Option<&str>,
+    timeout_config: Option<TimeoutConfig>,
+    options: &ConnectionOptions,
+    policy: &ConnectPolicy,
+) -> Result<()> {
+    let mut attempt = 1;
+    loop {
+        match connect(
+            conn,
+            ssid,
+            creds.clone(),
+            interface,
+            timeout_config,
+            options,
+        )
+        .await
+        {
+            Err(e) if policy.should_retry(&e, attempt) => {
+                let delay = policy.delay(attempt);
+                warn!(
+                    "Connecting to '{ssid}' failed (attempt {attempt}/{}): {e}, retrying in {delay:?}",
+                    policy.max_attempts
+                );
+                Delay::new(delay).await;
+                attempt += 1;
+            }
+            outcome => return outcome,
+        }
+    }
+}
+
+/// A step of [`connect_with_stages`] reported to its observer.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum ConnectStep {
+    /// Scanning for the target SSID.
+    Scanning,
+    /// The target access point was found.
+    ApFound,
+    /// The device entered an activation stage.
+    Stage(ActivationStage),
+}
+
+/// Connects to a Wi-Fi network, reporting progress to `on_step`.
+///
+/// Same flow as [`connect`]; when `on_step` is set, the scan and the
+/// device's intermediate states are forwarded while the connection
+/// activates.
+pub(crate) async fn connect_with_stages(
+    conn: &Connection,
+    ssid: &str,
+    creds: WifiSecurity,
+    interface: Option<&str>,
+    timeout_config: Option<TimeoutConfig>,
+    options: &ConnectionOptions,
+    wifi_options: &WifiOptions,
+    on_step: Option<&(dyn Fn(ConnectStep) + Send + Sync)>,
+) -> Result<()> {
+    let report = |step| {
+        if let Some(on_step) = on_step {
+            on_step(step);
+        }
+    };
+
     // Validate inputs before attempting connection
     validate_ssid(ssid)?;
     validate_wifi_security(&creds)?;
+    if let Some(policy) = &options.mac_policy {
+        policy.validate()?;
+    }
+    wifi_options.validate()?;
 
     debug!(
         "Connecting to '{}' on {:?} | secured={} is_psk={} is_eap={}",
@@ -56,22 +164,18 @@ pub(crate) async fn connect(
         creds.is_eap()
     );
 
-    let nm = NMProxy::new(conn).await?;
+    let backend = DbusBackend::from_connection(conn.clone());
 
-    let saved_raw = get_saved_connection_path(conn, ssid).await?;
+    let saved_raw = find_saved_connection(&backend, ssid, &MatchOptions::default()).await?;
     let decision = decide_saved_connection(saved_raw, &creds)?;
 
-    let wifi_device = resolve_wifi_device(conn, &nm, interface).await?;
+    let wifi_device = find_wifi_device(&backend, interface).await?;
     debug!("Resolved WiFi device: {}", wifi_device.as_str());
 
-    let wifi = NMWirelessProxy::builder(conn)
-        .path(wifi_device.clone())?
-        .build()
-        .await?;
-
     if let Some(active) = Wifi::current(conn).await {
         debug!("Currently connected to: {active}");
-        if active == ssid {
+        // With a band or channel requested, the current AP may not qualify
+        if active == ssid && *wifi_options == WifiOptions::default() {
             debug!("Already connected to {active}, skipping connect()");
             return Ok(());
         }
@@ -79,35 +183,32 @@ pub(crate) async fn connect(
         debug!("Not currently connected to any network");
     }
 
-    let specific_object = scan_and_resolve_ap(conn, &wifi, ssid).await?;
+    report(ConnectStep::Scanning);
+    let specific_object =
+        scan_and_resolve_ap(&backend, &wifi_device, ssid, wifi_options, timeout_config).await?;
+    report(ConnectStep::ApFound);
 
-    match decision {
-        SavedDecision::UseSaved(saved) => {
-            ensure_disconnected(conn, &wifi_device, timeout_config).await?;
-            connect_via_saved(
-                conn,
-                &nm,
-                &wifi_device,
-                &specific_object,
-                &creds,
-                saved,
-                timeout_config,
-            )
-            .await?;
-        }
-        SavedDecision::RebuildFresh => {
-            build_and_activate_new(
-                conn,
-                &nm,
-                &wifi_device,
-                &specific_object,
-                ssid,
-                creds,
-                timeout_config,
-            )
-            .await?;
+    let activation = activate_wifi(
+        &backend,
+        &wifi_device,
+        &specific_object,
+        ssid,
+        None,
+        decision,
+        creds,
+        timeout_config,
+        options,
+        wifi_options,
+    );
+
+    let outcome = match on_step {
+        Some(on_step) => {
+            let on_stage = |stage| on_step(ConnectStep::Stage(stage));
+            with_stage_updates(conn, &wifi_device, &on_stage, activation).await
         }
-    }
+        None => activation.await,
+    };
+    failures::track(conn, &wifi_device, ssid, outcome).await?;
 
     // Connection activation is now handled within connect_via_saved() and
     // build_and_activate_new() using signal-based monitoring
@@ -129,68 +230,348 @@ pub(crate) async fn connect(
 pub(crate) async fn connect_wired(
     conn: &Connection,
     timeout_config: Option<TimeoutConfig>,
+    options: &ConnectionOptions,
 ) -> Result<()> {
     debug!("Connecting to wired device");
 
-    let nm = NMProxy::new(conn).await?;
-
-    let wired_device = find_wired_device(conn, &nm).await?;
-    debug!("Found wired device: {}", wired_device.as_str());
+    let backend = DbusBackend::from_connection(conn.clone());
+    let (wired_device, interface, outcome) =
+        match activate_wired(&backend, timeout_config, options).await? {
+            WiredActivation::AlreadyActive => return Ok(()),
+            WiredActivation::Attempted {
+                device,
+                interface,
+                outcome,
+            } => (device, interface, outcome),
+        };
+    failures::track(conn, &wired_device, &interface, outcome).await?;
 
-    // Check if already connected
-    let dev = NMDeviceProxy::builder(conn)
+    if let Ok(wired) = NMWiredProxy::builder(conn)
         .path(wired_device.clone())?
         .build()
-        .await?;
-    let current_state = dev.state().await?;
-    if current_state == device_state::ACTIVATED {
+        .await
+        && let Ok(speed) = wired.speed().await
+    {
+        info!("Connected to wired device at {speed} Mb/s");
+    }
+
+    info!("Successfully connected to wired device");
+    Ok(())
+}
+
+/// What [`activate_wired`] did.
+pub(crate) enum WiredActivation {
+    /// The wired device was already activated.
+    AlreadyActive,
+    /// A profile was activated on `device`; `outcome` is the result.
+    Attempted {
+        device: OwnedObjectPath,
+        interface: String,
+        outcome: Result<()>,
+    },
+}
+
+/// Activates the first wired device's saved profile, or a new one.
+///
+/// The saved profile is the one whose ID is the interface name. Errors
+/// finding the device are returned directly; activation errors are
+/// reported in [`WiredActivation::Attempted`] so the caller can record
+/// them against the device.
+pub(crate) async fn activate_wired<B: NmBackend + ?Sized>(
+    backend: &B,
+    timeout_config: Option<TimeoutConfig>,
+    options: &ConnectionOptions,
+) -> Result<WiredActivation> {
+    if let Some(policy) = &options.mac_policy {
+        policy.validate()?;
+    }
+    let wired_device = find_device_by_type(backend, device_type::ETHERNET).await?;
+    debug!("Found wired device: {}", wired_device.as_str());
+
+    if backend.device_state(&wired_device).await? == device_state::ACTIVATED {
         debug!("Wired device already activated, skipping connect()");
-        return Ok(());
+        return Ok(WiredActivation::AlreadyActive);
     }
 
-    // Check for saved connection (by interface name)
-    let interface = dev.interface().await?;
-    let saved = get_saved_connection_path(conn, &interface).await?;
+    let interface = backend.device_interface(&wired_device).await?;
+    let saved = find_saved_connection(
+        backend,
+        &interface,
+        &MatchOptions::new().with_ssid_match(false),
+    )
+    .await?;
 
     // For Ethernet, we use "/" as the specific_object (no access point needed)
     let specific_object = OwnedObjectPath::default();
+    let timeout = timeout_config.map(|c| c.connection_timeout);
 
-    match saved {
-        Some(saved_path) => {
-            debug!("Activating saved wired connection: {}", saved_path.as_str());
-            let active_conn = nm
-                .activate_connection(saved_path, wired_device.clone(), specific_object)
-                .await?;
-            let timeout = timeout_config.map(|c| c.connection_timeout);
-            wait_for_connection_activation(conn, &active_conn, timeout).await?;
-        }
-        None => {
-            debug!("No saved connection found, creating new wired connection");
-            let opts = ConnectionOptions {
-                autoconnect: true,
-                autoconnect_priority: None,
-                autoconnect_retries: None,
-            };
-
-            let settings = build_ethernet_connection(&interface, &opts);
-            let (_, active_conn) = nm
-                .add_and_activate_connection(settings, wired_device.clone(), specific_object)
-                .await?;
-            let timeout = timeout_config.map(|c| c.connection_timeout);
-            wait_for_connection_activation(conn, &active_conn, timeout).await?;
-        }
+    let outcome = async {
+        let active_conn = match saved {
+            Some(saved_path) => {
+                debug!("Activating saved wired connection: {}", saved_path.as_str());
+                backend
+                    .activate_connection(&saved_path, &wired_device, &specific_object)
+                    .await?
+            }
+            None => {
+                debug!("No saved connection found, creating new wired connection");
+                let settings = build_ethernet_connection(&interface, options);
+                backend
+                    .add_and_activate_connection2(
+                        settings,
+                        &wired_device,
+                        &specific_object,
+                        activation_options(options),
+                    )
+                    .await?
+                    .1
+            }
+        };
+        backend.wait_for_activation(&active_conn, timeout).await
     }
+    .await;
 
-    if let Ok(wired) = NMWiredProxy::builder(conn)
-        .path(wired_device.clone())?
+    Ok(WiredActivation::Attempted {
+        device: wired_device,
+        interface,
+        outcome,
+    })
+}
+
+/// Resolves an Ethernet device path from its interface name.
+///
+/// Returns [`WiredInterfaceNotFound`] or [`NotAWiredDevice`] if the
+/// interface is missing or of another type.
+///
+/// [`WiredInterfaceNotFound`]: ConnectionError::WiredInterfaceNotFound
+/// [`NotAWiredDevice`]: ConnectionError::NotAWiredDevice
+pub(crate) async fn resolve_wired_device(
+    conn: &Connection,
+    interface: &str,
+) -> Result<OwnedObjectPath> {
+    let path = match get_device_by_interface(conn, interface).await {
+        Ok(p) => p,
+        Err(ConnectionError::NotFound) => {
+            return Err(ConnectionError::WiredInterfaceNotFound {
+                interface: interface.to_string(),
+            });
+        }
+        Err(e) => return Err(e),
+    };
+    let dev = NMDeviceProxy::builder(conn)
+        .path(path.clone())?
         .build()
+        .await?;
+    if dev.device_type().await? != device_type::ETHERNET {
+        return Err(ConnectionError::NotAWiredDevice {
+            interface: interface.to_string(),
+        });
+    }
+    Ok(path)
+}
+
+/// [`resolve_wired_device`] against any [`NmBackend`].
+pub(crate) async fn find_wired_device<B: NmBackend + ?Sized>(
+    backend: &B,
+    interface: &str,
+) -> Result<OwnedObjectPath> {
+    for path in backend.get_devices().await? {
+        if backend.device_interface(&path).await? != interface {
+            continue;
+        }
+        if backend.device_type(&path).await? != device_type::ETHERNET {
+            return Err(ConnectionError::NotAWiredDevice {
+                interface: interface.to_string(),
+            });
+        }
+        return Ok(path);
+    }
+    Err(ConnectionError::WiredInterfaceNotFound {
+        interface: interface.to_string(),
+    })
+}
+
+/// Lists saved Ethernet profiles NetworkManager can activate on `interface`.
+///
+/// Uses the device's `AvailableConnections`, so profiles bound to another
+/// interface or MAC address are excluded. Sorted by profile name.
+pub(crate) async fn list_wired_profiles(
+    conn: &Connection,
+    interface: &str,
+) -> Result<Vec<SavedConnectionBrief>> {
+    let device = resolve_wired_device(conn, interface).await?;
+    available_wired_profiles(conn, device, interface).await
+}
+
+async fn available_wired_profiles(
+    conn: &Connection,
+    device: OwnedObjectPath,
+    interface: &str,
+) -> Result<Vec<SavedConnectionBrief>> {
+    let dev = NMDeviceProxy::builder(conn).path(device)?.build().await?;
+    let paths = dev.available_connections().await.map_err(|e| {
+        dbus_operation(
+            format!("failed to read available connections on {interface}"),
+            e,
+        )
+    })?;
+
+    let mut profiles = Vec::new();
+    for path in paths {
+        if let Some(brief) = fetch_one_brief(conn, path).await?
+            && brief.connection_type == "802-3-ethernet"
+        {
+            profiles.push(brief);
+        }
+    }
+    profiles.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(profiles)
+}
+
+/// Activates the wired profile named `profile` on `interface`.
+///
+/// `profile` is matched against the connection id first, then the UUID.
+/// Returns [`SavedConnectionNotFound`] if no such profile is available on
+/// the interface.
+///
+/// [`SavedConnectionNotFound`]: ConnectionError::SavedConnectionNotFound
+pub(crate) async fn connect_wired_profile(
+    conn: &Connection,
+    interface: &str,
+    profile: &str,
+    timeout_config: Option<TimeoutConfig>,
+) -> Result<()> {
+    debug!("Connecting wired profile '{profile}' on {interface}");
+
+    let device = resolve_wired_device(conn, interface).await?;
+    let profiles = available_wired_profiles(conn, device.clone(), interface).await?;
+    let chosen = profiles
+        .iter()
+        .find(|p| p.id == profile)
+        .or_else(|| profiles.iter().find(|p| p.uuid == profile))
+        .ok_or_else(|| ConnectionError::SavedConnectionNotFound(profile.to_string()))?;
+
+    let nm = NMProxy::new(conn).await?;
+    let active_conn = nm
+        .activate_connection(chosen.path.clone(), device, OwnedObjectPath::default())
+        .await?;
+    let timeout = timeout_config.map(|c| c.connection_timeout);
+    wait_for_connection_activation(conn, &active_conn, timeout).await?;
+
+    info!("Activated wired profile '{}' on {interface}", chosen.id);
+    Ok(())
+}
+
+/// Re-activates the saved profile at `profile` if it is active, so
+/// changed settings take effect. Returns whether it was active.
+pub(crate) async fn reactivate_if_active(
+    conn: &Connection,
+    profile: &OwnedObjectPath,
+    timeout_config: Option<TimeoutConfig>,
+) -> Result<bool> {
+    let nm = NMProxy::new(conn).await?;
+    let active = list_active_connections(conn).await?;
+    let Some(active) = active.into_iter().find(|a| &a.connection == profile) else {
+        return Ok(false);
+    };
+    let device = active.devices.into_iter().next().unwrap_or_default();
+
+    debug!("Re-activating {}", profile.as_str());
+    let active_conn = nm
+        .activate_connection(profile.clone(), device, OwnedObjectPath::default())
+        .await?;
+    let timeout = timeout_config.map(|c| c.connection_timeout);
+    wait_for_connection_activation(conn, &active_conn, timeout).await?;
+    Ok(true)
+}
+
+/// Connects `interface` with a profile built from `wired`.
+///
+/// Unlike [`connect_wired`], the device is chosen by name and a saved
+/// profile named after it is replaced rather than reused, so the given
+/// options always take effect.
+pub(crate) async fn connect_wired_on(
+    conn: &Connection,
+    interface: &str,
+    timeout_config: Option<TimeoutConfig>,
+    options: &ConnectionOptions,
+    wired: &WiredOptions,
+) -> Result<()> {
+    debug!("Connecting wired device {interface} with {wired:?}");
+
+    let settings = build_wired_connection(interface, options, wired)?;
+    let device = resolve_wired_device(conn, interface).await?;
+    let backend = DbusBackend::from_connection(conn.clone());
+    let timeout = timeout_config.map(|c| c.connection_timeout);
+    let outcome = activate_wired_on(&backend, &device, settings, timeout).await;
+    failures::track(conn, &device, interface, outcome).await?;
+
+    info!("Successfully connected wired device {interface}");
+    Ok(())
+}
+
+/// Replaces the profiles named `interface` with `settings` and activates
+/// the result on `device`.
+pub(crate) async fn activate_wired_on<B: NmBackend + ?Sized>(
+    backend: &B,
+    device: &OwnedObjectPath,
+    settings: NewProfileSettings,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    let (_, active) = replace_profile(backend, device, settings).await?;
+    backend.wait_for_activation(&active, timeout).await
+}
+
+/// Deletes saved profiles with the ID of `settings`, then adds and
+/// activates `settings` on `device`. Returns the profile and active
+/// connection paths.
+pub(crate) async fn replace_profile<B: NmBackend + ?Sized>(
+    backend: &B,
+    device: &OwnedObjectPath,
+    settings: NewProfileSettings,
+) -> Result<(OwnedObjectPath, OwnedObjectPath)> {
+    let id = match settings.get("connection").and_then(|c| c.get("id")) {
+        Some(Value::Str(id)) => id.to_string(),
+        _ => String::new(),
+    };
+    let match_opts = MatchOptions::new().with_ssid_match(false);
+    while let Some(saved) = find_saved_connection(backend, &id, &match_opts).await? {
+        debug!("Replacing saved connection: {}", saved.as_str());
+        backend.delete_connection(&saved).await?;
+    }
+
+    backend
+        .add_and_activate_connection(settings, device, &OwnedObjectPath::default())
         .await
-        && let Ok(speed) = wired.speed().await
-    {
-        info!("Connected to wired device at {speed} Mb/s");
+}
+
+/// `AddAndActivateConnection2` options requested by `options`; empty for
+/// the defaults, so the plain `AddAndActivateConnection` is used.
+pub(crate) fn activation_options(options: &ConnectionOptions) -> ActivationOptions {
+    let mut out = ActivationOptions::new();
+    if options.persistence != ConnectionPersistence::Disk {
+        out.insert("persist", Value::from(options.persistence.as_str()));
+    }
+    if options.activation_binding != ActivationBinding::None {
+        out.insert(
+            "bind-activation",
+            Value::from(options.activation_binding.as_str()),
+        );
     }
+    out
+}
 
-    info!("Successfully connected to wired device");
+/// Disconnects the wired device `interface`.
+///
+/// The device stays down until a connection is activated on it again.
+pub(crate) async fn disconnect_wired(
+    conn: &Connection,
+    interface: &str,
+    timeout_config: Option<TimeoutConfig>,
+) -> Result<()> {
+    let device = resolve_wired_device(conn, interface).await?;
+    disconnect_device_and_wait(conn, &device, timeout_config).await?;
+    info!("Disconnected wired device {interface}");
     Ok(())
 }
 
@@ -202,22 +583,23 @@ pub(crate) async fn connect_wired(
 /// # Arguments
 ///
 /// * `conn` - D-Bus connection
+/// * `profiles` - Backend the saved profiles are read from and deleted through
 /// * `name` - Connection name/identifier to forget
 /// * `device_filter` - Optional device type filter (e.g., `Some(device_type::BLUETOOTH)`)
+/// * `match_options` - How profile IDs, SSIDs and bdaddrs are compared with `name`
 ///
 /// # Returns
 ///
 /// Returns `Ok(())` if at least one connection was deleted successfully.
 /// Returns `NoSavedConnection` if no matching connections were found.
-pub(crate) async fn forget_by_name_and_type(
+pub(crate) async fn forget_by_name_and_type<B: NmBackend + ?Sized>(
     conn: &Connection,
+    profiles: &B,
     name: &str,
     device_filter: Option<u32>,
+    match_options: &MatchOptions,
     timeout_config: Option<TimeoutConfig>,
 ) -> Result<()> {
-    use std::collections::HashMap;
-    use zvariant::{OwnedObjectPath, Value};
-
     // Validate SSID
     validate_ssid(name)?;
 
@@ -259,10 +641,11 @@ pub(crate) async fn forget_by_name_and_type(
                     .build()
                     .await?;
                 if let Ok(bytes) = ap.ssid().await
-                    && decode_ssid_or_empty(&bytes) == name
+                    && match_options.matches(&decode_ssid_or_empty(&bytes), name)
                 {
                     debug!("Disconnecting from active WiFi network: {name}");
-                    if let Err(e) = disconnect_wifi_and_wait(conn, dev_path, timeout_config).await {
+                    if let Err(e) = disconnect_device_and_wait(conn, dev_path, timeout_config).await
+                    {
                         warn!("Disconnect wait failed: {e}");
                         let final_state = dev.state().await?;
                         if final_state != device_state::DISCONNECTED
@@ -315,84 +698,43 @@ pub(crate) async fn forget_by_name_and_type(
     // Delete connection profiles (generic, works for all types)
     debug!("Starting connection deletion phase...");
 
-    let settings = nm_proxy(
-        conn,
-        "/org/freedesktop/NetworkManager/Settings",
-        "org.freedesktop.NetworkManager.Settings",
-    )
-    .await?;
-
-    let list_reply = settings.call_method("ListConnections", &()).await?;
-    let conns: Vec<OwnedObjectPath> = list_reply.body().deserialize()?;
-
     let mut deleted_count = 0;
 
-    for cpath in conns {
-        let cproxy = nm_proxy(
-            conn,
-            cpath.clone(),
-            "org.freedesktop.NetworkManager.Settings.Connection",
-        )
-        .await?;
+    for (cpath, owned) in profiles.all_connection_settings().await? {
+        let settings_map = settings_values(owned);
 
-        if let Ok(msg) = cproxy.call_method("GetSettings", &()).await {
-            let body = msg.body();
-            let settings_map: HashMap<String, HashMap<String, Value>> = body.deserialize()?;
+        let mut should_delete = false;
 
-            let mut should_delete = false;
-
-            // Match by connection ID (works for all connection types)
-            if let Some(conn_sec) = settings_map.get("connection")
-                && let Some(Value::Str(id)) = conn_sec.get("id")
-                && id.as_str() == name
-            {
-                should_delete = true;
-                debug!("Found connection by ID: {id}");
-            }
-
-            // Additional WiFi-specific matching by SSID
-            if let Some(wifi_sec) = settings_map.get("802-11-wireless")
-                && let Some(Value::Array(arr)) = wifi_sec.get("ssid")
-            {
-                let mut raw = Vec::new();
-                for v in arr.iter() {
-                    if let Ok(b) = u8::try_from(v.clone()) {
-                        raw.push(b);
-                    }
-                }
-                if decode_ssid_or_empty(&raw) == name {
-                    should_delete = true;
-                    debug!("Found WiFi connection by SSID match");
-                }
-            }
+        // Match by connection ID (works for all connection types) or,
+        // for WiFi, by SSID
+        if profile_matches(&settings_map, name, match_options) {
+            should_delete = true;
+            debug!("Found connection matching: {name}");
+        }
 
-            // Matching by bdaddr for Bluetooth connections
-            if let Some(bt_sec) = settings_map.get("bluetooth")
-                && let Some(Value::Str(bdaddr)) = bt_sec.get("bdaddr")
-                && bdaddr.as_str() == name
-            {
-                should_delete = true;
-                debug!("Found Bluetooth connection by bdaddr match");
-            }
+        // Matching by bdaddr for Bluetooth connections
+        if let Some(bt_sec) = settings_map.get("bluetooth")
+            && let Some(Value::Str(bdaddr)) = bt_sec.get("bdaddr")
+            && match_options.matches(bdaddr, name)
+        {
+            should_delete = true;
+            debug!("Found Bluetooth connection by bdaddr match");
+        }
 
-            if let Some(wsec) = settings_map.get("802-11-wireless-security") {
-                let missing_psk = !wsec.contains_key("psk");
-                let empty_psk = matches!(wsec.get("psk"), Some(Value::Str(s)) if s.is_empty());
+        if let Some(wsec) = settings_map.get("802-11-wireless-security") {
+            let missing_psk = !wsec.contains_key("psk");
+            let empty_psk = matches!(wsec.get("psk"), Some(Value::Str(s)) if s.is_empty());
 
-                if (missing_psk || empty_psk) && should_delete {
-                    debug!("Connection has missing/empty PSK, will delete");
-                }
+            if (missing_psk || empty_psk) && should_delete {
+                debug!("Connection has missing/empty PSK, will delete");
             }
+        }
 
-            if should_delete {
-                match cproxy.call_method("Delete", &()).await {
-                    Ok(_) => {
-                        deleted_count += 1;
-                        debug!("Deleted connection: {}", cpath.as_str());
-                    }
-                    Err(e) => {
-                        warn!("Failed to delete connection {}: {}", cpath.as_str(), e);
-                    }
+        if should_delete {
+            match profiles.delete_connection(&cpath).await {
+                Ok(()) => deleted_count += 1,
+                Err(e) => {
+                    warn!("Failed to delete connection {}: {}", cpath.as_str(), e);
                 }
             }
         }
@@ -416,16 +758,27 @@ pub(crate) async fn forget_by_name_and_type(
     }
 }
 
-/// Disconnects a Wi-Fi device and waits for it to reach disconnected state.
+/// Disconnects a device and waits for it to reach disconnected state.
 ///
 /// Calls the Disconnect method on the device and waits for the `StateChanged`
 /// signal to indicate the device has reached Disconnected or Unavailable state.
 /// This is more efficient than polling and responds immediately when the
 /// device disconnects.
-pub(crate) async fn disconnect_wifi_and_wait(
+pub(crate) async fn disconnect_device_and_wait(
     conn: &Connection,
     dev_path: &OwnedObjectPath,
     timeout_config: Option<TimeoutConfig>,
+) -> Result<()> {
+    let timeout = timeout_config.map(|c| c.disconnect_timeout);
+    disconnect_device_within(conn, dev_path, timeout).await
+}
+
+/// [`disconnect_device_and_wait`] with the wait bounded by `timeout`
+/// instead of a [`TimeoutConfig`].
+pub(crate) async fn disconnect_device_within(
+    conn: &Connection,
+    dev_path: &OwnedObjectPath,
+    timeout: Option<Duration>,
 ) -> Result<()> {
     let dev = NMDeviceProxy::builder(conn)
         .path(dev_path.clone())?
@@ -451,7 +804,6 @@ pub(crate) async fn disconnect_wifi_and_wait(
     debug!("Disconnect method called successfully");
 
     // Wait for disconnect using signal-based monitoring
-    let timeout = timeout_config.map(|c| c.disconnect_timeout);
     wait_for_device_disconnect(&dev, timeout).await?;
 
     // Brief stabilization delay
@@ -465,19 +817,12 @@ pub(crate) async fn disconnect_wifi_and_wait(
 /// Iterates through all devices managed by NetworkManager
 /// and returns the path of the first device matching the specified type.
 /// Returns an appropriate error if no matching device is found.
-async fn find_device_by_type(
-    conn: &Connection,
-    nm: &NMProxy<'_>,
+async fn find_device_by_type<B: NmBackend + ?Sized>(
+    backend: &B,
     device_type_id: u32,
 ) -> Result<OwnedObjectPath> {
-    let devices = nm.get_devices().await?;
-
-    for dp in devices {
-        let dev = NMDeviceProxy::builder(conn)
-            .path(dp.clone())?
-            .build()
-            .await?;
-        if dev.device_type().await? == device_type_id {
+    for dp in backend.get_devices().await? {
+        if backend.device_type(&dp).await? == device_type_id {
             return Ok(dp);
         }
     }
@@ -489,17 +834,6 @@ async fn find_device_by_type(
     }
 }
 
-pub(crate) async fn find_wired_device(
-    conn: &Connection,
-    nm: &NMProxy<'_>,
-) -> Result<OwnedObjectPath> {
-    find_device_by_type(conn, nm, device_type::ETHERNET).await
-}
-
-async fn find_wifi_device(conn: &Connection, nm: &NMProxy<'_>) -> Result<OwnedObjectPath> {
-    find_device_by_type(conn, nm, device_type::WIFI).await
-}
-
 /// Resolves a Wi-Fi device path from an optional interface name.
 ///
 /// `None` returns the first Wi-Fi device NM reports (back-compat behavior).
@@ -510,87 +844,82 @@ async fn find_wifi_device(conn: &Connection, nm: &NMProxy<'_>) -> Result<OwnedOb
 /// [`NotAWifiDevice`]: ConnectionError::NotAWifiDevice
 pub(crate) async fn resolve_wifi_device(
     conn: &Connection,
-    nm: &NMProxy<'_>,
     interface: Option<&str>,
 ) -> Result<OwnedObjectPath> {
-    match interface {
-        None => find_wifi_device(conn, nm).await,
-        Some(name) => {
-            let path = match get_device_by_interface(conn, name).await {
-                Ok(p) => p,
-                Err(ConnectionError::NotFound) => {
-                    return Err(ConnectionError::WifiInterfaceNotFound {
-                        interface: name.to_string(),
-                    });
-                }
-                Err(e) => return Err(e),
-            };
-            let dev = NMDeviceProxy::builder(conn)
-                .path(path.clone())?
-                .build()
-                .await?;
-            if dev.device_type().await? != device_type::WIFI {
-                return Err(ConnectionError::NotAWifiDevice {
-                    interface: name.to_string(),
-                });
-            }
-            Ok(path)
+    find_wifi_device(&DbusBackend::from_connection(conn.clone()), interface).await
+}
+
+/// [`resolve_wifi_device`] against any [`NmBackend`].
+pub(crate) async fn find_wifi_device<B: NmBackend + ?Sized>(
+    backend: &B,
+    interface: Option<&str>,
+) -> Result<OwnedObjectPath> {
+    let Some(name) = interface else {
+        return find_device_by_type(backend, device_type::WIFI).await;
+    };
+    for path in backend.get_devices().await? {
+        if backend.device_interface(&path).await.ok().as_deref() != Some(name) {
+            continue;
         }
+        if backend.device_type(&path).await? != device_type::WIFI {
+            return Err(ConnectionError::NotAWifiDevice {
+                interface: name.to_string(),
+            });
+        }
+        return Ok(path);
     }
+    Err(ConnectionError::WifiInterfaceNotFound {
+        interface: name.to_string(),
+    })
 }
 
 /// Finds an access point by SSID.
 ///
 /// Searches through all visible access points on the wireless device
-/// and returns the path of the first one matching the target SSID.
+/// and returns the path of the first one matching the target SSID on a
+/// band and channel `wifi_options` allows.
 /// Returns `NotFound` if no matching access point is visible.
-async fn find_ap(
-    conn: &Connection,
-    wifi: &NMWirelessProxy<'_>,
+async fn find_ap<B: NmBackend + ?Sized>(
+    backend: &B,
+    wifi_device: &OwnedObjectPath,
     target_ssid: &str,
+    wifi_options: &WifiOptions,
 ) -> Result<OwnedObjectPath> {
-    let access_points = wifi.access_points().await?;
-
-    for ap_path in access_points {
-        let ap = NMAccessPointProxy::builder(conn)
-            .path(ap_path.clone())?
-            .build()
-            .await?;
-
-        let ssid_bytes = ap.ssid().await?;
+    for ap_path in backend.access_points(wifi_device).await? {
+        let ssid_bytes = backend.access_point_ssid(&ap_path).await?;
         let ssid = decode_ssid_or_empty(&ssid_bytes);
 
-        if ssid == target_ssid {
-            return Ok(ap_path);
+        if ssid != target_ssid {
+            continue;
+        }
+        if *wifi_options != WifiOptions::default()
+            && !wifi_options.allows(backend.access_point_frequency(&ap_path).await?)
+        {
+            debug!("Skipping {} outside the requested band", ap_path.as_str());
+            continue;
         }
+        return Ok(ap_path);
     }
 
     Err(ConnectionError::NotFound)
 }
 
 /// Finds an access point matching both SSID and BSSID.
-async fn find_ap_by_bssid(
-    conn: &Connection,
-    wifi: &NMWirelessProxy<'_>,
+async fn find_ap_by_bssid<B: NmBackend + ?Sized>(
+    backend: &B,
+    wifi_device: &OwnedObjectPath,
     target_ssid: &str,
     target_bssid: &str,
 ) -> Result<OwnedObjectPath> {
-    let access_points = wifi.access_points().await?;
-
-    for ap_path in access_points {
-        let ap = NMAccessPointProxy::builder(conn)
-            .path(ap_path.clone())?
-            .build()
-            .await?;
-
-        let ssid_bytes = ap.ssid().await?;
+    for ap_path in backend.access_points(wifi_device).await? {
+        let ssid_bytes = backend.access_point_ssid(&ap_path).await?;
         let ssid = decode_ssid_or_empty(&ssid_bytes);
 
         if ssid != target_ssid {
             continue;
         }
 
-        let bssid = ap.hw_address().await?;
+        let bssid = backend.access_point_bssid(&ap_path).await?;
         if bssid.eq_ignore_ascii_case(target_bssid) {
             return Ok(ap_path);
         }
@@ -613,13 +942,14 @@ pub(crate) async fn connect_to_bssid(
     creds: WifiSecurity,
     interface: Option<&str>,
     timeout_config: Option<TimeoutConfig>,
+    options: &ConnectionOptions,
 ) -> Result<()> {
     if let Some(b) = bssid {
         validate_bssid(b)?;
     }
 
     match bssid {
-        None => connect(conn, ssid, creds, interface, timeout_config).await,
+        None => connect(conn, ssid, creds, interface, timeout_config, options).await,
         Some(target_bssid) => {
             validate_ssid(ssid)?;
             validate_wifi_security(&creds)?;
@@ -634,50 +964,32 @@ pub(crate) async fn connect_to_bssid(
                 creds.is_eap()
             );
 
-            let nm = NMProxy::new(conn).await?;
-            let saved_raw = get_saved_connection_path(conn, ssid).await?;
+            let backend = DbusBackend::from_connection(conn.clone());
+            let saved_raw = find_saved_connection(&backend, ssid, &MatchOptions::default()).await?;
             let decision = decide_saved_connection(saved_raw, &creds)?;
-            let wifi_device = resolve_wifi_device(conn, &nm, interface).await?;
-            let wifi = NMWirelessProxy::builder(conn)
-                .path(wifi_device.clone())?
-                .build()
-                .await?;
+            let wifi_device = find_wifi_device(&backend, interface).await?;
 
-            match wifi.request_scan(HashMap::new()).await {
-                Ok(_) => debug!("Scan requested successfully"),
-                Err(e) => warn!("Scan request failed: {e}"),
-            }
-            futures_timer::Delay::new(timeouts::scan_wait()).await;
+            backend
+                .request_scan_and_wait(&wifi_device, timeouts::scan_complete_timeout())
+                .await?;
 
-            let specific_object = find_ap_by_bssid(conn, &wifi, ssid, target_bssid).await?;
+            let specific_object =
+                find_ap_by_bssid(&backend, &wifi_device, ssid, target_bssid).await?;
 
-            match decision {
-                SavedDecision::UseSaved(saved) => {
-                    ensure_disconnected(conn, &wifi_device, timeout_config).await?;
-                    connect_via_saved(
-                        conn,
-                        &nm,
-                        &wifi_device,
-                        &specific_object,
-                        &creds,
-                        saved,
-                        timeout_config,
-                    )
-                    .await?;
-                }
-                SavedDecision::RebuildFresh => {
-                    build_and_activate_new(
-                        conn,
-                        &nm,
-                        &wifi_device,
-                        &specific_object,
-                        ssid,
-                        creds,
-                        timeout_config,
-                    )
-                    .await?;
-                }
-            }
+            let outcome = activate_wifi(
+                &backend,
+                &wifi_device,
+                &specific_object,
+                ssid,
+                Some(target_bssid),
+                decision,
+                creds,
+                timeout_config,
+                options,
+                &WifiOptions::default(),
+            )
+            .await;
+            failures::track(conn, &wifi_device, ssid, outcome).await?;
 
             info!("Successfully connected to '{ssid}' (BSSID: {target_bssid})");
             Ok(())
@@ -685,153 +997,172 @@ pub(crate) async fn connect_to_bssid(
     }
 }
 
+/// Activates `ssid` on `wifi_device` through the access point
+/// `specific_object`, reusing the saved profile or creating a new one as
+/// `decision` says.
+///
+/// A new profile is pinned to `bssid` when set.
+async fn activate_wifi<B: NmBackend + ?Sized>(
+    backend: &B,
+    wifi_device: &OwnedObjectPath,
+    specific_object: &OwnedObjectPath,
+    ssid: &str,
+    bssid: Option<&str>,
+    decision: SavedDecision,
+    creds: WifiSecurity,
+    timeout_config: Option<TimeoutConfig>,
+    options: &ConnectionOptions,
+    wifi_options: &WifiOptions,
+) -> Result<()> {
+    match decision {
+        SavedDecision::UseSaved(saved) => {
+            ensure_disconnected(backend, wifi_device, timeout_config).await?;
+            connect_via_saved(
+                backend,
+                wifi_device,
+                specific_object,
+                &creds,
+                saved,
+                timeout_config,
+                options,
+            )
+            .await
+        }
+        SavedDecision::RebuildFresh => {
+            build_and_activate_new(
+                backend,
+                wifi_device,
+                specific_object,
+                ssid,
+                bssid,
+                creds,
+                timeout_config,
+                options,
+                wifi_options,
+            )
+            .await
+        }
+    }
+}
+
 /// Ensures the target Wi-Fi device is torn down before attempting a new connection.
 ///
 /// Only the given `wifi_device` is affected. Other interfaces (e.g. VPN, wired,
 /// a second Wi-Fi radio) are not deactivated.
-async fn ensure_disconnected(
-    conn: &Connection,
+async fn ensure_disconnected<B: NmBackend + ?Sized>(
+    backend: &B,
     wifi_device: &OwnedObjectPath,
     timeout_config: Option<TimeoutConfig>,
 ) -> Result<()> {
-    disconnect_wifi_and_wait(conn, wifi_device, timeout_config).await
+    let timeout = timeout_config.map(|c| c.disconnect_timeout);
+    backend.disconnect_device(wifi_device, timeout).await
 }
 
 /// Attempts to connect using a saved connection profile.
 ///
-/// Activates the saved connection and monitors the activation state using
-/// D-Bus signals. If activation fails (device disconnects or enters failed
-/// state), deletes the saved connection and creates a fresh one with the
-/// provided credentials.
+/// Activates the saved connection and waits for it to come up. If
+/// activation fails (device disconnects or enters failed state), deletes
+/// the saved connection and creates a fresh one with the provided
+/// credentials.
 ///
 /// This handles cases where saved passwords are outdated or corrupted.
-async fn connect_via_saved(
-    conn: &Connection,
-    nm: &NMProxy<'_>,
+async fn connect_via_saved<B: NmBackend + ?Sized>(
+    backend: &B,
     wifi_device: &OwnedObjectPath,
     ap: &OwnedObjectPath,
     creds: &WifiSecurity,
     saved: OwnedObjectPath,
     timeout_config: Option<TimeoutConfig>,
+    options: &ConnectionOptions,
 ) -> Result<()> {
     debug!("Activating saved connection: {}", saved.as_str());
+    let timeout = timeout_config.map(|c| c.connection_timeout);
 
-    match nm
-        .activate_connection(saved.clone(), wifi_device.clone(), ap.clone())
-        .await
-    {
+    match backend.activate_connection(&saved, wifi_device, ap).await {
         Ok(active_conn) => {
             debug!(
                 "activate_connection() succeeded, active connection: {}",
                 active_conn.as_str()
             );
 
-            // Wait for connection activation using signal-based monitoring
-            let timeout = timeout_config.map(|c| c.connection_timeout);
-            match wait_for_connection_activation(conn, &active_conn, timeout).await {
+            match backend.wait_for_activation(&active_conn, timeout).await {
                 Ok(()) => {
                     debug!("Saved connection activated successfully");
+                    return Ok(());
                 }
                 Err(e) => {
                     warn!("Saved connection activation failed: {e}");
                     warn!("Deleting saved connection and retrying with fresh credentials");
 
-                    match nm.deactivate_connection(active_conn.clone()).await {
+                    match backend.deactivate_connection(&active_conn).await {
                         Ok(_) => debug!("Connection deactivated during cleanup"),
                         Err(e) => warn!("Failed to deactivate connection during cleanup: {}", e),
                     }
-                    match delete_connection(conn, saved.clone()).await {
-                        Ok(_) => debug!("Saved connection deleted"),
-                        Err(e) => warn!("Failed to delete saved connection during recovery: {}", e),
-                    }
-
-                    let opts = ConnectionOptions {
-                        autoconnect: true,
-                        autoconnect_priority: None,
-                        autoconnect_retries: None,
-                    };
-
-                    let settings = build_wifi_connection(ap.as_str(), creds, &opts);
-
-                    debug!("Creating fresh connection with corrected settings");
-                    let (_, new_active_conn) = nm
-                        .add_and_activate_connection(settings, wifi_device.clone(), ap.clone())
-                        .await
-                        .map_err(|e| {
-                            error!("Fresh connection also failed: {e}");
-                            e
-                        })?;
-
-                    // Wait for the fresh connection to activate
-                    let timeout = timeout_config.map(|c| c.connection_timeout);
-                    wait_for_connection_activation(conn, &new_active_conn, timeout).await?;
                 }
             }
         }
-
         Err(e) => {
             warn!("activate_connection() failed: {e}");
             warn!("Saved connection may be corrupted, deleting and retrying with fresh connection");
+        }
+    }
 
-            match delete_connection(conn, saved.clone()).await {
-                Ok(_) => debug!("Saved connection deleted"),
-                Err(e) => warn!("Failed to delete saved connection during recovery: {}", e),
-            }
-
-            let opts = ConnectionOptions {
-                autoconnect: true,
-                autoconnect_priority: None,
-                autoconnect_retries: None,
-            };
-
-            let settings = build_wifi_connection(ap.as_str(), creds, &opts);
+    match backend.delete_connection(&saved).await {
+        Ok(_) => debug!("Saved connection deleted"),
+        Err(e) => warn!("Failed to delete saved connection during recovery: {}", e),
+    }
 
-            let (_, active_conn) = nm
-                .add_and_activate_connection(settings, wifi_device.clone(), ap.clone())
-                .await
-                .map_err(|e| {
-                    error!("Fresh connection also failed: {e}");
-                    e
-                })?;
+    let settings = build_wifi_connection(ap.as_str(), creds, options);
 
-            // Wait for the fresh connection to activate
-            let timeout = timeout_config.map(|c| c.connection_timeout);
-            wait_for_connection_activation(conn, &active_conn, timeout).await?;
-        }
-    }
+    debug!("Creating fresh connection with corrected settings");
+    let (_, active_conn) = backend
+        .add_and_activate_connection2(settings, wifi_device, ap, activation_options(options))
+        .await
+        .map_err(|e| {
+            error!("Fresh connection also failed: {e}");
+            e
+        })?;
 
-    Ok(())
+    backend.wait_for_activation(&active_conn, timeout).await
 }
 
 /// Creates a new connection profile and activates it.
 ///
 /// Builds connection settings from the provided credentials, ensures the
 /// device is disconnected, then calls AddAndActivateConnection to create
-/// and activate the connection in one step. Monitors activation using
-/// D-Bus signals for immediate feedback on success or failure.
-async fn build_and_activate_new(
-    conn: &Connection,
-    nm: &NMProxy<'_>,
+/// and activate the connection in one step, and waits for it to come up.
+///
+/// When `bssid` is set, the new profile is locked to that AP through
+/// `802-11-wireless.bssid`.
+async fn build_and_activate_new<B: NmBackend + ?Sized>(
+    backend: &B,
     wifi_device: &OwnedObjectPath,
     ap: &OwnedObjectPath,
     ssid: &str,
+    bssid: Option<&str>,
     creds: WifiSecurity,
     timeout_config: Option<TimeoutConfig>,
+    options: &ConnectionOptions,
+    wifi_options: &WifiOptions,
 ) -> Result<()> {
-    let opts = ConnectionOptions {
-        autoconnect: true,
-        autoconnect_retries: None,
-        autoconnect_priority: None,
-    };
+    let mut settings = build_wifi_connection_with_options(ssid, &creds, options, wifi_options)?;
 
-    let settings = build_wifi_connection(ssid, &creds, &opts);
+    // Pin the new profile to the requested AP so NM does not roam away
+    if let Some(bssid) = bssid
+        && let Some(wireless) = settings.get_mut("802-11-wireless")
+    {
+        wireless.insert("bssid", zvariant::Value::from(bssid.to_string()));
+    }
 
-    debug!("Creating new connection, settings: \n{settings:#?}");
+    debug!(
+        "Creating new connection, settings: \n{:#?}",
+        RedactedSettings(&settings)
+    );
 
-    ensure_disconnected(conn, wifi_device, timeout_config).await?;
+    ensure_disconnected(backend, wifi_device, timeout_config).await?;
 
-    let (_, active_conn) = match nm
-        .add_and_activate_connection(settings, wifi_device.clone(), ap.clone())
+    let (_, active_conn) = match backend
+        .add_and_activate_connection2(settings, wifi_device, ap, activation_options(options))
         .await
     {
         Ok(paths) => {
@@ -843,15 +1174,14 @@ async fn build_and_activate_new(
         }
         Err(e) => {
             error!("add_and_activate_connection() failed: {e}");
-            return Err(e.into());
+            return Err(e);
         }
     };
 
-    debug!("Waiting for connection activation using signal monitoring...");
+    debug!("Waiting for connection activation...");
 
-    // Wait for connection activation using the ActiveConnection signals
     let timeout = timeout_config.map(|c| c.connection_timeout);
-    wait_for_connection_activation(conn, &active_conn, timeout).await?;
+    backend.wait_for_activation(&active_conn, timeout).await?;
 
     info!("Connection to '{ssid}' activated successfully");
 
@@ -860,29 +1190,70 @@ async fn build_and_activate_new(
 
 /// Triggers a Wi-Fi scan and finds the target access point.
 ///
-/// Requests a scan, waits briefly for results, then searches for an
-/// access point matching the target SSID. The wait time is shorter than
-/// polling-based approaches since we just need the scan to populate
-/// initial results.
-async fn scan_and_resolve_ap(
-    conn: &Connection,
-    wifi: &NMWirelessProxy<'_>,
+/// Requests a scan, waits for it to complete (see
+/// [`NmBackend::request_scan_and_wait`]), then searches for an access
+/// point matching the target SSID.
+///
+/// If the SSID is not visible and `scan_retries` is configured, falls
+/// back to [`rescan_for_ap`] before giving up.
+async fn scan_and_resolve_ap<B: NmBackend + ?Sized>(
+    backend: &B,
+    wifi_device: &OwnedObjectPath,
     ssid: &str,
+    wifi_options: &WifiOptions,
+    timeout_config: Option<TimeoutConfig>,
 ) -> Result<OwnedObjectPath> {
-    match wifi.request_scan(HashMap::new()).await {
-        Ok(_) => debug!("Scan requested successfully"),
-        Err(e) => warn!("Scan request failed: {e}"),
-    }
-
-    // Brief wait for scan results to populate
-    Delay::new(timeouts::scan_wait()).await;
-    debug!("Scan wait complete");
+    backend
+        .request_scan_and_wait(wifi_device, timeouts::scan_complete_timeout())
+        .await?;
 
-    let ap = find_ap(conn, wifi, ssid).await?;
+    let ap = match find_ap(backend, wifi_device, ssid, wifi_options).await {
+        Ok(ap) => ap,
+        Err(ConnectionError::NotFound) => {
+            let config = timeout_config.unwrap_or_default();
+            rescan_for_ap(backend, wifi_device, ssid, wifi_options, config).await?
+        }
+        Err(e) => return Err(e),
+    };
     debug!("Matched target SSID '{ssid}'");
     Ok(ap)
 }
 
+/// Rescans up to `config.scan_retries` times looking for `ssid`.
+///
+/// Each attempt requests a new scan and waits, at most a doubling delay,
+/// for the device's `LastScan` to advance. The results are only searched
+/// again once a scan has completed; until then they are the same ones
+/// that did not contain `ssid`.
+async fn rescan_for_ap<B: NmBackend + ?Sized>(
+    backend: &B,
+    wifi_device: &OwnedObjectPath,
+    ssid: &str,
+    wifi_options: &WifiOptions,
+    config: TimeoutConfig,
+) -> Result<OwnedObjectPath> {
+    for attempt in 1..=config.scan_retries {
+        debug!(
+            "'{ssid}' not visible, rescan attempt {attempt}/{}",
+            config.scan_retries
+        );
+
+        let wait = config.scan_retry_wait(attempt);
+        if !backend.request_scan_and_wait(wifi_device, wait).await? {
+            debug!("Rescan {attempt} did not complete within {wait:?}");
+            continue;
+        }
+
+        match find_ap(backend, wifi_device, ssid, wifi_options).await {
+            Ok(ap) => return Ok(ap),
+            Err(ConnectionError::NotFound) => continue,
+            Err(e) => return Err(e),
+        }
+    }
+
+    Err(ConnectionError::NotFound)
+}
+
 /// Decides whether to use a saved connection or create a fresh one.
 ///
 /// Decision logic:
@@ -928,10 +1299,12 @@ pub(crate) async fn is_connected(conn: &Connection, ssid: &str) -> Result<bool>
     Ok(false)
 }
 
-/// Disconnects from the currently active network.
+/// Disconnects the Wi-Fi device from its current network.
 ///
-/// This finds the current active WiFi connection and deactivates it,
-/// then waits for the device to reach disconnected state.
+/// Deactivates the active connection on the device and waits for it to
+/// go down. The device itself is not disconnected, so its autoconnect is
+/// left alone and NetworkManager may join another known network right
+/// away. The stored `connection.autoconnect` of the profile is unchanged.
 ///
 /// Returns `Ok(())` if disconnected successfully or if no active connection exists.
 pub(crate) async fn disconnect(
@@ -939,9 +1312,7 @@ pub(crate) async fn disconnect(
     interface: Option<&str>,
     timeout_config: Option<TimeoutConfig>,
 ) -> Result<()> {
-    let nm = NMProxy::new(conn).await?;
-
-    let wifi_device = match resolve_wifi_device(conn, &nm, interface).await {
+    let wifi_device = match resolve_wifi_device(conn, interface).await {
         Ok(dev) => dev,
         Err(ConnectionError::NoWifiDevice) => {
             debug!("No WiFi device found");
@@ -950,48 +1321,85 @@ pub(crate) async fn disconnect(
         Err(e) => return Err(e),
     };
 
-    let dev = NMDeviceProxy::builder(conn)
-        .path(wifi_device.clone())?
-        .build()
-        .await?;
+    let timeout = timeout_config.unwrap_or_default().disconnect_timeout;
+    let mut disconnected = false;
+    for active in list_active_connections(conn).await? {
+        if active.devices.contains(&wifi_device) {
+            deactivate_and_wait(conn, &active.path, timeout).await?;
+            disconnected = true;
+        }
+    }
 
-    let current_state = dev.state().await?;
-    if current_state == device_state::DISCONNECTED || current_state == device_state::UNAVAILABLE {
-        debug!("Device already disconnected");
-        return Ok(());
+    if disconnected {
+        info!("Disconnected from network");
+    } else {
+        debug!("No active connection on the device");
     }
+    Ok(())
+}
 
-    if let Ok(conns) = nm.active_connections().await {
-        for conn_path in conns {
-            let active = match crate::dbus::NMActiveConnectionProxy::builder(conn)
-                .path(conn_path.clone())?
-                .build()
-                .await
-            {
-                Ok(p) => p,
-                Err(e) => {
-                    warn!("Failed to build active connection proxy: {}", e);
-                    continue;
-                }
-            };
-            let owns_device = match active.devices().await {
-                Ok(devs) => devs.iter().any(|d| d == &wifi_device),
-                Err(_) => false,
-            };
-            if !owns_device {
-                continue;
-            }
-            match nm.deactivate_connection(conn_path.clone()).await {
-                Ok(_) => debug!("Connection deactivated"),
-                Err(e) => warn!("Failed to deactivate connection: {}", e),
-            }
+/// Disconnects the Wi-Fi network `ssid` and turns off autoconnect of its
+/// saved profile, so NetworkManager does not rejoin it.
+///
+/// `connection.autoconnect` is cleared before deactivating; the network is
+/// only joined again when activated explicitly.
+pub(crate) async fn disconnect_and_block(
+    conn: &Connection,
+    ssid: &str,
+    match_options: &MatchOptions,
+    timeout_config: Option<TimeoutConfig>,
+) -> Result<()> {
+    validate_ssid(ssid)?;
+    let path = get_saved_connection_path_with(conn, ssid, match_options)
+        .await?
+        .ok_or(ConnectionError::NoSavedConnection)?;
+    let brief = fetch_one_brief(conn, path.clone())
+        .await?
+        .ok_or(ConnectionError::NoSavedConnection)?;
+    update_saved_connection(
+        conn,
+        &brief.uuid,
+        &SettingsPatch::new().with_autoconnect(false),
+    )
+    .await?;
+    debug!("Turned off autoconnect of '{}'", brief.id);
+
+    let timeout = timeout_config.unwrap_or_default().disconnect_timeout;
+    for active in list_active_connections(conn).await? {
+        if active.connection == path {
+            deactivate_and_wait(conn, &active.path, timeout).await?;
+            info!("Disconnected from '{}'", brief.id);
         }
     }
+    Ok(())
+}
 
-    disconnect_wifi_and_wait(conn, &wifi_device, timeout_config).await?;
+/// Deactivates the active connection at `active` and waits until
+/// NetworkManager reports it deactivated.
+async fn deactivate_and_wait(
+    conn: &Connection,
+    active: &OwnedObjectPath,
+    timeout: Duration,
+) -> Result<()> {
+    let nm = NMProxy::new(conn).await?;
+    let ac = NMActiveConnectionProxy::builder(conn)
+        .path(active.clone())?
+        .build()
+        .await?;
+    // Subscribe first so the transition is not missed.
+    let changes = ac.receive_activation_state_changed().await?;
 
-    info!("Disconnected from network");
-    Ok(())
+    nm.deactivate_connection(active.clone())
+        .await
+        .map_err(|e| dbus_operation(format!("failed to deactivate {}", active.as_str()), e))?;
+
+    let states = changes.filter_map(|signal| async move {
+        signal
+            .args()
+            .ok()
+            .map(|args| ActiveConnectionState::from(args.state))
+    });
+    wait_for_connection_deactivation(&ac, states, timeout).await
 }
 
 /// Finds a device by its interface name.
@@ -1020,3 +1428,331 @@ pub(crate) async fn get_device_by_interface(
 
     Err(ConnectionError::NotFound)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::builders::WifiBand;
+    use crate::api::models::{DeviceState, DeviceType};
+    use crate::backend::{MockAccessPoint, MockBackend};
+    use zvariant::OwnedValue;
+
+    fn options() -> ConnectionOptions {
+        ConnectionOptions::new(true)
+    }
+
+    #[tokio::test]
+    async fn wired_activates_saved_profile_named_after_interface() {
+        let backend = MockBackend::new()
+            .with_device("wlan0", DeviceType::Wifi, DeviceState::Disconnected)
+            .with_device("eth0", DeviceType::Ethernet, DeviceState::Disconnected)
+            .with_profile(build_ethernet_connection("eth0", &options()));
+        let saved = backend.profiles()[0].0.clone();
+
+        let WiredActivation::Attempted {
+            interface, outcome, ..
+        } = activate_wired(&backend, None, &options()).await.unwrap()
+        else {
+            panic!("expected an activation");
+        };
+        assert_eq!(interface, "eth0");
+        assert!(outcome.is_ok());
+
+        let activations = backend.activations();
+        assert_eq!(activations.len(), 1);
+        assert_eq!(activations[0].connection, saved);
+        assert!(!activations[0].added);
+    }
+
+    #[tokio::test]
+    async fn wired_creates_profile_when_none_is_saved() {
+        let backend =
+            MockBackend::new().with_device("eth0", DeviceType::Ethernet, DeviceState::Disconnected);
+
+        activate_wired(&backend, None, &options()).await.unwrap();
+
+        let activations = backend.activations();
+        assert_eq!(activations.len(), 1);
+        assert!(activations[0].added);
+        assert!(activations[0].options.is_empty());
+        assert_eq!(backend.profiles().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn wired_volatile_profile_requests_persist_flag() {
+        let backend =
+            MockBackend::new().with_device("eth0", DeviceType::Ethernet, DeviceState::Disconnected);
+        let options = options().with_persistence(ConnectionPersistence::Volatile);
+
+        activate_wired(&backend, None, &options).await.unwrap();
+
+        let activations = backend.activations();
+        assert_eq!(activations.len(), 1);
+        assert!(activations[0].added);
+        assert_eq!(
+            activations[0].options.get("persist").map(String::as_str),
+            Some("volatile")
+        );
+    }
+
+    #[test]
+    fn activation_options_carry_binding() {
+        assert!(activation_options(&options()).is_empty());
+
+        let bound = activation_options(
+            &options()
+                .with_persistence(ConnectionPersistence::Memory)
+                .with_activation_binding(ActivationBinding::DbusClient),
+        );
+        assert_eq!(bound.len(), 2);
+        assert_eq!(bound["persist"], Value::from("memory"));
+        assert_eq!(bound["bind-activation"], Value::from("dbus-client"));
+    }
+
+    #[tokio::test]
+    async fn wired_skips_activated_device_and_reports_failures() {
+        let backend =
+            MockBackend::new().with_device("eth0", DeviceType::Ethernet, DeviceState::Activated);
+        assert!(matches!(
+            activate_wired(&backend, None, &options()).await.unwrap(),
+            WiredActivation::AlreadyActive
+        ));
+        assert!(backend.activations().is_empty());
+
+        let backend = MockBackend::new()
+            .with_device("eth0", DeviceType::Ethernet, DeviceState::Disconnected)
+            .with_activation_error(ConnectionError::DhcpFailed);
+        let WiredActivation::Attempted { outcome, .. } =
+            activate_wired(&backend, None, &options()).await.unwrap()
+        else {
+            panic!("expected an activation");
+        };
+        assert!(matches!(outcome, Err(ConnectionError::DhcpFailed)));
+    }
+
+    #[tokio::test]
+    async fn wired_without_device_fails() {
+        let backend =
+            MockBackend::new().with_device("wlan0", DeviceType::Wifi, DeviceState::Disconnected);
+        assert!(matches!(
+            activate_wired(&backend, None, &options()).await,
+            Err(ConnectionError::NoWiredDevice)
+        ));
+    }
+
+    #[tokio::test]
+    async fn wired_on_replaces_profile_named_after_interface() {
+        let backend = MockBackend::new()
+            .with_device("eth0", DeviceType::Ethernet, DeviceState::Disconnected)
+            .with_device("eth1", DeviceType::Ethernet, DeviceState::Disconnected)
+            .with_profile(build_ethernet_connection("eth1", &options()));
+        let old = backend.profiles()[0].0.clone();
+        let device = backend.get_devices().await.unwrap()[1].clone();
+        let wired = WiredOptions::new().with_mtu(9000);
+        let settings = build_wired_connection("eth1", &options(), &wired).unwrap();
+
+        activate_wired_on(&backend, &device, settings, None)
+            .await
+            .unwrap();
+
+        let profiles = backend.profiles();
+        assert_eq!(profiles.len(), 1);
+        assert_ne!(profiles[0].0, old);
+        let activations = backend.activations();
+        assert_eq!(activations.len(), 1);
+        assert_eq!(activations[0].device, device);
+        assert!(activations[0].added);
+    }
+
+    fn psk(psk: &str) -> WifiSecurity {
+        WifiSecurity::WpaPsk { psk: psk.into() }
+    }
+
+    fn wifi_backend() -> MockBackend {
+        MockBackend::new()
+            .with_device("eth0", DeviceType::Ethernet, DeviceState::Disconnected)
+            .with_device("wlan0", DeviceType::Wifi, DeviceState::Activated)
+    }
+
+    #[tokio::test]
+    async fn wifi_device_lookup_checks_interface_type() {
+        let backend = wifi_backend();
+        let wlan0 = backend.get_devices().await.unwrap()[1].clone();
+
+        assert_eq!(find_wifi_device(&backend, None).await.unwrap(), wlan0);
+        assert_eq!(
+            find_wifi_device(&backend, Some("wlan0")).await.unwrap(),
+            wlan0
+        );
+        assert!(matches!(
+            find_wifi_device(&backend, Some("eth0")).await,
+            Err(ConnectionError::NotAWifiDevice { interface }) if interface == "eth0"
+        ));
+        assert!(matches!(
+            find_wifi_device(&backend, Some("wlan9")).await,
+            Err(ConnectionError::WifiInterfaceNotFound { interface }) if interface == "wlan9"
+        ));
+    }
+
+    #[tokio::test]
+    async fn wifi_ap_resolution_filters_band_and_rescans() {
+        let backend = wifi_backend()
+            .with_access_point(
+                "wlan0",
+                MockAccessPoint::new("Home", "AA:00:00:00:00:01", 2412),
+            )
+            .with_access_point(
+                "wlan0",
+                MockAccessPoint::new("Home", "AA:00:00:00:00:02", 5180),
+            )
+            .with_access_point(
+                "wlan0",
+                MockAccessPoint::new("Late", "AA:00:00:00:00:03", 2437).with_visible_after_scans(4),
+            );
+        let device = find_wifi_device(&backend, None).await.unwrap();
+
+        let ap = scan_and_resolve_ap(
+            &backend,
+            &device,
+            "Home",
+            &WifiOptions::new().with_band(WifiBand::A),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            backend.access_point_bssid(&ap).await.unwrap(),
+            "AA:00:00:00:00:02"
+        );
+        assert_eq!(backend.scans("wlan0"), 1);
+
+        let no_retries = TimeoutConfig::new().with_scan_retries(0);
+        assert!(matches!(
+            scan_and_resolve_ap(
+                &backend,
+                &device,
+                "Late",
+                &WifiOptions::default(),
+                Some(no_retries)
+            )
+            .await,
+            Err(ConnectionError::NotFound)
+        ));
+
+        let retries = TimeoutConfig::new().with_scan_retries(3);
+        scan_and_resolve_ap(
+            &backend,
+            &device,
+            "Late",
+            &WifiOptions::default(),
+            Some(retries),
+        )
+        .await
+        .unwrap();
+        assert_eq!(backend.scans("wlan0"), 4);
+    }
+
+    #[tokio::test]
+    async fn wifi_bssid_lookup_reports_missing_bssid() {
+        let backend = wifi_backend().with_access_point(
+            "wlan0",
+            MockAccessPoint::new("Home", "AA:00:00:00:00:01", 2412),
+        );
+        let device = find_wifi_device(&backend, None).await.unwrap();
+
+        assert!(
+            find_ap_by_bssid(&backend, &device, "Home", "aa:00:00:00:00:01")
+                .await
+                .is_ok()
+        );
+        assert!(matches!(
+            find_ap_by_bssid(&backend, &device, "Home", "AA:00:00:00:00:09").await,
+            Err(ConnectionError::ApBssidNotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn wifi_new_profile_is_pinned_to_bssid() {
+        let backend = wifi_backend().with_access_point(
+            "wlan0",
+            MockAccessPoint::new("Home", "AA:00:00:00:00:01", 2412),
+        );
+        let device = find_wifi_device(&backend, None).await.unwrap();
+        let ap = backend.access_points(&device).await.unwrap()[0].clone();
+
+        activate_wifi(
+            &backend,
+            &device,
+            &ap,
+            "Home",
+            Some("AA:00:00:00:00:01"),
+            SavedDecision::RebuildFresh,
+            psk("hunter22"),
+            None,
+            &options(),
+            &WifiOptions::default(),
+        )
+        .await
+        .unwrap();
+
+        let activations = backend.activations();
+        assert_eq!(activations.len(), 1);
+        assert!(activations[0].added);
+        assert_eq!(activations[0].specific_object, ap);
+        let (_, settings) = &backend.profiles()[0];
+        assert_eq!(
+            settings["802-11-wireless"]["bssid"],
+            OwnedValue::try_from(Value::from("AA:00:00:00:00:01")).unwrap()
+        );
+        assert_eq!(
+            backend.device_state(&device).await.unwrap(),
+            u32::from(DeviceState::Activated)
+        );
+    }
+
+    #[tokio::test]
+    async fn wifi_failed_saved_profile_is_replaced() {
+        let backend = wifi_backend()
+            .with_access_point(
+                "wlan0",
+                MockAccessPoint::new("Home", "AA:00:00:00:00:01", 2412),
+            )
+            .with_profile(build_wifi_connection(
+                "Home",
+                &psk("old-password"),
+                &options(),
+            ))
+            .with_activation_failure(ConnectionError::AuthFailed);
+        let device = find_wifi_device(&backend, None).await.unwrap();
+        let ap = backend.access_points(&device).await.unwrap()[0].clone();
+
+        let saved = find_saved_connection(&backend, "Home", &MatchOptions::default())
+            .await
+            .unwrap()
+            .expect("profile is saved");
+        let decision = decide_saved_connection(Some(saved.clone()), &psk("")).unwrap();
+
+        activate_wifi(
+            &backend,
+            &device,
+            &ap,
+            "Home",
+            None,
+            decision,
+            psk(""),
+            None,
+            &options(),
+            &WifiOptions::default(),
+        )
+        .await
+        .unwrap();
+
+        let activations = backend.activations();
+        assert_eq!(activations.len(), 2);
+        assert_eq!(activations[0].connection, saved);
+        assert!(!activations[0].added);
+        assert!(activations[1].added);
+        assert_eq!(backend.deactivations(), [activations[0].active.clone()]);
+        assert!(backend.profiles().iter().all(|(path, _)| *path != saved));
+    }
+}
@@ -1,35 +1,68 @@
 //! Saved connection profile management.
 //!
 //! Provides functions for querying and deleting saved NetworkManager
-//! connection profiles. Saved connections persist across reboots and
-//! store credentials for automatic reconnection.
+//! connection profiles and reading their stored secrets. Saved connections
+//! persist across reboots and store credentials for automatic reconnection.
 
 use log::debug;
 use std::collections::HashMap;
 use zbus::Connection;
-use zvariant::{OwnedObjectPath, Value};
+use zvariant::{OwnedObjectPath, OwnedValue, Value};
 
 use crate::Result;
-use crate::api::models::ConnectionError;
-use crate::util::utils::{connection_settings_proxy, settings_proxy};
+#[cfg(feature = "qr")]
+use crate::api::models::WifiSecurity;
+use crate::api::models::{
+    ConnectionError, ConnectionSecrets, EapSecrets, MatchOptions, VpnSecrets, WifiSecrets,
+};
+use crate::backend::{DbusBackend, NmBackend, ProfileSettings};
+use crate::core::saved_connection::resolve_saved_path_by_uuid;
+use crate::dbus::{NMSettingsConnectionProxy, dbus_operation};
+#[cfg(feature = "qr")]
+use crate::qr::WifiQrCode;
+use crate::util::utils::connection_settings_proxy;
+#[cfg(feature = "qr")]
+use crate::util::utils::{decode_ssid_or_empty, property};
 use crate::util::validation::validate_ssid;
 
+/// Finds the D-Bus path of a saved connection by its exact connection ID.
+///
+/// Used for VPN, wired and Bluetooth profiles, whose names are never SSIDs.
+/// See [`get_saved_connection_path_with`] for Wi-Fi lookups.
+///
+/// Returns `None` if no saved connection has this ID.
+pub(crate) async fn get_saved_connection_path(
+    conn: &Connection,
+    name: &str,
+) -> Result<Option<OwnedObjectPath>> {
+    get_saved_connection_path_with(conn, name, &MatchOptions::new().with_ssid_match(false)).await
+}
+
 /// Finds the D-Bus path of a saved connection by SSID or connection name.
 ///
 /// Iterates through all saved connections in NetworkManager's settings
-/// and returns the path of the first one whose connection ID matches
-/// the given SSID or name.
+/// and returns the path of the first one that [`profile_matches`] `ssid`
+/// under `opts`.
 ///
 /// Note: This function is used for both WiFi SSIDs and VPN connection names.
 /// The validation enforces WiFi SSID rules (max 32 bytes), which is also
 /// reasonable for VPN connection names.
 ///
 /// Returns `None` if no saved connection exists for this SSID/name.
-pub(crate) async fn get_saved_connection_path(
+pub(crate) async fn get_saved_connection_path_with(
     conn: &Connection,
     ssid: &str,
+    opts: &MatchOptions,
+) -> Result<Option<OwnedObjectPath>> {
+    find_saved_connection(&DbusBackend::from_connection(conn.clone()), ssid, opts).await
+}
+
+/// [`get_saved_connection_path_with`] against any [`NmBackend`].
+pub(crate) async fn find_saved_connection<B: NmBackend + ?Sized>(
+    backend: &B,
+    ssid: &str,
+    opts: &MatchOptions,
 ) -> Result<Option<OwnedObjectPath>> {
-    // Validate the connection name/SSID
     if ssid.trim().is_empty() {
         return Ok(None);
     }
@@ -38,47 +71,67 @@ pub(crate) async fn get_saved_connection_path(
     // This applies to both WiFi SSIDs and connection names
     validate_ssid(ssid)?;
 
-    let settings = settings_proxy(conn).await?;
-
-    let reply = settings
-        .call_method("ListConnections", &())
-        .await
-        .map_err(|e| ConnectionError::DbusOperation {
-            context: "failed to list saved connections".to_string(),
-            source: e,
-        })?;
-
-    let conns: Vec<OwnedObjectPath> = reply.body().deserialize()?;
-
-    for cpath in conns {
-        let cproxy = connection_settings_proxy(conn, cpath.clone()).await?;
+    Ok(backend
+        .all_connection_settings()
+        .await?
+        .into_iter()
+        .find_map(|(cpath, owned)| {
+            profile_matches(&settings_values(owned), ssid, opts).then_some(cpath)
+        }))
+}
 
-        let msg = cproxy.call_method("GetSettings", &()).await.map_err(|e| {
-            ConnectionError::DbusOperation {
-                context: format!("failed to get settings for {}", cpath.as_str()),
-                source: e,
-            }
-        })?;
+/// Converts `GetSettings` output to the borrowed-value form
+/// [`profile_matches`] and the settings readers take.
+pub(crate) fn settings_values(
+    owned: ProfileSettings,
+) -> HashMap<String, HashMap<String, Value<'static>>> {
+    owned
+        .into_iter()
+        .map(|(k, v)| (k, v.into_iter().map(|(k, v)| (k, v.into())).collect()))
+        .collect()
+}
 
-        let body = msg.body();
-        let all: HashMap<String, HashMap<String, Value>> = body.deserialize()?;
+/// Reads the settings of every saved profile in one pass.
+///
+/// Lets callers that test many names against the saved profiles (e.g. the
+/// `known` flag in network listings) avoid one `ListConnections` walk per
+/// name. Profiles whose settings cannot be read are skipped.
+pub(crate) async fn all_saved_settings(
+    conn: &Connection,
+) -> Result<Vec<HashMap<String, HashMap<String, Value<'static>>>>> {
+    Ok(DbusBackend::from_connection(conn.clone())
+        .all_connection_settings()
+        .await?
+        .into_iter()
+        .map(|(_, owned)| settings_values(owned))
+        .collect())
+}
 
-        if let Some(conn_section) = all.get("connection")
-            && let Some(Value::Str(id)) = conn_section.get("id")
-            && id == ssid
-        {
-            return Ok(Some(cpath));
-        }
+/// Whether a profile's settings match `name` by connection ID or, for
+/// Wi-Fi profiles, by SSID bytes.
+pub(crate) fn profile_matches(
+    settings: &HashMap<String, HashMap<String, Value>>,
+    name: &str,
+    opts: &MatchOptions,
+) -> bool {
+    if let Some(conn_section) = settings.get("connection")
+        && let Some(Value::Str(id)) = conn_section.get("id")
+        && opts.matches(id, name)
+    {
+        return true;
     }
 
-    Ok(None)
-}
+    if let Some(wifi_section) = settings.get("802-11-wireless")
+        && let Some(Value::Array(arr)) = wifi_section.get("ssid")
+    {
+        let raw: Vec<u8> = arr
+            .iter()
+            .filter_map(|v| u8::try_from(v.clone()).ok())
+            .collect();
+        return opts.matches_ssid(&raw, name);
+    }
 
-/// Checks whether a saved connection exists for the given SSID.
-pub(crate) async fn has_saved_connection(conn: &Connection, ssid: &str) -> Result<bool> {
-    get_saved_connection_path(conn, ssid)
-        .await
-        .map(|p| p.is_some())
+    false
 }
 
 /// Deletes a saved connection by its D-Bus path.
@@ -88,14 +141,308 @@ pub(crate) async fn has_saved_connection(conn: &Connection, ssid: &str) -> Resul
 pub(crate) async fn delete_connection(conn: &Connection, conn_path: OwnedObjectPath) -> Result<()> {
     let cproxy = connection_settings_proxy(conn, conn_path.clone()).await?;
 
-    cproxy
-        .call_method("Delete", &())
-        .await
-        .map_err(|e| ConnectionError::DbusOperation {
-            context: format!("failed to delete connection {}", conn_path.as_str()),
-            source: e,
-        })?;
+    cproxy.call_method("Delete", &()).await.map_err(|e| {
+        dbus_operation(
+            format!("failed to delete connection {}", conn_path.as_str()),
+            e,
+        )
+    })?;
 
     debug!("Deleted connection: {}", conn_path.as_str());
     Ok(())
 }
+
+/// Reads the secrets of `setting_name` from the profile with `uuid`.
+///
+/// Wraps `Settings.Connection.GetSecrets`, which may trigger a polkit
+/// prompt and fails if the caller is not authorized.
+pub(crate) async fn get_secrets(
+    conn: &Connection,
+    uuid: &str,
+    setting_name: &str,
+) -> Result<ConnectionSecrets> {
+    let path = resolve_saved_path_by_uuid(conn, uuid).await?;
+    secrets_at_path(conn, path, setting_name).await
+}
+
+/// Returns the stored PSK of the saved Wi-Fi profile matching `ssid`.
+///
+/// `Ok(None)` means the profile exists but has no PSK NM can return
+/// (open or enterprise network, or an agent-owned secret).
+pub(crate) async fn get_saved_psk(
+    conn: &Connection,
+    ssid: &str,
+    opts: &MatchOptions,
+) -> Result<Option<String>> {
+    let path = get_saved_connection_path_with(conn, ssid, opts)
+        .await?
+        .ok_or(ConnectionError::NoSavedConnection)?;
+
+    match secrets_at_path(conn, path, "802-11-wireless-security").await? {
+        ConnectionSecrets::Wifi(w) => Ok(w.psk),
+        _ => Ok(None),
+    }
+}
+
+/// Builds the Wi-Fi QR code of the saved profile matching `ssid`, with
+/// its stored PSK.
+#[cfg(feature = "qr")]
+pub(crate) async fn saved_wifi_qr_code<B: NmBackend + ?Sized>(
+    conn: &Connection,
+    profiles: &B,
+    ssid: &str,
+    opts: &MatchOptions,
+) -> Result<WifiQrCode> {
+    let path = find_saved_connection(profiles, ssid, opts)
+        .await?
+        .ok_or(ConnectionError::NoSavedConnection)?;
+    let settings = profiles.connection_settings(&path).await?;
+    let unsupported = |reason: String| ConnectionError::InvalidInput {
+        field: "ssid".into(),
+        reason,
+    };
+
+    let wireless = settings
+        .get("802-11-wireless")
+        .ok_or_else(|| unsupported(format!("'{ssid}' is not a Wi-Fi profile")))?;
+    let ssid_bytes: Vec<u8> = property(wireless, "ssid").unwrap_or_default();
+    let hidden = property(wireless, "hidden").unwrap_or(false);
+    let key_mgmt: Option<String> = settings
+        .get("802-11-wireless-security")
+        .and_then(|sec| property(sec, "key-mgmt"));
+
+    let security = match key_mgmt.as_deref() {
+        None | Some("owe") => WifiSecurity::Open,
+        Some("wpa-psk" | "sae") => {
+            let psk = match secrets_at_path(conn, path, "802-11-wireless-security").await? {
+                ConnectionSecrets::Wifi(w) => w.psk,
+                _ => None,
+            };
+            WifiSecurity::WpaPsk {
+                psk: psk.ok_or(ConnectionError::MissingPassword)?,
+            }
+        }
+        Some(other) => {
+            return Err(unsupported(format!(
+                "'{ssid}' uses key management '{other}', which a QR code cannot carry"
+            )));
+        }
+    };
+
+    WifiQrCode::new(decode_ssid_or_empty(&ssid_bytes), security)
+        .map(|code| code.with_hidden(hidden))
+        .map_err(|e| unsupported(e.to_string()))
+}
+
+async fn secrets_at_path(
+    conn: &Connection,
+    path: OwnedObjectPath,
+    setting_name: &str,
+) -> Result<ConnectionSecrets> {
+    let proxy = NMSettingsConnectionProxy::builder(conn)
+        .path(path.clone())
+        .map_err(ConnectionError::Dbus)?
+        .build()
+        .await
+        .map_err(|e| dbus_operation("failed to build Settings.Connection proxy", e))?;
+
+    let all = proxy.get_secrets(setting_name).await.map_err(|e| {
+        dbus_operation(
+            format!("GetSecrets({setting_name}) failed for {}", path.as_str()),
+            e,
+        )
+    })?;
+
+    debug!(
+        "Read secrets for setting {setting_name} of {}",
+        path.as_str()
+    );
+    Ok(parse_secrets(setting_name, &all))
+}
+
+fn secret_str(section: &HashMap<String, OwnedValue>, key: &str) -> Option<String> {
+    String::try_from(section.get(key)?.clone())
+        .ok()
+        .filter(|s| !s.is_empty())
+}
+
+/// Converts a `GetSecrets` reply into typed secrets for `setting_name`.
+pub(crate) fn parse_secrets(
+    setting_name: &str,
+    all: &HashMap<String, HashMap<String, OwnedValue>>,
+) -> ConnectionSecrets {
+    let empty = HashMap::new();
+    let section = all.get(setting_name).unwrap_or(&empty);
+
+    match setting_name {
+        "802-11-wireless-security" => ConnectionSecrets::Wifi(WifiSecrets {
+            psk: secret_str(section, "psk"),
+            wep_keys: std::array::from_fn(|i| secret_str(section, &format!("wep-key{i}"))),
+            leap_password: secret_str(section, "leap-password"),
+        }),
+        "802-1x" => ConnectionSecrets::Eap(EapSecrets {
+            password: secret_str(section, "password"),
+            private_key_password: secret_str(section, "private-key-password"),
+            phase2_private_key_password: secret_str(section, "phase2-private-key-password"),
+            pin: secret_str(section, "pin"),
+        }),
+        "vpn" => ConnectionSecrets::Vpn(VpnSecrets {
+            secrets: section
+                .get("secrets")
+                .and_then(|v| HashMap::<String, String>::try_from(v.clone()).ok())
+                .unwrap_or_default(),
+        }),
+        other => ConnectionSecrets::Other {
+            setting: other.to_string(),
+            values: section
+                .keys()
+                .filter_map(|k| Some((k.clone(), secret_str(section, k)?)))
+                .collect(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wifi_profile(id: &str, ssid: &[u8]) -> HashMap<String, HashMap<String, Value<'static>>> {
+        let mut connection = HashMap::new();
+        connection.insert("id".to_string(), Value::from(id.to_string()));
+        let mut wireless = HashMap::new();
+        wireless.insert("ssid".to_string(), Value::from(ssid.to_vec()));
+        HashMap::from([
+            ("connection".to_string(), connection),
+            ("802-11-wireless".to_string(), wireless),
+        ])
+    }
+
+    #[test]
+    fn matches_by_id_or_ssid() {
+        let profile = wifi_profile("Home 1", b"Home");
+        let opts = MatchOptions::default();
+        assert!(profile_matches(&profile, "Home 1", &opts));
+        assert!(profile_matches(&profile, "Home", &opts));
+        assert!(!profile_matches(&profile, "home", &opts));
+    }
+
+    #[test]
+    fn ssid_match_can_be_disabled() {
+        let profile = wifi_profile("Home 1", b"Home");
+        let opts = MatchOptions::new().with_ssid_match(false);
+        assert!(!profile_matches(&profile, "Home", &opts));
+        assert!(profile_matches(&profile, "Home 1", &opts));
+    }
+
+    #[test]
+    fn normalized_id_match() {
+        let profile = wifi_profile(" Office ", b"corp-5g");
+        let opts = MatchOptions::new()
+            .with_case_insensitive(true)
+            .with_trim_whitespace(true);
+        assert!(profile_matches(&profile, "office", &opts));
+        assert!(profile_matches(&profile, "CORP-5G", &opts));
+    }
+
+    fn secrets_reply(
+        setting: &str,
+        entries: Vec<(&str, Value<'static>)>,
+    ) -> HashMap<String, HashMap<String, OwnedValue>> {
+        let section = entries
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), OwnedValue::try_from(v).unwrap()))
+            .collect();
+        HashMap::from([(setting.to_string(), section)])
+    }
+
+    #[tokio::test]
+    async fn finds_saved_profile_through_backend() {
+        use crate::api::builders::wifi::build_ethernet_connection;
+        use crate::api::models::ConnectionOptions;
+        use crate::backend::MockBackend;
+
+        let opts = ConnectionOptions::new(true);
+        let backend = MockBackend::new()
+            .with_profile(build_ethernet_connection("eth0", &opts))
+            .with_profile(build_ethernet_connection("eth1", &opts));
+        let eth1 = backend.profiles()[1].0.clone();
+
+        let exact = MatchOptions::new().with_ssid_match(false);
+        assert_eq!(
+            find_saved_connection(&backend, "eth1", &exact)
+                .await
+                .unwrap(),
+            Some(eth1)
+        );
+        assert_eq!(
+            find_saved_connection(&backend, "eth2", &exact)
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            find_saved_connection(&backend, " ", &exact).await.unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn parses_wifi_psk_and_wep_keys() {
+        let reply = secrets_reply(
+            "802-11-wireless-security",
+            vec![
+                ("psk", Value::from("hunter22")),
+                ("wep-key2", Value::from("abcde")),
+            ],
+        );
+        let ConnectionSecrets::Wifi(w) = parse_secrets("802-11-wireless-security", &reply) else {
+            panic!("expected Wi-Fi secrets");
+        };
+        assert_eq!(w.psk.as_deref(), Some("hunter22"));
+        assert_eq!(w.wep_keys[2].as_deref(), Some("abcde"));
+        assert!(w.wep_keys[0].is_none());
+    }
+
+    #[test]
+    fn empty_reply_has_no_psk() {
+        let reply = HashMap::new();
+        assert_eq!(
+            parse_secrets("802-11-wireless-security", &reply),
+            ConnectionSecrets::Wifi(WifiSecrets::default())
+        );
+    }
+
+    #[test]
+    fn parses_eap_and_vpn_secrets() {
+        let reply = secrets_reply("802-1x", vec![("password", Value::from("s3cret"))]);
+        let ConnectionSecrets::Eap(e) = parse_secrets("802-1x", &reply) else {
+            panic!("expected EAP secrets");
+        };
+        assert_eq!(e.password.as_deref(), Some("s3cret"));
+
+        let vpn = HashMap::from([("password".to_string(), "tok".to_string())]);
+        let reply = secrets_reply("vpn", vec![("secrets", Value::from(vpn.clone()))]);
+        assert_eq!(
+            parse_secrets("vpn", &reply),
+            ConnectionSecrets::Vpn(VpnSecrets { secrets: vpn })
+        );
+    }
+
+    #[test]
+    fn other_setting_keeps_string_values() {
+        let reply = secrets_reply(
+            "wireguard",
+            vec![
+                ("private-key", Value::from("KEY=")),
+                ("listen-port", Value::from(51820u32)),
+            ],
+        );
+        let ConnectionSecrets::Other { setting, values } = parse_secrets("wireguard", &reply)
+        else {
+            panic!("expected other secrets");
+        };
+        assert_eq!(setting, "wireguard");
+        assert_eq!(values.len(), 1);
+        assert_eq!(values["private-key"], "KEY=");
+    }
+}
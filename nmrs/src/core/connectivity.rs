@@ -5,7 +5,9 @@ use zbus::Connection;
 
 use crate::Result;
 use crate::api::models::{ConnectionError, ConnectivityReport, ConnectivityState};
-use crate::dbus::NMProxy;
+#[cfg(feature = "captive-portal")]
+use crate::captive_portal::PortalProbe;
+use crate::dbus::{NMProxy, dbus_operation};
 
 /// Reads `Connectivity` property.
 pub(crate) async fn connectivity(conn: &Connection) -> Result<ConnectivityState> {
@@ -13,10 +15,7 @@ pub(crate) async fn connectivity(conn: &Connection) -> Result<ConnectivityState>
     let raw = nm
         .connectivity()
         .await
-        .map_err(|e| ConnectionError::DbusOperation {
-            context: "read Connectivity property".into(),
-            source: e,
-        })?;
+        .map_err(|e| dbus_operation("read Connectivity property", e))?;
     Ok(ConnectivityState::from(raw))
 }
 
@@ -32,19 +31,43 @@ pub(crate) async fn check_connectivity(conn: &Connection) -> Result<Connectivity
     let raw = nm
         .check_connectivity()
         .await
-        .map_err(|e| ConnectionError::DbusOperation {
-            context: "CheckConnectivity call".into(),
-            source: e,
-        })?;
+        .map_err(|e| dbus_operation("CheckConnectivity call", e))?;
     Ok(ConnectivityState::from(raw))
 }
 
+/// Reads `ConnectivityCheckAvailable`.
+pub(crate) async fn connectivity_check_available(conn: &Connection) -> Result<bool> {
+    let nm = NMProxy::new(conn).await?;
+    nm.connectivity_check_available()
+        .await
+        .map_err(|e| dbus_operation("read ConnectivityCheckAvailable property", e))
+}
+
+/// Reads `ConnectivityCheckEnabled`.
+pub(crate) async fn connectivity_check_enabled(conn: &Connection) -> Result<bool> {
+    let nm = NMProxy::new(conn).await?;
+    nm.connectivity_check_enabled()
+        .await
+        .map_err(|e| dbus_operation("read ConnectivityCheckEnabled property", e))
+}
+
+/// Writes `ConnectivityCheckEnabled`.
+pub(crate) async fn set_connectivity_check_enabled(conn: &Connection, enabled: bool) -> Result<()> {
+    let nm = NMProxy::new(conn).await?;
+    nm.set_connectivity_check_enabled(enabled)
+        .await
+        .map_err(|e| dbus_operation(format!("set ConnectivityCheckEnabled to {enabled}"), e))?;
+    debug!("Connectivity checking enabled: {enabled}");
+    Ok(())
+}
+
 /// Builds a full [`ConnectivityReport`] from property reads.
 pub(crate) async fn connectivity_report(conn: &Connection) -> Result<ConnectivityReport> {
     let nm = NMProxy::new(conn).await?;
 
     let raw_state = nm.connectivity().await.unwrap_or(0);
     let state = ConnectivityState::from(raw_state);
+    let check_available = nm.connectivity_check_available().await.unwrap_or(false);
     let check_enabled = nm.connectivity_check_enabled().await.unwrap_or(false);
     let check_uri = nm
         .connectivity_check_uri()
@@ -60,12 +83,45 @@ pub(crate) async fn connectivity_report(conn: &Connection) -> Result<Connectivit
 
     Ok(ConnectivityReport {
         state,
+        check_available,
         check_enabled,
         check_uri,
         captive_portal_url,
     })
 }
 
+/// Re-checks connectivity and, behind a portal, resolves its login URL.
+///
+/// When NM's own checks are disabled the probe alone decides. When NM
+/// reports a portal but the probe cannot pin down the login page, NM's
+/// portal URL is used, then the probe URL.
+#[cfg(feature = "captive-portal")]
+pub(crate) async fn detect_captive_portal(
+    conn: &Connection,
+    probe: &PortalProbe,
+) -> Result<Option<String>> {
+    let state = match check_connectivity(conn).await {
+        Ok(state) => state,
+        Err(ConnectionError::ConnectivityCheckDisabled) => return probe.resolve().await,
+        Err(e) => return Err(e),
+    };
+    if !state.is_captive() {
+        return Ok(None);
+    }
+
+    match probe.resolve().await {
+        Ok(Some(url)) => return Ok(Some(url)),
+        Ok(None) => debug!("NM reports a portal but the probe was not intercepted"),
+        Err(e) => debug!("Portal probe failed: {e}"),
+    }
+    let nm = NMProxy::new(conn).await?;
+    Ok(Some(
+        detect_captive_portal_url(conn, &nm)
+            .await
+            .unwrap_or_else(|| probe.url.clone()),
+    ))
+}
+
 /// Best-effort captive portal URL detection.
 ///
 /// Tries NM's `Ip4Config` properties on the primary connection first
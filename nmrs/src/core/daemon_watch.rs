@@ -0,0 +1,253 @@
+//! Detection of NetworkManager daemon restarts.
+//!
+//! NetworkManager owns the bus name `org.freedesktop.NetworkManager`; when
+//! the daemon exits or comes back the bus broadcasts `NameOwnerChanged` for
+//! it. A restarted daemon renumbers its device, access point and active
+//! connection objects, so subscriptions made against the old instance never
+//! fire again and have to be re-established. [`Resubscribing`] does that
+//! for the callback monitors.
+
+use std::future::Future;
+
+use futures::stream::{BoxStream, SelectAll, Stream, StreamExt};
+use log::{debug, warn};
+use tokio::select;
+use zbus::Connection;
+use zbus::fdo::DBusProxy;
+
+use crate::Result;
+use crate::api::models::DaemonEvent;
+
+/// Well-known bus name of the NetworkManager daemon.
+pub(crate) const NM_BUS_NAME: &str = "org.freedesktop.NetworkManager";
+
+/// Streams NetworkManager stop and (re)start events.
+pub(crate) async fn daemon_events(
+    conn: &Connection,
+) -> Result<impl Stream<Item = DaemonEvent> + Send + 'static> {
+    let dbus = DBusProxy::new(conn).await?;
+    let changes = dbus
+        .receive_name_owner_changed_with_args(&[(0, NM_BUS_NAME)])
+        .await?;
+
+    Ok(changes.filter_map(|signal| async move {
+        let args = signal.args().ok()?;
+        let event = daemon_event(
+            args.old_owner.as_ref().map(|o| o.as_str()),
+            args.new_owner.as_ref().map(|o| o.as_str()),
+        )?;
+        debug!("NetworkManager daemon event: {event:?}");
+        Some(event)
+    }))
+}
+
+/// Maps an owner change of [`NM_BUS_NAME`] to a daemon event.
+///
+/// Losing the owner means the daemon stopped; gaining one (or a new owner
+/// replacing the old) means a fresh instance is on the bus.
+fn daemon_event(old_owner: Option<&str>, new_owner: Option<&str>) -> Option<DaemonEvent> {
+    match (old_owner, new_owner) {
+        (_, None) => Some(DaemonEvent::Stopped),
+        (old, Some(new)) if old != Some(new) => Some(DaemonEvent::Restarted),
+        _ => None,
+    }
+}
+
+/// Item of a [`Resubscribing`] signal set.
+pub(crate) enum Resubscribed<T, S> {
+    /// A signal from the current subscription.
+    Signal(T),
+    /// The daemon restarted and the subscription was re-established,
+    /// returning this fresh state.
+    Restarted(S),
+}
+
+/// Signal subscriptions that are re-established when the daemon restarts.
+///
+/// `subscribe` returns the signal streams and whatever state the monitor
+/// derives while subscribing (e.g. the objects it is watching). It runs
+/// once up front and again after every [`DaemonEvent::Restarted`]; if a
+/// re-subscription fails, the old streams are kept until the next restart.
+pub(crate) struct Resubscribing<T, F> {
+    restarts: BoxStream<'static, DaemonEvent>,
+    subscribe: F,
+    signals: SelectAll<BoxStream<'static, T>>,
+}
+
+impl<T, S, F, Fut> Resubscribing<T, F>
+where
+    T: Send + 'static,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<(Vec<BoxStream<'static, T>>, S)>>,
+{
+    /// Subscribes, watching `conn` for daemon restarts.
+    pub(crate) async fn new(conn: &Connection, subscribe: F) -> Result<(Self, S)> {
+        let restarts = daemon_events(conn).await?.boxed();
+        Self::with_restarts(restarts, subscribe).await
+    }
+
+    async fn with_restarts(
+        restarts: BoxStream<'static, DaemonEvent>,
+        mut subscribe: F,
+    ) -> Result<(Self, S)> {
+        let (signals, state) = subscribe().await?;
+        let signals = futures::stream::select_all(signals);
+        Ok((
+            Self {
+                restarts,
+                subscribe,
+                signals,
+            },
+            state,
+        ))
+    }
+
+    /// Adds a stream to the current subscription.
+    pub(crate) fn push(&mut self, signals: BoxStream<'static, T>) {
+        self.signals.push(signals);
+    }
+
+    /// Number of streams in the current subscription.
+    pub(crate) fn len(&self) -> usize {
+        self.signals.len()
+    }
+
+    /// Waits for the next signal or restart; `None` once every stream of
+    /// the current subscription has ended.
+    pub(crate) async fn next(&mut self) -> Option<Resubscribed<T, S>> {
+        loop {
+            select! {
+                Some(event) = self.restarts.next() => {
+                    if event != DaemonEvent::Restarted {
+                        continue;
+                    }
+                    match (self.subscribe)().await {
+                        Ok((signals, state)) => {
+                            debug!("Re-subscribed {} signal streams", signals.len());
+                            self.signals = futures::stream::select_all(signals);
+                            return Some(Resubscribed::Restarted(state));
+                        }
+                        Err(e) => warn!("Failed to re-subscribe after NetworkManager restart: {e}"),
+                    }
+                }
+                signal = self.signals.next() => return signal.map(Resubscribed::Signal),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use futures::channel::mpsc;
+    use futures::stream;
+
+    use crate::api::models::ConnectionError;
+
+    /// Restarts fed from a channel, and a subscription whose `n`-th call
+    /// emits `n` then stays open; calls listed in `failing` fail instead.
+    async fn counting(
+        failing: &'static [usize],
+    ) -> (
+        mpsc::UnboundedSender<DaemonEvent>,
+        Resubscribing<usize, impl FnMut() -> futures::future::Ready<SubscribeResult>>,
+    ) {
+        let (owner_changes, restarts) = mpsc::unbounded();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let subscribe = move || {
+            let n = calls.fetch_add(1, Ordering::SeqCst);
+            futures::future::ready(if failing.contains(&n) {
+                Err(ConnectionError::Stuck("NetworkManager not ready".into()))
+            } else {
+                let signals = stream::iter([n]).chain(stream::pending()).boxed();
+                Ok((vec![signals], n))
+            })
+        };
+        let (signals, first) = Resubscribing::with_restarts(restarts.boxed(), subscribe)
+            .await
+            .unwrap();
+        assert_eq!(first, 0);
+        (owner_changes, signals)
+    }
+
+    type SubscribeResult = Result<(Vec<BoxStream<'static, usize>>, usize)>;
+
+    fn owner_change(old: Option<&str>, new: Option<&str>) -> DaemonEvent {
+        daemon_event(old, new).unwrap()
+    }
+
+    #[tokio::test]
+    async fn resubscribes_when_the_owner_changes() {
+        let (owner_changes, mut signals) = counting(&[]).await;
+        assert!(matches!(
+            signals.next().await,
+            Some(Resubscribed::Signal(0))
+        ));
+
+        owner_changes
+            .unbounded_send(owner_change(Some(":1.5"), None))
+            .unwrap();
+        owner_changes
+            .unbounded_send(owner_change(None, Some(":1.9")))
+            .unwrap();
+        assert!(matches!(
+            signals.next().await,
+            Some(Resubscribed::Restarted(1))
+        ));
+        assert!(matches!(
+            signals.next().await,
+            Some(Resubscribed::Signal(1))
+        ));
+
+        owner_changes
+            .unbounded_send(owner_change(Some(":1.9"), Some(":1.12")))
+            .unwrap();
+        assert!(matches!(
+            signals.next().await,
+            Some(Resubscribed::Restarted(2))
+        ));
+        assert_eq!(signals.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn keeps_the_old_streams_when_resubscribing_fails() {
+        let (owner_changes, mut signals) = counting(&[1]).await;
+        assert!(matches!(
+            signals.next().await,
+            Some(Resubscribed::Signal(0))
+        ));
+
+        owner_changes
+            .unbounded_send(owner_change(None, Some(":1.9")))
+            .unwrap();
+        owner_changes
+            .unbounded_send(owner_change(Some(":1.9"), Some(":1.12")))
+            .unwrap();
+        // The first re-subscription fails; the second one takes over.
+        assert!(matches!(
+            signals.next().await,
+            Some(Resubscribed::Restarted(2))
+        ));
+        assert!(matches!(
+            signals.next().await,
+            Some(Resubscribed::Signal(2))
+        ));
+    }
+
+    #[test]
+    fn owner_changes_map_to_daemon_events() {
+        assert_eq!(daemon_event(Some(":1.5"), None), Some(DaemonEvent::Stopped));
+        assert_eq!(
+            daemon_event(None, Some(":1.9")),
+            Some(DaemonEvent::Restarted)
+        );
+        assert_eq!(
+            daemon_event(Some(":1.5"), Some(":1.9")),
+            Some(DaemonEvent::Restarted)
+        );
+        assert_eq!(daemon_event(Some(":1.5"), Some(":1.5")), None);
+    }
+}
@@ -8,10 +8,14 @@ use log::{debug, warn};
 use zbus::Connection;
 
 use crate::Result;
-use crate::api::models::{BluetoothDevice, ConnectionError, Device, DeviceIdentity, DeviceState};
+use crate::api::models::{
+    BluetoothDevice, ConnectionError, Device, DeviceIdentity, DeviceState, StateReason,
+    UnavailableReason, UnmanagedDevice, UnmanagedReason,
+};
 use crate::core::bluetooth::populate_bluez_info;
+use crate::core::rfkill::read_rfkill;
 use crate::core::state_wait::wait_for_wifi_device_ready;
-use crate::dbus::{NMBluetoothProxy, NMDeviceProxy, NMProxy};
+use crate::dbus::{NMBluetoothProxy, NMDeviceProxy, NMProxy, NMWiredProxy, dbus_operation};
 use crate::types::constants::device_type;
 use crate::util::utils::get_ip_addresses_from_active_connection;
 
@@ -24,114 +28,239 @@ pub(crate) async fn list_devices(conn: &Connection) -> Result<Vec<Device>> {
     let paths = proxy
         .get_devices()
         .await
-        .map_err(|e| ConnectionError::DbusOperation {
-            context: "failed to get device paths from NetworkManager".to_string(),
-            source: e,
-        })?;
+        .map_err(|e| dbus_operation("failed to get device paths from NetworkManager", e))?;
 
     let mut devices = Vec::new();
     for p in paths {
-        let d_proxy = NMDeviceProxy::builder(conn)
-            .path(p.clone())?
-            .build()
-            .await?;
+        devices.push(read_device(conn, &proxy, p).await?);
+    }
+    Ok(devices)
+}
 
-        let interface = d_proxy
-            .interface()
-            .await
-            .map_err(|e| ConnectionError::DbusOperation {
-                context: format!("failed to get interface name for device {}", p.as_str()),
-                source: e,
-            })?;
+/// Reads the full state of the device at `p`.
+async fn read_device(
+    conn: &Connection,
+    proxy: &NMProxy<'_>,
+    p: zvariant::OwnedObjectPath,
+) -> Result<Device> {
+    let d_proxy = NMDeviceProxy::builder(conn)
+        .path(p.clone())?
+        .build()
+        .await?;
+
+    let interface = d_proxy.interface().await.map_err(|e| {
+        dbus_operation(
+            format!("failed to get interface name for device {}", p.as_str()),
+            e,
+        )
+    })?;
+
+    let raw_type = d_proxy
+        .device_type()
+        .await
+        .map_err(|e| dbus_operation(format!("failed to get device type for {}", interface), e))?;
+    let current_mac = match d_proxy.hw_address().await {
+        Ok(addr) => addr,
+        Err(e) => {
+            warn!(
+                "Failed to get hardware address for device {}: {}",
+                interface, e
+            );
+            String::from("00:00:00:00:00:00")
+        }
+    };
+
+    let perm_mac = match d_proxy.perm_hw_address().await {
+        Ok(addr) => addr,
+        Err(e) => {
+            debug!(
+                "Permanent hardware address not available for device {}: {}",
+                interface, e
+            );
+            current_mac.clone()
+        }
+    };
+
+    let device_type = raw_type.into();
+    let raw_state = d_proxy.state().await?;
+    let state: DeviceState = raw_state.into();
+    let state_reason = match d_proxy.state_reason().await {
+        Ok((_, reason)) => StateReason::from(reason),
+        Err(e) => {
+            debug!("Failed to get state reason for device {}: {}", interface, e);
+            StateReason::Unknown
+        }
+    };
+    let managed = match d_proxy.managed().await {
+        Ok(m) => Some(m),
+        Err(e) => {
+            debug!(
+                "Failed to get 'managed' property for device {}: {}",
+                interface, e
+            );
+            None
+        }
+    };
+    let driver = match d_proxy.driver().await {
+        Ok(d) => Some(d),
+        Err(e) => {
+            debug!("Failed to get driver for device {}: {}", interface, e);
+            None
+        }
+    };
 
-        let raw_type = d_proxy
-            .device_type()
-            .await
-            .map_err(|e| ConnectionError::DbusOperation {
-                context: format!("failed to get device type for {}", interface),
-                source: e,
-            })?;
-        let current_mac = match d_proxy.hw_address().await {
-            Ok(addr) => addr,
-            Err(e) => {
-                warn!(
-                    "Failed to get hardware address for device {}: {}",
-                    interface, e
-                );
-                String::from("00:00:00:00:00:00")
-            }
-        };
+    // Get IP addresses from active connection
+    let (ip4_address, ip6_address) = if let Ok(active_conn_path) = d_proxy.active_connection().await
+    {
+        if active_conn_path.as_str() != "/" {
+            get_ip_addresses_from_active_connection(conn, &active_conn_path).await
+        } else {
+            (None, None)
+        }
+    } else {
+        (None, None)
+    };
+
+    // Avoiding this breaking change for now
+    // Get link speed for wired devices
+    /* let speed = if raw_type == device_type::ETHERNET {
+        async {
+            let wired = NMWiredProxy::builder(conn).path(p.clone())?.build().await?;
+            wired.speed().await
+        }
+        .await
+        .ok()
+    } else {
+        None
+    };*/
+    let unavailable_reason = if state == DeviceState::Unavailable {
+        Some(unavailable_reason(conn, proxy, &d_proxy, &p, raw_type).await)
+    } else {
+        None
+    };
+
+    Ok(Device {
+        path: p.to_string(),
+        interface,
+        identity: DeviceIdentity::new(perm_mac, current_mac),
+        device_type,
+        state,
+        state_reason,
+        managed,
+        driver,
+        ip4_address,
+        ip6_address,
+        unavailable_reason,
+        // speed,
+    })
+}
 
-        let perm_mac = match d_proxy.perm_hw_address().await {
-            Ok(addr) => addr,
-            Err(e) => {
-                debug!(
-                    "Permanent hardware address not available for device {}: {}",
-                    interface, e
-                );
-                current_mac.clone()
-            }
-        };
+/// Finds the device whose hardware matches `identity`.
+///
+/// Matching uses [`DeviceIdentity::same_hardware`], so the device is found
+/// even if its interface was renamed (e.g. `wlan0` re-enumerated as `wlan1`
+/// after a USB re-plug). Returns `None` if no such device is present.
+pub(crate) async fn find_device_by_identity(
+    conn: &Connection,
+    identity: &DeviceIdentity,
+) -> Result<Option<Device>> {
+    let Some((path, _)) = find_binding_by_identity(conn, identity).await? else {
+        return Ok(None);
+    };
+    let proxy = NMProxy::new(conn).await?;
+    read_device(conn, &proxy, path).await.map(Some)
+}
 
-        let device_type = raw_type.into();
-        let raw_state = d_proxy.state().await?;
-        let state = raw_state.into();
-        let managed = match d_proxy.managed().await {
-            Ok(m) => Some(m),
-            Err(e) => {
-                debug!(
-                    "Failed to get 'managed' property for device {}: {}",
-                    interface, e
-                );
-                None
-            }
-        };
-        let driver = match d_proxy.driver().await {
-            Ok(d) => Some(d),
-            Err(e) => {
-                debug!("Failed to get driver for device {}: {}", interface, e);
-                None
-            }
-        };
+/// Reads the interface name and hardware identity of the device at `path`.
+///
+/// A missing permanent MAC falls back to the current one, as in
+/// [`list_devices`].
+pub(crate) async fn device_binding(
+    conn: &Connection,
+    path: &zvariant::OwnedObjectPath,
+) -> zbus::Result<(String, DeviceIdentity)> {
+    let dev = NMDeviceProxy::builder(conn)
+        .path(path.clone())?
+        .build()
+        .await?;
+    let interface = dev.interface().await?;
+    let current_mac = dev.hw_address().await.unwrap_or_default();
+    let perm_mac = dev
+        .perm_hw_address()
+        .await
+        .unwrap_or_else(|_| current_mac.clone());
+    Ok((interface, DeviceIdentity::new(perm_mac, current_mac)))
+}
 
-        // Get IP addresses from active connection
-        let (ip4_address, ip6_address) =
-            if let Ok(active_conn_path) = d_proxy.active_connection().await {
-                if active_conn_path.as_str() != "/" {
-                    get_ip_addresses_from_active_connection(conn, &active_conn_path).await
-                } else {
-                    (None, None)
-                }
-            } else {
-                (None, None)
-            };
-
-        // Avoiding this breaking change for now
-        // Get link speed for wired devices
-        /* let speed = if raw_type == device_type::ETHERNET {
-            async {
-                let wired = NMWiredProxy::builder(conn).path(p.clone())?.build().await?;
-                wired.speed().await
-            }
-            .await
-            .ok()
-        } else {
-            None
-        };*/
-        devices.push(Device {
-            path: p.to_string(),
-            interface,
-            identity: DeviceIdentity::new(perm_mac, current_mac),
-            device_type,
-            state,
-            managed,
-            driver,
-            ip4_address,
-            ip6_address,
-            // speed,
-        });
+/// Finds the object path and interface name of the device matching
+/// `identity`, without reading the rest of its state.
+///
+/// This is the one place devices are matched by identity;
+/// [`find_device_by_identity`] and [`DeviceHandle`](crate::DeviceHandle)
+/// both go through it.
+pub(crate) async fn find_binding_by_identity(
+    conn: &Connection,
+    identity: &DeviceIdentity,
+) -> Result<Option<(zvariant::OwnedObjectPath, String)>> {
+    let nm = NMProxy::new(conn).await?;
+    let paths = nm
+        .get_devices()
+        .await
+        .map_err(|e| dbus_operation("failed to get device paths from NetworkManager", e))?;
+    for path in paths {
+        if let Ok((interface, found)) = device_binding(conn, &path).await
+            && found.same_hardware(identity)
+        {
+            return Ok(Some((path, interface)));
+        }
     }
-    Ok(devices)
+    Ok(None)
+}
+
+/// Works out why an `Unavailable` device cannot be used.
+///
+/// Checks `FirmwareMissing` / `NmPluginMissing`, then the radio state
+/// (NM's enabled flags plus the kernel rfkill hard block) for Wi-Fi and
+/// WWAN devices, and the carrier for wired devices.
+async fn unavailable_reason(
+    conn: &Connection,
+    nm: &NMProxy<'_>,
+    dev: &NMDeviceProxy<'_>,
+    path: &zvariant::OwnedObjectPath,
+    raw_type: u32,
+) -> UnavailableReason {
+    let firmware_missing = dev.firmware_missing().await.unwrap_or(false);
+    let plugin_missing = dev.nm_plugin_missing().await.unwrap_or(false);
+
+    let radio = match raw_type {
+        device_type::WIFI => {
+            let rfkill = read_rfkill();
+            let enabled = nm.wireless_enabled().await.unwrap_or(true);
+            let hw_enabled = nm.wireless_hardware_enabled().await.unwrap_or(true);
+            Some((enabled, hw_enabled && !rfkill.wlan_hard_block))
+        }
+        device_type::MODEM => {
+            let rfkill = read_rfkill();
+            let enabled = nm.wwan_enabled().await.unwrap_or(true);
+            let hw_enabled = nm.wwan_hardware_enabled().await.unwrap_or(true);
+            Some((enabled, hw_enabled && !rfkill.wwan_hard_block))
+        }
+        _ => None,
+    };
+
+    let carrier = if raw_type == device_type::ETHERNET {
+        match NMWiredProxy::builder(conn).path(path.clone()) {
+            Ok(builder) => match builder.build().await {
+                Ok(wired) => wired.carrier().await.ok(),
+                Err(_) => None,
+            },
+            Err(_) => None,
+        }
+    } else {
+        None
+    };
+
+    UnavailableReason::classify(firmware_missing, plugin_missing, radio, carrier)
 }
 
 /// Returns `true` if any network device is in a transitional state
@@ -148,13 +277,9 @@ pub(crate) async fn is_connecting(conn: &Connection) -> Result<bool> {
             .build()
             .await?;
 
-        let raw_state = dev
-            .state()
-            .await
-            .map_err(|e| ConnectionError::DbusOperation {
-                context: format!("failed to get state for device {}", dp.as_str()),
-                source: e,
-            })?;
+        let raw_state = dev.state().await.map_err(|e| {
+            dbus_operation(format!("failed to get state for device {}", dp.as_str()), e)
+        })?;
 
         let state: DeviceState = raw_state.into();
         if state.is_transitional() {
@@ -178,16 +303,15 @@ pub(crate) async fn list_bluetooth_devices(conn: &Connection) -> Result<Vec<Blue
             .await?;
 
         // Only process Bluetooth devices
-        let dev_type = d_proxy
-            .device_type()
-            .await
-            .map_err(|e| ConnectionError::DbusOperation {
-                context: format!(
+        let dev_type = d_proxy.device_type().await.map_err(|e| {
+            dbus_operation(
+                format!(
                     "failed to get device type for {} during Bluetooth scan",
                     p.as_str()
                 ),
-                source: e,
-            })?;
+                e,
+            )
+        })?;
 
         if dev_type != device_type::BLUETOOTH {
             continue;
@@ -262,6 +386,43 @@ pub(crate) async fn wait_for_wifi_ready(conn: &Connection) -> Result<()> {
     Err(ConnectionError::NoWifiDevice)
 }
 
+/// Lists devices NetworkManager knows about but does not manage.
+///
+/// A device is considered unmanaged when it is in the `Unmanaged` state or
+/// its `Managed` property is `false`. The reason is decoded from the
+/// device's `StateReason`.
+pub(crate) async fn list_unmanaged_devices(conn: &Connection) -> Result<Vec<UnmanagedDevice>> {
+    let devices = list_devices(conn).await?;
+
+    let mut unmanaged = Vec::new();
+    for device in devices
+        .into_iter()
+        .filter(|d| d.state == DeviceState::Unmanaged || d.managed == Some(false))
+    {
+        let d_proxy = NMDeviceProxy::builder(conn)
+            .path(device.path.clone())?
+            .build()
+            .await?;
+
+        let code = match d_proxy.state_reason().await {
+            Ok((_, reason)) => reason,
+            Err(e) => {
+                debug!(
+                    "Failed to get state reason for device {}: {}",
+                    device.interface, e
+                );
+                0
+            }
+        };
+
+        let reason = UnmanagedReason::from_state_reason(code, &device.device_type);
+        debug!("Device {} unmanaged: {reason}", device.interface);
+        unmanaged.push(UnmanagedDevice { device, reason });
+    }
+
+    Ok(unmanaged)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,181 @@
+//! DHCP lease details of a device.
+//!
+//! Reads the `DHCP4Config` / `DHCP6Config` objects NetworkManager attaches
+//! to a device while it holds a lease and converts their options to
+//! [`DhcpLease`].
+
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, SystemTime};
+
+use zbus::Connection;
+use zvariant::{OwnedValue, Value};
+
+use crate::Result;
+use crate::api::models::{DhcpInfo, DhcpLease};
+use crate::core::connection::get_device_by_interface;
+use crate::dbus::{NMDeviceProxy, NMDhcp4ConfigProxy, NMDhcp6ConfigProxy, dbus_operation};
+
+/// Option names the DHCPv4 client uses for the parsed lease fields.
+const V4_KEYS: LeaseKeys = LeaseKeys {
+    server_identifier: "dhcp_server_identifier",
+    lease_time: "dhcp_lease_time",
+    domain: &["domain_name", "domain_search"],
+    ntp_servers: &["ntp_servers"],
+};
+
+/// Option names the DHCPv6 client uses for the parsed lease fields.
+const V6_KEYS: LeaseKeys = LeaseKeys {
+    server_identifier: "dhcp6_server_id",
+    lease_time: "max_life",
+    domain: &["dhcp6_domain_search"],
+    ntp_servers: &["dhcp6_ntp_servers", "dhcp6_sntp_servers"],
+};
+
+struct LeaseKeys {
+    server_identifier: &'static str,
+    lease_time: &'static str,
+    /// Tried in order; the first present option wins.
+    domain: &'static [&'static str],
+    /// Merged in order.
+    ntp_servers: &'static [&'static str],
+}
+
+/// Returns the DHCP leases of `interface`.
+pub(crate) async fn dhcp_info(conn: &Connection, interface: &str) -> Result<DhcpInfo> {
+    let path = get_device_by_interface(conn, interface).await?;
+    let dev = NMDeviceProxy::builder(conn).path(path)?.build().await?;
+
+    let ipv4 = match dev.dhcp4_config().await {
+        Ok(p) if p.as_str() != "/" => {
+            let proxy = NMDhcp4ConfigProxy::builder(conn).path(p)?.build().await?;
+            let options = proxy.options().await.map_err(|e| {
+                dbus_operation(format!("failed to read DHCPv4 lease of {interface}"), e)
+            })?;
+            Some(parse_lease(&options, &V4_KEYS))
+        }
+        _ => None,
+    };
+    let ipv6 = match dev.dhcp6_config().await {
+        Ok(p) if p.as_str() != "/" => {
+            let proxy = NMDhcp6ConfigProxy::builder(conn).path(p)?.build().await?;
+            let options = proxy.options().await.map_err(|e| {
+                dbus_operation(format!("failed to read DHCPv6 lease of {interface}"), e)
+            })?;
+            Some(parse_lease(&options, &V6_KEYS))
+        }
+        _ => None,
+    };
+
+    Ok(DhcpInfo {
+        interface: interface.to_string(),
+        ipv4,
+        ipv6,
+    })
+}
+
+/// Converts an `Options` dict. NM sends every value as a string; anything
+/// else is dropped.
+fn parse_lease(raw: &HashMap<String, OwnedValue>, keys: &LeaseKeys) -> DhcpLease {
+    let options: BTreeMap<String, String> = raw
+        .iter()
+        .filter_map(|(k, v)| match &**v {
+            Value::Str(s) => Some((k.clone(), s.to_string())),
+            _ => None,
+        })
+        .collect();
+    let get = |key: &str| options.get(key).map(|v| v.trim()).filter(|v| !v.is_empty());
+
+    let mut ntp_servers: Vec<String> = Vec::new();
+    for server in keys
+        .ntp_servers
+        .iter()
+        .filter_map(|k| get(k))
+        .flat_map(str::split_whitespace)
+    {
+        if !ntp_servers.iter().any(|s| s == server) {
+            ntp_servers.push(server.to_string());
+        }
+    }
+
+    DhcpLease {
+        server_identifier: get(keys.server_identifier).map(str::to_string),
+        lease_time: get(keys.lease_time)
+            .and_then(|v| v.parse().ok())
+            .map(Duration::from_secs),
+        expiry: get("expiry")
+            .and_then(|v| v.parse().ok())
+            .map(|secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs)),
+        domain: keys.domain.iter().find_map(|k| get(k)).map(str::to_string),
+        ntp_servers,
+        options,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(entries: &[(&str, &str)]) -> HashMap<String, OwnedValue> {
+        entries
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), OwnedValue::from(zvariant::Str::from(*v))))
+            .collect()
+    }
+
+    #[test]
+    fn parses_v4_lease() {
+        let mut raw = options(&[
+            ("dhcp_server_identifier", "192.168.1.1"),
+            ("dhcp_lease_time", "86400"),
+            ("expiry", "1700000000"),
+            ("domain_name", "lan"),
+            ("domain_search", "corp.example"),
+            ("ntp_servers", "10.0.0.1 10.0.0.2"),
+            ("ip_address", "192.168.1.50"),
+        ]);
+        raw.insert("ignored".into(), OwnedValue::from(7u32));
+
+        let lease = parse_lease(&raw, &V4_KEYS);
+        assert_eq!(lease.server_identifier.as_deref(), Some("192.168.1.1"));
+        assert_eq!(lease.lease_time, Some(Duration::from_secs(86400)));
+        assert_eq!(
+            lease.expiry,
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000))
+        );
+        assert_eq!(lease.domain.as_deref(), Some("lan"));
+        assert_eq!(lease.ntp_servers, vec!["10.0.0.1", "10.0.0.2"]);
+        assert_eq!(lease.options.len(), 7);
+        assert_eq!(
+            lease.options.get("ip_address").map(String::as_str),
+            Some("192.168.1.50")
+        );
+    }
+
+    #[test]
+    fn parses_v6_lease_and_merges_ntp() {
+        let raw = options(&[
+            ("dhcp6_server_id", "0:1:0:1:2a:3b:4c:5d:0:11:22:33:44:55"),
+            ("dhcp6_domain_search", "corp.example"),
+            ("dhcp6_ntp_servers", "2001:db8::123"),
+            ("dhcp6_sntp_servers", "2001:db8::123 2001:db8::124"),
+        ]);
+
+        let lease = parse_lease(&raw, &V6_KEYS);
+        assert!(lease.server_identifier.is_some());
+        assert_eq!(lease.domain.as_deref(), Some("corp.example"));
+        assert_eq!(lease.ntp_servers, vec!["2001:db8::123", "2001:db8::124"]);
+        assert_eq!(lease.lease_time, None);
+        assert_eq!(lease.expiry, None);
+    }
+
+    #[test]
+    fn blank_and_malformed_values_are_none() {
+        let raw = options(&[("dhcp_lease_time", "forever"), ("domain_name", "  ")]);
+
+        let lease = parse_lease(&raw, &V4_KEYS);
+        assert_eq!(lease.lease_time, None);
+        assert_eq!(lease.domain, None);
+        assert!(lease.ntp_servers.is_empty());
+        assert_eq!(lease.options.len(), 2);
+    }
+}
@@ -0,0 +1,163 @@
+//! DNS overrides on saved profiles.
+//!
+//! Writes `dns`, `dns-search` and `ignore-auto-dns` into the `ipv4` and
+//! `ipv6` sections of an existing profile through `Update`, leaving the
+//! rest of the profile untouched.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+use log::info;
+use zbus::Connection;
+use zvariant::{OwnedValue, Value};
+
+use crate::Result;
+use crate::api::models::{ConnectionError, DnsConfig, SettingsPatch, TimeoutConfig};
+use crate::core::connection::reactivate_if_active;
+use crate::core::saved_connection::{
+    family_enabled, get_saved_connection_raw, resolve_saved_profile, update_saved_connection,
+};
+
+type Settings = HashMap<String, HashMap<String, OwnedValue>>;
+
+/// Applies `dns` to the saved profile `connection_id` (name or UUID),
+/// re-activating it afterwards if `reactivate` and it is active.
+pub(crate) async fn set_dns(
+    conn: &Connection,
+    connection_id: &str,
+    dns: &DnsConfig,
+    reactivate: bool,
+    timeout_config: Option<TimeoutConfig>,
+) -> Result<()> {
+    let profile = resolve_saved_profile(conn, connection_id).await?;
+    let current = get_saved_connection_raw(conn, &profile.uuid).await?;
+    let delta = dns_delta(&current, dns)?;
+    update_saved_connection(
+        conn,
+        &profile.uuid,
+        &SettingsPatch::new().with_raw_overlay(delta),
+    )
+    .await?;
+    info!("Updated DNS of '{}'", profile.id);
+
+    if reactivate {
+        reactivate_if_active(conn, &profile.path, timeout_config).await?;
+    }
+    Ok(())
+}
+
+/// Builds the `ipv4` / `ipv6` keys for `dns`.
+///
+/// Families that are disabled or absent in `current` are left alone;
+/// servers of such a family are rejected rather than silently dropped.
+fn dns_delta(current: &Settings, dns: &DnsConfig) -> Result<Settings> {
+    let v4: Vec<u32> = dns
+        .servers
+        .iter()
+        .filter_map(|addr| match addr {
+            // `ipv4.dns` holds in_addr_t values, i.e. network byte order.
+            IpAddr::V4(v4) => Some(u32::from_ne_bytes(v4.octets())),
+            IpAddr::V6(_) => None,
+        })
+        .collect();
+    let v6: Vec<Vec<u8>> = dns
+        .servers
+        .iter()
+        .filter_map(|addr| match addr {
+            IpAddr::V6(v6) => Some(v6.octets().to_vec()),
+            IpAddr::V4(_) => None,
+        })
+        .collect();
+
+    let mut delta = Settings::new();
+    for (family, servers, has_servers) in [
+        ("ipv4", Value::from(v4.clone()), !v4.is_empty()),
+        ("ipv6", Value::from(v6.clone()), !v6.is_empty()),
+    ] {
+        if !family_enabled(current, family) {
+            if has_servers {
+                return Err(ConnectionError::InvalidInput {
+                    field: "servers".to_string(),
+                    reason: format!("{family} is not configured on this profile"),
+                });
+            }
+            continue;
+        }
+        let section = HashMap::from([
+            ("dns".to_string(), owned(servers)?),
+            (
+                "dns-search".to_string(),
+                owned(Value::from(dns.search_domains.clone()))?,
+            ),
+            (
+                "ignore-auto-dns".to_string(),
+                OwnedValue::from(dns.ignore_auto_dns),
+            ),
+        ]);
+        delta.insert(family.to_string(), section);
+    }
+
+    if delta.is_empty() {
+        return Err(ConnectionError::InvalidInput {
+            field: "connection_id".to_string(),
+            reason: "profile has no IP configuration".to_string(),
+        });
+    }
+    Ok(delta)
+}
+
+fn owned(value: Value<'_>) -> Result<OwnedValue> {
+    Ok(OwnedValue::try_from(value).map_err(zbus::Error::from)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    fn section(method: &str) -> HashMap<String, OwnedValue> {
+        HashMap::from([(
+            "method".to_string(),
+            OwnedValue::from(zvariant::Str::from(method.to_string())),
+        )])
+    }
+
+    fn settings(v4: &str, v6: &str) -> Settings {
+        HashMap::from([
+            ("ipv4".to_string(), section(v4)),
+            ("ipv6".to_string(), section(v6)),
+        ])
+    }
+
+    #[test]
+    fn writes_servers_per_family() {
+        let dns = DnsConfig::new(vec![
+            IpAddr::V4(Ipv4Addr::new(8, 8, 4, 4)),
+            IpAddr::V6(Ipv6Addr::LOCALHOST),
+        ])
+        .with_search_domains(vec!["corp.example".into()])
+        .with_ignore_auto_dns(true);
+
+        let delta = dns_delta(&settings("auto", "auto"), &dns).unwrap();
+
+        let v4 = Vec::<u32>::try_from(delta["ipv4"]["dns"].try_clone().unwrap()).unwrap();
+        assert_eq!(v4, vec![u32::from_ne_bytes([8, 8, 4, 4])]);
+        let v6 = Vec::<Vec<u8>>::try_from(delta["ipv6"]["dns"].try_clone().unwrap()).unwrap();
+        assert_eq!(v6, vec![Ipv6Addr::LOCALHOST.octets().to_vec()]);
+        for family in ["ipv4", "ipv6"] {
+            assert!(bool::try_from(delta[family]["ignore-auto-dns"].try_clone().unwrap()).unwrap());
+        }
+    }
+
+    #[test]
+    fn skips_disabled_family() {
+        let dns = DnsConfig::new(vec![IpAddr::V4(Ipv4Addr::new(1, 1, 1, 1))]);
+        let delta = dns_delta(&settings("manual", "ignore"), &dns).unwrap();
+        assert!(delta.contains_key("ipv4"));
+        assert!(!delta.contains_key("ipv6"));
+
+        let dns = DnsConfig::new(vec![IpAddr::V6(Ipv6Addr::LOCALHOST)]);
+        assert!(dns_delta(&settings("auto", "disabled"), &dns).is_err());
+        assert!(dns_delta(&Settings::new(), &DnsConfig::default()).is_err());
+    }
+}
@@ -0,0 +1,134 @@
+//! Per-device record of the last activation failure.
+//!
+//! Failures of connect calls made through the crate are kept in a
+//! process-wide table keyed by interface name, so every `NetworkManager`
+//! and [`WifiScope`](crate::WifiScope) sees the same history. Devices with
+//! no recorded failure fall back to NetworkManager's `StateReason`.
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::SystemTime;
+
+use log::debug;
+use zbus::Connection;
+use zvariant::OwnedObjectPath;
+
+use crate::Result;
+use crate::api::models::{ConnectionFailure, StateReason};
+use crate::core::autoconnect::device_block_reason;
+use crate::core::connection::get_device_by_interface;
+use crate::dbus::{NMDeviceProxy, dbus_operation};
+
+static LAST_FAILURES: LazyLock<Mutex<HashMap<String, ConnectionFailure>>> =
+    LazyLock::new(Mutex::default);
+
+/// Records the outcome of activating `profile` on `device` and passes
+/// `outcome` through.
+///
+/// A failure replaces the device's entry; a success clears it.
+pub(crate) async fn track<T>(
+    conn: &Connection,
+    device: &OwnedObjectPath,
+    profile: &str,
+    outcome: Result<T>,
+) -> Result<T> {
+    let Ok(dev) = device_proxy(conn, device).await else {
+        return outcome;
+    };
+    let Ok(interface) = dev.interface().await else {
+        return outcome;
+    };
+
+    match &outcome {
+        Ok(_) => {
+            table().remove(&interface);
+        }
+        Err(e) => {
+            let (_, reason) = dev.state_reason().await.unwrap_or((0, 0));
+            debug!("Recording failure on {interface} for '{profile}': {e}");
+            table().insert(
+                interface.clone(),
+                ConnectionFailure {
+                    interface,
+                    profile: Some(profile.to_string()),
+                    error: Some(e.clone()),
+                    reason: StateReason::from(reason),
+                    at: Some(SystemTime::now()),
+                },
+            );
+        }
+    }
+    outcome
+}
+
+/// Last failure on `interface`, recorded or read from the device.
+pub(crate) async fn last_failure(
+    conn: &Connection,
+    interface: &str,
+) -> Result<Option<ConnectionFailure>> {
+    if let Some(recorded) = table().get(interface).cloned() {
+        return Ok(Some(recorded));
+    }
+
+    let path = get_device_by_interface(conn, interface).await?;
+    let (state, reason) = device_proxy(conn, &path)
+        .await?
+        .state_reason()
+        .await
+        .map_err(|e| dbus_operation(format!("failed to read StateReason of {interface}"), e))?;
+
+    Ok(from_state_reason(interface, state, reason))
+}
+
+/// Builds an unwitnessed failure when the device state shows one.
+fn from_state_reason(interface: &str, state: u32, reason: u32) -> Option<ConnectionFailure> {
+    device_block_reason(true, state, reason)?;
+    Some(ConnectionFailure {
+        interface: interface.to_string(),
+        profile: None,
+        error: None,
+        reason: StateReason::from(reason),
+        at: None,
+    })
+}
+
+fn table() -> std::sync::MutexGuard<'static, HashMap<String, ConnectionFailure>> {
+    LAST_FAILURES
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+async fn device_proxy<'a>(
+    conn: &'a Connection,
+    path: &OwnedObjectPath,
+) -> Result<NMDeviceProxy<'a>> {
+    Ok(NMDeviceProxy::builder(conn)
+        .path(path.clone())?
+        .build()
+        .await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::constants::{device_state, device_state_reason};
+
+    #[test]
+    fn failed_state_is_reported() {
+        let f = from_state_reason(
+            "wlan0",
+            device_state::FAILED,
+            device_state_reason::NO_SECRETS,
+        )
+        .unwrap();
+        assert_eq!(f.interface, "wlan0");
+        assert!(f.error.is_none() && f.at.is_none());
+    }
+
+    #[test]
+    fn healthy_device_has_no_failure() {
+        assert!(from_state_reason("wlan0", device_state::ACTIVATED, 0).is_none());
+        // 39 = NM_DEVICE_STATE_REASON_USER_REQUESTED
+        assert!(from_state_reason("wlan0", device_state::DISCONNECTED, 39).is_none());
+    }
+}
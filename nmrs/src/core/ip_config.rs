@@ -0,0 +1,293 @@
+//! Runtime IP configuration of active connections.
+//!
+//! Reads NetworkManager's `IP4Config` / `IP6Config` objects referenced by
+//! each active connection and converts them to [`IpDetails`].
+
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv6Addr};
+
+use log::debug;
+use zbus::Connection;
+use zvariant::{OwnedObjectPath, OwnedValue, Value};
+
+use crate::Result;
+use crate::api::builders::{IpConfig, Route};
+use crate::api::models::{ActiveConnectionDetails, IpDetails};
+use crate::dbus::{
+    NMActiveConnectionProxy, NMDeviceProxy, NMIP4ConfigProxy, NMIP6ConfigProxy, NMProxy,
+    dbus_operation,
+};
+
+/// Returns IP details for every active connection.
+///
+/// Connections that disappear while being read are skipped.
+pub(crate) async fn active_connection_details(
+    conn: &Connection,
+) -> Result<Vec<ActiveConnectionDetails>> {
+    let nm = NMProxy::new(conn).await?;
+    let active = nm
+        .active_connections()
+        .await
+        .map_err(|e| dbus_operation("failed to get active connections", e))?;
+
+    let mut out = Vec::with_capacity(active.len());
+    for path in active {
+        match connection_details(conn, path.clone()).await {
+            Ok(details) => out.push(details),
+            Err(e) => debug!("Skipping active connection {}: {e}", path.as_str()),
+        }
+    }
+    Ok(out)
+}
+
+async fn connection_details(
+    conn: &Connection,
+    path: OwnedObjectPath,
+) -> zbus::Result<ActiveConnectionDetails> {
+    let ac = NMActiveConnectionProxy::builder(conn)
+        .path(path)?
+        .build()
+        .await?;
+
+    let id = ac.id().await?;
+    let uuid = ac.uuid().await?;
+
+    let interface = match ac.devices().await?.into_iter().next() {
+        Some(dev_path) => {
+            let dev = NMDeviceProxy::builder(conn).path(dev_path)?.build().await?;
+            dev.interface().await.ok()
+        }
+        None => None,
+    };
+
+    let ipv4 = match ac.ip4_config().await {
+        Ok(p) if p.as_str() != "/" => ip4_details(conn, p).await.ok(),
+        _ => None,
+    };
+    let ipv6 = match ac.ip6_config().await {
+        Ok(p) if p.as_str() != "/" => ip6_details(conn, p).await.ok(),
+        _ => None,
+    };
+
+    Ok(ActiveConnectionDetails {
+        id,
+        uuid,
+        interface,
+        ipv4,
+        ipv6,
+    })
+}
+
+/// Reads an `IP4Config` object.
+pub(crate) async fn ip4_details(
+    conn: &Connection,
+    path: OwnedObjectPath,
+) -> zbus::Result<IpDetails> {
+    let proxy = NMIP4ConfigProxy::builder(conn).path(path)?.build().await?;
+
+    let nameservers = proxy
+        .nameserver_data()
+        .await
+        .map(|data| parse_nameserver_data(&data))
+        .unwrap_or_default();
+
+    Ok(IpDetails {
+        addresses: parse_address_data(&proxy.address_data().await?),
+        gateway: parse_gateway(&proxy.gateway().await.unwrap_or_default()),
+        nameservers,
+        domains: merge_domains(
+            proxy.domains().await.unwrap_or_default(),
+            proxy.searches().await.unwrap_or_default(),
+        ),
+        routes: parse_route_data(&proxy.route_data().await.unwrap_or_default()),
+    })
+}
+
+/// Reads an `IP6Config` object.
+pub(crate) async fn ip6_details(
+    conn: &Connection,
+    path: OwnedObjectPath,
+) -> zbus::Result<IpDetails> {
+    let proxy = NMIP6ConfigProxy::builder(conn).path(path)?.build().await?;
+
+    let nameservers = proxy
+        .nameservers()
+        .await
+        .map(|raw| parse_ip6_nameservers(&raw))
+        .unwrap_or_default();
+
+    Ok(IpDetails {
+        addresses: parse_address_data(&proxy.address_data().await?),
+        gateway: parse_gateway(&proxy.gateway().await.unwrap_or_default()),
+        nameservers,
+        domains: merge_domains(
+            proxy.domains().await.unwrap_or_default(),
+            proxy.searches().await.unwrap_or_default(),
+        ),
+        routes: parse_route_data(&proxy.route_data().await.unwrap_or_default()),
+    })
+}
+
+fn dict_str<'a>(map: &'a HashMap<String, OwnedValue>, key: &str) -> Option<&'a str> {
+    match &**map.get(key)? {
+        Value::Str(s) => Some(s.as_str()),
+        _ => None,
+    }
+}
+
+fn dict_u32(map: &HashMap<String, OwnedValue>, key: &str) -> Option<u32> {
+    match &**map.get(key)? {
+        Value::U32(v) => Some(*v),
+        _ => None,
+    }
+}
+
+/// Converts `AddressData` entries, skipping malformed ones.
+pub(crate) fn parse_address_data(data: &[HashMap<String, OwnedValue>]) -> Vec<IpConfig> {
+    data.iter()
+        .filter_map(|m| {
+            Some(IpConfig::new(
+                dict_str(m, "address")?,
+                dict_u32(m, "prefix")?,
+            ))
+        })
+        .collect()
+}
+
+/// Converts `RouteData` entries, skipping malformed ones.
+pub(crate) fn parse_route_data(data: &[HashMap<String, OwnedValue>]) -> Vec<Route> {
+    data.iter()
+        .filter_map(|m| {
+            let mut route = Route::new(dict_str(m, "dest")?, dict_u32(m, "prefix")?);
+            route.next_hop = dict_str(m, "next-hop").map(str::to_string);
+            route.metric = dict_u32(m, "metric");
+            Some(route)
+        })
+        .collect()
+}
+
+/// Converts IPv4 `NameserverData` entries.
+pub(crate) fn parse_nameserver_data(data: &[HashMap<String, OwnedValue>]) -> Vec<IpAddr> {
+    data.iter()
+        .filter_map(|m| dict_str(m, "address")?.parse().ok())
+        .collect()
+}
+
+/// Converts IPv6 `Nameservers`, which NM sends as raw 16-byte arrays.
+pub(crate) fn parse_ip6_nameservers(raw: &[Vec<u8>]) -> Vec<IpAddr> {
+    raw.iter()
+        .filter_map(|bytes| {
+            let octets: [u8; 16] = bytes.as_slice().try_into().ok()?;
+            Some(IpAddr::V6(Ipv6Addr::from(octets)))
+        })
+        .collect()
+}
+
+/// Parses the `Gateway` property; NM reports "no gateway" as an empty string.
+pub(crate) fn parse_gateway(gateway: &str) -> Option<IpAddr> {
+    gateway.parse().ok()
+}
+
+fn merge_domains(domains: Vec<String>, searches: Vec<String>) -> Vec<String> {
+    let mut out = domains;
+    for s in searches {
+        if !out.contains(&s) {
+            out.push(s);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    fn dict(entries: &[(&str, Value<'_>)]) -> HashMap<String, OwnedValue> {
+        entries
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), OwnedValue::try_from(v.clone()).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn address_data_parses_and_skips_malformed() {
+        let data = vec![
+            dict(&[
+                ("address", Value::from("192.168.1.50")),
+                ("prefix", Value::from(24u32)),
+            ]),
+            dict(&[("address", Value::from("10.0.0.1"))]),
+        ];
+        assert_eq!(
+            parse_address_data(&data),
+            vec![IpConfig::new("192.168.1.50", 24)]
+        );
+    }
+
+    #[test]
+    fn route_data_reads_optional_fields() {
+        let data = vec![
+            dict(&[
+                ("dest", Value::from("10.8.0.0")),
+                ("prefix", Value::from(16u32)),
+                ("next-hop", Value::from("192.168.1.1")),
+                ("metric", Value::from(600u32)),
+            ]),
+            dict(&[
+                ("dest", Value::from("192.168.1.0")),
+                ("prefix", Value::from(24u32)),
+            ]),
+        ];
+        let routes = parse_route_data(&data);
+        assert_eq!(
+            routes,
+            vec![
+                Route::new("10.8.0.0", 16)
+                    .next_hop("192.168.1.1")
+                    .metric(600),
+                Route::new("192.168.1.0", 24),
+            ]
+        );
+    }
+
+    #[test]
+    fn nameserver_data_parses_addresses() {
+        let data = vec![
+            dict(&[("address", Value::from("1.1.1.1"))]),
+            dict(&[("address", Value::from("not-an-ip"))]),
+        ];
+        assert_eq!(
+            parse_nameserver_data(&data),
+            vec![IpAddr::V4(Ipv4Addr::new(1, 1, 1, 1))]
+        );
+    }
+
+    #[test]
+    fn ip6_nameservers_require_16_bytes() {
+        let mut v6 = vec![0u8; 16];
+        v6[0] = 0x20;
+        v6[1] = 0x01;
+        v6[15] = 0x01;
+        let parsed = parse_ip6_nameservers(&[v6, vec![1, 2, 3]]);
+        assert_eq!(parsed, vec!["2001::1".parse::<IpAddr>().unwrap()]);
+    }
+
+    #[test]
+    fn empty_gateway_is_none() {
+        assert_eq!(parse_gateway(""), None);
+        assert_eq!(
+            parse_gateway("192.168.1.1"),
+            Some(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 1)))
+        );
+    }
+
+    #[test]
+    fn domains_are_merged_without_duplicates() {
+        let merged = merge_domains(
+            vec!["lan".into()],
+            vec!["lan".into(), "corp.example".into()],
+        );
+        assert_eq!(merged, vec!["lan".to_string(), "corp.example".to_string()]);
+    }
+}
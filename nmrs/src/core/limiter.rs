@@ -0,0 +1,229 @@
+//! Bounded concurrency and request coalescing for expensive D-Bus calls.
+//!
+//! Scans and listings fan out into many property reads. When several tasks
+//! issue them at the same time NetworkManager can start throttling, so
+//! [`Limiter`] caps how many run at once and lets identical concurrent
+//! requests share one in-flight result.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use futures::FutureExt;
+use futures::future::{BoxFuture, Shared};
+use log::debug;
+use tokio::sync::Semaphore;
+
+use crate::Result;
+
+/// Default number of expensive operations allowed to run concurrently.
+pub(crate) const DEFAULT_MAX_CONCURRENT: usize = 4;
+
+type SharedResult<T> = Shared<BoxFuture<'static, Result<T>>>;
+
+/// In-flight request registered under a coalescing key.
+struct Inflight {
+    id: u64,
+    future: Box<dyn Any + Send + Sync>,
+}
+
+/// Caps concurrent expensive operations and coalesces duplicates.
+pub(crate) struct Limiter {
+    permits: Arc<Semaphore>,
+    max_concurrent: usize,
+    next_id: AtomicU64,
+    inflight: Mutex<HashMap<String, Inflight>>,
+}
+
+impl Limiter {
+    /// Creates a limiter allowing `max_concurrent` operations at once (min 1).
+    pub(crate) fn new(max_concurrent: usize) -> Self {
+        let max_concurrent = max_concurrent.max(1);
+        Self {
+            permits: Arc::new(Semaphore::new(max_concurrent)),
+            max_concurrent,
+            next_id: AtomicU64::new(0),
+            inflight: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Runs `work` once a permit is available.
+    pub(crate) async fn run<T, F>(&self, work: F) -> Result<T>
+    where
+        F: Future<Output = Result<T>>,
+    {
+        let _permit = self.permits.acquire().await;
+        work.await
+    }
+
+    /// Runs `work` under a permit, sharing its result with concurrent callers
+    /// that use the same `key`.
+    ///
+    /// The first caller for a key starts the work; callers arriving while it
+    /// is still running wait for and receive a clone of the same result.
+    /// Once it completes the key is released, so later calls start fresh.
+    pub(crate) async fn coalesce<T, F>(&self, key: String, work: F) -> Result<T>
+    where
+        T: Clone + Send + Sync + 'static,
+        F: Future<Output = Result<T>> + Send + 'static,
+    {
+        let (id, shared) = {
+            let mut inflight = self.inflight.lock().unwrap_or_else(|e| e.into_inner());
+
+            let existing = inflight.get(&key).and_then(|entry| {
+                entry
+                    .future
+                    .downcast_ref::<SharedResult<T>>()
+                    .map(|f| (entry.id, f.clone()))
+            });
+
+            match existing {
+                Some(found) => {
+                    debug!("Coalescing concurrent request '{key}'");
+                    found
+                }
+                None => {
+                    let permits = Arc::clone(&self.permits);
+                    let shared = async move {
+                        let _permit = permits.acquire_owned().await;
+                        work.await
+                    }
+                    .boxed()
+                    .shared();
+
+                    let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+                    inflight.insert(
+                        key.clone(),
+                        Inflight {
+                            id,
+                            future: Box::new(shared.clone()),
+                        },
+                    );
+                    (id, shared)
+                }
+            }
+        };
+
+        let result = shared.await;
+
+        let mut inflight = self.inflight.lock().unwrap_or_else(|e| e.into_inner());
+        if inflight.get(&key).is_some_and(|entry| entry.id == id) {
+            inflight.remove(&key);
+        }
+
+        result
+    }
+}
+
+/// Builds the coalescing key for an operation optionally scoped to an interface.
+pub(crate) fn coalesce_key(operation: &str, interface: Option<&str>) -> String {
+    format!("{operation}:{}", interface.unwrap_or("*"))
+}
+
+impl Default for Limiter {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_CONCURRENT)
+    }
+}
+
+impl fmt::Debug for Limiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Limiter")
+            .field("max_concurrent", &self.max_concurrent)
+            .field("available", &self.permits.available_permits())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn coalesces_concurrent_calls() {
+        let limiter = Limiter::new(2);
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let make = || {
+            let calls = Arc::clone(&calls);
+            async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                Ok(vec![1, 2, 3])
+            }
+        };
+
+        let (a, b) = tokio::join!(
+            limiter.coalesce("list".into(), make()),
+            limiter.coalesce("list".into(), make()),
+        );
+
+        assert_eq!(a.unwrap(), vec![1, 2, 3]);
+        assert_eq!(b.unwrap(), vec![1, 2, 3]);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Key is released once the shared call completes.
+        limiter.coalesce("list".into(), make()).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn distinct_keys_run_separately() {
+        let limiter = Limiter::new(2);
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let make = || {
+            let calls = Arc::clone(&calls);
+            async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            }
+        };
+
+        let (a, b) = tokio::join!(
+            limiter.coalesce("wlan0".into(), make()),
+            limiter.coalesce("wlan1".into(), make()),
+        );
+        a.unwrap();
+        b.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn bounds_concurrency() {
+        let limiter = Arc::new(Limiter::new(1));
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let task = |limiter: Arc<Limiter>| {
+            let running = Arc::clone(&running);
+            let peak = Arc::clone(&peak);
+            async move {
+                limiter
+                    .run(async {
+                        let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                        peak.fetch_max(now, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(10)).await;
+                        running.fetch_sub(1, Ordering::SeqCst);
+                        Ok(())
+                    })
+                    .await
+            }
+        };
+
+        let (a, b, c) = tokio::join!(
+            task(Arc::clone(&limiter)),
+            task(Arc::clone(&limiter)),
+            task(Arc::clone(&limiter)),
+        );
+        a.unwrap();
+        b.unwrap();
+        c.unwrap();
+        assert_eq!(peak.load(Ordering::SeqCst), 1);
+    }
+}
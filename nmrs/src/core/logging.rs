@@ -0,0 +1,35 @@
+//! NetworkManager daemon log level control.
+
+use log::debug;
+use zbus::Connection;
+
+use crate::Result;
+use crate::api::models::{NmLogLevel, NmLogging};
+use crate::dbus::{NMProxy, dbus_operation};
+
+/// Calls `SetLogging` with the given level and domains.
+///
+/// An empty `domains` slice leaves the enabled domains unchanged.
+pub(crate) async fn set_logging(
+    conn: &Connection,
+    level: NmLogLevel,
+    domains: &[&str],
+) -> Result<()> {
+    let nm = NMProxy::new(conn).await?;
+    let domains = domains.join(",");
+    debug!("Setting NM logging: level={level} domains={domains:?}");
+
+    nm.set_logging(level.as_str(), &domains)
+        .await
+        .map_err(|e| dbus_operation("SetLogging call", e))
+}
+
+/// Calls `GetLogging`.
+pub(crate) async fn get_logging(conn: &Connection) -> Result<NmLogging> {
+    let nm = NMProxy::new(conn).await?;
+    let (level, domains) = nm
+        .get_logging()
+        .await
+        .map_err(|e| dbus_operation("GetLogging call", e))?;
+    Ok(NmLogging::from_raw(&level, &domains))
+}
@@ -0,0 +1,116 @@
+//! Mobile broadband (GSM/LTE) connection management.
+//!
+//! NetworkManager talks to the modem through ModemManager; these helpers
+//! only pick the modem device and activate or deactivate a `gsm` profile
+//! on it.
+
+use log::{debug, info};
+use zbus::Connection;
+use zvariant::OwnedObjectPath;
+
+use crate::Result;
+use crate::api::models::{ConnectionError, ConnectionOptions, GsmCredentials, TimeoutConfig};
+use crate::backend::{DbusBackend, NmBackend};
+use crate::builders::build_gsm_connection;
+use crate::core::connection::activation_options;
+use crate::core::connection_settings::get_saved_connection_path;
+use crate::core::failures;
+use crate::core::state_wait::{wait_for_connection_activation, wait_for_device_disconnect};
+use crate::dbus::{NMDeviceProxy, NMProxy, dbus_operation};
+use crate::types::constants::{device_state, device_type};
+
+/// Returns the first modem device NetworkManager manages.
+pub(crate) async fn find_modem_device(
+    conn: &Connection,
+    nm: &NMProxy<'_>,
+) -> Result<OwnedObjectPath> {
+    for dp in nm.get_devices().await? {
+        let dev = NMDeviceProxy::builder(conn)
+            .path(dp.clone())?
+            .build()
+            .await?;
+        if dev.device_type().await? == device_type::MODEM {
+            return Ok(dp);
+        }
+    }
+    Err(ConnectionError::NoModemDevice)
+}
+
+/// Activates the mobile broadband profile `name`, creating it from
+/// `creds` if it does not exist yet.
+///
+/// A saved profile is activated unchanged.
+pub(crate) async fn connect_mobile(
+    conn: &Connection,
+    name: &str,
+    creds: &GsmCredentials,
+    timeout_config: Option<TimeoutConfig>,
+    options: &ConnectionOptions,
+) -> Result<()> {
+    debug!(
+        "Connecting to '{name}' (mobile broadband) | apn={}",
+        creds.apn
+    );
+
+    let nm = NMProxy::new(conn).await?;
+    let modem = find_modem_device(conn, &nm).await?;
+    let saved = get_saved_connection_path(conn, name).await?;
+    let timeout = timeout_config.map(|c| c.connection_timeout);
+
+    let activation = async {
+        let active_conn = match saved {
+            Some(saved_path) => {
+                debug!(
+                    "Activating saved mobile connection: {}",
+                    saved_path.as_str()
+                );
+                nm.activate_connection(saved_path, modem.clone(), OwnedObjectPath::default())
+                    .await?
+            }
+            None => {
+                debug!("No saved connection found, creating new mobile connection");
+                let settings = build_gsm_connection(name, creds, options);
+                let (_, active_conn) = DbusBackend::from_connection(conn.clone())
+                    .add_and_activate_connection2(
+                        settings,
+                        &modem,
+                        &OwnedObjectPath::default(),
+                        activation_options(options),
+                    )
+                    .await?;
+                active_conn
+            }
+        };
+        wait_for_connection_activation(conn, &active_conn, timeout).await
+    };
+    failures::track(conn, &modem, name, activation.await).await?;
+
+    info!("Successfully connected to mobile broadband '{name}'");
+    Ok(())
+}
+
+/// Disconnects the modem device and waits until it is down.
+pub(crate) async fn disconnect_mobile(
+    conn: &Connection,
+    timeout_config: Option<TimeoutConfig>,
+) -> Result<()> {
+    let nm = NMProxy::new(conn).await?;
+    let modem = find_modem_device(conn, &nm).await?;
+    let dev = NMDeviceProxy::builder(conn)
+        .path(modem.clone())?
+        .build()
+        .await?;
+
+    let state = dev.state().await?;
+    if state == device_state::DISCONNECTED || state == device_state::UNAVAILABLE {
+        debug!("Modem already disconnected");
+        return Ok(());
+    }
+
+    dev.disconnect()
+        .await
+        .map_err(|e| dbus_operation(format!("failed to disconnect modem {}", modem.as_str()), e))?;
+
+    let timeout = timeout_config.map(|c| c.disconnect_timeout);
+    wait_for_device_disconnect(&dev, timeout).await
+}
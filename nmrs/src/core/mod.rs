@@ -3,16 +3,37 @@
 //! This module contains the internal implementation details for managing
 //! network connections, devices, scanning, and state monitoring.
 
+pub(crate) mod active_connection;
 pub(crate) mod airplane;
+pub(crate) mod autoconnect;
 pub(crate) mod bluetooth;
+pub(crate) mod bridge;
+pub(crate) mod cancel;
 pub(crate) mod connection;
 pub(crate) mod connection_settings;
 pub(crate) mod connectivity;
+pub(crate) mod daemon_watch;
 pub(crate) mod device;
+pub(crate) mod dhcp;
+pub(crate) mod dns;
+pub(crate) mod failures;
+pub(crate) mod ip_config;
+pub(crate) mod limiter;
+pub(crate) mod logging;
+pub(crate) mod mobile;
+pub(crate) mod networking;
 pub(crate) mod ovpn_parser;
+pub(crate) mod p2p;
+pub(crate) mod permissions;
 pub(crate) mod rfkill;
+pub(crate) mod roaming;
+pub(crate) mod routes;
 pub(crate) mod saved_connection;
 pub(crate) mod scan;
+pub(crate) mod sharing;
+pub(crate) mod snapshot;
 pub(crate) mod state_wait;
 pub(crate) mod vpn;
+pub(crate) mod vpn_supervisor;
 pub(crate) mod wifi_device;
+pub(crate) mod wps;
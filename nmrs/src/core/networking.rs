@@ -0,0 +1,49 @@
+//! Global networking and sleep state of NetworkManager.
+//!
+//! `Enable(false)` is the user-facing "disable networking" switch and
+//! `Sleep(true)` what power management calls before suspend; both take
+//! every device down. Either call fails when NetworkManager is already in
+//! the requested state, which is treated as success here.
+
+use log::info;
+use zbus::Connection;
+
+use crate::Result;
+use crate::dbus::{NMProxy, dbus_operation, is_nm_error};
+
+/// Reads the `NetworkingEnabled` property.
+pub(crate) async fn networking_enabled(conn: &Connection) -> Result<bool> {
+    let nm = NMProxy::new(conn).await?;
+    nm.networking_enabled()
+        .await
+        .map_err(|e| dbus_operation("read NetworkingEnabled property", e))
+}
+
+/// Calls `Enable`.
+pub(crate) async fn set_networking_enabled(conn: &Connection, enabled: bool) -> Result<()> {
+    let nm = NMProxy::new(conn).await?;
+    match nm.enable(enabled).await {
+        Ok(()) => {
+            info!(
+                "Networking {}",
+                if enabled { "enabled" } else { "disabled" }
+            );
+            Ok(())
+        }
+        Err(e) if is_nm_error(&e, "AlreadyEnabledOrDisabled") => Ok(()),
+        Err(e) => Err(dbus_operation("Enable call", e)),
+    }
+}
+
+/// Calls `Sleep`.
+pub(crate) async fn set_sleeping(conn: &Connection, sleep: bool) -> Result<()> {
+    let nm = NMProxy::new(conn).await?;
+    match nm.sleep(sleep).await {
+        Ok(()) => {
+            info!("NetworkManager {}", if sleep { "asleep" } else { "awake" });
+            Ok(())
+        }
+        Err(e) if is_nm_error(&e, "AlreadyAsleepOrAwake") => Ok(()),
+        Err(e) => Err(dbus_operation("Sleep call", e)),
+    }
+}
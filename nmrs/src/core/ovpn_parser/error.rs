@@ -1,8 +1,9 @@
 use std::fmt;
 
 use crate::ConnectionError;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum OvpnParseError {
     InvalidDirectiveSyntax {
         line: usize,
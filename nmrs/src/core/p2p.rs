@@ -0,0 +1,159 @@
+//! Wi-Fi Direct (P2P) peer discovery and connections.
+//!
+//! NetworkManager exposes a separate `wifi-p2p` device next to each Wi-Fi
+//! card that supports it. Peers appear on that device after a find, and a
+//! connection is an in-memory `wifi-p2p` profile activated with the peer as
+//! specific object.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use log::{debug, info};
+use zbus::Connection;
+use zvariant::{OwnedObjectPath, Value};
+
+use crate::Result;
+use crate::api::models::{ConnectionError, ConnectionOptions, P2pPeer, TimeoutConfig};
+use crate::builders::build_wifi_p2p_connection;
+use crate::core::failures;
+use crate::core::state_wait::{wait_for_connection_activation, wait_for_device_disconnect};
+use crate::dbus::{
+    NMDeviceProxy, NMProxy, NMSettingsProxy, NMWifiP2PPeerProxy, NMWifiP2PProxy, dbus_operation,
+};
+use crate::types::constants::{device_state, device_type};
+
+/// Paths and proxies of all Wi-Fi P2P devices.
+async fn p2p_devices(conn: &Connection) -> Result<Vec<(OwnedObjectPath, NMDeviceProxy<'_>)>> {
+    let nm = NMProxy::new(conn).await?;
+    let mut devices = Vec::new();
+    for dp in nm.get_devices().await? {
+        let dev = NMDeviceProxy::builder(conn)
+            .path(dp.clone())?
+            .build()
+            .await?;
+        if dev.device_type().await? == device_type::WIFI_P2P {
+            devices.push((dp, dev));
+        }
+    }
+    if devices.is_empty() {
+        return Err(ConnectionError::NoWifiP2pDevice);
+    }
+    Ok(devices)
+}
+
+/// Starts peer discovery on every P2P device.
+///
+/// NetworkManager stops on its own after `timeout` (30 s when `None`).
+pub(crate) async fn start_p2p_find(conn: &Connection, timeout: Option<Duration>) -> Result<()> {
+    for (dp, _) in p2p_devices(conn).await? {
+        let p2p = NMWifiP2PProxy::builder(conn)
+            .path(dp.clone())?
+            .build()
+            .await?;
+        let mut options = HashMap::new();
+        if let Some(t) = timeout {
+            let secs = i32::try_from(t.as_secs()).unwrap_or(i32::MAX).max(1);
+            options.insert("timeout", Value::from(secs));
+        }
+        p2p.start_find(options).await.map_err(|e| {
+            dbus_operation(format!("failed to start P2P find on {}", dp.as_str()), e)
+        })?;
+    }
+    Ok(())
+}
+
+/// Lists the peers currently known to all P2P devices.
+pub(crate) async fn list_p2p_peers(conn: &Connection) -> Result<Vec<P2pPeer>> {
+    let mut peers = Vec::new();
+    for (dp, dev) in p2p_devices(conn).await? {
+        let interface = dev.interface().await.unwrap_or_default();
+        let p2p = NMWifiP2PProxy::builder(conn)
+            .path(dp.clone())?
+            .build()
+            .await?;
+
+        for peer_path in p2p.peers().await? {
+            let peer = NMWifiP2PPeerProxy::builder(conn)
+                .path(peer_path.clone())?
+                .build()
+                .await?;
+            let last_seen = peer.last_seen().await.unwrap_or(-1);
+            peers.push(P2pPeer {
+                path: peer_path,
+                device_path: dp.clone(),
+                interface: interface.clone(),
+                name: peer.name().await.unwrap_or_default(),
+                hw_address: peer.hw_address().await?,
+                manufacturer: peer.manufacturer().await.unwrap_or_default(),
+                model: peer.model().await.unwrap_or_default(),
+                wfd_ies: peer.wfd_ies().await.unwrap_or_default(),
+                strength: peer.strength().await.unwrap_or(0),
+                last_seen_secs: (last_seen >= 0).then(|| i64::from(last_seen)),
+            });
+        }
+    }
+    Ok(peers)
+}
+
+/// Connects to `peer` through an in-memory `wifi-p2p` profile.
+pub(crate) async fn connect_p2p(
+    conn: &Connection,
+    peer: &P2pPeer,
+    timeout_config: Option<TimeoutConfig>,
+    options: &ConnectionOptions,
+) -> Result<()> {
+    let label = if peer.name.is_empty() {
+        &peer.hw_address
+    } else {
+        &peer.name
+    };
+    debug!("Connecting to P2P peer '{label}' ({})", peer.hw_address);
+
+    let nm = NMProxy::new(conn).await?;
+    let settings = NMSettingsProxy::new(conn).await?;
+    let timeout = timeout_config.map(|c| c.connection_timeout);
+
+    let activation = async {
+        let profile = build_wifi_p2p_connection(
+            &format!("Wi-Fi P2P {label}"),
+            &peer.hw_address,
+            None,
+            options,
+        );
+        let path = settings
+            .add_connection_unsaved(profile)
+            .await
+            .map_err(|e| dbus_operation("failed to add P2P connection", e))?;
+        let active = nm
+            .activate_connection(path, peer.device_path.clone(), peer.path.clone())
+            .await?;
+        wait_for_connection_activation(conn, &active, timeout).await
+    };
+    failures::track(conn, &peer.device_path, label, activation.await).await?;
+
+    info!("Connected to P2P peer '{label}'");
+    Ok(())
+}
+
+/// Tears down P2P connections on every P2P device.
+pub(crate) async fn disconnect_p2p(
+    conn: &Connection,
+    timeout_config: Option<TimeoutConfig>,
+) -> Result<()> {
+    let timeout = timeout_config.map(|c| c.disconnect_timeout);
+    for (dp, dev) in p2p_devices(conn).await? {
+        let state = dev.state().await?;
+        if state == device_state::DISCONNECTED || state == device_state::UNAVAILABLE {
+            continue;
+        }
+        debug!("Disconnecting P2P device {}", dp.as_str());
+        dev.disconnect().await.map_err(|e| {
+            dbus_operation(
+                format!("failed to disconnect P2P device {}", dp.as_str()),
+                e,
+            )
+        })?;
+        wait_for_device_disconnect(&dev, timeout).await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,17 @@
+//! Polkit permission preflight.
+
+use zbus::Connection;
+
+use crate::Result;
+use crate::api::models::Permissions;
+use crate::dbus::{NMProxy, dbus_operation};
+
+/// Calls `GetPermissions`.
+pub(crate) async fn get_permissions(conn: &Connection) -> Result<Permissions> {
+    let nm = NMProxy::new(conn).await?;
+    let raw = nm
+        .get_permissions()
+        .await
+        .map_err(|e| dbus_operation("GetPermissions call", e))?;
+    Ok(Permissions::from_raw(&raw))
+}
@@ -0,0 +1,189 @@
+//! Steering a connected Wi-Fi device between access points of one SSID.
+//!
+//! NetworkManager leaves roaming to wpa_supplicant, which can stay on a
+//! weak AP long after a better one of the same network is in range.
+//! Re-activating the current profile with another AP as the specific
+//! object makes the device re-associate with that BSSID.
+
+use std::cmp::Reverse;
+use std::time::Duration;
+
+use log::{debug, info};
+use zbus::Connection;
+
+use crate::Result;
+use crate::api::models::ConnectionError;
+use crate::api::models::access_point::AccessPoint;
+use crate::core::scan::list_access_points;
+use crate::core::state_wait::wait_for_connection_activation;
+use crate::dbus::{NMActiveConnectionProxy, NMDeviceProxy, NMProxy};
+use crate::util::validation::validate_bssid;
+
+/// All visible access points broadcasting `ssid`, strongest first.
+pub(crate) async fn list_bssids(conn: &Connection, ssid: &str) -> Result<Vec<AccessPoint>> {
+    Ok(bssids_of(list_access_points(conn, None).await?, ssid))
+}
+
+/// Re-associates the device that sees `bssid` with that access point.
+///
+/// The device must be connected to the same SSID. Does nothing if it is
+/// already associated with `bssid`.
+pub(crate) async fn roam_to(
+    conn: &Connection,
+    bssid: &str,
+    interface: Option<&str>,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    validate_bssid(bssid)?;
+
+    let aps = list_access_points(conn, interface).await?;
+    let (current, target) = roam_pair(&aps, bssid)?;
+    if current.path == target.path {
+        debug!("{} is already associated with {bssid}", target.interface);
+        return Ok(());
+    }
+
+    let dev = NMDeviceProxy::builder(conn)
+        .path(target.device_path.clone())?
+        .build()
+        .await?;
+    let active = NMActiveConnectionProxy::builder(conn)
+        .path(dev.active_connection().await?)?
+        .build()
+        .await?;
+    let profile = active.connection().await?;
+
+    debug!(
+        "Roaming {} on '{}' from {} to {bssid}",
+        target.interface, target.ssid, current.bssid
+    );
+    let nm = NMProxy::new(conn).await?;
+    let active_conn = nm
+        .activate_connection(profile, target.device_path.clone(), target.path.clone())
+        .await?;
+    wait_for_connection_activation(conn, &active_conn, timeout).await?;
+
+    info!(
+        "Roamed {} on '{}' to {bssid} ({}%)",
+        target.interface, target.ssid, target.strength
+    );
+    Ok(())
+}
+
+fn bssids_of(aps: Vec<AccessPoint>, ssid: &str) -> Vec<AccessPoint> {
+    let mut matching: Vec<_> = aps.into_iter().filter(|ap| ap.ssid == ssid).collect();
+    matching.sort_by_key(|ap| Reverse(ap.strength));
+    matching
+}
+
+/// Finds the access point `bssid` and the one its device is associated
+/// with, which must broadcast the same SSID.
+fn roam_pair<'a>(
+    aps: &'a [AccessPoint],
+    bssid: &str,
+) -> Result<(&'a AccessPoint, &'a AccessPoint)> {
+    let Some(target) = aps.iter().find(|ap| ap.bssid.eq_ignore_ascii_case(bssid)) else {
+        let ssid = aps
+            .iter()
+            .find(|ap| ap.is_active)
+            .map(|ap| ap.ssid.clone())
+            .unwrap_or_default();
+        return Err(ConnectionError::ApBssidNotFound {
+            ssid,
+            bssid: bssid.to_string(),
+        });
+    };
+
+    let invalid = |reason: String| ConnectionError::InvalidInput {
+        field: "bssid".into(),
+        reason,
+    };
+    let current = aps
+        .iter()
+        .find(|ap| ap.is_active && ap.device_path == target.device_path)
+        .ok_or_else(|| {
+            invalid(format!(
+                "{} is not connected to a Wi-Fi network",
+                target.interface
+            ))
+        })?;
+    if current.ssid_bytes != target.ssid_bytes {
+        return Err(invalid(format!(
+            "{bssid} broadcasts '{}', not the connected network '{}'",
+            target.ssid, current.ssid
+        )));
+    }
+
+    Ok((current, target))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::models::access_point::ApMode;
+    use crate::api::models::{DeviceState, SecurityFeatures};
+    use zvariant::OwnedObjectPath;
+
+    fn ap(n: u32, ssid: &str, strength: u8, active: bool) -> AccessPoint {
+        AccessPoint {
+            path: OwnedObjectPath::try_from(format!("/ap/{n}")).unwrap(),
+            device_path: OwnedObjectPath::try_from("/dev/1").unwrap(),
+            interface: "wlan0".into(),
+            ssid: ssid.into(),
+            ssid_bytes: ssid.as_bytes().to_vec(),
+            bssid: format!("AA:AA:AA:AA:AA:0{n}"),
+            frequency_mhz: 5180,
+            max_bitrate_kbps: 0,
+            strength,
+            mode: ApMode::Infrastructure,
+            security: SecurityFeatures::default(),
+            last_seen_secs: None,
+            seen: None,
+            is_active: active,
+            device_state: DeviceState::Activated,
+            bss_load: None,
+        }
+    }
+
+    #[test]
+    fn lists_bssids_strongest_first() {
+        let aps = vec![
+            ap(1, "Mesh", 40, true),
+            ap(2, "Cafe", 90, false),
+            ap(3, "Mesh", 75, false),
+        ];
+        let mesh = bssids_of(aps, "Mesh");
+        let bssids: Vec<_> = mesh.iter().map(|ap| ap.bssid.as_str()).collect();
+        assert_eq!(bssids, ["AA:AA:AA:AA:AA:03", "AA:AA:AA:AA:AA:01"]);
+    }
+
+    #[test]
+    fn pairs_target_with_associated_ap() {
+        let aps = [
+            ap(1, "Mesh", 40, true),
+            ap(2, "Cafe", 90, false),
+            ap(3, "Mesh", 75, false),
+        ];
+        let (current, target) = roam_pair(&aps, "aa:aa:aa:aa:aa:03").unwrap();
+        assert_eq!(current.bssid, "AA:AA:AA:AA:AA:01");
+        assert_eq!(target.bssid, "AA:AA:AA:AA:AA:03");
+
+        assert!(matches!(
+            roam_pair(&aps, "AA:AA:AA:AA:AA:02"),
+            Err(ConnectionError::InvalidInput { .. })
+        ));
+        assert!(matches!(
+            roam_pair(&aps, "AA:AA:AA:AA:AA:09"),
+            Err(ConnectionError::ApBssidNotFound { ssid, .. }) if ssid == "Mesh"
+        ));
+    }
+
+    #[test]
+    fn requires_an_association() {
+        let aps = [ap(1, "Mesh", 40, false), ap(3, "Mesh", 75, false)];
+        assert!(matches!(
+            roam_pair(&aps, "AA:AA:AA:AA:AA:03"),
+            Err(ConnectionError::InvalidInput { .. })
+        ));
+    }
+}
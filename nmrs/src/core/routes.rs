@@ -0,0 +1,235 @@
+//! Static routes on saved profiles.
+//!
+//! Routes live in the `route-data` key of a profile's `ipv4` / `ipv6`
+//! section. `GetSettings` also returns the deprecated `routes` key, and
+//! NetworkManager ignores `route-data` when `routes` is sent, so edits drop
+//! the old key.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+use log::info;
+use zbus::Connection;
+use zvariant::{OwnedValue, Value};
+
+use crate::Result;
+use crate::api::builders::Route;
+use crate::api::models::ConnectionError;
+use crate::core::ip_config::{active_connection_details, parse_route_data};
+use crate::core::saved_connection::{edit_saved_connection, family_enabled, resolve_saved_profile};
+
+type Settings = HashMap<String, HashMap<String, OwnedValue>>;
+
+/// Adds `route` to the saved profile `connection_id` (name or UUID).
+///
+/// A route with the same destination and next hop is replaced.
+pub(crate) async fn add_route(conn: &Connection, connection_id: &str, route: &Route) -> Result<()> {
+    let family = route_family(route)?;
+    let profile = resolve_saved_profile(conn, connection_id).await?;
+    edit_saved_connection(conn, &profile.uuid, |mut settings| {
+        let mut routes = enabled_routes(&settings, family)?;
+        routes.retain(|r| !same_route(r, route));
+        routes.push(route.clone());
+        write_routes(&mut settings, family, &routes)?;
+        Ok(settings)
+    })
+    .await?;
+    info!(
+        "Added route {}/{} to '{}'",
+        route.dest, route.prefix, profile.id
+    );
+    Ok(())
+}
+
+/// Removes the routes of saved profile `connection_id` matching `route`.
+///
+/// Destination and prefix must be equal; next hop and metric only have to
+/// match when set in `route`. Returns whether anything was removed.
+pub(crate) async fn remove_route(
+    conn: &Connection,
+    connection_id: &str,
+    route: &Route,
+) -> Result<bool> {
+    let family = route_family(route)?;
+    let profile = resolve_saved_profile(conn, connection_id).await?;
+    let mut removed = false;
+    edit_saved_connection(conn, &profile.uuid, |mut settings| {
+        let mut routes = enabled_routes(&settings, family)?;
+        let before = routes.len();
+        routes.retain(|r| !route_matches(r, route));
+        removed = routes.len() != before;
+        if removed {
+            write_routes(&mut settings, family, &routes)?;
+        }
+        Ok(settings)
+    })
+    .await?;
+    if removed {
+        info!(
+            "Removed route {}/{} from '{}'",
+            route.dest, route.prefix, profile.id
+        );
+    }
+    Ok(removed)
+}
+
+/// Routes NetworkManager installed for the active connection
+/// `connection_id` (name or UUID), IPv4 first. Empty if it is not active.
+pub(crate) async fn active_routes(conn: &Connection, connection_id: &str) -> Result<Vec<Route>> {
+    Ok(active_connection_details(conn)
+        .await?
+        .into_iter()
+        .filter(|details| details.id == connection_id || details.uuid == connection_id)
+        .flat_map(|details| [details.ipv4, details.ipv6])
+        .flatten()
+        .flat_map(|ip| ip.routes)
+        .collect())
+}
+
+/// Validates `route` and returns its settings section (`ipv4` / `ipv6`).
+fn route_family(route: &Route) -> Result<&'static str> {
+    let invalid = |reason: String| ConnectionError::InvalidInput {
+        field: "route".to_string(),
+        reason,
+    };
+    let dest: IpAddr = route
+        .dest
+        .parse()
+        .map_err(|_| invalid(format!("'{}' is not an IP address", route.dest)))?;
+    let max_prefix = if dest.is_ipv4() { 32 } else { 128 };
+    if route.prefix > max_prefix {
+        return Err(invalid(format!(
+            "prefix {} exceeds {max_prefix}",
+            route.prefix
+        )));
+    }
+    if let Some(next_hop) = &route.next_hop {
+        let hop: IpAddr = next_hop
+            .parse()
+            .map_err(|_| invalid(format!("next hop '{next_hop}' is not an IP address")))?;
+        if hop.is_ipv4() != dest.is_ipv4() {
+            return Err(invalid(
+                "next hop and destination are of different address families".to_string(),
+            ));
+        }
+    }
+    Ok(if dest.is_ipv4() { "ipv4" } else { "ipv6" })
+}
+
+/// Routes saved in `family`, which must be configured on the profile.
+fn enabled_routes(settings: &Settings, family: &str) -> Result<Vec<Route>> {
+    if !family_enabled(settings, family) {
+        return Err(ConnectionError::InvalidInput {
+            field: "route".to_string(),
+            reason: format!("{family} is not configured on this profile"),
+        });
+    }
+    Ok(read_routes(settings, family))
+}
+
+fn read_routes(settings: &Settings, family: &str) -> Vec<Route> {
+    settings
+        .get(family)
+        .and_then(|section| section.get("route-data"))
+        .and_then(|data| Vec::<HashMap<String, OwnedValue>>::try_from(data.try_clone().ok()?).ok())
+        .map(|data| parse_route_data(&data))
+        .unwrap_or_default()
+}
+
+fn write_routes(settings: &mut Settings, family: &str, routes: &[Route]) -> Result<()> {
+    let data: Vec<HashMap<String, Value<'static>>> = routes
+        .iter()
+        .map(|route| {
+            let mut entry = HashMap::new();
+            entry.insert("dest".to_string(), Value::from(route.dest.clone()));
+            entry.insert("prefix".to_string(), Value::from(route.prefix));
+            if let Some(next_hop) = &route.next_hop {
+                entry.insert("next-hop".to_string(), Value::from(next_hop.clone()));
+            }
+            if let Some(metric) = route.metric {
+                entry.insert("metric".to_string(), Value::from(metric));
+            }
+            entry
+        })
+        .collect();
+    let data = OwnedValue::try_from(Value::from(data)).map_err(zbus::Error::from)?;
+
+    let section = settings.entry(family.to_string()).or_default();
+    section.remove("routes");
+    section.insert("route-data".to_string(), data);
+    Ok(())
+}
+
+/// Whether `a` and `b` describe the same route, ignoring the metric.
+fn same_route(a: &Route, b: &Route) -> bool {
+    a.dest == b.dest && a.prefix == b.prefix && a.next_hop == b.next_hop
+}
+
+/// Whether `route` matches `pattern`; unset fields of `pattern` match any.
+fn route_matches(route: &Route, pattern: &Route) -> bool {
+    route.dest == pattern.dest
+        && route.prefix == pattern.prefix
+        && (pattern.next_hop.is_none() || route.next_hop == pattern.next_hop)
+        && (pattern.metric.is_none() || route.metric == pattern.metric)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(method: &str) -> Settings {
+        let ipv4 = HashMap::from([
+            (
+                "method".to_string(),
+                OwnedValue::from(zvariant::Str::from(method.to_string())),
+            ),
+            ("routes".to_string(), OwnedValue::from(0u32)),
+        ]);
+        HashMap::from([("ipv4".to_string(), ipv4)])
+    }
+
+    #[test]
+    fn validates_routes() {
+        assert_eq!(route_family(&Route::new("10.0.0.0", 8)).unwrap(), "ipv4");
+        assert_eq!(route_family(&Route::new("fd00::", 64)).unwrap(), "ipv6");
+        assert!(route_family(&Route::new("10.0.0.0", 33)).is_err());
+        assert!(route_family(&Route::new("corp", 8)).is_err());
+        assert!(route_family(&Route::new("10.0.0.0", 8).next_hop("fd00::1")).is_err());
+    }
+
+    #[test]
+    fn writes_route_data_and_drops_legacy_key() {
+        let mut settings = profile("auto");
+        let routes = vec![
+            Route::new("10.0.0.0", 8).next_hop("192.168.1.1").metric(50),
+            Route::new("172.16.0.0", 12),
+        ];
+        write_routes(&mut settings, "ipv4", &routes).unwrap();
+
+        assert!(!settings["ipv4"].contains_key("routes"));
+        assert_eq!(read_routes(&settings, "ipv4"), routes);
+    }
+
+    #[test]
+    fn requires_enabled_family() {
+        assert!(enabled_routes(&profile("manual"), "ipv4").is_ok());
+        assert!(enabled_routes(&profile("disabled"), "ipv4").is_err());
+        assert!(enabled_routes(&profile("auto"), "ipv6").is_err());
+    }
+
+    #[test]
+    fn matches_unset_fields_as_wildcards() {
+        let route = Route::new("10.0.0.0", 8).next_hop("192.168.1.1").metric(50);
+        assert!(route_matches(&route, &Route::new("10.0.0.0", 8)));
+        assert!(route_matches(
+            &route,
+            &Route::new("10.0.0.0", 8).next_hop("192.168.1.1")
+        ));
+        assert!(!route_matches(
+            &route,
+            &Route::new("10.0.0.0", 8).metric(10)
+        ));
+        assert!(!route_matches(&route, &Route::new("10.0.0.0", 16)));
+        assert!(same_route(&route, &route.clone().metric(1)));
+    }
+}
@@ -3,7 +3,8 @@
 use std::collections::HashMap;
 
 use futures::stream::{self, StreamExt};
-use log::warn;
+use log::{info, warn};
+use uuid::Uuid;
 use zbus::Connection;
 use zvariant::{OwnedObjectPath, OwnedValue, Str};
 
@@ -12,7 +13,8 @@ use crate::api::models::{
     ConnectionError, SavedConnection, SavedConnectionBrief, SettingsPatch, SettingsSummary,
     VpnSecretFlags, WifiKeyMgmt, WifiSecuritySummary,
 };
-use crate::dbus::{NMSettingsConnectionProxy, NMSettingsProxy};
+use crate::dbus::{NMSettingsConnectionProxy, NMSettingsProxy, dbus_operation};
+use crate::keyfile::Keyfile;
 use crate::util::utils::decode_ssid_or_empty;
 
 /// Builds the `a{sa{sv}}` delta for [`SettingsPatch`] (unit-tested).
@@ -61,6 +63,17 @@ pub(crate) fn build_settings_patch_delta(
     delta
 }
 
+/// Overlays `delta` onto `base`, key by key within each section.
+pub(crate) fn merge_settings(
+    mut base: HashMap<String, HashMap<String, OwnedValue>>,
+    delta: HashMap<String, HashMap<String, OwnedValue>>,
+) -> HashMap<String, HashMap<String, OwnedValue>> {
+    for (section, entries) in delta {
+        base.entry(section).or_default().extend(entries);
+    }
+    base
+}
+
 fn owned_to_str(v: &OwnedValue) -> Option<String> {
     Str::try_from(v.clone())
         .ok()
@@ -456,7 +469,7 @@ async fn fetch_one_full(
     }
 }
 
-async fn fetch_one_brief(
+pub(crate) async fn fetch_one_brief(
     conn: &Connection,
     path: OwnedObjectPath,
 ) -> Result<Option<SavedConnectionBrief>> {
@@ -507,21 +520,14 @@ async fn fetch_one_brief(
 pub(crate) async fn list_saved_connections(conn: &Connection) -> Result<Vec<SavedConnection>> {
     const IN_FLIGHT: usize = 16;
 
-    let settings =
-        NMSettingsProxy::new(conn)
-            .await
-            .map_err(|e| ConnectionError::DbusOperation {
-                context: "failed to create NM Settings proxy".into(),
-                source: e,
-            })?;
+    let settings = NMSettingsProxy::new(conn)
+        .await
+        .map_err(|e| dbus_operation("failed to create NM Settings proxy", e))?;
 
     let paths = settings
         .list_connections()
         .await
-        .map_err(|e| ConnectionError::DbusOperation {
-            context: "failed to list saved connections".into(),
-            source: e,
-        })?;
+        .map_err(|e| dbus_operation("failed to list saved connections", e))?;
 
     let conn = conn.clone();
     let mut out: Vec<SavedConnection> = stream::iter(paths)
@@ -553,21 +559,14 @@ pub(crate) async fn list_saved_connections_brief(
 ) -> Result<Vec<SavedConnectionBrief>> {
     const IN_FLIGHT: usize = 16;
 
-    let settings =
-        NMSettingsProxy::new(conn)
-            .await
-            .map_err(|e| ConnectionError::DbusOperation {
-                context: "failed to create NM Settings proxy".into(),
-                source: e,
-            })?;
+    let settings = NMSettingsProxy::new(conn)
+        .await
+        .map_err(|e| dbus_operation("failed to create NM Settings proxy", e))?;
 
     let paths = settings
         .list_connections()
         .await
-        .map_err(|e| ConnectionError::DbusOperation {
-            context: "failed to list saved connections".into(),
-            source: e,
-        })?;
+        .map_err(|e| dbus_operation("failed to list saved connections", e))?;
 
     let conn = conn.clone();
     let mut out: Vec<SavedConnectionBrief> = stream::iter(paths)
@@ -597,13 +596,9 @@ pub(crate) async fn resolve_saved_path_by_uuid(
     conn: &Connection,
     uuid: &str,
 ) -> Result<OwnedObjectPath> {
-    let settings =
-        NMSettingsProxy::new(conn)
-            .await
-            .map_err(|e| ConnectionError::DbusOperation {
-                context: "failed to create NM Settings proxy".into(),
-                source: e,
-            })?;
+    let settings = NMSettingsProxy::new(conn)
+        .await
+        .map_err(|e| dbus_operation("failed to create NM Settings proxy", e))?;
 
     settings
         .get_connection_by_uuid(uuid)
@@ -611,6 +606,24 @@ pub(crate) async fn resolve_saved_path_by_uuid(
         .map_err(|_| ConnectionError::SavedConnectionNotFound(uuid.to_string()))
 }
 
+/// Finds a saved profile by UUID, falling back to its name
+/// (`connection.id`).
+pub(crate) async fn resolve_saved_profile(
+    conn: &Connection,
+    connection_id: &str,
+) -> Result<SavedConnectionBrief> {
+    if let Ok(path) = resolve_saved_path_by_uuid(conn, connection_id).await
+        && let Some(brief) = fetch_one_brief(conn, path).await?
+    {
+        return Ok(brief);
+    }
+    list_saved_connections_brief(conn)
+        .await?
+        .into_iter()
+        .find(|brief| brief.id == connection_id)
+        .ok_or_else(|| ConnectionError::SavedConnectionNotFound(connection_id.to_string()))
+}
+
 pub(crate) async fn get_saved_connection(conn: &Connection, uuid: &str) -> Result<SavedConnection> {
     let path = resolve_saved_path_by_uuid(conn, uuid).await?;
     fetch_one_full(conn, path)
@@ -628,18 +641,48 @@ pub(crate) async fn get_saved_connection_raw(
         .map_err(ConnectionError::Dbus)?
         .build()
         .await
-        .map_err(|e| ConnectionError::DbusOperation {
-            context: "failed to build Settings.Connection proxy".into(),
-            source: e,
-        })?;
+        .map_err(|e| dbus_operation("failed to build Settings.Connection proxy", e))?;
 
     proxy
         .get_settings()
         .await
-        .map_err(|e| ConnectionError::DbusOperation {
-            context: "GetSettings failed".into(),
-            source: e,
-        })
+        .map_err(|e| dbus_operation("GetSettings failed", e))
+}
+
+/// Serializes the saved profile `uuid` as `.nmconnection` keyfile text.
+///
+/// Secrets are not included: `GetSettings` never returns them.
+pub(crate) async fn export_profile(conn: &Connection, uuid: &str) -> Result<String> {
+    let settings = get_saved_connection_raw(conn, uuid).await?;
+    Ok(Keyfile::from_settings(&settings).to_string())
+}
+
+/// Adds the profile described by `keyfile` via `AddConnection` and returns
+/// its UUID. A UUID is generated if the keyfile has none.
+pub(crate) async fn import_profile(conn: &Connection, keyfile: &str) -> Result<String> {
+    let mut settings = Keyfile::parse(keyfile)?.to_settings()?;
+    let section = settings.entry("connection".to_string()).or_default();
+    let uuid = match section.get("uuid").and_then(owned_to_str) {
+        Some(uuid) => uuid,
+        None => {
+            let uuid = Uuid::new_v4().to_string();
+            section.insert(
+                "uuid".to_string(),
+                OwnedValue::from(Str::from(uuid.clone())),
+            );
+            uuid
+        }
+    };
+
+    let proxy = NMSettingsProxy::new(conn)
+        .await
+        .map_err(|e| dbus_operation("failed to create NM Settings proxy", e))?;
+    proxy
+        .add_connection(settings)
+        .await
+        .map_err(|e| dbus_operation("AddConnection failed", e))?;
+    info!("Imported profile {uuid}");
+    Ok(uuid)
 }
 
 pub(crate) async fn delete_saved_connection(conn: &Connection, uuid: &str) -> Result<()> {
@@ -649,18 +692,12 @@ pub(crate) async fn delete_saved_connection(conn: &Connection, uuid: &str) -> Re
         .map_err(ConnectionError::Dbus)?
         .build()
         .await
-        .map_err(|e| ConnectionError::DbusOperation {
-            context: "failed to build Settings.Connection proxy".into(),
-            source: e,
-        })?;
+        .map_err(|e| dbus_operation("failed to build Settings.Connection proxy", e))?;
 
     proxy
         .delete()
         .await
-        .map_err(|e| ConnectionError::DbusOperation {
-            context: "Delete failed".into(),
-            source: e,
-        })
+        .map_err(|e| dbus_operation("Delete failed", e))
 }
 
 pub(crate) async fn update_saved_connection(
@@ -668,67 +705,83 @@ pub(crate) async fn update_saved_connection(
     uuid: &str,
     patch: &SettingsPatch,
 ) -> Result<()> {
+    let delta = build_settings_patch_delta(patch);
+    if delta.is_empty() {
+        return Ok(());
+    }
+
+    // Update/UpdateUnsaved replace the whole profile, so apply the delta on
+    // top of the current settings rather than sending it alone.
+    edit_saved_connection(conn, uuid, |current| Ok(merge_settings(current, delta))).await
+}
+
+/// Reads the settings of profile `uuid`, passes them through `edit` and
+/// writes the result back (`UpdateUnsaved` for in-memory profiles,
+/// `Update` otherwise).
+///
+/// For changes a [`SettingsPatch`] cannot express, such as removing keys.
+pub(crate) async fn edit_saved_connection<F>(conn: &Connection, uuid: &str, edit: F) -> Result<()>
+where
+    F: FnOnce(
+        HashMap<String, HashMap<String, OwnedValue>>,
+    ) -> Result<HashMap<String, HashMap<String, OwnedValue>>>,
+{
     let path = resolve_saved_path_by_uuid(conn, uuid).await?;
     let proxy = NMSettingsConnectionProxy::builder(conn)
         .path(path)
         .map_err(ConnectionError::Dbus)?
         .build()
         .await
-        .map_err(|e| ConnectionError::DbusOperation {
-            context: "failed to build Settings.Connection proxy".into(),
-            source: e,
-        })?;
+        .map_err(|e| dbus_operation("failed to build Settings.Connection proxy", e))?;
 
-    let delta = build_settings_patch_delta(patch);
-    if delta.is_empty() {
-        return Ok(());
-    }
+    let current = proxy
+        .get_settings()
+        .await
+        .map_err(|e| dbus_operation("GetSettings failed", e))?;
+    let settings = edit(current)?;
 
     let unsaved = proxy
         .unsaved()
         .await
-        .map_err(|e| ConnectionError::DbusOperation {
-            context: "read unsaved property".into(),
-            source: e,
-        })?;
+        .map_err(|e| dbus_operation("read unsaved property", e))?;
 
     if unsaved {
         proxy
-            .update_unsaved(delta)
+            .update_unsaved(settings)
             .await
-            .map_err(|e| ConnectionError::DbusOperation {
-                context: "UpdateUnsaved failed".into(),
-                source: e,
-            })?;
+            .map_err(|e| dbus_operation("UpdateUnsaved failed", e))?;
     } else {
         proxy
-            .update(delta)
+            .update(settings)
             .await
-            .map_err(|e| ConnectionError::DbusOperation {
-                context: "Update failed".into(),
-                source: e,
-            })?;
+            .map_err(|e| dbus_operation("Update failed", e))?;
     }
 
     Ok(())
 }
 
+/// Whether address `family` (`"ipv4"` / `"ipv6"`) is present and not
+/// disabled in `settings`.
+pub(crate) fn family_enabled(
+    settings: &HashMap<String, HashMap<String, OwnedValue>>,
+    family: &str,
+) -> bool {
+    let Some(section) = settings.get(family) else {
+        return false;
+    };
+    let method = section.get("method").and_then(owned_to_str);
+    !matches!(method.as_deref(), Some("disabled" | "ignore"))
+}
+
 pub(crate) async fn reload_saved_connections(conn: &Connection) -> Result<()> {
-    let settings =
-        NMSettingsProxy::new(conn)
-            .await
-            .map_err(|e| ConnectionError::DbusOperation {
-                context: "failed to create NM Settings proxy".into(),
-                source: e,
-            })?;
+    let settings = NMSettingsProxy::new(conn)
+        .await
+        .map_err(|e| dbus_operation("failed to create NM Settings proxy", e))?;
 
     let _ok = settings
         .reload_connections()
         .await
-        .map_err(|e| ConnectionError::DbusOperation {
-            context: "ReloadConnections failed".into(),
-            source: e,
-        })?;
+        .map_err(|e| dbus_operation("ReloadConnections failed", e))?;
 
     Ok(())
 }
@@ -946,4 +999,62 @@ mod tests {
             Some("bar")
         );
     }
+
+    #[test]
+    fn patch_builder_sets_connection_keys() {
+        let patch = SettingsPatch::new()
+            .with_autoconnect_priority(5)
+            .with_interface_name(None);
+        assert!(!patch.is_empty());
+        let d = build_settings_patch_delta(&patch);
+        let c = d.get("connection").unwrap();
+        assert_eq!(c.get("autoconnect-priority"), Some(&OwnedValue::from(5i32)));
+        assert_eq!(
+            owned_to_str(c.get("interface-name").unwrap()).as_deref(),
+            Some("")
+        );
+        assert!(SettingsPatch::new().is_empty());
+    }
+
+    #[test]
+    fn merge_settings_keeps_untouched_keys() {
+        let mut base = HashMap::new();
+        base.insert(
+            "connection".into(),
+            conn_section("u5", "Home", "802-11-wireless"),
+        );
+        let delta = build_settings_patch_delta(&SettingsPatch::new().with_id("Office"));
+
+        let merged = merge_settings(base, delta);
+        let c = merged.get("connection").unwrap();
+        assert_eq!(
+            owned_to_str(c.get("id").unwrap()).as_deref(),
+            Some("Office")
+        );
+        assert_eq!(owned_to_str(c.get("uuid").unwrap()).as_deref(), Some("u5"));
+        assert_eq!(
+            owned_to_str(c.get("type").unwrap()).as_deref(),
+            Some("802-11-wireless")
+        );
+    }
+
+    #[test]
+    fn last_used_is_none_for_zero_timestamp() {
+        let mut settings = HashMap::new();
+        settings.insert("connection".into(), conn_section("u6", "tun", "tun"));
+        let mut c = decode_saved(
+            OwnedObjectPath::try_from("/o6").unwrap(),
+            false,
+            None,
+            settings,
+        )
+        .unwrap();
+        assert!(c.last_used().is_none());
+
+        c.timestamp_unix = 1_700_000_000;
+        assert_eq!(
+            c.last_used(),
+            Some(std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_700_000_000))
+        );
+    }
 }
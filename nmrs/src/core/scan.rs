@@ -3,18 +3,38 @@
 //! Provides functions to trigger Wi-Fi scans and list visible networks
 //! with their properties (SSID, signal strength, security type).
 
+use futures::future::{self, Either};
+use futures::stream::{self, SelectAll, Stream, StreamExt, TryStreamExt};
+use futures_timer::Delay;
+use log::debug;
 use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::pin::Pin;
+use std::time::Duration;
 use zbus::Connection;
+use zvariant::{OwnedObjectPath, OwnedValue, Value};
 
 use crate::Result;
-use crate::api::models::access_point::{AccessPoint, ApMode, decode_security};
-use crate::api::models::{ConnectionError, DeviceState, Network};
-use crate::core::connection_settings::has_saved_connection;
-use crate::dbus::{NMAccessPointProxy, NMDeviceProxy, NMProxy, NMWirelessProxy};
+use crate::api::models::access_point::{
+    AccessPoint, ApMode, BssLoad, decode_security, parse_bss_load,
+};
+use crate::api::models::{
+    ConnectionError, DeviceState, KnownNetwork, MatchOptions, Network, SavedConnection,
+    ScanOptions, SettingsSummary,
+};
+use crate::backend::{DbusBackend, NmBackend};
+use crate::core::connection::find_wifi_device;
+use crate::core::connection_settings::{profile_matches, settings_values};
+use crate::core::saved_connection::list_saved_connections;
+use crate::dbus::{
+    NMAccessPointProxy, NMDeviceProxy, NMProxy, NMWirelessProxy, WpaSupplicantBssProxy,
+    WpaSupplicantInterfaceProxy, WpaSupplicantProxy,
+};
 use crate::monitoring::info::current_ssid;
-use crate::types::constants::{device_type, security_flags, wifi_mode};
+use crate::types::constants::{device_type, security_flags, timeouts};
 use crate::util::utils::{
-    decode_ssid_or_empty, decode_ssid_or_hidden, get_ip_addresses_from_active_connection,
+    boottime_to_system_time, decode_ssid_or_empty, decode_ssid_or_hidden,
+    get_ip_addresses_from_active_connection, property,
 };
 
 /// Triggers a Wi-Fi scan.
@@ -23,50 +43,46 @@ use crate::util::utils::{
 /// When `Some`, scans only the matching device.
 /// The scan runs asynchronously; call [`list_networks`] after a delay.
 pub(crate) async fn scan_networks(conn: &Connection, interface: Option<&str>) -> Result<()> {
-    let nm = NMProxy::new(conn).await?;
-    let devices = nm.get_devices().await?;
+    scan_networks_with_options(conn, interface, &ScanOptions::default()).await
+}
 
-    let mut scanned_any = false;
-    for dp in devices {
-        let d_proxy = NMDeviceProxy::builder(conn)
-            .path(dp.clone())?
-            .build()
-            .await?;
+/// Triggers a Wi-Fi scan that also probes for `options.ssids`.
+pub(crate) async fn scan_networks_with_options(
+    conn: &Connection,
+    interface: Option<&str>,
+    options: &ScanOptions,
+) -> Result<()> {
+    request_scans(
+        &DbusBackend::from_connection(conn.clone()),
+        interface,
+        options,
+    )
+    .await
+}
 
-        let dev_type = d_proxy
-            .device_type()
-            .await
-            .map_err(|e| ConnectionError::DbusOperation {
-                context: format!(
-                    "failed to get device type for {} during Wi-Fi scan",
-                    dp.as_str()
-                ),
-                source: e,
-            })?;
+/// [`scan_networks_with_options`] against any [`NmBackend`].
+pub(crate) async fn request_scans<B: NmBackend + ?Sized>(
+    backend: &B,
+    interface: Option<&str>,
+    options: &ScanOptions,
+) -> Result<()> {
+    let request = scan_request(options)?;
 
+    let mut scanned_any = false;
+    for dp in backend.get_devices().await? {
+        let dev_type = backend.device_type(&dp).await?;
         if dev_type != device_type::WIFI {
             continue;
         }
 
         if let Some(want) = interface {
-            let iface = d_proxy.interface().await.unwrap_or_default();
+            let iface = backend.device_interface(&dp).await.unwrap_or_default();
             if iface != want {
                 continue;
             }
         }
 
-        let wifi = NMWirelessProxy::builder(conn)
-            .path(dp.clone())?
-            .build()
-            .await?;
-
-        let opts = std::collections::HashMap::new();
-        wifi.request_scan(opts)
-            .await
-            .map_err(|e| ConnectionError::DbusOperation {
-                context: format!("failed to request Wi-Fi scan on device {}", dp.as_str()),
-                source: e,
-            })?;
+        backend.request_scan(&dp, request.clone()).await?;
         scanned_any = true;
     }
 
@@ -81,6 +97,81 @@ pub(crate) async fn scan_networks(conn: &Connection, interface: Option<&str>) ->
     Ok(())
 }
 
+/// Scans, waits for the scan to complete and lists the networks found.
+///
+/// Every targeted device is scanned concurrently; the results are read as
+/// soon as each device's `LastScan` advances, bounded by
+/// [`timeouts::scan_complete_timeout`].
+pub(crate) async fn scan_and_wait(
+    conn: &Connection,
+    interface: Option<&str>,
+) -> Result<Vec<Network>> {
+    wait_for_fresh_scan(conn, interface).await?;
+    list_networks(conn, interface).await
+}
+
+/// Scans every targeted device and waits for the scans to complete, as
+/// [`scan_and_wait`] does, without reading the results.
+pub(crate) async fn wait_for_fresh_scan(conn: &Connection, interface: Option<&str>) -> Result<()> {
+    wait_for_scans(&DbusBackend::from_connection(conn.clone()), interface).await?;
+    Ok(())
+}
+
+/// [`wait_for_fresh_scan`] against any [`NmBackend`]; returns how many
+/// scans completed in time.
+pub(crate) async fn wait_for_scans<B: NmBackend + ?Sized>(
+    backend: &B,
+    interface: Option<&str>,
+) -> Result<usize> {
+    let devices = wifi_devices(backend, interface).await?;
+    let completed =
+        future::try_join_all(devices.iter().map(|device| {
+            backend.request_scan_and_wait(device, timeouts::scan_complete_timeout())
+        }))
+        .await?;
+    let done = completed.iter().filter(|done| **done).count();
+    debug!("{done} of {} scans completed", completed.len());
+    Ok(done)
+}
+
+/// Resolves `interface`, or every Wi-Fi device when `None`.
+async fn wifi_devices<B: NmBackend + ?Sized>(
+    backend: &B,
+    interface: Option<&str>,
+) -> Result<Vec<OwnedObjectPath>> {
+    if interface.is_some() {
+        return Ok(vec![find_wifi_device(backend, interface).await?]);
+    }
+    let mut wifi = Vec::new();
+    for path in backend.get_devices().await? {
+        if backend.device_type(&path).await? == device_type::WIFI {
+            wifi.push(path);
+        }
+    }
+    Ok(wifi)
+}
+
+/// Builds the `RequestScan` options dictionary.
+fn scan_request(options: &ScanOptions) -> Result<HashMap<String, Value<'static>>> {
+    let mut request = HashMap::new();
+    if options.ssids.is_empty() {
+        return Ok(request);
+    }
+
+    let mut ssids = Vec::with_capacity(options.ssids.len());
+    for ssid in &options.ssids {
+        if ssid.is_empty() || ssid.len() > 32 {
+            return Err(ConnectionError::InvalidInput {
+                field: "ssids".into(),
+                reason: format!("'{ssid}' must be 1 to 32 bytes long"),
+            });
+        }
+        ssids.push(ssid.as_bytes().to_vec());
+    }
+    request.insert("ssids".to_string(), Value::from(ssids));
+    Ok(request)
+}
+
 /// Lists all visible access points, one entry per BSSID.
 ///
 /// When `interface` is `Some`, only APs from that wireless device are returned.
@@ -92,22 +183,22 @@ pub(crate) async fn list_access_points(
     conn: &Connection,
     interface: Option<&str>,
 ) -> Result<Vec<AccessPoint>> {
-    let nm = NMProxy::new(conn).await?;
-    let devices = nm.get_devices().await?;
+    read_access_points(&DbusBackend::from_connection(conn.clone()), interface).await
+}
 
+/// [`list_access_points`] against any [`NmBackend`].
+pub(crate) async fn read_access_points<B: NmBackend + ?Sized>(
+    backend: &B,
+    interface: Option<&str>,
+) -> Result<Vec<AccessPoint>> {
     let mut results = Vec::new();
 
-    for dp in devices {
-        let dev = NMDeviceProxy::builder(conn)
-            .path(dp.clone())?
-            .build()
-            .await?;
-
-        if dev.device_type().await? != device_type::WIFI {
+    for dp in backend.get_devices().await? {
+        if backend.device_type(&dp).await? != device_type::WIFI {
             continue;
         }
 
-        let iface = dev.interface().await.unwrap_or_default();
+        let iface = backend.device_interface(&dp).await.unwrap_or_default();
 
         if let Some(target) = interface
             && iface != target
@@ -115,62 +206,175 @@ pub(crate) async fn list_access_points(
             continue;
         }
 
-        let raw_state = dev.state().await?;
-        let device_state: DeviceState = raw_state.into();
+        let device = DeviceContext {
+            state: backend.device_state(&dp).await?.into(),
+            active_ap: backend.active_access_point(&dp).await?,
+            last_scan_ms: backend.last_scan(&dp).await.ok().filter(|&ms| ms >= 0),
+            bss_loads: backend.bss_loads(&iface).await,
+            interface: iface,
+            path: dp,
+        };
 
-        let wifi = NMWirelessProxy::builder(conn)
-            .path(dp.clone())?
-            .build()
+        let device = &device;
+        let aps: Vec<AccessPoint> = stream::iter(backend.access_points(&device.path).await?)
+            .map(|ap_path| async move {
+                let props = backend.access_point_properties(&ap_path).await?;
+                Ok::<_, ConnectionError>(access_point_from_properties(ap_path, &props, device))
+            })
+            .buffered(IN_FLIGHT)
+            .try_collect()
             .await?;
+        results.extend(aps);
+    }
 
-        let active_ap = wifi.active_access_point().await?;
-        let is_active_ap = |path: &zvariant::OwnedObjectPath| -> bool {
-            active_ap.as_str() != "/" && &active_ap == path
-        };
+    Ok(results)
+}
 
-        for ap_path in wifi.access_points().await? {
-            let ap = NMAccessPointProxy::builder(conn)
-                .path(ap_path.clone())?
-                .build()
-                .await?;
-
-            let ssid_bytes = ap.ssid().await?;
-            let ssid = decode_ssid_or_hidden(&ssid_bytes);
-            let bssid = ap.hw_address().await?;
-            let flags = ap.flags().await?;
-            let wpa = ap.wpa_flags().await?;
-            let rsn = ap.rsn_flags().await?;
-            let frequency_mhz = ap.frequency().await?;
-            let max_bitrate_kbps = ap.max_bitrate().await.unwrap_or(0);
-            let strength = ap.strength().await?;
-            let mode_raw = ap.mode().await.unwrap_or(0);
-            let last_seen_raw = ap.last_seen().await.unwrap_or(-1);
-            let last_seen_secs = if last_seen_raw < 0 {
-                None
-            } else {
-                Some(i64::from(last_seen_raw))
-            };
-
-            results.push(AccessPoint {
-                path: ap_path.clone(),
-                device_path: dp.clone(),
-                interface: iface.clone(),
-                ssid: ssid.to_string(),
-                ssid_bytes: ssid_bytes.clone(),
-                bssid,
-                frequency_mhz,
-                max_bitrate_kbps,
-                strength,
-                mode: ApMode::from(mode_raw),
-                security: decode_security(flags, wpa, rsn),
-                last_seen_secs,
-                is_active: is_active_ap(&ap_path),
-                device_state: device_state.clone(),
-            });
+/// D-Bus interface of NetworkManager access point objects.
+pub(crate) const AP_INTERFACE: &str = "org.freedesktop.NetworkManager.AccessPoint";
+
+/// Access points read concurrently per device.
+const IN_FLIGHT: usize = 16;
+
+/// Per-device state shared by the access points of one Wi-Fi device.
+pub(crate) struct DeviceContext {
+    pub(crate) path: OwnedObjectPath,
+    pub(crate) interface: String,
+    pub(crate) state: DeviceState,
+    pub(crate) active_ap: OwnedObjectPath,
+    /// `LastScan` of the device in `CLOCK_BOOTTIME` ms, `None` if never.
+    pub(crate) last_scan_ms: Option<i64>,
+    pub(crate) bss_loads: HashMap<String, BssLoad>,
+}
+
+/// Builds an [`AccessPoint`] from its `GetAll` properties. Properties
+/// NetworkManager leaves out read as zero or empty.
+pub(crate) fn access_point_from_properties(
+    ap_path: OwnedObjectPath,
+    props: &HashMap<String, OwnedValue>,
+    device: &DeviceContext,
+) -> AccessPoint {
+    let number = |key: &str| property::<u32>(props, key).unwrap_or(0);
+    let ssid_bytes = ssid_property(props);
+    let ssid = decode_ssid_or_hidden(&ssid_bytes).into_owned();
+    let bssid: String = property(props, "HwAddress").unwrap_or_default();
+    let flags = number("Flags");
+    let wpa = number("WpaFlags");
+    let rsn = number("RsnFlags");
+    let frequency_mhz = number("Frequency");
+    let max_bitrate_kbps = number("MaxBitrate");
+    let strength = property::<u8>(props, "Strength").unwrap_or(0);
+    let bss_load = device.bss_loads.get(&bssid.to_ascii_uppercase()).copied();
+    let mode_raw = number("Mode");
+    let last_seen_raw = property::<i32>(props, "LastSeen").unwrap_or(-1);
+    let last_seen_secs = if last_seen_raw < 0 {
+        None
+    } else {
+        Some(i64::from(last_seen_raw))
+    };
+
+    let is_active = device.active_ap.as_str() != "/" && device.active_ap == ap_path;
+    AccessPoint {
+        path: ap_path,
+        device_path: device.path.clone(),
+        interface: device.interface.clone(),
+        ssid,
+        ssid_bytes,
+        bssid,
+        frequency_mhz,
+        max_bitrate_kbps,
+        strength,
+        mode: ApMode::from(mode_raw),
+        security: decode_security(flags, wpa, rsn),
+        last_seen_secs,
+        seen: seen_boot_ms(last_seen_secs, device.last_scan_ms).and_then(boottime_to_system_time),
+        is_active,
+        device_state: device.state.clone(),
+        bss_load,
+    }
+}
+
+/// `Ssid` of a `GetAll` reply, copied straight out of the reply's byte
+/// array instead of through a clone of the whole D-Bus value.
+fn ssid_property(props: &HashMap<String, OwnedValue>) -> Vec<u8> {
+    match props.get("Ssid").map(|value| &**value) {
+        Some(Value::Array(bytes)) => bytes
+            .iter()
+            .filter_map(|byte| match byte {
+                Value::U8(b) => Some(*b),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+async fn last_scan_ms(wifi: &NMWirelessProxy<'_>) -> Option<i64> {
+    wifi.last_scan().await.ok().filter(|&ms| ms >= 0)
+}
+
+/// When an AP was last seen, in `CLOCK_BOOTTIME` ms.
+///
+/// `LastSeen` only has second resolution; an AP seen in the second of the
+/// device's last scan was seen by that scan, so its exact time is used.
+fn seen_boot_ms(last_seen_secs: Option<i64>, last_scan_ms: Option<i64>) -> Option<i64> {
+    let seen_ms = last_seen_secs? * 1000;
+    match last_scan_ms {
+        Some(scan) if scan / 1000 == seen_ms / 1000 => Some(scan),
+        _ => Some(seen_ms),
+    }
+}
+
+/// Reads BSS Load elements for `iface` from wpa_supplicant, keyed by
+/// upper-case BSSID.
+///
+/// NetworkManager does not expose information elements, so this asks
+/// wpa_supplicant directly. Any failure (supplicant not on the bus, access
+/// denied, BSS gone) yields an empty or partial map.
+pub(crate) async fn supplicant_bss_loads(
+    conn: &Connection,
+    iface: &str,
+) -> HashMap<String, BssLoad> {
+    let mut loads = HashMap::new();
+
+    let supplicant = match WpaSupplicantProxy::new(conn).await {
+        Ok(p) => p,
+        Err(e) => {
+            debug!("wpa_supplicant unavailable, skipping BSS load: {e}");
+            return loads;
+        }
+    };
+    let Ok(iface_path) = supplicant.get_interface(iface).await else {
+        debug!("No wpa_supplicant interface for {iface}, skipping BSS load");
+        return loads;
+    };
+    let Ok(builder) = WpaSupplicantInterfaceProxy::builder(conn).path(iface_path) else {
+        return loads;
+    };
+    let Ok(sup_iface) = builder.build().await else {
+        return loads;
+    };
+
+    for bss_path in sup_iface.bss_list().await.unwrap_or_default() {
+        if let Ok(builder) = WpaSupplicantBssProxy::builder(conn).path(bss_path)
+            && let Ok(bss) = builder.build().await
+            && let Ok(bssid) = bss.bssid().await
+            && let Ok(ies) = bss.ies().await
+            && let Some(load) = parse_bss_load(&ies)
+        {
+            loads.insert(format_bssid(&bssid), load);
         }
     }
 
-    Ok(results)
+    loads
+}
+
+fn format_bssid(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{b:02X}"))
+        .collect::<Vec<_>>()
+        .join(":")
 }
 
 /// Lists all visible Wi-Fi networks.
@@ -180,89 +384,168 @@ pub(crate) async fn list_access_points(
 /// APs by SSID, picking the strongest signal as the representative.
 ///
 /// Each returned [`Network`] carries the `best_bssid`, `bssids` list, and
-/// `security_features` from the underlying access points.
+/// `security_features` from the underlying access points, plus their `mode`.
 pub(crate) async fn list_networks(
     conn: &Connection,
     interface: Option<&str>,
 ) -> Result<Vec<Network>> {
-    let aps = list_access_points(conn, interface).await?;
+    read_networks(&DbusBackend::from_connection(conn.clone()), interface).await
+}
 
-    let mut groups: HashMap<(String, String), Network> = HashMap::new();
+/// [`list_networks`] against any [`NmBackend`].
+pub(crate) async fn read_networks<B: NmBackend + ?Sized>(
+    backend: &B,
+    interface: Option<&str>,
+) -> Result<Vec<Network>> {
+    let aps = read_access_points(backend, interface).await?;
+    let mut networks = group_networks(&aps);
+    annotate_networks(backend, &aps, &mut networks).await;
+    Ok(networks)
+}
 
-    for ap in &aps {
-        let key = (ap.interface.clone(), ap.ssid.clone());
-        let sec_flags = ap.security;
-        let secured = !sec_flags.is_open();
-        let is_psk = sec_flags.psk;
-        let is_eap = sec_flags.eap || sec_flags.eap_suite_b_192;
-        let is_hotspot = ap.mode == ApMode::Ap;
+/// Lists visible Wi-Fi networks joined with the saved Wi-Fi profiles whose
+/// SSID they carry.
+///
+/// Saved profiles of networks not currently in range are not returned.
+pub(crate) async fn known_networks(
+    conn: &Connection,
+    interface: Option<&str>,
+) -> Result<Vec<KnownNetwork>> {
+    let networks = list_networks(conn, interface).await?;
+    let profiles = list_saved_connections(conn).await?;
+    Ok(networks
+        .into_iter()
+        .map(|net| join_profiles(net, &profiles))
+        .collect())
+}
 
-        let (ip4_address, ip6_address) = if ap.is_active {
-            active_ip_addresses(conn, &ap.device_path).await
-        } else {
-            (None, None)
-        };
+/// Joins `net` with the Wi-Fi profiles among `profiles` for its SSID.
+fn join_profiles(network: Network, profiles: &[SavedConnection]) -> KnownNetwork {
+    let matching: Vec<&SavedConnection> = if network.ssid.trim().is_empty() {
+        Vec::new()
+    } else {
+        profiles
+            .iter()
+            .filter(|p| {
+                matches!(&p.summary, SettingsSummary::Wifi { ssid, .. } if *ssid == network.ssid)
+            })
+            .collect()
+    };
+    KnownNetwork {
+        saved: !matching.is_empty(),
+        autoconnect: matching.iter().any(|p| p.autoconnect),
+        last_connected: matching.iter().filter_map(|p| p.last_used()).max(),
+        network,
+    }
+}
 
-        let net = Network {
-            device: if ap.is_active {
-                ap.interface.clone()
-            } else {
-                String::new()
-            },
-            ssid: ap.ssid.clone(),
-            bssid: Some(ap.bssid.clone()),
-            strength: Some(ap.strength),
-            frequency: Some(ap.frequency_mhz),
-            secured,
-            is_psk,
-            is_eap,
-            is_hotspot,
-            ip4_address,
-            ip6_address,
-            best_bssid: ap.bssid.clone(),
-            bssids: vec![ap.bssid.clone()],
-            is_active: ap.is_active,
-            known: false,
-            security_features: sec_flags,
-        };
+/// Fills `known` and, for networks in use, their IP addresses.
+async fn annotate_networks<B: NmBackend + ?Sized>(
+    backend: &B,
+    aps: &[AccessPoint],
+    networks: &mut [Network],
+) {
+    // Saved profiles are read once rather than once per network.
+    let saved: Vec<_> = backend
+        .all_connection_settings()
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(_, owned)| settings_values(owned))
+        .collect();
+    let opts = MatchOptions::default();
 
-        groups
-            .entry(key)
-            .and_modify(|n| n.merge_ap(&net))
-            .or_insert(net);
-    }
+    for net in networks {
+        net.known = !net.ssid.trim().is_empty()
+            && saved.iter().any(|p| profile_matches(p, &net.ssid, &opts));
 
-    // Populate `known` by checking saved connections
-    for net in groups.values_mut() {
-        net.known = has_saved_connection(conn, &net.ssid).await.unwrap_or(false);
-        if net.device.is_empty()
-            && net.is_active
+        if net.is_active
             && let Some(ap) = aps.iter().find(|a| a.ssid == net.ssid && a.is_active)
         {
-            net.device.clone_from(&ap.interface);
+            if net.device.is_empty() {
+                net.device.clone_from(&ap.interface);
+            }
+            (net.ip4_address, net.ip6_address) = backend
+                .device_ip_addresses(&ap.device_path)
+                .await
+                .unwrap_or_default();
+        }
+    }
+}
+
+/// Groups access points into networks keyed by (interface, SSID, mode).
+///
+/// Keys borrow from `aps` and each group is built from its first AP, so
+/// an AP costs at most one BSSID allocation however many share its SSID.
+pub(crate) fn group_networks(aps: &[AccessPoint]) -> Vec<Network> {
+    let mut index: HashMap<(&str, &str, ApMode), usize> = HashMap::with_capacity(aps.len());
+    let mut networks: Vec<Network> = Vec::new();
+
+    for ap in aps {
+        match index.entry((ap.interface.as_str(), ap.ssid.as_str(), ap.mode)) {
+            Entry::Occupied(slot) => merge_access_point(&mut networks[*slot.get()], ap),
+            Entry::Vacant(slot) => {
+                slot.insert(networks.len());
+                networks.push(network_from_access_point(ap));
+            }
         }
     }
 
-    Ok(groups.into_values().collect())
+    networks
 }
 
-/// Helper to get IP addresses from the active connection on a device.
-async fn active_ip_addresses(
-    conn: &Connection,
-    device_path: &zvariant::OwnedObjectPath,
-) -> (Option<String>, Option<String>) {
-    let builder = match NMDeviceProxy::builder(conn).path(device_path.clone()) {
-        Ok(b) => b,
-        Err(_) => return (None, None),
-    };
-    let dev = match builder.build().await {
-        Ok(d) => d,
-        Err(_) => return (None, None),
-    };
+fn network_from_access_point(ap: &AccessPoint) -> Network {
+    let sec = ap.security;
+    Network {
+        device: if ap.is_active {
+            ap.interface.clone()
+        } else {
+            String::new()
+        },
+        ssid: ap.ssid.clone(),
+        bssid: Some(ap.bssid.clone()),
+        strength: Some(ap.strength),
+        frequency: Some(ap.frequency_mhz),
+        secured: !sec.is_open(),
+        is_psk: sec.psk,
+        is_eap: sec.eap || sec.eap_suite_b_192,
+        is_hotspot: ap.mode == ApMode::Ap,
+        ip4_address: None,
+        ip6_address: None,
+        best_bssid: ap.bssid.clone(),
+        bssids: vec![ap.bssid.clone()],
+        is_active: ap.is_active,
+        known: false,
+        security_features: sec,
+        mode: ap.mode,
+        seen: ap.seen,
+    }
+}
 
-    match dev.active_connection().await {
-        Ok(ac) if ac.as_str() != "/" => get_ip_addresses_from_active_connection(conn, &ac).await,
-        _ => (None, None),
+/// Same merge as [`Network::merge_ap`], reading the AP in place.
+fn merge_access_point(net: &mut Network, ap: &AccessPoint) {
+    if !net.bssids.contains(&ap.bssid) {
+        net.bssids.push(ap.bssid.clone());
+    }
+
+    if ap.strength > net.strength.unwrap_or(0) {
+        net.strength = Some(ap.strength);
+        net.frequency = Some(ap.frequency_mhz);
+        net.bssid = Some(ap.bssid.clone());
+        net.best_bssid.clone_from(&ap.bssid);
+        net.security_features = ap.security;
+    }
+
+    let sec = ap.security;
+    net.secured |= !sec.is_open();
+    net.is_psk |= sec.psk;
+    net.is_eap |= sec.eap || sec.eap_suite_b_192;
+    net.is_hotspot |= ap.mode == ApMode::Ap;
+    net.is_active |= ap.is_active;
+    net.seen = net.seen.max(ap.seen);
+
+    if net.device.is_empty() && ap.is_active {
+        net.device.clone_from(&ap.interface);
     }
 }
 
@@ -323,7 +606,11 @@ pub(crate) async fn current_network(conn: &Connection) -> Result<Option<Network>
         let secured = (flags & security_flags::WEP) != 0 || wpa != 0 || rsn != 0;
         let is_psk = (wpa & security_flags::PSK) != 0 || (rsn & security_flags::PSK) != 0;
         let is_eap = (wpa & security_flags::EAP) != 0 || (rsn & security_flags::EAP) != 0;
-        let is_hotspot = ap.mode().await.unwrap_or(0) == wifi_mode::AP;
+        let mode = ApMode::from(ap.mode().await.unwrap_or(0));
+        let is_hotspot = mode == ApMode::Ap;
+        let last_seen = ap.last_seen().await.ok().filter(|&s| s >= 0).map(i64::from);
+        let seen =
+            seen_boot_ms(last_seen, last_scan_ms(&wifi).await).and_then(boottime_to_system_time);
 
         let interface = dev.interface().await.unwrap_or_default();
 
@@ -358,8 +645,564 @@ pub(crate) async fn current_network(conn: &Connection) -> Result<Option<Network>
             is_active: true,
             known: true,
             security_features: sec_features,
+            mode,
+            seen,
         }));
     }
 
     Ok(None)
 }
+
+/// What woke the scan scheduler.
+enum Trigger {
+    /// Time for a periodic scan request.
+    Tick,
+    Added {
+        device: OwnedObjectPath,
+        ap: OwnedObjectPath,
+    },
+    Removed(OwnedObjectPath),
+    /// A scan finished or the active access point changed; re-read all APs.
+    Refresh,
+}
+
+type TriggerSource = Pin<Box<dyn Stream<Item = Trigger> + Send>>;
+
+/// State behind [`scan_snapshots`]: the AP table and its signal sources.
+struct Scheduler {
+    conn: Connection,
+    interface: Option<String>,
+    radios: Vec<NMWirelessProxy<'static>>,
+    /// Visible access points, one entry per object path, in arrival order.
+    table: Vec<AccessPoint>,
+    sources: SelectAll<TriggerSource>,
+    debounce: Duration,
+    started: bool,
+}
+
+/// Scans every `interval` and streams debounced network snapshots.
+///
+/// The AP table is seeded from NetworkManager, then kept current from
+/// `AccessPointAdded`/`AccessPointRemoved` and re-read whenever a scan
+/// completes (`LastScan` changes). Changes arriving within `debounce` of
+/// each other produce a single snapshot. The first snapshot is yielded
+/// immediately. Wi-Fi devices added after the stream is created are not
+/// picked up.
+pub(crate) async fn scan_snapshots(
+    conn: &Connection,
+    interface: Option<&str>,
+    interval: Duration,
+    debounce: Duration,
+) -> Result<impl Stream<Item = Vec<Network>> + Send + Unpin + 'static> {
+    let devices = wifi_devices(&DbusBackend::from_connection(conn.clone()), interface).await?;
+
+    let mut sources: SelectAll<TriggerSource> = SelectAll::new();
+    let mut radios = Vec::with_capacity(devices.len());
+    for path in devices {
+        let wifi = NMWirelessProxy::builder(conn)
+            .path(path.clone())?
+            .build()
+            .await?;
+
+        let device = path.clone();
+        sources.push(Box::pin(
+            wifi.receive_access_point_added()
+                .await?
+                .filter_map(move |s| {
+                    let device = device.clone();
+                    future::ready(s.args().ok().map(|a| Trigger::Added { device, ap: a.path }))
+                }),
+        ));
+        sources.push(Box::pin(
+            wifi.receive_access_point_removed()
+                .await?
+                .filter_map(|s| future::ready(s.args().ok().map(|a| Trigger::Removed(a.path)))),
+        ));
+        sources.push(Box::pin(
+            wifi.receive_last_scan_changed()
+                .await
+                .skip(1)
+                .map(|_| Trigger::Refresh),
+        ));
+        sources.push(Box::pin(
+            wifi.receive_active_access_point_changed()
+                .await
+                .skip(1)
+                .map(|_| Trigger::Refresh),
+        ));
+        radios.push(wifi);
+    }
+
+    // The first tick fires right away, later ones `interval` after the last.
+    let interval = interval.max(Duration::from_secs(1));
+    sources.push(Box::pin(stream::unfold(
+        Duration::ZERO,
+        move |wait| async move {
+            Delay::new(wait).await;
+            Some((Trigger::Tick, interval))
+        },
+    )));
+
+    let scheduler = Scheduler {
+        conn: conn.clone(),
+        interface: interface.map(str::to_string),
+        radios,
+        table: Vec::new(),
+        sources,
+        debounce,
+        started: false,
+    };
+
+    Ok(Box::pin(stream::unfold(scheduler, |mut s| async move {
+        let snapshot = s.next_snapshot().await?;
+        Some((snapshot, s))
+    })))
+}
+
+impl Scheduler {
+    async fn next_snapshot(&mut self) -> Option<Vec<Network>> {
+        if !self.started {
+            self.started = true;
+            self.refresh().await;
+            return Some(network_snapshot(&self.conn, &self.table).await);
+        }
+
+        loop {
+            let trigger = self.sources.next().await?;
+            if !self.apply(trigger).await {
+                continue;
+            }
+
+            let mut quiet = Delay::new(self.debounce);
+            while let Either::Left((Some(trigger), _)) =
+                future::select(self.sources.next(), &mut quiet).await
+            {
+                self.apply(trigger).await;
+            }
+            return Some(network_snapshot(&self.conn, &self.table).await);
+        }
+    }
+
+    /// Applies `trigger` and reports whether the table changed.
+    async fn apply(&mut self, trigger: Trigger) -> bool {
+        match trigger {
+            Trigger::Tick => {
+                for radio in &self.radios {
+                    if let Err(e) = radio.request_scan(HashMap::new()).await {
+                        // NM rejects requests while a scan is running.
+                        debug!("Periodic scan request failed: {e}");
+                    }
+                }
+                false
+            }
+            Trigger::Added { device, ap } => match added_access_point(&self.conn, device, ap).await
+            {
+                Ok(entry) => {
+                    upsert_access_point(&mut self.table, entry);
+                    true
+                }
+                Err(e) => {
+                    debug!("Failed to read added access point: {e}");
+                    false
+                }
+            },
+            Trigger::Removed(ap) => remove_access_point(&mut self.table, &ap),
+            Trigger::Refresh => {
+                self.refresh().await;
+                true
+            }
+        }
+    }
+
+    async fn refresh(&mut self) {
+        match list_access_points(&self.conn, self.interface.as_deref()).await {
+            Ok(aps) => self.table = aps,
+            Err(e) => debug!("Failed to refresh access points: {e}"),
+        }
+    }
+}
+
+/// Reads an access point reported by `AccessPointAdded` on `device`.
+async fn added_access_point(
+    conn: &Connection,
+    device: OwnedObjectPath,
+    ap: OwnedObjectPath,
+) -> Result<AccessPoint> {
+    let backend = DbusBackend::from_connection(conn.clone());
+    let context = DeviceContext {
+        interface: backend.device_interface(&device).await.unwrap_or_default(),
+        state: backend.device_state(&device).await?.into(),
+        active_ap: backend.active_access_point(&device).await?,
+        last_scan_ms: backend.last_scan(&device).await.ok().filter(|&ms| ms >= 0),
+        // Filled in by the next full refresh.
+        bss_loads: HashMap::new(),
+        path: device,
+    };
+    let props = backend.access_point_properties(&ap).await?;
+    Ok(access_point_from_properties(ap, &props, &context))
+}
+
+async fn network_snapshot(conn: &Connection, table: &[AccessPoint]) -> Vec<Network> {
+    let mut networks = group_networks(table);
+    annotate_networks(
+        &DbusBackend::from_connection(conn.clone()),
+        table,
+        &mut networks,
+    )
+    .await;
+    networks
+}
+
+/// Inserts `ap`, replacing any entry with the same object path.
+fn upsert_access_point(table: &mut Vec<AccessPoint>, ap: AccessPoint) {
+    match table.iter_mut().find(|e| e.path == ap.path) {
+        Some(existing) => *existing = ap,
+        None => table.push(ap),
+    }
+}
+
+/// Removes the entry for `path`, reporting whether there was one.
+fn remove_access_point(table: &mut Vec<AccessPoint>, path: &OwnedObjectPath) -> bool {
+    let before = table.len();
+    table.retain(|e| &e.path != path);
+    table.len() != before
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::models::{ConnectionOptions, DeviceType, SecurityFeatures, WifiSecurity};
+    use crate::backend::{MockAccessPoint, MockBackend};
+    use crate::builders::build_wifi_connection;
+
+    fn ap(iface: &str, ssid: &str, bssid: &str, strength: u8, active: bool) -> AccessPoint {
+        AccessPoint {
+            path: zvariant::OwnedObjectPath::default(),
+            device_path: zvariant::OwnedObjectPath::default(),
+            interface: iface.into(),
+            ssid: ssid.into(),
+            ssid_bytes: ssid.as_bytes().to_vec(),
+            bssid: bssid.into(),
+            frequency_mhz: 2412,
+            max_bitrate_kbps: 0,
+            strength,
+            mode: ApMode::Infrastructure,
+            security: SecurityFeatures::default(),
+            last_seen_secs: None,
+            seen: None,
+            is_active: active,
+            device_state: DeviceState::Disconnected,
+            bss_load: None,
+        }
+    }
+
+    #[test]
+    fn groups_by_interface_and_ssid_keeping_strongest() {
+        let aps = vec![
+            ap("wlan0", "Home", "aa:aa:aa:aa:aa:01", 40, false),
+            ap("wlan0", "Cafe", "aa:aa:aa:aa:aa:02", 70, false),
+            ap("wlan0", "Home", "aa:aa:aa:aa:aa:03", 80, true),
+            ap("wlan1", "Home", "aa:aa:aa:aa:aa:04", 10, false),
+            ap("wlan0", "Home", "aa:aa:aa:aa:aa:01", 40, false),
+        ];
+        let nets = group_networks(&aps);
+
+        assert_eq!(nets.len(), 3);
+        let home = &nets[0];
+        assert_eq!(home.ssid, "Home");
+        assert_eq!(home.strength, Some(80));
+        assert_eq!(home.best_bssid, "aa:aa:aa:aa:aa:03");
+        assert_eq!(home.bssids, ["aa:aa:aa:aa:aa:01", "aa:aa:aa:aa:aa:03"]);
+        assert!(home.is_active);
+        assert_eq!(home.device, "wlan0");
+
+        assert_eq!(nets[1].ssid, "Cafe");
+        assert_eq!(nets[2].bssids, ["aa:aa:aa:aa:aa:04"]);
+        assert!(nets[2].device.is_empty());
+    }
+
+    #[test]
+    fn matches_network_merge_ap() {
+        let aps = [
+            ap("wlan0", "Net", "aa:aa:aa:aa:aa:01", 30, true),
+            ap("wlan0", "Net", "aa:aa:aa:aa:aa:02", 90, false),
+        ];
+        let mut expected = network_from_access_point(&aps[0]);
+        expected.merge_ap(&network_from_access_point(&aps[1]));
+
+        let nets = group_networks(&aps);
+        assert_eq!(nets.len(), 1);
+        assert_eq!(format!("{:?}", nets[0]), format!("{expected:?}"));
+    }
+
+    #[test]
+    fn table_is_keyed_by_object_path() {
+        let path = |n: u32| OwnedObjectPath::try_from(format!("/ap/{n}")).unwrap();
+        let mut table = Vec::new();
+
+        let mut first = ap("wlan0", "Home", "aa:aa:aa:aa:aa:01", 40, false);
+        first.path = path(1);
+        upsert_access_point(&mut table, first.clone());
+        let mut second = ap("wlan0", "Cafe", "aa:aa:aa:aa:aa:02", 70, false);
+        second.path = path(2);
+        upsert_access_point(&mut table, second);
+
+        first.strength = 90;
+        upsert_access_point(&mut table, first);
+        assert_eq!(table.len(), 2);
+        assert_eq!((table[0].ssid.as_str(), table[0].strength), ("Home", 90));
+
+        assert!(remove_access_point(&mut table, &path(1)));
+        assert!(!remove_access_point(&mut table, &path(1)));
+        assert_eq!(table.len(), 1);
+    }
+
+    #[test]
+    fn seen_prefers_last_scan_within_same_second() {
+        assert_eq!(seen_boot_ms(Some(120), Some(120_450)), Some(120_450));
+        assert_eq!(seen_boot_ms(Some(60), Some(120_450)), Some(60_000));
+        assert_eq!(seen_boot_ms(Some(60), None), Some(60_000));
+        assert_eq!(seen_boot_ms(None, Some(120_450)), None);
+    }
+
+    #[test]
+    fn scan_request_carries_ssid_hints() {
+        assert!(scan_request(&ScanOptions::new()).unwrap().is_empty());
+
+        let request = scan_request(&ScanOptions::new().with_ssids(["Hidden", "Lab"])).unwrap();
+        assert_eq!(
+            request.get("ssids"),
+            Some(&Value::from(vec![b"Hidden".to_vec(), b"Lab".to_vec()]))
+        );
+
+        let too_long = "x".repeat(33);
+        for bad in ["", too_long.as_str()] {
+            let err = scan_request(&ScanOptions::new().with_ssid(bad)).unwrap_err();
+            assert!(matches!(err, ConnectionError::InvalidInput { .. }));
+        }
+    }
+
+    fn wifi_profile(ssid: &str, autoconnect: bool, timestamp_unix: u64) -> SavedConnection {
+        SavedConnection {
+            path: zvariant::OwnedObjectPath::default(),
+            uuid: String::new(),
+            id: ssid.into(),
+            connection_type: "802-11-wireless".into(),
+            interface_name: None,
+            autoconnect,
+            autoconnect_priority: 0,
+            timestamp_unix,
+            permissions: Vec::new(),
+            unsaved: false,
+            filename: None,
+            summary: SettingsSummary::Wifi {
+                ssid: ssid.into(),
+                mode: None,
+                security: None,
+                band: None,
+                channel: None,
+                bssid: None,
+                hidden: false,
+                mac_randomization: None,
+            },
+        }
+    }
+
+    #[test]
+    fn joins_networks_with_their_profiles() {
+        let nets = group_networks(&[
+            ap("wlan0", "Home", "aa:aa:aa:aa:aa:01", 80, false),
+            ap("wlan0", "Cafe", "aa:aa:aa:aa:aa:02", 60, false),
+        ]);
+        let profiles = [
+            wifi_profile("Home", false, 1_700_000_000),
+            wifi_profile("Home", true, 1_600_000_000),
+            wifi_profile("Office", true, 1_750_000_000),
+        ];
+
+        let home = join_profiles(nets[0].clone(), &profiles);
+        assert!(home.saved);
+        assert!(home.autoconnect);
+        assert_eq!(
+            home.last_connected,
+            Some(std::time::UNIX_EPOCH + Duration::from_secs(1_700_000_000))
+        );
+
+        let cafe = join_profiles(nets[1].clone(), &profiles);
+        assert!(!cafe.saved);
+        assert!(!cafe.autoconnect);
+        assert_eq!(cafe.last_connected, None);
+    }
+
+    #[test]
+    fn access_point_from_get_all_properties() {
+        let ap_path =
+            OwnedObjectPath::try_from("/org/freedesktop/NetworkManager/AccessPoint/7").unwrap();
+        let device = DeviceContext {
+            path: OwnedObjectPath::try_from("/org/freedesktop/NetworkManager/Devices/3").unwrap(),
+            interface: "wlan0".into(),
+            state: DeviceState::Activated,
+            active_ap: ap_path.clone(),
+            last_scan_ms: None,
+            bss_loads: HashMap::new(),
+        };
+        let props = HashMap::from([
+            (
+                "Ssid".to_string(),
+                OwnedValue::try_from(Value::from(b"Home".to_vec())).unwrap(),
+            ),
+            (
+                "HwAddress".to_string(),
+                OwnedValue::try_from(Value::from("AA:BB:CC:DD:EE:FF")).unwrap(),
+            ),
+            ("Flags".to_string(), OwnedValue::from(1u32)),
+            ("RsnFlags".to_string(), OwnedValue::from(0x188u32)),
+            ("Frequency".to_string(), OwnedValue::from(5180u32)),
+            ("MaxBitrate".to_string(), OwnedValue::from(866_700u32)),
+            ("Strength".to_string(), OwnedValue::from(72u8)),
+            ("Mode".to_string(), OwnedValue::from(2u32)),
+            ("LastSeen".to_string(), OwnedValue::from(-1i32)),
+        ]);
+
+        let ap = access_point_from_properties(ap_path, &props, &device);
+        assert_eq!(ap.ssid, "Home");
+        assert_eq!(ap.bssid, "AA:BB:CC:DD:EE:FF");
+        assert_eq!(ap.frequency_mhz, 5180);
+        assert_eq!(ap.max_bitrate_kbps, 866_700);
+        assert_eq!(ap.strength, 72);
+        assert_eq!(ap.mode, ApMode::Infrastructure);
+        assert!(ap.security.psk && ap.security.ccmp);
+        assert_eq!(ap.last_seen_secs, None);
+        assert!(ap.is_active);
+
+        let bare =
+            access_point_from_properties(OwnedObjectPath::default(), &HashMap::new(), &device);
+        assert_eq!(bare.frequency_mhz, 0);
+        assert!(bare.ssid_bytes.is_empty());
+        assert!(!bare.is_active);
+    }
+
+    fn radios() -> MockBackend {
+        MockBackend::new()
+            .with_device("eth0", DeviceType::Ethernet, DeviceState::Activated)
+            .with_device("wlan0", DeviceType::Wifi, DeviceState::Disconnected)
+            .with_device("wlan1", DeviceType::Wifi, DeviceState::Disconnected)
+    }
+
+    #[tokio::test]
+    async fn scan_requests_target_wifi_devices() {
+        let backend = radios();
+        request_scans(&backend, None, &ScanOptions::default())
+            .await
+            .unwrap();
+        assert_eq!((backend.scans("wlan0"), backend.scans("wlan1")), (1, 1));
+
+        request_scans(&backend, Some("wlan1"), &ScanOptions::default())
+            .await
+            .unwrap();
+        assert_eq!((backend.scans("wlan0"), backend.scans("wlan1")), (1, 2));
+        assert_eq!(backend.scans("eth0"), 0);
+
+        assert!(matches!(
+            request_scans(&backend, Some("eth0"), &ScanOptions::default()).await,
+            Err(ConnectionError::WifiInterfaceNotFound { interface }) if interface == "eth0"
+        ));
+    }
+
+    #[tokio::test]
+    async fn waits_for_each_targeted_scan() {
+        let backend = radios();
+        assert_eq!(wait_for_scans(&backend, None).await.unwrap(), 2);
+        assert_eq!(wait_for_scans(&backend, Some("wlan0")).await.unwrap(), 1);
+        assert_eq!((backend.scans("wlan0"), backend.scans("wlan1")), (2, 1));
+        assert!(matches!(
+            wait_for_scans(&backend, Some("eth0")).await,
+            Err(ConnectionError::NotAWifiDevice { .. })
+        ));
+    }
+
+    fn crowded() -> MockBackend {
+        radios()
+            .with_access_point(
+                "wlan0",
+                MockAccessPoint::new("Home", "AA:AA:AA:AA:AA:01", 2412).with_strength(40),
+            )
+            .with_access_point(
+                "wlan0",
+                MockAccessPoint::new("Home", "AA:AA:AA:AA:AA:02", 5180).with_strength(80),
+            )
+            .with_access_point(
+                "wlan0",
+                MockAccessPoint::new("Cafe", "AA:AA:AA:AA:AA:03", 2437).with_strength(60),
+            )
+            .with_access_point(
+                "wlan1",
+                MockAccessPoint::new("Home", "AA:AA:AA:AA:AA:04", 2412).with_strength(20),
+            )
+            .with_profile(build_wifi_connection(
+                "Home",
+                &WifiSecurity::Open,
+                &ConnectionOptions::new(true),
+            ))
+    }
+
+    #[tokio::test]
+    async fn lists_access_points_of_the_targeted_devices() {
+        let backend = crowded();
+        let aps = read_access_points(&backend, None).await.unwrap();
+        assert_eq!(aps.len(), 4);
+        assert_eq!(aps[1].bssid, "AA:AA:AA:AA:AA:02");
+        assert_eq!((aps[1].frequency_mhz, aps[1].strength), (5180, 80));
+        assert_eq!(aps[1].interface, "wlan0");
+        assert!(aps.iter().all(|ap| !ap.is_active));
+
+        let aps = read_access_points(&backend, Some("wlan1")).await.unwrap();
+        assert_eq!(aps.len(), 1);
+        assert_eq!(aps[0].interface, "wlan1");
+    }
+
+    #[tokio::test]
+    async fn lists_networks_marking_known_and_active() {
+        let backend = crowded();
+        let aps = read_access_points(&backend, Some("wlan0")).await.unwrap();
+        let (_, active) = backend
+            .add_and_activate_connection(
+                build_wifi_connection("Cafe", &WifiSecurity::Open, &ConnectionOptions::new(true)),
+                &aps[2].device_path,
+                &aps[2].path,
+            )
+            .await
+            .unwrap();
+        backend.wait_for_activation(&active, None).await.unwrap();
+
+        let nets = read_networks(&backend, None).await.unwrap();
+        assert_eq!(nets.len(), 3);
+
+        let home = &nets[0];
+        assert_eq!(home.ssid, "Home");
+        assert_eq!(home.best_bssid, "AA:AA:AA:AA:AA:02");
+        assert_eq!(home.bssids.len(), 2);
+        assert!(home.known && !home.is_active);
+
+        let cafe = &nets[1];
+        assert_eq!(cafe.ssid, "Cafe");
+        assert!(cafe.known && cafe.is_active);
+        assert_eq!(cafe.device, "wlan0");
+
+        assert_eq!(nets[2].bssids, ["AA:AA:AA:AA:AA:04"]);
+    }
+
+    #[tokio::test]
+    async fn scanned_access_points_carry_their_age() {
+        let backend = radios().with_access_point(
+            "wlan0",
+            MockAccessPoint::new("Late", "AA:AA:AA:AA:AA:05", 2412).with_visible_after_scans(1),
+        );
+        assert!(read_access_points(&backend, None).await.unwrap().is_empty());
+
+        wait_for_scans(&backend, Some("wlan0")).await.unwrap();
+        let aps = read_access_points(&backend, None).await.unwrap();
+        assert_eq!(aps.len(), 1);
+        assert_eq!(aps[0].last_seen_secs, Some(1));
+    }
+}
@@ -0,0 +1,400 @@
+//! Internet connection sharing.
+//!
+//! A shared profile has `ipv4.method=shared`: NetworkManager runs a DHCP
+//! server on the interface and masquerades its clients out through the
+//! default route. Over Ethernet the profile is bound to the port; over
+//! Wi-Fi it is an access-point (hotspot) profile bound to the radio.
+//!
+//! Existing shared profiles bound to the interface are reused, so sharing
+//! again does not pile up profiles. Profiles created here do not
+//! autoconnect; sharing starts only when asked for.
+
+use log::{debug, info};
+use std::collections::HashMap;
+use std::time::Duration;
+use zbus::Connection;
+use zvariant::{OwnedObjectPath, Value};
+
+use crate::Result;
+use crate::api::builders::{ConnectionBuilder, WifiConnectionBuilder, WifiMode};
+use crate::api::models::{ConnectionError, Secret, ShareOptions, SharedConnection, TimeoutConfig};
+use crate::backend::{DbusBackend, NewProfileSettings, NmBackend, ProfileSettings};
+use crate::core::connection::replace_profile;
+use crate::core::connection_settings::settings_values;
+use crate::dbus::{NMActiveConnectionProxy, NMDeviceProxy};
+use crate::types::constants::device_type;
+use crate::util::utils::decode_ssid_or_empty;
+
+/// Length of a generated hotspot passphrase.
+const GENERATED_PSK_LEN: usize = 12;
+
+/// Link a connection is shared over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ShareLink {
+    Ethernet,
+    Hotspot,
+}
+
+/// Shares the uplink over `interface` and waits for the shared profile to
+/// activate.
+pub(crate) async fn share_connection(
+    conn: &Connection,
+    interface: &str,
+    options: &ShareOptions,
+    timeout_config: Option<TimeoutConfig>,
+) -> Result<SharedConnection> {
+    let backend = DbusBackend::from_connection(conn.clone());
+    let timeout = timeout_config.map(|c| c.connection_timeout);
+    let shared = activate_shared(&backend, interface, options, timeout).await?;
+    info!("Sharing connection over {interface} with '{}'", shared.id);
+    Ok(shared)
+}
+
+/// [`share_connection`] against any [`NmBackend`].
+///
+/// With default options an existing shared profile for `interface` is
+/// activated; otherwise a `shared-<interface>` profile is (re)created.
+pub(crate) async fn activate_shared<B: NmBackend + ?Sized>(
+    backend: &B,
+    interface: &str,
+    options: &ShareOptions,
+    timeout: Option<Duration>,
+) -> Result<SharedConnection> {
+    options.validate()?;
+    let (device, link) = find_share_device(backend, interface).await?;
+
+    if *options == ShareOptions::default()
+        && let Some(existing) = find_shared_profile(backend, interface).await?
+    {
+        debug!("Reusing shared profile '{}'", existing.id);
+        let active = backend
+            .activate_connection(&existing.path, &device, &OwnedObjectPath::default())
+            .await?;
+        backend.wait_for_activation(&active, timeout).await?;
+        return Ok(SharedConnection {
+            active: true,
+            ..existing
+        });
+    }
+
+    let (settings, ssid, psk) = shared_profile(interface, link, options);
+    let uuid = match settings.get("connection").and_then(|c| c.get("uuid")) {
+        Some(Value::Str(uuid)) => uuid.to_string(),
+        _ => String::new(),
+    };
+    let (path, active) = replace_profile(backend, &device, settings).await?;
+    backend.wait_for_activation(&active, timeout).await?;
+    Ok(SharedConnection {
+        interface: interface.to_string(),
+        path,
+        id: shared_id(interface),
+        uuid,
+        ssid,
+        psk,
+        active: true,
+    })
+}
+
+/// Finds the shared profile bound to `interface` and whether it is active.
+pub(crate) async fn shared_connection(
+    conn: &Connection,
+    interface: &str,
+) -> Result<Option<SharedConnection>> {
+    let backend = DbusBackend::from_connection(conn.clone());
+    let (device, _) = find_share_device(&backend, interface).await?;
+    let Some(mut shared) = find_shared_profile(&backend, interface).await? else {
+        return Ok(None);
+    };
+    shared.active = active_profile(conn, &device).await? == Some(shared.path.clone());
+    Ok(Some(shared))
+}
+
+/// Deactivates the shared profile active on `interface`, if any.
+///
+/// The profile is kept, so sharing again reuses it.
+pub(crate) async fn stop_sharing(conn: &Connection, interface: &str) -> Result<bool> {
+    let backend = DbusBackend::from_connection(conn.clone());
+    let (device, _) = find_share_device(&backend, interface).await?;
+    let Some(profile) = active_profile(conn, &device).await? else {
+        debug!("Nothing active on {interface}");
+        return Ok(false);
+    };
+    if !is_shared(&backend.connection_settings(&profile).await?, interface) {
+        debug!("Active profile on {interface} is not shared, leaving it up");
+        return Ok(false);
+    }
+
+    let dev = NMDeviceProxy::builder(conn).path(device)?.build().await?;
+    backend
+        .deactivate_connection(&dev.active_connection().await?)
+        .await?;
+    info!("Stopped sharing connection over {interface}");
+    Ok(true)
+}
+
+/// Profile active on `device`, or `None`.
+async fn active_profile(
+    conn: &Connection,
+    device: &OwnedObjectPath,
+) -> Result<Option<OwnedObjectPath>> {
+    let dev = NMDeviceProxy::builder(conn)
+        .path(device.clone())?
+        .build()
+        .await?;
+    let active = dev.active_connection().await?;
+    if active.as_str() == "/" {
+        return Ok(None);
+    }
+    let active = NMActiveConnectionProxy::builder(conn)
+        .path(active)?
+        .build()
+        .await?;
+    Ok(Some(active.connection().await?))
+}
+
+/// Resolves `interface` to a device that can share a connection.
+async fn find_share_device<B: NmBackend + ?Sized>(
+    backend: &B,
+    interface: &str,
+) -> Result<(OwnedObjectPath, ShareLink)> {
+    for path in backend.get_devices().await? {
+        if backend.device_interface(&path).await? != interface {
+            continue;
+        }
+        let link = match backend.device_type(&path).await? {
+            device_type::ETHERNET => ShareLink::Ethernet,
+            device_type::WIFI => ShareLink::Hotspot,
+            _ => {
+                return Err(ConnectionError::InvalidInput {
+                    field: "via_interface".to_string(),
+                    reason: format!("'{interface}' is neither an Ethernet nor a Wi-Fi device"),
+                });
+            }
+        };
+        return Ok((path, link));
+    }
+    Err(ConnectionError::InvalidInput {
+        field: "via_interface".to_string(),
+        reason: format!("no device named '{interface}'"),
+    })
+}
+
+/// First saved profile sharing a connection over `interface`.
+async fn find_shared_profile<B: NmBackend + ?Sized>(
+    backend: &B,
+    interface: &str,
+) -> Result<Option<SharedConnection>> {
+    Ok(backend
+        .all_connection_settings()
+        .await?
+        .into_iter()
+        .find(|(_, settings)| is_shared(settings, interface))
+        .map(|(path, settings)| {
+            let values = settings_values(settings);
+            let text = |section: &str, key: &str| match values.get(section)?.get(key)? {
+                Value::Str(s) => Some(s.to_string()),
+                _ => None,
+            };
+            let ssid = match values.get("802-11-wireless").and_then(|w| w.get("ssid")) {
+                Some(Value::Array(bytes)) => {
+                    let raw: Vec<u8> = bytes
+                        .iter()
+                        .filter_map(|b| u8::try_from(b.clone()).ok())
+                        .collect();
+                    Some(decode_ssid_or_empty(&raw).into_owned())
+                }
+                _ => None,
+            };
+            SharedConnection {
+                interface: interface.to_string(),
+                path,
+                id: text("connection", "id").unwrap_or_default(),
+                uuid: text("connection", "uuid").unwrap_or_default(),
+                ssid,
+                psk: None,
+                active: false,
+            }
+        }))
+}
+
+/// Whether `settings` share a connection over `interface`: bound to it,
+/// `ipv4.method=shared`, and Ethernet or a Wi-Fi access point.
+fn is_shared(settings: &ProfileSettings, interface: &str) -> bool {
+    let text = |section: &str, key: &str| {
+        settings
+            .get(section)
+            .and_then(|s| s.get(key))
+            .and_then(|v| String::try_from(v.try_clone().ok()?).ok())
+    };
+    let link_ok = match text("connection", "type").as_deref() {
+        Some("802-3-ethernet") => true,
+        Some("802-11-wireless") => text("802-11-wireless", "mode").as_deref() == Some("ap"),
+        _ => false,
+    };
+    link_ok
+        && text("connection", "interface-name").as_deref() == Some(interface)
+        && text("ipv4", "method").as_deref() == Some("shared")
+}
+
+/// Name of profiles created by [`activate_shared`].
+fn shared_id(interface: &str) -> String {
+    format!("shared-{interface}")
+}
+
+/// Builds the shared profile for `interface`, with the hotspot SSID and
+/// passphrase when sharing over Wi-Fi.
+fn shared_profile(
+    interface: &str,
+    link: ShareLink,
+    options: &ShareOptions,
+) -> (NewProfileSettings, Option<String>, Option<Secret>) {
+    let id = shared_id(interface);
+    match link {
+        ShareLink::Ethernet => {
+            let settings = ConnectionBuilder::new("802-3-ethernet", &id)
+                .autoconnect(false)
+                .interface_name(interface)
+                .with_section("802-3-ethernet", HashMap::new())
+                .ipv4_shared()
+                .ipv6_ignore()
+                .build();
+            (settings, None, None)
+        }
+        ShareLink::Hotspot => {
+            let ssid = options
+                .ssid
+                .clone()
+                .unwrap_or_else(|| format!("Hotspot-{interface}"));
+            let psk = options.psk.clone().unwrap_or_else(generate_psk);
+            let mut settings = WifiConnectionBuilder::new(&ssid)
+                .mode(WifiMode::Ap)
+                .autoconnect(false)
+                .interface_name(interface)
+                .wpa_psk(psk.expose())
+                .ipv4_shared()
+                .ipv6_ignore()
+                .build();
+            if let Some(connection) = settings.get_mut("connection") {
+                connection.insert("id", Value::from(id));
+            }
+            (settings, Some(ssid), Some(psk))
+        }
+    }
+}
+
+/// A random passphrase of [`GENERATED_PSK_LEN`] lowercase hex digits.
+fn generate_psk() -> Secret {
+    let mut psk = uuid::Uuid::new_v4().simple().to_string();
+    psk.truncate(GENERATED_PSK_LEN);
+    Secret::new(psk)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::models::{DeviceState, DeviceType};
+    use crate::backend::MockBackend;
+
+    fn profile_settings(settings: NewProfileSettings) -> ProfileSettings {
+        settings
+            .into_iter()
+            .map(|(section, values)| {
+                let values = values
+                    .into_iter()
+                    .map(|(k, v)| (k.to_string(), v.try_into().unwrap()))
+                    .collect();
+                (section.to_string(), values)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn builds_ethernet_and_hotspot_profiles() {
+        let (eth, ssid, psk) = shared_profile("eth0", ShareLink::Ethernet, &ShareOptions::new());
+        assert!(ssid.is_none() && psk.is_none());
+        assert!(is_shared(&profile_settings(eth), "eth0"));
+
+        let (wifi, ssid, psk) = shared_profile("wlan0", ShareLink::Hotspot, &ShareOptions::new());
+        assert_eq!(ssid.as_deref(), Some("Hotspot-wlan0"));
+        assert_eq!(psk.unwrap().len(), GENERATED_PSK_LEN);
+        assert_eq!(
+            wifi["802-11-wireless"].get("mode"),
+            Some(&Value::from("ap"))
+        );
+        assert_eq!(
+            wifi["connection"].get("id"),
+            Some(&Value::from("shared-wlan0"))
+        );
+        let wifi = profile_settings(wifi);
+        assert!(is_shared(&wifi, "wlan0"));
+        assert!(!is_shared(&wifi, "wlan1"));
+    }
+
+    #[test]
+    fn client_profiles_are_not_shared() {
+        let client = WifiConnectionBuilder::new("Home")
+            .interface_name("wlan0")
+            .ipv4_shared()
+            .build();
+        assert!(!is_shared(&profile_settings(client), "wlan0"));
+
+        let dhcp = ConnectionBuilder::new("802-3-ethernet", "eth0")
+            .interface_name("eth0")
+            .ipv4_auto()
+            .build();
+        assert!(!is_shared(&profile_settings(dhcp), "eth0"));
+    }
+
+    #[tokio::test]
+    async fn shares_over_ethernet_and_reuses_the_profile() {
+        let backend =
+            MockBackend::new().with_device("eth0", DeviceType::Ethernet, DeviceState::Disconnected);
+
+        let created = activate_shared(&backend, "eth0", &ShareOptions::new(), None)
+            .await
+            .unwrap();
+        assert_eq!(created.id, "shared-eth0");
+        assert!(created.active && created.ssid.is_none());
+        assert_eq!(backend.profiles().len(), 1);
+
+        let reused = activate_shared(&backend, "eth0", &ShareOptions::new(), None)
+            .await
+            .unwrap();
+        assert_eq!(reused.path, created.path);
+        assert_eq!(reused.uuid, created.uuid);
+        assert_eq!(backend.profiles().len(), 1);
+        let activations = backend.activations();
+        assert_eq!(activations.len(), 2);
+        assert!(activations[0].added && !activations[1].added);
+    }
+
+    #[tokio::test]
+    async fn hotspot_options_replace_the_profile() {
+        let backend =
+            MockBackend::new().with_device("wlan0", DeviceType::Wifi, DeviceState::Disconnected);
+        activate_shared(&backend, "wlan0", &ShareOptions::new(), None)
+            .await
+            .unwrap();
+
+        let options = ShareOptions::new()
+            .with_ssid("Workshop")
+            .with_psk("correct horse");
+        let shared = activate_shared(&backend, "wlan0", &options, None)
+            .await
+            .unwrap();
+        assert_eq!(shared.ssid.as_deref(), Some("Workshop"));
+        assert_eq!(shared.psk.unwrap().expose(), "correct horse");
+        assert_eq!(backend.profiles().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn rejects_other_devices() {
+        let backend =
+            MockBackend::new().with_device("wwan0", DeviceType::Modem, DeviceState::Disconnected);
+        for interface in ["wwan0", "eth9"] {
+            let result = activate_shared(&backend, interface, &ShareOptions::new(), None).await;
+            assert!(matches!(result, Err(ConnectionError::InvalidInput { .. })));
+        }
+        assert!(backend.activations().is_empty());
+    }
+}
@@ -0,0 +1,440 @@
+//! Whole-state reads through `GetManagedObjects`.
+//!
+//! NetworkManager exports an `org.freedesktop.DBus.ObjectManager` at
+//! `/org/freedesktop` whose `GetManagedObjects` returns every object it
+//! exports with all of their properties. [`snapshot`] builds the device
+//! list, the network list and the active connections from that one reply
+//! rather than one property read per device and access point.
+//!
+//! Saved profiles are not part of the reply: the `known` flag of networks
+//! is filled from the backend, which caches them. BSS Load elements come
+//! from wpa_supplicant and are left out.
+
+use std::collections::HashMap;
+
+use zbus::Connection;
+use zbus::fdo::{ManagedObjects, ObjectManagerProxy};
+use zbus::names::OwnedInterfaceName;
+use zbus::proxy::CacheProperties;
+use zvariant::{OwnedObjectPath, OwnedValue};
+
+use crate::Result;
+use crate::api::models::{
+    AccessPoint, ActiveConnection, Device, DeviceIdentity, DeviceState, MatchOptions, Network,
+    NetworkSnapshot, StateReason, UnavailableReason,
+};
+use crate::backend::NmBackend;
+use crate::core::active_connection::{ACTIVE_INTERFACE, active_connection_from_properties};
+use crate::core::connection_settings::{profile_matches, settings_values};
+use crate::core::daemon_watch::NM_BUS_NAME;
+use crate::core::rfkill::{RfkillSnapshot, read_rfkill};
+use crate::core::scan::{
+    AP_INTERFACE, DeviceContext, access_point_from_properties, group_networks,
+};
+use crate::dbus::dbus_operation;
+use crate::types::constants::device_type;
+use crate::util::utils::property;
+
+/// Path of NetworkManager's object manager.
+const OBJECT_MANAGER_PATH: &str = "/org/freedesktop";
+
+const MANAGER_PATH: &str = "/org/freedesktop/NetworkManager";
+const MANAGER_INTERFACE: &str = "org.freedesktop.NetworkManager";
+const DEVICE_INTERFACE: &str = "org.freedesktop.NetworkManager.Device";
+const WIRED_INTERFACE: &str = "org.freedesktop.NetworkManager.Device.Wired";
+const WIRELESS_INTERFACE: &str = "org.freedesktop.NetworkManager.Device.Wireless";
+const IP4_INTERFACE: &str = "org.freedesktop.NetworkManager.IP4Config";
+const IP6_INTERFACE: &str = "org.freedesktop.NetworkManager.IP6Config";
+
+type Properties = HashMap<String, OwnedValue>;
+
+/// Reads devices, networks and active connections with one D-Bus call.
+///
+/// `profiles` supplies the saved profiles used for `Network::known`.
+pub(crate) async fn snapshot<B: NmBackend + ?Sized>(
+    conn: &Connection,
+    profiles: &B,
+) -> Result<NetworkSnapshot> {
+    let manager = ObjectManagerProxy::builder(conn)
+        .destination(NM_BUS_NAME)?
+        .path(OBJECT_MANAGER_PATH)?
+        .cache_properties(CacheProperties::No)
+        .build()
+        .await?;
+    let objects = manager
+        .get_managed_objects()
+        .await
+        .map_err(|e| dbus_operation("GetManagedObjects call", e.into()))?;
+
+    let mut snapshot = build_snapshot(&objects, read_rfkill());
+
+    let saved: Vec<_> = profiles
+        .all_connection_settings()
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(_, settings)| settings_values(settings))
+        .collect();
+    let opts = MatchOptions::default();
+    for net in &mut snapshot.networks {
+        net.known = !net.ssid.trim().is_empty()
+            && saved.iter().any(|p| profile_matches(p, &net.ssid, &opts));
+    }
+
+    Ok(snapshot)
+}
+
+/// Builds a [`NetworkSnapshot`] from a `GetManagedObjects` reply. Objects
+/// or properties missing from the reply are skipped or read as defaults.
+fn build_snapshot(objects: &ManagedObjects, rfkill: RfkillSnapshot) -> NetworkSnapshot {
+    let manager = interface(objects, MANAGER_PATH, MANAGER_INTERFACE);
+
+    let devices: Vec<Device> = ordered(objects, manager, "Devices", DEVICE_INTERFACE)
+        .into_iter()
+        .filter_map(|path| device(objects, path, manager, rfkill))
+        .collect();
+
+    let mut aps = Vec::new();
+    for dev in devices.iter().filter(|d| d.is_wireless()) {
+        let Some(wifi) = interface(objects, &dev.path, WIRELESS_INTERFACE) else {
+            continue;
+        };
+        let Ok(path) = OwnedObjectPath::try_from(dev.path.as_str()) else {
+            continue;
+        };
+        let context = DeviceContext {
+            path,
+            interface: dev.interface.clone(),
+            state: dev.state.clone(),
+            active_ap: property(wifi, "ActiveAccessPoint").unwrap_or_default(),
+            last_scan_ms: property::<i64>(wifi, "LastScan").filter(|&ms| ms >= 0),
+            bss_loads: HashMap::new(),
+        };
+        let ap_paths: Vec<OwnedObjectPath> = property(wifi, "AccessPoints").unwrap_or_default();
+        aps.extend(ap_paths.into_iter().filter_map(|ap_path| {
+            let props = interface(objects, &ap_path, AP_INTERFACE)?;
+            Some(access_point_from_properties(ap_path, props, &context))
+        }));
+    }
+
+    let mut networks = group_networks(&aps);
+    for net in networks.iter_mut().filter(|n| n.is_active) {
+        fill_addresses(net, &aps, &devices);
+    }
+
+    let active_connections = ordered(objects, manager, "ActiveConnections", ACTIVE_INTERFACE)
+        .into_iter()
+        .filter_map(|path| active_connection(objects, path))
+        .collect();
+
+    NetworkSnapshot {
+        devices,
+        networks,
+        active_connections,
+    }
+}
+
+/// Properties of `iface` on the object at `path`, if it has that interface.
+fn interface<'a>(objects: &'a ManagedObjects, path: &str, iface: &str) -> Option<&'a Properties> {
+    let interfaces = objects.get(&OwnedObjectPath::try_from(path).ok()?)?;
+    let name = OwnedInterfaceName::try_from(iface).ok()?;
+    interfaces.get(&name)
+}
+
+/// Objects with `iface`, in the order of the manager's `key` property, or
+/// by path if the manager object is missing from the reply.
+fn ordered<'a>(
+    objects: &'a ManagedObjects,
+    manager: Option<&Properties>,
+    key: &str,
+    iface: &str,
+) -> Vec<&'a OwnedObjectPath> {
+    if let Some(listed) = manager.and_then(|m| property::<Vec<OwnedObjectPath>>(m, key)) {
+        return listed
+            .iter()
+            .filter_map(|path| objects.get_key_value(path).map(|(path, _)| path))
+            .filter(|path| interface(objects, path, iface).is_some())
+            .collect();
+    }
+    let mut paths: Vec<_> = objects
+        .keys()
+        .filter(|path| interface(objects, path, iface).is_some())
+        .collect();
+    // Shorter first, so `/Devices/10` sorts after `/Devices/9`.
+    paths.sort_by_key(|path| (path.len(), path.as_str()));
+    paths
+}
+
+fn device(
+    objects: &ManagedObjects,
+    path: &OwnedObjectPath,
+    manager: Option<&Properties>,
+    rfkill: RfkillSnapshot,
+) -> Option<Device> {
+    let props = interface(objects, path, DEVICE_INTERFACE)?;
+    let raw_type = property::<u32>(props, "DeviceType").unwrap_or(0);
+    let state: DeviceState = property::<u32>(props, "State").unwrap_or(0).into();
+
+    let current_mac: String =
+        property(props, "HwAddress").unwrap_or_else(|| String::from("00:00:00:00:00:00"));
+    // `PermHwAddress` lives on the type-specific interface.
+    let perm_mac = objects[path]
+        .values()
+        .find_map(|p| property::<String>(p, "PermHwAddress"))
+        .filter(|mac| !mac.is_empty())
+        .unwrap_or_else(|| current_mac.clone());
+
+    let state_reason = property::<(u32, u32)>(props, "StateReason")
+        .map_or(StateReason::Unknown, |(_, reason)| {
+            StateReason::from(reason)
+        });
+
+    let (ip4_address, ip6_address) = property::<OwnedObjectPath>(props, "ActiveConnection")
+        .filter(|ac| ac.as_str() != "/")
+        .and_then(|ac| interface(objects, &ac, ACTIVE_INTERFACE))
+        .map_or((None, None), |active| {
+            (
+                ip_address(objects, active, "Ip4Config", IP4_INTERFACE),
+                ip_address(objects, active, "Ip6Config", IP6_INTERFACE),
+            )
+        });
+
+    let unavailable_reason = (state == DeviceState::Unavailable).then(|| {
+        let radio = |enabled: &str, hw_enabled: &str, hard_block: bool| {
+            let flag = |key| manager.and_then(|m| property(m, key)).unwrap_or(true);
+            Some((flag(enabled), flag(hw_enabled) && !hard_block))
+        };
+        let radio = match raw_type {
+            device_type::WIFI => radio(
+                "WirelessEnabled",
+                "WirelessHardwareEnabled",
+                rfkill.wlan_hard_block,
+            ),
+            device_type::MODEM => {
+                radio("WwanEnabled", "WwanHardwareEnabled", rfkill.wwan_hard_block)
+            }
+            _ => None,
+        };
+        let carrier = if raw_type == device_type::ETHERNET {
+            interface(objects, path, WIRED_INTERFACE).and_then(|w| property(w, "Carrier"))
+        } else {
+            None
+        };
+        UnavailableReason::classify(
+            property(props, "FirmwareMissing").unwrap_or(false),
+            property(props, "NmPluginMissing").unwrap_or(false),
+            radio,
+            carrier,
+        )
+    });
+
+    Some(Device {
+        path: path.to_string(),
+        interface: property(props, "Interface").unwrap_or_default(),
+        identity: DeviceIdentity::new(perm_mac, current_mac),
+        device_type: raw_type.into(),
+        state,
+        state_reason,
+        managed: property(props, "Managed"),
+        driver: property(props, "Driver"),
+        ip4_address,
+        ip6_address,
+        unavailable_reason,
+    })
+}
+
+/// First address of the IP config referenced by `key` of an active
+/// connection, in CIDR notation.
+fn ip_address(
+    objects: &ManagedObjects,
+    active: &Properties,
+    key: &str,
+    iface: &str,
+) -> Option<String> {
+    let config_path: OwnedObjectPath = property(active, key)?;
+    let config = interface(objects, &config_path, iface)?;
+    let addresses: Vec<HashMap<String, OwnedValue>> = property(config, "AddressData")?;
+    let first = addresses.first()?;
+    let address: String = property(first, "address")?;
+    let prefix: u32 = property(first, "prefix")?;
+    Some(format!("{address}/{prefix}"))
+}
+
+/// Sets the interface and IP addresses of an active network from the
+/// device of its active access point.
+fn fill_addresses(net: &mut Network, aps: &[AccessPoint], devices: &[Device]) {
+    let Some(ap) = aps.iter().find(|a| a.ssid == net.ssid && a.is_active) else {
+        return;
+    };
+    if net.device.is_empty() {
+        net.device.clone_from(&ap.interface);
+    }
+    if let Some(dev) = devices.iter().find(|d| d.path == ap.device_path.as_str()) {
+        net.ip4_address.clone_from(&dev.ip4_address);
+        net.ip6_address.clone_from(&dev.ip6_address);
+    }
+}
+
+fn active_connection(objects: &ManagedObjects, path: &OwnedObjectPath) -> Option<ActiveConnection> {
+    let props = interface(objects, path, ACTIVE_INTERFACE)?;
+    Some(active_connection_from_properties(path.clone(), props))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zvariant::Value;
+
+    fn path(p: &str) -> OwnedObjectPath {
+        OwnedObjectPath::try_from(p).unwrap()
+    }
+
+    fn value<'a>(v: impl Into<Value<'a>>) -> OwnedValue {
+        OwnedValue::try_from(v.into()).unwrap()
+    }
+
+    fn object(objects: &mut ManagedObjects, at: &str, iface: &str, props: Vec<(&str, OwnedValue)>) {
+        objects.entry(path(at)).or_default().insert(
+            OwnedInterfaceName::try_from(iface).unwrap(),
+            props.into_iter().map(|(k, v)| (k.to_string(), v)).collect(),
+        );
+    }
+
+    fn managed_objects() -> ManagedObjects {
+        let dev = "/org/freedesktop/NetworkManager/Devices/3";
+        let ap = "/org/freedesktop/NetworkManager/AccessPoint/7";
+        let active = "/org/freedesktop/NetworkManager/ActiveConnection/1";
+        let ip4 = "/org/freedesktop/NetworkManager/IP4Config/2";
+
+        let mut objects = ManagedObjects::new();
+        object(
+            &mut objects,
+            MANAGER_PATH,
+            MANAGER_INTERFACE,
+            vec![
+                ("Devices", value(vec![path(dev)])),
+                ("ActiveConnections", value(vec![path(active)])),
+            ],
+        );
+        object(
+            &mut objects,
+            dev,
+            DEVICE_INTERFACE,
+            vec![
+                ("Interface", value("wlan0")),
+                ("DeviceType", value(device_type::WIFI)),
+                ("State", value(100u32)),
+                ("HwAddress", value("02:00:00:00:00:01")),
+                ("Driver", value("iwlwifi")),
+                ("Managed", value(true)),
+                ("ActiveConnection", value(path(active))),
+            ],
+        );
+        object(
+            &mut objects,
+            dev,
+            WIRELESS_INTERFACE,
+            vec![
+                ("PermHwAddress", value("00:11:22:33:44:55")),
+                ("AccessPoints", value(vec![path(ap)])),
+                ("ActiveAccessPoint", value(path(ap))),
+            ],
+        );
+        object(
+            &mut objects,
+            ap,
+            AP_INTERFACE,
+            vec![
+                ("Ssid", value(b"Home".to_vec())),
+                ("HwAddress", value("AA:BB:CC:DD:EE:FF")),
+                ("Strength", value(70u8)),
+            ],
+        );
+        object(
+            &mut objects,
+            active,
+            ACTIVE_INTERFACE,
+            vec![
+                ("Id", value("Home")),
+                ("Type", value("802-11-wireless")),
+                ("State", value(2u32)),
+                ("Devices", value(vec![path(dev)])),
+                ("Default", value(true)),
+                ("SpecificObject", value(path(ap))),
+                ("Ip4Config", value(path(ip4))),
+                ("Ip6Config", value(path("/"))),
+            ],
+        );
+        let address = HashMap::from([
+            ("address", Value::from("192.168.1.20")),
+            ("prefix", Value::from(24u32)),
+        ]);
+        object(
+            &mut objects,
+            ip4,
+            IP4_INTERFACE,
+            vec![("AddressData", value(vec![address]))],
+        );
+        objects
+    }
+
+    #[test]
+    fn builds_everything_from_one_reply() {
+        let snapshot = build_snapshot(&managed_objects(), RfkillSnapshot::default());
+
+        let [device] = snapshot.devices.as_slice() else {
+            panic!("expected one device: {:?}", snapshot.devices);
+        };
+        assert_eq!(device.interface, "wlan0");
+        assert_eq!(device.state, DeviceState::Activated);
+        assert_eq!(device.identity.permanent_mac, "00:11:22:33:44:55");
+        assert_eq!(device.identity.current_mac, "02:00:00:00:00:01");
+        assert_eq!(device.driver.as_deref(), Some("iwlwifi"));
+        assert_eq!(device.ip4_address.as_deref(), Some("192.168.1.20/24"));
+        assert_eq!(device.ip6_address, None);
+
+        let [network] = snapshot.networks.as_slice() else {
+            panic!("expected one network: {:?}", snapshot.networks);
+        };
+        assert_eq!(network.ssid, "Home");
+        assert!(network.is_active);
+        assert_eq!(network.device, "wlan0");
+        assert_eq!(network.ip4_address.as_deref(), Some("192.168.1.20/24"));
+
+        let [active] = snapshot.active_connections.as_slice() else {
+            panic!("expected one active connection");
+        };
+        assert_eq!(active.id, "Home");
+        assert_eq!(active.state, crate::ActiveConnectionState::Activated);
+        assert!(active.default && !active.default6 && !active.vpn);
+        assert_eq!(
+            active.devices,
+            vec![path("/org/freedesktop/NetworkManager/Devices/3")]
+        );
+        assert_eq!(
+            active.specific_object,
+            Some(path("/org/freedesktop/NetworkManager/AccessPoint/7"))
+        );
+    }
+
+    #[test]
+    fn orders_by_path_without_the_manager_object() {
+        let mut objects = ManagedObjects::new();
+        for n in [10, 9] {
+            object(
+                &mut objects,
+                &format!("/org/freedesktop/NetworkManager/Devices/{n}"),
+                DEVICE_INTERFACE,
+                vec![("Interface", value(format!("eth{n}")))],
+            );
+        }
+        let snapshot = build_snapshot(&objects, RfkillSnapshot::default());
+        let names: Vec<_> = snapshot
+            .devices
+            .iter()
+            .map(|d| d.interface.as_str())
+            .collect();
+        assert_eq!(names, ["eth9", "eth10"]);
+        assert!(snapshot.networks.is_empty() && snapshot.active_connections.is_empty());
+    }
+}
@@ -11,6 +11,7 @@
 //!
 //! - `NMDevice.StateChanged` - Emitted when device state changes
 //! - `NMActiveConnection.StateChanged` - Emitted when connection activation state changes
+//! - `VPN.Connection.VpnStateChanged` - Emitted when a plugin VPN changes state
 //!
 //! This provides a few benefits:
 //! - Immediate response to state changes (no polling delay)
@@ -18,20 +19,22 @@
 //! - More reliable; at least in the sense that we won't miss rapid state transitions.
 //! - Better error messages with specific failure reasons
 
-use futures::{FutureExt, StreamExt, select};
+use futures::{FutureExt, Stream, StreamExt, select};
 use futures_timer::Delay;
 use log::{debug, warn};
+use std::collections::HashMap;
+use std::future::Future;
 use std::pin::pin;
 use std::time::Duration;
 use zbus::Connection;
 
 use crate::Result;
 use crate::api::models::{
-    ActiveConnectionState, ConnectionError, ConnectionStateReason,
-    connection_state_reason_to_error, reason_to_error,
+    ActivationStage, ActiveConnectionState, ConnectionError, ConnectionStateReason, DeviceState,
+    VpnStateReason, connection_state_reason_to_error, reason_to_error, vpn_state_reason_to_error,
 };
-use crate::dbus::{NMActiveConnectionProxy, NMDeviceProxy};
-use crate::types::constants::{device_state, timeouts};
+use crate::dbus::{NMActiveConnectionProxy, NMDeviceProxy, NMVpnConnectionProxy, NMWirelessProxy};
+use crate::types::constants::{device_state, timeouts, vpn_state};
 
 /// Default timeout for connection activation (30 seconds).
 const CONNECTION_TIMEOUT: Duration = Duration::from_secs(30);
@@ -59,6 +62,62 @@ async fn refine_device_disconnected_error(
     ConnectionError::ActivationFailed(ConnectionStateReason::DeviceDisconnected)
 }
 
+/// Requests a scan on `wifi` and waits for it to finish.
+///
+/// Completion is detected from the device's `LastScan` property advancing,
+/// subscribed to before the request so a fast scan is not missed. Waits at
+/// most [`timeouts::scan_complete_timeout`], or [`timeouts::scan_wait`]
+/// when NetworkManager rejects the request (it may still be finishing a
+/// scan of its own). Returns whether a newer scan completed; either way the
+/// caller goes on with whatever results NetworkManager has.
+pub(crate) async fn request_scan_and_wait(
+    wifi: &NMWirelessProxy<'_>,
+    options: HashMap<String, zvariant::Value<'_>>,
+) -> bool {
+    request_scan_and_wait_at_most(wifi, options, timeouts::scan_complete_timeout()).await
+}
+
+/// Like [`request_scan_and_wait`], but waits at most `limit` for an
+/// accepted scan to finish.
+pub(crate) async fn request_scan_and_wait_at_most(
+    wifi: &NMWirelessProxy<'_>,
+    options: HashMap<String, zvariant::Value<'_>>,
+    limit: Duration,
+) -> bool {
+    let before = wifi.last_scan().await.unwrap_or(-1);
+    let mut changes = wifi.receive_last_scan_changed().await;
+
+    let limit = match wifi.request_scan(options).await {
+        Ok(()) => {
+            debug!("Scan requested successfully");
+            limit
+        }
+        Err(e) => {
+            warn!("Scan request failed: {e}");
+            timeouts::scan_wait()
+        }
+    };
+    let mut deadline = pin!(Delay::new(limit).fuse());
+
+    loop {
+        select! {
+            _ = deadline => {
+                debug!("No scan completed within {limit:?}");
+                return false;
+            }
+            change = changes.next().fuse() => match change {
+                Some(change) => {
+                    if change.get().await.is_ok_and(|last| last > before) {
+                        debug!("Scan completed");
+                        return true;
+                    }
+                }
+                None => return false,
+            },
+        }
+    }
+}
+
 /// Default timeout for device disconnection (10 seconds).
 const DISCONNECT_TIMEOUT: Duration = Duration::from_secs(10);
 
@@ -168,6 +227,143 @@ pub(crate) async fn wait_for_connection_activation(
     }
 }
 
+/// Waits for a VPN activation to finish, decoding plugin failures.
+///
+/// For plugin VPNs this follows `VPN.Connection.VpnStateChanged`, whose
+/// reason tells a rejected login ([`ConnectionError::VpnAuthFailed`]) apart
+/// from a missing or crashed plugin ([`ConnectionError::VpnServiceFailed`]);
+/// the generic `StateChanged` signal only reports these as unknown. Native
+/// WireGuard connections have no VPN interface and are handed to
+/// [`wait_for_connection_activation`].
+pub(crate) async fn wait_for_vpn_activation(
+    conn: &Connection,
+    active_conn_path: &zvariant::OwnedObjectPath,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    let active_conn = NMActiveConnectionProxy::builder(conn)
+        .path(active_conn_path.clone())?
+        .build()
+        .await?;
+    if !active_conn.vpn().await.unwrap_or(false) {
+        return wait_for_connection_activation(conn, active_conn_path, timeout).await;
+    }
+
+    let vpn = NMVpnConnectionProxy::builder(conn)
+        .path(active_conn_path.clone())?
+        .build()
+        .await?;
+
+    // Subscribe to signals FIRST to avoid race condition
+    let mut stream = vpn.receive_vpn_connection_state_changed().await?;
+    debug!("Subscribed to VPN.Connection VpnStateChanged signal");
+
+    match vpn.vpn_state().await? {
+        vpn_state::ACTIVATED => {
+            debug!("VPN already activated");
+            return Ok(());
+        }
+        vpn_state::FAILED | vpn_state::DISCONNECTED => {
+            warn!("VPN already failed");
+            return Err(ConnectionError::VpnFailed(
+                "VPN connection ended before activation".into(),
+            ));
+        }
+        _ => {}
+    }
+
+    let timeout_duration = timeout.unwrap_or(CONNECTION_TIMEOUT);
+    let mut timeout_delay = pin!(Delay::new(timeout_duration).fuse());
+
+    loop {
+        select! {
+            _ = timeout_delay => {
+                warn!("VPN activation timed out after {:?}", timeout_duration);
+                return Err(ConnectionError::Timeout);
+            }
+            signal_opt = stream.next() => {
+                let Some(signal) = signal_opt else {
+                    return Err(ConnectionError::Stuck("signal stream ended".into()));
+                };
+                let args = match signal.args() {
+                    Ok(args) => args,
+                    Err(e) => {
+                        warn!("Failed to parse VpnStateChanged signal args: {e}");
+                        continue;
+                    }
+                };
+                let reason = VpnStateReason::from(args.reason);
+                debug!("VPN state changed to {} (reason: {reason})", args.state);
+
+                match args.state {
+                    vpn_state::ACTIVATED => {
+                        debug!("VPN activation successful");
+                        return Ok(());
+                    }
+                    vpn_state::FAILED | vpn_state::DISCONNECTED => {
+                        debug!("VPN activation failed: {reason}");
+                        return Err(vpn_state_reason_to_error(args.reason));
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+/// Runs `work` while forwarding the device's activation stages to `on_stage`.
+///
+/// Subscribes to `NMDevice.StateChanged` on `device_path` before `work` is
+/// polled so early transitions are not missed. States outside activation
+/// are ignored and consecutive duplicates are collapsed. Returns the result
+/// of `work` once it completes.
+pub(crate) async fn with_stage_updates<T, W>(
+    conn: &Connection,
+    device_path: &zvariant::OwnedObjectPath,
+    on_stage: &(dyn Fn(ActivationStage) + Send + Sync),
+    work: W,
+) -> Result<T>
+where
+    W: Future<Output = Result<T>>,
+{
+    let dev = NMDeviceProxy::builder(conn)
+        .path(device_path.clone())?
+        .build()
+        .await?;
+
+    let mut stream = dev.receive_device_state_changed().await?;
+    debug!("Subscribed to device StateChanged signal for activation stages");
+
+    let mut work = pin!(work.fuse());
+    let mut last_stage = None;
+
+    loop {
+        select! {
+            result = work => return result,
+            signal_opt = stream.next() => {
+                let Some(signal) = signal_opt else {
+                    debug!("Device signal stream ended, no further stage updates");
+                    return work.await;
+                };
+                match signal.args() {
+                    Ok(args) => {
+                        let state = DeviceState::from(args.new_state);
+                        if let Some(stage) = ActivationStage::from_device_state(state)
+                            && last_stage != Some(stage)
+                        {
+                            debug!("Activation stage: {stage}");
+                            last_stage = Some(stage);
+                            on_stage(stage);
+                        }
+                    }
+                    Err(e) => {
+                        warn!("Failed to parse StateChanged signal args: {e}");
+                    }
+                }
+            }
+        }
+    }
+}
+
 /// Waits for a device to reach the disconnected state using D-Bus signals.
 ///
 /// # Arguments
@@ -244,6 +440,56 @@ pub(crate) async fn wait_for_device_disconnect(
     }
 }
 
+/// Waits for an active connection to go down after `DeactivateConnection`.
+///
+/// `changes` must be subscribed before deactivating so the transition is
+/// not missed. If no `Deactivated` state arrives within `timeout`, the
+/// state is read once more; NetworkManager drops the object once it is
+/// deactivated, so a failed read also counts as done.
+pub(crate) async fn wait_for_connection_deactivation<S>(
+    ac: &NMActiveConnectionProxy<'_>,
+    changes: S,
+    timeout: Duration,
+) -> Result<()>
+where
+    S: Stream<Item = ActiveConnectionState>,
+{
+    if reached_deactivated(changes, timeout).await {
+        debug!("Active connection deactivated");
+        return Ok(());
+    }
+    match ac.state().await.map(ActiveConnectionState::from) {
+        Ok(ActiveConnectionState::Deactivated) | Err(_) => Ok(()),
+        Ok(state) => {
+            warn!("Deactivation timed out, connection still {state:?}");
+            Err(ConnectionError::Stuck(format!(
+                "active connection {state:?}"
+            )))
+        }
+    }
+}
+
+/// Whether `states` yields [`ActiveConnectionState::Deactivated`] before
+/// `timeout` passes or the stream ends.
+async fn reached_deactivated<S>(states: S, timeout: Duration) -> bool
+where
+    S: Stream<Item = ActiveConnectionState>,
+{
+    let mut states = pin!(states.fuse());
+    let mut timeout_delay = pin!(Delay::new(timeout).fuse());
+
+    loop {
+        select! {
+            _ = timeout_delay => return false,
+            state = states.next() => match state {
+                Some(ActiveConnectionState::Deactivated) => return true,
+                Some(state) => debug!("Active connection state during deactivation: {state:?}"),
+                None => return false,
+            },
+        }
+    }
+}
+
 /// Waits for a Wi-Fi device to be ready (Disconnected or Activated state).
 pub(crate) async fn wait_for_wifi_device_ready(dev: &NMDeviceProxy<'_>) -> Result<()> {
     // Subscribe to signals FIRST to avoid race condition
@@ -309,3 +555,31 @@ pub(crate) async fn wait_for_wifi_device_ready(dev: &NMDeviceProxy<'_>) -> Resul
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::stream;
+
+    #[tokio::test]
+    async fn deactivation_seen_after_intermediate_states() {
+        let states = stream::iter([
+            ActiveConnectionState::Deactivating,
+            ActiveConnectionState::Deactivated,
+        ])
+        .chain(stream::pending());
+        assert!(reached_deactivated(states, Duration::from_secs(5)).await);
+    }
+
+    #[tokio::test]
+    async fn deactivation_times_out() {
+        let states = stream::iter([ActiveConnectionState::Deactivating]).chain(stream::pending());
+        assert!(!reached_deactivated(states, Duration::from_millis(20)).await);
+    }
+
+    #[tokio::test]
+    async fn deactivation_not_assumed_when_stream_ends() {
+        let states = stream::iter([ActiveConnectionState::Activated]);
+        assert!(!reached_deactivated(states, Duration::from_secs(5)).await);
+    }
+}
@@ -8,22 +8,30 @@
 
 use log::{debug, info, warn};
 use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use zbus::Connection;
-use zvariant::OwnedObjectPath;
+use zvariant::{OwnedObjectPath, OwnedValue};
 
 use crate::Result;
 use crate::api::models::{
-    ConnectionError, ConnectionOptions, DeviceState, OpenVpnConnectionType, TimeoutConfig,
-    VpnConfig, VpnConnection, VpnConnectionInfo, VpnCredentials, VpnDetails, VpnKind,
-    VpnSecretFlags, VpnType,
+    ConnectionError, ConnectionOptions, DeviceState, MatchOptions, OpenVpnConnectionType,
+    TimeoutConfig, VpnConfig, VpnConnection, VpnConnectionInfo, VpnCredentials, VpnDetails,
+    VpnKind, VpnSecretFlags, VpnType, WireGuardPeer,
 };
-use crate::builders::{build_openvpn_connection, build_wireguard_connection};
-use crate::core::state_wait::wait_for_connection_activation;
-use crate::dbus::{NMActiveConnectionProxy, NMProxy};
+use crate::backend::{DbusBackend, NmBackend};
+use crate::builders::wireguard_builder::encode_peers;
+use crate::builders::{
+    build_ikev2_connection, build_openvpn_connection, build_wireguard_connection,
+};
+use crate::core::active_connection::list_active_connections;
+use crate::core::connection_settings::{find_saved_connection, settings_values};
+use crate::core::saved_connection::{edit_saved_connection, resolve_saved_profile};
+use crate::dbus::{NMActiveConnectionProxy, NMDeviceProxy, NMProxy, dbus_operation};
 use crate::models::VpnConfiguration;
-use crate::util::utils::{extract_connection_state_reason, nm_proxy, settings_proxy};
+use crate::util::utils::{extract_connection_state_reason, nm_proxy};
 use crate::util::validation::{
-    validate_connection_name, validate_openvpn_config, validate_vpn_credentials,
+    validate_connection_name, validate_ikev2_config, validate_openvpn_config,
+    validate_vpn_credentials, validate_wireguard_peers,
 };
 
 /// Detects whether a saved connection is a VPN and what kind.
@@ -339,6 +347,17 @@ fn extract_id(settings: &HashMap<String, HashMap<String, zvariant::Value<'_>>>)
     })
 }
 
+/// Extracts `connection.timestamp` (seconds since the epoch, 0 = never).
+fn extract_timestamp(
+    settings: &HashMap<String, HashMap<String, zvariant::Value<'_>>>,
+) -> Option<SystemTime> {
+    match settings.get("connection")?.get("timestamp")? {
+        zvariant::Value::U64(0) => None,
+        zvariant::Value::U64(secs) => Some(UNIX_EPOCH + Duration::from_secs(*secs)),
+        _ => None,
+    }
+}
+
 /// Extracts `vpn.service-type` or returns empty string for WireGuard.
 fn extract_service_type(
     kind: VpnKind,
@@ -384,52 +403,25 @@ fn extract_password_flags(
 
 /// Lists all saved VPN connections with rich metadata.
 pub(crate) async fn list_vpn_connections(conn: &Connection) -> Result<Vec<VpnConnection>> {
-    let nm = NMProxy::new(conn).await?;
-
-    let settings_proxy = nm_proxy(
-        conn,
-        "/org/freedesktop/NetworkManager/Settings",
-        "org.freedesktop.NetworkManager.Settings",
-    )
-    .await?;
-
-    let list_reply = settings_proxy
-        .call_method("ListConnections", &())
-        .await
-        .map_err(|e| ConnectionError::DbusOperation {
-            context: "failed to list saved connections".to_string(),
-            source: e,
-        })?;
+    list_vpn_connections_with(conn, &DbusBackend::from_connection(conn.clone())).await
+}
 
-    let saved_paths: Vec<OwnedObjectPath> = list_reply.body().deserialize()?;
+/// [`list_vpn_connections`] reading saved profiles through `profiles`.
+pub(crate) async fn list_vpn_connections_with<B: NmBackend + ?Sized>(
+    conn: &Connection,
+    profiles: &B,
+) -> Result<Vec<VpnConnection>> {
+    let nm = NMProxy::new(conn).await?;
 
-    let active_map = build_active_vpn_map(conn, &nm).await;
+    let (saved, active_map) = futures::join!(
+        profiles.all_connection_settings(),
+        build_active_vpn_map(conn, &nm)
+    );
 
     let mut vpn_conns = Vec::new();
 
-    for cpath in saved_paths {
-        let cproxy = match nm_proxy(
-            conn,
-            cpath.clone(),
-            "org.freedesktop.NetworkManager.Settings.Connection",
-        )
-        .await
-        {
-            Ok(p) => p,
-            Err(_) => continue,
-        };
-
-        let msg = match cproxy.call_method("GetSettings", &()).await {
-            Ok(m) => m,
-            Err(_) => continue,
-        };
-
-        let body = msg.body();
-        let settings_map: HashMap<String, HashMap<String, zvariant::Value>> =
-            match body.deserialize() {
-                Ok(m) => m,
-                Err(_) => continue,
-            };
+    for (_, owned) in saved? {
+        let settings_map = settings_values(owned);
 
         let Some(kind) = detect_vpn_kind(&settings_map) else {
             continue;
@@ -450,6 +442,7 @@ pub(crate) async fn list_vpn_connections(conn: &Connection) -> Result<Vec<VpnCon
                 .get(&uuid)
                 .cloned()
                 .unwrap_or((DeviceState::Other(0), None, false));
+        let activated_at = active.then(|| extract_timestamp(&settings_map)).flatten();
 
         vpn_conns.push(VpnConnection {
             uuid,
@@ -463,6 +456,7 @@ pub(crate) async fn list_vpn_connections(conn: &Connection) -> Result<Vec<VpnCon
             password_flags,
             service_type,
             kind,
+            activated_at,
         });
     }
 
@@ -475,68 +469,73 @@ pub(crate) async fn active_vpn_connections(conn: &Connection) -> Result<Vec<VpnC
     Ok(all.into_iter().filter(|v| v.active).collect())
 }
 
+/// The most recently activated VPN, if any is active.
+pub(crate) async fn current_vpn(conn: &Connection) -> Result<Option<VpnConnection>> {
+    let active = active_vpn_connections(conn).await?;
+    Ok(active.into_iter().max_by_key(|v| v.activated_at))
+}
+
 /// Builds uuid → (state, interface, active) map from NM active connections.
 async fn build_active_vpn_map(
     conn: &Connection,
     nm: &NMProxy<'_>,
 ) -> HashMap<String, (DeviceState, Option<String>, bool)> {
-    let mut map = HashMap::new();
-
     let active_conns = match nm.active_connections().await {
         Ok(c) => c,
-        Err(_) => return map,
+        Err(_) => return HashMap::new(),
     };
 
-    for ac_path in active_conns {
-        let ac_proxy = match nm_proxy(
-            conn,
-            ac_path.clone(),
-            "org.freedesktop.NetworkManager.Connection.Active",
-        )
-        .await
-        {
-            Ok(p) => p,
-            Err(_) => continue,
-        };
-
-        let uuid: String = match ac_proxy.get_property("Uuid").await {
-            Ok(u) => u,
-            Err(_) => continue,
-        };
-
-        let conn_type: String = match ac_proxy.get_property("Type").await {
-            Ok(t) => t,
-            Err(_) => continue,
-        };
+    futures::future::join_all(
+        active_conns
+            .into_iter()
+            .map(|ac_path| active_vpn_entry(conn, ac_path)),
+    )
+    .await
+    .into_iter()
+    .flatten()
+    .collect()
+}
 
-        if conn_type != "vpn" && conn_type != "wireguard" {
-            continue;
-        }
+/// Entry of [`build_active_vpn_map`] for `ac_path`, or `None` if it is
+/// not a VPN.
+async fn active_vpn_entry(
+    conn: &Connection,
+    ac_path: OwnedObjectPath,
+) -> Option<(String, (DeviceState, Option<String>, bool))> {
+    let ac_proxy = nm_proxy(
+        conn,
+        ac_path,
+        "org.freedesktop.NetworkManager.Connection.Active",
+    )
+    .await
+    .ok()?;
 
-        let state = ac_proxy
-            .get_property::<u32>("State")
-            .await
-            .map(DeviceState::from)
-            .unwrap_or(DeviceState::Other(0));
+    let uuid: String = ac_proxy.get_property("Uuid").await.ok()?;
+    let conn_type: String = ac_proxy.get_property("Type").await.ok()?;
+    if conn_type != "vpn" && conn_type != "wireguard" {
+        return None;
+    }
 
-        let interface = ac_proxy
-            .get_property::<Vec<OwnedObjectPath>>("Devices")
-            .await
-            .ok()
-            .and_then(|devs| devs.first().cloned())
-            .and_then(|dev_path| {
-                futures::executor::block_on(async {
-                    let dp = nm_proxy(conn, dev_path, "org.freedesktop.NetworkManager.Device")
-                        .await
-                        .ok()?;
-                    dp.get_property::<String>("Interface").await.ok()
-                })
-            });
+    let state = ac_proxy
+        .get_property::<u32>("State")
+        .await
+        .map(DeviceState::from)
+        .unwrap_or(DeviceState::Other(0));
 
-        map.insert(uuid, (state, interface, true));
+    let interface = async {
+        let devices: Vec<OwnedObjectPath> = ac_proxy.get_property("Devices").await.ok()?;
+        let dp = nm_proxy(
+            conn,
+            devices.into_iter().next()?,
+            "org.freedesktop.NetworkManager.Device",
+        )
+        .await
+        .ok()?;
+        dp.get_property::<String>("Interface").await.ok()
     }
+    .await;
 
-    map
+    Some((uuid, (state, interface, true)))
 }
 
 /// Activate a saved VPN by UUID.
@@ -545,32 +544,39 @@ pub(crate) async fn connect_vpn_by_uuid(
     uuid: &str,
     timeout_config: Option<TimeoutConfig>,
 ) -> Result<()> {
-    let nm = NMProxy::new(conn).await?;
+    activate_vpn_by_uuid(conn, uuid, timeout_config.map(|c| c.connection_timeout)).await?;
+    Ok(())
+}
 
-    let settings_proxy = nm_proxy(
-        conn,
-        "/org/freedesktop/NetworkManager/Settings",
-        "org.freedesktop.NetworkManager.Settings",
-    )
-    .await?;
+/// Activates a saved VPN by UUID and returns its active connection path.
+pub(crate) async fn activate_vpn_by_uuid(
+    conn: &Connection,
+    uuid: &str,
+    timeout: Option<Duration>,
+) -> Result<OwnedObjectPath> {
+    activate_saved_vpn(&DbusBackend::from_connection(conn.clone()), uuid, timeout).await
+}
 
-    let reply = settings_proxy
-        .call_method("GetConnectionByUuid", &(uuid,))
+/// [`activate_vpn_by_uuid`] against any [`NmBackend`].
+pub(crate) async fn activate_saved_vpn<B: NmBackend + ?Sized>(
+    backend: &B,
+    uuid: &str,
+    timeout: Option<Duration>,
+) -> Result<OwnedObjectPath> {
+    let conn_path = backend
+        .connection_by_uuid(uuid)
         .await
         .map_err(|_| ConnectionError::VpnNotFound(uuid.to_string()))?;
 
-    let conn_path: OwnedObjectPath = reply.body().deserialize()?;
-
-    let active_conn = nm
-        .activate_connection(
-            conn_path,
-            OwnedObjectPath::default(),
-            OwnedObjectPath::default(),
-        )
+    let none = OwnedObjectPath::default();
+    let active_conn = backend
+        .activate_connection(&conn_path, &none, &none)
         .await?;
 
-    let timeout = timeout_config.map(|c| c.connection_timeout);
-    wait_for_connection_activation(conn, &active_conn, timeout).await
+    backend
+        .wait_for_vpn_activation(&active_conn, timeout)
+        .await?;
+    Ok(active_conn)
 }
 
 /// Activate a saved VPN by connection id (display name).
@@ -620,61 +626,128 @@ pub(crate) async fn disconnect_vpn_by_uuid(conn: &Connection, uuid: &str) -> Res
     Ok(())
 }
 
-/// Connects to a VPN (WireGuard or OpenVPN) from configuration.
+/// Resolves the active connection on `interface` that a VPN should run over.
+///
+/// NetworkManager ignores the device argument when activating a VPN and
+/// takes the base connection from the specific object instead. WireGuard
+/// profiles are devices of their own and cannot be scoped this way.
+async fn base_connection_on<B: NmBackend + ?Sized>(
+    backend: &B,
+    config: &VpnConfiguration,
+    interface: &str,
+) -> Result<OwnedObjectPath> {
+    if matches!(config, VpnConfiguration::WireGuard(_)) {
+        return Err(ConnectionError::InvalidInput {
+            field: "interface".into(),
+            reason: "WireGuard tunnels cannot be bound to a base interface".into(),
+        });
+    }
+
+    let mut dev_path = None;
+    for path in backend.get_devices().await? {
+        if backend.device_interface(&path).await.ok().as_deref() == Some(interface) {
+            dev_path = Some(path);
+            break;
+        }
+    }
+    let dev_path = dev_path.ok_or_else(|| ConnectionError::InvalidInput {
+        field: "interface".into(),
+        reason: format!("no device named '{interface}'"),
+    })?;
+    let active = backend.device_active_connection(&dev_path).await?;
+    if active.as_str() == "/" {
+        return Err(ConnectionError::InvalidInput {
+            field: "interface".into(),
+            reason: format!("'{interface}' has no active connection to carry the VPN"),
+        });
+    }
+
+    debug!("Binding VPN to {} via {}", interface, active.as_str());
+    Ok(active)
+}
+
+/// Connects to a VPN (WireGuard, OpenVPN or IKEv2) from configuration.
+///
+/// With `interface`, plugin VPNs are activated on top of the connection
+/// currently active on that device instead of the default route's.
+/// A new profile is created with `options`; a saved one is activated as is.
 pub(crate) async fn connect_vpn(
     conn: &Connection,
     config: VpnConfiguration,
+    interface: Option<&str>,
     timeout_config: Option<TimeoutConfig>,
+    options: &ConnectionOptions,
+) -> Result<()> {
+    let backend = DbusBackend::from_connection(conn.clone());
+    connect_vpn_with(&backend, config, interface, timeout_config, options).await
+}
+
+/// [`connect_vpn`] against any [`NmBackend`].
+pub(crate) async fn connect_vpn_with<B: NmBackend + ?Sized>(
+    backend: &B,
+    config: VpnConfiguration,
+    interface: Option<&str>,
+    timeout_config: Option<TimeoutConfig>,
+    options: &ConnectionOptions,
 ) -> Result<()> {
     let name = config.name().to_string();
     debug!("Connecting to VPN: {}", name);
 
-    let nm = NMProxy::new(conn).await?;
-
-    let saved = crate::core::connection_settings::get_saved_connection_path(conn, &name).await?;
+    let saved =
+        find_saved_connection(backend, &name, &MatchOptions::new().with_ssid_match(false)).await?;
 
     let vpn_device_path = OwnedObjectPath::default();
-    let specific_object = OwnedObjectPath::default();
+    let specific_object = match interface {
+        Some(iface) => base_connection_on(backend, &config, iface).await?,
+        None => OwnedObjectPath::default(),
+    };
 
     let active_conn = if let Some(saved_path) = saved {
         debug!("Activating existent VPN connection");
-        nm.activate_connection(saved_path, vpn_device_path.clone(), specific_object.clone())
+        backend
+            .activate_connection(&saved_path, &vpn_device_path, &specific_object)
             .await?
     } else {
         debug!("Creating new VPN connection");
-        let opts = ConnectionOptions {
-            autoconnect: false,
-            autoconnect_priority: None,
-            autoconnect_retries: None,
-        };
-
         let settings = match config {
             VpnConfiguration::WireGuard(ref wg) => {
                 let creds: VpnCredentials = wg.clone().into();
                 validate_vpn_credentials(&creds)?;
-                build_wireguard_connection(&creds, &opts)?
+                build_wireguard_connection(&creds, options)?
             }
             VpnConfiguration::OpenVpn(ref ovpn) => {
                 validate_openvpn_config(ovpn)?;
-                build_openvpn_connection(ovpn, &opts)?
+                build_openvpn_connection(ovpn, options)?
+            }
+            VpnConfiguration::Ikev2(ref ikev2) => {
+                validate_ikev2_config(ikev2)?;
+                build_ikev2_connection(ikev2, options)?
             }
         };
 
-        let settings_api = settings_proxy(conn).await?;
-
         debug!("Adding connection via Settings API");
-        let add_reply = settings_api
-            .call_method("AddConnection", &(settings,))
-            .await?;
-        let conn_path: OwnedObjectPath = add_reply.body().deserialize()?;
+        let conn_path = backend.add_connection(settings).await?;
         debug!("Connection added, activating VPN connection");
 
-        nm.activate_connection(conn_path, vpn_device_path, specific_object)
+        backend
+            .activate_connection(&conn_path, &vpn_device_path, &specific_object)
             .await?
     };
 
     let timeout = timeout_config.map(|c| c.connection_timeout);
-    wait_for_connection_activation(conn, &active_conn, timeout).await?;
+    backend
+        .wait_for_vpn_activation(&active_conn, timeout)
+        .await?;
+    info!("Successfully connected to VPN: {}", name);
+    Ok(())
+}
+
+/// Re-reads the state of `active_conn` once its VPN wait has returned, and
+/// fails if it deactivated right after activating.
+pub(crate) async fn confirm_vpn_activated(
+    conn: &Connection,
+    active_conn: &OwnedObjectPath,
+) -> Result<()> {
     debug!("Connection reached Activated state, waiting briefly...");
 
     match NMActiveConnectionProxy::builder(conn).path(active_conn.clone()) {
@@ -685,13 +758,10 @@ pub(crate) async fn connect_vpn(
                 debug!("Connection state after delay: {:?}", state);
 
                 match state {
-                    crate::api::models::ActiveConnectionState::Activated => {
-                        info!("Successfully connected to VPN: {}", name);
-                        Ok(())
-                    }
+                    crate::api::models::ActiveConnectionState::Activated => Ok(()),
                     crate::api::models::ActiveConnectionState::Deactivated => {
                         warn!("Connection deactivated immediately after activation");
-                        let reason = extract_connection_state_reason(conn, &active_conn).await;
+                        let reason = extract_connection_state_reason(conn, active_conn).await;
                         Err(crate::api::models::ConnectionError::ActivationFailed(
                             reason,
                         ))
@@ -707,7 +777,7 @@ pub(crate) async fn connect_vpn(
             }
             Err(e) => {
                 warn!("Failed to build active connection proxy after delay: {}", e);
-                let reason = extract_connection_state_reason(conn, &active_conn).await;
+                let reason = extract_connection_state_reason(conn, active_conn).await;
                 Err(crate::api::models::ConnectionError::ActivationFailed(
                     reason,
                 ))
@@ -718,7 +788,7 @@ pub(crate) async fn connect_vpn(
                 "Failed to create active connection proxy builder after delay: {}",
                 e
             );
-            let reason = extract_connection_state_reason(conn, &active_conn).await;
+            let reason = extract_connection_state_reason(conn, active_conn).await;
             Err(crate::api::models::ConnectionError::ActivationFailed(
                 reason,
             ))
@@ -844,10 +914,7 @@ pub(crate) async fn forget_vpn(conn: &Connection, name: &str) -> Result<()> {
         if id_ok && vpn_kind.is_some() {
             debug!("Found VPN connection, deleting: {name}");
             cproxy.call_method("Delete", &()).await.map_err(|e| {
-                ConnectionError::DbusOperation {
-                    context: format!("failed to delete VPN connection '{}'", name),
-                    source: e,
-                }
+                dbus_operation(format!("failed to delete VPN connection '{}'", name), e)
             })?;
             info!("Successfully deleted VPN connection: {name}");
 
@@ -864,6 +931,51 @@ pub(crate) async fn forget_vpn(conn: &Connection, name: &str) -> Result<()> {
     Ok(())
 }
 
+/// Replaces the peers of the WireGuard profile `name` (name or UUID).
+///
+/// If the profile is active, the change is pushed to its device with
+/// `Reapply`, so edited allowed IPs take effect without a reconnect.
+pub(crate) async fn update_wireguard_peers(
+    conn: &Connection,
+    name: &str,
+    peers: Vec<WireGuardPeer>,
+) -> Result<()> {
+    validate_wireguard_peers(&peers)?;
+
+    let profile = resolve_saved_profile(conn, name).await?;
+    if profile.connection_type != "wireguard" {
+        return Err(ConnectionError::InvalidInput {
+            field: "name".into(),
+            reason: format!("'{}' is not a WireGuard connection", profile.id),
+        });
+    }
+
+    let count = peers.len();
+    let value = OwnedValue::try_from(encode_peers(peers)).map_err(zbus::Error::from)?;
+    edit_saved_connection(conn, &profile.uuid, |mut settings| {
+        settings
+            .entry("wireguard".to_string())
+            .or_default()
+            .insert("peers".to_string(), value);
+        Ok(settings)
+    })
+    .await?;
+    info!("Updated '{}' to {count} WireGuard peer(s)", profile.id);
+
+    let active = list_active_connections(conn).await?;
+    if let Some(active) = active.into_iter().find(|a| a.uuid == profile.uuid) {
+        for dev_path in active.devices {
+            let dev = NMDeviceProxy::builder(conn).path(dev_path)?.build().await?;
+            dev.reapply(HashMap::new(), 0, 0)
+                .await
+                .map_err(|e| dbus_operation(format!("Reapply of '{}' failed", profile.id), e))?;
+        }
+        debug!("Reapplied '{}' to its active connection", profile.id);
+    }
+
+    Ok(())
+}
+
 /// Gets detailed information about an active VPN connection.
 pub(crate) async fn get_vpn_info(conn: &Connection, name: &str) -> Result<VpnConnectionInfo> {
     validate_connection_name(name)?;
@@ -1145,6 +1257,9 @@ fn extract_wireguard_details(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::api::models::{DeviceType, Ikev2Auth, Ikev2Config, WireGuardConfig};
+    use crate::backend::MockBackend;
+    use crate::builders::build_ethernet_connection;
     use std::collections::HashMap;
 
     fn openvpn_settings_with_data(
@@ -1199,6 +1314,19 @@ mod tests {
         assert_eq!(detect_vpn_kind(&settings), None);
     }
 
+    #[test]
+    fn timestamp_zero_means_never_activated() {
+        let at = |secs: u64| {
+            let conn_sec = HashMap::from([("timestamp".to_string(), zvariant::Value::U64(secs))]);
+            extract_timestamp(&HashMap::from([("connection".to_string(), conn_sec)]))
+        };
+        assert_eq!(at(0), None);
+        assert_eq!(
+            at(1_700_000_000),
+            Some(UNIX_EPOCH + Duration::from_secs(1_700_000_000))
+        );
+    }
+
     #[test]
     fn decode_openvpn_full() {
         let data = HashMap::from([
@@ -1426,4 +1554,119 @@ mod tests {
             _ => panic!("expected WireGuard variant"),
         }
     }
+
+    fn wireguard(name: &str) -> VpnConfiguration {
+        let key = "HIgo9xNzJMWLKAShlKl6/bUT1VI9Q0SDBXGtLXkPFXc=";
+        let peer = WireGuardPeer::new(key, "vpn.example.com:51820", vec!["0.0.0.0/0".into()]);
+        VpnConfiguration::WireGuard(WireGuardConfig::new(
+            name,
+            "vpn.example.com:51820",
+            key,
+            "10.0.0.2/24",
+            vec![peer],
+        ))
+    }
+
+    #[tokio::test]
+    async fn connect_vpn_saves_then_reuses_the_profile() {
+        let backend = MockBackend::new();
+        let options = ConnectionOptions::new(false);
+
+        connect_vpn_with(&backend, wireguard("Corp"), None, None, &options)
+            .await
+            .unwrap();
+        let profiles = backend.profiles();
+        assert_eq!(profiles.len(), 1);
+
+        connect_vpn_with(&backend, wireguard("Corp"), None, None, &options)
+            .await
+            .unwrap();
+        let activations = backend.activations();
+        assert_eq!(activations.len(), 2);
+        assert!(activations.iter().all(|a| a.connection == profiles[0].0));
+        assert!(activations.iter().all(|a| a.device.as_str() == "/"));
+        assert_eq!(backend.profiles().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn connect_vpn_reports_activation_failure() {
+        let backend = MockBackend::new()
+            .with_activation_failure(ConnectionError::VpnFailed("gateway unreachable".into()));
+        let err = connect_vpn_with(
+            &backend,
+            wireguard("Corp"),
+            None,
+            None,
+            &ConnectionOptions::new(false),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, ConnectionError::VpnFailed(_)));
+    }
+
+    #[tokio::test]
+    async fn interface_scoped_vpn_needs_an_active_base_connection() {
+        let backend =
+            MockBackend::new().with_device("eth0", DeviceType::Ethernet, DeviceState::Disconnected);
+        let ikev2 = VpnConfiguration::from(Ikev2Config::new(
+            "Corp",
+            "vpn.example.com",
+            Ikev2Auth::Psk {
+                psk: "secret".into(),
+            },
+        ));
+
+        assert!(matches!(
+            base_connection_on(&backend, &ikev2, "eth0").await,
+            Err(ConnectionError::InvalidInput { reason, .. }) if reason.contains("no active connection")
+        ));
+        assert!(matches!(
+            base_connection_on(&backend, &ikev2, "eth9").await,
+            Err(ConnectionError::InvalidInput { reason, .. }) if reason.contains("no device")
+        ));
+        assert!(matches!(
+            base_connection_on(&backend, &wireguard("Corp"), "eth0").await,
+            Err(ConnectionError::InvalidInput { .. })
+        ));
+
+        let device = backend.get_devices().await.unwrap().remove(0);
+        let (_, uplink) = backend
+            .add_and_activate_connection(
+                build_ethernet_connection("eth0", &ConnectionOptions::new(true)),
+                &device,
+                &OwnedObjectPath::default(),
+            )
+            .await
+            .unwrap();
+        backend.wait_for_activation(&uplink, None).await.unwrap();
+        assert_eq!(
+            base_connection_on(&backend, &ikev2, "eth0").await.unwrap(),
+            uplink
+        );
+    }
+
+    #[tokio::test]
+    async fn activates_saved_vpn_by_uuid() {
+        let settings = build_wireguard_connection(
+            &match wireguard("Corp") {
+                VpnConfiguration::WireGuard(wg) => wg.into(),
+                _ => unreachable!(),
+            },
+            &ConnectionOptions::new(false),
+        )
+        .unwrap();
+        let backend = MockBackend::new().with_profile(settings);
+        let (profile, settings) = backend.profiles().remove(0);
+        let uuid = String::try_from(settings["connection"]["uuid"].try_clone().unwrap()).unwrap();
+
+        let active = activate_saved_vpn(&backend, &uuid, None).await.unwrap();
+        let activations = backend.activations();
+        assert_eq!(activations[0].active, active);
+        assert_eq!(activations[0].connection, profile);
+
+        assert!(matches!(
+            activate_saved_vpn(&backend, "00000000-0000-0000-0000-000000000000", None).await,
+            Err(ConnectionError::VpnNotFound(_))
+        ));
+    }
 }
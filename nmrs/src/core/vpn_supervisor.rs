@@ -0,0 +1,543 @@
+//! Keeps a VPN up: reconnects it, or holds a kill switch, when it drops.
+//!
+//! The supervisor follows the VPN's `Connection.Active.StateChanged`
+//! signal. A deactivation with any reason other than "user disconnected"
+//! starts recovery; each step is reported as a [`VpnSupervisorEvent`].
+//!
+//! Under a kill switch, a reconnect brings the uplinks up only to carry
+//! the VPN activation: if restoring them or activating the VPN fails, they
+//! are disconnected again before the next attempt, and stay down once the
+//! supervisor gives up.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use futures::stream::{self, Stream, StreamExt};
+use futures_timer::Delay;
+use log::{debug, info, warn};
+use zbus::Connection;
+use zvariant::OwnedObjectPath;
+
+use crate::Result;
+use crate::api::models::{
+    ActiveConnectionState, ConnectionError, ConnectionStateReason, VpnSupervisorEvent,
+    VpnSupervisorPolicy,
+};
+use crate::backend::{DbusBackend, NmBackend};
+use crate::core::vpn::activate_saved_vpn;
+use crate::dbus::{
+    ActiveConnectionStateChangedStream, NMActiveConnectionProxy, NMDeviceProxy, NMProxy,
+};
+
+/// Connection types a kill switch takes down.
+const UPLINK_TYPES: &[&str] = &[
+    "802-3-ethernet",
+    "802-11-wireless",
+    "gsm",
+    "cdma",
+    "bluetooth",
+];
+
+/// Settings of one supervisor run.
+#[derive(Debug, Clone)]
+pub(crate) struct SupervisorConfig {
+    pub(crate) policy: VpnSupervisorPolicy,
+    pub(crate) initial_backoff: Duration,
+    pub(crate) max_backoff: Duration,
+    pub(crate) max_attempts: Option<u32>,
+    pub(crate) activation_timeout: Option<Duration>,
+}
+
+/// A connection the kill switch takes down and later restores.
+#[derive(Debug, Clone)]
+struct Uplink {
+    device: OwnedObjectPath,
+    connection: OwnedObjectPath,
+    interface: String,
+}
+
+enum Phase {
+    Watching(Box<ActiveConnectionStateChangedStream>),
+    Recovering { attempt: u32 },
+    Attempting { attempt: u32, delay: Duration },
+    Done,
+}
+
+struct Supervisor {
+    conn: Connection,
+    recovery: Recovery<DbusBackend>,
+    phase: Phase,
+}
+
+/// Reconnect and kill-switch steps, run against an [`NmBackend`].
+struct Recovery<B> {
+    backend: B,
+    uuid: String,
+    config: SupervisorConfig,
+    uplinks: Vec<Uplink>,
+    /// Whether the kill switch currently holds the uplinks down.
+    blocked: bool,
+    pending: VecDeque<VpnSupervisorEvent>,
+}
+
+/// Starts supervising the active VPN `id` (name or UUID).
+///
+/// Supervision runs while the returned stream is polled and stops when it
+/// is dropped or yields `None`.
+pub(crate) async fn supervise(
+    conn: &Connection,
+    id: &str,
+    config: SupervisorConfig,
+) -> Result<impl Stream<Item = VpnSupervisorEvent> + Send + Unpin + 'static> {
+    let (active, uuid) = find_active_vpn(conn, id)
+        .await?
+        .ok_or(ConnectionError::NoVpnConnection)?;
+
+    let mut supervisor = Supervisor {
+        conn: conn.clone(),
+        recovery: Recovery {
+            backend: DbusBackend::from_connection(conn.clone()),
+            uuid,
+            config,
+            uplinks: Vec::new(),
+            blocked: false,
+            pending: VecDeque::new(),
+        },
+        phase: Phase::Done,
+    };
+    supervisor.watch(active).await?;
+    info!("Supervising VPN {}", supervisor.recovery.uuid);
+
+    Ok(Box::pin(stream::unfold(supervisor, |mut s| async move {
+        let event = s.next_event().await?;
+        Some((event, s))
+    })))
+}
+
+impl Supervisor {
+    async fn next_event(&mut self) -> Option<VpnSupervisorEvent> {
+        loop {
+            if let Some(event) = self.recovery.pending.pop_front() {
+                return Some(event);
+            }
+            match std::mem::replace(&mut self.phase, Phase::Done) {
+                Phase::Done => return None,
+                Phase::Watching(mut changes) => match wait_for_drop(&mut changes).await {
+                    ConnectionStateReason::UserDisconnected => {
+                        info!("VPN {} disconnected by the user", self.recovery.uuid);
+                        self.recovery.pending.push_back(VpnSupervisorEvent::Stopped);
+                    }
+                    reason => self.dropped(reason).await,
+                },
+                Phase::Recovering { attempt } => self.schedule(attempt).await,
+                Phase::Attempting { attempt, delay } => self.attempt(attempt, delay).await,
+            }
+        }
+    }
+
+    /// Subscribes to `active`'s state and, for a kill switch, records the
+    /// uplinks next to it.
+    async fn watch(&mut self, active: OwnedObjectPath) -> Result<()> {
+        let ac = active_proxy(&self.conn, &active).await?;
+        let changes = ac.receive_activation_state_changed().await?;
+        if self.recovery.kill_switch() {
+            self.recovery.uplinks = uplinks(&self.conn, &active).await;
+        }
+
+        // It may have dropped before the subscription was in place.
+        match ac.state().await.map(ActiveConnectionState::from) {
+            Ok(ActiveConnectionState::Deactivated) | Err(_) => {
+                self.dropped(ConnectionStateReason::Unknown).await;
+            }
+            Ok(_) => self.phase = Phase::Watching(Box::new(changes)),
+        }
+        Ok(())
+    }
+
+    async fn dropped(&mut self, reason: ConnectionStateReason) {
+        warn!("VPN {} dropped: {reason}", self.recovery.uuid);
+        self.recovery
+            .pending
+            .push_back(VpnSupervisorEvent::Dropped { reason });
+        self.recovery.block().await;
+        self.phase = Phase::Recovering { attempt: 1 };
+    }
+
+    async fn schedule(&mut self, attempt: u32) {
+        let config = &self.recovery.config;
+        if config.max_attempts.is_some_and(|max| attempt > max) {
+            self.recovery.give_up(attempt - 1).await;
+            return;
+        }
+        let delay = backoff(config.initial_backoff, config.max_backoff, attempt);
+        self.recovery
+            .pending
+            .push_back(VpnSupervisorEvent::Reconnecting { attempt, delay });
+        self.phase = Phase::Attempting { attempt, delay };
+    }
+
+    async fn attempt(&mut self, attempt: u32, delay: Duration) {
+        Delay::new(delay).await;
+        match self.recovery.attempt(attempt).await {
+            Some(active) => {
+                if let Err(e) = self.watch(active).await {
+                    warn!(
+                        "Failed to watch reconnected VPN {}: {e}",
+                        self.recovery.uuid
+                    );
+                    self.dropped(ConnectionStateReason::Unknown).await;
+                }
+            }
+            None => {
+                self.phase = Phase::Recovering {
+                    attempt: attempt + 1,
+                };
+            }
+        }
+    }
+}
+
+impl<B: NmBackend> Recovery<B> {
+    fn kill_switch(&self) -> bool {
+        self.config.policy == VpnSupervisorPolicy::KillSwitch
+    }
+
+    /// Takes the uplinks down if the kill switch is on and they are up.
+    async fn block(&mut self) {
+        if !self.kill_switch() || self.blocked {
+            return;
+        }
+        let interfaces = block(&self.backend, &self.uplinks, self.config.activation_timeout).await;
+        self.blocked = true;
+        self.pending
+            .push_back(VpnSupervisorEvent::TrafficBlocked { interfaces });
+    }
+
+    /// Runs reconnect attempt `attempt`; returns the new active VPN, or
+    /// `None` after reporting the failure and, under a kill switch, taking
+    /// the uplinks down again.
+    async fn attempt(&mut self, attempt: u32) -> Option<OwnedObjectPath> {
+        debug!("Reconnecting VPN {} (attempt {attempt})", self.uuid);
+        match self.reconnect().await {
+            Ok(active) => {
+                info!("VPN {} reconnected", self.uuid);
+                self.pending.push_back(VpnSupervisorEvent::Reconnected);
+                Some(active)
+            }
+            Err(error) => {
+                warn!("Reconnecting VPN {} failed: {error}", self.uuid);
+                self.pending
+                    .push_back(VpnSupervisorEvent::ReconnectFailed { attempt, error });
+                self.block().await;
+                None
+            }
+        }
+    }
+
+    /// Restores the uplinks for a kill switch, then activates the VPN.
+    async fn reconnect(&mut self) -> Result<OwnedObjectPath> {
+        let timeout = self.config.activation_timeout;
+        if self.kill_switch() {
+            self.blocked = false;
+            restore(&self.backend, &self.uplinks, timeout).await?;
+        }
+        activate_saved_vpn(&self.backend, &self.uuid, timeout).await
+    }
+
+    /// Stops recovering after `attempts` failed attempts, leaving a kill
+    /// switch engaged.
+    async fn give_up(&mut self, attempts: u32) {
+        warn!("Giving up on VPN {} after {attempts} attempts", self.uuid);
+        self.block().await;
+        self.pending
+            .push_back(VpnSupervisorEvent::GaveUp { attempts });
+    }
+}
+
+/// Waits for the active connection to deactivate and returns why.
+async fn wait_for_drop(changes: &mut ActiveConnectionStateChangedStream) -> ConnectionStateReason {
+    while let Some(signal) = changes.next().await {
+        let Ok(args) = signal.args() else {
+            continue;
+        };
+        if ActiveConnectionState::from(args.state) == ActiveConnectionState::Deactivated {
+            return ConnectionStateReason::from(args.reason);
+        }
+    }
+    ConnectionStateReason::Unknown
+}
+
+/// Backoff before `attempt` (1-based): doubling from `initial`, capped at `max`.
+fn backoff(initial: Duration, max: Duration, attempt: u32) -> Duration {
+    let factor = 1u32
+        .checked_shl(attempt.saturating_sub(1))
+        .unwrap_or(u32::MAX);
+    initial.saturating_mul(factor).min(max)
+}
+
+async fn active_proxy(
+    conn: &Connection,
+    path: &OwnedObjectPath,
+) -> zbus::Result<NMActiveConnectionProxy<'static>> {
+    NMActiveConnectionProxy::builder(conn)
+        .path(path.clone())?
+        .build()
+        .await
+}
+
+async fn device_proxy(
+    conn: &Connection,
+    path: &OwnedObjectPath,
+) -> zbus::Result<NMDeviceProxy<'static>> {
+    NMDeviceProxy::builder(conn)
+        .path(path.clone())?
+        .build()
+        .await
+}
+
+/// Finds the active VPN or WireGuard connection named or identified by `id`.
+async fn find_active_vpn(conn: &Connection, id: &str) -> Result<Option<(OwnedObjectPath, String)>> {
+    let nm = NMProxy::new(conn).await?;
+    for path in nm.active_connections().await? {
+        let Ok(ac) = active_proxy(conn, &path).await else {
+            continue;
+        };
+        if !ac
+            .connection_type()
+            .await
+            .is_ok_and(|ty| ty == "vpn" || ty == "wireguard")
+        {
+            continue;
+        }
+        let uuid = ac.uuid().await.unwrap_or_default();
+        if uuid == id || ac.id().await.is_ok_and(|name| name == id) {
+            return Ok(Some((path, uuid)));
+        }
+    }
+    Ok(None)
+}
+
+/// Ethernet, Wi-Fi and mobile connections active next to `vpn`.
+async fn uplinks(conn: &Connection, vpn: &OwnedObjectPath) -> Vec<Uplink> {
+    let Ok(nm) = NMProxy::new(conn).await else {
+        return Vec::new();
+    };
+    let mut out = Vec::new();
+    for path in nm.active_connections().await.unwrap_or_default() {
+        if &path == vpn {
+            continue;
+        }
+        let Ok(ac) = active_proxy(conn, &path).await else {
+            continue;
+        };
+        if !ac
+            .connection_type()
+            .await
+            .is_ok_and(|ty| UPLINK_TYPES.contains(&ty.as_str()))
+        {
+            continue;
+        }
+        let Ok(connection) = ac.connection().await else {
+            continue;
+        };
+        for device in ac.devices().await.unwrap_or_default() {
+            let interface = match device_proxy(conn, &device).await {
+                Ok(dev) => dev.interface().await.unwrap_or_default(),
+                Err(_) => String::new(),
+            };
+            out.push(Uplink {
+                device,
+                connection: connection.clone(),
+                interface,
+            });
+        }
+    }
+    out
+}
+
+/// Disconnects the uplinks' devices; returns the interfaces taken down.
+async fn block<B: NmBackend + ?Sized>(
+    backend: &B,
+    uplinks: &[Uplink],
+    timeout: Option<Duration>,
+) -> Vec<String> {
+    let mut blocked = Vec::new();
+    for uplink in uplinks {
+        match backend.disconnect_device(&uplink.device, timeout).await {
+            Ok(()) => blocked.push(uplink.interface.clone()),
+            Err(e) => debug!("Could not disconnect {}: {e}", uplink.interface),
+        }
+    }
+    blocked
+}
+
+/// Re-activates the uplinks; succeeds if at least one comes up.
+async fn restore<B: NmBackend + ?Sized>(
+    backend: &B,
+    uplinks: &[Uplink],
+    timeout: Option<Duration>,
+) -> Result<()> {
+    let mut restored = uplinks.is_empty();
+    let mut last_error = None;
+    for uplink in uplinks {
+        let result = async {
+            let active = backend
+                .activate_connection(
+                    &uplink.connection,
+                    &uplink.device,
+                    &OwnedObjectPath::default(),
+                )
+                .await?;
+            backend.wait_for_activation(&active, timeout).await
+        }
+        .await;
+        match result {
+            Ok(()) => restored = true,
+            Err(e) => {
+                debug!("Could not restore {}: {e}", uplink.interface);
+                last_error = Some(e);
+            }
+        }
+    }
+    match last_error {
+        Some(e) if !restored => Err(e),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::builders::{build_ethernet_connection, build_wireguard_connection};
+    use crate::api::models::{
+        ConnectionOptions, DeviceState, DeviceType, WireGuardConfig, WireGuardPeer,
+    };
+    use crate::backend::MockBackend;
+
+    /// A kill-switch recovery for a saved WireGuard profile over eth0,
+    /// with the uplink up.
+    async fn kill_switch(backend: MockBackend) -> Recovery<MockBackend> {
+        let options = ConnectionOptions::new(false);
+        let key = "HIgo9xNzJMWLKAShlKl6/bUT1VI9Q0SDBXGtLXkPFXc=";
+        let peer = WireGuardPeer::new(key, "vpn.example.com:51820", vec!["0.0.0.0/0".into()]);
+        let wireguard = WireGuardConfig::new(
+            "Corp",
+            "vpn.example.com:51820",
+            key,
+            "10.0.0.2/24",
+            vec![peer],
+        );
+        let backend = backend
+            .with_device("eth0", DeviceType::Ethernet, DeviceState::Disconnected)
+            .with_profile(build_ethernet_connection("eth0", &options))
+            .with_profile(build_wireguard_connection(&wireguard.into(), &options).unwrap());
+        let profiles = backend.profiles();
+        let uuid =
+            String::try_from(profiles[1].1["connection"]["uuid"].try_clone().unwrap()).unwrap();
+        let device = backend.get_devices().await.unwrap().remove(0);
+        let uplink = Uplink {
+            device,
+            connection: profiles[0].0.clone(),
+            interface: "eth0".into(),
+        };
+        restore(&backend, std::slice::from_ref(&uplink), None)
+            .await
+            .unwrap();
+
+        Recovery {
+            backend,
+            uuid,
+            config: SupervisorConfig {
+                policy: VpnSupervisorPolicy::KillSwitch,
+                initial_backoff: Duration::ZERO,
+                max_backoff: Duration::ZERO,
+                max_attempts: Some(1),
+                activation_timeout: None,
+            },
+            uplinks: vec![uplink],
+            blocked: false,
+            pending: VecDeque::new(),
+        }
+    }
+
+    async fn uplink_state(recovery: &Recovery<MockBackend>) -> DeviceState {
+        let device = &recovery.uplinks[0].device;
+        recovery.backend.device_state(device).await.unwrap().into()
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let initial = Duration::from_secs(2);
+        let max = Duration::from_secs(30);
+        assert_eq!(backoff(initial, max, 1), Duration::from_secs(2));
+        assert_eq!(backoff(initial, max, 2), Duration::from_secs(4));
+        assert_eq!(backoff(initial, max, 4), Duration::from_secs(16));
+        assert_eq!(backoff(initial, max, 5), max);
+        assert_eq!(backoff(initial, max, 100), max);
+    }
+
+    #[tokio::test]
+    async fn kill_switch_blocks_again_when_reconnecting_fails() {
+        let backend = MockBackend::new()
+            .with_vpn_activation_failure(ConnectionError::VpnFailed("unreachable".into()));
+        let mut recovery = kill_switch(backend).await;
+        recovery.block().await;
+        assert_eq!(uplink_state(&recovery).await, DeviceState::Disconnected);
+
+        assert!(recovery.attempt(1).await.is_none());
+        // The uplink came back for the VPN activation, then went down again.
+        assert_eq!(recovery.backend.activations().len(), 3);
+        assert_eq!(uplink_state(&recovery).await, DeviceState::Disconnected);
+        let events: Vec<_> = recovery.pending.drain(..).collect();
+        assert!(matches!(
+            events.as_slice(),
+            [
+                VpnSupervisorEvent::TrafficBlocked { .. },
+                VpnSupervisorEvent::ReconnectFailed { attempt: 1, .. },
+                VpnSupervisorEvent::TrafficBlocked { interfaces },
+            ] if interfaces == &["eth0"]
+        ));
+
+        recovery.give_up(1).await;
+        assert!(matches!(
+            recovery.pending.drain(..).collect::<Vec<_>>().as_slice(),
+            [VpnSupervisorEvent::GaveUp { attempts: 1 }]
+        ));
+        assert_eq!(uplink_state(&recovery).await, DeviceState::Disconnected);
+    }
+
+    #[tokio::test]
+    async fn kill_switch_stays_engaged_after_giving_up() {
+        let mut recovery = kill_switch(MockBackend::new()).await;
+        assert_eq!(uplink_state(&recovery).await, DeviceState::Activated);
+
+        recovery.give_up(3).await;
+        assert_eq!(uplink_state(&recovery).await, DeviceState::Disconnected);
+        assert!(matches!(
+            recovery.pending.drain(..).collect::<Vec<_>>().as_slice(),
+            [
+                VpnSupervisorEvent::TrafficBlocked { .. },
+                VpnSupervisorEvent::GaveUp { attempts: 3 },
+            ]
+        ));
+    }
+
+    #[tokio::test]
+    async fn kill_switch_reconnects_over_restored_uplinks() {
+        let mut recovery = kill_switch(MockBackend::new()).await;
+        recovery.block().await;
+        recovery.pending.clear();
+
+        let active = recovery.attempt(1).await.unwrap();
+        assert_eq!(uplink_state(&recovery).await, DeviceState::Activated);
+        assert_eq!(
+            recovery.backend.activations().last().unwrap().active,
+            active
+        );
+        assert!(!recovery.blocked);
+        assert!(matches!(
+            recovery.pending.drain(..).collect::<Vec<_>>().as_slice(),
+            [VpnSupervisorEvent::Reconnected]
+        ));
+    }
+}
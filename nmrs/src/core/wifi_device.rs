@@ -11,8 +11,8 @@ use zbus::Connection;
 
 use crate::Result;
 use crate::api::models::{ConnectionError, WifiDevice};
-use crate::core::connection::{disconnect_wifi_and_wait, get_device_by_interface};
-use crate::dbus::{NMAccessPointProxy, NMDeviceProxy, NMProxy, NMWirelessProxy};
+use crate::core::connection::{disconnect_device_and_wait, get_device_by_interface};
+use crate::dbus::{NMAccessPointProxy, NMDeviceProxy, NMProxy, NMWirelessProxy, dbus_operation};
 use crate::types::constants::device_type;
 use crate::util::utils::decode_ssid_or_hidden;
 
@@ -117,14 +117,14 @@ pub(crate) async fn set_wifi_enabled_for_interface(
     debug!("setting Autoconnect={} for {}", enabled, interface);
     if let Err(e) = dev.set_autoconnect(enabled).await {
         warn!("failed to set autoconnect on {}: {}", interface, e);
-        return Err(ConnectionError::DbusOperation {
-            context: format!("failed to set Autoconnect on {}", interface),
-            source: e,
-        });
+        return Err(dbus_operation(
+            format!("failed to set Autoconnect on {}", interface),
+            e,
+        ));
     }
 
     if !enabled {
-        disconnect_wifi_and_wait(conn, &path, None).await?;
+        disconnect_device_and_wait(conn, &path, None).await?;
     }
 
     Ok(())
@@ -0,0 +1,168 @@
+//! Wi-Fi Protected Setup (WPS) onboarding.
+//!
+//! Picks an access point that currently offers the requested WPS method,
+//! creates a profile without a passphrase and lets wpa_supplicant obtain
+//! the key. NetworkManager drives push-button enrollment itself; for PIN
+//! enrollment the caller's PIN is handed to wpa_supplicant once the device
+//! asks for credentials.
+
+use futures::StreamExt;
+use log::{debug, info, warn};
+use std::collections::HashMap;
+use zbus::Connection;
+use zvariant::{OwnedObjectPath, Value};
+
+use crate::Result;
+use crate::api::builders::{WifiConnectionBuilder, WpsMethod};
+use crate::api::models::{ConnectionError, ConnectionOptions, TimeoutConfig};
+use crate::backend::{DbusBackend, NmBackend};
+use crate::core::connection::{
+    activation_options, disconnect_device_and_wait, resolve_wifi_device,
+};
+use crate::core::state_wait::{request_scan_and_wait, wait_for_connection_activation};
+use crate::dbus::{
+    NMAccessPointProxy, NMDeviceProxy, NMWirelessProxy, WpaSupplicantProxy, WpaSupplicantWpsProxy,
+    dbus_operation,
+};
+use crate::types::constants::{ap_flags, device_state};
+use crate::util::utils::decode_ssid_or_empty;
+use crate::util::validation::validate_wps_pin;
+
+/// Connects to an access point using WPS push-button (`pin = None`) or PIN.
+///
+/// Scans, then picks the strongest access point advertising the requested
+/// method. Returns `NotFound` if none is visible.
+pub(crate) async fn connect_wps(
+    conn: &Connection,
+    interface: Option<&str>,
+    pin: Option<&str>,
+    timeout_config: Option<TimeoutConfig>,
+    options: &ConnectionOptions,
+) -> Result<()> {
+    if let Some(pin) = pin {
+        validate_wps_pin(pin)?;
+    }
+    let method = if pin.is_some() {
+        WpsMethod::Pin
+    } else {
+        WpsMethod::PushButton
+    };
+
+    let wifi_device = resolve_wifi_device(conn, interface).await?;
+    let wifi = NMWirelessProxy::builder(conn)
+        .path(wifi_device.clone())?
+        .build()
+        .await?;
+
+    request_scan_and_wait(&wifi, HashMap::new()).await;
+
+    let (ap, ssid) = find_wps_ap(conn, &wifi, method).await?;
+    debug!("Starting WPS ({method:?}) with '{ssid}'");
+
+    let settings = WifiConnectionBuilder::new(ssid.as_str())
+        .options(options)
+        .wps(method)
+        .ipv4_auto()
+        .ipv6_auto()
+        .build();
+
+    disconnect_device_and_wait(conn, &wifi_device, timeout_config).await?;
+
+    let (_, active_conn) = DbusBackend::from_connection(conn.clone())
+        .add_and_activate_connection2(settings, &wifi_device, &ap, activation_options(options))
+        .await?;
+
+    let timeout = timeout_config.map(|c| c.connection_timeout);
+    match pin {
+        None => wait_for_connection_activation(conn, &active_conn, timeout).await?,
+        Some(pin) => {
+            futures::try_join!(
+                wait_for_connection_activation(conn, &active_conn, timeout),
+                enroll_pin(conn, &wifi_device, pin),
+            )?;
+        }
+    }
+
+    info!("Connected to '{ssid}' via WPS");
+    Ok(())
+}
+
+/// Finds the strongest access point currently offering `method`.
+async fn find_wps_ap(
+    conn: &Connection,
+    wifi: &NMWirelessProxy<'_>,
+    method: WpsMethod,
+) -> Result<(OwnedObjectPath, String)> {
+    let wanted = match method {
+        WpsMethod::Pin => ap_flags::WPS_PIN,
+        _ => ap_flags::WPS_PBC,
+    };
+
+    let mut best: Option<(OwnedObjectPath, String, u8)> = None;
+    for ap_path in wifi.access_points().await? {
+        let ap = NMAccessPointProxy::builder(conn)
+            .path(ap_path.clone())?
+            .build()
+            .await?;
+        if ap.flags().await.unwrap_or(0) & wanted == 0 {
+            continue;
+        }
+        let strength = ap.strength().await.unwrap_or(0);
+        if best.as_ref().is_some_and(|(_, _, s)| *s >= strength) {
+            continue;
+        }
+        let ssid = decode_ssid_or_empty(&ap.ssid().await?).into_owned();
+        if !ssid.is_empty() {
+            best = Some((ap_path, ssid, strength));
+        }
+    }
+
+    best.map(|(path, ssid, _)| (path, ssid))
+        .ok_or(ConnectionError::NotFound)
+}
+
+/// Waits for the device to ask for credentials, then starts PIN enrollment.
+async fn enroll_pin(conn: &Connection, wifi_device: &OwnedObjectPath, pin: &str) -> Result<()> {
+    let dev = NMDeviceProxy::builder(conn)
+        .path(wifi_device.clone())?
+        .build()
+        .await?;
+
+    let mut stream = dev.receive_device_state_changed().await?;
+    if dev.state().await? != device_state::NEED_AUTH {
+        loop {
+            let Some(signal) = stream.next().await else {
+                return Err(ConnectionError::Stuck("signal stream ended".into()));
+            };
+            match signal.args() {
+                Ok(args) if args.new_state == device_state::NEED_AUTH => break,
+                Ok(_) => {}
+                Err(e) => warn!("Failed to parse StateChanged signal args: {e}"),
+            }
+        }
+    }
+
+    let ifname = dev.interface().await?;
+    let supplicant = WpaSupplicantProxy::new(conn).await?;
+    let iface_path = supplicant.get_interface(&ifname).await.map_err(|e| {
+        dbus_operation(
+            format!("failed to find wpa_supplicant interface for {ifname}"),
+            e,
+        )
+    })?;
+    let wps = WpaSupplicantWpsProxy::builder(conn)
+        .path(iface_path)?
+        .build()
+        .await?;
+
+    let args = HashMap::from([
+        ("Role", Value::from("enrollee")),
+        ("Type", Value::from("pin")),
+        ("Pin", Value::from(pin)),
+    ]);
+    wps.start(args)
+        .await
+        .map_err(|e| dbus_operation("failed to start WPS PIN enrollment", e))?;
+    debug!("WPS PIN enrollment started on {ifname}");
+    Ok(())
+}
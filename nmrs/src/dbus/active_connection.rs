@@ -57,6 +57,15 @@ pub trait NMActiveConnection {
     #[zbus(property)]
     fn uuid(&self) -> Result<String>;
 
+    /// Connection type (`connection.type` of the profile, e.g. `"vpn"`).
+    #[zbus(property, name = "Type")]
+    fn connection_type(&self) -> Result<String>;
+
+    /// Whether this is a plugin VPN, i.e. also implements
+    /// `org.freedesktop.NetworkManager.VPN.Connection`.
+    #[zbus(property)]
+    fn vpn(&self) -> Result<bool>;
+
     /// Paths to devices using this connection.
     #[zbus(property)]
     fn devices(&self) -> Result<Vec<OwnedObjectPath>>;
@@ -0,0 +1,27 @@
+//! NetworkManager Checkpoint proxy.
+
+use zbus::{Result, proxy};
+use zvariant::OwnedObjectPath;
+
+/// Proxy for a checkpoint created with `CheckpointCreate`.
+///
+/// The object disappears from the manager's `Checkpoints` property when the
+/// checkpoint is destroyed, rolled back by a client, or rolled back
+/// automatically after `RollbackTimeout` expires.
+#[proxy(
+    interface = "org.freedesktop.NetworkManager.Checkpoint",
+    default_service = "org.freedesktop.NetworkManager"
+)]
+pub trait NMCheckpoint {
+    /// Devices whose configuration the checkpoint covers.
+    #[zbus(property)]
+    fn devices(&self) -> Result<Vec<OwnedObjectPath>>;
+
+    /// Creation time in `CLOCK_BOOTTIME` milliseconds.
+    #[zbus(property)]
+    fn created(&self) -> Result<i64>;
+
+    /// Seconds after creation at which NM rolls back automatically (0 = never).
+    #[zbus(property)]
+    fn rollback_timeout(&self) -> Result<u32>;
+}
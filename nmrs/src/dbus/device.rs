@@ -1,7 +1,9 @@
 //! NetworkManager Device proxy.
 
+use std::collections::HashMap;
+
 use zbus::{Result, proxy};
-use zvariant::OwnedObjectPath;
+use zvariant::{OwnedObjectPath, OwnedValue};
 
 /// Proxy for NetworkManager device interface.
 ///
@@ -42,6 +44,14 @@ pub trait NMDevice {
     #[zbus(property)]
     fn managed(&self) -> Result<bool>;
 
+    /// Whether the device is missing required firmware.
+    #[zbus(property)]
+    fn firmware_missing(&self) -> Result<bool>;
+
+    /// Whether the NM plugin needed to manage this device type is missing.
+    #[zbus(property)]
+    fn nm_plugin_missing(&self) -> Result<bool>;
+
     /// The kernel driver in use.
     #[zbus(property)]
     fn driver(&self) -> Result<String>;
@@ -64,6 +74,20 @@ pub trait NMDevice {
     #[zbus(property)]
     fn active_connection(&self) -> Result<OwnedObjectPath>;
 
+    /// Path to the DHCPv4 lease of the device, or "/" if IPv4 was not
+    /// configured by DHCP.
+    #[zbus(property, name = "Dhcp4Config")]
+    fn dhcp4_config(&self) -> Result<OwnedObjectPath>;
+
+    /// Path to the DHCPv6 lease of the device, or "/" if IPv6 was not
+    /// configured by DHCPv6.
+    #[zbus(property, name = "Dhcp6Config")]
+    fn dhcp6_config(&self) -> Result<OwnedObjectPath>;
+
+    /// Saved connection profiles that NM considers usable on this device.
+    #[zbus(property)]
+    fn available_connections(&self) -> Result<Vec<OwnedObjectPath>>;
+
     /// Whether NM automatically activates known connections on this device.
     #[zbus(property)]
     fn autoconnect(&self) -> Result<bool>;
@@ -75,6 +99,16 @@ pub trait NMDevice {
     /// Disconnect the active connection on this device, if any.
     fn disconnect(&self) -> Result<()>;
 
+    /// Applies changed settings to the active connection without
+    /// reactivating it. An empty `connection` reapplies the saved profile;
+    /// a `version_id` of 0 skips the applied-connection version check.
+    fn reapply(
+        &self,
+        connection: HashMap<String, HashMap<String, OwnedValue>>,
+        version_id: u64,
+        flags: u32,
+    ) -> Result<()>;
+
     /// Signal emitted when device state changes.
     ///
     /// The method is named `device_state_changed` to avoid conflicts with the
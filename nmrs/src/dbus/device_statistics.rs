@@ -0,0 +1,30 @@
+//! NetworkManager Device.Statistics proxy.
+
+use zbus::{Result, proxy};
+
+/// Proxy for the traffic counters of a device.
+///
+/// Available on every device object. NetworkManager only refreshes
+/// `TxBytes` / `RxBytes` while `RefreshRateMs` is non-zero, so set a rate
+/// before reading them.
+#[proxy(
+    interface = "org.freedesktop.NetworkManager.Device.Statistics",
+    default_service = "org.freedesktop.NetworkManager"
+)]
+pub trait NMDeviceStatistics {
+    /// Counter refresh interval in milliseconds (0 = disabled).
+    #[zbus(property)]
+    fn refresh_rate_ms(&self) -> Result<u32>;
+
+    /// Sets the counter refresh interval in milliseconds.
+    #[zbus(property)]
+    fn set_refresh_rate_ms(&self, value: u32) -> Result<()>;
+
+    /// Bytes transmitted since the device appeared.
+    #[zbus(property)]
+    fn tx_bytes(&self) -> Result<u64>;
+
+    /// Bytes received since the device appeared.
+    #[zbus(property)]
+    fn rx_bytes(&self) -> Result<u64>;
+}
@@ -0,0 +1,34 @@
+//! NetworkManager DHCP4Config / DHCP6Config proxies.
+
+use std::collections::HashMap;
+
+use zbus::{Result, proxy};
+use zvariant::OwnedValue;
+
+/// Proxy for the DHCPv4 lease of a device.
+///
+/// Obtained from the `Dhcp4Config` property of a device or active
+/// connection, which is `/` unless the configuration came from DHCP.
+#[proxy(
+    interface = "org.freedesktop.NetworkManager.DHCP4Config",
+    default_service = "org.freedesktop.NetworkManager"
+)]
+pub trait NMDhcp4Config {
+    /// Options and values the DHCP server sent, keyed by the DHCP client's
+    /// option names (`dhcp_server_identifier`, `dhcp_lease_time`, ...).
+    /// Values are strings.
+    #[zbus(property)]
+    fn options(&self) -> Result<HashMap<String, OwnedValue>>;
+}
+
+/// Proxy for the DHCPv6 lease of a device.
+#[proxy(
+    interface = "org.freedesktop.NetworkManager.DHCP6Config",
+    default_service = "org.freedesktop.NetworkManager"
+)]
+pub trait NMDhcp6Config {
+    /// Options and values the DHCPv6 server sent (`dhcp6_server_id`,
+    /// `dhcp6_domain_search`, ...). Values are strings.
+    #[zbus(property)]
+    fn options(&self) -> Result<HashMap<String, OwnedValue>>;
+}
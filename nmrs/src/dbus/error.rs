@@ -0,0 +1,171 @@
+//! Mapping of NetworkManager's D-Bus error names to [`ConnectionError`].
+//!
+//! NetworkManager reports failed method calls as D-Bus errors named after
+//! its error domains, e.g. `org.freedesktop.NetworkManager.UnknownDevice`
+//! or `org.freedesktop.NetworkManager.Settings.PermissionDenied`. The
+//! well-known ones become typed [`ConnectionError`] variants here, so
+//! callers can branch on them without parsing strings; everything else
+//! stays a [`ConnectionError::Dbus`] or [`ConnectionError::DbusOperation`].
+
+use zbus::fdo;
+
+use crate::api::models::ConnectionError;
+
+const NM_PREFIX: &str = "org.freedesktop.NetworkManager.";
+
+impl From<zbus::Error> for ConnectionError {
+    fn from(e: zbus::Error) -> Self {
+        typed(&e).unwrap_or(ConnectionError::Dbus(e))
+    }
+}
+
+/// Wraps a failed D-Bus call made while doing `context`.
+///
+/// Well-known NetworkManager errors become their typed variant, dropping
+/// `context`; NetworkManager's own message says what went wrong.
+pub(crate) fn dbus_operation(context: impl Into<String>, source: zbus::Error) -> ConnectionError {
+    typed(&source).unwrap_or_else(|| ConnectionError::DbusOperation {
+        context: context.into(),
+        source,
+    })
+}
+
+/// Whether `e` is the NetworkManager D-Bus error `name`, given without the
+/// `org.freedesktop.NetworkManager.` prefix (e.g. `"Settings.UuidExists"`).
+pub(crate) fn is_nm_error(e: &zbus::Error, name: &str) -> bool {
+    match e {
+        zbus::Error::MethodError(error, _, _) => error
+            .as_str()
+            .strip_prefix(NM_PREFIX)
+            .is_some_and(|rest| rest == name),
+        _ => false,
+    }
+}
+
+/// The typed variant for `e`, if it is a well-known NetworkManager error.
+fn typed(e: &zbus::Error) -> Option<ConnectionError> {
+    let (name, message) = match e {
+        zbus::Error::MethodError(name, message, _) => {
+            (name.as_str(), message.clone().unwrap_or_default())
+        }
+        zbus::Error::FDO(fdo) => match &**fdo {
+            fdo::Error::AccessDenied(message) => {
+                return Some(ConnectionError::PermissionDenied(message.clone()));
+            }
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    if name == "org.freedesktop.DBus.Error.AccessDenied" {
+        return Some(ConnectionError::PermissionDenied(message));
+    }
+    let name = name.strip_prefix(NM_PREFIX)?;
+    let error = match name {
+        "UnknownDevice" => ConnectionError::UnknownDevice(message),
+        "MissingPlugin" => ConnectionError::MissingPlugin(message),
+        "Settings.InvalidConnection"
+        | "Device.InvalidConnection"
+        | "Settings.Connection.MissingSetting"
+        | "Settings.Connection.InvalidSetting"
+        | "Settings.Connection.MissingProperty"
+        | "Settings.Connection.InvalidProperty" => ConnectionError::ConnectionInvalid(message),
+        // Manager, Settings and AgentManager all have one.
+        _ if name.ends_with("PermissionDenied") => ConnectionError::PermissionDenied(message),
+        _ => return None,
+    };
+    Some(error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zbus::message::Message;
+    use zbus::names::OwnedErrorName;
+
+    fn method_error(name: &str, message: &str) -> zbus::Error {
+        let reply = Message::method_call("/", "Ping")
+            .unwrap()
+            .build(&())
+            .unwrap();
+        zbus::Error::MethodError(
+            OwnedErrorName::try_from(name).unwrap(),
+            Some(message.into()),
+            reply,
+        )
+    }
+
+    #[test]
+    fn maps_well_known_names() {
+        let cases = [
+            (
+                "org.freedesktop.NetworkManager.UnknownDevice",
+                "UnknownDevice",
+            ),
+            (
+                "org.freedesktop.NetworkManager.PermissionDenied",
+                "PermissionDenied",
+            ),
+            (
+                "org.freedesktop.NetworkManager.Settings.PermissionDenied",
+                "PermissionDenied",
+            ),
+            (
+                "org.freedesktop.DBus.Error.AccessDenied",
+                "PermissionDenied",
+            ),
+            (
+                "org.freedesktop.NetworkManager.MissingPlugin",
+                "MissingPlugin",
+            ),
+            (
+                "org.freedesktop.NetworkManager.Settings.InvalidConnection",
+                "ConnectionInvalid",
+            ),
+            (
+                "org.freedesktop.NetworkManager.Settings.Connection.InvalidProperty",
+                "ConnectionInvalid",
+            ),
+        ];
+        for (name, kind) in cases {
+            let error = ConnectionError::from(method_error(name, "nope"));
+            assert!(
+                format!("{error:?}").starts_with(kind),
+                "{name} -> {error:?}"
+            );
+            assert!(error.to_string().contains("nope"));
+        }
+    }
+
+    #[test]
+    fn keeps_other_errors_opaque() {
+        let error = ConnectionError::from(method_error(
+            "org.freedesktop.NetworkManager.ConnectionAlreadyActive",
+            "busy",
+        ));
+        assert!(matches!(error, ConnectionError::Dbus(_)));
+
+        let error = dbus_operation(
+            "ActivateConnection failed",
+            method_error("org.freedesktop.NetworkManager.Failed", "busy"),
+        );
+        assert!(matches!(error, ConnectionError::DbusOperation { .. }));
+
+        let error = dbus_operation(
+            "ActivateConnection failed",
+            method_error("org.freedesktop.NetworkManager.UnknownDevice", "gone"),
+        );
+        assert!(matches!(error, ConnectionError::UnknownDevice(m) if m == "gone"));
+    }
+
+    #[test]
+    fn matches_nm_error_names() {
+        let e = method_error("org.freedesktop.NetworkManager.AlreadyAsleepOrAwake", "");
+        assert!(is_nm_error(&e, "AlreadyAsleepOrAwake"));
+        assert!(!is_nm_error(&e, "AlreadyEnabledOrDisabled"));
+        assert!(!is_nm_error(
+            &zbus::Error::InvalidReply,
+            "AlreadyAsleepOrAwake"
+        ));
+    }
+}
@@ -0,0 +1,74 @@
+//! NetworkManager IP4Config / IP6Config proxies.
+
+use std::collections::HashMap;
+
+use zbus::{Result, proxy};
+use zvariant::OwnedValue;
+
+/// Proxy for the IPv4 configuration of an active connection or device.
+///
+/// Obtained from the `Ip4Config` property of an active connection. The
+/// object reflects the configuration currently applied to the interface,
+/// whether it came from DHCP, static settings or a VPN plugin.
+#[proxy(
+    interface = "org.freedesktop.NetworkManager.IP4Config",
+    default_service = "org.freedesktop.NetworkManager"
+)]
+pub trait NMIP4Config {
+    /// Addresses as dicts with `address` (string) and `prefix` (u32) keys.
+    #[zbus(property)]
+    fn address_data(&self) -> Result<Vec<HashMap<String, OwnedValue>>>;
+
+    /// Default gateway, or an empty string if there is none.
+    #[zbus(property)]
+    fn gateway(&self) -> Result<String>;
+
+    /// Routes as dicts with `dest`, `prefix`, and optional `next-hop` and
+    /// `metric` keys.
+    #[zbus(property)]
+    fn route_data(&self) -> Result<Vec<HashMap<String, OwnedValue>>>;
+
+    /// Nameservers as dicts with an `address` (string) key.
+    #[zbus(property)]
+    fn nameserver_data(&self) -> Result<Vec<HashMap<String, OwnedValue>>>;
+
+    /// DNS domains associated with this configuration.
+    #[zbus(property)]
+    fn domains(&self) -> Result<Vec<String>>;
+
+    /// DNS search domains.
+    #[zbus(property)]
+    fn searches(&self) -> Result<Vec<String>>;
+}
+
+/// Proxy for the IPv6 configuration of an active connection or device.
+#[proxy(
+    interface = "org.freedesktop.NetworkManager.IP6Config",
+    default_service = "org.freedesktop.NetworkManager"
+)]
+pub trait NMIP6Config {
+    /// Addresses as dicts with `address` (string) and `prefix` (u32) keys.
+    #[zbus(property)]
+    fn address_data(&self) -> Result<Vec<HashMap<String, OwnedValue>>>;
+
+    /// Default gateway, or an empty string if there is none.
+    #[zbus(property)]
+    fn gateway(&self) -> Result<String>;
+
+    /// Routes as dicts with `dest`, `prefix`, and optional `next-hop` and
+    /// `metric` keys.
+    #[zbus(property)]
+    fn route_data(&self) -> Result<Vec<HashMap<String, OwnedValue>>>;
+
+    /// Nameservers as 16-byte network-order addresses.
+    #[zbus(property)]
+    fn nameservers(&self) -> Result<Vec<Vec<u8>>>;
+
+    /// DNS domains associated with this configuration.
+    #[zbus(property)]
+    fn domains(&self) -> Result<Vec<String>>;
+
+    /// DNS search domains.
+    #[zbus(property)]
+    fn searches(&self) -> Result<Vec<String>>;
+}
@@ -0,0 +1,18 @@
+//! systemd-logind Manager proxy for suspend/resume notifications.
+
+use zbus::proxy;
+
+/// Proxy for `org.freedesktop.login1.Manager`.
+///
+/// Only the `PrepareForSleep` signal is modelled: logind emits it with
+/// `start = true` right before suspend and `start = false` after resume.
+#[proxy(
+    interface = "org.freedesktop.login1.Manager",
+    default_service = "org.freedesktop.login1",
+    default_path = "/org/freedesktop/login1"
+)]
+pub trait Login1Manager {
+    /// Emitted before suspend (`start = true`) and after resume (`start = false`).
+    #[zbus(signal, name = "PrepareForSleep")]
+    fn prepare_for_sleep(&self, start: bool);
+}
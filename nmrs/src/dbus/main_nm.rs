@@ -43,6 +43,22 @@ pub trait NM {
         specific_object: OwnedObjectPath,
     ) -> zbus::Result<(OwnedObjectPath, OwnedObjectPath)>;
 
+    /// Like `AddAndActivateConnection`, with options such as `persist`.
+    ///
+    /// Returns the new connection settings, the active connection and a
+    /// result dict. Available since NetworkManager 1.16.
+    fn add_and_activate_connection2(
+        &self,
+        connection: HashMap<&str, HashMap<&str, zvariant::Value<'_>>>,
+        device: OwnedObjectPath,
+        specific_object: OwnedObjectPath,
+        options: HashMap<&str, zvariant::Value<'_>>,
+    ) -> zbus::Result<(
+        OwnedObjectPath,
+        OwnedObjectPath,
+        HashMap<String, zvariant::OwnedValue>,
+    )>;
+
     /// Activates an existing saved connection.
     fn activate_connection(
         &self,
@@ -62,6 +78,18 @@ pub trait NM {
     #[zbus(signal, name = "DeviceRemoved")]
     fn device_removed(&self, device: OwnedObjectPath);
 
+    /// Whether networking is enabled (see [`enable`](Self::enable)).
+    #[zbus(property)]
+    fn networking_enabled(&self) -> zbus::Result<bool>;
+
+    /// Enables or disables all networking; disabling deactivates every
+    /// device. Fails with `AlreadyEnabledOrDisabled` if nothing changes.
+    fn enable(&self, enable: bool) -> zbus::Result<()>;
+
+    /// Puts NetworkManager to sleep or wakes it, as done around system
+    /// suspend. Fails with `AlreadyAsleepOrAwake` if nothing changes.
+    fn sleep(&self, sleep: bool) -> zbus::Result<()>;
+
     /// Whether WWAN (mobile broadband) is globally enabled.
     #[zbus(property)]
     fn wwan_enabled(&self) -> zbus::Result<bool>;
@@ -82,10 +110,18 @@ pub trait NM {
     #[zbus(property)]
     fn connectivity(&self) -> zbus::Result<u32>;
 
+    /// Whether connectivity checks are possible (a check URI is configured).
+    #[zbus(property)]
+    fn connectivity_check_available(&self) -> zbus::Result<bool>;
+
     /// Whether NM is allowed to probe for connectivity.
     #[zbus(property)]
     fn connectivity_check_enabled(&self) -> zbus::Result<bool>;
 
+    /// Allow or forbid connectivity probing.
+    #[zbus(property)]
+    fn set_connectivity_check_enabled(&self, value: bool) -> zbus::Result<()>;
+
     /// URL NM probes when checking connectivity.
     #[zbus(property)]
     fn connectivity_check_uri(&self) -> zbus::Result<String>;
@@ -94,6 +130,24 @@ pub trait NM {
     #[zbus(property)]
     fn primary_connection(&self) -> zbus::Result<OwnedObjectPath>;
 
+    /// `connection.type` of the primary connection (empty when none).
+    #[zbus(property)]
+    fn primary_connection_type(&self) -> zbus::Result<String>;
+
+    /// Paths of the checkpoints that currently exist.
+    #[zbus(property)]
+    fn checkpoints(&self) -> zbus::Result<Vec<OwnedObjectPath>>;
+
     /// Forces a fresh connectivity check; blocks until done.
     fn check_connectivity(&self) -> zbus::Result<u32>;
+
+    /// Sets NM's log level and enabled domains (comma-separated).
+    fn set_logging(&self, level: &str, domains: &str) -> zbus::Result<()>;
+
+    /// Returns NM's current log level and enabled domains.
+    fn get_logging(&self) -> zbus::Result<(String, String)>;
+
+    /// Returns the caller's polkit result (`yes`, `auth` or `no`) for each
+    /// NetworkManager action.
+    fn get_permissions(&self) -> zbus::Result<HashMap<String, String>>;
 }
@@ -1,28 +1,51 @@
 //! D-Bus proxy interfaces for NetworkManager.
 //!
 //! This module contains low-level D-Bus proxy definitions for communicating
-//! with NetworkManager over the system bus.
+//! with NetworkManager over the system bus, and the mapping of its D-Bus
+//! errors to [`ConnectionError`](crate::ConnectionError).
 
 mod access_point;
 mod active_connection;
 pub(crate) mod agent_manager;
 mod bluetooth;
 mod bluez_adapter;
+mod checkpoint;
 mod device;
+mod device_statistics;
+mod dhcp_config;
+mod error;
+mod ip_config;
+mod login1;
 mod main_nm;
 mod settings;
 mod settings_connection;
+mod vpn_connection;
+mod wifi_p2p;
 mod wired;
 mod wireless;
+mod wpa_supplicant;
 
 pub(crate) use access_point::NMAccessPointProxy;
-pub(crate) use active_connection::NMActiveConnectionProxy;
+pub(crate) use active_connection::{
+    NMActiveConnectionProxy, StateChangedStream as ActiveConnectionStateChangedStream,
+};
 pub(crate) use agent_manager::AgentManagerProxy;
 pub(crate) use bluetooth::{BluezDeviceExtProxy, NMBluetoothProxy};
 pub(crate) use bluez_adapter::BluezAdapterProxy;
+pub(crate) use checkpoint::NMCheckpointProxy;
 pub(crate) use device::NMDeviceProxy;
+pub(crate) use device_statistics::NMDeviceStatisticsProxy;
+pub(crate) use dhcp_config::{NMDhcp4ConfigProxy, NMDhcp6ConfigProxy};
+pub(crate) use error::{dbus_operation, is_nm_error};
+pub(crate) use ip_config::{NMIP4ConfigProxy, NMIP6ConfigProxy};
+pub(crate) use login1::Login1ManagerProxy;
 pub(crate) use main_nm::NMProxy;
 pub(crate) use settings::NMSettingsProxy;
 pub(crate) use settings_connection::NMSettingsConnectionProxy;
+pub(crate) use vpn_connection::NMVpnConnectionProxy;
+pub(crate) use wifi_p2p::{NMWifiP2PPeerProxy, NMWifiP2PProxy};
 pub(crate) use wired::NMWiredProxy;
 pub(crate) use wireless::NMWirelessProxy;
+pub(crate) use wpa_supplicant::{
+    WpaSupplicantBssProxy, WpaSupplicantInterfaceProxy, WpaSupplicantProxy, WpaSupplicantWpsProxy,
+};
@@ -1,5 +1,7 @@
 //! NetworkManager Settings D-Bus proxy (`org.freedesktop.NetworkManager.Settings`).
 
+use std::collections::HashMap;
+
 use zbus::proxy;
 use zvariant::OwnedObjectPath;
 
@@ -16,6 +18,26 @@ pub trait NMSettings {
     /// Resolves a connection object path by UUID string.
     fn get_connection_by_uuid(&self, uuid: &str) -> zbus::Result<OwnedObjectPath>;
 
+    /// Adds a profile and persists it to disk.
+    fn add_connection(
+        &self,
+        connection: HashMap<String, HashMap<String, zvariant::OwnedValue>>,
+    ) -> zbus::Result<OwnedObjectPath>;
+
+    /// Adds a profile that lives in memory only and is lost on restart.
+    fn add_connection_unsaved(
+        &self,
+        connection: HashMap<&str, HashMap<&str, zvariant::Value<'_>>>,
+    ) -> zbus::Result<OwnedObjectPath>;
+
     /// Reload connection profiles from disk.
     fn reload_connections(&self) -> zbus::Result<bool>;
+
+    /// Emitted when a profile is added.
+    #[zbus(signal)]
+    fn new_connection(&self, connection: OwnedObjectPath) -> zbus::Result<()>;
+
+    /// Emitted when a profile is deleted.
+    #[zbus(signal)]
+    fn connection_removed(&self, connection: OwnedObjectPath) -> zbus::Result<()>;
 }
@@ -13,6 +13,13 @@ pub trait NMSettingsConnection {
     /// Full connection settings (`a{sa{sv}}`), excluding secrets.
     fn get_settings(&self) -> zbus::Result<HashMap<String, HashMap<String, OwnedValue>>>;
 
+    /// Secrets of one setting (e.g. `802-11-wireless-security`), subject to
+    /// polkit authorization.
+    fn get_secrets(
+        &self,
+        setting_name: &str,
+    ) -> zbus::Result<HashMap<String, HashMap<String, OwnedValue>>>;
+
     /// Merges partial settings into this profile.
     fn update(&self, settings: HashMap<String, HashMap<String, OwnedValue>>) -> zbus::Result<()>;
 
@@ -0,0 +1,47 @@
+//! NetworkManager VPN Connection proxy.
+
+use zbus::{Result, proxy};
+
+/// Proxy for the VPN-specific interface of a plugin VPN's active connection.
+///
+/// Lives on the same object path as its
+/// [`NMActiveConnectionProxy`](super::NMActiveConnectionProxy) (whose `Vpn`
+/// property is `true`). Native WireGuard connections do not implement it.
+///
+/// The `VpnStateChanged` signal carries the plugin's failure reason, which
+/// the generic `StateChanged` signal only reports as "unknown".
+#[proxy(
+    interface = "org.freedesktop.NetworkManager.VPN.Connection",
+    default_service = "org.freedesktop.NetworkManager"
+)]
+pub trait NMVpnConnection {
+    /// Current VPN state.
+    ///
+    /// Values:
+    /// - 0: Unknown
+    /// - 1: Prepare
+    /// - 2: NeedAuth
+    /// - 3: Connect
+    /// - 4: IpConfigGet
+    /// - 5: Activated
+    /// - 6: Failed
+    /// - 7: Disconnected
+    #[zbus(property)]
+    fn vpn_state(&self) -> Result<u32>;
+
+    /// Login banner sent by the VPN server, if any.
+    #[zbus(property)]
+    fn banner(&self) -> Result<String>;
+
+    /// Signal emitted when the VPN state changes.
+    ///
+    /// Named `vpn_connection_state_changed` to avoid clashing with the
+    /// `VpnState` property's change stream; subscribe with
+    /// `receive_vpn_connection_state_changed()`.
+    ///
+    /// Arguments:
+    /// - `state`: The new VPN state (see `VpnConnectionState`)
+    /// - `reason`: The reason for the change (see `VpnStateReason`)
+    #[zbus(signal, name = "VpnStateChanged")]
+    fn vpn_connection_state_changed(&self, state: u32, reason: u32);
+}
@@ -0,0 +1,61 @@
+//! NetworkManager Wi-Fi P2P (Wi-Fi Direct) device and peer proxies.
+
+use std::collections::HashMap;
+
+use zbus::{Result, proxy};
+use zvariant::{OwnedObjectPath, Value};
+
+/// Proxy for Wi-Fi P2P devices.
+///
+/// Discovers Wi-Fi Direct peers; connecting is done by activating a
+/// `wifi-p2p` profile with a peer as the specific object.
+#[proxy(
+    interface = "org.freedesktop.NetworkManager.Device.WifiP2P",
+    default_service = "org.freedesktop.NetworkManager"
+)]
+pub trait NMWifiP2P {
+    /// Peers currently known to the device.
+    #[zbus(property)]
+    fn peers(&self) -> Result<Vec<OwnedObjectPath>>;
+
+    /// Starts looking for peers. Supports a `timeout` option in seconds.
+    fn start_find(&self, options: HashMap<&str, Value<'_>>) -> Result<()>;
+
+    /// Stops looking for peers.
+    fn stop_find(&self) -> Result<()>;
+}
+
+/// Proxy for a discovered Wi-Fi P2P peer.
+#[proxy(
+    interface = "org.freedesktop.NetworkManager.WifiP2PPeer",
+    default_service = "org.freedesktop.NetworkManager"
+)]
+pub trait NMWifiP2PPeer {
+    /// Device name advertised by the peer.
+    #[zbus(property)]
+    fn name(&self) -> Result<String>;
+
+    /// Manufacturer advertised by the peer.
+    #[zbus(property)]
+    fn manufacturer(&self) -> Result<String>;
+
+    /// Model advertised by the peer.
+    #[zbus(property)]
+    fn model(&self) -> Result<String>;
+
+    /// Wi-Fi Display information elements.
+    #[zbus(property, name = "WfdIEs")]
+    fn wfd_ies(&self) -> Result<Vec<u8>>;
+
+    /// MAC address of the peer.
+    #[zbus(property)]
+    fn hw_address(&self) -> Result<String>;
+
+    /// Signal strength as percentage (0-100).
+    #[zbus(property)]
+    fn strength(&self) -> Result<u8>;
+
+    /// Monotonic seconds since boot when this peer was last seen, or -1.
+    #[zbus(property)]
+    fn last_seen(&self) -> Result<i32>;
+}
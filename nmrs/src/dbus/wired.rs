@@ -14,4 +14,8 @@ pub trait NMWired {
     /// Design speed of the device, in megabits/second (Mb/s).
     #[zbus(property)]
     fn speed(&self) -> Result<u32>;
+
+    /// Whether a cable is plugged in (link carrier detected).
+    #[zbus(property)]
+    fn carrier(&self) -> Result<bool>;
 }
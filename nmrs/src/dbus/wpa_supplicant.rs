@@ -0,0 +1,63 @@
+//! wpa_supplicant proxies for data NetworkManager does not expose.
+//!
+//! Used for WPS PIN enrollment, for reading raw BSS information elements
+//! and for polling the signal level of the current link.
+
+use std::collections::HashMap;
+use zbus::{Result, proxy};
+use zvariant::{OwnedObjectPath, OwnedValue, Value};
+
+/// Proxy for the wpa_supplicant root object.
+#[proxy(
+    interface = "fi.w1.wpa_supplicant1",
+    default_service = "fi.w1.wpa_supplicant1",
+    default_path = "/fi/w1/wpa_supplicant1"
+)]
+pub trait WpaSupplicant {
+    /// Returns the object path of the interface named `ifname`.
+    fn get_interface(&self, ifname: &str) -> Result<OwnedObjectPath>;
+}
+
+/// Proxy for an interface's WPS operations.
+///
+/// NetworkManager never passes a caller-chosen PIN to wpa_supplicant, so
+/// PIN enrollment is (re)started here; NM still picks up the resulting
+/// `Credentials` signal and stores the key in the profile.
+#[proxy(
+    interface = "fi.w1.wpa_supplicant1.Interface.WPS",
+    default_service = "fi.w1.wpa_supplicant1"
+)]
+pub trait WpaSupplicantWps {
+    /// Starts WPS enrollment (`Role`, `Type` and optional `Pin`/`Bssid`).
+    fn start(&self, args: HashMap<&str, Value<'_>>) -> Result<HashMap<String, OwnedValue>>;
+}
+
+/// Proxy for a wpa_supplicant network interface.
+#[proxy(
+    interface = "fi.w1.wpa_supplicant1.Interface",
+    default_service = "fi.w1.wpa_supplicant1"
+)]
+pub trait WpaSupplicantInterface {
+    /// Polls the driver for the current link: `rssi`, `avg-rssi` and
+    /// `noise` in dBm, `linkspeed` in Mbit/s, among others.
+    fn signal_poll(&self) -> Result<HashMap<String, OwnedValue>>;
+
+    /// BSS objects from the most recent scan results.
+    #[zbus(property, name = "BSSs")]
+    fn bss_list(&self) -> Result<Vec<OwnedObjectPath>>;
+}
+
+/// Proxy for a BSS seen by wpa_supplicant.
+#[proxy(
+    interface = "fi.w1.wpa_supplicant1.BSS",
+    default_service = "fi.w1.wpa_supplicant1"
+)]
+pub trait WpaSupplicantBss {
+    /// Raw BSSID bytes.
+    #[zbus(property, name = "BSSID")]
+    fn bssid(&self) -> Result<Vec<u8>>;
+
+    /// Information elements from the beacon or probe response.
+    #[zbus(property, name = "IEs")]
+    fn ies(&self) -> Result<Vec<u8>>;
+}
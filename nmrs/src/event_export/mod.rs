@@ -0,0 +1,231 @@
+//! Newline-delimited JSON export of [`NetworkEvent`]s.
+//!
+//! Enabled with the `event-export` feature. Mirrors the stream returned by
+//! [`NetworkManager::events`](crate::NetworkManager::events) as one JSON
+//! object per line, so status bars, shell scripts or Electron apps can
+//! follow network changes without linking against nmrs.
+//!
+//! Each object has an `"event"` tag naming the variant in `snake_case`;
+//! the remaining keys mirror the variant's fields. Object paths are plain
+//! strings, device and connectivity states carry both their display text
+//! and their NetworkManager code:
+//!
+//! ```text
+//! {"event":"connection_state_changed","device":"/org/freedesktop/NetworkManager/Devices/3","interface":"wlan0","state":"Activated","state_code":100,"previous":"IpCheck","previous_code":80,"reason":"none"}
+//! {"event":"connectivity_changed","connectivity":"full","connectivity_code":4}
+//! ```
+//!
+//! Use [`NetworkManager::export_events`](crate::NetworkManager::export_events)
+//! to write to any [`AsyncWrite`](tokio::io::AsyncWrite), or
+//! [`NetworkManager::serve_events`](crate::NetworkManager::serve_events) to
+//! let several clients connect to a Unix socket.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use nmrs::NetworkManager;
+//! use tokio::net::UnixListener;
+//!
+//! # async fn example() -> nmrs::Result<()> {
+//! let nm = NetworkManager::new().await?;
+//! let listener = UnixListener::bind("/run/user/1000/nmrs-events.sock")
+//!     .expect("socket path is free");
+//! // e.g. `socat - UNIX-CONNECT:/run/user/1000/nmrs-events.sock | jq .`
+//! nm.serve_events(listener).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::io;
+
+use futures::{Stream, StreamExt};
+use serde_json::{Value, json};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use crate::api::models::{CheckpointEvent, DaemonEvent, NetworkEvent};
+
+/// Converts `event` to its JSON representation.
+#[must_use]
+pub fn to_json(event: &NetworkEvent) -> Value {
+    match event {
+        NetworkEvent::DeviceAdded { path } => json!({
+            "event": "device_added",
+            "path": path.as_str(),
+        }),
+        NetworkEvent::DeviceRemoved { path } => json!({
+            "event": "device_removed",
+            "path": path.as_str(),
+        }),
+        NetworkEvent::AccessPointAdded { device, path } => json!({
+            "event": "access_point_added",
+            "device": device.as_str(),
+            "path": path.as_str(),
+        }),
+        NetworkEvent::AccessPointRemoved { device, path } => json!({
+            "event": "access_point_removed",
+            "device": device.as_str(),
+            "path": path.as_str(),
+        }),
+        NetworkEvent::ConnectionStateChanged {
+            device,
+            interface,
+            state,
+            previous,
+            reason,
+        } => json!({
+            "event": "connection_state_changed",
+            "device": device.as_str(),
+            "interface": interface,
+            "state": state.to_string(),
+            "state_code": u32::from(state.clone()),
+            "previous": previous.to_string(),
+            "previous_code": u32::from(previous.clone()),
+            "reason": reason.to_string(),
+        }),
+        NetworkEvent::WirelessEnabledChanged(enabled) => json!({
+            "event": "wireless_enabled_changed",
+            "enabled": enabled,
+        }),
+        NetworkEvent::ConnectivityChanged(state) => json!({
+            "event": "connectivity_changed",
+            "connectivity": state.to_string(),
+            "connectivity_code": u32::from(*state),
+        }),
+        NetworkEvent::Daemon(event) => json!({
+            "event": "daemon",
+            "state": match event {
+                DaemonEvent::Stopped => "stopped",
+                DaemonEvent::Restarted => "restarted",
+            },
+        }),
+        NetworkEvent::Checkpoint(CheckpointEvent::Created {
+            path,
+            devices,
+            rollback_timeout,
+        }) => json!({
+            "event": "checkpoint",
+            "change": "created",
+            "path": path,
+            "devices": devices,
+            "rollback_timeout_secs": rollback_timeout.map(|t| t.as_secs()),
+        }),
+        NetworkEvent::Checkpoint(event) => json!({
+            "event": "checkpoint",
+            "change": match event {
+                CheckpointEvent::RolledBack { .. } => "rolled_back",
+                _ => "destroyed",
+            },
+            "path": event.path(),
+        }),
+    }
+}
+
+/// Writes each event of `events` to `writer` as one line of JSON.
+///
+/// Every line is flushed as it is written. Returns when `events` ends, or
+/// with the first write error (typically the reader going away).
+///
+/// # Errors
+///
+/// Returns the I/O error that stopped the export.
+pub async fn write_events<S, W>(mut events: S, mut writer: W) -> io::Result<()>
+where
+    S: Stream<Item = NetworkEvent> + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    while let Some(event) = events.next().await {
+        let mut line = to_json(&event).to_string();
+        line.push('\n');
+        writer.write_all(line.as_bytes()).await?;
+        writer.flush().await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::models::{ConnectivityState, DeviceState, StateReason};
+    use zvariant::OwnedObjectPath;
+
+    fn path(p: &str) -> OwnedObjectPath {
+        OwnedObjectPath::try_from(p).unwrap()
+    }
+
+    #[test]
+    fn state_change_carries_text_and_codes() {
+        let event = NetworkEvent::ConnectionStateChanged {
+            device: path("/org/freedesktop/NetworkManager/Devices/3"),
+            interface: "wlan0".into(),
+            state: DeviceState::Activated,
+            previous: DeviceState::IpCheck,
+            reason: StateReason::from(0),
+        };
+        let v = to_json(&event);
+        assert_eq!(v["event"], "connection_state_changed");
+        assert_eq!(v["interface"], "wlan0");
+        assert_eq!(v["state"], "Activated");
+        assert_eq!(v["state_code"], 100);
+        assert_eq!(v["previous_code"], 80);
+    }
+
+    #[tokio::test]
+    async fn writes_one_line_per_event() {
+        let events = futures::stream::iter([
+            NetworkEvent::WirelessEnabledChanged(false),
+            NetworkEvent::ConnectivityChanged(ConnectivityState::Portal),
+            NetworkEvent::DeviceRemoved {
+                path: path("/org/freedesktop/NetworkManager/Devices/7"),
+            },
+            NetworkEvent::Daemon(DaemonEvent::Restarted),
+        ]);
+        let mut out = Vec::new();
+        write_events(events, &mut out).await.unwrap();
+
+        let lines: Vec<Value> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                json!({"event": "wireless_enabled_changed", "enabled": false}),
+                json!({"event": "connectivity_changed", "connectivity": "portal", "connectivity_code": 2}),
+                json!({"event": "device_removed", "path": "/org/freedesktop/NetworkManager/Devices/7"}),
+                json!({"event": "daemon", "state": "restarted"}),
+            ]
+        );
+    }
+
+    #[test]
+    fn checkpoints_report_their_change() {
+        let created = NetworkEvent::Checkpoint(CheckpointEvent::Created {
+            path: "/org/freedesktop/NetworkManager/Checkpoint/1".into(),
+            devices: vec!["eth0".into()],
+            rollback_timeout: Some(std::time::Duration::from_secs(30)),
+        });
+        assert_eq!(
+            to_json(&created),
+            json!({
+                "event": "checkpoint",
+                "change": "created",
+                "path": "/org/freedesktop/NetworkManager/Checkpoint/1",
+                "devices": ["eth0"],
+                "rollback_timeout_secs": 30,
+            })
+        );
+
+        let rolled_back = NetworkEvent::Checkpoint(CheckpointEvent::RolledBack {
+            path: "/org/freedesktop/NetworkManager/Checkpoint/1".into(),
+        });
+        assert_eq!(
+            to_json(&rolled_back),
+            json!({
+                "event": "checkpoint",
+                "change": "rolled_back",
+                "path": "/org/freedesktop/NetworkManager/Checkpoint/1",
+            })
+        );
+    }
+}
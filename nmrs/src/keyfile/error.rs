@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Errors produced while parsing `.nmconnection` keyfiles or wg-quick configs.
+///
+/// Line numbers are 1-based.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Error, Serialize, Deserialize)]
+pub enum KeyfileError {
+    /// A line is neither a `[group]` header, a `key=value` entry, nor a comment.
+    #[error("line {line}: expected '[group]' or 'key=value'")]
+    InvalidLine {
+        /// Offending line.
+        line: usize,
+    },
+
+    /// A group header is missing its closing `]` or has an empty name.
+    #[error("line {line}: malformed group header")]
+    InvalidGroup {
+        /// Offending line.
+        line: usize,
+    },
+
+    /// A `key=value` entry appears before any `[group]` header.
+    #[error("line {line}: entry outside of any group")]
+    EntryOutsideGroup {
+        /// Offending line.
+        line: usize,
+    },
+
+    /// An entry has an empty key (`=value`).
+    #[error("line {line}: empty key")]
+    EmptyKey {
+        /// Offending line.
+        line: usize,
+    },
+
+    /// A value contains an unknown or truncated backslash escape.
+    #[error("line {line}: invalid escape sequence in value")]
+    InvalidEscape {
+        /// Offending line.
+        line: usize,
+    },
+
+    /// A value could not be interpreted for its key.
+    #[error("invalid value '{value}' for '{key}' in [{group}]")]
+    InvalidValue {
+        /// Group containing the key.
+        group: String,
+        /// Key whose value was rejected.
+        key: String,
+        /// The rejected value.
+        value: String,
+    },
+
+    /// A required group is absent.
+    #[error("missing [{group}] section")]
+    MissingGroup {
+        /// Name of the missing group.
+        group: String,
+    },
+
+    /// A required key is absent from a group.
+    #[error("missing '{key}' in [{group}]")]
+    MissingKey {
+        /// Group that should contain the key.
+        group: String,
+        /// Name of the missing key.
+        key: String,
+    },
+}
@@ -0,0 +1,115 @@
+//! Pure parsers for NetworkManager keyfiles and wg-quick configs.
+//!
+//! Nothing in this module touches the filesystem or D-Bus: every function
+//! takes the file contents as a `&str` and returns a typed result or a
+//! [`KeyfileError`](crate::keyfile::KeyfileError) pointing at the offending line. That makes the parsers
+//! safe to run on untrusted input (they are fuzzed, see `nmrs/fuzz`) and
+//! reusable by tools that never talk to NetworkManager.
+//!
+//! - [`Keyfile`](crate::keyfile::Keyfile) — GLib keyfile as used by `.nmconnection` profiles in
+//!   `/etc/NetworkManager/system-connections/`. Round-trips via
+//!   [`Display`](std::fmt::Display), and converts to and from the settings
+//!   dictionaries NetworkManager uses on D-Bus with
+//!   [`Keyfile::to_settings`](crate::keyfile::Keyfile::to_settings) /
+//!   [`Keyfile::from_settings`](crate::keyfile::Keyfile::from_settings).
+//! - [`WgQuickConfig`](crate::keyfile::WgQuickConfig) — `wg-quick(8)` style `[Interface]` / `[Peer]`
+//!   configs, convertible to a [`WireGuardConfig`](crate::WireGuardConfig).
+//!
+//! # Example
+//!
+//! ```rust
+//! use nmrs::keyfile::Keyfile;
+//!
+//! let kf = Keyfile::parse("[connection]\nid=Home\ntype=wifi\n\n[wifi]\nssid=Home\n")?;
+//! assert_eq!(kf.get("connection", "type"), Some("wifi"));
+//! # Ok::<(), nmrs::keyfile::KeyfileError>(())
+//! ```
+
+mod error;
+mod nmconnection;
+mod settings;
+mod wg_quick;
+
+pub use error::KeyfileError;
+pub use nmconnection::{Keyfile, KeyfileGroup};
+pub use wg_quick::{WgQuickConfig, WgQuickInterface, WgQuickPeer};
+
+/// One meaningful line of an INI-style file.
+#[derive(Debug, PartialEq, Eq)]
+enum Line<'a> {
+    Group(&'a str),
+    Entry { key: &'a str, value: &'a str },
+}
+
+/// Splits INI-style content into group headers and raw `key=value` entries,
+/// skipping blank lines and `#` comments.
+///
+/// Yields `(line_number, line)` with 1-based line numbers. Keys and values
+/// are trimmed; values are otherwise returned verbatim (no unescaping).
+fn lines(input: &str) -> impl Iterator<Item = Result<(usize, Line<'_>), KeyfileError>> {
+    input.lines().enumerate().filter_map(|(idx, raw)| {
+        let line = idx + 1;
+        let text = raw.trim();
+        if text.is_empty() || text.starts_with('#') {
+            return None;
+        }
+        if let Some(rest) = text.strip_prefix('[') {
+            return Some(match rest.strip_suffix(']') {
+                Some(name) if !name.trim().is_empty() => Ok((line, Line::Group(name.trim()))),
+                _ => Err(KeyfileError::InvalidGroup { line }),
+            });
+        }
+        Some(match text.split_once('=') {
+            Some((key, _)) if key.trim().is_empty() => Err(KeyfileError::EmptyKey { line }),
+            Some((key, value)) => Ok((
+                line,
+                Line::Entry {
+                    key: key.trim(),
+                    value: value.trim(),
+                },
+            )),
+            None => Err(KeyfileError::InvalidLine { line }),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_skip_comments_and_blanks() {
+        let got: Vec<_> = lines("# c\n\n[a]\n  k = v=w \n")
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            got,
+            vec![
+                (3, Line::Group("a")),
+                (
+                    4,
+                    Line::Entry {
+                        key: "k",
+                        value: "v=w"
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn lines_report_errors_with_line_numbers() {
+        let errs: Vec<_> = lines("[ok]\n[bad\n=x\nnoequals\n[]\n")
+            .filter_map(Result::err)
+            .collect();
+        assert_eq!(
+            errs,
+            vec![
+                KeyfileError::InvalidGroup { line: 2 },
+                KeyfileError::EmptyKey { line: 3 },
+                KeyfileError::InvalidLine { line: 4 },
+                KeyfileError::InvalidGroup { line: 5 },
+            ]
+        );
+    }
+}
@@ -0,0 +1,286 @@
+use std::fmt;
+
+use super::{KeyfileError, Line, lines};
+
+/// One `[group]` of a keyfile with its entries in file order.
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyfileGroup {
+    /// Group name, e.g. `"connection"` or `"wifi-security"`.
+    pub name: String,
+    /// Unescaped `(key, value)` pairs. Keys are unique within a group.
+    pub entries: Vec<(String, String)>,
+}
+
+impl KeyfileGroup {
+    /// Returns the value of `key`, if present.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn set(&mut self, key: &str, value: String) {
+        match self.entries.iter_mut().find(|(k, _)| k == key) {
+            Some((_, v)) => *v = value,
+            None => self.entries.push((key.to_string(), value)),
+        }
+    }
+}
+
+/// A parsed GLib keyfile, the on-disk format of `.nmconnection` profiles.
+///
+/// Follows GLib semantics: repeated groups are merged and a repeated key
+/// keeps its last value. Values are unescaped (`\s`, `\n`, `\t`, `\r`,
+/// `\\`) on parse and escaped again by the [`Display`](fmt::Display)
+/// implementation, so `Keyfile::parse(&kf.to_string())` yields `kf`.
+///
+/// # Example
+///
+/// ```rust
+/// use nmrs::keyfile::Keyfile;
+///
+/// let mut kf = Keyfile::parse("[connection]\nid=Office\n")?;
+/// kf.set("connection", "autoconnect", "false");
+/// assert_eq!(kf.get_bool("connection", "autoconnect")?, Some(false));
+/// assert!(kf.to_string().contains("autoconnect=false"));
+/// # Ok::<(), nmrs::keyfile::KeyfileError>(())
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Keyfile {
+    /// Groups in first-seen order.
+    pub groups: Vec<KeyfileGroup>,
+}
+
+impl Keyfile {
+    /// Parses keyfile contents.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`KeyfileError`] for malformed headers, entries outside a
+    /// group, empty keys, or invalid escape sequences.
+    pub fn parse(input: &str) -> Result<Self, KeyfileError> {
+        let mut kf = Self::default();
+        let mut current: Option<usize> = None;
+
+        for item in lines(input) {
+            let (line, parsed) = item?;
+            match parsed {
+                Line::Group(name) => current = Some(kf.group_index(name)),
+                Line::Entry { key, value } => {
+                    let idx = current.ok_or(KeyfileError::EntryOutsideGroup { line })?;
+                    let value = unescape(value).ok_or(KeyfileError::InvalidEscape { line })?;
+                    kf.groups[idx].set(key, value);
+                }
+            }
+        }
+        Ok(kf)
+    }
+
+    /// Returns the group named `name`, if present.
+    #[must_use]
+    pub fn group(&self, name: &str) -> Option<&KeyfileGroup> {
+        self.groups.iter().find(|g| g.name == name)
+    }
+
+    /// Returns the value of `key` in `group`, if present.
+    #[must_use]
+    pub fn get(&self, group: &str, key: &str) -> Option<&str> {
+        self.group(group).and_then(|g| g.get(key))
+    }
+
+    /// Returns `key` in `group` parsed as a keyfile boolean (`true`/`false`, `1`/`0`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`KeyfileError::InvalidValue`] if the value is not a boolean.
+    pub fn get_bool(&self, group: &str, key: &str) -> Result<Option<bool>, KeyfileError> {
+        self.get(group, key)
+            .map(|v| match v {
+                "true" | "1" => Ok(true),
+                "false" | "0" => Ok(false),
+                _ => Err(KeyfileError::InvalidValue {
+                    group: group.to_string(),
+                    key: key.to_string(),
+                    value: v.to_string(),
+                }),
+            })
+            .transpose()
+    }
+
+    /// Returns `key` in `group` as a `;`-separated list, dropping empty items.
+    #[must_use]
+    pub fn get_list(&self, group: &str, key: &str) -> Vec<String> {
+        self.get(group, key)
+            .map(|v| {
+                v.split(';')
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Sets `key` in `group`, creating the group if needed.
+    pub fn set(&mut self, group: &str, key: &str, value: impl Into<String>) {
+        let idx = self.group_index(group);
+        self.groups[idx].set(key, value.into());
+    }
+
+    fn group_index(&mut self, name: &str) -> usize {
+        match self.groups.iter().position(|g| g.name == name) {
+            Some(idx) => idx,
+            None => {
+                self.groups.push(KeyfileGroup {
+                    name: name.to_string(),
+                    entries: Vec::new(),
+                });
+                self.groups.len() - 1
+            }
+        }
+    }
+}
+
+impl fmt::Display for Keyfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, group) in self.groups.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            writeln!(f, "[{}]", group.name)?;
+            for (key, value) in &group.entries {
+                writeln!(f, "{key}={}", escape(value))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn unescape(value: &str) -> Option<String> {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        out.push(match chars.next()? {
+            's' => ' ',
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            '\\' => '\\',
+            _ => return None,
+        });
+    }
+    Some(out)
+}
+
+fn escape(value: &str) -> String {
+    let last = value.chars().count().saturating_sub(1);
+    let mut out = String::with_capacity(value.len());
+    for (i, c) in value.chars().enumerate() {
+        match c {
+            ' ' if i == 0 || i == last => out.push_str("\\s"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            '\\' => out.push_str("\\\\"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WIFI: &str = "\
+[connection]
+id=Coffee Shop
+uuid=6ba7b810-9dad-11d1-80b4-00c04fd430c8
+type=wifi
+autoconnect=false
+
+[wifi]
+ssid=Coffee Shop
+mode=infrastructure
+
+[wifi-security]
+key-mgmt=wpa-psk
+psk=p\\sass\\\\word
+
+[ipv4]
+dns=1.1.1.1;9.9.9.9;
+method=auto
+";
+
+    #[test]
+    fn parses_nmconnection() {
+        let kf = Keyfile::parse(WIFI).unwrap();
+        assert_eq!(kf.groups.len(), 4);
+        assert_eq!(kf.get("connection", "id"), Some("Coffee Shop"));
+        assert_eq!(kf.get("wifi-security", "psk"), Some("p ass\\word"));
+        assert_eq!(kf.get_bool("connection", "autoconnect"), Ok(Some(false)));
+        assert_eq!(kf.get_list("ipv4", "dns"), vec!["1.1.1.1", "9.9.9.9"]);
+        assert_eq!(kf.get("wifi", "missing"), None);
+    }
+
+    #[test]
+    fn round_trips_through_display() {
+        let kf = Keyfile::parse(WIFI).unwrap();
+        assert_eq!(Keyfile::parse(&kf.to_string()).unwrap(), kf);
+
+        let mut kf = Keyfile::default();
+        kf.set("a", "lead", " x\ty ");
+        assert_eq!(Keyfile::parse(&kf.to_string()).unwrap(), kf);
+    }
+
+    #[test]
+    fn merges_repeated_groups_and_keys() {
+        let kf = Keyfile::parse("[a]\nk=1\n[b]\n[a]\nk=2\nj=3\n").unwrap();
+        assert_eq!(kf.groups.len(), 2);
+        assert_eq!(kf.get("a", "k"), Some("2"));
+        assert_eq!(kf.get("a", "j"), Some("3"));
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        assert_eq!(
+            Keyfile::parse("k=v\n"),
+            Err(KeyfileError::EntryOutsideGroup { line: 1 })
+        );
+        assert_eq!(
+            Keyfile::parse("[a]\nk=bad\\q\n"),
+            Err(KeyfileError::InvalidEscape { line: 2 })
+        );
+        assert_eq!(
+            Keyfile::parse("[a]\nk=trailing\\\n"),
+            Err(KeyfileError::InvalidEscape { line: 2 })
+        );
+        assert!(matches!(
+            Keyfile::parse("[a]\nb=maybe\n").unwrap().get_bool("a", "b"),
+            Err(KeyfileError::InvalidValue { .. })
+        ));
+    }
+
+    #[test]
+    fn never_panics_on_garbage() {
+        for input in [
+            "[",
+            "]",
+            "=",
+            "[[]]",
+            "\\",
+            "[a]\n=\n",
+            "[a]\nk=\\",
+            "\u{feff}[a]",
+        ] {
+            let _ = Keyfile::parse(input);
+        }
+    }
+}
@@ -0,0 +1,931 @@
+//! Conversion between [`Keyfile`]s and NetworkManager settings dictionaries.
+//!
+//! Keyfiles spell a few settings differently from D-Bus (`[wifi]` for
+//! `802-11-wireless`, `address1=` for `address-data`, one `[vpn]` key per
+//! data item, one `[wireguard-peer.<key>]` group per peer); this module
+//! translates between the two. Keys are typed from a table of known
+//! properties; unknown keys are passed through as strings.
+
+use std::collections::{BTreeMap, HashMap};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use zvariant::{OwnedValue, Str, Value};
+
+use super::{Keyfile, KeyfileError};
+use crate::backend::ProfileSettings;
+
+/// `(keyfile name, D-Bus name)` of settings, also used for `connection.type`.
+const SETTING_ALIASES: &[(&str, &str)] = &[
+    ("wifi", "802-11-wireless"),
+    ("wifi-security", "802-11-wireless-security"),
+    ("ethernet", "802-3-ethernet"),
+];
+
+const WG_PEER_PREFIX: &str = "wireguard-peer.";
+
+const BOOL_KEYS: &[&str] = &[
+    "auto-config",
+    "auto-negotiate",
+    "autoconnect",
+    "dhcp-send-hostname",
+    "hairpin-mode",
+    "hidden",
+    "home-only",
+    "ignore-auto-dns",
+    "ignore-auto-routes",
+    "may-fail",
+    "multicast-snooping",
+    "never-default",
+    "peer-routes",
+    "persistent",
+    "read-only",
+    "stp",
+    "system-ca-certs",
+    "vlan-filtering",
+];
+
+const I32_KEYS: &[&str] = &[
+    "ap-isolation",
+    "auth-retries",
+    "autoconnect-ports",
+    "autoconnect-priority",
+    "autoconnect-retries",
+    "autoconnect-slaves",
+    "dad-timeout",
+    "dhcp-timeout",
+    "dns-priority",
+    "fils",
+    "ip4-auto-default-route",
+    "ip6-auto-default-route",
+    "ip6-privacy",
+    "llmnr",
+    "lldp",
+    "mdns",
+    "metered",
+    "multi-connect",
+    "pmf",
+    "ra-timeout",
+    "required-timeout",
+    "wait-device-timeout",
+];
+
+const U32_KEYS: &[&str] = &[
+    "ageing-time",
+    "channel",
+    "forward-delay",
+    "fwmark",
+    "gateway-ping-timeout",
+    "hello-time",
+    "listen-port",
+    "max-age",
+    "mtu",
+    "path-cost",
+    "persistent-keepalive",
+    "powersave",
+    "priority",
+    "route-table",
+    "speed",
+    "timeout",
+    "wake-on-lan",
+    "wake-on-wlan",
+    "wep-key-type",
+    "wep-tx-keyidx",
+    "wps-method",
+];
+
+const LIST_KEYS: &[&str] = &[
+    "allowed-ips",
+    "altsubject-matches",
+    "dns-options",
+    "dns-search",
+    "eap",
+    "egress-priority-map",
+    "group",
+    "ingress-priority-map",
+    "pairwise",
+    "permissions",
+    "proto",
+    "secondaries",
+];
+
+const CERT_KEYS: &[&str] = &[
+    "ca-cert",
+    "client-cert",
+    "phase2-ca-cert",
+    "phase2-client-cert",
+    "phase2-private-key",
+    "private-key",
+];
+
+/// `ipv6.addr-gen-mode` nicks as written by NetworkManager.
+const ADDR_GEN_MODES: &[(&str, i32)] = &[
+    ("eui64", 0),
+    ("stable-privacy", 1),
+    ("default-or-eui64", 2),
+    ("default", 3),
+];
+
+/// How a keyfile value is encoded on D-Bus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Bool,
+    I32,
+    I64,
+    U32,
+    U64,
+    Ssid,
+    Mac,
+    Cert,
+    List,
+    Str,
+}
+
+fn kind(setting: &str, key: &str) -> Kind {
+    match (setting, key) {
+        ("802-11-wireless", "ssid") => Kind::Ssid,
+        ("802-1x", k) if CERT_KEYS.contains(&k) => Kind::Cert,
+        (_, "mac-address" | "bssid" | "bdaddr") => Kind::Mac,
+        ("vlan", "id" | "flags") => Kind::U32,
+        ("connection", "timestamp") => Kind::U64,
+        (_, "route-metric") => Kind::I64,
+        (_, k) if k.ends_with("-flags") => Kind::U32,
+        (_, k) if BOOL_KEYS.contains(&k) => Kind::Bool,
+        (_, k) if I32_KEYS.contains(&k) => Kind::I32,
+        (_, k) if U32_KEYS.contains(&k) => Kind::U32,
+        (_, k) if LIST_KEYS.contains(&k) => Kind::List,
+        _ => Kind::Str,
+    }
+}
+
+fn to_dbus_name(name: &str) -> &str {
+    SETTING_ALIASES
+        .iter()
+        .find(|(kf, _)| *kf == name)
+        .map_or(name, |(_, dbus)| dbus)
+}
+
+fn to_keyfile_name(name: &str) -> &str {
+    SETTING_ALIASES
+        .iter()
+        .find(|(_, dbus)| *dbus == name)
+        .map_or(name, |(kf, _)| kf)
+}
+
+fn owned(value: Value<'_>) -> OwnedValue {
+    // Only values carrying file descriptors can fail to convert.
+    OwnedValue::try_from(value).expect("settings values hold no file descriptors")
+}
+
+fn str_value(s: &str) -> OwnedValue {
+    OwnedValue::from(Str::from(s.to_string()))
+}
+
+/// Unwraps a `v` variant as found inside `a{sv}` dictionaries.
+fn inner<'a>(value: &'a Value<'a>) -> &'a Value<'a> {
+    match value {
+        Value::Value(v) => inner(v),
+        v => v,
+    }
+}
+
+impl Keyfile {
+    /// Converts the keyfile into the settings dictionary NetworkManager's
+    /// `AddConnection` expects.
+    ///
+    /// Group and `connection.type` aliases are resolved (`wifi` becomes
+    /// `802-11-wireless`), `addressN` / `routeN` become `address-data` /
+    /// `route-data`, and `[vpn]`, `[vpn-secrets]`, `[bond]` and
+    /// `[wireguard-peer.*]` groups are folded into their dictionaries.
+    /// `routeN_options` entries are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`KeyfileError::MissingGroup`] without a `[connection]` group
+    /// and [`KeyfileError::InvalidValue`] for values that do not match the
+    /// type of their key.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nmrs::keyfile::Keyfile;
+    ///
+    /// let kf = Keyfile::parse("[connection]\nid=Home\ntype=wifi\n\n[wifi]\nssid=Home\n")?;
+    /// let settings = kf.to_settings()?;
+    /// assert!(settings.contains_key("802-11-wireless"));
+    /// # Ok::<(), nmrs::keyfile::KeyfileError>(())
+    /// ```
+    pub fn to_settings(&self) -> Result<ProfileSettings, KeyfileError> {
+        if self.group("connection").is_none() {
+            return Err(KeyfileError::MissingGroup {
+                group: "connection".to_string(),
+            });
+        }
+
+        let mut settings = ProfileSettings::new();
+        let mut peers = Vec::new();
+        for group in &self.groups {
+            let invalid = |key: &str, value: &str| KeyfileError::InvalidValue {
+                group: group.name.clone(),
+                key: key.to_string(),
+                value: value.to_string(),
+            };
+
+            if let Some(public_key) = group.name.strip_prefix(WG_PEER_PREFIX) {
+                let mut peer: HashMap<String, Value<'static>> = HashMap::from([(
+                    "public-key".to_string(),
+                    Value::from(public_key.to_string()),
+                )]);
+                for (key, value) in &group.entries {
+                    let v = typed_value(kind("wireguard", key), value)
+                        .ok_or_else(|| invalid(key, value))?;
+                    peer.insert(key.clone(), v);
+                }
+                peers.push(peer);
+                continue;
+            }
+
+            match group.name.as_str() {
+                "vpn" => {
+                    let section = settings.entry("vpn".to_string()).or_default();
+                    let mut data = HashMap::new();
+                    for (key, value) in &group.entries {
+                        match key.as_str() {
+                            "service-type" | "user-name" => {
+                                section.insert(key.clone(), str_value(value));
+                            }
+                            "persistent" | "timeout" => {
+                                let v = typed_value(kind("vpn", key), value)
+                                    .ok_or_else(|| invalid(key, value))?;
+                                section.insert(key.clone(), owned(v));
+                            }
+                            _ => {
+                                data.insert(key.clone(), value.clone());
+                            }
+                        }
+                    }
+                    section.insert("data".to_string(), owned(Value::from(data)));
+                }
+                "vpn-secrets" => {
+                    let secrets: HashMap<String, String> = group.entries.iter().cloned().collect();
+                    settings
+                        .entry("vpn".to_string())
+                        .or_default()
+                        .insert("secrets".to_string(), owned(Value::from(secrets)));
+                }
+                "bond" => {
+                    let options: HashMap<String, String> = group.entries.iter().cloned().collect();
+                    settings
+                        .entry("bond".to_string())
+                        .or_default()
+                        .insert("options".to_string(), owned(Value::from(options)));
+                }
+                "ipv4" | "ipv6" => {
+                    let section = ip_section(&group.name, &group.entries)
+                        .map_err(|(key, value)| invalid(&key, &value))?;
+                    settings
+                        .entry(group.name.clone())
+                        .or_default()
+                        .extend(section);
+                }
+                name => {
+                    let setting = to_dbus_name(name);
+                    let section = settings.entry(setting.to_string()).or_default();
+                    for (key, value) in &group.entries {
+                        if setting == "connection" && key == "type" {
+                            section.insert(key.clone(), str_value(to_dbus_name(value)));
+                            continue;
+                        }
+                        if key == "cloned-mac-address" && parse_mac(value).is_none() {
+                            // Special values ("random", "stable", ...) only
+                            // exist in the string form of the property.
+                            section.insert("assigned-mac-address".to_string(), str_value(value));
+                            continue;
+                        }
+                        let v = typed_value(kind(setting, key), value)
+                            .ok_or_else(|| invalid(key, value))?;
+                        section.insert(key.clone(), owned(v));
+                    }
+                }
+            }
+        }
+
+        if !peers.is_empty() {
+            settings
+                .entry("wireguard".to_string())
+                .or_default()
+                .insert("peers".to_string(), owned(Value::from(peers)));
+        }
+        Ok(settings)
+    }
+
+    /// Builds a keyfile from a `GetSettings` dictionary, the inverse of
+    /// [`to_settings`](Self::to_settings).
+    ///
+    /// The `[connection]` group comes first, the rest in name order.
+    /// Deprecated duplicates (`ipv4.addresses`, `ipv4.routes`, ...) and
+    /// values without a keyfile representation, such as inline certificate
+    /// blobs, are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nmrs::keyfile::Keyfile;
+    ///
+    /// let kf = Keyfile::parse("[connection]\nid=Office\ntype=ethernet\n")?;
+    /// let back = Keyfile::from_settings(&kf.to_settings()?);
+    /// assert_eq!(back.get("connection", "type"), Some("ethernet"));
+    /// # Ok::<(), nmrs::keyfile::KeyfileError>(())
+    /// ```
+    #[must_use]
+    pub fn from_settings(settings: &ProfileSettings) -> Self {
+        let mut names: Vec<&String> = settings.keys().collect();
+        names.sort_by_key(|name| (name.as_str() != "connection", name.as_str()));
+
+        let mut kf = Self::default();
+        for name in names {
+            let section: BTreeMap<&String, &OwnedValue> = settings[name].iter().collect();
+            let group = to_keyfile_name(name);
+            for (key, value) in section {
+                let value = inner(value);
+                match (name.as_str(), key.as_str()) {
+                    ("connection", "type") => {
+                        if let Value::Str(ty) = value {
+                            kf.set(group, key, to_keyfile_name(ty.as_str()));
+                        }
+                    }
+                    ("vpn", "data") => set_dict(&mut kf, "vpn", value),
+                    ("vpn", "secrets") => set_dict(&mut kf, "vpn-secrets", value),
+                    ("bond", "options") => set_dict(&mut kf, "bond", value),
+                    ("wireguard", "peers") => set_peers(&mut kf, value),
+                    ("ipv4" | "ipv6", "addresses" | "routes") => {}
+                    ("ipv4" | "ipv6", "address-data") => {
+                        for (i, entry) in dicts(value).iter().enumerate() {
+                            if let Some(text) = format_address(entry) {
+                                kf.set(group, &format!("address{}", i + 1), text);
+                            }
+                        }
+                    }
+                    ("ipv4" | "ipv6", "route-data") => {
+                        for (i, entry) in dicts(value).iter().enumerate() {
+                            if let Some(text) = format_route(entry) {
+                                kf.set(group, &format!("route{}", i + 1), text);
+                            }
+                        }
+                    }
+                    ("ipv4" | "ipv6", "dns") => {
+                        if let Some(text) = format_dns(value) {
+                            kf.set(group, key, text);
+                        }
+                    }
+                    ("ipv6", "addr-gen-mode") => {
+                        if let Value::I32(mode) = value {
+                            let nick = ADDR_GEN_MODES.iter().find(|(_, m)| m == mode);
+                            kf.set(
+                                group,
+                                key,
+                                nick.map_or(mode.to_string(), |(n, _)| n.to_string()),
+                            );
+                        }
+                    }
+                    (_, "cloned-mac-address")
+                        if settings[name].contains_key("assigned-mac-address") => {}
+                    (_, "assigned-mac-address") => {
+                        if let Value::Str(s) = value {
+                            kf.set(group, "cloned-mac-address", s.as_str());
+                        }
+                    }
+                    _ => {
+                        if let Some(text) = format_value(kind(name, key), value) {
+                            kf.set(group, key, text);
+                        }
+                    }
+                }
+            }
+        }
+        kf
+    }
+}
+
+/// Parses one scalar keyfile value as `kind`.
+fn typed_value(kind: Kind, value: &str) -> Option<Value<'static>> {
+    Some(match kind {
+        Kind::Bool => Value::from(match value {
+            "true" | "1" => true,
+            "false" | "0" => false,
+            _ => return None,
+        }),
+        Kind::I32 => Value::from(value.parse::<i32>().ok()?),
+        Kind::I64 => Value::from(value.parse::<i64>().ok()?),
+        Kind::U32 => Value::from(value.parse::<u32>().ok()?),
+        Kind::U64 => Value::from(value.parse::<u64>().ok()?),
+        Kind::Ssid => {
+            Value::from(parse_byte_list(value).unwrap_or_else(|| value.as_bytes().to_vec()))
+        }
+        Kind::Mac => Value::from(parse_mac(value)?),
+        Kind::Cert => {
+            let path = value.strip_prefix("file://").unwrap_or(value);
+            Value::from(format!("file://{path}\0").into_bytes())
+        }
+        Kind::List => Value::from(split_list(value)),
+        Kind::Str => Value::from(value.to_string()),
+    })
+}
+
+/// Formats one D-Bus value as keyfile text, or `None` if it has no keyfile form.
+fn format_value(kind: Kind, value: &Value<'_>) -> Option<String> {
+    let bytes = || -> Option<Vec<u8>> { Vec::<u8>::try_from(value.try_clone().ok()?).ok() };
+    match kind {
+        Kind::Ssid => {
+            let bytes = bytes()?;
+            Some(match std::str::from_utf8(&bytes) {
+                Ok(s) if !s.contains(';') && !s.chars().any(char::is_control) => s.to_string(),
+                _ => join_list(bytes.iter().map(u8::to_string)),
+            })
+        }
+        Kind::Mac => {
+            let bytes = bytes()?;
+            Some(
+                bytes
+                    .iter()
+                    .map(|b| format!("{b:02X}"))
+                    .collect::<Vec<_>>()
+                    .join(":"),
+            )
+        }
+        Kind::Cert => {
+            let bytes = bytes()?;
+            let text = std::str::from_utf8(&bytes).ok()?.trim_end_matches('\0');
+            text.strip_prefix("file://").map(str::to_string)
+        }
+        _ => match value {
+            Value::Array(items) => {
+                let items: Option<Vec<String>> = items.iter().map(|v| scalar(inner(v))).collect();
+                Some(join_list(items?.into_iter()))
+            }
+            v => scalar(v),
+        },
+    }
+}
+
+fn scalar(value: &Value<'_>) -> Option<String> {
+    Some(match value {
+        Value::Bool(b) => b.to_string(),
+        Value::U8(n) => n.to_string(),
+        Value::I16(n) => n.to_string(),
+        Value::U16(n) => n.to_string(),
+        Value::I32(n) => n.to_string(),
+        Value::U32(n) => n.to_string(),
+        Value::I64(n) => n.to_string(),
+        Value::U64(n) => n.to_string(),
+        Value::F64(n) => n.to_string(),
+        Value::Str(s) => s.to_string(),
+        Value::ObjectPath(p) => p.to_string(),
+        _ => return None,
+    })
+}
+
+/// Converts an `[ipv4]` / `[ipv6]` group; errors carry the offending entry.
+fn ip_section(
+    family: &str,
+    entries: &[(String, String)],
+) -> Result<HashMap<String, OwnedValue>, (String, String)> {
+    let mut section = HashMap::new();
+    let mut addresses = BTreeMap::new();
+    let mut routes = BTreeMap::new();
+    let mut gateway = None;
+
+    for (key, value) in entries {
+        let invalid = || (key.clone(), value.clone());
+        if let Some(n) = indexed(key, "address").or_else(|| indexed(key, "addresses")) {
+            let (entry, gw) = parse_address(value).ok_or_else(invalid)?;
+            gateway = gateway.or(gw);
+            addresses.insert(n, entry);
+        } else if let Some(n) = indexed(key, "route").or_else(|| indexed(key, "routes")) {
+            routes.insert(n, parse_route(value).ok_or_else(invalid)?);
+        } else if key.starts_with("route") && key.ends_with("_options") {
+            continue;
+        } else if key == "dns" {
+            let servers = split_list(value);
+            let v = if family == "ipv4" {
+                let addrs: Option<Vec<u32>> = servers
+                    .iter()
+                    .map(|s| {
+                        // `ipv4.dns` holds in_addr_t values, i.e. network byte order.
+                        let ip: Ipv4Addr = s.parse().ok()?;
+                        Some(u32::from_ne_bytes(ip.octets()))
+                    })
+                    .collect();
+                Value::from(addrs.ok_or_else(invalid)?)
+            } else {
+                let addrs: Option<Vec<Vec<u8>>> = servers
+                    .iter()
+                    .map(|s| Some(s.parse::<Ipv6Addr>().ok()?.octets().to_vec()))
+                    .collect();
+                Value::from(addrs.ok_or_else(invalid)?)
+            };
+            section.insert(key.clone(), owned(v));
+        } else if key == "addr-gen-mode" {
+            let mode = ADDR_GEN_MODES
+                .iter()
+                .find(|(nick, _)| nick == value)
+                .map(|(_, m)| *m)
+                .or_else(|| value.parse().ok())
+                .ok_or_else(invalid)?;
+            section.insert(key.clone(), OwnedValue::from(mode));
+        } else {
+            let v = typed_value(kind(family, key), value).ok_or_else(invalid)?;
+            section.insert(key.clone(), owned(v));
+        }
+    }
+
+    if !addresses.is_empty() {
+        let data: Vec<_> = addresses.into_values().collect();
+        section.insert("address-data".to_string(), owned(Value::from(data)));
+    }
+    if !routes.is_empty() {
+        let data: Vec<_> = routes.into_values().collect();
+        section.insert("route-data".to_string(), owned(Value::from(data)));
+    }
+    if let Some(gw) = gateway {
+        section
+            .entry("gateway".to_string())
+            .or_insert_with(|| str_value(&gw));
+    }
+    Ok(section)
+}
+
+/// Index `N` of a `{prefix}N` key.
+fn indexed(key: &str, prefix: &str) -> Option<u32> {
+    key.strip_prefix(prefix)?.parse().ok()
+}
+
+/// Parses `ADDR/PREFIX[,GATEWAY]`.
+fn parse_address(value: &str) -> Option<(HashMap<String, Value<'static>>, Option<String>)> {
+    let mut parts = value.split(',');
+    let (address, prefix) = parse_cidr(parts.next()?)?;
+    let gateway = parts.next().filter(|gw| !gw.is_empty());
+    if gateway.is_some_and(|gw| gw.parse::<IpAddr>().is_err()) {
+        return None;
+    }
+    let entry = HashMap::from([
+        ("address".to_string(), Value::from(address)),
+        ("prefix".to_string(), Value::from(prefix)),
+    ]);
+    Some((entry, gateway.map(str::to_string)))
+}
+
+/// Parses `DEST/PREFIX[,NEXT_HOP[,METRIC]]`.
+fn parse_route(value: &str) -> Option<HashMap<String, Value<'static>>> {
+    let mut parts = value.split(',');
+    let (dest, prefix) = parse_cidr(parts.next()?)?;
+    let mut entry = HashMap::from([
+        ("dest".to_string(), Value::from(dest)),
+        ("prefix".to_string(), Value::from(prefix)),
+    ]);
+    if let Some(hop) = parts.next().filter(|h| !h.is_empty()) {
+        hop.parse::<IpAddr>().ok()?;
+        entry.insert("next-hop".to_string(), Value::from(hop.to_string()));
+    }
+    if let Some(metric) = parts.next().filter(|m| !m.is_empty()) {
+        entry.insert(
+            "metric".to_string(),
+            Value::from(metric.parse::<u32>().ok()?),
+        );
+    }
+    Some(entry)
+}
+
+fn parse_cidr(text: &str) -> Option<(String, u32)> {
+    let (addr, prefix) = text.split_once('/')?;
+    let ip: IpAddr = addr.parse().ok()?;
+    let prefix: u32 = prefix.parse().ok()?;
+    let max = if ip.is_ipv4() { 32 } else { 128 };
+    (prefix <= max).then(|| (ip.to_string(), prefix))
+}
+
+fn parse_mac(value: &str) -> Option<Vec<u8>> {
+    let bytes: Option<Vec<u8>> = value
+        .split(':')
+        .map(|b| {
+            (b.len() == 2)
+                .then(|| u8::from_str_radix(b, 16).ok())
+                .flatten()
+        })
+        .collect();
+    bytes.filter(|b| b.len() == 6)
+}
+
+/// Parses the `1;2;3;` byte-list form keyfiles use for non-printable SSIDs.
+fn parse_byte_list(value: &str) -> Option<Vec<u8>> {
+    if !value.contains(';') {
+        return None;
+    }
+    split_list(value).iter().map(|b| b.parse().ok()).collect()
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(';')
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn join_list(items: impl Iterator<Item = String>) -> String {
+    items.map(|item| item + ";").collect()
+}
+
+/// Entries of an `aa{sv}` value.
+fn dicts<'a>(value: &'a Value<'a>) -> Vec<HashMap<String, &'a Value<'a>>> {
+    let Value::Array(items) = value else {
+        return Vec::new();
+    };
+    items
+        .iter()
+        .filter_map(|item| match inner(item) {
+            Value::Dict(dict) => Some(
+                dict.iter()
+                    .filter_map(|(k, v)| match k {
+                        Value::Str(k) => Some((k.to_string(), inner(v))),
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            _ => None,
+        })
+        .collect()
+}
+
+fn dict_scalar(entry: &HashMap<String, &Value<'_>>, key: &str) -> Option<String> {
+    entry.get(key).and_then(|v| scalar(v))
+}
+
+fn format_address(entry: &HashMap<String, &Value<'_>>) -> Option<String> {
+    Some(format!(
+        "{}/{}",
+        dict_scalar(entry, "address")?,
+        dict_scalar(entry, "prefix")?
+    ))
+}
+
+fn format_route(entry: &HashMap<String, &Value<'_>>) -> Option<String> {
+    let mut text = format!(
+        "{}/{}",
+        dict_scalar(entry, "dest")?,
+        dict_scalar(entry, "prefix")?
+    );
+    let hop = dict_scalar(entry, "next-hop");
+    let metric = dict_scalar(entry, "metric");
+    if hop.is_some() || metric.is_some() {
+        text.push(',');
+        text.push_str(hop.as_deref().unwrap_or_default());
+    }
+    if let Some(metric) = metric {
+        text.push(',');
+        text.push_str(&metric);
+    }
+    Some(text)
+}
+
+fn format_dns(value: &Value<'_>) -> Option<String> {
+    let Value::Array(items) = value else {
+        return None;
+    };
+    let servers: Option<Vec<String>> = items
+        .iter()
+        .map(|item| match inner(item) {
+            Value::U32(n) => Some(Ipv4Addr::from(n.to_ne_bytes()).to_string()),
+            v @ Value::Array(_) => {
+                let octets: [u8; 16] = Vec::<u8>::try_from(v.try_clone().ok()?)
+                    .ok()?
+                    .try_into()
+                    .ok()?;
+                Some(Ipv6Addr::from(octets).to_string())
+            }
+            Value::Str(s) => Some(s.to_string()),
+            _ => None,
+        })
+        .collect();
+    Some(join_list(servers?.into_iter()))
+}
+
+/// Writes the entries of an `a{ss}` value into `group`.
+fn set_dict(kf: &mut Keyfile, group: &str, value: &Value<'_>) {
+    let Value::Dict(dict) = value else {
+        return;
+    };
+    let entries: BTreeMap<String, String> = dict
+        .iter()
+        .filter_map(|(k, v)| Some((scalar(inner(k))?, scalar(inner(v))?)))
+        .collect();
+    for (key, value) in entries {
+        kf.set(group, &key, value);
+    }
+}
+
+/// Writes `wireguard.peers` as one `[wireguard-peer.<public-key>]` group each.
+fn set_peers(kf: &mut Keyfile, value: &Value<'_>) {
+    for peer in dicts(value) {
+        let Some(public_key) = dict_scalar(&peer, "public-key") else {
+            continue;
+        };
+        let group = format!("{WG_PEER_PREFIX}{public_key}");
+        let entries: BTreeMap<&String, &&Value<'_>> =
+            peer.iter().filter(|(k, _)| *k != "public-key").collect();
+        for (key, value) in entries {
+            if let Some(text) = format_value(kind("wireguard", key), value) {
+                kf.set(&group, key, text);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(input: &str) -> Keyfile {
+        let kf = Keyfile::parse(input).unwrap();
+        Keyfile::from_settings(&kf.to_settings().unwrap())
+    }
+
+    fn get<T: TryFrom<OwnedValue>>(settings: &ProfileSettings, setting: &str, key: &str) -> T {
+        T::try_from(settings[setting][key].clone()).ok().unwrap()
+    }
+
+    const WIFI: &str = "\
+[connection]
+id=Home
+uuid=6c7b5a6e-3a55-4c2e-9e59-2f7c4f1f0c11
+type=wifi
+autoconnect-priority=10
+
+[wifi]
+mode=infrastructure
+ssid=Home Net
+mac-address=AA:BB:CC:DD:EE:FF
+cloned-mac-address=stable
+
+[wifi-security]
+key-mgmt=wpa-psk
+psk-flags=1
+
+[ipv4]
+method=manual
+address1=192.168.1.10/24,192.168.1.1
+route1=10.0.0.0/8,192.168.1.254,100
+dns=1.1.1.1;9.9.9.9;
+dns-search=home.arpa;
+route-metric=50
+
+[ipv6]
+method=auto
+addr-gen-mode=stable-privacy
+dns=2606:4700:4700::1111;
+";
+
+    #[test]
+    fn wifi_profile_converts_to_dbus_types() {
+        let kf = Keyfile::parse(WIFI).unwrap();
+        let settings = kf.to_settings().unwrap();
+
+        assert_eq!(
+            get::<String>(&settings, "connection", "type"),
+            "802-11-wireless"
+        );
+        assert_eq!(
+            get::<i32>(&settings, "connection", "autoconnect-priority"),
+            10
+        );
+        assert_eq!(
+            get::<Vec<u8>>(&settings, "802-11-wireless", "ssid"),
+            b"Home Net"
+        );
+        assert_eq!(
+            get::<Vec<u8>>(&settings, "802-11-wireless", "mac-address"),
+            [0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF]
+        );
+        assert_eq!(
+            get::<String>(&settings, "802-11-wireless", "assigned-mac-address"),
+            "stable"
+        );
+        assert_eq!(
+            get::<u32>(&settings, "802-11-wireless-security", "psk-flags"),
+            1
+        );
+        assert_eq!(get::<String>(&settings, "ipv4", "gateway"), "192.168.1.1");
+        assert_eq!(get::<i64>(&settings, "ipv4", "route-metric"), 50);
+        assert_eq!(
+            get::<Vec<u32>>(&settings, "ipv4", "dns"),
+            [
+                u32::from_ne_bytes([1, 1, 1, 1]),
+                u32::from_ne_bytes([9, 9, 9, 9])
+            ]
+        );
+        assert_eq!(get::<i32>(&settings, "ipv6", "addr-gen-mode"), 1);
+    }
+
+    #[test]
+    fn wifi_profile_round_trips() {
+        let back = round_trip(WIFI);
+        assert_eq!(back, Keyfile::parse(&back.to_string()).unwrap());
+        assert_eq!(back.groups[0].name, "connection");
+        assert_eq!(back.get("connection", "type"), Some("wifi"));
+        assert_eq!(back.get("wifi", "ssid"), Some("Home Net"));
+        assert_eq!(back.get("wifi", "mac-address"), Some("AA:BB:CC:DD:EE:FF"));
+        assert_eq!(back.get("wifi", "cloned-mac-address"), Some("stable"));
+        assert_eq!(back.get("wifi-security", "psk-flags"), Some("1"));
+        assert_eq!(back.get("ipv4", "address1"), Some("192.168.1.10/24"));
+        assert_eq!(back.get("ipv4", "gateway"), Some("192.168.1.1"));
+        assert_eq!(
+            back.get("ipv4", "route1"),
+            Some("10.0.0.0/8,192.168.1.254,100")
+        );
+        assert_eq!(back.get("ipv4", "dns"), Some("1.1.1.1;9.9.9.9;"));
+        assert_eq!(back.get("ipv4", "dns-search"), Some("home.arpa;"));
+        assert_eq!(back.get("ipv6", "addr-gen-mode"), Some("stable-privacy"));
+        assert_eq!(back.get("ipv6", "dns"), Some("2606:4700:4700::1111;"));
+        assert_eq!(round_trip(&back.to_string()), back);
+    }
+
+    #[test]
+    fn vpn_data_and_secrets_round_trip() {
+        let back = round_trip(
+            "[connection]\nid=Corp\ntype=vpn\n\n\
+             [vpn]\nservice-type=org.freedesktop.NetworkManager.openvpn\n\
+             remote=vpn.example.com\nconnection-type=password\n\n\
+             [vpn-secrets]\npassword=hunter2\n",
+        );
+        assert_eq!(
+            back.get("vpn", "service-type"),
+            Some("org.freedesktop.NetworkManager.openvpn")
+        );
+        assert_eq!(back.get("vpn", "remote"), Some("vpn.example.com"));
+        assert_eq!(back.get("vpn-secrets", "password"), Some("hunter2"));
+    }
+
+    #[test]
+    fn wireguard_peers_round_trip() {
+        let input = "[connection]\nid=wg0\ntype=wireguard\n\n\
+                     [wireguard]\nlisten-port=51820\n\n\
+                     [wireguard-peer.cGVlcg==]\nendpoint=203.0.113.1:51820\n\
+                     allowed-ips=0.0.0.0/0;::/0;\npersistent-keepalive=25\n";
+        let settings = Keyfile::parse(input).unwrap().to_settings().unwrap();
+        assert_eq!(get::<u32>(&settings, "wireguard", "listen-port"), 51820);
+
+        let back = Keyfile::from_settings(&settings);
+        let peer = "wireguard-peer.cGVlcg==";
+        assert_eq!(back.get(peer, "endpoint"), Some("203.0.113.1:51820"));
+        assert_eq!(back.get(peer, "allowed-ips"), Some("0.0.0.0/0;::/0;"));
+        assert_eq!(back.get(peer, "persistent-keepalive"), Some("25"));
+    }
+
+    #[test]
+    fn eap_certificates_use_file_uris() {
+        let settings = Keyfile::parse(
+            "[connection]\nid=Corp\ntype=wifi\n\n[802-1x]\neap=tls;\nca-cert=/etc/ssl/ca.pem\n",
+        )
+        .unwrap()
+        .to_settings()
+        .unwrap();
+        assert_eq!(
+            get::<Vec<u8>>(&settings, "802-1x", "ca-cert"),
+            b"file:///etc/ssl/ca.pem\0"
+        );
+        let back = Keyfile::from_settings(&settings);
+        assert_eq!(back.get("802-1x", "ca-cert"), Some("/etc/ssl/ca.pem"));
+        assert_eq!(back.get("802-1x", "eap"), Some("tls;"));
+    }
+
+    #[test]
+    fn non_printable_ssid_uses_byte_list() {
+        let back = round_trip("[connection]\nid=x\ntype=wifi\n\n[wifi]\nssid=0;255;1;\n");
+        assert_eq!(back.get("wifi", "ssid"), Some("0;255;1;"));
+    }
+
+    #[test]
+    fn rejects_mistyped_values() {
+        let kf = Keyfile::parse("[connection]\nid=x\nautoconnect=maybe\n").unwrap();
+        assert!(matches!(
+            kf.to_settings(),
+            Err(KeyfileError::InvalidValue { key, .. }) if key == "autoconnect"
+        ));
+
+        let kf = Keyfile::parse("[connection]\nid=x\n\n[ipv4]\naddress1=10.0.0.1/40\n").unwrap();
+        assert!(matches!(
+            kf.to_settings(),
+            Err(KeyfileError::InvalidValue { key, .. }) if key == "address1"
+        ));
+    }
+
+    #[test]
+    fn requires_connection_group() {
+        let kf = Keyfile::parse("[wifi]\nssid=x\n").unwrap();
+        assert_eq!(
+            kf.to_settings(),
+            Err(KeyfileError::MissingGroup {
+                group: "connection".to_string()
+            })
+        );
+    }
+}
@@ -0,0 +1,360 @@
+use std::str::FromStr;
+
+use super::{KeyfileError, Line, lines};
+use crate::api::models::{WireGuardConfig, WireGuardPeer};
+
+const INTERFACE: &str = "Interface";
+const PEER: &str = "Peer";
+
+/// The `[Interface]` section of a wg-quick config.
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WgQuickInterface {
+    /// Base64 private key (`PrivateKey`).
+    pub private_key: String,
+    /// Addresses with CIDR prefix (`Address`, comma-separated, may repeat).
+    pub addresses: Vec<String>,
+    /// DNS servers or search domains (`DNS`).
+    pub dns: Vec<String>,
+    /// UDP listen port (`ListenPort`).
+    pub listen_port: Option<u16>,
+    /// Interface MTU (`MTU`).
+    pub mtu: Option<u32>,
+    /// Keys nmrs does not model (`Table`, `FwMark`, `PostUp`, ...), in file order.
+    pub extra: Vec<(String, String)>,
+}
+
+/// A `[Peer]` section of a wg-quick config.
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WgQuickPeer {
+    /// Base64 public key (`PublicKey`).
+    pub public_key: String,
+    /// Optional base64 pre-shared key (`PresharedKey`).
+    pub preshared_key: Option<String>,
+    /// Routed prefixes (`AllowedIPs`, comma-separated, may repeat).
+    pub allowed_ips: Vec<String>,
+    /// `host:port` endpoint (`Endpoint`).
+    pub endpoint: Option<String>,
+    /// Keepalive interval in seconds (`PersistentKeepalive`).
+    pub persistent_keepalive: Option<u32>,
+    /// Keys nmrs does not model, in file order.
+    pub extra: Vec<(String, String)>,
+}
+
+/// A parsed `wg-quick(8)` configuration file.
+///
+/// Keys are matched case-insensitively, as `wg` does.
+///
+/// # Example
+///
+/// ```rust
+/// use nmrs::keyfile::WgQuickConfig;
+///
+/// let conf = WgQuickConfig::parse("\
+/// [Interface]
+/// PrivateKey = YAnz5TF+lXXJte14tji3zlMNq+hd2rYUIgJBgB3fBmk=
+/// Address = 10.0.0.2/24
+///
+/// [Peer]
+/// PublicKey = xTIBA5rboUvnH4htodjb6e697QjLERt1NAB4mZqp8Dg=
+/// Endpoint = vpn.example.com:51820
+/// AllowedIPs = 0.0.0.0/0, ::/0
+/// ")?;
+///
+/// let config = conf.to_wireguard_config("work")?;
+/// assert_eq!(config.gateway, "vpn.example.com:51820");
+/// # Ok::<(), nmrs::keyfile::KeyfileError>(())
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WgQuickConfig {
+    /// The `[Interface]` section.
+    pub interface: WgQuickInterface,
+    /// Every `[Peer]` section, in file order.
+    pub peers: Vec<WgQuickPeer>,
+}
+
+enum Section {
+    Interface,
+    Peer,
+}
+
+impl WgQuickConfig {
+    /// Parses wg-quick config contents.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`KeyfileError`] for malformed lines, unknown sections,
+    /// unparsable numbers, a missing or repeated `[Interface]`, or a missing
+    /// `PrivateKey` / `PublicKey`.
+    pub fn parse(input: &str) -> Result<Self, KeyfileError> {
+        let mut conf = Self::default();
+        let mut section = None;
+        let mut seen_interface = false;
+
+        for item in lines(input) {
+            let (line, parsed) = item?;
+            match parsed {
+                Line::Group(name) if name.eq_ignore_ascii_case(INTERFACE) => {
+                    if seen_interface {
+                        return Err(KeyfileError::InvalidGroup { line });
+                    }
+                    seen_interface = true;
+                    section = Some(Section::Interface);
+                }
+                Line::Group(name) if name.eq_ignore_ascii_case(PEER) => {
+                    conf.peers.push(WgQuickPeer::default());
+                    section = Some(Section::Peer);
+                }
+                Line::Group(_) => return Err(KeyfileError::InvalidGroup { line }),
+                Line::Entry { key, value } => match section {
+                    None => return Err(KeyfileError::EntryOutsideGroup { line }),
+                    Some(Section::Interface) => conf.interface.apply(key, value)?,
+                    Some(Section::Peer) => {
+                        // A peer is always pushed before `section` becomes `Peer`.
+                        if let Some(peer) = conf.peers.last_mut() {
+                            peer.apply(key, value)?;
+                        }
+                    }
+                },
+            }
+        }
+
+        if !seen_interface {
+            return Err(KeyfileError::MissingGroup {
+                group: INTERFACE.into(),
+            });
+        }
+        if conf.interface.private_key.is_empty() {
+            return Err(missing(INTERFACE, "PrivateKey"));
+        }
+        if conf.peers.iter().any(|p| p.public_key.is_empty()) {
+            return Err(missing(PEER, "PublicKey"));
+        }
+        Ok(conf)
+    }
+
+    /// Converts to a [`WireGuardConfig`] named `name`.
+    ///
+    /// The first address becomes the tunnel address and the first peer
+    /// endpoint becomes the gateway. Peers without their own endpoint reuse
+    /// that gateway.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`KeyfileError::MissingKey`] when there is no `Address` or no
+    /// peer with an `Endpoint`, and [`KeyfileError::MissingGroup`] when there
+    /// is no `[Peer]` at all.
+    pub fn to_wireguard_config(&self, name: &str) -> Result<WireGuardConfig, KeyfileError> {
+        let address = self
+            .interface
+            .addresses
+            .first()
+            .ok_or_else(|| missing(INTERFACE, "Address"))?;
+        if self.peers.is_empty() {
+            return Err(KeyfileError::MissingGroup { group: PEER.into() });
+        }
+        let gateway = self
+            .peers
+            .iter()
+            .find_map(|p| p.endpoint.clone())
+            .ok_or_else(|| missing(PEER, "Endpoint"))?;
+
+        let peers = self
+            .peers
+            .iter()
+            .map(|p| {
+                let mut peer = WireGuardPeer::new(
+                    p.public_key.clone(),
+                    p.endpoint.clone().unwrap_or_else(|| gateway.clone()),
+                    p.allowed_ips.clone(),
+                );
+                if let Some(psk) = &p.preshared_key {
+                    peer = peer.with_preshared_key(psk.clone());
+                }
+                if let Some(keepalive) = p.persistent_keepalive {
+                    peer = peer.with_persistent_keepalive(keepalive);
+                }
+                peer
+            })
+            .collect();
+
+        let mut config = WireGuardConfig::new(
+            name,
+            gateway,
+            self.interface.private_key.clone(),
+            address.clone(),
+            peers,
+        );
+        if !self.interface.dns.is_empty() {
+            config = config.with_dns(self.interface.dns.clone());
+        }
+        if let Some(mtu) = self.interface.mtu {
+            config = config.with_mtu(mtu);
+        }
+        Ok(config)
+    }
+}
+
+impl WgQuickInterface {
+    fn apply(&mut self, key: &str, value: &str) -> Result<(), KeyfileError> {
+        match key.to_ascii_lowercase().as_str() {
+            "privatekey" => self.private_key = value.to_string(),
+            "address" => self.addresses.extend(split_list(value)),
+            "dns" => self.dns.extend(split_list(value)),
+            "listenport" => self.listen_port = Some(number(INTERFACE, key, value)?),
+            "mtu" => self.mtu = Some(number(INTERFACE, key, value)?),
+            _ => self.extra.push((key.to_string(), value.to_string())),
+        }
+        Ok(())
+    }
+}
+
+impl WgQuickPeer {
+    fn apply(&mut self, key: &str, value: &str) -> Result<(), KeyfileError> {
+        match key.to_ascii_lowercase().as_str() {
+            "publickey" => self.public_key = value.to_string(),
+            "presharedkey" => self.preshared_key = Some(value.to_string()),
+            "allowedips" => self.allowed_ips.extend(split_list(value)),
+            "endpoint" => self.endpoint = Some(value.to_string()),
+            "persistentkeepalive" if value.eq_ignore_ascii_case("off") => {
+                self.persistent_keepalive = None;
+            }
+            "persistentkeepalive" => {
+                self.persistent_keepalive = Some(number(PEER, key, value)?);
+            }
+            _ => self.extra.push((key.to_string(), value.to_string())),
+        }
+        Ok(())
+    }
+}
+
+fn split_list(value: &str) -> impl Iterator<Item = String> + '_ {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+fn number<T: FromStr>(group: &str, key: &str, value: &str) -> Result<T, KeyfileError> {
+    value.parse().map_err(|_| KeyfileError::InvalidValue {
+        group: group.to_string(),
+        key: key.to_string(),
+        value: value.to_string(),
+    })
+}
+
+fn missing(group: &str, key: &str) -> KeyfileError {
+    KeyfileError::MissingKey {
+        group: group.to_string(),
+        key: key.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONF: &str = "\
+# laptop tunnel
+[Interface]
+PrivateKey = YAnz5TF+lXXJte14tji3zlMNq+hd2rYUIgJBgB3fBmk=
+Address = 10.0.0.2/24, fd00::2/64
+DNS = 1.1.1.1
+ListenPort = 51820
+MTU = 1420
+PostUp = iptables -A FORWARD -i %i -j ACCEPT
+
+[Peer]
+PublicKey = xTIBA5rboUvnH4htodjb6e697QjLERt1NAB4mZqp8Dg=
+PresharedKey = /UwcSPg38hW/D9Y3tcS1FOV0K1wuURMbS0sesJEP5ak=
+Endpoint = vpn.example.com:51820
+AllowedIPs = 0.0.0.0/0
+AllowedIPs = ::/0
+PersistentKeepalive = 25
+
+[peer]
+publickey = TrMvSoP4jYQlY6RIzBgbssQqY3vxI2Pi+y71lOWWXX0=
+allowedips = 10.10.0.0/16
+";
+
+    #[test]
+    fn parses_wg_quick() {
+        let conf = WgQuickConfig::parse(CONF).unwrap();
+        assert_eq!(conf.interface.addresses, vec!["10.0.0.2/24", "fd00::2/64"]);
+        assert_eq!(conf.interface.dns, vec!["1.1.1.1"]);
+        assert_eq!(conf.interface.listen_port, Some(51820));
+        assert_eq!(conf.interface.mtu, Some(1420));
+        assert_eq!(conf.interface.extra.len(), 1);
+        assert_eq!(conf.peers.len(), 2);
+        assert_eq!(conf.peers[0].allowed_ips, vec!["0.0.0.0/0", "::/0"]);
+        assert_eq!(conf.peers[0].persistent_keepalive, Some(25));
+        assert!(conf.peers[0].preshared_key.is_some());
+        assert_eq!(conf.peers[1].endpoint, None);
+    }
+
+    #[test]
+    fn converts_to_wireguard_config() {
+        let config = WgQuickConfig::parse(CONF)
+            .unwrap()
+            .to_wireguard_config("work")
+            .unwrap();
+        assert_eq!(config.name, "work");
+        assert_eq!(config.gateway, "vpn.example.com:51820");
+        assert_eq!(config.address, "10.0.0.2/24");
+        assert_eq!(config.mtu, Some(1420));
+        assert_eq!(config.peers.len(), 2);
+        assert_eq!(config.peers[1].gateway, "vpn.example.com:51820");
+        assert_eq!(config.peers[0].persistent_keepalive, Some(25));
+    }
+
+    #[test]
+    fn rejects_invalid_configs() {
+        assert_eq!(
+            WgQuickConfig::parse("[Peer]\nPublicKey = x\n"),
+            Err(KeyfileError::MissingGroup {
+                group: "Interface".into()
+            })
+        );
+        assert_eq!(
+            WgQuickConfig::parse("[Interface]\nAddress = 10.0.0.2/24\n"),
+            Err(missing("Interface", "PrivateKey"))
+        );
+        assert_eq!(
+            WgQuickConfig::parse("[Interface]\nPrivateKey = k\n[Peer]\nEndpoint = h:1\n"),
+            Err(missing("Peer", "PublicKey"))
+        );
+        assert!(matches!(
+            WgQuickConfig::parse("[Interface]\nPrivateKey = k\nListenPort = 99999\n"),
+            Err(KeyfileError::InvalidValue { .. })
+        ));
+        assert_eq!(
+            WgQuickConfig::parse("[Interface]\nPrivateKey = k\n[Interface]\n"),
+            Err(KeyfileError::InvalidGroup { line: 3 })
+        );
+        assert_eq!(
+            WgQuickConfig::parse("[Interface]\nPrivateKey = k\n[Wat]\n"),
+            Err(KeyfileError::InvalidGroup { line: 3 })
+        );
+    }
+
+    #[test]
+    fn conversion_requires_address_and_endpoint() {
+        let no_addr = WgQuickConfig::parse("[Interface]\nPrivateKey = k\n").unwrap();
+        assert_eq!(
+            no_addr.to_wireguard_config("x").unwrap_err(),
+            missing("Interface", "Address")
+        );
+
+        let no_endpoint = WgQuickConfig::parse(
+            "[Interface]\nPrivateKey = k\nAddress = 10.0.0.2/32\n[Peer]\nPublicKey = p\n",
+        )
+        .unwrap();
+        assert_eq!(
+            no_endpoint.to_wireguard_config("x").unwrap_err(),
+            missing("Peer", "Endpoint")
+        );
+    }
+}
@@ -113,15 +113,20 @@
 //! - [`Network`] / [`AccessPoint`] / [`NetworkInfo`] — discovered Wi-Fi data
 //! - [`WifiDevice`] — per-Wi-Fi-device summary
 //! - [`WifiSecurity`] / [`EapOptions`] / [`EapMethod`] / [`Phase2`] — Wi-Fi security
-//! - [`ConnectionOptions`] / [`TimeoutConfig`] — connection knobs
+//! - [`ConnectionOptions`] / [`ConnectionPersistence`] / [`ActivationBinding`] / [`TimeoutConfig`] — connection knobs
 //! - [`WireGuardConfig`] / [`WireGuardPeer`] — WireGuard configuration
 //! - [`OpenVpnConfig`] / [`OpenVpnAuthType`] / [`OpenVpnProxy`] — OpenVPN configuration
+//! - [`Ikev2Config`] / [`Ikev2Auth`] — IKEv2 (strongSwan) configuration
 //! - [`VpnConfig`] / [`VpnConfiguration`] — generic VPN dispatch trait/enum
 //! - [`VpnConnection`] / [`VpnConnectionInfo`] / [`VpnDetails`] / [`VpnType`] / [`VpnKind`] — saved or active VPN data
 //! - [`SavedConnection`] / [`SavedConnectionBrief`] / [`SettingsSummary`] / [`SettingsPatch`] — saved profile management
 //! - [`AirplaneModeState`] / [`RadioState`] — radio/rfkill state
 //! - [`BluetoothDevice`] / [`BluetoothIdentity`] / [`BluetoothNetworkRole`] — Bluetooth networking
 //! - [`ConnectivityState`] / [`ConnectivityReport`] — internet connectivity
+//! - [`Permissions`] / [`Permission`] / [`PermissionResult`] — polkit permissions of the caller
+//! - [`ConnectHandle`] — cancels a connection attempt
+//! - [`DeviceHandle`] / [`DeviceRef`] — a device tracked across interface renames
+//! - [`ConnectProgress`] — steps reported by a streaming Wi-Fi connect
 //! - [`ConnectionError`] / [`StateReason`] / [`ConnectionStateReason`] — errors
 //!
 //! [`VpnCredentials`] is still re-exported but is **deprecated**; new code
@@ -133,7 +138,7 @@
 //! ([`builders::ConnectionBuilder`], [`builders::WifiConnectionBuilder`],
 //! [`builders::WireGuardBuilder`], [`builders::OpenVpnBuilder`]) and
 //! free functions (`build_wifi_connection`, `build_ethernet_connection`,
-//! `build_wireguard_connection`, `build_openvpn_connection`,
+//! `build_wireguard_connection`, `build_openvpn_connection`, `build_ikev2_connection`,
 //! `build_bluetooth_connection`, `build_vlan_connection`) for constructing
 //! NetworkManager settings dictionaries. Most callers should reach for the
 //! higher-level [`NetworkManager`] API; these builders are exposed for
@@ -284,7 +289,18 @@
 //!
 //! # Feature Flags
 //!
-//! This crate currently has no optional features. All functionality is enabled by default.
+//! All core functionality is enabled by default. Optional features:
+//!
+//! - `usage-history` — `nmrs::usage` module and
+//!   `NetworkManager::track_usage` for persistent per-profile data usage.
+//! - `event-export` — `nmrs::event_export` module and
+//!   `NetworkManager::export_events` / `serve_events` for mirroring network
+//!   events as newline-delimited JSON.
+//! - `captive-portal` — `nmrs::captive_portal` module and
+//!   `NetworkManager::detect_captive_portal` / `connect_checking_portal`
+//!   for finding a portal's login page with a plain-HTTP probe.
+//! - `test-util` — `nmrs::backend::MockBackend`, an in-memory
+//!   [`NmBackend`](backend::NmBackend) for unit tests.
 //!
 //! # Platform Support
 //!
@@ -306,6 +322,41 @@ mod util;
 /// lifecycle, and a full example.
 pub mod agent;
 
+/// Pluggable NetworkManager backend and, with feature `test-util`, an
+/// in-memory mock.
+///
+/// See the [module documentation](backend) for details.
+pub mod backend;
+
+/// Captive portal detection and login-page discovery (feature `captive-portal`).
+///
+/// See the [module documentation](captive_portal) for details.
+#[cfg(feature = "captive-portal")]
+pub mod captive_portal;
+
+/// Newline-delimited JSON export of network events (feature `event-export`).
+///
+/// See the [module documentation](event_export) for details.
+#[cfg(feature = "event-export")]
+pub mod event_export;
+
+/// Pure, I/O-free parsers for `.nmconnection` keyfiles and wg-quick configs.
+///
+/// See the [module documentation](keyfile) for details.
+pub mod keyfile;
+
+/// Wi-Fi QR code payloads for sharing and joining networks (feature `qr`).
+///
+/// See the [module documentation](qr) for details.
+#[cfg(feature = "qr")]
+pub mod qr;
+
+/// Persistent per-profile data usage history (feature `usage-history`).
+///
+/// See the [module documentation](usage) for details.
+#[cfg(feature = "usage-history")]
+pub mod usage;
+
 // ============================================================================
 // Public API
 // ============================================================================
@@ -358,20 +409,48 @@ pub mod builders {
 /// - [`Device`] — Network device representation
 /// - [`Network`] — Wi-Fi network representation (SSID-grouped)
 /// - [`AccessPoint`] — Per-BSSID access point details
+/// - [`BssLoad`] — Station count and channel utilization advertised by an AP
 /// - [`NetworkInfo`] — Detailed network information returned by `show_details`
+/// - [`NetworkDetails`] — Per-AP ciphers, WPA generation and 802.11 standard
+/// - [`ActiveConnection`] — Typed `Connection.Active` object
+/// - [`PrimaryConnection`] — The default uplink and its [`UplinkKind`]
+/// - [`NetworkSnapshot`] — Devices, networks and active connections read at once
+/// - [`ActiveConnectionDetails`] / [`IpDetails`] — Runtime IP configuration of active connections
+/// - [`DhcpInfo`] / [`DhcpLease`] — DHCP leases held by a device
+/// - [`NetworkEvent`] — Typed change events yielded by `NetworkManager::events`
+/// - [`DaemonEvent`] — NetworkManager daemon stop/restart, carried by `NetworkEvent::Daemon`
 /// - [`WifiDevice`] — Wi-Fi-specific device summary
 /// - [`BluetoothDevice`] — Discovered Bluetooth peer
 /// - [`SavedConnection`] / [`SavedConnectionBrief`] — Saved profile snapshots
 /// - [`SettingsSummary`] / [`SettingsPatch`] — Decoded NM settings & update patches
+/// - [`ConnectionSecrets`] — Stored secrets returned by `get_secrets`
+/// - [`AutoconnectStatus`] — Whether and why a profile is blocked from autoconnecting
+/// - [`ConnectionFailure`] — Last activation failure recorded for a device
+/// - [`GsmCredentials`] — APN and credentials for mobile broadband
+/// - [`P2pPeer`] — Wi-Fi Direct peer found by P2P discovery
 /// - [`VpnConnection`] / [`VpnConnectionInfo`] / [`VpnDetails`] — Active or saved VPN data
+/// - [`ResumeReport`] — Network state re-checked after resume from suspend
+/// - [`CheckpointEvent`] — Checkpoint created, rolled back, or destroyed
+/// - [`SharedConnection`] — Profile sharing the internet connection over an interface
 ///
 /// # Configuration
 /// - [`WifiSecurity`] — Wi-Fi security types (Open, WPA-PSK, WPA-EAP)
+/// - [`WifiOptions`] — Band and channel to join a Wi-Fi network on
 /// - [`EapOptions`] — Enterprise authentication options
 /// - [`ConnectionOptions`] — Connection settings (autoconnect, priority, retries)
+/// - [`ConnectionPersistence`] — Whether profiles added by a connect are saved
+/// - [`ActivationBinding`] — Whether an activation ends with this process
 /// - [`TimeoutConfig`] — Timeout configuration for connection operations
+/// - [`ConnectPolicy`] — Retry policy for [`NetworkManager::connect_with_policy`]
+/// - [`TrustLevel`] — Home/Work/Public presets for new profiles
+/// - [`ShareOptions`] — Hotspot SSID and passphrase for [`NetworkManager::share_connection_with_options`]
+/// - [`MatchOptions`] — How saved profiles are matched by name or SSID
+/// - [`IpSettings`] — Static or automatic IPv4/IPv6 configuration for new profiles
+/// - [`NmLogging`] — NetworkManager daemon logging configuration
+/// - [`Permissions`] — Polkit permissions of the caller
 /// - [`WireGuardConfig`] / [`WireGuardPeer`] — WireGuard tunnel configuration
 /// - [`OpenVpnConfig`] — OpenVPN plugin configuration
+/// - [`Ikev2Config`] / [`Ikev2Auth`] — IKEv2 configuration for the strongSwan plugin
 /// - [`VlanConfig`] — VLAN tagging configuration
 /// - [`BluetoothIdentity`] — Bluetooth target (bdaddr + role)
 /// - [`VpnConfig`] / [`VpnConfiguration`] — Trait & enum used by `connect_vpn`
@@ -379,8 +458,14 @@ pub mod builders {
 /// # Enums
 /// - [`DeviceType`] — Device types (Ethernet, Wi-Fi, Bluetooth, etc.)
 /// - [`DeviceState`] — Device states (Disconnected, Activated, etc.)
+/// - [`UnavailableReason`] — Why a device is unavailable (firmware, rfkill, carrier)
+/// - [`IpMethod`] — How an address family is configured (auto, manual, link-local, disabled)
+/// - [`UnmanagedReason`] — Why NetworkManager leaves a device unmanaged
 /// - [`ActiveConnectionState`] — State of an active connection
+/// - [`ActivationStage`] — Intermediate step reported while a connection activates
+/// - [`UplinkKind`] — Wi-Fi / Ethernet / mobile broadband / VPN link of a connection
 /// - [`ConnectivityState`] — NM-reported internet connectivity
+/// - [`NmLogLevel`] — NetworkManager daemon log level
 /// - [`RadioState`] / [`AirplaneModeState`] — Radio/rfkill state
 /// - [`ApMode`] — Access point operating mode
 /// - [`BluetoothNetworkRole`] — PAN-U / NAP / DUN roles
@@ -392,34 +477,54 @@ pub mod builders {
 /// - [`VpnSecretFlags`] — NM secret flags for VPN credentials
 /// - [`WifiKeyMgmt`] / [`WifiSecuritySummary`] / [`SecurityFeatures`] — Decoded Wi-Fi security info
 /// - [`ConnectType`] — How a `connect_vpn` call resolved (saved vs. new)
+/// - [`WifiCipher`] / [`MfpStatus`] / [`CipherSuites`] — Decoded cipher suites and PMF status
 ///
 /// # Errors
 /// - [`ConnectionError`] — Comprehensive error type for all operations
+/// - [`ErrorClass`] — Broad category of a [`ConnectionError`], for retry decisions
 /// - [`StateReason`] — Device state change reasons
 /// - [`ConnectionStateReason`] — Connection state change reasons
+/// - [`VpnStateReason`] — Plugin VPN state change reasons
 ///
 /// # Helper Functions
 /// - [`reason_to_error`] — Convert a device state reason to a [`ConnectionError`]
 /// - [`connection_state_reason_to_error`] — Convert an active-connection state reason to a [`ConnectionError`]
+/// - [`vpn_state_reason_to_error`] — Convert a VPN state reason to a [`ConnectionError`]
 pub mod models {
     pub use crate::api::models::*;
 }
 
 // Re-export commonly used types at crate root for convenience
+pub use api::connect_handle::ConnectHandle;
+pub use api::device_handle::{DeviceHandle, DeviceRef};
 #[allow(deprecated)]
 pub use api::models::{
-    AccessPoint, ActiveConnectionState, AirplaneModeState, ApMode, BluetoothDevice,
-    BluetoothIdentity, BluetoothNetworkRole, ConnectType, ConnectionError, ConnectionOptions,
-    ConnectionStateReason, ConnectivityReport, ConnectivityState, Device, DeviceState, DeviceType,
-    EapMethod, EapOptions, Network, NetworkInfo, OpenVpnAuthType, OpenVpnCompression,
-    OpenVpnConfig, OpenVpnConnectionType, OpenVpnProxy, Phase2, RadioState, SavedConnection,
-    SavedConnectionBrief, SecurityFeatures, SettingsPatch, SettingsSummary, StateReason,
-    TimeoutConfig, VlanConfig, VpnConfig, VpnConfiguration, VpnConnection, VpnConnectionInfo,
-    VpnCredentials, VpnDetails, VpnKind, VpnRoute, VpnSecretFlags, VpnType, WifiDevice,
-    WifiKeyMgmt, WifiSecurity, WifiSecuritySummary, WireGuardConfig, WireGuardPeer,
-    connection_state_reason_to_error, reason_to_error,
+    AccessPoint, ActivationBinding, ActivationStage, ActiveConnection, ActiveConnectionDetails,
+    ActiveConnectionState, AirplaneModeState, ApMode, AutoconnectBlockReason, AutoconnectStatus,
+    BluetoothDevice, BluetoothIdentity, BluetoothNetworkRole, BondMode, BridgeOptions, BssLoad,
+    CheckpointEvent, CipherSuites, ConnectPolicy, ConnectProgress, ConnectType, ConnectionError,
+    ConnectionFailure, ConnectionOptions, ConnectionPersistence, ConnectionSecrets,
+    ConnectionStateReason, ConnectivityReport, ConnectivityState, DaemonEvent, Device, DeviceState,
+    DeviceType, DhcpInfo, DhcpLease, DnsConfig, Duplex, EapMethod, EapOptions, EapSecrets,
+    ErrorClass, FastProvisioning, GsmCredentials, Ikev2Auth, Ikev2Config, IpDetails, IpMethod,
+    IpSettings, KnownNetwork, LinkStats, LinkSummary, MacPolicy, MatchOptions, MetricSummary,
+    MfpStatus, Network, NetworkDetails, NetworkEvent, NetworkInfo, NetworkSnapshot, NmLogLevel,
+    NmLogging, OpenVpnAuthType, OpenVpnCompression, OpenVpnConfig, OpenVpnConnectionType,
+    OpenVpnProxy, P2pPeer, Permission, PermissionResult, Permissions, Phase2, PrimaryConnection,
+    RadioState, ResumeReport, SavedConnection, SavedConnectionBrief, ScanOptions, Secret,
+    SecurityFeatures, SettingsPatch, SettingsSummary, ShareOptions, SharedConnection, SlaveType,
+    StateReason, TimeoutConfig, TrafficDelta, TrafficStats, TrustLevel, UnavailableReason,
+    UnmanagedDevice, UnmanagedReason, UplinkKind, VlanConfig, VpnConfig, VpnConfiguration,
+    VpnConnection, VpnConnectionInfo, VpnCredentials, VpnDetails, VpnKind, VpnRoute,
+    VpnSecretFlags, VpnSecrets, VpnStateReason, VpnSupervisorEvent, VpnSupervisorPolicy, VpnType,
+    WifiCipher, WifiDevice, WifiKeyMgmt, WifiOptions, WifiSecrets, WifiSecurity,
+    WifiSecuritySummary, WifiStandard, WireGuardConfig, WireGuardPeer, WiredOptions, WpaGeneration,
+    connection_state_reason_to_error, reason_to_error, vpn_state_reason_to_error,
 };
-pub use api::network_manager::NetworkManager;
+pub use api::network_manager::{NetworkManager, NetworkManagerBuilder};
+pub use api::scan_scheduler::ScanScheduler;
+pub use api::stats_collector::StatsCollector;
+pub use api::vpn_supervisor::VpnSupervisor;
 pub use api::wifi_scope::WifiScope;
 
 /// A specialized `Result` type for network operations.
@@ -0,0 +1,279 @@
+//! Checkpoint lifecycle monitoring.
+//!
+//! Watches the manager's `Checkpoints` property and reports checkpoints as
+//! they appear and disappear. A checkpoint that vanishes once its rollback
+//! timeout has passed is reported as rolled back; NM gives no other signal
+//! for automatic rollbacks. After a NetworkManager restart the property is
+//! re-subscribed and re-read; checkpoints of the old daemon are reported
+//! gone and those of the new one created.
+
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use futures::future::ready;
+use futures::stream::{self, Stream, StreamExt};
+use log::{debug, warn};
+use tokio::select;
+use tokio::sync::watch;
+use zbus::Connection;
+use zvariant::OwnedObjectPath;
+
+use crate::Result;
+use crate::api::models::{CheckpointEvent, ConnectionError, DaemonEvent};
+use crate::core::daemon_watch::daemon_events;
+use crate::dbus::{NMCheckpointProxy, NMDeviceProxy, NMProxy, dbus_operation};
+use crate::util::utils::boottime_ms;
+
+/// Slack for comparing the removal time with the rollback deadline, since
+/// `/proc/uptime` only has centisecond resolution.
+const EXPIRY_GRACE_MS: i64 = 500;
+
+/// Calls `callback` for every checkpoint created or removed.
+///
+/// Checkpoints that already exist when monitoring starts are tracked but
+/// not reported as created. Runs until `shutdown` fires or the property
+/// stream ends.
+pub(crate) async fn monitor_checkpoints<F>(
+    conn: &Connection,
+    mut shutdown: watch::Receiver<()>,
+    callback: F,
+) -> Result<()>
+where
+    F: Fn(CheckpointEvent) + Send + 'static,
+{
+    let mut restarts = daemon_events(conn)
+        .await?
+        .filter(|event| ready(*event == DaemonEvent::Restarted))
+        .boxed();
+    let nm = NMProxy::new(conn).await?;
+    let mut changes = nm.receive_checkpoints_changed().await;
+    let mut known = existing(conn, &nm).await?;
+
+    debug!("Monitoring {} existing checkpoint(s)", known.len());
+
+    loop {
+        let mut restarted = false;
+        let current = select! {
+            _ = shutdown.changed() => {
+                debug!("Checkpoint monitoring shutdown requested");
+                return Ok(());
+            }
+            change = changes.next() => {
+                let Some(change) = change else {
+                    return Err(ConnectionError::Stuck("checkpoint property stream ended".into()));
+                };
+                match change.get().await {
+                    Ok(paths) => paths,
+                    Err(e) => {
+                        warn!("Failed to read Checkpoints property: {e}");
+                        continue;
+                    }
+                }
+            }
+            Some(_) = restarts.next() => {
+                debug!("NetworkManager restarted, re-reading checkpoints");
+                let paths = match NMProxy::new(conn).await {
+                    Ok(nm) => {
+                        changes = nm.receive_checkpoints_changed().await;
+                        nm.checkpoints().await
+                    }
+                    Err(e) => Err(e),
+                };
+                match paths {
+                    Ok(paths) => {
+                        restarted = true;
+                        paths
+                    }
+                    Err(e) => {
+                        warn!("Failed to re-read checkpoints after NetworkManager restart: {e}");
+                        continue;
+                    }
+                }
+            }
+        };
+
+        for event in reconcile(conn, &mut known, current, restarted).await {
+            callback(event);
+        }
+    }
+}
+
+/// Streams every checkpoint created or removed.
+///
+/// Like [`monitor_checkpoints`], checkpoints that already exist are tracked
+/// but not reported. The stream ends with the property stream; it does not
+/// follow daemon restarts itself.
+pub(crate) async fn checkpoint_events(
+    conn: &Connection,
+) -> Result<impl Stream<Item = CheckpointEvent> + Send + 'static> {
+    let nm = NMProxy::new(conn).await?;
+    let changes = nm.receive_checkpoints_changed().await;
+    let known = existing(conn, &nm).await?;
+
+    let events = stream::unfold(
+        (conn.clone(), changes, known),
+        |(conn, mut changes, mut known)| async move {
+            loop {
+                let current = match changes.next().await?.get().await {
+                    Ok(paths) => paths,
+                    Err(e) => {
+                        warn!("Failed to read Checkpoints property: {e}");
+                        continue;
+                    }
+                };
+                let events = reconcile(&conn, &mut known, current, false).await;
+                return Some((stream::iter(events), (conn, changes, known)));
+            }
+        },
+    );
+    Ok(events.flatten())
+}
+
+/// Existing checkpoints by path, with their rollback deadline in
+/// `CLOCK_BOOTTIME` ms.
+async fn existing(conn: &Connection, nm: &NMProxy<'_>) -> Result<HashMap<String, Option<i64>>> {
+    let mut known = HashMap::new();
+    let existing = nm
+        .checkpoints()
+        .await
+        .map_err(|e| dbus_operation("failed to read checkpoints", e))?;
+    for path in existing {
+        let (_, _, deadline) = describe(conn, &path).await;
+        known.insert(path.to_string(), deadline);
+    }
+    Ok(known)
+}
+
+/// Brings `known` in line with the `current` checkpoints, returning what
+/// was removed and then what was created.
+async fn reconcile(
+    conn: &Connection,
+    known: &mut HashMap<String, Option<i64>>,
+    current: Vec<OwnedObjectPath>,
+    restarted: bool,
+) -> Vec<CheckpointEvent> {
+    let current_set: HashSet<String> = current.iter().map(|p| p.to_string()).collect();
+    let mut events = forget_gone(known, &current_set, restarted, boottime_ms());
+
+    for path in current {
+        if known.contains_key(path.as_str()) {
+            continue;
+        }
+        let (devices, rollback_timeout, deadline) = describe(conn, &path).await;
+        known.insert(path.to_string(), deadline);
+        events.push(CheckpointEvent::Created {
+            path: path.to_string(),
+            devices,
+            rollback_timeout,
+        });
+    }
+    events
+}
+
+/// Drops the checkpoints missing from `current` from `known`, returning
+/// them as rolled back or destroyed.
+///
+/// Checkpoints live in the daemon's memory, so after a restart all of them
+/// are gone, even where the new daemon reuses a path.
+fn forget_gone(
+    known: &mut HashMap<String, Option<i64>>,
+    current: &HashSet<String>,
+    restarted: bool,
+    now: Option<i64>,
+) -> Vec<CheckpointEvent> {
+    let mut gone = Vec::new();
+    known.retain(|path, deadline| {
+        if !restarted && current.contains(path) {
+            return true;
+        }
+        let path = path.clone();
+        gone.push(if reached_deadline(*deadline, now) {
+            CheckpointEvent::RolledBack { path }
+        } else {
+            CheckpointEvent::Destroyed { path }
+        });
+        false
+    });
+    gone
+}
+
+/// Reads a checkpoint's device interfaces, timeout and rollback deadline.
+///
+/// Best-effort: a checkpoint can vanish while it is being inspected.
+async fn describe(
+    conn: &Connection,
+    path: &OwnedObjectPath,
+) -> (Vec<String>, Option<Duration>, Option<i64>) {
+    let proxy = match NMCheckpointProxy::builder(conn).path(path.clone()) {
+        Ok(b) => match b.build().await {
+            Ok(p) => p,
+            Err(e) => {
+                debug!("Failed to build checkpoint proxy for {path}: {e}");
+                return (Vec::new(), None, None);
+            }
+        },
+        Err(e) => {
+            debug!("Invalid checkpoint path {path}: {e}");
+            return (Vec::new(), None, None);
+        }
+    };
+
+    let mut devices = Vec::new();
+    for dev_path in proxy.devices().await.unwrap_or_default() {
+        if let Ok(builder) = NMDeviceProxy::builder(conn).path(dev_path)
+            && let Ok(dev) = builder.build().await
+            && let Ok(iface) = dev.interface().await
+        {
+            devices.push(iface);
+        }
+    }
+
+    let timeout = proxy.rollback_timeout().await.unwrap_or(0);
+    let rollback_timeout = (timeout > 0).then(|| Duration::from_secs(timeout.into()));
+    let deadline = match (proxy.created().await, rollback_timeout) {
+        (Ok(created), Some(_)) => Some(created + i64::from(timeout) * 1000),
+        _ => None,
+    };
+
+    (devices, rollback_timeout, deadline)
+}
+
+/// Whether a checkpoint removed at `now` had already hit its rollback deadline.
+fn reached_deadline(deadline: Option<i64>, now: Option<i64>) -> bool {
+    match (deadline, now) {
+        (Some(deadline), Some(now)) => now + EXPIRY_GRACE_MS >= deadline,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deadline_classification() {
+        assert!(reached_deadline(Some(10_000), Some(10_200)));
+        assert!(reached_deadline(Some(10_000), Some(9_700)));
+        assert!(!reached_deadline(Some(10_000), Some(5_000)));
+        assert!(!reached_deadline(None, Some(10_000)));
+        assert!(!reached_deadline(Some(10_000), None));
+    }
+
+    #[test]
+    fn restart_forgets_every_checkpoint() {
+        let path = "/org/freedesktop/NetworkManager/Checkpoint/1".to_string();
+        let current = HashSet::from([path.clone()]);
+        let mut known = HashMap::from([(path.clone(), Some(10_000))]);
+
+        assert!(forget_gone(&mut known, &current, false, Some(5_000)).is_empty());
+        assert_eq!(known.len(), 1);
+
+        // The new daemon numbered its own checkpoint like the old one.
+        let gone = forget_gone(&mut known, &current, true, Some(5_000));
+        assert!(matches!(
+            gone.as_slice(),
+            [CheckpointEvent::Destroyed { path: p }] if *p == path
+        ));
+        assert!(known.is_empty());
+    }
+}
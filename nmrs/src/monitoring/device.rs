@@ -4,22 +4,27 @@
 //! plugged in/out, device activation/deactivation) in real-time without needing
 //! to poll. This enables live UI updates for both wired and wireless devices.
 
-use futures::stream::{Stream, StreamExt};
-use log::debug;
+use futures::future::ready;
+use futures::stream::{BoxStream, Stream, StreamExt};
+use log::{debug, warn};
 use std::pin::Pin;
 use tokio::select;
 use tokio::sync::watch;
 use zbus::Connection;
 
 use crate::Result;
-use crate::api::models::ConnectionError;
+use crate::api::models::{ConnectionError, DaemonEvent, Device, DeviceIdentity};
+use crate::core::daemon_watch::{Resubscribing, daemon_events};
+use crate::core::device::find_device_by_identity;
 use crate::dbus::{NMDeviceProxy, NMProxy};
 
 /// Monitors device state changes on all network devices.
 ///
 /// Subscribes to `StateChanged` signals on all network devices. When any signal
 /// is received (device activated, disconnected, cable plugged in, etc.), invokes
-/// the callback to notify the caller that device states have changed.
+/// the callback to notify the caller that device states have changed. After a
+/// NetworkManager restart every subscription is re-established and the
+/// callback invoked once.
 ///
 /// This function runs indefinitely until an error occurs or the connection
 /// is lost. Run it in a background task.
@@ -40,10 +45,43 @@ pub async fn monitor_device_changes<F>(
 where
     F: Fn() + Send + 'static,
 {
+    let (mut merged, ()) = Resubscribing::new(conn, || subscribe(conn)).await?;
+
+    debug!(
+        "Monitoring {} signal streams for device changes",
+        merged.len()
+    );
+
+    loop {
+        select! {
+            _ = shutdown.changed() => {
+                debug!("Network monitoring shutdown requested");
+                break;
+            }
+            signal = merged.next() => {
+                match signal {
+                    Some(_) => callback(),
+                    None => break,
+                }
+            }
+        }
+    }
+
+    while let Some(_signal) = merged.next().await {
+        debug!("Device change detected");
+        callback();
+    }
+
+    Err(ConnectionError::Stuck("monitoring stream ended".into()))
+}
+
+/// Subscribes to NetworkManager's device signals and to the state of every
+/// existing device.
+async fn subscribe(conn: &Connection) -> Result<(Vec<BoxStream<'static, ()>>, ())> {
     let nm = NMProxy::new(conn).await?;
 
     // Use dynamic dispatch to handle different signal stream types
-    let mut streams: Vec<Pin<Box<dyn Stream<Item = _> + Send>>> = Vec::new();
+    let mut streams: Vec<BoxStream<'static, ()>> = Vec::new();
 
     // Subscribe to DeviceAdded and DeviceRemoved signals from main NetworkManager
     // This is more reliable than subscribing to individual devices
@@ -71,33 +109,122 @@ where
         }
     }
 
+    Ok((streams, ()))
+}
+
+/// Monitors a single device identified by its hardware identity.
+///
+/// The device is looked up by MAC rather than interface name or D-Bus path.
+/// When NetworkManager removes and re-adds it (USB re-plug, interface
+/// rename), the monitor re-resolves the device and re-binds to its new
+/// object path automatically.
+///
+/// The callback receives the refreshed [`Device`] on every state change or
+/// re-bind, and `None` while the device is absent. A NetworkManager restart
+/// re-subscribes and re-binds as well.
+pub async fn monitor_device_by_identity<F>(
+    conn: &Connection,
+    identity: DeviceIdentity,
+    mut shutdown: watch::Receiver<()>,
+    callback: F,
+) -> Result<()>
+where
+    F: Fn(Option<Device>) + Send + 'static,
+{
+    let mut restarts = daemon_events(conn)
+        .await?
+        .filter(|event| ready(*event == DaemonEvent::Restarted))
+        .boxed();
+    let nm = NMProxy::new(conn).await?;
+    let mut added = nm.receive_device_added().await?;
+    let mut removed = nm.receive_device_removed().await?;
+
+    let mut current = find_device_by_identity(conn, &identity).await?;
+    let mut state_stream = match &current {
+        Some(device) => subscribe_device_state(conn, &device.path).await,
+        None => None,
+    };
     debug!(
-        "Monitoring {} signal streams for device changes",
-        streams.len()
+        "Monitoring device {} (currently {:?})",
+        identity.permanent_mac,
+        current.as_ref().map(|d| d.interface.as_str())
     );
 
-    // Merge all streams and listen for any signal
-    let mut merged = futures::stream::select_all(streams);
-
     loop {
-        select! {
+        let mut restarted = false;
+        let rebind = select! {
             _ = shutdown.changed() => {
-                debug!("Network monitoring shutdown requested");
-                break;
+                debug!("Device identity monitoring shutdown requested");
+                return Ok(());
             }
-            signal = merged.next() => {
-                match signal {
-                    Some(_) => callback(),
-                    None => break,
+            signal = added.next() => {
+                if signal.is_none() {
+                    break;
+                }
+                true
+            }
+            signal = removed.next() => {
+                if signal.is_none() {
+                    break;
+                }
+                true
+            }
+            signal = async { state_stream.as_mut()?.next().await }, if state_stream.is_some() => {
+                if signal.is_none() {
+                    state_stream = None;
+                }
+                false
+            }
+            Some(_) = restarts.next() => {
+                debug!("NetworkManager restarted, re-subscribing device {}", identity.permanent_mac);
+                match (nm.receive_device_added().await, nm.receive_device_removed().await) {
+                    (Ok(a), Ok(r)) => (added, removed) = (a, r),
+                    (Err(e), _) | (_, Err(e)) => {
+                        warn!("Failed to re-subscribe after NetworkManager restart: {e}");
+                    }
                 }
+                restarted = true;
+                true
             }
+        };
+
+        let refreshed = find_device_by_identity(conn, &identity).await?;
+        let old_path = current.as_ref().map(|d| d.path.clone());
+        let new_path = refreshed.as_ref().map(|d| d.path.clone());
+
+        // A restarted daemon may hand out the old path to another object.
+        if old_path != new_path || restarted {
+            debug!(
+                "Device {} re-bound: {old_path:?} -> {new_path:?}",
+                identity.permanent_mac
+            );
+            state_stream = match &new_path {
+                Some(path) => subscribe_device_state(conn, path).await,
+                None => None,
+            };
+        } else if rebind {
+            // Unrelated device came or went.
+            continue;
         }
-    }
 
-    while let Some(_signal) = merged.next().await {
-        debug!("Device change detected");
-        callback();
+        current = refreshed;
+        callback(current.clone());
     }
 
     Err(ConnectionError::Stuck("monitoring stream ended".into()))
 }
+
+/// Subscribes to `StateChanged` on the device at `path`, if it still exists.
+async fn subscribe_device_state(
+    conn: &Connection,
+    path: &str,
+) -> Option<Pin<Box<dyn Stream<Item = ()> + Send>>> {
+    let dev = NMDeviceProxy::builder(conn)
+        .path(path.to_string())
+        .ok()?
+        .build()
+        .await
+        .ok()?;
+    let stream = dev.receive_device_state_changed().await.ok()?;
+    Some(Box::pin(stream.map(|_| ())))
+}
@@ -0,0 +1,252 @@
+//! Typed event stream over NetworkManager signals.
+//!
+//! Unlike the callback monitors, which only report "something changed",
+//! this merges device, access point, radio, connectivity and checkpoint
+//! signals into a single stream of [`NetworkEvent`]s. Devices that appear after the stream
+//! is created are subscribed to automatically and unsubscribed when they
+//! are removed, and every subscription is re-established when the
+//! NetworkManager daemon restarts.
+
+use futures::stream::{self, AbortHandle, SelectAll, Stream, StreamExt};
+use log::{debug, warn};
+use std::collections::HashMap;
+use std::pin::Pin;
+use zbus::Connection;
+use zvariant::OwnedObjectPath;
+
+use crate::Result;
+use crate::api::models::{ConnectivityState, DaemonEvent, DeviceState, NetworkEvent, StateReason};
+use crate::core::daemon_watch::daemon_events;
+use crate::dbus::{NMDeviceProxy, NMProxy, NMWirelessProxy};
+use crate::monitoring::checkpoint::checkpoint_events;
+use crate::types::constants::device_type;
+
+/// A signal source; `None` items are signals that could not be decoded.
+type EventSource = Pin<Box<dyn Stream<Item = Option<NetworkEvent>> + Send>>;
+
+/// The merged sources, with each device's sources keyed by its object path
+/// so they (and their match rules) are dropped when the device goes away.
+#[derive(Default)]
+struct Sources {
+    streams: SelectAll<EventSource>,
+    devices: HashMap<OwnedObjectPath, AbortHandle>,
+}
+
+impl Sources {
+    fn push(&mut self, source: EventSource) {
+        self.streams.push(source);
+    }
+
+    /// Subscribes to a device's signals.
+    async fn add_device(&mut self, conn: &Connection, path: OwnedObjectPath) {
+        let sources = device_sources(conn, path.clone()).await;
+        self.insert_device(path, sources);
+    }
+
+    /// Adds a device's sources, replacing any it already had.
+    fn insert_device(&mut self, path: OwnedObjectPath, sources: Vec<EventSource>) {
+        if sources.is_empty() {
+            return;
+        }
+        let (source, handle) = stream::abortable(stream::select_all(sources));
+        if let Some(stale) = self.devices.insert(path, handle) {
+            stale.abort();
+        }
+        self.streams.push(Box::pin(source));
+    }
+
+    /// Ends a device's sources; they leave the set on the next poll.
+    fn remove_device(&mut self, path: &OwnedObjectPath) {
+        if let Some(handle) = self.devices.remove(path) {
+            handle.abort();
+        }
+    }
+}
+
+/// Subscribes to all event sources and returns the merged stream.
+pub(crate) async fn network_events(
+    conn: &Connection,
+) -> Result<impl Stream<Item = NetworkEvent> + Send + Unpin + 'static> {
+    let sources = subscribe(conn).await?;
+
+    let events = stream::unfold((conn.clone(), sources), |(conn, mut sources)| async move {
+        loop {
+            let Some(event) = sources.streams.next().await? else {
+                continue;
+            };
+            match &event {
+                NetworkEvent::DeviceAdded { path } => sources.add_device(&conn, path.clone()).await,
+                NetworkEvent::DeviceRemoved { path } => sources.remove_device(path),
+                NetworkEvent::Daemon(DaemonEvent::Restarted) => match subscribe(&conn).await {
+                    Ok(fresh) => sources = fresh,
+                    Err(e) => warn!("Failed to re-subscribe after NetworkManager restart: {e}"),
+                },
+                _ => {}
+            }
+            return Some((event, (conn, sources)));
+        }
+    });
+
+    Ok(Box::pin(events))
+}
+
+/// Subscribes to the daemon, NetworkManager-wide and per-device sources.
+async fn subscribe(conn: &Connection) -> Result<Sources> {
+    let nm = NMProxy::new(conn).await?;
+    let mut sources = Sources::default();
+
+    sources.push(Box::pin(
+        daemon_events(conn)
+            .await?
+            .map(|e| Some(NetworkEvent::Daemon(e))),
+    ));
+
+    sources.push(Box::pin(nm.receive_device_added().await?.map(|s| {
+        s.args()
+            .ok()
+            .map(|a| NetworkEvent::DeviceAdded { path: a.device })
+    })));
+    sources.push(Box::pin(nm.receive_device_removed().await?.map(|s| {
+        s.args()
+            .ok()
+            .map(|a| NetworkEvent::DeviceRemoved { path: a.device })
+    })));
+    sources.push(Box::pin(
+        nm.receive_wireless_enabled_changed()
+            .await
+            .skip(1)
+            .then(|change| async move {
+                change
+                    .get()
+                    .await
+                    .ok()
+                    .map(NetworkEvent::WirelessEnabledChanged)
+            }),
+    ));
+    sources.push(Box::pin(
+        nm.receive_connectivity_changed()
+            .await
+            .skip(1)
+            .then(|change| async move {
+                change
+                    .get()
+                    .await
+                    .ok()
+                    .map(|c| NetworkEvent::ConnectivityChanged(ConnectivityState::from(c)))
+            }),
+    ));
+
+    // Checkpoints need NetworkManager 1.12; go on without them.
+    match checkpoint_events(conn).await {
+        Ok(events) => sources.push(Box::pin(events.map(|e| Some(NetworkEvent::Checkpoint(e))))),
+        Err(e) => debug!("Event stream runs without checkpoint events: {e}"),
+    }
+
+    for path in nm.get_devices().await? {
+        sources.add_device(conn, path).await;
+    }
+
+    debug!(
+        "Event stream subscribed to {} signal sources",
+        sources.streams.len()
+    );
+    Ok(sources)
+}
+
+/// State signals for one device, plus access point signals for Wi-Fi.
+///
+/// Failures are logged and yield fewer sources rather than an error, so a
+/// device vanishing mid-subscription does not break the stream.
+async fn device_sources(conn: &Connection, path: OwnedObjectPath) -> Vec<EventSource> {
+    match try_device_sources(conn, path.clone()).await {
+        Ok(sources) => sources,
+        Err(e) => {
+            debug!("Failed to subscribe to events of {}: {e}", path.as_str());
+            Vec::new()
+        }
+    }
+}
+
+async fn try_device_sources(
+    conn: &Connection,
+    path: OwnedObjectPath,
+) -> zbus::Result<Vec<EventSource>> {
+    let dev = NMDeviceProxy::builder(conn)
+        .path(path.clone())?
+        .build()
+        .await?;
+    let interface = dev.interface().await.unwrap_or_default();
+    let mut sources: Vec<EventSource> = Vec::new();
+
+    let device = path.clone();
+    sources.push(Box::pin(dev.receive_device_state_changed().await?.map(
+        move |s| {
+            s.args().ok().map(|a| NetworkEvent::ConnectionStateChanged {
+                device: device.clone(),
+                interface: interface.clone(),
+                state: DeviceState::from(a.new_state),
+                previous: DeviceState::from(a.old_state),
+                reason: StateReason::from(a.reason),
+            })
+        },
+    )));
+
+    if dev.device_type().await? == device_type::WIFI {
+        let wifi = NMWirelessProxy::builder(conn)
+            .path(path.clone())?
+            .build()
+            .await?;
+
+        let device = path.clone();
+        sources.push(Box::pin(wifi.receive_access_point_added().await?.map(
+            move |s| {
+                s.args().ok().map(|a| NetworkEvent::AccessPointAdded {
+                    device: device.clone(),
+                    path: a.path,
+                })
+            },
+        )));
+        let device = path;
+        sources.push(Box::pin(wifi.receive_access_point_removed().await?.map(
+            move |s| {
+                s.args().ok().map(|a| NetworkEvent::AccessPointRemoved {
+                    device: device.clone(),
+                    path: a.path,
+                })
+            },
+        )));
+    }
+
+    Ok(sources)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(p: &str) -> OwnedObjectPath {
+        OwnedObjectPath::try_from(p).unwrap()
+    }
+
+    #[tokio::test]
+    async fn removed_device_sources_are_dropped() {
+        let mut sources = Sources::default();
+        let wlan = path("/org/freedesktop/NetworkManager/Devices/3");
+        let eth = path("/org/freedesktop/NetworkManager/Devices/4");
+        sources.insert_device(wlan.clone(), vec![Box::pin(stream::pending())]);
+        sources.insert_device(
+            eth.clone(),
+            vec![Box::pin(stream::iter([None]).chain(stream::pending()))],
+        );
+        assert_eq!(sources.streams.len(), 2);
+
+        sources.remove_device(&wlan);
+        assert_eq!(sources.streams.next().await, Some(None));
+        assert_eq!(sources.streams.len(), 1);
+        assert!(!sources.devices.contains_key(&wlan));
+
+        sources.remove_device(&eth);
+        assert_eq!(sources.streams.next().await, None);
+        assert!(sources.streams.is_empty());
+    }
+}
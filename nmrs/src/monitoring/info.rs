@@ -7,7 +7,12 @@ use log::debug;
 use zbus::Connection;
 
 use crate::Result;
-use crate::api::models::{ConnectionError, Network, NetworkInfo};
+use crate::api::models::access_point::{decode_ciphers, decode_wpa_generation};
+use crate::api::models::{
+    ConnectionError, MatchOptions, Network, NetworkDetails, NetworkInfo, WifiStandard,
+};
+use crate::core::connection_settings::{all_saved_settings, profile_matches};
+use crate::core::scan::list_access_points;
 use crate::dbus::{NMAccessPointProxy, NMDeviceProxy, NMProxy, NMWirelessProxy};
 use crate::try_log;
 use crate::types::constants::{device_type, rate, security_flags};
@@ -25,6 +30,7 @@ use crate::util::utils::{
 /// - Wi-Fi mode (infrastructure, adhoc, etc.)
 /// - Connection speed (actual if connected, max otherwise)
 /// - Security capabilities (WEP, WPA, WPA2, PSK, 802.1X)
+/// - Pairwise/group cipher suites and management frame protection
 /// - Current connection status
 pub(crate) async fn show_details(conn: &Connection, net: &Network) -> Result<NetworkInfo> {
     let active_ssid = current_ssid(conn).await;
@@ -156,6 +162,7 @@ pub(crate) async fn show_details(conn: &Connection, net: &Network) -> Result<Net
                 rate_mbps,
                 bars,
                 security,
+                ciphers: decode_ciphers(wpa_flags, rsn_flags),
                 status,
                 ip4_address: None,
                 ip6_address: None,
@@ -176,6 +183,44 @@ pub(crate) async fn show_details(conn: &Connection, net: &Network) -> Result<Net
     Ok(info)
 }
 
+/// Returns the full details of the access point of `net` in use, or else
+/// its strongest one.
+///
+/// Access points are looked up again, so the result reflects the latest
+/// scan rather than the state `net` was built from.
+pub(crate) async fn network_details(conn: &Connection, net: &Network) -> Result<NetworkDetails> {
+    let interface = (!net.device.is_empty()).then_some(net.device.as_str());
+    let access_point = list_access_points(conn, interface)
+        .await?
+        .into_iter()
+        .filter(|ap| ap.ssid == net.ssid && ap.mode == net.mode)
+        .max_by_key(|ap| (ap.is_active, ap.strength))
+        .ok_or(ConnectionError::NotFound)?;
+
+    let ap = NMAccessPointProxy::builder(conn)
+        .path(access_point.path.clone())?
+        .build()
+        .await?;
+    let flags = ap.flags().await?;
+    let wpa_flags = ap.wpa_flags().await?;
+    let rsn_flags = ap.rsn_flags().await?;
+
+    let saved = !net.ssid.trim().is_empty()
+        && all_saved_settings(conn)
+            .await
+            .unwrap_or_default()
+            .iter()
+            .any(|p| profile_matches(p, &net.ssid, &MatchOptions::default()));
+
+    Ok(NetworkDetails {
+        ciphers: decode_ciphers(wpa_flags, rsn_flags),
+        wpa: decode_wpa_generation(flags, wpa_flags, rsn_flags),
+        standard: WifiStandard::guess(access_point.frequency_mhz, access_point.max_bitrate_kbps),
+        saved,
+        access_point,
+    })
+}
+
 /// Returns the SSID of the currently connected Wi-Fi network.
 ///
 /// Checks all Wi-Fi devices for an active access point and returns
@@ -0,0 +1,189 @@
+//! Link quality of a connected Wi-Fi device.
+//!
+//! Strength and transmit bitrate come from NetworkManager's `AccessPoint`
+//! and `Device.Wireless` objects. Levels in dBm, the receive bitrate and
+//! the retry and beacon-loss counters are only known to wpa_supplicant,
+//! which is asked through `SignalPoll`; when it is not reachable, or too
+//! old to report them, those fields stay `None`.
+
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+use futures::stream::{self, StreamExt};
+use log::debug;
+use zbus::Connection;
+use zvariant::OwnedValue;
+
+use crate::Result;
+use crate::api::models::LinkStats;
+use crate::api::stats_collector::StatsCollector;
+use crate::core::connection::resolve_wifi_device;
+use crate::dbus::{
+    NMAccessPointProxy, NMDeviceProxy, NMWirelessProxy, WpaSupplicantInterfaceProxy,
+    WpaSupplicantProxy,
+};
+use crate::monitoring::signal::signal_strength;
+use crate::util::utils::decode_ssid_or_hidden;
+
+/// Values read from wpa_supplicant's `SignalPoll`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct SignalPoll {
+    signal_dbm: Option<i32>,
+    avg_signal_dbm: Option<i32>,
+    noise_dbm: Option<i32>,
+    rx_bitrate_kbps: Option<u32>,
+    tx_retries: Option<u32>,
+    tx_failed: Option<u32>,
+    beacon_losses: Option<u32>,
+}
+
+/// Current link quality of Wi-Fi device `interface`, or `None` if it is
+/// not associated with an access point.
+pub(crate) async fn link_stats(conn: &Connection, interface: &str) -> Result<Option<LinkStats>> {
+    let device = resolve_wifi_device(conn, Some(interface)).await?;
+    let wifi = NMWirelessProxy::builder(conn).path(device)?.build().await?;
+
+    let active_ap = wifi.active_access_point().await?;
+    if active_ap.as_str() == "/" {
+        return Ok(None);
+    }
+    let ap = NMAccessPointProxy::builder(conn)
+        .path(active_ap)?
+        .build()
+        .await?;
+
+    let poll = signal_poll(conn, interface).await;
+    Ok(Some(LinkStats {
+        interface: interface.to_string(),
+        ssid: decode_ssid_or_hidden(&ap.ssid().await?).into_owned(),
+        bssid: ap.hw_address().await?,
+        frequency_mhz: ap.frequency().await?,
+        strength: ap.strength().await?,
+        tx_bitrate_kbps: wifi.bitrate().await?,
+        signal_dbm: poll.signal_dbm,
+        avg_signal_dbm: poll.avg_signal_dbm,
+        noise_dbm: poll.noise_dbm,
+        rx_bitrate_kbps: poll.rx_bitrate_kbps,
+        tx_retries: poll.tx_retries,
+        tx_failed: poll.tx_failed,
+        beacon_losses: poll.beacon_losses,
+        sampled_at: SystemTime::now(),
+    }))
+}
+
+/// Records a sample of `interface` into `collector` now and whenever its
+/// signal strength or bitrate changes. Returns once the change streams end.
+pub(crate) async fn collect_link_stats(
+    conn: &Connection,
+    interface: &str,
+    collector: StatsCollector,
+) -> Result<()> {
+    let device = resolve_wifi_device(conn, Some(interface)).await?;
+    let dev = NMDeviceProxy::builder(conn)
+        .path(device.clone())?
+        .build()
+        .await?;
+    let wifi = NMWirelessProxy::builder(conn).path(device)?.build().await?;
+
+    // Both streams yield the current value first, which takes the initial
+    // sample; the strength stream follows roams to another access point.
+    let strength = signal_strength(conn, interface).await?.map(|_| ());
+    let bitrate = wifi.receive_bitrate_changed().await.map(|_| ());
+    let mut changes = stream::select(strength, bitrate);
+
+    while changes.next().await.is_some() {
+        match link_stats(conn, interface).await {
+            Ok(Some(stats)) => collector.record(stats),
+            Ok(None) => {}
+            Err(e) => {
+                // The device may have been removed; stop if so.
+                dev.interface().await?;
+                debug!("Skipping link sample for {interface}: {e}");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn signal_poll(conn: &Connection, interface: &str) -> SignalPoll {
+    let poll = async {
+        let supplicant = WpaSupplicantProxy::new(conn).await?;
+        let path = supplicant.get_interface(interface).await?;
+        WpaSupplicantInterfaceProxy::builder(conn)
+            .path(path)?
+            .build()
+            .await?
+            .signal_poll()
+            .await
+    };
+    match poll.await {
+        Ok(values) => parse_signal_poll(&values),
+        Err(e) => {
+            debug!("SignalPoll on {interface} failed, no supplicant link data: {e}");
+            SignalPoll::default()
+        }
+    }
+}
+
+fn parse_signal_poll(values: &HashMap<String, OwnedValue>) -> SignalPoll {
+    let level = |key: &str| {
+        values
+            .get(key)
+            .and_then(|v| i32::try_from(v).ok())
+            // The driver reports 0 when it has no measurement.
+            .filter(|&dbm| dbm != 0)
+    };
+    // Only reported when non-zero, as u32 by current wpa_supplicant.
+    let counter = |key: &str| {
+        let value = values.get(key)?;
+        u32::try_from(value)
+            .ok()
+            .or_else(|| u64::try_from(value).ok().map(saturate))
+    };
+    SignalPoll {
+        signal_dbm: level("rssi"),
+        avg_signal_dbm: level("avg-rssi"),
+        noise_dbm: level("noise"),
+        // In Mbit/s, like `linkspeed`.
+        rx_bitrate_kbps: counter("linkrxspeed").map(|mbps| mbps.saturating_mul(1000)),
+        tx_retries: counter("retries"),
+        tx_failed: counter("retries-failed"),
+        beacon_losses: counter("beacon-losses"),
+    }
+}
+
+fn saturate(value: u64) -> u32 {
+    u32::try_from(value).unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_signal_poll_levels() {
+        let values = HashMap::from([
+            ("rssi".to_string(), OwnedValue::from(-58i32)),
+            ("avg-rssi".to_string(), OwnedValue::from(-61i32)),
+            ("noise".to_string(), OwnedValue::from(0i32)),
+            ("linkspeed".to_string(), OwnedValue::from(866u32)),
+            ("linkrxspeed".to_string(), OwnedValue::from(650u32)),
+            ("retries".to_string(), OwnedValue::from(42u32)),
+            ("beacon-losses".to_string(), OwnedValue::from(3u64)),
+        ]);
+        assert_eq!(
+            parse_signal_poll(&values),
+            SignalPoll {
+                signal_dbm: Some(-58),
+                avg_signal_dbm: Some(-61),
+                noise_dbm: None,
+                rx_bitrate_kbps: Some(650_000),
+                tx_retries: Some(42),
+                tx_failed: None,
+                beacon_losses: Some(3),
+            }
+        );
+        assert_eq!(parse_signal_poll(&HashMap::new()), SignalPoll::default());
+    }
+}
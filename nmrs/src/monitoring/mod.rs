@@ -4,8 +4,16 @@
 //! device state changes, and retrieving current connection information.
 
 pub(crate) mod bluetooth;
+pub(crate) mod checkpoint;
 pub(crate) mod device;
+pub(crate) mod events;
 pub(crate) mod info;
+pub(crate) mod link_stats;
 pub(crate) mod network;
+pub(crate) mod resume;
+pub(crate) mod signal;
+pub(crate) mod traffic;
 pub(crate) mod transport;
+#[cfg(feature = "usage-history")]
+pub(crate) mod usage;
 pub(crate) mod wifi;
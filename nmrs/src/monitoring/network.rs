@@ -4,10 +4,9 @@
 //! and signal strength changes in real-time without needing to poll. This
 //! enables live UI updates.
 
-use futures::stream::{Stream, StreamExt};
+use futures::stream::{BoxStream, StreamExt};
 use log::{debug, warn};
 use std::collections::HashSet;
-use std::pin::Pin;
 use tokio::select;
 use tokio::sync::watch;
 use zbus::Connection;
@@ -15,23 +14,31 @@ use zvariant::OwnedObjectPath;
 
 use crate::Result;
 use crate::api::models::ConnectionError;
+use crate::core::connection::resolve_wifi_device;
+use crate::core::daemon_watch::{Resubscribed, Resubscribing};
 use crate::dbus::{NMAccessPointProxy, NMDeviceProxy, NMProxy, NMWirelessProxy};
 use crate::types::constants::device_type;
 
-type NetworkChangeStream = Pin<Box<dyn Stream<Item = NetworkChange> + Send>>;
+type NetworkChangeStream = BoxStream<'static, NetworkChange>;
 
 enum NetworkChange {
     Added(OwnedObjectPath),
     Removed(OwnedObjectPath),
     SignalStrengthChanged,
+    DeviceAdded(OwnedObjectPath),
 }
 
-/// Monitors access point changes on all Wi-Fi devices.
+/// Access points whose signal strength is being watched, by path.
+type MonitoredAccessPoints = HashSet<String>;
+
+/// Monitors access point changes on all Wi-Fi devices, or only on `interface`.
 ///
-/// Subscribes to `AccessPointAdded` and `AccessPointRemoved` signals on all
-/// wireless devices, plus `Strength` property changes on visible access points.
+/// Subscribes to `AccessPointAdded` and `AccessPointRemoved` signals on the
+/// selected wireless devices, plus `Strength` property changes on visible access points.
 /// When any signal is received, invokes the callback to notify the caller that
-/// the network list or signal data has changed.
+/// the network list or signal data has changed. Wi-Fi devices added later are
+/// picked up, and after a NetworkManager restart every subscription is
+/// re-established and the callback invoked once.
 ///
 /// This function runs indefinitely until an error occurs or the connection
 /// is lost. Run it in a background task.
@@ -46,92 +53,31 @@ enum NetworkChange {
 /// ```
 pub async fn monitor_network_changes<F>(
     conn: &Connection,
+    interface: Option<&str>,
     mut shutdown: watch::Receiver<()>,
     callback: F,
 ) -> Result<()>
 where
     F: Fn() + Send + 'static,
 {
-    let nm = NMProxy::new(conn).await?;
-    let devices = nm.get_devices().await?;
-
-    // Use dynamic dispatch to handle different signal stream types
-    let mut streams: Vec<NetworkChangeStream> = Vec::new();
-    let mut monitored_access_points = HashSet::new();
-
-    // Subscribe to signals on all Wi-Fi devices
-    for dev_path in devices {
-        let dev = NMDeviceProxy::builder(conn)
-            .path(dev_path.clone())?
-            .build()
-            .await?;
-
-        if dev.device_type().await? != device_type::WIFI {
-            continue;
-        }
-
-        let wifi = NMWirelessProxy::builder(conn)
-            .path(dev_path.clone())?
-            .build()
-            .await?;
-
-        let added_stream = wifi.receive_access_point_added().await?;
-        let removed_stream = wifi.receive_access_point_removed().await?;
-
-        streams.push(Box::pin(added_stream.map(|signal| {
-            signal.args().map_or_else(
-                |err| {
-                    debug!("Failed to parse AccessPointAdded signal: {err}");
-                    NetworkChange::SignalStrengthChanged
-                },
-                |args| NetworkChange::Added(args.path().clone()),
-            )
-        })));
-        streams.push(Box::pin(removed_stream.map(|signal| {
-            signal.args().map_or_else(
-                |err| {
-                    debug!("Failed to parse AccessPointRemoved signal: {err}");
-                    NetworkChange::SignalStrengthChanged
-                },
-                |args| NetworkChange::Removed(args.path().clone()),
-            )
-        })));
-
-        match wifi.access_points().await {
-            Ok(ap_paths) => {
-                for ap_path in ap_paths {
-                    if !monitored_access_points.insert(ap_path.to_string()) {
-                        continue;
-                    }
-
-                    match access_point_strength_stream(conn, ap_path.clone()).await {
-                        Ok(stream) => streams.push(stream),
-                        Err(err) => debug!(
-                            "Failed to monitor signal strength for access point {}: {}",
-                            ap_path, err
-                        ),
-                    }
-                }
-            }
-            Err(err) => debug!("Failed to list access points on device {dev_path}: {err}"),
-        }
-
-        debug!("Subscribed to network change signals on device: {dev_path}");
+    if interface.is_some() {
+        resolve_wifi_device(conn, interface).await?;
     }
 
-    if streams.is_empty() {
+    let interface = interface.map(str::to_string);
+    let (mut merged, (devices, mut monitored_access_points)) =
+        Resubscribing::new(conn, || subscribe(conn, interface.as_deref())).await?;
+
+    if devices == 0 {
         warn!("No Wi-Fi devices found to monitor");
         return Err(ConnectionError::NoWifiDevice);
     }
 
     debug!(
         "Monitoring {} signal streams for network changes",
-        streams.len()
+        merged.len()
     );
 
-    // Merge all streams and listen for any signal
-    let mut merged = futures::stream::select_all(streams);
-
     loop {
         select! {
             _ = shutdown.changed() => {
@@ -140,7 +86,7 @@ where
             }
             signal = merged.next() => {
                 match signal {
-                    Some(NetworkChange::Added(path)) => {
+                    Some(Resubscribed::Signal(NetworkChange::Added(path))) => {
                         if monitored_access_points.insert(path.to_string()) {
                             match access_point_strength_stream(conn, path.clone()).await {
                                 Ok(stream) => merged.push(stream),
@@ -152,11 +98,30 @@ where
                         }
                         callback();
                     }
-                    Some(NetworkChange::Removed(path)) => {
+                    Some(Resubscribed::Signal(NetworkChange::Removed(path))) => {
                         monitored_access_points.remove(path.as_str());
                         callback();
                     }
-                    Some(NetworkChange::SignalStrengthChanged) => callback(),
+                    Some(Resubscribed::Signal(NetworkChange::SignalStrengthChanged)) => callback(),
+                    Some(Resubscribed::Signal(NetworkChange::DeviceAdded(path))) => {
+                        let streams = device_streams(
+                            conn,
+                            path,
+                            interface.as_deref(),
+                            &mut monitored_access_points,
+                        )
+                        .await;
+                        if !streams.is_empty() {
+                            for stream in streams {
+                                merged.push(stream);
+                            }
+                            callback();
+                        }
+                    }
+                    Some(Resubscribed::Restarted((_, access_points))) => {
+                        monitored_access_points = access_points;
+                        callback();
+                    }
                     None => break,
                 }
             }
@@ -171,6 +136,128 @@ where
     Err(ConnectionError::Stuck("monitoring stream ended".into()))
 }
 
+/// Subscribes to device additions and to the selected Wi-Fi devices.
+///
+/// Returns the streams, the number of Wi-Fi devices subscribed to and the
+/// access points whose strength is watched.
+async fn subscribe(
+    conn: &Connection,
+    interface: Option<&str>,
+) -> Result<(Vec<NetworkChangeStream>, (usize, MonitoredAccessPoints))> {
+    let nm = NMProxy::new(conn).await?;
+    let mut streams: Vec<NetworkChangeStream> = vec![
+        nm.receive_device_added()
+            .await?
+            .filter_map(|signal| async move {
+                signal
+                    .args()
+                    .ok()
+                    .map(|args| NetworkChange::DeviceAdded(args.device))
+            })
+            .boxed(),
+    ];
+    let mut monitored_access_points = HashSet::new();
+    let mut devices = 0;
+
+    for dev_path in nm.get_devices().await? {
+        let device = device_streams(conn, dev_path, interface, &mut monitored_access_points).await;
+        if !device.is_empty() {
+            devices += 1;
+            streams.extend(device);
+        }
+    }
+
+    Ok((streams, (devices, monitored_access_points)))
+}
+
+/// Access point and strength streams of `dev_path`, or none if it is not a
+/// selected Wi-Fi device or could not be subscribed to.
+async fn device_streams(
+    conn: &Connection,
+    dev_path: OwnedObjectPath,
+    interface: Option<&str>,
+    monitored_access_points: &mut MonitoredAccessPoints,
+) -> Vec<NetworkChangeStream> {
+    match try_device_streams(conn, dev_path.clone(), interface, monitored_access_points).await {
+        Ok(streams) => streams,
+        Err(err) => {
+            debug!("Failed to subscribe to network changes on device {dev_path}: {err}");
+            Vec::new()
+        }
+    }
+}
+
+async fn try_device_streams(
+    conn: &Connection,
+    dev_path: OwnedObjectPath,
+    interface: Option<&str>,
+    monitored_access_points: &mut MonitoredAccessPoints,
+) -> Result<Vec<NetworkChangeStream>> {
+    let dev = NMDeviceProxy::builder(conn)
+        .path(dev_path.clone())?
+        .build()
+        .await?;
+
+    if dev.device_type().await? != device_type::WIFI {
+        return Ok(Vec::new());
+    }
+    if let Some(name) = interface
+        && dev.interface().await? != name
+    {
+        return Ok(Vec::new());
+    }
+
+    let wifi = NMWirelessProxy::builder(conn)
+        .path(dev_path.clone())?
+        .build()
+        .await?;
+
+    let added_stream = wifi.receive_access_point_added().await?;
+    let removed_stream = wifi.receive_access_point_removed().await?;
+
+    let mut streams: Vec<NetworkChangeStream> = Vec::new();
+    streams.push(Box::pin(added_stream.map(|signal| {
+        signal.args().map_or_else(
+            |err| {
+                debug!("Failed to parse AccessPointAdded signal: {err}");
+                NetworkChange::SignalStrengthChanged
+            },
+            |args| NetworkChange::Added(args.path().clone()),
+        )
+    })));
+    streams.push(Box::pin(removed_stream.map(|signal| {
+        signal.args().map_or_else(
+            |err| {
+                debug!("Failed to parse AccessPointRemoved signal: {err}");
+                NetworkChange::SignalStrengthChanged
+            },
+            |args| NetworkChange::Removed(args.path().clone()),
+        )
+    })));
+
+    match wifi.access_points().await {
+        Ok(ap_paths) => {
+            for ap_path in ap_paths {
+                if !monitored_access_points.insert(ap_path.to_string()) {
+                    continue;
+                }
+
+                match access_point_strength_stream(conn, ap_path.clone()).await {
+                    Ok(stream) => streams.push(stream),
+                    Err(err) => debug!(
+                        "Failed to monitor signal strength for access point {}: {}",
+                        ap_path, err
+                    ),
+                }
+            }
+        }
+        Err(err) => debug!("Failed to list access points on device {dev_path}: {err}"),
+    }
+
+    debug!("Subscribed to network change signals on device: {dev_path}");
+    Ok(streams)
+}
+
 async fn access_point_strength_stream(
     conn: &Connection,
     ap_path: OwnedObjectPath,
@@ -0,0 +1,104 @@
+//! Post-resume revalidation driven by systemd-logind.
+//!
+//! After suspend, NetworkManager's scan list is out of date and the active
+//! connection may have silently died. This module listens for logind's
+//! `PrepareForSleep(false)` signal and re-checks both.
+
+use futures::StreamExt;
+use log::{debug, warn};
+use tokio::select;
+use tokio::sync::watch;
+use zbus::Connection;
+
+use crate::Result;
+use crate::api::models::{ConnectionError, ConnectivityState, ResumeReport};
+use crate::core::active_connection::primary_connection;
+use crate::core::connectivity::{check_connectivity, connectivity};
+use crate::core::scan::scan_networks;
+use crate::dbus::{Login1ManagerProxy, dbus_operation};
+
+/// Waits for resume-from-suspend and runs [`check_after_resume`] each time.
+///
+/// Suspend notifications (`PrepareForSleep(true)`) are ignored. Runs until
+/// `shutdown` fires or the signal stream ends.
+pub(crate) async fn resume_hook<F>(
+    conn: &Connection,
+    mut shutdown: watch::Receiver<()>,
+    callback: F,
+) -> Result<()>
+where
+    F: Fn(ResumeReport) + Send + 'static,
+{
+    let login1 = Login1ManagerProxy::new(conn)
+        .await
+        .map_err(|e| dbus_operation("connect to systemd-logind", e))?;
+    let mut sleep_signals = login1
+        .receive_prepare_for_sleep()
+        .await
+        .map_err(|e| dbus_operation("subscribe to PrepareForSleep", e))?;
+
+    debug!("Resume hook subscribed to logind PrepareForSleep");
+
+    loop {
+        select! {
+            _ = shutdown.changed() => {
+                debug!("Resume hook shutting down");
+                return Ok(());
+            }
+            signal = sleep_signals.next() => {
+                let Some(signal) = signal else {
+                    return Err(ConnectionError::Stuck("logind signal stream ended".into()));
+                };
+                match signal.args() {
+                    Ok(args) if !*args.start() => {
+                        debug!("Resumed from suspend, revalidating network state");
+                        callback(check_after_resume(conn).await);
+                    }
+                    Ok(_) => debug!("System is about to suspend"),
+                    Err(e) => warn!("Failed to parse PrepareForSleep signal: {e}"),
+                }
+            }
+        }
+    }
+}
+
+/// Rescans Wi-Fi and re-validates the primary connection.
+///
+/// Individual failures are logged and reflected in the report rather than
+/// aborting the check, since partial information is still useful here.
+pub(crate) async fn check_after_resume(conn: &Connection) -> ResumeReport {
+    let rescanned = match scan_networks(conn, None).await {
+        Ok(()) => true,
+        Err(e) => {
+            debug!("Post-resume rescan failed: {e}");
+            false
+        }
+    };
+
+    let connectivity = match check_connectivity(conn).await {
+        Ok(state) => state,
+        Err(ConnectionError::ConnectivityCheckDisabled) => connectivity(conn)
+            .await
+            .unwrap_or(ConnectivityState::Unknown),
+        Err(e) => {
+            debug!("Post-resume connectivity check failed: {e}");
+            ConnectivityState::Unknown
+        }
+    };
+
+    let (primary_connection, primary_state) = match primary_connection(conn).await {
+        Ok(Some(primary)) => (Some(primary.connection.id), Some(primary.connection.state)),
+        Ok(None) => (None, None),
+        Err(e) => {
+            debug!("Failed to read primary connection after resume: {e}");
+            (None, None)
+        }
+    };
+
+    ResumeReport {
+        rescanned,
+        connectivity,
+        primary_connection,
+        primary_state,
+    }
+}
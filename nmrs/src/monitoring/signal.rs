@@ -0,0 +1,137 @@
+//! Live signal strength of the access point a Wi-Fi device is using.
+//!
+//! Instead of rescanning on a timer, this subscribes to `PropertiesChanged`
+//! on the device's active access point and yields every new `Strength`.
+//! The device's `ActiveAccessPoint` is watched too, so the stream follows
+//! roams to another BSSID.
+
+use futures::stream::{self, Stream, StreamExt};
+use log::debug;
+use std::pin::Pin;
+use zbus::Connection;
+use zvariant::OwnedObjectPath;
+
+use crate::Result;
+use crate::api::models::ConnectionError;
+use crate::core::connection::resolve_wifi_device;
+use crate::dbus::{NMAccessPointProxy, NMDeviceProxy, NMProxy, NMWirelessProxy};
+use crate::types::constants::device_type;
+use crate::util::utils::decode_ssid_or_empty;
+
+enum Update {
+    Roamed(OwnedObjectPath),
+    Strength(u8),
+}
+
+/// A property source; `None` items are changes that could not be read.
+type UpdateSource = Pin<Box<dyn Stream<Item = Option<Update>> + Send>>;
+
+/// Streams the signal strength of the access point used by `target`.
+///
+/// `target` is a Wi-Fi interface name or the SSID of an active Wi-Fi
+/// connection. The current strength is yielded first. While the device is
+/// disconnected nothing is yielded; updates resume when it associates again.
+pub(crate) async fn signal_strength(
+    conn: &Connection,
+    target: &str,
+) -> Result<impl Stream<Item = u8> + Send + Unpin + 'static> {
+    let device = resolve_device(conn, target).await?;
+    let wifi = NMWirelessProxy::builder(conn).path(device)?.build().await?;
+
+    // Not skipped: the first item is the current access point, which
+    // subscribes to it and yields its current strength.
+    let roams: UpdateSource = Box::pin(
+        wifi.receive_active_access_point_changed()
+            .await
+            .then(|change| async move { change.get().await.ok().map(Update::Roamed) }),
+    );
+
+    let updates = stream::unfold(
+        (conn.clone(), roams, None::<UpdateSource>),
+        |(conn, mut roams, mut strength)| async move {
+            loop {
+                let update = match strength.as_mut() {
+                    Some(current) => tokio::select! {
+                        update = roams.next() => update?,
+                        update = current.next() => match update {
+                            Some(update) => update,
+                            None => {
+                                strength = None;
+                                continue;
+                            }
+                        },
+                    },
+                    None => roams.next().await?,
+                };
+                match update {
+                    Some(Update::Roamed(ap)) => strength = strength_source(&conn, ap).await,
+                    Some(Update::Strength(s)) => return Some((s, (conn, roams, strength))),
+                    None => {}
+                }
+            }
+        },
+    );
+
+    Ok(Box::pin(updates))
+}
+
+/// Subscribes to `Strength` of `ap`; `None` when disconnected or the access
+/// point vanished before the subscription was made.
+async fn strength_source(conn: &Connection, ap: OwnedObjectPath) -> Option<UpdateSource> {
+    if ap.as_str() == "/" {
+        return None;
+    }
+    let proxy = match NMAccessPointProxy::builder(conn).path(ap.clone()) {
+        Ok(builder) => builder.build().await,
+        Err(e) => Err(e),
+    };
+    match proxy {
+        Ok(proxy) => {
+            debug!("Watching signal strength of {}", ap.as_str());
+            Some(Box::pin(proxy.receive_strength_changed().await.then(
+                |change| async move { change.get().await.ok().map(Update::Strength) },
+            )))
+        }
+        Err(e) => {
+            debug!("Failed to watch access point {}: {e}", ap.as_str());
+            None
+        }
+    }
+}
+
+/// Finds the Wi-Fi device named `target`, or else the one connected to the
+/// SSID `target`.
+async fn resolve_device(conn: &Connection, target: &str) -> Result<OwnedObjectPath> {
+    let nm = NMProxy::new(conn).await?;
+    match resolve_wifi_device(conn, Some(target)).await {
+        Err(ConnectionError::WifiInterfaceNotFound { .. }) => {}
+        other => return other,
+    }
+
+    for path in nm.get_devices().await? {
+        let dev = NMDeviceProxy::builder(conn)
+            .path(path.clone())?
+            .build()
+            .await?;
+        if dev.device_type().await? != device_type::WIFI {
+            continue;
+        }
+        let wifi = NMWirelessProxy::builder(conn)
+            .path(path.clone())?
+            .build()
+            .await?;
+        let active_ap = wifi.active_access_point().await?;
+        if active_ap.as_str() == "/" {
+            continue;
+        }
+        let ap = NMAccessPointProxy::builder(conn)
+            .path(active_ap)?
+            .build()
+            .await?;
+        if decode_ssid_or_empty(&ap.ssid().await?) == target {
+            return Ok(path);
+        }
+    }
+
+    Err(ConnectionError::NotFound)
+}
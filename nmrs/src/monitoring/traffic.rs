@@ -0,0 +1,125 @@
+//! Live traffic counters of a device.
+//!
+//! NetworkManager only updates `Device.Statistics` while the device's
+//! `RefreshRateMs` is non-zero, so reading or watching a device enables
+//! refresh on it when it is off. The rate is left in place afterwards, as
+//! other clients may rely on it.
+
+use std::time::{Duration, Instant};
+
+use futures::stream::{self, Stream};
+use futures_timer::Delay;
+use log::debug;
+use zbus::Connection;
+
+use crate::Result;
+use crate::api::models::{TrafficDelta, TrafficStats};
+use crate::core::connection::get_device_by_interface;
+use crate::dbus::NMDeviceStatisticsProxy;
+
+/// Refresh interval set on devices that have counter refresh disabled,
+/// and the sampling interval of [`watch_traffic`].
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Reads the byte counters of `interface`.
+pub(crate) async fn traffic_stats(conn: &Connection, interface: &str) -> Result<TrafficStats> {
+    let stats = statistics_proxy(conn, interface).await?;
+    let (rx_bytes, tx_bytes) = read_counters(&stats).await?;
+    Ok(TrafficStats {
+        interface: interface.to_string(),
+        rx_bytes,
+        tx_bytes,
+    })
+}
+
+/// Streams the traffic of `interface` once per [`REFRESH_INTERVAL`].
+///
+/// The first item covers the first interval after subscribing. A counter
+/// reset skips one interval. The stream ends when the device goes away.
+pub(crate) async fn watch_traffic(
+    conn: &Connection,
+    interface: &str,
+) -> Result<impl Stream<Item = TrafficDelta> + Send + Unpin + use<>> {
+    let stats = statistics_proxy(conn, interface).await?;
+    let baseline = read_counters(&stats).await?;
+
+    let state = (stats, baseline, Instant::now(), interface.to_string());
+    Ok(Box::pin(stream::unfold(
+        state,
+        |(stats, mut prev, mut at, interface)| async move {
+            loop {
+                // Sample one interval after the previous read.
+                Delay::new(REFRESH_INTERVAL.saturating_sub(at.elapsed())).await;
+                let current = match read_counters(&stats).await {
+                    Ok(counters) => counters,
+                    Err(e) => {
+                        debug!("Stopped watching traffic of {interface}: {e}");
+                        return None;
+                    }
+                };
+                let now = Instant::now();
+                let delta = counter_delta(prev, current);
+                let elapsed = now - at;
+                (prev, at) = (current, now);
+
+                if let Some((rx_bytes, tx_bytes)) = delta {
+                    let item = TrafficDelta {
+                        interface: interface.clone(),
+                        rx_bytes,
+                        tx_bytes,
+                        elapsed,
+                    };
+                    return Some((item, (stats, prev, at, interface)));
+                }
+            }
+        },
+    )))
+}
+
+/// Builds the statistics proxy of `interface`, enabling counter refresh.
+async fn statistics_proxy(
+    conn: &Connection,
+    interface: &str,
+) -> Result<NMDeviceStatisticsProxy<'static>> {
+    let path = get_device_by_interface(conn, interface).await?;
+    let stats = NMDeviceStatisticsProxy::builder(conn)
+        .path(path)?
+        .build()
+        .await?;
+    if stats.refresh_rate_ms().await? == 0 {
+        let ms = u32::try_from(REFRESH_INTERVAL.as_millis()).unwrap_or(u32::MAX);
+        stats.set_refresh_rate_ms(ms).await?;
+        debug!("Enabled traffic counter refresh on {interface}");
+    }
+    Ok(stats)
+}
+
+async fn read_counters(stats: &NMDeviceStatisticsProxy<'_>) -> zbus::Result<(u64, u64)> {
+    Ok((stats.rx_bytes().await?, stats.tx_bytes().await?))
+}
+
+/// `(rx, tx)` bytes between two readings, or `None` if the counters were
+/// reset in between.
+fn counter_delta(prev: (u64, u64), current: (u64, u64)) -> Option<(u64, u64)> {
+    Some((
+        current.0.checked_sub(prev.0)?,
+        current.1.checked_sub(prev.1)?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delta_between_readings() {
+        assert_eq!(counter_delta((1_000, 200), (1_500, 260)), Some((500, 60)));
+        assert_eq!(counter_delta((1_000, 200), (1_000, 200)), Some((0, 0)));
+    }
+
+    #[test]
+    fn reset_counters_yield_no_delta() {
+        assert_eq!(counter_delta((1_000, 200), (10, 260)), None);
+        assert_eq!(counter_delta((1_000, 200), (1_500, 5)), None);
+    }
+}
@@ -0,0 +1,160 @@
+//! Periodic sampling of device traffic counters into a
+//! [`UsageStore`](crate::usage::UsageStore).
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use futures_timer::Delay;
+use log::{debug, warn};
+use tokio::select;
+use tokio::sync::watch;
+use zbus::Connection;
+use zvariant::OwnedObjectPath;
+
+use crate::Result;
+use crate::api::models::DeviceType;
+use crate::dbus::{NMActiveConnectionProxy, NMDeviceProxy, NMDeviceStatisticsProxy, NMProxy};
+use crate::usage::{UsageDate, UsageStore};
+
+/// Last counter reading of a device and the profile it was attributed to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Sample {
+    profile: String,
+    rx: u64,
+    tx: u64,
+}
+
+/// Samples every `interval` until `shutdown` fires, flushing the store
+/// after each round.
+pub(crate) async fn track_usage(
+    conn: &Connection,
+    store: UsageStore,
+    interval: Duration,
+    mut shutdown: watch::Receiver<()>,
+) -> Result<()> {
+    let nm = NMProxy::new(conn).await?;
+    let refresh_ms = u32::try_from(interval.as_millis()).unwrap_or(u32::MAX);
+    let mut last: HashMap<OwnedObjectPath, Sample> = HashMap::new();
+
+    loop {
+        let devices = nm.get_devices().await?;
+        last.retain(|path, _| devices.contains(path));
+
+        for path in devices {
+            match read_device(conn, path.clone(), refresh_ms).await {
+                Ok(Some(sample)) => {
+                    let (rx, tx) = counter_delta(last.get(&path), &sample);
+                    store.record(UsageDate::today(), &sample.profile, rx, tx);
+                    last.insert(path, sample);
+                }
+                Ok(None) => {
+                    last.remove(&path);
+                }
+                Err(e) => debug!("Skipping usage sample for {}: {e}", path.as_str()),
+            }
+        }
+
+        if let Err(e) = store.flush() {
+            warn!("Failed to write usage store: {e}");
+        }
+
+        select! {
+            _ = shutdown.changed() => {
+                debug!("Usage tracking shutdown requested");
+                return Ok(());
+            }
+            () = Delay::new(interval) => {}
+        }
+    }
+}
+
+/// Reads the counters of a device with an active connection.
+///
+/// Returns `None` for loopback devices and devices without an active
+/// connection. Enables counter refresh on the device if it is off.
+async fn read_device(
+    conn: &Connection,
+    path: OwnedObjectPath,
+    refresh_ms: u32,
+) -> zbus::Result<Option<Sample>> {
+    let dev = NMDeviceProxy::builder(conn)
+        .path(path.clone())?
+        .build()
+        .await?;
+    if matches!(
+        DeviceType::from(dev.device_type().await?),
+        DeviceType::Loopback
+    ) {
+        return Ok(None);
+    }
+
+    let ac_path = dev.active_connection().await?;
+    if ac_path.as_str() == "/" {
+        return Ok(None);
+    }
+    let ac = NMActiveConnectionProxy::builder(conn)
+        .path(ac_path)?
+        .build()
+        .await?;
+    let profile = ac.id().await?;
+
+    let stats = NMDeviceStatisticsProxy::builder(conn)
+        .path(path)?
+        .build()
+        .await?;
+    if stats.refresh_rate_ms().await? == 0 {
+        stats.set_refresh_rate_ms(refresh_ms).await?;
+    }
+
+    Ok(Some(Sample {
+        profile,
+        rx: stats.rx_bytes().await?,
+        tx: stats.tx_bytes().await?,
+    }))
+}
+
+/// Traffic between two readings of the same device.
+///
+/// The first reading, a profile switch and a counter reset only set a new
+/// baseline, so traffic from before tracking started is never counted.
+fn counter_delta(prev: Option<&Sample>, current: &Sample) -> (u64, u64) {
+    match prev {
+        Some(p) if p.profile == current.profile && current.rx >= p.rx && current.tx >= p.tx => {
+            (current.rx - p.rx, current.tx - p.tx)
+        }
+        _ => (0, 0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(profile: &str, rx: u64, tx: u64) -> Sample {
+        Sample {
+            profile: profile.into(),
+            rx,
+            tx,
+        }
+    }
+
+    #[test]
+    fn delta_between_readings() {
+        let prev = sample("Home", 1_000, 200);
+        assert_eq!(
+            counter_delta(Some(&prev), &sample("Home", 1_500, 260)),
+            (500, 60)
+        );
+    }
+
+    #[test]
+    fn first_reading_and_resets_only_set_baseline() {
+        assert_eq!(counter_delta(None, &sample("Home", 1_500, 260)), (0, 0));
+        let prev = sample("Home", 1_000, 200);
+        assert_eq!(counter_delta(Some(&prev), &sample("Home", 10, 5)), (0, 0));
+        assert_eq!(
+            counter_delta(Some(&prev), &sample("Cafe", 2_000, 300)),
+            (0, 0)
+        );
+    }
+}
@@ -0,0 +1,350 @@
+//! Wi-Fi QR codes ("share network" / "join from QR").
+//!
+//! Phones and most network settings UIs share Wi-Fi credentials as a QR
+//! code holding a `WIFI:` payload such as `WIFI:S:Home;T:WPA;P:hunter22;;`.
+//! [`WifiQrCode`](crate::qr::WifiQrCode) builds that payload and parses it
+//! back. Rendering the payload as an actual QR image is left to a QR library.
+//!
+//! Special characters (`\`, `;`, `,`, `:` and `"`) are backslash-escaped,
+//! and values that would read as hexadecimal are quoted, as the format
+//! requires. Enterprise (802.1X) credentials cannot be represented.
+//!
+//! # Example
+//!
+//! ```rust
+//! use nmrs::WifiSecurity;
+//! use nmrs::qr::WifiQrCode;
+//!
+//! let code = WifiQrCode::new("Café; 2.4", WifiSecurity::WpaPsk { psk: "p@ss:word".into() })?;
+//! assert_eq!(code.to_string(), r"WIFI:S:Café\; 2.4;T:WPA;P:p@ss\:word;;");
+//!
+//! let parsed = WifiQrCode::parse("WIFI:T:nopass;S:Guest;H:true;;")?;
+//! assert_eq!(parsed.ssid(), "Guest");
+//! assert!(parsed.is_hidden());
+//! # Ok::<(), nmrs::qr::QrError>(())
+//! ```
+//!
+//! A code for a saved network is available from
+//! [`NetworkManager::wifi_qr_code`](crate::NetworkManager::wifi_qr_code).
+
+use std::fmt::{self, Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::WifiSecurity;
+
+/// Errors produced while building or parsing a `WIFI:` payload.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Error, Serialize, Deserialize)]
+pub enum QrError {
+    /// The payload does not start with `WIFI:`.
+    #[error("not a Wi-Fi QR payload")]
+    NotWifi,
+
+    /// The payload has no `S:` field, or it is empty.
+    #[error("missing SSID")]
+    MissingSsid,
+
+    /// A secured network has no `P:` field, or it is empty.
+    #[error("missing password")]
+    MissingPassword,
+
+    /// The security type cannot be represented.
+    #[error("unsupported security type '{0}'")]
+    UnsupportedSecurity(String),
+
+    /// A field is not `key:value`, or its value is malformed.
+    #[error("invalid field '{0}'")]
+    InvalidField(String),
+
+    /// The payload ends in the middle of a backslash escape.
+    #[error("truncated escape sequence")]
+    InvalidEscape,
+}
+
+/// A network that can be shared as a Wi-Fi QR code.
+///
+/// [`Display`] writes the `WIFI:` payload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WifiQrCode {
+    ssid: String,
+    security: WifiSecurity,
+    hidden: bool,
+}
+
+impl WifiQrCode {
+    /// Creates a code for an open or WPA-PSK network.
+    ///
+    /// # Errors
+    ///
+    /// [`QrError::MissingSsid`] for an empty SSID,
+    /// [`QrError::MissingPassword`] for an empty PSK, and
+    /// [`QrError::UnsupportedSecurity`] for
+    /// [`WifiSecurity::WpaEap`].
+    pub fn new(ssid: impl Into<String>, security: WifiSecurity) -> Result<Self, QrError> {
+        let ssid = ssid.into();
+        if ssid.is_empty() {
+            return Err(QrError::MissingSsid);
+        }
+        match &security {
+            WifiSecurity::Open => {}
+            WifiSecurity::WpaPsk { psk } if psk.is_empty() => {
+                return Err(QrError::MissingPassword);
+            }
+            WifiSecurity::WpaPsk { .. } => {}
+            WifiSecurity::WpaEap { .. } => {
+                return Err(QrError::UnsupportedSecurity("WPA-EAP".into()));
+            }
+        }
+        Ok(Self {
+            ssid,
+            security,
+            hidden: false,
+        })
+    }
+
+    /// Marks the network as hidden (`H:true`), so scanners probe for it.
+    #[must_use]
+    pub fn with_hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
+    /// Parses a `WIFI:` payload.
+    ///
+    /// Fields may appear in any order; unknown fields are ignored. `T:WPA`,
+    /// `WPA2`, `WPA3` and `SAE` become [`WifiSecurity::WpaPsk`], and `nopass`
+    /// or no `T:` field [`WifiSecurity::Open`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`QrError`] describing the first problem found. WEP and
+    /// enterprise payloads are [`QrError::UnsupportedSecurity`].
+    pub fn parse(payload: &str) -> Result<Self, QrError> {
+        let payload = payload.trim();
+        let body = match payload.get(..5) {
+            Some(scheme) if scheme.eq_ignore_ascii_case("WIFI:") => &payload[5..],
+            _ => return Err(QrError::NotWifi),
+        };
+
+        let mut ssid = None;
+        let mut kind = None;
+        let mut password = None;
+        let mut hidden = false;
+        for field in split_fields(body)? {
+            if field.is_empty() {
+                continue;
+            }
+            let (key, raw) = field
+                .split_once(':')
+                .ok_or_else(|| QrError::InvalidField(field.clone()))?;
+            let value = unquote(raw);
+            match key.to_ascii_uppercase().as_str() {
+                "S" => ssid = Some(value),
+                "T" => kind = Some(value),
+                "P" => password = Some(value),
+                "H" => {
+                    hidden = match value.to_ascii_lowercase().as_str() {
+                        "true" => true,
+                        "false" | "" => false,
+                        _ => return Err(QrError::InvalidField(field)),
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let ssid = ssid.filter(|s| !s.is_empty()).ok_or(QrError::MissingSsid)?;
+        let security = match kind.as_deref().map(str::to_ascii_uppercase).as_deref() {
+            None | Some("" | "NOPASS") => WifiSecurity::Open,
+            Some("WPA" | "WPA2" | "WPA3" | "SAE") => WifiSecurity::WpaPsk {
+                psk: password
+                    .filter(|p| !p.is_empty())
+                    .ok_or(QrError::MissingPassword)?,
+            },
+            Some(_) => return Err(QrError::UnsupportedSecurity(kind.unwrap_or_default())),
+        };
+        Ok(Self {
+            ssid,
+            security,
+            hidden,
+        })
+    }
+
+    /// The network's SSID.
+    #[must_use]
+    pub fn ssid(&self) -> &str {
+        &self.ssid
+    }
+
+    /// The network's security, with the PSK for WPA networks.
+    #[must_use]
+    pub fn security(&self) -> &WifiSecurity {
+        &self.security
+    }
+
+    /// Whether the network does not broadcast its SSID.
+    #[must_use]
+    pub fn is_hidden(&self) -> bool {
+        self.hidden
+    }
+
+    /// The SSID and security, ready for
+    /// [`NetworkManager::connect`](crate::NetworkManager::connect).
+    #[must_use]
+    pub fn into_parts(self) -> (String, WifiSecurity) {
+        (self.ssid, self.security)
+    }
+}
+
+impl Display for WifiQrCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "WIFI:S:{};", Escaped(&self.ssid))?;
+        match &self.security {
+            WifiSecurity::WpaPsk { psk } => write!(f, "T:WPA;P:{};", Escaped(psk))?,
+            // `new` rejects enterprise networks.
+            WifiSecurity::Open | WifiSecurity::WpaEap { .. } => write!(f, "T:nopass;")?,
+        }
+        if self.hidden {
+            write!(f, "H:true;")?;
+        }
+        write!(f, ";")
+    }
+}
+
+/// A payload value, escaped and, if it would read as hex, quoted.
+struct Escaped<'a>(&'a str);
+
+impl Display for Escaped<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let quote = !self.0.is_empty() && self.0.chars().all(|c| c.is_ascii_hexdigit());
+        if quote {
+            write!(f, "\"")?;
+        }
+        for c in self.0.chars() {
+            if matches!(c, '\\' | ';' | ',' | ':' | '"') {
+                write!(f, "\\")?;
+            }
+            write!(f, "{c}")?;
+        }
+        if quote {
+            write!(f, "\"")?;
+        }
+        Ok(())
+    }
+}
+
+/// Splits on unescaped `;`, keeping escapes in place so `:` inside values
+/// still splits key from value correctly.
+fn split_fields(body: &str) -> Result<Vec<String>, QrError> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut chars = body.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                current.push(c);
+                current.push(chars.next().ok_or(QrError::InvalidEscape)?);
+            }
+            ';' => fields.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    fields.push(current);
+    Ok(fields)
+}
+
+/// Removes quotes around a raw value, unless the closing one is escaped,
+/// then backslash escapes.
+fn unquote(raw: &str) -> String {
+    let inner = raw
+        .strip_prefix('"')
+        .and_then(|r| r.strip_suffix('"'))
+        .filter(|r| r.chars().rev().take_while(|&c| c == '\\').count() % 2 == 0)
+        .unwrap_or(raw);
+    let mut value = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => value.extend(chars.next()),
+            _ => value.push(c),
+        }
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn psk(p: &str) -> WifiSecurity {
+        WifiSecurity::WpaPsk { psk: p.into() }
+    }
+
+    #[test]
+    fn formats_payloads() {
+        let open = WifiQrCode::new("Guest", WifiSecurity::Open).unwrap();
+        assert_eq!(open.to_string(), "WIFI:S:Guest;T:nopass;;");
+
+        let hidden = WifiQrCode::new("Lab", psk("secret12"))
+            .unwrap()
+            .with_hidden(true);
+        assert_eq!(hidden.to_string(), "WIFI:S:Lab;T:WPA;P:secret12;H:true;;");
+    }
+
+    #[test]
+    fn escapes_and_quotes_values() {
+        let code = WifiQrCode::new(r#"a;b,c:d\e"f"#, psk("DEADBEEF")).unwrap();
+        assert_eq!(
+            code.to_string(),
+            r#"WIFI:S:a\;b\,c\:d\\e\"f;T:WPA;P:"DEADBEEF";;"#
+        );
+        assert_eq!(WifiQrCode::parse(&code.to_string()).unwrap(), code);
+    }
+
+    #[test]
+    fn parses_fields_in_any_order() {
+        let code = WifiQrCode::parse("wifi:P:hunter22;H:TRUE;T:wpa2;S:Home;X:ignored;;").unwrap();
+        assert_eq!(code.ssid(), "Home");
+        assert_eq!(code.security(), &psk("hunter22"));
+        assert!(code.is_hidden());
+
+        let open = WifiQrCode::parse("WIFI:S:Cafe").unwrap();
+        assert_eq!(open.into_parts(), ("Cafe".to_string(), WifiSecurity::Open));
+    }
+
+    #[test]
+    fn rejects_bad_payloads() {
+        assert_eq!(WifiQrCode::parse("MECARD:N:x;;"), Err(QrError::NotWifi));
+        assert_eq!(
+            WifiQrCode::parse("WIFI:T:WPA;P:x;;"),
+            Err(QrError::MissingSsid)
+        );
+        assert_eq!(
+            WifiQrCode::parse("WIFI:S:Home;T:WPA;;"),
+            Err(QrError::MissingPassword)
+        );
+        assert_eq!(
+            WifiQrCode::parse("WIFI:S:Old;T:WEP;P:abc;;"),
+            Err(QrError::UnsupportedSecurity("WEP".into()))
+        );
+        assert_eq!(
+            WifiQrCode::parse("WIFI:S:Home;H:maybe;;"),
+            Err(QrError::InvalidField("H:maybe".into()))
+        );
+        assert_eq!(
+            WifiQrCode::parse("WIFI:S:Home\\"),
+            Err(QrError::InvalidEscape)
+        );
+        assert!(matches!(
+            WifiQrCode::new(
+                "Corp",
+                WifiSecurity::WpaEap {
+                    opts: Default::default()
+                }
+            ),
+            Err(QrError::UnsupportedSecurity(_))
+        ));
+    }
+}
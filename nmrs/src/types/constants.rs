@@ -10,7 +10,7 @@ pub mod device_type {
     pub const BLUETOOTH: u32 = 5;
     /// Mobile broadband / WWAN modem device.
     pub const MODEM: u32 = 8;
-    // pub const WIFI_P2P: u32 = 30;
+    pub const WIFI_P2P: u32 = 30;
     // pub const LOOPBACK: u32 = 32;
 }
 
@@ -22,12 +22,108 @@ pub mod device_state {
     pub const DISCONNECTED: u32 = 30;
     // pub const PREPARE: u32 = 40;
     // pub const CONFIG: u32 = 50;
-    // pub const NEED_AUTH: u32 = 60;
+    pub const NEED_AUTH: u32 = 60;
     // pub const IP_CONFIG: u32 = 70;
     // pub const IP_CHECK: u32 = 80;
     // pub const SECONDARIES: u32 = 90;
     pub const ACTIVATED: u32 = 100;
     // pub const DEACTIVATING: u32 = 110;
+    pub const FAILED: u32 = 120;
+}
+
+/// `NMVpnConnectionState` values.
+pub mod vpn_state {
+    pub const ACTIVATED: u32 = 5;
+    pub const FAILED: u32 = 6;
+    pub const DISCONNECTED: u32 = 7;
+}
+
+/// `NMDeviceStateReason` codes, as reported by `Device.StateReason`.
+///
+/// Each constant is `NM_DEVICE_STATE_REASON_<NAME>` from NetworkManager's
+/// `nm-dbus-interface.h`; the `UNMANAGED_*` codes were added in 1.48.
+///
+/// Reference: <https://networkmanager.dev/docs/api/latest/nm-dbus-types.html#NMDeviceStateReason>
+pub mod device_state_reason {
+    pub const NONE: u32 = 0;
+    pub const UNKNOWN: u32 = 1;
+    pub const NOW_MANAGED: u32 = 2;
+    pub const NOW_UNMANAGED: u32 = 3;
+    pub const CONFIG_FAILED: u32 = 4;
+    pub const IP_CONFIG_UNAVAILABLE: u32 = 5;
+    pub const IP_CONFIG_EXPIRED: u32 = 6;
+    pub const NO_SECRETS: u32 = 7;
+    pub const SUPPLICANT_DISCONNECT: u32 = 8;
+    pub const SUPPLICANT_CONFIG_FAILED: u32 = 9;
+    pub const SUPPLICANT_FAILED: u32 = 10;
+    pub const SUPPLICANT_TIMEOUT: u32 = 11;
+    pub const PPP_START_FAILED: u32 = 12;
+    pub const PPP_DISCONNECT: u32 = 13;
+    pub const PPP_FAILED: u32 = 14;
+    pub const DHCP_START_FAILED: u32 = 15;
+    pub const DHCP_ERROR: u32 = 16;
+    pub const DHCP_FAILED: u32 = 17;
+    pub const SHARED_START_FAILED: u32 = 18;
+    pub const SHARED_FAILED: u32 = 19;
+    pub const AUTOIP_START_FAILED: u32 = 20;
+    pub const AUTOIP_ERROR: u32 = 21;
+    pub const AUTOIP_FAILED: u32 = 22;
+    pub const MODEM_BUSY: u32 = 23;
+    pub const MODEM_NO_DIAL_TONE: u32 = 24;
+    pub const MODEM_NO_CARRIER: u32 = 25;
+    pub const MODEM_DIAL_TIMEOUT: u32 = 26;
+    pub const MODEM_DIAL_FAILED: u32 = 27;
+    pub const MODEM_INIT_FAILED: u32 = 28;
+    pub const GSM_APN_FAILED: u32 = 29;
+    pub const GSM_REGISTRATION_NOT_SEARCHING: u32 = 30;
+    pub const GSM_REGISTRATION_DENIED: u32 = 31;
+    pub const GSM_REGISTRATION_TIMEOUT: u32 = 32;
+    pub const GSM_REGISTRATION_FAILED: u32 = 33;
+    pub const GSM_PIN_CHECK_FAILED: u32 = 34;
+    pub const FIRMWARE_MISSING: u32 = 35;
+    pub const REMOVED: u32 = 36;
+    pub const SLEEPING: u32 = 37;
+    pub const CONNECTION_REMOVED: u32 = 38;
+    pub const USER_REQUESTED: u32 = 39;
+    pub const CARRIER: u32 = 40;
+    pub const CONNECTION_ASSUMED: u32 = 41;
+    pub const SUPPLICANT_AVAILABLE: u32 = 42;
+    pub const MODEM_NOT_FOUND: u32 = 43;
+    pub const BT_FAILED: u32 = 44;
+    pub const GSM_SIM_NOT_INSERTED: u32 = 45;
+    pub const GSM_SIM_PIN_REQUIRED: u32 = 46;
+    pub const GSM_SIM_PUK_REQUIRED: u32 = 47;
+    pub const GSM_SIM_WRONG: u32 = 48;
+    pub const INFINIBAND_MODE: u32 = 49;
+    pub const DEPENDENCY_FAILED: u32 = 50;
+    pub const BR2684_FAILED: u32 = 51;
+    pub const MODEM_MANAGER_UNAVAILABLE: u32 = 52;
+    pub const SSID_NOT_FOUND: u32 = 53;
+    pub const SECONDARY_CONNECTION_FAILED: u32 = 54;
+    pub const DCB_FCOE_FAILED: u32 = 55;
+    pub const TEAMD_CONTROL_FAILED: u32 = 56;
+    pub const MODEM_FAILED: u32 = 57;
+    pub const MODEM_AVAILABLE: u32 = 58;
+    pub const SIM_PIN_INCORRECT: u32 = 59;
+    pub const NEW_ACTIVATION: u32 = 60;
+    pub const PARENT_CHANGED: u32 = 61;
+    pub const PARENT_MANAGED_CHANGED: u32 = 62;
+    pub const OVSDB_FAILED: u32 = 63;
+    pub const IP_ADDRESS_DUPLICATE: u32 = 64;
+    pub const IP_METHOD_UNSUPPORTED: u32 = 65;
+    pub const SRIOV_CONFIGURATION_FAILED: u32 = 66;
+    pub const PEER_NOT_FOUND: u32 = 67;
+    pub const DEVICE_HANDLER_FAILED: u32 = 68;
+    pub const UNMANAGED_BY_DEFAULT: u32 = 69;
+    pub const UNMANAGED_EXTERNAL_DOWN: u32 = 70;
+    pub const UNMANAGED_LINK_NOT_INIT: u32 = 71;
+    pub const UNMANAGED_QUITTING: u32 = 72;
+    pub const UNMANAGED_SLEEPING: u32 = 73;
+    pub const UNMANAGED_USER_CONF: u32 = 74;
+    pub const UNMANAGED_USER_EXPLICIT: u32 = 75;
+    pub const UNMANAGED_USER_SETTINGS: u32 = 76;
+    pub const UNMANAGED_USER_UDEV: u32 = 77;
+    pub const UNMANAGED_NETWORKING_OFF: u32 = 78;
 }
 
 /// WiFi security flag constants
@@ -37,6 +133,12 @@ pub mod security_flags {
     pub const EAP: u32 = 0x0200;
 }
 
+/// Access point `Flags` (`NM80211ApFlags`) constants
+pub mod ap_flags {
+    pub const WPS_PBC: u32 = 0x4;
+    pub const WPS_PIN: u32 = 0x8;
+}
+
 /// WiFi mode constants
 pub mod wifi_mode {
     pub const ADHOC: u32 = 1;
@@ -56,12 +158,16 @@ pub mod timeouts {
     /// Used after enabling Wi-Fi to wait for the hardware to initialize.
     const WIFI_READY_TIMEOUT_SECS: u64 = 60;
 
-    /// Time to wait after requesting a scan before checking results (2 seconds).
-    ///
-    /// While we could use signals for scan completion, a short delay is
-    /// sufficient for now, and simpler for most use cases.
+    /// Time to wait for a scan to finish when NetworkManager rejected the
+    /// request, e.g. because it scanned moments ago (2 seconds).
     const SCAN_WAIT_SECS: u64 = 2;
 
+    /// Maximum time to wait for a requested scan to finish (10 seconds).
+    ///
+    /// Scan completion is detected from the device's `LastScan` property;
+    /// this only bounds drivers that never report it.
+    const SCAN_COMPLETE_TIMEOUT_SECS: u64 = 10;
+
     /// Brief delay after state transitions to allow NetworkManager to stabilize.
     const STABILIZATION_DELAY_MS: u64 = 100;
 
@@ -75,6 +181,11 @@ pub mod timeouts {
         Duration::from_secs(SCAN_WAIT_SECS)
     }
 
+    /// Returns the scan completion timeout.
+    pub fn scan_complete_timeout() -> Duration {
+        Duration::from_secs(SCAN_COMPLETE_TIMEOUT_SECS)
+    }
+
     /// Returns a brief stabilization delay.
     pub fn stabilization_delay() -> Duration {
         Duration::from_millis(STABILIZATION_DELAY_MS)
@@ -176,6 +176,27 @@ impl DeviceTypeInfo for VlanDeviceType {
     }
 }
 
+/// Mobile broadband modem device type implementation.
+struct ModemDeviceType;
+
+impl DeviceTypeInfo for ModemDeviceType {
+    fn nm_type_code(&self) -> u32 {
+        8
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Modem"
+    }
+
+    fn connection_type(&self) -> &'static str {
+        "gsm"
+    }
+
+    fn has_global_enabled_state(&self) -> bool {
+        true
+    }
+}
+
 /// TUN/TAP device type implementation.
 struct TunDeviceType;
 
@@ -229,6 +250,7 @@ fn registry() -> &'static HashMap<u32, Box<dyn DeviceTypeInfo>> {
             Box::new(BridgeDeviceType),
             Box::new(BondDeviceType),
             Box::new(VlanDeviceType),
+            Box::new(ModemDeviceType),
             Box::new(TunDeviceType),
             Box::new(WireGuardDeviceType),
         ];
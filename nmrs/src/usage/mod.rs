@@ -0,0 +1,33 @@
+//! Persistent per-profile data usage history.
+//!
+//! Enabled with the `usage-history` feature.
+//! [`NetworkManager::track_usage`](crate::NetworkManager::track_usage)
+//! periodically samples each device's `Device.Statistics` counters and adds
+//! the traffic since the previous sample to a
+//! [`UsageStore`](crate::usage::UsageStore), keyed by day and by the name of
+//! the profile active on the device at that time.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::time::Duration;
+//! use nmrs::NetworkManager;
+//! use nmrs::usage::{UsageDate, UsageStore};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let nm = NetworkManager::new().await?;
+//! let store = UsageStore::open("/var/lib/myapp/usage.tsv")?;
+//!
+//! let tracker = store.clone();
+//! let nm_clone = nm.clone();
+//! tokio::spawn(async move { nm_clone.track_usage(tracker, Duration::from_secs(60)).await });
+//!
+//! let week = store.totals_since("HomeWiFi", UsageDate::today().days_before(6));
+//! println!("{:.1} GB on HomeWiFi this week", week.total_bytes() as f64 / 1e9);
+//! # Ok(())
+//! # }
+//! ```
+
+mod store;
+
+pub use store::{DailyUsage, UsageDate, UsageStore, UsageTotals};
@@ -0,0 +1,402 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A calendar day in UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UsageDate {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+impl UsageDate {
+    /// Creates a date, or `None` if it does not exist.
+    #[must_use]
+    pub fn new(year: i32, month: u8, day: u8) -> Option<Self> {
+        let date = Self { year, month, day };
+        (Self::from_unix_days(date.unix_days()) == date).then_some(date)
+    }
+
+    /// Today's date in UTC.
+    #[must_use]
+    pub fn today() -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Self::from_unix_days((secs / 86_400) as i64)
+    }
+
+    /// The date `days` days before this one.
+    #[must_use]
+    pub fn days_before(self, days: u32) -> Self {
+        Self::from_unix_days(self.unix_days() - i64::from(days))
+    }
+
+    /// Year.
+    #[must_use]
+    pub fn year(self) -> i32 {
+        self.year
+    }
+
+    /// Month, 1-12.
+    #[must_use]
+    pub fn month(self) -> u8 {
+        self.month
+    }
+
+    /// Day of month, 1-31.
+    #[must_use]
+    pub fn day(self) -> u8 {
+        self.day
+    }
+
+    // Civil-from-days and days-from-civil after Howard Hinnant's algorithms.
+    fn from_unix_days(days: i64) -> Self {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+        let year = (yoe + era * 400 + i64::from(month <= 2)) as i32;
+        Self { year, month, day }
+    }
+
+    fn unix_days(self) -> i64 {
+        let y = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = y.div_euclid(400);
+        let yoe = y.rem_euclid(400);
+        let m = i64::from(self.month);
+        let doy = (153 * if m > 2 { m - 3 } else { m + 9 } + 2) / 5 + i64::from(self.day) - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146_097 + doe - 719_468
+    }
+}
+
+impl fmt::Display for UsageDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl FromStr for UsageDate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(3, '-');
+        let mut next = || parts.next().ok_or_else(|| format!("invalid date '{s}'"));
+        let year = next()?
+            .parse()
+            .map_err(|_| format!("invalid year in '{s}'"))?;
+        let month = next()?
+            .parse()
+            .map_err(|_| format!("invalid month in '{s}'"))?;
+        let day = next()?
+            .parse()
+            .map_err(|_| format!("invalid day in '{s}'"))?;
+        Self::new(year, month, day).ok_or_else(|| format!("no such date '{s}'"))
+    }
+}
+
+/// Traffic of one profile on one day.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DailyUsage {
+    /// Day the traffic was counted on (UTC).
+    pub date: UsageDate,
+    /// Connection profile name (`connection.id`, usually the SSID for Wi-Fi).
+    pub profile: String,
+    /// Bytes received.
+    pub rx_bytes: u64,
+    /// Bytes transmitted.
+    pub tx_bytes: u64,
+}
+
+/// Summed traffic over a period.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UsageTotals {
+    /// Bytes received.
+    pub rx_bytes: u64,
+    /// Bytes transmitted.
+    pub tx_bytes: u64,
+}
+
+impl UsageTotals {
+    /// Received plus transmitted bytes.
+    #[must_use]
+    pub fn total_bytes(&self) -> u64 {
+        self.rx_bytes.saturating_add(self.tx_bytes)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    path: Option<PathBuf>,
+    days: BTreeMap<(UsageDate, String), (u64, u64)>,
+    dirty: bool,
+}
+
+/// Daily per-profile traffic totals, optionally backed by a file.
+///
+/// Cloning is cheap and clones share the same data, so one clone can be
+/// handed to [`NetworkManager::track_usage`](crate::NetworkManager::track_usage)
+/// while another answers queries.
+///
+/// The file is plain text, one `date<TAB>profile<TAB>rx<TAB>tx` line per
+/// day and profile, and is replaced atomically on [`flush`](Self::flush).
+#[derive(Debug, Clone, Default)]
+pub struct UsageStore {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl UsageStore {
+    /// Opens the store at `path`, starting empty if the file does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the file cannot be read or contains a malformed line.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let days = match fs::read_to_string(&path) {
+            Ok(content) => parse_store(&content)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(Self {
+            inner: Arc::new(Mutex::new(Inner {
+                path: Some(path),
+                days,
+                dirty: false,
+            })),
+        })
+    }
+
+    /// Creates a store that is never written to disk.
+    #[must_use]
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Adds traffic to the totals of `profile` on `date`.
+    pub fn record(&self, date: UsageDate, profile: &str, rx_bytes: u64, tx_bytes: u64) {
+        if rx_bytes == 0 && tx_bytes == 0 {
+            return;
+        }
+        let mut inner = self.lock();
+        let entry = inner.days.entry((date, profile.to_string())).or_default();
+        entry.0 = entry.0.saturating_add(rx_bytes);
+        entry.1 = entry.1.saturating_add(tx_bytes);
+        inner.dirty = true;
+    }
+
+    /// Writes pending changes to the backing file.
+    ///
+    /// Does nothing for in-memory stores or when nothing changed.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the file or its temporary sibling cannot be written.
+    pub fn flush(&self) -> io::Result<()> {
+        let mut inner = self.lock();
+        let Some(path) = inner.path.clone().filter(|_| inner.dirty) else {
+            return Ok(());
+        };
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, format_store(&inner.days))?;
+        fs::rename(&tmp, &path)?;
+        inner.dirty = false;
+        Ok(())
+    }
+
+    /// All daily records, oldest first.
+    #[must_use]
+    pub fn records(&self) -> Vec<DailyUsage> {
+        self.lock()
+            .days
+            .iter()
+            .map(|((date, profile), (rx, tx))| DailyUsage {
+                date: *date,
+                profile: profile.clone(),
+                rx_bytes: *rx,
+                tx_bytes: *tx,
+            })
+            .collect()
+    }
+
+    /// Names of every profile with recorded traffic.
+    #[must_use]
+    pub fn profiles(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.lock().days.keys().map(|(_, p)| p.clone()).collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Traffic of `profile` from `since` through today, inclusive.
+    ///
+    /// For "this week" use `UsageDate::today().days_before(6)`.
+    #[must_use]
+    pub fn totals_since(&self, profile: &str, since: UsageDate) -> UsageTotals {
+        self.lock()
+            .days
+            .range((since, String::new())..)
+            .filter(|((_, p), _)| p == profile)
+            .fold(UsageTotals::default(), |acc, (_, (rx, tx))| UsageTotals {
+                rx_bytes: acc.rx_bytes.saturating_add(*rx),
+                tx_bytes: acc.tx_bytes.saturating_add(*tx),
+            })
+    }
+
+    /// Drops records older than `date`.
+    pub fn prune_before(&self, date: UsageDate) {
+        let mut inner = self.lock();
+        let kept = inner.days.split_off(&(date, String::new()));
+        if !inner.days.is_empty() {
+            inner.dirty = true;
+        }
+        inner.days = kept;
+    }
+}
+
+fn escape(profile: &str) -> String {
+    profile
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+fn unescape(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => out.push('\t'),
+            Some('n') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+fn format_store(days: &BTreeMap<(UsageDate, String), (u64, u64)>) -> String {
+    days.iter()
+        .map(|((date, profile), (rx, tx))| format!("{date}\t{}\t{rx}\t{tx}\n", escape(profile)))
+        .collect()
+}
+
+fn parse_store(content: &str) -> io::Result<BTreeMap<(UsageDate, String), (u64, u64)>> {
+    let invalid = |n: usize, what: &str| {
+        io::Error::new(io::ErrorKind::InvalidData, format!("line {n}: {what}"))
+    };
+
+    let mut days = BTreeMap::new();
+    for (idx, line) in content.lines().enumerate() {
+        let n = idx + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [date, profile, rx, tx] = fields[..] else {
+            return Err(invalid(n, "expected 4 tab-separated fields"));
+        };
+        let date: UsageDate = date.parse().map_err(|e: String| invalid(n, &e))?;
+        let rx: u64 = rx
+            .parse()
+            .map_err(|_| invalid(n, "invalid rx byte count"))?;
+        let tx: u64 = tx
+            .parse()
+            .map_err(|_| invalid(n, "invalid tx byte count"))?;
+        days.insert((date, unescape(profile)), (rx, tx));
+    }
+    Ok(days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> UsageDate {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn date_round_trips_through_unix_days() {
+        assert_eq!(UsageDate::from_unix_days(0), date("1970-01-01"));
+        assert_eq!(date("2024-03-01").days_before(1), date("2024-02-29"));
+        assert_eq!(date("2026-01-03").days_before(6), date("2025-12-28"));
+        assert!(UsageDate::new(2025, 2, 29).is_none());
+        assert!("2025-13-01".parse::<UsageDate>().is_err());
+    }
+
+    #[test]
+    fn record_accumulates_per_day_and_profile() {
+        let store = UsageStore::in_memory();
+        store.record(date("2026-10-10"), "Home", 100, 10);
+        store.record(date("2026-10-10"), "Home", 50, 5);
+        store.record(date("2026-10-11"), "Home", 1, 1);
+        store.record(date("2026-10-11"), "Cafe", 7, 7);
+
+        assert_eq!(store.records().len(), 3);
+        assert_eq!(
+            store.profiles(),
+            vec!["Cafe".to_string(), "Home".to_string()]
+        );
+
+        let all = store.totals_since("Home", date("2026-10-01"));
+        assert_eq!((all.rx_bytes, all.tx_bytes), (151, 16));
+        assert_eq!(
+            store.totals_since("Home", date("2026-10-11")).total_bytes(),
+            2
+        );
+    }
+
+    #[test]
+    fn prune_drops_old_days() {
+        let store = UsageStore::in_memory();
+        store.record(date("2026-10-01"), "Home", 1, 1);
+        store.record(date("2026-10-05"), "Home", 2, 2);
+        store.prune_before(date("2026-10-03"));
+        assert_eq!(store.records().len(), 1);
+        assert_eq!(store.records()[0].date, date("2026-10-05"));
+    }
+
+    #[test]
+    fn file_round_trip_escapes_profile_names() {
+        let path = std::env::temp_dir().join(format!("nmrs-usage-{}.tsv", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let store = UsageStore::open(&path).unwrap();
+        store.record(date("2026-10-12"), "Guest\tNet\\2", 3_200_000_000, 42);
+        store.flush().unwrap();
+
+        let reopened = UsageStore::open(&path).unwrap();
+        assert_eq!(reopened.records(), store.records());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn malformed_line_is_rejected() {
+        let err = parse_store("2026-10-12\tHome\t1\n").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("line 1"));
+    }
+}
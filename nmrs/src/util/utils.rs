@@ -6,12 +6,15 @@
 use log::{debug, warn};
 use std::borrow::Cow;
 use std::collections::HashMap;
+use std::fmt;
 use std::str;
+use std::time::{Duration, SystemTime};
 use zbus::Connection;
-use zvariant::OwnedObjectPath;
+use zvariant::{OwnedObjectPath, OwnedValue};
 
 use crate::Result;
-use crate::api::models::ConnectionStateReason;
+use crate::api::models::{ConnectionStateReason, Redacted};
+use crate::backend::NewProfileSettings;
 use crate::dbus::{
     NMAccessPointProxy, NMActiveConnectionProxy, NMDeviceProxy, NMProxy, NMWirelessProxy,
 };
@@ -326,6 +329,56 @@ async fn extract_ip_address(
     })
 }
 
+/// Property `key` of a `GetAll` reply, if present and of type `T`.
+pub(crate) fn property<T: TryFrom<OwnedValue>>(
+    props: &HashMap<String, OwnedValue>,
+    key: &str,
+) -> Option<T> {
+    props.get(key)?.try_clone().ok()?.try_into().ok()
+}
+
+/// Settings keys whose values are secrets.
+const SECRET_KEYS: &[&str] = &[
+    "psk",
+    "password",
+    "private-key",
+    "private-key-password",
+    "phase2-private-key-password",
+    "leap-password",
+    "wep-key0",
+    "wep-key1",
+    "wep-key2",
+    "wep-key3",
+    "pin",
+    "preshared-key",
+    "secrets",
+];
+
+/// `Debug` view of a settings dictionary with secret values replaced by
+/// `<redacted>`, for logging.
+pub(crate) struct RedactedSettings<'a>(pub(crate) &'a NewProfileSettings);
+
+impl fmt::Debug for RedactedSettings<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.0.iter().map(|(section, values)| {
+                let values: HashMap<_, &dyn fmt::Debug> = values
+                    .iter()
+                    .map(|(key, value)| {
+                        let value: &dyn fmt::Debug = if SECRET_KEYS.contains(key) {
+                            &Redacted
+                        } else {
+                            value
+                        };
+                        (key, value)
+                    })
+                    .collect();
+                (section, values)
+            }))
+            .finish()
+    }
+}
+
 /// Extracts IPv4 and IPv6 addresses from an active connection.
 ///
 /// Returns a tuple of (ipv4_address, ipv6_address) where each is an Option<String>
@@ -369,10 +422,50 @@ pub(crate) async fn get_ip_addresses_from_active_connection(
     (ip4_address, ip6_address)
 }
 
+/// Current `CLOCK_BOOTTIME` in milliseconds, the clock NM timestamps use
+/// (`LastScan`, `LastSeen`, checkpoint `Created`).
+pub(crate) fn boottime_ms() -> Option<i64> {
+    let uptime = std::fs::read_to_string("/proc/uptime").ok()?;
+    parse_uptime_ms(&uptime)
+}
+
+fn parse_uptime_ms(uptime: &str) -> Option<i64> {
+    let secs: f64 = uptime.split_whitespace().next()?.parse().ok()?;
+    Some((secs * 1000.0) as i64)
+}
+
+/// Converts a `CLOCK_BOOTTIME` timestamp in milliseconds to wall-clock time.
+pub(crate) fn boottime_to_system_time(boot_ms: i64) -> Option<SystemTime> {
+    boot_ms_to_system_time(boot_ms, boottime_ms()?, SystemTime::now())
+}
+
+fn boot_ms_to_system_time(boot_ms: i64, now_boot_ms: i64, now: SystemTime) -> Option<SystemTime> {
+    let elapsed = u64::try_from(now_boot_ms.saturating_sub(boot_ms)).unwrap_or(0);
+    now.checked_sub(Duration::from_millis(elapsed))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn parses_proc_uptime() {
+        assert_eq!(parse_uptime_ms("12345.67 54321.00\n"), Some(12_345_670));
+        assert_eq!(parse_uptime_ms(""), None);
+        assert_eq!(parse_uptime_ms("abc 1"), None);
+    }
+
+    #[test]
+    fn boottime_maps_to_wall_clock() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        assert_eq!(
+            boot_ms_to_system_time(55_000, 60_000, now),
+            Some(now - Duration::from_secs(5))
+        );
+        // Timestamps slightly ahead of /proc/uptime are clamped to now.
+        assert_eq!(boot_ms_to_system_time(60_010, 60_000, now), Some(now));
+    }
+
     #[test]
     fn test_channel_from_freq_2_4ghz() {
         assert_eq!(channel_from_freq(2412), Some(1));
@@ -442,4 +535,19 @@ mod tests {
         assert_eq!(strength_or_zero(Some(100)), 100);
         assert_eq!(strength_or_zero(None), 0);
     }
+
+    #[test]
+    fn redacted_settings_hide_secrets() {
+        let settings = crate::api::builders::build_wifi_connection(
+            "Home",
+            &crate::WifiSecurity::WpaPsk {
+                psk: "hunter22".into(),
+            },
+            &crate::ConnectionOptions::default(),
+        );
+        let logged = format!("{:?}", RedactedSettings(&settings));
+        assert!(!logged.contains("hunter22"));
+        assert!(logged.contains("\"psk\": <redacted>"));
+        assert!(logged.contains("wpa-psk"));
+    }
 }
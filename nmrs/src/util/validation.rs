@@ -6,8 +6,8 @@
 #![allow(deprecated)]
 
 use crate::api::models::{
-    ConnectionError, OpenVpnAuthType, OpenVpnConfig, OpenVpnProxy, VpnCredentials, WifiSecurity,
-    WireGuardPeer,
+    ConnectionError, EapMethod, FastProvisioning, Ikev2Auth, Ikev2Config, OpenVpnAuthType,
+    OpenVpnConfig, OpenVpnProxy, VpnCredentials, WifiSecurity, WireGuardPeer,
 };
 
 /// Maximum SSID length in bytes (802.11 standard).
@@ -99,6 +99,41 @@ pub fn validate_connection_name(name: &str) -> Result<(), ConnectionError> {
     Ok(())
 }
 
+/// Validates a kernel network interface name.
+///
+/// # Rules
+/// - Must be 1-15 bytes (`IFNAMSIZ` minus the terminator)
+/// - Must not be `.` or `..`
+/// - Must not contain `/`, `:` or whitespace
+///
+/// # Errors
+/// Returns `ConnectionError::InvalidInput` if the name is invalid.
+pub fn validate_interface_name(name: &str) -> Result<(), ConnectionError> {
+    let reason = if name.is_empty() || name.len() > 15 {
+        Some(format!(
+            "interface name must be 1-15 bytes, got {}",
+            name.len()
+        ))
+    } else if name == "." || name == ".." {
+        Some(format!("'{name}' is not a valid interface name"))
+    } else if name
+        .chars()
+        .any(|c| c == '/' || c == ':' || c.is_whitespace())
+    {
+        Some(format!("'{name}' contains '/', ':' or whitespace"))
+    } else {
+        None
+    };
+
+    match reason {
+        Some(reason) => Err(ConnectionError::InvalidInput {
+            field: "interface".to_string(),
+            reason,
+        }),
+        None => Ok(()),
+    }
+}
+
 /// Validates WiFi security credentials.
 ///
 /// # Rules
@@ -145,13 +180,47 @@ pub fn validate_wifi_security(security: &WifiSecurity) -> Result<(), ConnectionE
                 ));
             }
 
-            // Validate password
-            if opts.password.is_empty() {
+            // TLS authenticates with a certificate instead of a password
+            if opts.method == EapMethod::Tls {
+                for (path, what) in [
+                    (&opts.client_cert_path, "client certificate"),
+                    (&opts.private_key_path, "private key"),
+                ] {
+                    let Some(path) = path else {
+                        return Err(ConnectionError::InvalidAddress(format!(
+                            "EAP-TLS requires a {what} path"
+                        )));
+                    };
+                    if !path.starts_with("file://") {
+                        return Err(ConnectionError::InvalidAddress(format!(
+                            "EAP {what} path must start with 'file://'"
+                        )));
+                    }
+                }
+            } else if opts.password.is_empty() {
                 return Err(ConnectionError::InvalidAddress(
                     "EAP password cannot be empty".to_string(),
                 ));
             }
 
+            // FAST without in-band provisioning needs an existing PAC
+            if opts.method == EapMethod::Fast {
+                if let Some(ref pac) = opts.pac_file
+                    && pac.trim().is_empty()
+                {
+                    return Err(ConnectionError::InvalidAddress(
+                        "EAP-FAST PAC file path cannot be empty if provided".to_string(),
+                    ));
+                }
+                if opts.fast_provisioning == Some(FastProvisioning::Disabled)
+                    && opts.pac_file.is_none()
+                {
+                    return Err(ConnectionError::InvalidAddress(
+                        "EAP-FAST requires a PAC file when provisioning is disabled".to_string(),
+                    ));
+                }
+            }
+
             // Validate anonymous identity if provided
             if let Some(ref anon_id) = opts.anonymous_identity
                 && anon_id.trim().is_empty()
@@ -385,6 +454,40 @@ fn validate_cidr(cidr: &str) -> Result<(), ConnectionError> {
     Ok(())
 }
 
+/// Validates the peer list of a WireGuard connection.
+///
+/// # Rules
+/// - At least one peer must be configured
+/// - All peers must be valid (see `validate_wireguard_peer`)
+///
+/// # Errors
+/// Returns appropriate `ConnectionError` naming the index of the first
+/// invalid peer.
+pub fn validate_wireguard_peers(peers: &[WireGuardPeer]) -> Result<(), ConnectionError> {
+    if peers.is_empty() {
+        return Err(ConnectionError::InvalidPeers(
+            "VPN must have at least one peer configured".to_string(),
+        ));
+    }
+
+    for (i, peer) in peers.iter().enumerate() {
+        validate_wireguard_peer(peer).map_err(|e| match e {
+            ConnectionError::InvalidPeers(msg) => {
+                ConnectionError::InvalidPeers(format!("Peer {}: {}", i, msg))
+            }
+            ConnectionError::InvalidGateway(msg) => {
+                ConnectionError::InvalidGateway(format!("Peer {}: {}", i, msg))
+            }
+            ConnectionError::InvalidPublicKey(msg) => {
+                ConnectionError::InvalidPublicKey(format!("Peer {}: {}", i, msg))
+            }
+            other => other,
+        })?;
+    }
+
+    Ok(())
+}
+
 /// Validates VPN credentials.
 ///
 /// # Rules
@@ -434,26 +537,7 @@ pub fn validate_vpn_credentials(creds: &VpnCredentials) -> Result<(), Connection
     validate_cidr(&creds.address)?;
 
     // Validate peers
-    if creds.peers.is_empty() {
-        return Err(ConnectionError::InvalidPeers(
-            "VPN must have at least one peer configured".to_string(),
-        ));
-    }
-
-    for (i, peer) in creds.peers.iter().enumerate() {
-        validate_wireguard_peer(peer).map_err(|e| match e {
-            ConnectionError::InvalidPeers(msg) => {
-                ConnectionError::InvalidPeers(format!("Peer {}: {}", i, msg))
-            }
-            ConnectionError::InvalidGateway(msg) => {
-                ConnectionError::InvalidGateway(format!("Peer {}: {}", i, msg))
-            }
-            ConnectionError::InvalidPublicKey(msg) => {
-                ConnectionError::InvalidPublicKey(format!("Peer {}: {}", i, msg))
-            }
-            other => other,
-        })?;
-    }
+    validate_wireguard_peers(&creds.peers)?;
 
     // Validate DNS servers if provided
     if let Some(ref dns_servers) = creds.dns {
@@ -634,6 +718,76 @@ pub fn validate_openvpn_config(config: &OpenVpnConfig) -> Result<(), ConnectionE
     Ok(())
 }
 
+/// Validates an IKEv2 (strongSwan) configuration.
+///
+/// # Rules
+/// - Connection name must be valid (via [`validate_connection_name`])
+/// - Gateway must not be empty
+/// - Auth-method-specific required fields:
+///   - `Psk`: the key must not be empty
+///   - `Certificate`: certificate and key paths must not be empty
+///   - `Eap`: username must not be empty
+/// - CA cert path (if set) must be non-empty
+/// - DNS servers (if provided) must be valid IP addresses
+/// - MTU (if provided) must be in 576–9000
+///
+/// # Errors
+/// Returns appropriate `ConnectionError` if the configuration is invalid.
+pub fn validate_ikev2_config(config: &Ikev2Config) -> Result<(), ConnectionError> {
+    validate_connection_name(&config.name)?;
+
+    if config.gateway.trim().is_empty() {
+        return Err(ConnectionError::InvalidGateway(
+            "IKEv2 gateway cannot be empty".to_string(),
+        ));
+    }
+
+    match &config.auth {
+        Ikev2Auth::Psk { psk } => {
+            if psk.is_empty() {
+                return Err(ConnectionError::InvalidAddress(
+                    "Pre-shared key is required for PSK authentication".to_string(),
+                ));
+            }
+        }
+        Ikev2Auth::Certificate { cert, key, .. } => {
+            if cert.trim().is_empty() {
+                return Err(ConnectionError::InvalidAddress(
+                    "Client certificate path is required for certificate authentication"
+                        .to_string(),
+                ));
+            }
+            if key.trim().is_empty() {
+                return Err(ConnectionError::InvalidAddress(
+                    "Client key path is required for certificate authentication".to_string(),
+                ));
+            }
+        }
+        Ikev2Auth::Eap { username, .. } => {
+            if username.trim().is_empty() {
+                return Err(ConnectionError::InvalidAddress(
+                    "Username is required for EAP authentication".to_string(),
+                ));
+            }
+        }
+    }
+
+    validate_optional_cert_path(&config.ca_cert, "CA certificate")?;
+
+    if let Some(ref dns_servers) = config.dns {
+        if dns_servers.is_empty() {
+            return Err(ConnectionError::InvalidAddress(
+                "DNS server list cannot be empty if provided".to_string(),
+            ));
+        }
+        for dns in dns_servers {
+            validate_ip_address(dns)?;
+        }
+    }
+
+    validate_mtu(config.mtu)
+}
+
 /// Validates that TLS cert paths required for certificate authentication are present.
 fn validate_openvpn_cert_paths(config: &OpenVpnConfig) -> Result<(), ConnectionError> {
     if config.ca_cert.as_deref().unwrap_or("").is_empty() {
@@ -739,6 +893,43 @@ pub fn validate_bssid(bssid: &str) -> Result<(), ConnectionError> {
     Ok(())
 }
 
+/// Validates a WPS PIN.
+///
+/// Accepts the 4-digit short form or an 8-digit PIN whose last digit is the
+/// WPS checksum of the first seven.
+///
+/// # Errors
+///
+/// Returns [`ConnectionError::InvalidInput`] if the PIN is malformed.
+pub fn validate_wps_pin(pin: &str) -> Result<(), ConnectionError> {
+    let invalid = |reason: &str| ConnectionError::InvalidInput {
+        field: "WPS PIN".into(),
+        reason: reason.into(),
+    };
+
+    if !pin.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid("must contain only digits"));
+    }
+
+    match pin.len() {
+        4 => Ok(()),
+        8 => {
+            let digits: Vec<u32> = pin.bytes().map(|b| u32::from(b - b'0')).collect();
+            let sum: u32 = digits[..7]
+                .iter()
+                .enumerate()
+                .map(|(i, d)| if i % 2 == 0 { d * 3 } else { *d })
+                .sum();
+            if (10 - sum % 10) % 10 == digits[7] {
+                Ok(())
+            } else {
+                Err(invalid("checksum digit does not match"))
+            }
+        }
+        _ => Err(invalid("must be 4 or 8 digits")),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -827,6 +1018,11 @@ mod tests {
                 domain_suffix_match: Some("example.com".to_string()),
                 ca_cert_path: Some("file:///etc/ssl/cert.pem".to_string()),
                 system_ca_certs: false,
+                client_cert_path: None,
+                private_key_path: None,
+                private_key_password: None,
+                fast_provisioning: None,
+                pac_file: None,
                 method: EapMethod::Peap,
                 phase2: Phase2::Mschapv2,
             },
@@ -844,6 +1040,11 @@ mod tests {
                 domain_suffix_match: None,
                 ca_cert_path: None,
                 system_ca_certs: true,
+                client_cert_path: None,
+                private_key_path: None,
+                private_key_password: None,
+                fast_provisioning: None,
+                pac_file: None,
                 method: EapMethod::Peap,
                 phase2: Phase2::Mschapv2,
             },
@@ -861,6 +1062,11 @@ mod tests {
                 domain_suffix_match: None,
                 ca_cert_path: Some("/etc/ssl/cert.pem".to_string()), // Missing file://
                 system_ca_certs: false,
+                client_cert_path: None,
+                private_key_path: None,
+                private_key_password: None,
+                fast_provisioning: None,
+                pac_file: None,
                 method: EapMethod::Peap,
                 phase2: Phase2::Mschapv2,
             },
@@ -868,6 +1074,35 @@ mod tests {
         assert!(validate_wifi_security(&eap).is_err());
     }
 
+    #[test]
+    fn test_validate_wifi_security_eap_tls() {
+        let tls = EapOptions::tls(
+            "host/laptop",
+            "file:///etc/pki/laptop.crt",
+            "file:///etc/pki/laptop.key",
+        );
+        assert!(validate_wifi_security(&WifiSecurity::WpaEap { opts: tls.clone() }).is_ok());
+
+        let mut missing_key = tls.clone();
+        missing_key.private_key_path = None;
+        assert!(validate_wifi_security(&WifiSecurity::WpaEap { opts: missing_key }).is_err());
+
+        let relative = tls.with_client_cert_path("/etc/pki/laptop.crt");
+        assert!(validate_wifi_security(&WifiSecurity::WpaEap { opts: relative }).is_err());
+    }
+
+    #[test]
+    fn test_validate_wifi_security_eap_fast_requires_pac_without_provisioning() {
+        let fast = EapOptions::new("jdoe", "token")
+            .with_method(EapMethod::Fast)
+            .with_phase2(Phase2::Gtc)
+            .with_fast_provisioning(FastProvisioning::Disabled);
+        assert!(validate_wifi_security(&WifiSecurity::WpaEap { opts: fast.clone() }).is_err());
+
+        let with_pac = fast.with_pac_file("/var/lib/corp.pac");
+        assert!(validate_wifi_security(&WifiSecurity::WpaEap { opts: with_pac }).is_ok());
+    }
+
     #[test]
     fn test_validate_cidr_ipv4_valid() {
         assert!(validate_cidr("10.0.0.0/24").is_ok());
@@ -949,6 +1184,58 @@ mod tests {
         assert!(validate_bluetooth_address("").is_err());
     }
 
+    fn base_ikev2_config() -> Ikev2Config {
+        Ikev2Config::new(
+            "WorkVPN",
+            "vpn.example.com",
+            Ikev2Auth::Eap {
+                username: "alice".into(),
+                password: None,
+            },
+        )
+    }
+
+    #[test]
+    fn test_validate_ikev2_valid_minimal() {
+        assert!(validate_ikev2_config(&base_ikev2_config()).is_ok());
+    }
+
+    #[test]
+    fn test_validate_ikev2_empty_gateway() {
+        let mut config = base_ikev2_config();
+        config.gateway = "  ".into();
+        assert!(matches!(
+            validate_ikev2_config(&config),
+            Err(ConnectionError::InvalidGateway(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_ikev2_auth_requirements() {
+        let mut config = base_ikev2_config();
+        config.auth = Ikev2Auth::Eap {
+            username: String::new(),
+            password: None,
+        };
+        assert!(validate_ikev2_config(&config).is_err());
+
+        config.auth = Ikev2Auth::Psk { psk: String::new() };
+        assert!(validate_ikev2_config(&config).is_err());
+
+        config.auth = Ikev2Auth::Certificate {
+            cert: "/etc/ipsec.d/certs/me.pem".into(),
+            key: String::new(),
+            key_password: None,
+        };
+        assert!(validate_ikev2_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_validate_ikev2_invalid_dns() {
+        let config = base_ikev2_config().with_dns(vec!["not-an-ip".into()]);
+        assert!(validate_ikev2_config(&config).is_err());
+    }
+
     fn base_openvpn_config() -> OpenVpnConfig {
         OpenVpnConfig::new("MyVPN", "vpn.example.com", 1194, false)
     }
@@ -1166,6 +1453,17 @@ mod tests {
         assert!(validate_openvpn_config(&config).is_ok());
     }
 
+    #[test]
+    fn test_validate_interface_name() {
+        assert!(validate_interface_name("br0").is_ok());
+        assert!(validate_interface_name("enp0s31f6.100").is_ok());
+        assert!(validate_interface_name("").is_err());
+        assert!(validate_interface_name("averyveryverylong").is_err());
+        assert!(validate_interface_name("..").is_err());
+        assert!(validate_interface_name("br 0").is_err());
+        assert!(validate_interface_name("br/0").is_err());
+    }
+
     #[test]
     fn test_validate_bssid_valid_uppercase() {
         assert!(validate_bssid("AA:BB:CC:DD:EE:FF").is_ok());
@@ -1200,4 +1498,30 @@ mod tests {
     fn test_validate_bssid_invalid_segment() {
         assert!(validate_bssid("GG:BB:CC:DD:EE:FF").is_err());
     }
+
+    #[test]
+    fn test_validate_wps_pin() {
+        assert!(validate_wps_pin("12345670").is_ok());
+        assert!(validate_wps_pin("1234").is_ok());
+        assert!(validate_wps_pin("12345678").is_err());
+        assert!(validate_wps_pin("1234567").is_err());
+        assert!(validate_wps_pin("1234a670").is_err());
+        assert!(validate_wps_pin("").is_err());
+    }
+
+    #[test]
+    fn test_validate_wireguard_peers() {
+        let peer = WireGuardPeer::new(
+            "HIgo9xNzJMWLKAShlKl6/bUT1VI9Q0SDBXGtLXkPFXc=",
+            "vpn.example.com:51820",
+            vec!["10.0.0.0/8".into()],
+        );
+        assert!(validate_wireguard_peers(std::slice::from_ref(&peer)).is_ok());
+        assert!(validate_wireguard_peers(&[]).is_err());
+
+        let mut bad = peer.clone();
+        bad.gateway = "vpn.example.com".into();
+        let err = validate_wireguard_peers(&[peer, bad]).unwrap_err();
+        assert!(matches!(err, ConnectionError::InvalidGateway(msg) if msg.starts_with("Peer 1:")));
+    }
 }
@@ -787,24 +787,24 @@ async fn test_concurrent_operations() {
 /// Test that reason_to_error maps auth failures correctly
 #[test]
 fn reason_to_error_auth_mapping() {
-    // Supplicant failed (code 9) should map to AuthFailed
-    assert!(matches!(reason_to_error(9), ConnectionError::AuthFailed));
+    // Supplicant failed (code 10) should map to AuthFailed
+    assert!(matches!(reason_to_error(10), ConnectionError::AuthFailed));
 
-    // Supplicant disconnected (code 7) should map to AuthFailed
-    assert!(matches!(reason_to_error(7), ConnectionError::AuthFailed));
+    // Supplicant disconnected (code 8) should map to AuthFailed
+    assert!(matches!(reason_to_error(8), ConnectionError::AuthFailed));
 
     // DHCP failed (code 17) should map to DhcpFailed
     assert!(matches!(reason_to_error(17), ConnectionError::DhcpFailed));
 
-    // SSID not found (code 70) should map to NotFound
-    assert!(matches!(reason_to_error(70), ConnectionError::NotFound));
+    // SSID not found (code 53) should map to NotFound
+    assert!(matches!(reason_to_error(53), ConnectionError::NotFound));
 }
 
 /// Test StateReason conversions
 #[test]
 fn state_reason_conversion() {
-    assert_eq!(StateReason::from(9), StateReason::SupplicantFailed);
-    assert_eq!(StateReason::from(70), StateReason::SsidNotFound);
+    assert_eq!(StateReason::from(10), StateReason::SupplicantFailed);
+    assert_eq!(StateReason::from(53), StateReason::SsidNotFound);
     assert_eq!(StateReason::from(999), StateReason::Other(999));
 }
 
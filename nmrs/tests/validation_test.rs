@@ -261,3 +261,12 @@ fn test_connection_error_types() {
     let _err8 = ConnectionError::InvalidPublicKey("test".to_string());
     let _err9 = ConnectionError::MissingPassword;
 }
+
+#[tokio::test]
+async fn test_invalid_bus_address() {
+    let result = nmrs::NetworkManager::from_address("not-an-address").await;
+    assert!(matches!(
+        result,
+        Err(ConnectionError::InvalidInput { ref field, .. }) if field == "address"
+    ));
+}